@@ -20,12 +20,20 @@ pub struct DeployItem {
     /// Payment code.
     pub payment: ExecutableDeployItem,
     /// Gas price specified for this deploy by the user.
+    ///
+    /// If a base fee applies, this is the maximum price per gas unit the user is willing to pay.
     pub gas_price: GasPrice,
     /// List of accounts that signed this deploy.
     pub authorization_keys: BTreeSet<AccountHash>,
     /// A unique identifier of the deploy.
     /// Currently it is the hash of the deploy header (see `DeployHeader` in the `types` crate).
     pub deploy_hash: DeployHash,
+    /// Account which pays for this deploy, if other than `address`.  This account will be used as
+    /// a context for executing payment code, and receives the refund.
+    pub sponsor: Option<AccountHash>,
+    /// The maximum price per gas unit above the base fee which is paid to the proposer as a tip.
+    /// If `None`, the whole excess of `gas_price` over the base fee is paid as a tip.
+    pub max_priority_fee_per_gas: Option<GasPrice>,
 }
 
 impl DeployItem {
@@ -45,6 +53,30 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            sponsor: None,
+            max_priority_fee_per_gas: None,
         }
     }
+
+    /// Sets the account which pays for the [`DeployItem`].
+    pub fn with_sponsor(mut self, sponsor: AccountHash) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    /// Sets the maximum tip per gas unit paid to the proposer for the [`DeployItem`].
+    pub fn with_max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: GasPrice) -> Self {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        self
+    }
+
+    /// Returns the price per gas unit paid for the [`DeployItem`] in a block with the given base
+    /// fee, or `None` if the base fee exceeds its gas price.
+    pub fn effective_gas_price(&self, base_fee: GasPrice) -> Option<GasPrice> {
+        let tip = self
+            .gas_price
+            .checked_sub(base_fee)?
+            .min(self.max_priority_fee_per_gas.unwrap_or(GasPrice::MAX));
+        Some(base_fee + tip)
+    }
 }
@@ -37,6 +37,8 @@ const DAY_MILLIS: usize = 24 * 60 * 60 * 1000;
 /// Default length of total vesting schedule period expressed in days.
 pub const DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS: u64 =
     VESTING_SCHEDULE_LENGTH_DAYS as u64 * DAY_MILLIS as u64;
+/// Default number of consecutive inactive eras after which a validator's bid is deactivated.
+pub const DEFAULT_INACTIVE_ERAS_BEFORE_EVICTION: u64 = 1;
 /// Default value for allowing auction bids.
 pub const DEFAULT_ALLOW_AUCTION_BIDS: bool = true;
 /// Default value for allowing unrestricted transfers.
@@ -64,7 +66,9 @@ pub struct EngineConfig {
     /// Vesting schedule period in milliseconds.
     vesting_schedule_period_millis: u64,
     max_delegators_per_validator: Option<u32>,
-    wasm_config: WasmConfig,
+    /// Number of consecutive eras a validator can be inactive before its bid is deactivated.
+    inactive_eras_before_eviction: u64,
+    pub(crate) wasm_config: WasmConfig,
     system_config: SystemConfig,
     /// A private network specifies a list of administrative accounts.
     pub(crate) administrative_accounts: BTreeSet<AccountHash>,
@@ -93,6 +97,7 @@ impl Default for EngineConfig {
             strict_argument_checking: DEFAULT_STRICT_ARGUMENT_CHECKING,
             vesting_schedule_period_millis: DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             max_delegators_per_validator: None,
+            inactive_eras_before_eviction: DEFAULT_INACTIVE_ERAS_BEFORE_EVICTION,
             wasm_config: WasmConfig::default(),
             system_config: SystemConfig::default(),
             administrative_accounts: Default::default(),
@@ -133,6 +138,7 @@ impl EngineConfig {
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
+            inactive_eras_before_eviction: DEFAULT_INACTIVE_ERAS_BEFORE_EVICTION,
             wasm_config,
             system_config,
             administrative_accounts: Default::default(),
@@ -183,6 +189,11 @@ impl EngineConfig {
         self.max_delegators_per_validator
     }
 
+    /// Returns the number of consecutive inactive eras after which a validator is evicted.
+    pub fn inactive_eras_before_eviction(&self) -> u64 {
+        self.inactive_eras_before_eviction
+    }
+
     /// Returns the engine config's administrative accounts.
     pub fn administrative_accounts(&self) -> &BTreeSet<AccountHash> {
         &self.administrative_accounts
@@ -233,6 +244,7 @@ pub struct EngineConfigBuilder {
     strict_argument_checking: Option<bool>,
     vesting_schedule_period_millis: Option<u64>,
     max_delegators_per_validator: Option<u32>,
+    inactive_eras_before_eviction: Option<u64>,
     wasm_config: Option<WasmConfig>,
     system_config: Option<SystemConfig>,
     administrative_accounts: Option<BTreeSet<PublicKey>>,
@@ -287,6 +299,12 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the inactive eras before eviction config option.
+    pub fn with_inactive_eras_before_eviction(mut self, value: u64) -> Self {
+        self.inactive_eras_before_eviction = Some(value);
+        self
+    }
+
     /// Sets the wasm config options.
     pub fn with_wasm_config(mut self, wasm_config: WasmConfig) -> Self {
         self.wasm_config = Some(wasm_config);
@@ -391,6 +409,9 @@ impl EngineConfigBuilder {
             .vesting_schedule_period_millis
             .unwrap_or(DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS);
         let max_delegators_per_validator = self.max_delegators_per_validator;
+        let inactive_eras_before_eviction = self
+            .inactive_eras_before_eviction
+            .unwrap_or(DEFAULT_INACTIVE_ERAS_BEFORE_EVICTION);
 
         EngineConfig {
             max_query_depth,
@@ -407,6 +428,7 @@ impl EngineConfigBuilder {
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
+            inactive_eras_before_eviction,
         }
     }
 }
@@ -46,6 +46,14 @@ pub enum Error {
     /// Payment code provided insufficient funds for execution.
     #[error("Insufficient payment")]
     InsufficientPayment,
+    /// The deploy's gas price is below the base fee of the block.
+    #[error("Gas price of {gas_price} is below the base fee of {base_fee}")]
+    GasPriceBelowBaseFee {
+        /// The deploy's gas price.
+        gas_price: u64,
+        /// The base fee of the block.
+        base_fee: u64,
+    },
     /// Motes to gas conversion resulted in an overflow.
     #[error("Gas conversion overflow")]
     GasConversionOverflow,
@@ -100,6 +108,12 @@ pub enum Error {
     /// Failed to retrieve the current EraId from the auction state.
     #[error("Failed to retrieve the era_id from the auction state")]
     FailedToRetrieveEraId,
+    /// Failed to retrieve the governance proposals from the auction state.
+    #[error("Failed to retrieve the governance proposals from the auction state")]
+    FailedToRetrieveGovernanceProposals,
+    /// Failed to retrieve the parameter overrides of applied governance proposals.
+    #[error("Failed to retrieve the parameter overrides of applied governance proposals")]
+    FailedToRetrieveGovernanceParameterOverrides,
     /// Failed to put a trie node into global state because some of its children were missing.
     #[error("Failed to put a trie into global state because some of its children were missing")]
     MissingTrieNodeChildren(Vec<Digest>),
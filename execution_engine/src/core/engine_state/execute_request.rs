@@ -19,6 +19,13 @@ pub struct ExecuteRequest {
     pub protocol_version: ProtocolVersion,
     /// The owner of the node that proposed the block containing this request.
     pub proposer: PublicKey,
+    /// The base fee per unit of gas of the block containing this request.
+    ///
+    /// The part of a deploy's gas price above the base fee is paid to the proposer as a tip,
+    /// regardless of the configured fee handling.  If `None`, no tips are paid.
+    pub base_fee: Option<u64>,
+    /// The random beacon of the block containing this request.
+    pub random_beacon: Digest,
 }
 
 impl ExecuteRequest {
@@ -36,9 +43,23 @@ impl ExecuteRequest {
             deploys,
             protocol_version,
             proposer,
+            base_fee: None,
+            random_beacon: Digest::default(),
         }
     }
 
+    /// Sets the base fee per unit of gas of the block containing this request.
+    pub fn with_base_fee(mut self, base_fee: u64) -> Self {
+        self.base_fee = Some(base_fee);
+        self
+    }
+
+    /// Sets the random beacon of the block containing this request.
+    pub fn with_random_beacon(mut self, random_beacon: Digest) -> Self {
+        self.random_beacon = random_beacon;
+        self
+    }
+
     /// Returns deploys, and overwrites the existing value with empty list.
     pub fn take_deploys(&mut self) -> Vec<DeployItem> {
         mem::take(&mut self.deploys)
@@ -61,6 +82,8 @@ impl Default for ExecuteRequest {
             deploys: vec![],
             protocol_version: Default::default(),
             proposer,
+            base_fee: None,
+            random_beacon: Digest::default(),
         }
     }
 }
@@ -26,6 +26,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
     rc::Rc,
+    sync::Arc,
 };
 
 use num::Zero;
@@ -35,22 +36,23 @@ use tracing::{debug, error, trace, warn};
 
 use casper_hashing::Digest;
 use casper_types::{
-    account::{Account, AccountHash},
+    account::{Account, AccountHash, AssociatedKeys},
     bytesrepr::ToBytes,
     contracts::NamedKeys,
     system::{
         auction::{
-            EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
-            ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
-            LOCKED_FUNDS_PERIOD_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY,
-            VALIDATOR_SLOTS_KEY,
+            governance_parameter_overrides_key, governance_proposals_key, EraValidators,
+            ParameterChanges, ProposalStatus, Proposals, ARG_ERA_END_TIMESTAMP_MILLIS,
+            ARG_EVICTED_VALIDATORS, ARG_INACTIVE_VALIDATORS, ARG_REWARD_FACTORS,
+            ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY,
+            SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment::{self, ACCUMULATION_PURSE_KEY},
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
-    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, Gas, Key,
-    KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, URef, U512,
+    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, EraId, Gas,
+    Key, KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, URef, U512,
 };
 
 pub use self::{
@@ -91,7 +93,10 @@ use crate::{
         runtime::RuntimeStack,
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
-    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    shared::{
+        additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform,
+        wasm_config::WasmConfig,
+    },
     storage::{
         global_state::{
             lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
@@ -148,6 +153,18 @@ impl EngineState<LmdbGlobalState> {
         Ok(())
     }
 
+    /// Returns an engine state backed by the same LMDB global state, using the given Wasm config.
+    pub fn with_wasm_config(&self, wasm_config: WasmConfig) -> EngineState<LmdbGlobalState> {
+        let mut config = self.config.clone();
+        config.wasm_config = wasm_config;
+        let state = LmdbGlobalState::new(
+            Arc::clone(&self.state.environment),
+            Arc::clone(&self.state.trie_store),
+            self.state.empty_root_hash,
+        );
+        EngineState { config, state }
+    }
+
     /// Provide a local cached-only version of engine-state.
     pub fn get_scratch_engine_state(&self) -> EngineState<ScratchGlobalState> {
         EngineState {
@@ -368,11 +385,87 @@ where
             .map_err(Error::ProtocolUpgrade)?;
 
         // 3.1.1.1.1.7 new total validator slots is optional
-        if let Some(new_validator_slots) = upgrade_config.new_validator_slots() {
-            // 3.1.2.4 if new total validator slots is provided, update auction contract state
+        let parameter_changes = ParameterChanges {
+            validator_slots: upgrade_config.new_validator_slots(),
+            auction_delay: upgrade_config.new_auction_delay(),
+            locked_funds_period_millis: upgrade_config.new_locked_funds_period_millis(),
+            round_seigniorage_rate: upgrade_config.new_round_seigniorage_rate(),
+            ..Default::default()
+        };
+        // 3.1.2.4 if new total validator slots is provided, update auction contract state
+        self.apply_parameter_changes(
+            correlation_id,
+            &tracking_copy,
+            *mint_hash,
+            *auction_hash,
+            &parameter_changes,
+        )?;
+
+        // apply the accepted modifications to global state.
+        for (key, value) in upgrade_config.global_state_update() {
+            tracking_copy.borrow_mut().write(*key, value.clone());
+        }
+        // We insert the new unbonding delay once the purses to be paid out have been transformed
+        // based on the previous unbonding delay.
+        let parameter_changes = ParameterChanges {
+            unbonding_delay: upgrade_config.new_unbonding_delay(),
+            ..Default::default()
+        };
+        self.apply_parameter_changes(
+            correlation_id,
+            &tracking_copy,
+            *mint_hash,
+            *auction_hash,
+            &parameter_changes,
+        )?;
+
+        // Parameters changed by governance proposals take precedence over the upgrade's own values,
+        // and proposals approved to activate by the time of this upgrade take effect now.
+        if let Some(activation_point) = upgrade_config.activation_point() {
+            self.apply_governance_changes(
+                correlation_id,
+                &tracking_copy,
+                *mint_hash,
+                *auction_hash,
+                activation_point,
+            )?;
+        }
+
+        let execution_effect = tracking_copy.borrow().effect();
+
+        // commit
+        let post_state_hash = self
+            .state
+            .commit(
+                correlation_id,
+                pre_state_hash,
+                execution_effect.transforms.to_owned(),
+            )
+            .map_err(Into::into)?;
+
+        // return result and effects
+        Ok(UpgradeSuccess {
+            post_state_hash,
+            execution_effect,
+        })
+    }
+
+    /// Writes the given chainspec parameter changes into the named keys of the system contracts
+    /// holding them.
+    ///
+    /// Used both by protocol upgrades and when applying approved governance proposals.
+    fn apply_parameter_changes(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        mint_hash: ContractHash,
+        auction_hash: ContractHash,
+        parameter_changes: &ParameterChanges,
+    ) -> Result<(), Error> {
+        if let Some(new_validator_slots) = parameter_changes.validator_slots {
             let auction_contract = tracking_copy
                 .borrow_mut()
-                .get_contract(correlation_id, *auction_hash)?;
+                .get_contract(correlation_id, auction_hash)?;
 
             let validator_slots_key = auction_contract.named_keys()[VALIDATOR_SLOTS_KEY];
             let value = StoredValue::CLValue(
@@ -382,11 +475,11 @@ where
             tracking_copy.borrow_mut().write(validator_slots_key, value);
         }
 
-        if let Some(new_auction_delay) = upgrade_config.new_auction_delay() {
-            debug!(%new_auction_delay, "Auction delay changed as part of the upgrade");
+        if let Some(new_auction_delay) = parameter_changes.auction_delay {
+            debug!(%new_auction_delay, "Auction delay changed");
             let auction_contract = tracking_copy
                 .borrow_mut()
-                .get_contract(correlation_id, *auction_hash)?;
+                .get_contract(correlation_id, auction_hash)?;
 
             let auction_delay_key = auction_contract.named_keys()[AUCTION_DELAY_KEY];
             let value = StoredValue::CLValue(
@@ -396,10 +489,10 @@ where
             tracking_copy.borrow_mut().write(auction_delay_key, value);
         }
 
-        if let Some(new_locked_funds_period) = upgrade_config.new_locked_funds_period_millis() {
+        if let Some(new_locked_funds_period) = parameter_changes.locked_funds_period_millis {
             let auction_contract = tracking_copy
                 .borrow_mut()
-                .get_contract(correlation_id, *auction_hash)?;
+                .get_contract(correlation_id, auction_hash)?;
 
             let locked_funds_period_key = auction_contract.named_keys()[LOCKED_FUNDS_PERIOD_KEY];
             let value = StoredValue::CLValue(
@@ -411,7 +504,7 @@ where
                 .write(locked_funds_period_key, value);
         }
 
-        if let Some(new_round_seigniorage_rate) = upgrade_config.new_round_seigniorage_rate() {
+        if let Some(new_round_seigniorage_rate) = parameter_changes.round_seigniorage_rate {
             let new_round_seigniorage_rate: Ratio<U512> = {
                 let (numer, denom) = new_round_seigniorage_rate.into();
                 Ratio::new(numer.into(), denom.into())
@@ -419,28 +512,22 @@ where
 
             let mint_contract = tracking_copy
                 .borrow_mut()
-                .get_contract(correlation_id, *mint_hash)?;
+                .get_contract(correlation_id, mint_hash)?;
 
-            let locked_funds_period_key = mint_contract.named_keys()[ROUND_SEIGNIORAGE_RATE_KEY];
+            let round_seigniorage_rate_key = mint_contract.named_keys()[ROUND_SEIGNIORAGE_RATE_KEY];
             let value = StoredValue::CLValue(
                 CLValue::from_t(new_round_seigniorage_rate)
                     .map_err(|_| Error::Bytesrepr("new_round_seigniorage_rate".to_string()))?,
             );
             tracking_copy
                 .borrow_mut()
-                .write(locked_funds_period_key, value);
+                .write(round_seigniorage_rate_key, value);
         }
 
-        // apply the accepted modifications to global state.
-        for (key, value) in upgrade_config.global_state_update() {
-            tracking_copy.borrow_mut().write(*key, value.clone());
-        }
-        // We insert the new unbonding delay once the purses to be paid out have been transformed
-        // based on the previous unbonding delay.
-        if let Some(new_unbonding_delay) = upgrade_config.new_unbonding_delay() {
+        if let Some(new_unbonding_delay) = parameter_changes.unbonding_delay {
             let auction_contract = tracking_copy
                 .borrow_mut()
-                .get_contract(correlation_id, *auction_hash)?;
+                .get_contract(correlation_id, auction_hash)?;
 
             let unbonding_delay_key = auction_contract.named_keys()[UNBONDING_DELAY_KEY];
             let value = StoredValue::CLValue(
@@ -450,23 +537,114 @@ where
             tracking_copy.borrow_mut().write(unbonding_delay_key, value);
         }
 
-        let execution_effect = tracking_copy.borrow().effect();
+        Ok(())
+    }
 
-        // commit
-        let post_state_hash = self
-            .state
-            .commit(
-                correlation_id,
-                pre_state_hash,
-                execution_effect.transforms.to_owned(),
-            )
-            .map_err(Into::into)?;
+    /// Applies the parameter changes of governance proposals approved to activate at or before
+    /// `activation_point`, marks them as applied and prunes all finished proposals.
+    ///
+    /// The changes of all applied proposals are accumulated under
+    /// [`governance_parameter_overrides_key`] and reapplied, so that a protocol upgrade doesn't
+    /// revert them to the chainspec values.
+    fn apply_governance_changes(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+        mint_hash: ContractHash,
+        auction_hash: ContractHash,
+        activation_point: EraId,
+    ) -> Result<(), Error> {
+        let proposals_key = governance_proposals_key();
+        let mut proposals: Proposals = match tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &proposals_key)
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::CLValue(cl_value)) => cl_value
+                .into_t()
+                .map_err(|_| Error::FailedToRetrieveGovernanceProposals)?,
+            Some(_) => return Err(Error::FailedToRetrieveGovernanceProposals),
+            None => Proposals::default(),
+        };
+        let mut overrides = self.read_parameter_overrides(correlation_id, tracking_copy)?;
 
-        // return result and effects
-        Ok(UpgradeSuccess {
-            post_state_hash,
-            execution_effect,
-        })
+        // Proposals are applied in the order they were made, so later ones take precedence.
+        let mut overrides_changed = false;
+        for (id, proposal) in proposals.iter_mut() {
+            if proposal.status() == ProposalStatus::Approved
+                && proposal.activation_era() <= activation_point
+            {
+                debug!(proposal_id = id, %activation_point, "applying governance proposal");
+                overrides.extend(proposal.parameter_changes());
+                proposal.mark_applied();
+                overrides_changed = true;
+            }
+        }
+
+        self.apply_parameter_changes(
+            correlation_id,
+            tracking_copy,
+            mint_hash,
+            auction_hash,
+            &overrides,
+        )?;
+
+        if overrides_changed {
+            let value = StoredValue::CLValue(
+                CLValue::from_t(overrides)
+                    .map_err(|_| Error::Bytesrepr("parameter_overrides".to_string()))?,
+            );
+            tracking_copy
+                .borrow_mut()
+                .write(governance_parameter_overrides_key(), value);
+        }
+
+        let proposal_count = proposals.len();
+        proposals.prune_finished();
+        if proposals.len() != proposal_count {
+            let value = StoredValue::CLValue(
+                CLValue::from_t(proposals)
+                    .map_err(|_| Error::Bytesrepr("proposals".to_string()))?,
+            );
+            tracking_copy.borrow_mut().write(proposals_key, value);
+        }
+        Ok(())
+    }
+
+    /// Reads the accumulated parameter changes of applied governance proposals.
+    fn read_parameter_overrides(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &Rc<RefCell<TrackingCopy<S::Reader>>>,
+    ) -> Result<ParameterChanges, Error> {
+        match tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &governance_parameter_overrides_key())
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::CLValue(cl_value)) => cl_value
+                .into_t()
+                .map_err(|_| Error::FailedToRetrieveGovernanceParameterOverrides),
+            Some(_) => Err(Error::FailedToRetrieveGovernanceParameterOverrides),
+            None => Ok(ParameterChanges::default()),
+        }
+    }
+
+    /// Returns the accumulated parameter changes of applied governance proposals as of the given
+    /// state root hash.
+    ///
+    /// These override the corresponding chainspec values, including those which aren't held in
+    /// global state such as the block gas limit.
+    pub fn get_parameter_overrides(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Digest,
+    ) -> Result<ParameterChanges, Error> {
+        let tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Err(Error::RootNotFound(state_hash)),
+        };
+        self.read_parameter_overrides(correlation_id, &tracking_copy)
     }
 
     /// Commit a prune of leaf nodes from the tip of the merkle trie.
@@ -552,7 +730,8 @@ where
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
     ) -> Result<ExecutionResults, Error> {
-        let executor = Executor::new(self.config().clone());
+        let executor =
+            Executor::new(self.config().clone()).with_random_beacon(exec_request.random_beacon);
 
         let deploys = exec_request.take_deploys();
         let mut results = ExecutionResults::with_capacity(deploys.len());
@@ -576,6 +755,7 @@ where
                     BlockTime::new(exec_request.block_time),
                     deploy_item,
                     exec_request.proposer.clone(),
+                    exec_request.base_fee,
                 ),
             };
             match result {
@@ -626,6 +806,74 @@ where
         Ok(account)
     }
 
+    /// Returns the authorized account and sponsor of a sponsored deploy.
+    ///
+    /// Each of the authorization keys is attributed to the account, the sponsor or both, according
+    /// to their associated keys.  Both must be authorized by the keys attributed to them, and the
+    /// keys which are attributed to neither cause an authorization error.
+    fn get_authorized_account_and_sponsor(
+        &self,
+        correlation_id: CorrelationId,
+        account_hash: AccountHash,
+        sponsor_hash: AccountHash,
+        authorization_keys: &BTreeSet<AccountHash>,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+    ) -> Result<
+        (
+            Account,
+            BTreeSet<AccountHash>,
+            Account,
+            BTreeSet<AccountHash>,
+        ),
+        Error,
+    > {
+        if account_hash == sponsor_hash {
+            return Err(Error::Authorization);
+        }
+
+        let read_associated_keys = |hash: AccountHash| -> Result<AssociatedKeys, Error> {
+            match tracking_copy.borrow_mut().get_account(correlation_id, hash) {
+                Ok(account) => Ok(account.associated_keys().clone()),
+                Err(_) => Err(Error::Authorization),
+            }
+        };
+        let account_associated_keys = read_associated_keys(account_hash)?;
+        let sponsor_associated_keys = read_associated_keys(sponsor_hash)?;
+        let admin_set = self.config().administrative_accounts();
+
+        let mut account_keys = BTreeSet::new();
+        let mut sponsor_keys = BTreeSet::new();
+        for key in authorization_keys {
+            let is_admin = admin_set.contains(key);
+            let mut is_attributed = false;
+            if is_admin || account_associated_keys.contains_key(key) {
+                account_keys.insert(*key);
+                is_attributed = true;
+            }
+            if is_admin || sponsor_associated_keys.contains_key(key) {
+                sponsor_keys.insert(*key);
+                is_attributed = true;
+            }
+            if !is_attributed {
+                return Err(Error::Authorization);
+            }
+        }
+
+        let account = self.get_authorized_account(
+            correlation_id,
+            account_hash,
+            &account_keys,
+            Rc::clone(&tracking_copy),
+        )?;
+        let sponsor = self.get_authorized_account(
+            correlation_id,
+            sponsor_hash,
+            &sponsor_keys,
+            tracking_copy,
+        )?;
+        Ok((account, account_keys, sponsor, sponsor_keys))
+    }
+
     /// Get the balance of a passed purse referenced by its [`URef`].
     pub fn get_purse_balance(
         &self,
@@ -1182,6 +1430,11 @@ where
     /// unspent collateral will be transferred back to the proposer of the deploy, as specified
     /// in the request.
     ///
+    /// If `base_fee` is provided, the deploy pays the base fee plus its tip per gas unit, capped at
+    /// its gas price, and fails its preconditions if its gas price is below the base fee.  The tip
+    /// is paid to the proposer, and only the remainder of the fee is subject to the fee handling
+    /// config.
+    ///
     /// Returns [`ExecutionResult`], or an error condition.
     #[allow(clippy::too_many_arguments)]
     pub fn deploy(
@@ -1193,6 +1446,7 @@ where
         blocktime: BlockTime,
         deploy_item: DeployItem,
         proposer: PublicKey,
+        base_fee: Option<u64>,
     ) -> Result<ExecutionResult, Error> {
         // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
 
@@ -1205,25 +1459,61 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        // The price actually paid per gas unit.
+        let gas_price = match base_fee {
+            Some(base_fee) => match deploy_item.effective_gas_price(base_fee) {
+                Some(gas_price) => gas_price,
+                None => {
+                    return Ok(ExecutionResult::precondition_failure(
+                        Error::GasPriceBelowBaseFee {
+                            gas_price: deploy_item.gas_price,
+                            base_fee,
+                        },
+                    ))
+                }
+            },
+            None => deploy_item.gas_price,
+        };
+
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
 
         let authorization_keys = deploy_item.authorization_keys;
 
-        // Get account from tracking copy
+        // Get account from tracking copy, and the sponsor paying for the deploy if there is one.
+        // The authorization keys are split between the two.
         // validation_spec_3: account validity
-        let account = {
-            let account_hash = deploy_item.address;
-            match self.get_authorized_account(
-                correlation_id,
-                account_hash,
-                &authorization_keys,
-                Rc::clone(&tracking_copy),
-            ) {
-                Ok(account) => account,
-                Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
-            }
-        };
+        let (account, authorization_keys, maybe_sponsor, payment_authorization_keys) =
+            match deploy_item.sponsor {
+                None => match self.get_authorized_account(
+                    correlation_id,
+                    deploy_item.address,
+                    &authorization_keys,
+                    Rc::clone(&tracking_copy),
+                ) {
+                    Ok(account) => (
+                        account,
+                        authorization_keys.clone(),
+                        None,
+                        authorization_keys,
+                    ),
+                    Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+                },
+                Some(sponsor_hash) => match self.get_authorized_account_and_sponsor(
+                    correlation_id,
+                    deploy_item.address,
+                    sponsor_hash,
+                    &authorization_keys,
+                    Rc::clone(&tracking_copy),
+                ) {
+                    Ok((account, account_keys, sponsor, sponsor_keys)) => {
+                        (account, account_keys, Some(sponsor), sponsor_keys)
+                    }
+                    Err(e) => return Ok(ExecutionResult::precondition_failure(e)),
+                },
+            };
+        // The account which runs the payment code and pays for the deploy.
+        let payer = maybe_sponsor.as_ref().unwrap_or(&account);
 
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
@@ -1258,10 +1548,10 @@ where
             }
         };
 
-        // Get account main purse balance key
+        // Get payer's main purse balance key
         // validation_spec_5: account main purse minimum balance
         let account_main_purse_balance_key: Key = {
-            let account_key = Key::URef(account.main_purse());
+            let account_key = Key::URef(payer.main_purse());
             match tracking_copy
                 .borrow_mut()
                 .get_purse_balance_key(correlation_id, account_key)
@@ -1340,6 +1630,25 @@ where
             }
         };
 
+        // The tip is only paid separately if the fee itself doesn't go to the proposer.
+        let maybe_tip_per_gas = match base_fee {
+            Some(base_fee)
+                if gas_price > base_fee
+                    && !matches!(self.config.fee_handling(), FeeHandling::PayToProposer) =>
+            {
+                Some(gas_price - base_fee)
+            }
+            Some(_) | None => None,
+        };
+
+        let maybe_proposer_purse = match maybe_tip_per_gas {
+            Some(_) => match self.get_proposer_purse(correlation_id, &proposer, prestate_hash) {
+                Ok(proposer_purse) => Some(proposer_purse),
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            },
+            None => None,
+        };
+
         let rewards_target_purse =
             match self.get_rewards_purse(correlation_id, proposer, prestate_hash) {
                 Ok(target_purse) => target_purse,
@@ -1367,7 +1676,7 @@ where
         let payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
             // gas_price)
-            let payment_gas_limit = match Gas::from_motes(max_payment_cost, deploy_item.gas_price) {
+            let payment_gas_limit = match Gas::from_motes(max_payment_cost, gas_price) {
                 Some(gas) => gas,
                 None => {
                     return Ok(ExecutionResult::precondition_failure(
@@ -1381,14 +1690,14 @@ where
             let phase = Phase::Payment;
 
             let payment_stack = RuntimeStack::from_account_hash(
-                deploy_item.address,
+                payer.account_hash(),
                 self.config.max_runtime_call_stack_height() as usize,
             );
 
             // payment_code_spec_2: execute payment code
-            let payment_access_rights = account.extract_access_rights();
+            let payment_access_rights = payer.extract_access_rights();
 
-            let mut payment_named_keys = account.named_keys().clone();
+            let mut payment_named_keys = payer.named_keys().clone();
 
             let payment_args = payment.args().clone();
 
@@ -1396,11 +1705,11 @@ where
                 // Todo potentially could be moved to Executor::Exec
                 executor.exec_standard_payment(
                     payment_args,
-                    Key::Account(account.account_hash()),
-                    &account,
+                    Key::Account(payer.account_hash()),
+                    payer,
                     &mut payment_named_keys,
                     payment_access_rights,
-                    authorization_keys.clone(),
+                    payment_authorization_keys.clone(),
                     blocktime,
                     deploy_hash,
                     payment_gas_limit,
@@ -1413,7 +1722,7 @@ where
             } else {
                 let payment_execution_kind = match ExecutionKind::new(
                     Rc::clone(&tracking_copy),
-                    account.named_keys(),
+                    payer.named_keys(),
                     payment,
                     correlation_id,
                     &protocol_version,
@@ -1427,10 +1736,10 @@ where
                 executor.exec(
                     payment_execution_kind,
                     payment_args,
-                    &account,
+                    payer,
                     &mut payment_named_keys,
                     payment_access_rights,
-                    authorization_keys.clone(),
+                    payment_authorization_keys.clone(),
                     blocktime,
                     deploy_hash,
                     payment_gas_limit,
@@ -1481,7 +1790,7 @@ where
         };
 
         if let Some(forced_transfer) =
-            payment_result.check_forced_transfer(payment_purse_balance, deploy_item.gas_price)
+            payment_result.check_forced_transfer(payment_purse_balance, gas_price)
         {
             // Get rewards purse balance key
             // payment_code_spec_6: system contract validity
@@ -1493,7 +1802,7 @@ where
                     .unwrap_or(Error::InsufficientPayment),
             };
 
-            let gas_cost = match Gas::from_motes(max_payment_cost, deploy_item.gas_price) {
+            let gas_cost = match Gas::from_motes(max_payment_cost, gas_price) {
                 Some(gas) => gas,
                 None => {
                     return Ok(ExecutionResult::precondition_failure(
@@ -1537,17 +1846,16 @@ where
             // session_code_spec_1: gas limit = ((balance of handle payment payment purse) /
             // gas_price)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas =
-                match Gas::from_motes(payment_purse_balance, deploy_item.gas_price)
-                    .and_then(|gas| gas.checked_sub(payment_result_cost))
-                {
-                    Some(gas) => gas,
-                    None => {
-                        return Ok(ExecutionResult::precondition_failure(
-                            Error::GasConversionOverflow,
-                        ))
-                    }
-                };
+            let session_gas_limit: Gas = match Gas::from_motes(payment_purse_balance, gas_price)
+                .and_then(|gas| gas.checked_sub(payment_result_cost))
+            {
+                Some(gas) => gas,
+                None => {
+                    return Ok(ExecutionResult::precondition_failure(
+                        Error::GasConversionOverflow,
+                    ))
+                }
+            };
 
             executor.exec(
                 session_execution_kind,
@@ -1576,7 +1884,7 @@ where
                 deploy_hash,
                 transfers,
                 account.account_hash(),
-                account.main_purse(),
+                payer.main_purse(),
                 cost,
             );
             session_tracking_copy.borrow_mut().write(
@@ -1631,22 +1939,38 @@ where
 
             let handle_payment_args = {
                 //((gas spent during payment code execution) + (gas spent during session code execution)) * gas_price
-                let finalize_cost_motes = match Motes::from_gas(
-                    execution_result_builder.total_cost(),
-                    deploy_item.gas_price,
-                ) {
-                    Some(motes) => motes,
-                    None => {
-                        return Ok(ExecutionResult::precondition_failure(
-                            Error::GasConversionOverflow,
-                        ))
+                let finalize_cost_motes =
+                    match Motes::from_gas(execution_result_builder.total_cost(), gas_price) {
+                        Some(motes) => motes,
+                        None => {
+                            return Ok(ExecutionResult::precondition_failure(
+                                Error::GasConversionOverflow,
+                            ))
+                        }
+                    };
+
+                //(total gas spent) * (gas_price - base_fee)
+                let maybe_proposer_tip = match (maybe_proposer_purse, maybe_tip_per_gas) {
+                    (Some(proposer_purse), Some(tip_per_gas)) => {
+                        match Motes::from_gas(execution_result_builder.total_cost(), tip_per_gas) {
+                            Some(tip) => Some((proposer_purse, tip.value())),
+                            None => {
+                                return Ok(ExecutionResult::precondition_failure(
+                                    Error::GasConversionOverflow,
+                                ))
+                            }
+                        }
                     }
+                    _ => None,
                 };
 
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(handle_payment::ARG_AMOUNT, finalize_cost_motes.value())?;
-                    args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())?;
+                    args.insert(handle_payment::ARG_ACCOUNT, payer.account_hash())?;
                     args.insert(handle_payment::ARG_TARGET, rewards_target_purse)?;
+                    if let Some(proposer_tip) = maybe_proposer_tip {
+                        args.insert(handle_payment::ARG_PROPOSER_TIP, proposer_tip)?;
+                    }
                     Ok(())
                 });
                 match maybe_runtime_args {
@@ -1682,6 +2006,9 @@ where
             let mut handle_payment_access_rights =
                 handle_payment_contract.extract_access_rights(*handle_payment_contract_hash);
             handle_payment_access_rights.extend(&[payment_purse_uref, rewards_target_purse]);
+            if let Some(proposer_purse) = maybe_proposer_purse {
+                handle_payment_access_rights.extend(&[proposer_purse]);
+            }
 
             let gas_limit = Gas::new(U512::MAX);
 
@@ -1720,6 +2047,22 @@ where
         Ok(ret)
     }
 
+    fn get_proposer_purse(
+        &self,
+        correlation_id: CorrelationId,
+        proposer: &PublicKey,
+        prestate_hash: Digest,
+    ) -> Result<URef, Error> {
+        let mut tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => return Err(error),
+            Ok(None) => return Err(Error::RootNotFound(prestate_hash)),
+            Ok(Some(tracking_copy)) => tracking_copy,
+        };
+        let proposer_account =
+            tracking_copy.get_account(correlation_id, proposer.to_account_hash())?;
+        Ok(proposer_account.main_purse())
+    }
+
     fn get_rewards_purse(
         &self,
         correlation_id: CorrelationId,
@@ -2064,6 +2407,10 @@ where
                     .map(|item| item.validator_id.clone())
                     .collect::<Vec<PublicKey>>(),
             )?;
+            args.insert(
+                ARG_INACTIVE_VALIDATORS,
+                step_request.inactive_validators.clone(),
+            )?;
             Ok(())
         })?;
 
@@ -2375,6 +2722,7 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::Storage(_)
             | Error::Authorization
             | Error::InsufficientPayment
+            | Error::GasPriceBelowBaseFee { .. }
             | Error::GasConversionOverflow
             | Error::Deploy
             | Error::Finalization
@@ -2393,6 +2741,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToGetWithdrawPurses
             | Error::FailedToRetrieveUnbondingDelay
             | Error::FailedToRetrieveEraId
+            | Error::FailedToRetrieveGovernanceProposals
+            | Error::FailedToRetrieveGovernanceParameterOverrides
             | Error::MissingTrieNodeChildren(_)
             | Error::FailedToRetrieveAccumulationPurse => false,
         },
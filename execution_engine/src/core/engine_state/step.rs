@@ -77,6 +77,11 @@ pub struct StepRequest {
     /// Compared to a slashing, evictions are deactivating a given validator, but his stake is
     /// unchanged. A further re-activation is possible.
     pub evict_items: Vec<EvictItem>,
+    /// List of validators that were inactive in the current era.
+    ///
+    /// An inactive validator is only evicted once it has been inactive for the configured number
+    /// of consecutive eras.
+    pub inactive_validators: Vec<PublicKey>,
     /// Specifies which era validators will be returned based on `next_era_id`.
     ///
     /// Intended use is to always specify the current era id + 1 which will return computed era at
@@ -95,6 +100,7 @@ impl StepRequest {
         slash_items: Vec<SlashItem>,
         reward_items: Vec<RewardItem>,
         evict_items: Vec<EvictItem>,
+        inactive_validators: Vec<PublicKey>,
         next_era_id: EraId,
         era_end_timestamp_millis: u64,
     ) -> Self {
@@ -104,6 +110,7 @@ impl StepRequest {
             slash_items,
             reward_items,
             evict_items,
+            inactive_validators,
             next_era_id,
             era_end_timestamp_millis,
         }
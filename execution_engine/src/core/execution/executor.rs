@@ -1,5 +1,6 @@
 use std::{cell::RefCell, collections::BTreeSet, rc::Rc};
 
+use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
     bytesrepr::FromBytes,
@@ -35,12 +36,22 @@ fn try_get_amount(runtime_args: &RuntimeArgs) -> Result<U512, ExecError> {
 /// Executor object deals with execution of WASM modules.
 pub struct Executor {
     config: EngineConfig,
+    random_beacon: Digest,
 }
 
 impl Executor {
     /// Creates new executor object.
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            random_beacon: Digest::default(),
+        }
+    }
+
+    /// Sets the random beacon of the block whose deploys are executed.
+    pub fn with_random_beacon(mut self, random_beacon: Digest) -> Self {
+        self.random_beacon = random_beacon;
+        self
     }
 
     /// Executes a WASM module.
@@ -380,6 +391,7 @@ impl Executor {
             account,
             base_key,
             blocktime,
+            self.random_beacon,
             deploy_hash,
             gas_limit,
             gas_counter,
@@ -5,7 +5,7 @@ use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
     crypto,
     system::{
-        auction::{Bid, EraInfo, Error, UnbondingPurse},
+        auction::{self, Bid, EraInfo, Error, Proposals, UnbondingPurse, ValidatorInactivity},
         mint,
     },
     CLTyped, CLValue, EraId, Key, KeyTag, PublicKey, RuntimeArgs, StoredValue, URef,
@@ -110,6 +110,50 @@ where
         Runtime::record_era_summary(self, era_summary)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
     }
+
+    fn read_proposals(&mut self) -> Result<Proposals, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&auction::governance_proposals_key())
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(cl_value)) => cl_value.into_t().map_err(|_| Error::CLValue),
+            Some(_) => Err(Error::Storage),
+            None => Ok(Proposals::default()),
+        }
+    }
+
+    fn write_proposals(&mut self, proposals: Proposals) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(proposals).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(
+                auction::governance_proposals_key(),
+                StoredValue::CLValue(cl_value),
+            )
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
+    fn read_validator_inactivity(&mut self) -> Result<ValidatorInactivity, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&auction::validator_inactivity_key())
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(cl_value)) => cl_value.into_t().map_err(|_| Error::CLValue),
+            Some(_) => Err(Error::Storage),
+            None => Ok(ValidatorInactivity::new()),
+        }
+    }
+
+    fn write_validator_inactivity(&mut self, inactivity: ValidatorInactivity) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(inactivity).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(
+                auction::validator_inactivity_key(),
+                StoredValue::CLValue(cl_value),
+            )
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
 }
 
 impl<'a, R> RuntimeProvider for Runtime<'a, R>
@@ -938,7 +938,8 @@ where
                 let target: URef = self.t_from_mem(target_ptr, target_size)?;
                 let amount: U512 = self.t_from_mem(amount_ptr, amount_size)?;
                 let id: Option<u64> = self.t_from_mem(id_ptr, id_size)?;
-                self.record_transfer(maybe_to, source, target, amount, id)?;
+                // Transfers recorded through the host function are always of the native token.
+                self.record_transfer(maybe_to, source, target, amount, id, None)?;
                 Ok(Some(RuntimeValue::I32(0)))
             }
 
@@ -1100,6 +1101,13 @@ where
 
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(result))))
             }
+            FunctionIndex::GetRandomBeaconIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let (dest_ptr,) = Args::parse(args)?;
+                self.charge_host_function_call(&host_function_costs.get_random_beacon, [dest_ptr])?;
+                self.get_random_beacon(dest_ptr)?;
+                Ok(None)
+            }
         }
     }
 }
@@ -1,8 +1,12 @@
 use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
-    system::{mint::Error, CallStackElement},
-    CLTyped, CLValue, Key, Phase, StoredValue, URef, U512,
+    system::{
+        auction::ERA_ID_KEY,
+        mint::{self, AssetId, Error, PurseLock},
+        CallStackElement, AUCTION,
+    },
+    AccessRights, BlockTime, CLTyped, CLValue, EraId, Key, Phase, StoredValue, URef, U512,
 };
 
 use super::Runtime;
@@ -44,6 +48,37 @@ where
         self.context.phase()
     }
 
+    fn get_blocktime(&self) -> BlockTime {
+        self.context.get_blocktime()
+    }
+
+    fn get_era_id(&mut self) -> Result<EraId, Error> {
+        let auction_hash = self
+            .context
+            .get_system_contract(AUCTION)
+            .map_err(|_| Error::MissingKey)?;
+        let era_id_key = match self
+            .context
+            .read_gs_direct(&Key::from(auction_hash))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?
+        {
+            Some(StoredValue::Contract(contract)) => contract
+                .named_keys()
+                .get(ERA_ID_KEY)
+                .copied()
+                .ok_or(Error::MissingKey)?,
+            Some(_) | None => return Err(Error::MissingKey),
+        };
+        match self
+            .context
+            .read_gs_direct(&era_id_key)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?
+        {
+            Some(StoredValue::CLValue(value)) => CLValue::into_t(value).map_err(|_| Error::CLValue),
+            Some(_) | None => Err(Error::MissingKey),
+        }
+    }
+
     fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error> {
         self.context
             .put_key(name.to_string(), key)
@@ -162,6 +197,71 @@ where
             .metered_add_gs_unsafe(Key::Balance(uref.addr()), StoredValue::CLValue(cl_value))
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
+
+    fn read_purse_asset(&mut self, uref: URef) -> Result<Option<AssetId>, Error> {
+        // Purses of the native token have no entry under their purse asset key.
+        let maybe_value = self
+            .context
+            .read_gs_direct(&mint::purse_asset_key(uref))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(value)) => {
+                let asset_id = CLValue::into_t(value).map_err(|_| Error::CLValue)?;
+                Ok(Some(asset_id))
+            }
+            Some(_cl_value) => Err(Error::CLValue),
+            None => Ok(None),
+        }
+    }
+
+    fn write_purse_asset(&mut self, uref: URef, asset_id: AssetId) -> Result<(), Error> {
+        // A purse's asset is fixed on creation and can never be changed.
+        if self.read_purse_asset(uref)?.is_some() {
+            return Err(Error::MismatchedAssets);
+        }
+        let cl_value = CLValue::from_t(asset_id).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(mint::purse_asset_key(uref), StoredValue::CLValue(cl_value))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
+    fn read_asset_supply(&mut self, asset_id: AssetId) -> Result<Option<U512>, Error> {
+        let supply_uref = URef::new(asset_id.value(), AccessRights::NONE);
+        let maybe_value = self
+            .context
+            .read_gs_direct(&Key::URef(supply_uref))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(value)) => {
+                let value = CLValue::into_t(value).map_err(|_| Error::CLValue)?;
+                Ok(Some(value))
+            }
+            Some(_cl_value) => Err(Error::CLValue),
+            None => Ok(None),
+        }
+    }
+
+    fn read_purse_lock(&mut self, uref: URef) -> Result<Option<PurseLock>, Error> {
+        let maybe_value = self
+            .context
+            .read_gs_direct(&mint::purse_lock_key(uref))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        match maybe_value {
+            Some(StoredValue::CLValue(value)) => {
+                let value = CLValue::into_t(value).map_err(|_| Error::CLValue)?;
+                Ok(Some(value))
+            }
+            Some(_cl_value) => Err(Error::CLValue),
+            None => Ok(None),
+        }
+    }
+
+    fn write_purse_lock(&mut self, uref: URef, purse_lock: PurseLock) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(purse_lock).map_err(|_| Error::CLValue)?;
+        self.context
+            .metered_write_gs_unsafe(mint::purse_lock_key(uref), StoredValue::CLValue(cl_value))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
 }
 
 impl<'a, R> SystemProvider for Runtime<'a, R>
@@ -176,8 +276,9 @@ where
         target: URef,
         amount: U512,
         id: Option<u64>,
+        asset_id: Option<AssetId>,
     ) -> Result<(), Error> {
-        let result = Runtime::record_transfer(self, maybe_to, source, target, amount, id);
+        let result = Runtime::record_transfer(self, maybe_to, source, target, amount, id, asset_id);
         result.map_err(|exec_error| {
             <Option<Error>>::from(exec_error).unwrap_or(Error::RecordTransferFailure)
         })
@@ -37,13 +37,15 @@ use casper_types::{
     system::{
         self,
         auction::{self, EraInfo},
-        handle_payment, mint, standard_payment, CallStackElement, SystemContractType, AUCTION,
-        HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
+        handle_payment,
+        mint::{self, AssetId, PurseLock},
+        standard_payment, CallStackElement, SystemContractType, AUCTION, HANDLE_PAYMENT, MINT,
+        STANDARD_PAYMENT,
     },
     AccessRights, ApiError, CLTyped, CLValue, ContextAccessRights, ContractHash,
     ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, Gas,
     GrantedAccess, Key, NamedArg, Parameter, Phase, PublicKey, RuntimeArgs, StoredValue, Transfer,
-    TransferResult, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    TransferResult, TransferredTo, URef, URefAddr, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
 };
 
 use crate::{
@@ -427,6 +429,14 @@ where
             .map_err(|e| Error::Interpreter(e.into()).into())
     }
 
+    /// Writes the block's random beacon to dest_ptr in Wasm memory.
+    fn get_random_beacon(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let random_beacon = self.context.get_random_beacon();
+        self.try_get_memory()?
+            .set(dest_ptr, random_beacon.as_ref())
+            .map_err(|e| Error::Interpreter(e.into()).into())
+    }
+
     /// Load the uref known by the given name into the Wasm memory
     fn load_call_stack(
         &mut self,
@@ -681,6 +691,62 @@ where
                     mint_runtime.transfer(maybe_to, source, target, amount, id);
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
+            // Type: `fn create_asset(amount: U512) -> Result<URef, Error>`
+            mint::METHOD_CREATE_ASSET => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.mint)?;
+
+                let initial_supply: U512 =
+                    Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+                let result: Result<URef, mint::Error> = mint_runtime.create_asset(initial_supply);
+                if let Err(mint::Error::GasLimit) = result {
+                    return Err(execution::Error::GasLimit);
+                }
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn create_asset_purse(asset_id: AssetId) -> Result<URef, Error>`
+            mint::METHOD_CREATE_ASSET_PURSE => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.create)?;
+
+                let asset_id: AssetId = Self::get_named_argument(runtime_args, mint::ARG_ASSET_ID)?;
+                let result: Result<URef, mint::Error> = mint_runtime.create_asset_purse(asset_id);
+                if let Err(mint::Error::GasLimit) = result {
+                    return Err(execution::Error::GasLimit);
+                }
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn purse_asset(purse: URef) -> Result<Option<AssetId>, Error>`
+            mint::METHOD_PURSE_ASSET => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.balance)?;
+
+                let purse: URef = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let result: Result<Option<AssetId>, mint::Error> = mint_runtime.purse_asset(purse);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn set_purse_lock(purse: URef, unlock_timestamp: Option<u64>,
+            //     era_spending_limit: Option<U512>, allowed_targets: Vec<URefAddr>)
+            //     -> Result<(), Error>`
+            mint::METHOD_SET_PURSE_LOCK => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.set_purse_lock)?;
+
+                let purse: URef = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let unlock_timestamp: Option<u64> =
+                    Self::get_named_argument(runtime_args, mint::ARG_UNLOCK_TIMESTAMP)?;
+                let era_spending_limit: Option<U512> =
+                    Self::get_named_argument(runtime_args, mint::ARG_ERA_SPENDING_LIMIT)?;
+                let allowed_targets: Vec<URefAddr> =
+                    Self::get_named_argument(runtime_args, mint::ARG_ALLOWED_TARGETS)?;
+                let purse_lock = PurseLock::new(
+                    unlock_timestamp,
+                    era_spending_limit,
+                    allowed_targets.into_iter().collect(),
+                );
+                let result: Result<(), mint::Error> =
+                    mint_runtime.set_purse_lock(purse, purse_lock);
+                if let Err(mint::Error::GasLimit) = result {
+                    return Err(execution::Error::GasLimit);
+                }
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
             // Type: `fn read_base_round_reward() -> Result<U512, Error>`
             mint::METHOD_READ_BASE_ROUND_REWARD => (|| {
                 mint_runtime.charge_system_contract_call(mint_costs.read_base_round_reward)?;
@@ -791,8 +857,16 @@ where
                     Self::get_named_argument(runtime_args, handle_payment::ARG_ACCOUNT)?;
                 let target: URef =
                     Self::get_named_argument(runtime_args, handle_payment::ARG_TARGET)?;
+                let proposer_tip: Option<(URef, U512)> =
+                    match runtime_args.get(handle_payment::ARG_PROPOSER_TIP) {
+                        Some(_) => Some(Self::get_named_argument(
+                            runtime_args,
+                            handle_payment::ARG_PROPOSER_TIP,
+                        )?),
+                        None => None,
+                    };
                 runtime
-                    .finalize_payment(amount_spent, account, target)
+                    .finalize_payment(amount_spent, account, target, proposer_tip)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
@@ -971,13 +1045,22 @@ where
                     Self::get_named_argument(runtime_args, auction::ARG_ERA_END_TIMESTAMP_MILLIS)?;
                 let evicted_validators =
                     Self::get_named_argument(runtime_args, auction::ARG_EVICTED_VALIDATORS)?;
+                let inactive_validators = match runtime_args.get(auction::ARG_INACTIVE_VALIDATORS) {
+                    Some(_) => {
+                        Self::get_named_argument(runtime_args, auction::ARG_INACTIVE_VALIDATORS)?
+                    }
+                    None => Vec::new(),
+                };
 
                 let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let inactive_eras_before_eviction = self.config.inactive_eras_before_eviction();
 
                 runtime
                     .run_auction(
                         era_end_timestamp_millis,
                         evicted_validators,
+                        inactive_validators,
+                        inactive_eras_before_eviction,
                         max_delegators_per_validator,
                     )
                     .map_err(Self::reverter)?;
@@ -1028,6 +1111,36 @@ where
                 CLValue::from_t(()).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_PROPOSE_PARAMETER_CHANGES => (|| {
+                runtime.charge_system_contract_call(auction_costs.propose_parameter_changes)?;
+
+                let proposer = Self::get_named_argument(runtime_args, auction::ARG_PROPOSER)?;
+                let parameter_changes =
+                    Self::get_named_argument(runtime_args, auction::ARG_PARAMETER_CHANGES)?;
+                let activation_era =
+                    Self::get_named_argument(runtime_args, auction::ARG_ACTIVATION_ERA)?;
+
+                let result = runtime
+                    .propose_parameter_changes(proposer, parameter_changes, activation_era)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_VOTE_ON_PROPOSAL => (|| {
+                runtime.charge_system_contract_call(auction_costs.vote_on_proposal)?;
+
+                let voter = Self::get_named_argument(runtime_args, auction::ARG_VOTER)?;
+                let proposal_id = Self::get_named_argument(runtime_args, auction::ARG_PROPOSAL_ID)?;
+                let approve = Self::get_named_argument(runtime_args, auction::ARG_APPROVE)?;
+
+                runtime
+                    .vote_on_proposal(voter, proposal_id, approve)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
 
@@ -1887,6 +2000,7 @@ where
         target: URef,
         amount: U512,
         id: Option<u64>,
+        asset_id: Option<AssetId>,
     ) -> Result<(), Error> {
         if self.context.base_key() != Key::from(self.context.get_system_contract(MINT)?) {
             return Err(Error::InvalidContext);
@@ -1901,7 +2015,17 @@ where
             let deploy_hash: DeployHash = self.context.get_deploy_hash();
             let from: AccountHash = self.context.account().account_hash();
             let fee: U512 = U512::zero(); // TODO
-            Transfer::new(deploy_hash, from, maybe_to, source, target, amount, fee, id)
+            Transfer::new(
+                deploy_hash,
+                from,
+                maybe_to,
+                source,
+                target,
+                amount,
+                fee,
+                id,
+                asset_id,
+            )
         };
         {
             let transfers = self.context.transfers_mut();
@@ -9,6 +9,7 @@ use std::{
 
 use tracing::error;
 
+use casper_hashing::Digest;
 use casper_types::{
     account::{
         Account, AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
@@ -93,6 +94,7 @@ pub struct RuntimeContext<'a, R> {
     //(could point at an account or contract in the global state)
     base_key: Key,
     blocktime: BlockTime,
+    random_beacon: Digest,
     deploy_hash: DeployHash,
     gas_limit: Gas,
     gas_counter: Gas,
@@ -125,6 +127,7 @@ where
         account: &'a Account,
         base_key: Key,
         blocktime: BlockTime,
+        random_beacon: Digest,
         deploy_hash: DeployHash,
         gas_limit: Gas,
         gas_counter: Gas,
@@ -145,6 +148,7 @@ where
             account,
             authorization_keys,
             blocktime,
+            random_beacon,
             deploy_hash,
             base_key,
             gas_limit,
@@ -174,6 +178,7 @@ where
         let authorization_keys = self.authorization_keys.clone();
         let account = self.account;
         let blocktime = self.blocktime;
+        let random_beacon = self.random_beacon;
         let deploy_hash = self.deploy_hash;
         let gas_limit = self.gas_limit;
         let gas_counter = self.gas_counter;
@@ -194,6 +199,7 @@ where
             account,
             authorization_keys,
             blocktime,
+            random_beacon,
             deploy_hash,
             base_key,
             gas_limit,
@@ -351,6 +357,11 @@ where
         self.blocktime
     }
 
+    /// Returns the random beacon of the block.
+    pub fn get_random_beacon(&self) -> Digest {
+        self.random_beacon
+    }
+
     /// Returns the deploy hash.
     pub fn get_deploy_hash(&self) -> DeployHash {
         self.deploy_hash
@@ -7,6 +7,7 @@ use std::{
 
 use rand::RngCore;
 
+use casper_hashing::Digest;
 use casper_types::{
     account::{
         Account, AccountHash, ActionType, AddKeyFailure, AssociatedKeys, RemoveKeyFailure,
@@ -131,6 +132,7 @@ fn new_runtime_context<'a>(
         account,
         base_key,
         BlockTime::new(0),
+        Digest::default(),
         DeployHash::new([1u8; 32]),
         Gas::new(U512::from(GAS_LIMIT)),
         Gas::default(),
@@ -392,6 +394,7 @@ fn contract_key_addable_valid() {
         &account,
         contract_key,
         BlockTime::new(0),
+        Digest::default(),
         DeployHash::new(DEPLOY_HASH),
         Gas::new(U512::from(GAS_LIMIT)),
         Gas::default(),
@@ -468,6 +471,7 @@ fn contract_key_addable_invalid() {
         &account,
         other_contract_key,
         BlockTime::new(0),
+        Digest::default(),
         DeployHash::new(DEPLOY_HASH),
         Gas::default(),
         Gas::default(),
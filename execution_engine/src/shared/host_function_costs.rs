@@ -32,6 +32,7 @@ const DEFAULT_GET_KEY_COST: u32 = 2_000;
 const DEFAULT_GET_KEY_NAME_SIZE_WEIGHT: u32 = 440;
 const DEFAULT_GET_MAIN_PURSE_COST: u32 = 1_300;
 const DEFAULT_GET_PHASE_COST: u32 = 710;
+const DEFAULT_GET_RANDOM_BEACON_COST: u32 = 330;
 const DEFAULT_GET_SYSTEM_CONTRACT_COST: u32 = 1_100;
 const DEFAULT_HAS_KEY_COST: u32 = 1_500;
 const DEFAULT_HAS_KEY_NAME_SIZE_WEIGHT: u32 = 840;
@@ -287,6 +288,8 @@ pub struct HostFunctionCosts {
     pub random_bytes: HostFunction<[Cost; 2]>,
     /// Cost of calling the `enable_contract_version` host function.
     pub enable_contract_version: HostFunction<[Cost; 4]>,
+    /// Cost of calling the `get_random_beacon` host function.
+    pub get_random_beacon: HostFunction<[Cost; 1]>,
 }
 
 impl Default for HostFunctionCosts {
@@ -452,6 +455,7 @@ impl Default for HostFunctionCosts {
             ),
             random_bytes: HostFunction::default(),
             enable_contract_version: HostFunction::default(),
+            get_random_beacon: HostFunction::fixed(DEFAULT_GET_RANDOM_BEACON_COST),
         }
     }
 }
@@ -503,6 +507,7 @@ impl ToBytes for HostFunctionCosts {
         ret.append(&mut self.blake2b.to_bytes()?);
         ret.append(&mut self.random_bytes.to_bytes()?);
         ret.append(&mut self.enable_contract_version.to_bytes()?);
+        ret.append(&mut self.get_random_beacon.to_bytes()?);
         Ok(ret)
     }
 
@@ -551,6 +556,7 @@ impl ToBytes for HostFunctionCosts {
             + self.blake2b.serialized_length()
             + self.random_bytes.serialized_length()
             + self.enable_contract_version.serialized_length()
+            + self.get_random_beacon.serialized_length()
     }
 }
 
@@ -600,6 +606,7 @@ impl FromBytes for HostFunctionCosts {
         let (blake2b, rem) = FromBytes::from_bytes(rem)?;
         let (random_bytes, rem) = FromBytes::from_bytes(rem)?;
         let (enable_contract_version, rem) = FromBytes::from_bytes(rem)?;
+        let (get_random_beacon, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             HostFunctionCosts {
                 read_value,
@@ -646,6 +653,7 @@ impl FromBytes for HostFunctionCosts {
                 blake2b,
                 random_bytes,
                 enable_contract_version,
+                get_random_beacon,
             },
             rem,
         ))
@@ -699,6 +707,7 @@ impl Distribution<HostFunctionCosts> for Standard {
             blake2b: rng.gen(),
             random_bytes: rng.gen(),
             enable_contract_version: rng.gen(),
+            get_random_beacon: rng.gen(),
         }
     }
 }
@@ -760,6 +769,7 @@ pub mod gens {
             blake2b in host_function_cost_arb(),
             random_bytes in host_function_cost_arb(),
             enable_contract_version in host_function_cost_arb(),
+            get_random_beacon in host_function_cost_arb(),
         ) -> HostFunctionCosts {
             HostFunctionCosts {
                 read_value,
@@ -806,6 +816,7 @@ pub mod gens {
                 blake2b,
                 random_bytes,
                 enable_contract_version,
+                get_random_beacon,
             }
         }
     }
@@ -1,5 +1,5 @@
 //! Support for Wasm opcode costs.
-use std::{convert::TryInto, num::NonZeroU32};
+use std::{collections::BTreeMap, convert::TryInto, num::NonZeroU32};
 
 use casper_wasm::elements::Instruction;
 use casper_wasm_utils::rules::{MemoryGrowCost, Rules};
@@ -363,6 +363,47 @@ pub struct OpcodeCosts {
     pub control_flow: ControlFlowCosts,
 }
 
+impl OpcodeCosts {
+    /// Returns a mutable reference to the cost with the given name, as used in the chainspec.
+    ///
+    /// Only the top-level costs are named; control flow costs can't be looked up this way.
+    fn cost_mut(&mut self, name: &str) -> Option<&mut u32> {
+        let cost = match name {
+            "bit" => &mut self.bit,
+            "add" => &mut self.add,
+            "mul" => &mut self.mul,
+            "div" => &mut self.div,
+            "load" => &mut self.load,
+            "store" => &mut self.store,
+            "const" => &mut self.op_const,
+            "local" => &mut self.local,
+            "global" => &mut self.global,
+            "integer_comparison" => &mut self.integer_comparison,
+            "conversion" => &mut self.conversion,
+            "unreachable" => &mut self.unreachable,
+            "nop" => &mut self.nop,
+            "current_memory" => &mut self.current_memory,
+            "grow_memory" => &mut self.grow_memory,
+            _ => return None,
+        };
+        Some(cost)
+    }
+
+    /// Returns `true` if `name` is the chainspec name of a top-level cost.
+    pub fn is_named_cost(name: &str) -> bool {
+        OpcodeCosts::default().cost_mut(name).is_some()
+    }
+
+    /// Returns a copy with the named costs replaced, or `None` if any name is unknown.
+    pub fn with_changes(&self, changes: &BTreeMap<String, u32>) -> Option<OpcodeCosts> {
+        let mut opcode_costs = *self;
+        for (name, new_cost) in changes {
+            *opcode_costs.cost_mut(name)? = *new_cost;
+        }
+        Some(opcode_costs)
+    }
+}
+
 impl Rules for OpcodeCosts {
     fn instruction_cost(&self, instruction: &Instruction) -> Option<u32> {
         match instruction {
@@ -839,11 +880,13 @@ pub mod gens {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use proptest::proptest;
 
     use casper_types::bytesrepr;
 
-    use super::gens;
+    use super::{gens, OpcodeCosts};
 
     proptest! {
         #[test]
@@ -853,4 +896,19 @@ mod tests {
             bytesrepr::test_serialization_roundtrip(&opcode_costs);
         }
     }
+
+    #[test]
+    fn should_apply_named_changes() {
+        let changes: BTreeMap<String, u32> = [("const".to_string(), 1), ("add".to_string(), 2)]
+            .into_iter()
+            .collect();
+        let opcode_costs = OpcodeCosts::default().with_changes(&changes).unwrap();
+        assert_eq!(opcode_costs.op_const, 1);
+        assert_eq!(opcode_costs.add, 2);
+        assert_eq!(opcode_costs.mul, OpcodeCosts::default().mul);
+
+        let unknown: BTreeMap<String, u32> = [("call".to_string(), 1)].into_iter().collect();
+        assert!(OpcodeCosts::default().with_changes(&unknown).is_none());
+        assert!(!OpcodeCosts::is_named_cost("control_flow"));
+    }
 }
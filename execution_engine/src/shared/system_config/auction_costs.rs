@@ -32,6 +32,10 @@ pub const DEFAULT_WITHDRAW_VALIDATOR_REWARD_COST: u32 = 10_000;
 pub const DEFAULT_READ_ERA_ID_COST: u32 = 10_000;
 /// Default cost of the `activate_bid` auction entry point.
 pub const DEFAULT_ACTIVATE_BID_COST: u32 = 10_000;
+/// Default cost of the `propose_parameter_changes` auction entry point.
+pub const DEFAULT_PROPOSE_PARAMETER_CHANGES_COST: u32 = 2_500_000_000;
+/// Default cost of the `vote_on_proposal` auction entry point.
+pub const DEFAULT_VOTE_ON_PROPOSAL_COST: u32 = 10_000;
 
 /// Description of the costs of calling auction entrypoints.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -65,6 +69,10 @@ pub struct AuctionCosts {
     pub activate_bid: u32,
     /// Cost of calling the `redelegate` entry point.
     pub redelegate: u32,
+    /// Cost of calling the `propose_parameter_changes` entry point.
+    pub propose_parameter_changes: u32,
+    /// Cost of calling the `vote_on_proposal` entry point.
+    pub vote_on_proposal: u32,
 }
 
 impl Default for AuctionCosts {
@@ -84,6 +92,8 @@ impl Default for AuctionCosts {
             read_era_id: DEFAULT_READ_ERA_ID_COST,
             activate_bid: DEFAULT_ACTIVATE_BID_COST,
             redelegate: DEFAULT_REDELEGATE_COST,
+            propose_parameter_changes: DEFAULT_PROPOSE_PARAMETER_CHANGES_COST,
+            vote_on_proposal: DEFAULT_VOTE_ON_PROPOSAL_COST,
         }
     }
 }
@@ -107,6 +117,8 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            propose_parameter_changes,
+            vote_on_proposal,
         } = self;
 
         ret.append(&mut get_era_validators.to_bytes()?);
@@ -123,6 +135,8 @@ impl ToBytes for AuctionCosts {
         ret.append(&mut read_era_id.to_bytes()?);
         ret.append(&mut activate_bid.to_bytes()?);
         ret.append(&mut redelegate.to_bytes()?);
+        ret.append(&mut propose_parameter_changes.to_bytes()?);
+        ret.append(&mut vote_on_proposal.to_bytes()?);
 
         Ok(ret)
     }
@@ -143,6 +157,8 @@ impl ToBytes for AuctionCosts {
             read_era_id,
             activate_bid,
             redelegate,
+            propose_parameter_changes,
+            vote_on_proposal,
         } = self;
 
         get_era_validators.serialized_length()
@@ -159,6 +175,8 @@ impl ToBytes for AuctionCosts {
             + read_era_id.serialized_length()
             + activate_bid.serialized_length()
             + redelegate.serialized_length()
+            + propose_parameter_changes.serialized_length()
+            + vote_on_proposal.serialized_length()
     }
 }
 
@@ -178,6 +196,8 @@ impl FromBytes for AuctionCosts {
         let (read_era_id, rem) = FromBytes::from_bytes(rem)?;
         let (activate_bid, rem) = FromBytes::from_bytes(rem)?;
         let (redelegate, rem) = FromBytes::from_bytes(rem)?;
+        let (propose_parameter_changes, rem) = FromBytes::from_bytes(rem)?;
+        let (vote_on_proposal, rem) = FromBytes::from_bytes(rem)?;
         Ok((
             Self {
                 get_era_validators,
@@ -194,6 +214,8 @@ impl FromBytes for AuctionCosts {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                propose_parameter_changes,
+                vote_on_proposal,
             },
             rem,
         ))
@@ -217,6 +239,8 @@ impl Distribution<AuctionCosts> for Standard {
             read_era_id: rng.gen(),
             activate_bid: rng.gen(),
             redelegate: rng.gen(),
+            propose_parameter_changes: rng.gen(),
+            vote_on_proposal: rng.gen(),
         }
     }
 }
@@ -244,6 +268,8 @@ pub mod gens {
             read_era_id in num::u32::ANY,
             activate_bid in num::u32::ANY,
             redelegate in num::u32::ANY,
+            propose_parameter_changes in num::u32::ANY,
+            vote_on_proposal in num::u32::ANY,
         ) -> AuctionCosts {
             AuctionCosts {
                 get_era_validators,
@@ -260,6 +286,8 @@ pub mod gens {
                 read_era_id,
                 activate_bid,
                 redelegate,
+                propose_parameter_changes,
+                vote_on_proposal,
             }
         }
     }
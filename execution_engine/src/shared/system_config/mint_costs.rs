@@ -18,6 +18,8 @@ pub const DEFAULT_TRANSFER_COST: u32 = 10_000;
 pub const DEFAULT_READ_BASE_ROUND_REWARD_COST: u32 = 10_000;
 /// Default cost of the `mint_into_existing_purse` mint entry point.
 pub const DEFAULT_MINT_INTO_EXISTING_PURSE_COST: u32 = 2_500_000_000;
+/// Default cost of the `set_purse_lock` mint entry point.
+pub const DEFAULT_SET_PURSE_LOCK_COST: u32 = 10_000;
 
 /// Description of the costs of calling mint entry points.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -37,6 +39,8 @@ pub struct MintCosts {
     pub read_base_round_reward: u32,
     /// Cost of calling the `mint_into_existing_purse` entry point.
     pub mint_into_existing_purse: u32,
+    /// Cost of calling the `set_purse_lock` entry point.
+    pub set_purse_lock: u32,
 }
 
 impl Default for MintCosts {
@@ -49,6 +53,7 @@ impl Default for MintCosts {
             transfer: DEFAULT_TRANSFER_COST,
             read_base_round_reward: DEFAULT_READ_BASE_ROUND_REWARD_COST,
             mint_into_existing_purse: DEFAULT_MINT_INTO_EXISTING_PURSE_COST,
+            set_purse_lock: DEFAULT_SET_PURSE_LOCK_COST,
         }
     }
 }
@@ -65,6 +70,7 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            set_purse_lock,
         } = self;
 
         ret.append(&mut mint.to_bytes()?);
@@ -74,6 +80,7 @@ impl ToBytes for MintCosts {
         ret.append(&mut transfer.to_bytes()?);
         ret.append(&mut read_base_round_reward.to_bytes()?);
         ret.append(&mut mint_into_existing_purse.to_bytes()?);
+        ret.append(&mut set_purse_lock.to_bytes()?);
 
         Ok(ret)
     }
@@ -87,6 +94,7 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            set_purse_lock,
         } = self;
 
         mint.serialized_length()
@@ -96,6 +104,7 @@ impl ToBytes for MintCosts {
             + transfer.serialized_length()
             + read_base_round_reward.serialized_length()
             + mint_into_existing_purse.serialized_length()
+            + set_purse_lock.serialized_length()
     }
 }
 
@@ -108,6 +117,7 @@ impl FromBytes for MintCosts {
         let (transfer, rem) = FromBytes::from_bytes(rem)?;
         let (read_base_round_reward, rem) = FromBytes::from_bytes(rem)?;
         let (mint_into_existing_purse, rem) = FromBytes::from_bytes(rem)?;
+        let (set_purse_lock, rem) = FromBytes::from_bytes(rem)?;
 
         Ok((
             Self {
@@ -118,6 +128,7 @@ impl FromBytes for MintCosts {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                set_purse_lock,
             },
             rem,
         ))
@@ -134,6 +145,7 @@ impl Distribution<MintCosts> for Standard {
             transfer: rng.gen(),
             read_base_round_reward: rng.gen(),
             mint_into_existing_purse: rng.gen(),
+            set_purse_lock: rng.gen(),
         }
     }
 }
@@ -154,6 +166,7 @@ pub mod gens {
             transfer in num::u32::ANY,
             read_base_round_reward in num::u32::ANY,
             mint_into_existing_purse in num::u32::ANY,
+            set_purse_lock in num::u32::ANY,
         ) -> MintCosts {
             MintCosts {
                 mint,
@@ -163,6 +176,7 @@ pub mod gens {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                set_purse_lock,
             }
         }
     }
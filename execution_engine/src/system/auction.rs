@@ -9,13 +9,15 @@ use num_traits::{CheckedMul, CheckedSub};
 use casper_types::{
     account::AccountHash,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
-        SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
+        Bid, DelegationRate, EraInfo, EraValidators, Error, ParameterChanges, Proposal,
+        ProposalStatus, SeigniorageAllocation, SeigniorageRecipients, ValidatorWeights,
+        BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
     },
     ApiError, EraId, PublicKey, U512,
 };
 
 use self::providers::{AccountProvider, MintProvider, RuntimeProvider, StorageProvider};
+use crate::shared::opcode_costs::OpcodeCosts;
 
 /// Bonding auction contract interface
 pub trait Auction:
@@ -425,11 +427,17 @@ pub trait Auction:
     /// added to their delegators') ordered by size from largest to smallest, then takes the top N
     /// (number of auction slots) bidders and replaces era_validators with these.
     ///
+    /// The bids of `evicted_validators` are deactivated immediately, while `inactive_validators`
+    /// only have their bids deactivated once they have been inactive for
+    /// `inactive_eras_before_eviction` consecutive eras.  Either can rejoin using `activate_bid`.
+    ///
     /// Accessed by: node
     fn run_auction(
         &mut self,
         era_end_timestamp_millis: u64,
         evicted_validators: Vec<PublicKey>,
+        inactive_validators: Vec<PublicKey>,
+        inactive_eras_before_eviction: u64,
         max_delegators_per_validator: Option<u32>,
     ) -> Result<(), ApiError> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
@@ -445,6 +453,13 @@ pub trait Auction:
         // Process unbond requests
         detail::process_unbond_requests(self, max_delegators_per_validator)?;
 
+        // Track consecutive inactivity and find validators who reached the eviction threshold
+        let inactive_evicted_validators = detail::update_validator_inactivity(
+            self,
+            &inactive_validators,
+            inactive_eras_before_eviction,
+        )?;
+
         let mut bids = detail::get_bids(self)?;
 
         // Process bids
@@ -457,8 +472,10 @@ pub trait Auction:
                 bids_modified = true;
             }
 
-            if evicted_validators.contains(validator_public_key) {
-                bids_modified = bid.deactivate()
+            if evicted_validators.contains(validator_public_key)
+                || inactive_evicted_validators.contains(validator_public_key)
+            {
+                bids_modified |= bid.deactivate()
             }
         }
 
@@ -512,6 +529,9 @@ pub trait Auction:
                 .collect()
         };
 
+        // Close voting on governance proposals activating in the next era
+        detail::tally_proposals(self, era_id)?;
+
         // Increment era
         era_id = era_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
 
@@ -676,4 +696,85 @@ pub trait Auction:
 
         Ok(())
     }
+
+    /// Proposes a set of chainspec parameter changes to be applied at the start of
+    /// `activation_era`, counting as an approving vote of the proposer.
+    ///
+    /// Only validators of the current era can make proposals, and `activation_era` has to be a
+    /// future era.  Votes are collected until the end of the era preceding `activation_era`.
+    ///
+    /// Returns the id of the new proposal.
+    fn propose_parameter_changes(
+        &mut self,
+        proposer: PublicKey,
+        parameter_changes: ParameterChanges,
+        activation_era: EraId,
+    ) -> Result<u64, Error> {
+        let provided_account_hash = AccountHash::from_public_key(&proposer, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let era_id = detail::get_era_id(self)?;
+        if parameter_changes.is_empty()
+            || activation_era <= era_id
+            || parameter_changes.block_gas_limit == Some(0)
+            || !parameter_changes
+                .opcode_costs
+                .keys()
+                .all(|name| OpcodeCosts::is_named_cost(name))
+        {
+            return Err(Error::InvalidProposal);
+        }
+
+        let validator_weights = detail::get_era_validator_weights(self, era_id)?;
+        if !validator_weights.contains_key(&proposer) {
+            return Err(Error::NotAnEraValidator);
+        }
+
+        let mut proposals = self.read_proposals()?;
+        let mut proposal = Proposal::new(proposer.clone(), parameter_changes, activation_era);
+        proposal.vote(proposer, true);
+        let proposal_id = proposals
+            .insert(proposal)
+            .ok_or(Error::ArithmeticOverflow)?;
+        self.write_proposals(proposals)?;
+
+        Ok(proposal_id)
+    }
+
+    /// Records the vote of a validator of the current era on a pending governance proposal,
+    /// replacing the validator's previous vote if any.
+    fn vote_on_proposal(
+        &mut self,
+        voter: PublicKey,
+        proposal_id: u64,
+        approve: bool,
+    ) -> Result<(), Error> {
+        let provided_account_hash = AccountHash::from_public_key(&voter, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let era_id = detail::get_era_id(self)?;
+        let validator_weights = detail::get_era_validator_weights(self, era_id)?;
+        if !validator_weights.contains_key(&voter) {
+            return Err(Error::NotAnEraValidator);
+        }
+
+        let mut proposals = self.read_proposals()?;
+        let proposal = proposals
+            .get_mut(&proposal_id)
+            .ok_or(Error::ProposalNotFound)?;
+        if proposal.status() != ProposalStatus::Pending {
+            return Err(Error::ProposalClosed);
+        }
+
+        proposal.vote(voter, approve);
+        self.write_proposals(proposals)?;
+
+        Ok(())
+    }
 }
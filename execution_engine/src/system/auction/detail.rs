@@ -6,9 +6,10 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        Bids, Delegator, Error, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
-        UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
-        ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+        Bids, Delegator, Error, ProposalStatus, SeigniorageAllocation,
+        SeigniorageRecipientsSnapshot, UnbondingPurse, UnbondingPurses, ValidatorInactivity,
+        AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY,
+        SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
     },
     ApiError, CLTyped, EraId, Key, KeyTag, PublicKey, URef, U512,
 };
@@ -494,6 +495,86 @@ pub(crate) fn era_validators_from_snapshot(
         .collect()
 }
 
+/// Returns the weights of the validators of the given era.
+pub(crate) fn get_era_validator_weights<P>(
+    provider: &mut P,
+    era_id: EraId,
+) -> Result<ValidatorWeights, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let snapshot = get_seigniorage_recipients_snapshot(provider)?;
+    let mut era_validators = era_validators_from_snapshot(snapshot);
+    Ok(era_validators.remove(&era_id).unwrap_or_default())
+}
+
+/// Tallies the votes of pending governance proposals whose voting ends with the given era, using
+/// the validator weights of that era.
+pub(crate) fn tally_proposals<P>(provider: &mut P, era_id: EraId) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let mut proposals = provider.read_proposals()?;
+    let next_era_id = era_id.successor();
+    let mut closing_proposals = proposals
+        .values_mut()
+        .filter(|proposal| {
+            proposal.status() == ProposalStatus::Pending && proposal.activation_era() <= next_era_id
+        })
+        .peekable();
+    if closing_proposals.peek().is_none() {
+        return Ok(());
+    }
+
+    let validator_weights = get_era_validator_weights(provider, era_id)?;
+    for proposal in closing_proposals {
+        proposal.tally(&validator_weights);
+    }
+    provider.write_proposals(proposals)
+}
+
+/// Updates the number of consecutive eras in which each validator was inactive, given the
+/// validators reported as inactive in the era that just ended.
+///
+/// Returns the validators whose inactivity reached `inactive_eras_before_eviction`; their counters
+/// are reset so that they are not evicted again right after reactivating their bids.
+pub(crate) fn update_validator_inactivity<P>(
+    provider: &mut P,
+    inactive_validators: &[PublicKey],
+    inactive_eras_before_eviction: u64,
+) -> Result<Vec<PublicKey>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let inactivity = provider.read_validator_inactivity()?;
+    if inactivity.is_empty() && inactive_validators.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut evicted_validators = Vec::new();
+    let mut updated_inactivity = ValidatorInactivity::new();
+    for public_key in inactive_validators {
+        if updated_inactivity.contains_key(public_key) {
+            continue;
+        }
+        let inactive_eras = inactivity
+            .get(public_key)
+            .copied()
+            .unwrap_or_default()
+            .saturating_add(1);
+        if inactive_eras >= inactive_eras_before_eviction {
+            evicted_validators.push(public_key.clone());
+        } else {
+            updated_inactivity.insert(public_key.clone(), inactive_eras);
+        }
+    }
+
+    if updated_inactivity != inactivity {
+        provider.write_validator_inactivity(updated_inactivity)?;
+    }
+    Ok(evicted_validators)
+}
+
 fn is_under_max_delegator_cap(
     max_delegators_per_validator: Option<u32>,
     new_validator_delegator_len: usize,
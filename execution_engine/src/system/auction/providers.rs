@@ -4,7 +4,7 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{
-        auction::{Bid, EraInfo, Error, UnbondingPurse},
+        auction::{Bid, EraInfo, Error, Proposals, UnbondingPurse, ValidatorInactivity},
         mint,
     },
     CLTyped, EraId, Key, KeyTag, URef, BLAKE2B_DIGEST_LENGTH, U512,
@@ -60,6 +60,18 @@ pub trait StorageProvider {
 
     /// Records era summary.
     fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error>;
+
+    /// Reads all governance [`Proposals`].
+    fn read_proposals(&mut self) -> Result<Proposals, Error>;
+
+    /// Writes given governance [`Proposals`].
+    fn write_proposals(&mut self, proposals: Proposals) -> Result<(), Error>;
+
+    /// Reads the number of consecutive eras in which validators were inactive.
+    fn read_validator_inactivity(&mut self) -> Result<ValidatorInactivity, Error>;
+
+    /// Writes the number of consecutive eras in which validators were inactive.
+    fn write_validator_inactivity(&mut self, inactivity: ValidatorInactivity) -> Result<(), Error>;
 }
 
 /// Provides an access to mint.
@@ -34,13 +34,17 @@ pub trait HandlePayment: MintProvider + RuntimeProvider + StorageProvider + Size
     }
 
     /// Finalize payment with `amount_spent` and a given `account`.
+    ///
+    /// If `proposer_tip` is provided, the given amount is paid out of the fee to the given purse
+    /// before the rest of the fee is handled.
     fn finalize_payment(
         &mut self,
         amount_spent: U512,
         account: AccountHash,
         target: URef,
+        proposer_tip: Option<(URef, U512)>,
     ) -> Result<(), Error> {
-        internal::finalize_payment(self, amount_spent, account, target)
+        internal::finalize_payment(self, amount_spent, account, target, proposer_tip)
     }
 
     /// Distribute fees from an accumulation purse.
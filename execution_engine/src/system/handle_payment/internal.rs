@@ -95,6 +95,7 @@ pub(crate) fn finalize_payment<P: MintProvider + RuntimeProvider + StorageProvid
     gas_spent: U512,
     account: AccountHash,
     target: URef,
+    proposer_tip: Option<(URef, U512)>,
 ) -> Result<(), Error> {
     let caller = provider.get_caller();
     if caller != PublicKey::System.to_account_hash() {
@@ -111,7 +112,7 @@ pub(crate) fn finalize_payment<P: MintProvider + RuntimeProvider + StorageProvid
         return Err(Error::InsufficientPaymentForAmountSpent);
     }
 
-    let (refund, fee) =
+    let (refund, mut fee) =
         calculate_refund_and_fee(gas_spent, payment_amount, provider.refund_handling())?;
 
     debug_assert_eq!(fee + refund, payment_amount);
@@ -173,6 +174,19 @@ pub(crate) fn finalize_payment<P: MintProvider + RuntimeProvider + StorageProvid
         }
     }
 
+    // Pay the proposer's tip, which is never more than the fee.
+    if let Some((proposer_purse, tip)) = proposer_tip {
+        let tip = tip.min(fee);
+        if !tip.is_zero() {
+            if let Err(error) = provider.transfer_purse_to_purse(payment_purse, proposer_purse, tip)
+            {
+                error!(%error, %tip, %proposer_purse, "unable to transfer tip");
+                return Err(Error::FailedTransferToRewardsPurse);
+            }
+            fee -= tip;
+        }
+    }
+
     // Pay or burn the fee.
     match provider.fee_handling() {
         FeeHandling::PayToProposer | FeeHandling::Accumulate => {
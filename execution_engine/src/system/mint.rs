@@ -9,7 +9,7 @@ use tracing::warn;
 use casper_types::{
     account::AccountHash,
     system::{
-        mint::{Error, ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY},
+        mint::{AssetId, Error, PurseLock, ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY},
         CallStackElement,
     },
     Key, Phase, PublicKey, StoredValue, URef, U512,
@@ -243,6 +243,33 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if self.read_balance(target)?.is_none() {
             return Err(Error::DestNotFound);
         }
+        let asset_id = self.read_purse_asset(source)?;
+        if self.read_purse_asset(target)? != asset_id {
+            return Err(Error::MismatchedAssets);
+        }
+        let maybe_purse_lock = match self.read_purse_lock(source)? {
+            Some(mut purse_lock) => {
+                if !purse_lock.is_allowed_target(target.addr()) {
+                    return Err(Error::TargetNotAllowed);
+                }
+                if purse_lock.is_time_locked(self.get_blocktime().into()) {
+                    return Err(Error::PurseLocked);
+                }
+                if let Some(era_spending_limit) = purse_lock.era_spending_limit() {
+                    let era_id = self.get_era_id()?;
+                    let spent_in_era = purse_lock
+                        .spent_in_era(era_id)
+                        .checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    if spent_in_era > era_spending_limit {
+                        return Err(Error::EraSpendingLimitExceeded);
+                    }
+                    purse_lock.record_spending(era_id, amount);
+                }
+                Some(purse_lock)
+            }
+            None => None,
+        };
         if self.get_caller() != PublicKey::System.to_account_hash()
             && self.get_main_purse().addr() == source.addr()
         {
@@ -251,9 +278,12 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             }
             self.sub_approved_spending_limit(amount);
         }
+        if let Some(purse_lock) = maybe_purse_lock {
+            self.write_purse_lock(source, purse_lock)?;
+        }
         self.write_balance(source, source_balance - amount)?;
         self.add_balance(target, amount)?;
-        self.record_transfer(maybe_to, source, target, amount, id)?;
+        self.record_transfer(maybe_to, source, target, amount, id, asset_id)?;
         Ok(())
     }
 
@@ -301,6 +331,9 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if self.read_balance(existing_purse)?.is_none() {
             return Err(Error::PurseNotFound);
         }
+        if self.read_purse_asset(existing_purse)?.is_some() {
+            return Err(Error::MismatchedAssets);
+        }
         self.add_balance(existing_purse, amount)?;
         // get total supply uref if exists, otherwise error.
         let total_supply_uref = match self.get_key(TOTAL_SUPPLY_KEY) {
@@ -317,4 +350,70 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         self.add(total_supply_uref, amount)?;
         Ok(())
     }
+
+    /// Creates a new asset with a fixed supply of `initial_supply`.  Only administrators and
+    /// contracts may create assets.
+    ///
+    /// Returns a new purse holding the whole supply of the asset on success, otherwise an error.
+    fn create_asset(&mut self, initial_supply: U512) -> Result<URef, Error> {
+        let caller = self.get_caller();
+        let is_called_by_contract = matches!(
+            self.get_immediate_caller(),
+            Some(CallStackElement::StoredContract { .. })
+                | Some(CallStackElement::StoredSession { .. })
+        );
+        if !is_called_by_contract
+            && caller != PublicKey::System.to_account_hash()
+            && !self.is_administrator(&caller)
+        {
+            return Err(Error::InvalidAssetCreation);
+        }
+
+        // The asset is identified by the address of the `URef` holding its total supply.
+        let supply_uref: URef = self.new_uref(initial_supply)?;
+        let asset_id = AssetId::new(supply_uref.addr());
+
+        let purse_uref: URef = self.new_uref(())?;
+        self.write_balance(purse_uref, initial_supply)?;
+        self.write_purse_asset(purse_uref, asset_id)?;
+        Ok(purse_uref)
+    }
+
+    /// Creates a new empty purse for the given asset.
+    fn create_asset_purse(&mut self, asset_id: AssetId) -> Result<URef, Error> {
+        if self.read_asset_supply(asset_id)?.is_none() {
+            return Err(Error::AssetNotFound);
+        }
+        let purse_uref: URef = self.new_uref(())?;
+        self.write_balance(purse_uref, U512::zero())?;
+        self.write_purse_asset(purse_uref, asset_id)?;
+        Ok(purse_uref)
+    }
+
+    /// Returns the asset held in `purse`, or `None` if it holds the native token.
+    fn purse_asset(&mut self, purse: URef) -> Result<Option<AssetId>, Error> {
+        if self.read_balance(purse)?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        self.read_purse_asset(purse)
+    }
+
+    /// Places `purse_lock` on `purse`, restricting transfers out of it.  Once a purse is locked,
+    /// only the system or an administrator may replace its lock.
+    fn set_purse_lock(&mut self, purse: URef, purse_lock: PurseLock) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if self.read_balance(purse)?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        let caller = self.get_caller();
+        if self.read_purse_lock(purse)?.is_some()
+            && caller != PublicKey::System.to_account_hash()
+            && !self.is_administrator(&caller)
+        {
+            return Err(Error::PurseLockAlreadySet);
+        }
+        self.write_purse_lock(purse, purse_lock)
+    }
 }
@@ -1,7 +1,7 @@
 use casper_types::{
     account::AccountHash,
     system::{mint::Error, CallStackElement},
-    Key, Phase, StoredValue, URef, U512,
+    BlockTime, EraId, Key, Phase, StoredValue, URef, U512,
 };
 
 use crate::core::{engine_state::SystemContractRegistry, execution};
@@ -27,6 +27,12 @@ pub trait RuntimeProvider {
     /// Gets execution phase
     fn get_phase(&self) -> Phase;
 
+    /// Gets the block time of the block being executed.
+    fn get_blocktime(&self) -> BlockTime;
+
+    /// Gets the current era id as recorded by the auction.
+    fn get_era_id(&mut self) -> Result<EraId, Error>;
+
     /// This method should handle storing given [`Key`] under `name`.
     fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error>;
 
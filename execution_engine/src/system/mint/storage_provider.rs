@@ -1,6 +1,6 @@
 use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
-    system::mint::Error,
+    system::mint::{AssetId, Error, PurseLock},
     CLTyped, URef, U512,
 };
 
@@ -26,4 +26,19 @@ pub trait StorageProvider {
 
     /// Add amount to an existing balance.
     fn add_balance(&mut self, uref: URef, value: U512) -> Result<(), Error>;
+
+    /// Read the asset held in a purse, or `None` if it holds the native token.
+    fn read_purse_asset(&mut self, uref: URef) -> Result<Option<AssetId>, Error>;
+
+    /// Write the asset held in a newly created purse.
+    fn write_purse_asset(&mut self, uref: URef, asset_id: AssetId) -> Result<(), Error>;
+
+    /// Read the total supply of an asset, or `None` if the asset doesn't exist.
+    fn read_asset_supply(&mut self, asset_id: AssetId) -> Result<Option<U512>, Error>;
+
+    /// Read the lock placed on a purse, if any.
+    fn read_purse_lock(&mut self, uref: URef) -> Result<Option<PurseLock>, Error>;
+
+    /// Write the lock placed on a purse.
+    fn write_purse_lock(&mut self, uref: URef, purse_lock: PurseLock) -> Result<(), Error>;
 }
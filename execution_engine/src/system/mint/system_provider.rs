@@ -1,4 +1,8 @@
-use casper_types::{account::AccountHash, system::mint::Error, URef, U512};
+use casper_types::{
+    account::AccountHash,
+    system::mint::{AssetId, Error},
+    URef, U512,
+};
 
 /// Provides functionality of a system module.
 pub trait SystemProvider {
@@ -10,5 +14,6 @@ pub trait SystemProvider {
         target: URef,
         amount: U512,
         id: Option<u64>,
+        asset_id: Option<AssetId>,
     ) -> Result<(), Error>;
 }
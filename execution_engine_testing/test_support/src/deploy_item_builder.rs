@@ -21,6 +21,7 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<AccountHash>,
     pub deploy_hash: Option<DeployHash>,
+    pub sponsor: Option<AccountHash>,
 }
 
 /// Builds a [`DeployItem`].
@@ -261,6 +262,12 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Sets the account which pays for the deploy.
+    pub fn with_sponsor(mut self, sponsor: AccountHash) -> Self {
+        self.deploy_item.sponsor = Some(sponsor);
+        self
+    }
+
     /// Consumes self and returns a [`DeployItem`].
     pub fn build(self) -> DeployItem {
         DeployItem {
@@ -282,6 +289,7 @@ impl DeployItemBuilder {
                 .deploy_item
                 .deploy_hash
                 .unwrap_or_else(|| rand::thread_rng().gen()),
+            sponsor: self.deploy_item.sponsor,
         }
     }
 }
@@ -3,6 +3,7 @@ use rand::Rng;
 use casper_execution_engine::core::engine_state::{
     deploy_item::DeployItem, execute_request::ExecuteRequest,
 };
+use casper_hashing::Digest;
 use casper_types::{
     account::AccountHash, runtime_args, ContractHash, ContractPackageHash, ContractVersion,
     ProtocolVersion, RuntimeArgs,
@@ -59,6 +60,18 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    /// Sets the base fee per unit of gas used by the [`ExecuteRequest`].
+    pub fn with_base_fee(mut self, base_fee: u64) -> Self {
+        self.execute_request.base_fee = Some(base_fee);
+        self
+    }
+
+    /// Sets the random beacon used by the [`ExecuteRequest`].
+    pub fn with_random_beacon(mut self, random_beacon: Digest) -> Self {
+        self.execute_request.random_beacon = random_beacon;
+        self
+    }
+
     /// Consumes self and returns an [`ExecuteRequest`].
     pub fn build(self) -> ExecuteRequest {
         self.execute_request
@@ -3,7 +3,7 @@ use casper_execution_engine::core::engine_state::{
     StepRequest,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{EraId, ProtocolVersion, PublicKey};
 
 /// Builder for creating a [`StepRequest`].
 #[derive(Debug, Clone)]
@@ -13,6 +13,7 @@ pub struct StepRequestBuilder {
     slash_items: Vec<SlashItem>,
     reward_items: Vec<RewardItem>,
     evict_items: Vec<EvictItem>,
+    inactive_validators: Vec<PublicKey>,
     run_auction: bool,
     next_era_id: EraId,
     era_end_timestamp_millis: u64,
@@ -66,6 +67,21 @@ impl StepRequestBuilder {
         self
     }
 
+    /// Pushes the given [`PublicKey`] into `inactive_validators`.
+    pub fn with_inactive_validator(mut self, inactive_validator: PublicKey) -> Self {
+        self.inactive_validators.push(inactive_validator);
+        self
+    }
+
+    /// Appends the given vector of [`PublicKey`] into `inactive_validators`.
+    pub fn with_inactive_validators(
+        mut self,
+        inactive_validators: impl IntoIterator<Item = PublicKey>,
+    ) -> Self {
+        self.inactive_validators.extend(inactive_validators);
+        self
+    }
+
     /// Sets `run_auction`.
     pub fn with_run_auction(mut self, run_auction: bool) -> Self {
         self.run_auction = run_auction;
@@ -92,6 +108,7 @@ impl StepRequestBuilder {
             self.slash_items,
             self.reward_items,
             self.evict_items,
+            self.inactive_validators,
             self.next_era_id,
             self.era_end_timestamp_millis,
         )
@@ -106,6 +123,7 @@ impl Default for StepRequestBuilder {
             slash_items: Default::default(),
             reward_items: Default::default(),
             evict_items: Default::default(),
+            inactive_validators: Default::default(),
             run_auction: true, //<-- run_auction by default
             next_era_id: Default::default(),
             era_end_timestamp_millis: Default::default(),
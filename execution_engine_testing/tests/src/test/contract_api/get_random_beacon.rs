@@ -0,0 +1,44 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_hashing::Digest;
+use casper_types::{runtime_args, RuntimeArgs};
+
+const CONTRACT_GET_RANDOM_BEACON: &str = "get_random_beacon.wasm";
+const ARG_KNOWN_RANDOM_BEACON: &str = "known_random_beacon";
+
+#[ignore]
+#[test]
+fn should_run_get_random_beacon_contract() {
+    let random_beacon = Digest::hash(b"random beacon");
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_RANDOM_BEACON,
+        runtime_args! { ARG_KNOWN_RANDOM_BEACON => random_beacon.value() },
+    )
+    .with_random_beacon(random_beacon)
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_default_to_zero_random_beacon() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_RANDOM_BEACON,
+        runtime_args! { ARG_KNOWN_RANDOM_BEACON => [0u8; Digest::LENGTH] },
+    )
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .expect_success();
+}
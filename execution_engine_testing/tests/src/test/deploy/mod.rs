@@ -2,4 +2,5 @@ mod context_association;
 mod non_standard_payment;
 mod preconditions;
 mod receipts;
+mod sponsored;
 mod stored_contracts;
@@ -0,0 +1,107 @@
+use assert_matches::assert_matches;
+
+use casper_engine_test_support::{
+    utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PAYMENT, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::engine_state::Error;
+use casper_types::{account::AccountHash, runtime_args, RuntimeArgs, U512};
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ARG_TARGET: &str = "target";
+const ARG_AMOUNT: &str = "amount";
+const ACCOUNT_1_FUNDING_AMOUNT: u64 = 1;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let fund_account_1_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => ACCOUNT_1_ADDR,
+            ARG_AMOUNT => U512::from(ACCOUNT_1_FUNDING_AMOUNT)
+        },
+    )
+    .build();
+    builder
+        .exec(fund_account_1_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_charge_sponsor_for_sponsored_deploy() {
+    let mut builder = setup();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    let sponsor = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have sponsor");
+    let sponsor_balance_before = builder.get_purse_balance(sponsor.main_purse());
+
+    // account 1 can't afford the payment on its own
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_sponsor(*DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[ACCOUNT_1_ADDR, *DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        U512::from(ACCOUNT_1_FUNDING_AMOUNT),
+        "account 1 should not have paid for its deploy"
+    );
+    assert!(
+        builder.get_purse_balance(sponsor.main_purse()) < sponsor_balance_before,
+        "sponsor should have paid for the deploy"
+    );
+    assert!(
+        builder.get_purse_balance(sponsor.main_purse()) > sponsor_balance_before - *DEFAULT_PAYMENT,
+        "sponsor should have been refunded the unused payment"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_raise_precondition_authorization_failure_without_sponsor_approval() {
+    let mut builder = setup();
+
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(ACCOUNT_1_ADDR)
+            .with_sponsor(*DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(DO_NOTHING_WASM, RuntimeArgs::default())
+            .with_empty_payment_bytes(runtime_args! { ARG_AMOUNT => *DEFAULT_PAYMENT })
+            .with_authorization_keys(&[ACCOUNT_1_ADDR])
+            .with_deploy_hash([1; 32])
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    };
+
+    builder.exec(exec_request).commit();
+
+    let response = builder
+        .get_exec_result_owned(1)
+        .expect("there should be a response");
+
+    let precondition_failure = utils::get_precondition_failure(&response);
+    assert_matches!(precondition_failure, Error::Authorization);
+}
@@ -1,3 +1,4 @@
+mod assets;
 mod burn_fees_and_refund;
 mod fees_accumulation;
 pub mod management;
@@ -0,0 +1,240 @@
+use casper_engine_test_support::{ExecuteRequestBuilder, InMemoryWasmTestBuilder};
+use casper_execution_engine::core::{engine_state::Error, execution};
+use casper_types::{
+    account::AccountHash, runtime_args, system::mint, Key, RuntimeArgs, URef, U512,
+};
+
+use super::{ACCOUNT_1_ADDR, DEFAULT_ADMIN_ACCOUNT_ADDR};
+
+const MINT_ASSETS_CONTRACT: &str = "mint_assets.wasm";
+const TRANSFER_TO_NAMED_PURSE_CONTRACT: &str = "transfer_to_named_purse.wasm";
+const OVERWRITE_UREF_CONTENT_CONTRACT: &str = "overwrite_uref_content.wasm";
+
+const ARG_ACTION: &str = "action";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE_NAME: &str = "purse_name";
+const ARG_SOURCE_PURSE_NAME: &str = "source_purse_name";
+const ARG_CONTRACT_UREF: &str = "contract_uref";
+
+const ACTION_CREATE_ASSET: &str = "create_asset";
+const ACTION_CREATE_ASSET_PURSE: &str = "create_asset_purse";
+const ACTION_TRANSFER: &str = "transfer";
+
+const ISSUER_PURSE: &str = "issuer_purse";
+const HOLDER_PURSE: &str = "holder_purse";
+const NATIVE_PURSE: &str = "native_purse";
+
+const INITIAL_SUPPLY: u64 = 1_000_000;
+
+fn create_asset_request(account_hash: AccountHash) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        account_hash,
+        MINT_ASSETS_CONTRACT,
+        runtime_args! {
+            ARG_ACTION => ACTION_CREATE_ASSET,
+            ARG_PURSE_NAME => ISSUER_PURSE,
+            ARG_AMOUNT => U512::from(INITIAL_SUPPLY),
+        },
+    )
+}
+
+fn transfer_request(
+    source_purse_name: &str,
+    target_purse_name: &str,
+    amount: U512,
+) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        MINT_ASSETS_CONTRACT,
+        runtime_args! {
+            ARG_ACTION => ACTION_TRANSFER,
+            ARG_SOURCE_PURSE_NAME => source_purse_name,
+            ARG_PURSE_NAME => target_purse_name,
+            ARG_AMOUNT => amount,
+        },
+    )
+}
+
+fn get_admin_purse(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> URef {
+    let admin = builder
+        .get_account(*DEFAULT_ADMIN_ACCOUNT_ADDR)
+        .expect("should have admin account");
+    admin
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .copied()
+        .expect("should have purse")
+}
+
+/// Creates an asset held in the admin's `ISSUER_PURSE`, and an empty `HOLDER_PURSE` of the same
+/// asset.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = super::private_chain_setup();
+
+    let create_asset_request = create_asset_request(*DEFAULT_ADMIN_ACCOUNT_ADDR).build();
+    builder.exec(create_asset_request).expect_success().commit();
+
+    let create_asset_purse_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        MINT_ASSETS_CONTRACT,
+        runtime_args! {
+            ARG_ACTION => ACTION_CREATE_ASSET_PURSE,
+            ARG_SOURCE_PURSE_NAME => ISSUER_PURSE,
+            ARG_PURSE_NAME => HOLDER_PURSE,
+        },
+    )
+    .build();
+    builder
+        .exec(create_asset_purse_request)
+        .expect_success()
+        .commit();
+
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_transfer_asset_between_purses() {
+    let mut builder = setup();
+
+    let issuer_purse = get_admin_purse(&builder, ISSUER_PURSE);
+    let holder_purse = get_admin_purse(&builder, HOLDER_PURSE);
+    assert_eq!(
+        builder.get_purse_balance(issuer_purse),
+        U512::from(INITIAL_SUPPLY)
+    );
+    assert_eq!(builder.get_purse_balance(holder_purse), U512::zero());
+
+    let amount = U512::from(1_000);
+    let transfer_request = transfer_request(ISSUER_PURSE, HOLDER_PURSE, amount).build();
+    builder.exec(transfer_request).expect_success().commit();
+
+    assert_eq!(
+        builder.get_purse_balance(issuer_purse),
+        U512::from(INITIAL_SUPPLY) - amount
+    );
+    assert_eq!(builder.get_purse_balance(holder_purse), amount);
+
+    let exec_result = builder
+        .get_last_exec_results()
+        .expect("should have results")
+        .pop()
+        .expect("should have result");
+    let transfer_addr = exec_result
+        .transfers()
+        .last()
+        .copied()
+        .expect("should have transfer");
+    let transfer = builder
+        .get_transfer(transfer_addr)
+        .expect("should have transfer record");
+    assert_eq!(transfer.source.addr(), issuer_purse.addr());
+    assert_eq!(transfer.amount, amount);
+    assert!(
+        transfer.asset_id.is_some(),
+        "transfer record should carry the asset id"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_asset_to_native_purse() {
+    let mut builder = setup();
+
+    let create_native_purse_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        TRANSFER_TO_NAMED_PURSE_CONTRACT,
+        runtime_args! {
+            ARG_PURSE_NAME => NATIVE_PURSE,
+            ARG_AMOUNT => U512::one(),
+        },
+    )
+    .build();
+    builder
+        .exec(create_native_purse_request)
+        .expect_success()
+        .commit();
+
+    let transfer_request = transfer_request(ISSUER_PURSE, NATIVE_PURSE, U512::one()).build();
+    builder.exec(transfer_request).expect_failure().commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(revert)) if revert == mint::Error::MismatchedAssets.into()
+        ),
+        "expected MismatchedAssets error, found {:?}",
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_change_purse_asset_by_overwriting_purse() {
+    let mut builder = setup();
+
+    let holder_purse = get_admin_purse(&builder, HOLDER_PURSE);
+    let overwrite_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        OVERWRITE_UREF_CONTENT_CONTRACT,
+        runtime_args! {
+            ARG_CONTRACT_UREF => holder_purse,
+        },
+    )
+    .build();
+    builder.exec(overwrite_request).expect_success().commit();
+
+    // The holder purse still holds the asset.
+    let amount = U512::from(1_000);
+    let transfer_request = transfer_request(ISSUER_PURSE, HOLDER_PURSE, amount).build();
+    builder.exec(transfer_request).expect_success().commit();
+    assert_eq!(builder.get_purse_balance(holder_purse), amount);
+
+    let create_native_purse_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ADMIN_ACCOUNT_ADDR,
+        TRANSFER_TO_NAMED_PURSE_CONTRACT,
+        runtime_args! {
+            ARG_PURSE_NAME => NATIVE_PURSE,
+            ARG_AMOUNT => U512::one(),
+        },
+    )
+    .build();
+    builder
+        .exec(create_native_purse_request)
+        .expect_success()
+        .commit();
+
+    let transfer_request = transfer_request(NATIVE_PURSE, HOLDER_PURSE, U512::one()).build();
+    builder.exec(transfer_request).expect_failure().commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(revert)) if revert == mint::Error::MismatchedAssets.into()
+        ),
+        "expected MismatchedAssets error, found {:?}",
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_non_administrator_session_to_create_asset() {
+    let mut builder = super::private_chain_setup();
+
+    let create_asset_request = create_asset_request(*ACCOUNT_1_ADDR).build();
+    builder.exec(create_asset_request).expect_failure().commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(revert)) if revert == mint::Error::InvalidAssetCreation.into()
+        ),
+        "expected InvalidAssetCreation error, found {:?}",
+        error
+    );
+}
@@ -1,7 +1,8 @@
 use casper_engine_test_support::{
-    ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder, UpgradeRequestBuilder,
-    DEFAULT_ACCOUNT_ADDR, DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION,
-    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST, TIMESTAMP_MILLIS_INCREMENT,
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, StepRequestBuilder,
+    UpgradeRequestBuilder, ARG_AMOUNT, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
+    DEFAULT_PROPOSER_ADDR, DEFAULT_PROTOCOL_VERSION, MINIMUM_ACCOUNT_CREATION_BALANCE,
+    PRODUCTION_RUN_GENESIS_REQUEST, TIMESTAMP_MILLIS_INCREMENT,
 };
 use casper_execution_engine::core::engine_state::{
     engine_config::FeeHandling, EngineConfigBuilder, RewardItem,
@@ -9,7 +10,7 @@ use casper_execution_engine::core::engine_state::{
 use casper_types::{
     runtime_args,
     system::{handle_payment::ACCUMULATION_PURSE_KEY, mint},
-    EraId, ProtocolVersion, RuntimeArgs, U512,
+    EraId, Motes, ProtocolVersion, RuntimeArgs, U512,
 };
 use once_cell::sync::Lazy;
 
@@ -196,6 +197,62 @@ fn should_accumulate_deploy_fees() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_pay_tip_above_base_fee_to_proposer() {
+    const BASE_FEE: u64 = 1;
+    const GAS_PRICE: u64 = 3;
+
+    let mut builder = super::private_chain_setup();
+
+    let handle_payment_hash = builder.get_handle_payment_contract_hash();
+    let handle_payment_contract = builder
+        .get_contract(handle_payment_hash)
+        .expect("should have handle payment contract");
+    let rewards_purse = handle_payment_contract.named_keys()[ACCUMULATION_PURSE_KEY]
+        .into_uref()
+        .expect("should be uref");
+    let rewards_balance_before = builder.get_purse_balance(rewards_purse);
+
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ADMIN_ACCOUNT_ADDR)
+        .with_session_bytes(wasm_utils::do_minimum_bytes(), RuntimeArgs::default())
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_authorization_keys(&[*DEFAULT_ADMIN_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .with_gas_price(GAS_PRICE)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy_item)
+        .with_base_fee(BASE_FEE)
+        .build();
+
+    let exec_request_proposer = exec_request.proposer.clone();
+
+    builder.exec(exec_request).expect_success().commit();
+
+    let gas_cost = builder.last_exec_gas_cost();
+    let expected_tip = Motes::from_gas(gas_cost, GAS_PRICE - BASE_FEE).expect("should convert");
+    let expected_base = Motes::from_gas(gas_cost, BASE_FEE).expect("should convert");
+
+    let proposer_account = builder
+        .get_account(exec_request_proposer.to_account_hash())
+        .expect("should have proposer account");
+    assert_eq!(
+        builder.get_purse_balance(proposer_account.main_purse()),
+        expected_tip.value(),
+        "proposer should receive the tip"
+    );
+
+    let rewards_balance_after = builder.get_purse_balance(rewards_purse);
+    assert!(
+        rewards_balance_after >= rewards_balance_before + expected_base.value(),
+        "rewards purse should accumulate at least the base fee"
+    );
+}
+
 #[ignore]
 #[test]
 fn should_distribute_accumulated_fees_to_admins() {
@@ -2069,6 +2069,104 @@ fn should_handle_evictions() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_evict_validators_after_consecutive_inactive_eras() {
+    const INACTIVE_ERAS_BEFORE_EVICTION: u64 = 3;
+    const ARG_VALIDATOR_PUBLIC_KEY: &str = "validator_public_key";
+
+    let step = |builder: &mut InMemoryWasmTestBuilder, inactive_validators: Vec<PublicKey>| {
+        let step_request = StepRequestBuilder::new()
+            .with_parent_state_hash(builder.get_post_state_hash())
+            .with_protocol_version(DEFAULT_PROTOCOL_VERSION)
+            .with_next_era_id(builder.get_era().successor())
+            .with_inactive_validators(inactive_validators)
+            .with_run_auction(true)
+            .build();
+        builder
+            .step(step_request)
+            .expect("must execute step request");
+    };
+
+    let is_inactive = |builder: &mut InMemoryWasmTestBuilder, validator_public_key: &PublicKey| {
+        let bids: Bids = builder.get_bids();
+        bids.get(validator_public_key)
+            .expect("should have bid")
+            .inactive()
+    };
+
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account_1 = GenesisAccount::account(
+            ACCOUNT_1_PK.clone(),
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(ACCOUNT_1_BOND.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        let account_2 = GenesisAccount::account(
+            BID_ACCOUNT_1_PK.clone(),
+            Motes::new(BID_ACCOUNT_1_BALANCE.into()),
+            Some(GenesisValidator::new(
+                Motes::new(300_000.into()),
+                DelegationRate::zero(),
+            )),
+        );
+        tmp.push(account_1);
+        tmp.push(account_2);
+        tmp
+    };
+
+    let engine_config = EngineConfigBuilder::new()
+        .with_inactive_eras_before_eviction(INACTIVE_ERAS_BEFORE_EVICTION)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    // Two consecutive inactive eras are tolerated
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    assert!(!is_inactive(&mut builder, &BID_ACCOUNT_1_PK));
+
+    // An active era resets the count
+    step(&mut builder, Vec::new());
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    assert!(!is_inactive(&mut builder, &BID_ACCOUNT_1_PK));
+
+    // The third consecutive inactive era evicts the validator
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    assert!(is_inactive(&mut builder, &BID_ACCOUNT_1_PK));
+    assert!(!is_inactive(&mut builder, &ACCOUNT_1_PK));
+
+    let era_validators: EraValidators = builder.get_era_validators();
+    let (_, latest_validators) = era_validators
+        .iter()
+        .next_back()
+        .expect("should have validators");
+    assert!(!latest_validators.contains_key(&BID_ACCOUNT_1_PK));
+    assert!(latest_validators.contains_key(&ACCOUNT_1_PK));
+
+    // The evicted validator can rejoin using `activate_bid`
+    let activate_bid_request = ExecuteRequestBuilder::standard(
+        *BID_ACCOUNT_1_ADDR,
+        CONTRACT_ACTIVATE_BID,
+        runtime_args! {
+            ARG_VALIDATOR_PUBLIC_KEY => BID_ACCOUNT_1_PK.clone(),
+        },
+    )
+    .build();
+    builder.exec(activate_bid_request).commit().expect_success();
+    assert!(!is_inactive(&mut builder, &BID_ACCOUNT_1_PK));
+
+    // Its inactivity count starts over after reactivation
+    step(&mut builder, vec![BID_ACCOUNT_1_PK.clone()]);
+    assert!(!is_inactive(&mut builder, &BID_ACCOUNT_1_PK));
+}
+
 #[should_panic(expected = "OrphanedDelegator")]
 #[ignore]
 #[test]
@@ -0,0 +1,396 @@
+use std::collections::BTreeMap;
+
+use num_rational::Ratio;
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use casper_engine_test_support::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNTS,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_ACCOUNT_PUBLIC_KEY,
+    DEFAULT_PROTOCOL_VERSION,
+};
+use casper_execution_engine::{
+    core::{
+        engine_state::{
+            genesis::{GenesisAccount, GenesisValidator},
+            Error,
+        },
+        execution,
+    },
+    shared::newtypes::CorrelationId,
+};
+use casper_types::{
+    account::AccountHash,
+    runtime_args,
+    system::auction::{
+        self, DelegationRate, ProposalStatus, Proposals, ARG_ACTIVATION_ERA, ARG_APPROVE,
+        ARG_PROPOSAL_ID, ARG_PROPOSER, ARG_VOTER, VALIDATOR_SLOTS_KEY,
+    },
+    EraId, Motes, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, StoredValue,
+};
+
+const CONTRACT_PROPOSE_PARAMETER_CHANGES: &str = "propose_parameter_changes.wasm";
+const CONTRACT_VOTE_ON_PROPOSAL: &str = "vote_on_proposal.wasm";
+
+const ARG_VALIDATOR_SLOTS: &str = "validator_slots";
+const ARG_AUCTION_DELAY: &str = "auction_delay";
+const ARG_LOCKED_FUNDS_PERIOD_MILLIS: &str = "locked_funds_period_millis";
+const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
+const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
+const ARG_BLOCK_GAS_LIMIT: &str = "block_gas_limit";
+const ARG_OPCODE_COSTS: &str = "opcode_costs";
+
+const VALIDATOR_1_STAKE: u64 = 600_000_000_000;
+const VALIDATOR_2_STAKE: u64 = 300_000_000_000;
+const VALIDATOR_3_STAKE: u64 = 100_000_000_000;
+
+const NEW_VALIDATOR_SLOTS: u32 = 42;
+const NEW_BLOCK_GAS_LIMIT: u64 = 1_000_000_000;
+const NEW_ADD_COST: u32 = 7;
+const ACTIVATION_ERA: EraId = EraId::new(2);
+const FIRST_PROPOSAL_ID: u64 = 0;
+
+static VALIDATOR_1: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([3; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_2: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([5; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+static VALIDATOR_3: Lazy<PublicKey> = Lazy::new(|| {
+    let secret_key = SecretKey::ed25519_from_bytes([7; SecretKey::ED25519_LENGTH]).unwrap();
+    PublicKey::from(&secret_key)
+});
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for (validator, stake) in [
+            (&*VALIDATOR_1, VALIDATOR_1_STAKE),
+            (&*VALIDATOR_2, VALIDATOR_2_STAKE),
+            (&*VALIDATOR_3, VALIDATOR_3_STAKE),
+        ] {
+            tmp.push(GenesisAccount::account(
+                validator.clone(),
+                Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into()),
+                Some(GenesisValidator::new(
+                    Motes::new(stake.into()),
+                    DelegationRate::zero(),
+                )),
+            ));
+        }
+        tmp
+    };
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+fn propose_request(proposer: &PublicKey, activation_era: EraId) -> ExecuteRequestBuilder {
+    propose_node_parameters_request(proposer, activation_era, None, BTreeMap::new())
+}
+
+fn propose_node_parameters_request(
+    proposer: &PublicKey,
+    activation_era: EraId,
+    block_gas_limit: Option<u64>,
+    opcode_costs: BTreeMap<String, u32>,
+) -> ExecuteRequestBuilder {
+    let validator_slots = if block_gas_limit.is_none() && opcode_costs.is_empty() {
+        Some(NEW_VALIDATOR_SLOTS)
+    } else {
+        None
+    };
+    ExecuteRequestBuilder::standard(
+        AccountHash::from(proposer),
+        CONTRACT_PROPOSE_PARAMETER_CHANGES,
+        runtime_args! {
+            ARG_PROPOSER => proposer.clone(),
+            ARG_ACTIVATION_ERA => activation_era,
+            ARG_VALIDATOR_SLOTS => validator_slots,
+            ARG_AUCTION_DELAY => Option::<u64>::None,
+            ARG_LOCKED_FUNDS_PERIOD_MILLIS => Option::<u64>::None,
+            ARG_ROUND_SEIGNIORAGE_RATE => Option::<Ratio<u64>>::None,
+            ARG_UNBONDING_DELAY => Option::<u64>::None,
+            ARG_BLOCK_GAS_LIMIT => block_gas_limit,
+            ARG_OPCODE_COSTS => opcode_costs,
+        },
+    )
+}
+
+fn vote_request(voter: &PublicKey, proposal_id: u64, approve: bool) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::standard(
+        AccountHash::from(voter),
+        CONTRACT_VOTE_ON_PROPOSAL,
+        runtime_args! {
+            ARG_VOTER => voter.clone(),
+            ARG_PROPOSAL_ID => proposal_id,
+            ARG_APPROVE => approve,
+        },
+    )
+}
+
+fn get_proposals(builder: &InMemoryWasmTestBuilder) -> Proposals {
+    match builder.query(None, auction::governance_proposals_key(), &[]) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be proposals"),
+        other => panic!("expected proposals, found {:?}", other),
+    }
+}
+
+/// Runs a protocol upgrade which, like the node's upgrades, sets the validator slots from the
+/// chainspec.
+fn upgrade(builder: &mut InMemoryWasmTestBuilder, activation_point: EraId, validator_slots: u32) {
+    let new_protocol_version = ProtocolVersion::from_parts(
+        DEFAULT_PROTOCOL_VERSION.value().major,
+        DEFAULT_PROTOCOL_VERSION.value().minor,
+        DEFAULT_PROTOCOL_VERSION.value().patch + 1,
+    );
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(activation_point)
+        .with_new_validator_slots(validator_slots)
+        .build();
+    builder
+        .upgrade_with_upgrade_request_and_config(None, &mut upgrade_request)
+        .expect_upgrade_success();
+}
+
+fn get_validator_slots(builder: &mut InMemoryWasmTestBuilder) -> u32 {
+    let auction_hash = builder.get_auction_contract_hash();
+    builder.get_value(auction_hash, VALIDATOR_SLOTS_KEY)
+}
+
+fn assert_auction_error(builder: &InMemoryWasmTestBuilder, expected: auction::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            &error,
+            Error::Exec(execution::Error::Revert(revert)) if *revert == expected.into()
+        ),
+        "expected {:?} error, found {:?}",
+        expected,
+        error
+    );
+}
+
+fn proposal_status(builder: &InMemoryWasmTestBuilder, proposal_id: u64) -> Option<ProposalStatus> {
+    get_proposals(builder)
+        .get(&proposal_id)
+        .map(|proposal| proposal.status())
+}
+
+#[ignore]
+#[test]
+fn should_apply_approved_proposal_at_upgrade() {
+    let mut builder = setup();
+    let initial_validator_slots = get_validator_slots(&mut builder);
+
+    builder
+        .exec(propose_request(&VALIDATOR_1, ACTIVATION_ERA).build())
+        .expect_success()
+        .commit();
+    builder
+        .exec(vote_request(&VALIDATOR_2, FIRST_PROPOSAL_ID, true).build())
+        .expect_success()
+        .commit();
+
+    builder.advance_era(Vec::new(), Vec::new());
+    assert_eq!(
+        proposal_status(&builder, FIRST_PROPOSAL_ID),
+        Some(ProposalStatus::Pending)
+    );
+
+    // Approved proposals are only applied by protocol upgrades.
+    builder.advance_era(Vec::new(), Vec::new());
+    assert_eq!(builder.get_era(), ACTIVATION_ERA);
+    assert_eq!(
+        proposal_status(&builder, FIRST_PROPOSAL_ID),
+        Some(ProposalStatus::Approved)
+    );
+    assert_eq!(get_validator_slots(&mut builder), initial_validator_slots);
+
+    // An upgrade activating before the proposal's activation era doesn't apply it.
+    upgrade(
+        &mut builder,
+        ACTIVATION_ERA.predecessor().unwrap(),
+        initial_validator_slots,
+    );
+    assert_eq!(
+        proposal_status(&builder, FIRST_PROPOSAL_ID),
+        Some(ProposalStatus::Approved)
+    );
+    assert_eq!(get_validator_slots(&mut builder), initial_validator_slots);
+
+    // Applied proposals are pruned.
+    upgrade(&mut builder, ACTIVATION_ERA, initial_validator_slots);
+    assert_eq!(proposal_status(&builder, FIRST_PROPOSAL_ID), None);
+    assert_eq!(get_validator_slots(&mut builder), NEW_VALIDATOR_SLOTS);
+
+    // Later upgrades don't revert the change to the chainspec value.
+    upgrade(
+        &mut builder,
+        ACTIVATION_ERA.successor(),
+        initial_validator_slots,
+    );
+    assert_eq!(get_validator_slots(&mut builder), NEW_VALIDATOR_SLOTS);
+}
+
+#[ignore]
+#[test]
+fn should_record_node_parameter_changes_at_upgrade() {
+    let mut builder = setup();
+    let initial_validator_slots = get_validator_slots(&mut builder);
+
+    let opcode_costs: BTreeMap<String, u32> =
+        [("add".to_string(), NEW_ADD_COST)].into_iter().collect();
+    builder
+        .exec(
+            propose_node_parameters_request(
+                &VALIDATOR_1,
+                ACTIVATION_ERA,
+                Some(NEW_BLOCK_GAS_LIMIT),
+                opcode_costs.clone(),
+            )
+            .build(),
+        )
+        .expect_success()
+        .commit();
+    builder
+        .exec(vote_request(&VALIDATOR_2, FIRST_PROPOSAL_ID, true).build())
+        .expect_success()
+        .commit();
+    builder.advance_eras_by(2, Vec::new(), Vec::new());
+    upgrade(&mut builder, ACTIVATION_ERA, initial_validator_slots);
+
+    let overrides = builder
+        .get_engine_state()
+        .get_parameter_overrides(CorrelationId::new(), builder.get_post_state_hash())
+        .expect("should read parameter overrides");
+    assert_eq!(overrides.block_gas_limit, Some(NEW_BLOCK_GAS_LIMIT));
+    assert_eq!(overrides.opcode_costs, opcode_costs);
+    assert_eq!(overrides.validator_slots, None);
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_proposal_with_unknown_opcode_cost() {
+    let mut builder = setup();
+
+    let opcode_costs: BTreeMap<String, u32> = [("call".to_string(), 1)].into_iter().collect();
+    builder
+        .exec(
+            propose_node_parameters_request(&VALIDATOR_1, ACTIVATION_ERA, None, opcode_costs)
+                .build(),
+        )
+        .expect_failure()
+        .commit();
+    assert_auction_error(&builder, auction::Error::InvalidProposal);
+}
+
+#[ignore]
+#[test]
+fn should_reject_proposal_without_supermajority() {
+    let mut builder = setup();
+    let initial_validator_slots = get_validator_slots(&mut builder);
+
+    builder
+        .exec(propose_request(&VALIDATOR_1, ACTIVATION_ERA).build())
+        .expect_success()
+        .commit();
+    builder
+        .exec(vote_request(&VALIDATOR_2, FIRST_PROPOSAL_ID, false).build())
+        .expect_success()
+        .commit();
+    builder
+        .exec(vote_request(&VALIDATOR_3, FIRST_PROPOSAL_ID, true).build())
+        .expect_success()
+        .commit();
+
+    builder.advance_eras_by(2, Vec::new(), Vec::new());
+    assert_eq!(
+        proposal_status(&builder, FIRST_PROPOSAL_ID),
+        Some(ProposalStatus::Rejected)
+    );
+    assert_eq!(get_validator_slots(&mut builder), initial_validator_slots);
+
+    // Rejected proposals are pruned by the next upgrade, but their ids aren't reused.
+    let current_era = builder.get_era();
+    upgrade(&mut builder, current_era, initial_validator_slots);
+    assert_eq!(proposal_status(&builder, FIRST_PROPOSAL_ID), None);
+    assert_eq!(get_validator_slots(&mut builder), initial_validator_slots);
+
+    builder
+        .exec(propose_request(&VALIDATOR_1, current_era.successor()).build())
+        .expect_success()
+        .commit();
+    assert_eq!(
+        proposal_status(&builder, FIRST_PROPOSAL_ID + 1),
+        Some(ProposalStatus::Pending)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_non_validators_to_propose() {
+    let mut builder = setup();
+
+    let propose_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PROPOSE_PARAMETER_CHANGES,
+        runtime_args! {
+            ARG_PROPOSER => DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+            ARG_ACTIVATION_ERA => ACTIVATION_ERA,
+            ARG_VALIDATOR_SLOTS => Some(NEW_VALIDATOR_SLOTS),
+            ARG_AUCTION_DELAY => Option::<u64>::None,
+            ARG_LOCKED_FUNDS_PERIOD_MILLIS => Option::<u64>::None,
+            ARG_ROUND_SEIGNIORAGE_RATE => Option::<Ratio<u64>>::None,
+            ARG_UNBONDING_DELAY => Option::<u64>::None,
+            ARG_BLOCK_GAS_LIMIT => Option::<u64>::None,
+            ARG_OPCODE_COSTS => BTreeMap::<String, u32>::new(),
+        },
+    )
+    .build();
+    builder.exec(propose_request).expect_failure().commit();
+    assert_auction_error(&builder, auction::Error::NotAnEraValidator);
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_proposal_activating_in_current_era() {
+    let mut builder = setup();
+    let current_era = builder.get_era();
+
+    builder
+        .exec(propose_request(&VALIDATOR_1, current_era).build())
+        .expect_failure()
+        .commit();
+    assert_auction_error(&builder, auction::Error::InvalidProposal);
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_votes_after_voting_ended() {
+    let mut builder = setup();
+
+    builder
+        .exec(propose_request(&VALIDATOR_1, ACTIVATION_ERA).build())
+        .expect_success()
+        .commit();
+    builder.advance_eras_by(2, Vec::new(), Vec::new());
+
+    builder
+        .exec(vote_request(&VALIDATOR_2, FIRST_PROPOSAL_ID, true).build())
+        .expect_failure()
+        .commit();
+    assert_auction_error(&builder, auction::Error::ProposalClosed);
+
+    builder
+        .exec(vote_request(&VALIDATOR_2, FIRST_PROPOSAL_ID + 1, true).build())
+        .expect_failure()
+        .commit();
+    assert_auction_error(&builder, auction::Error::ProposalNotFound);
+}
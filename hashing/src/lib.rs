@@ -357,7 +357,7 @@ impl<'de> Deserialize<'de> for Digest {
             let bytes =
                 checksummed_hex::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
             let data =
-                <[u8; Digest::LENGTH]>::try_from(bytes.as_ref()).map_err(SerdeError::custom)?;
+                <[u8; Digest::LENGTH]>::try_from(bytes.as_slice()).map_err(SerdeError::custom)?;
             Ok(Digest::from(data))
         } else {
             let data = <Vec<u8>>::deserialize(deserializer)?;
@@ -21,7 +21,7 @@ use toml::{value::Table, Value};
 use tracing::info;
 
 use crate::{
-    components::network::Identity as NetworkIdentity,
+    components::{consensus::slashing_protection, network::Identity as NetworkIdentity},
     logging,
     reactor::{main_reactor, Runner},
     setup_signal_hooks,
@@ -78,6 +78,26 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Export the validator's slashing protection database, e.g. to move the validator to a
+    /// different machine.
+    ExportSlashingProtection {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path of the interchange file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Import slashing protection data exported from a different machine into the validator's
+    /// slashing protection database.
+    ///
+    /// The node must not be running.
+    ImportSlashingProtection {
+        /// Path to configuration file.
+        config: PathBuf,
+        /// Path of the interchange file to read.
+        #[structopt(long)]
+        input: PathBuf,
+    },
 }
 
 #[derive(Debug)]
@@ -237,6 +257,27 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ExportSlashingProtection { config, output } => {
+                let (root, config) = Self::init(&config, vec![])?.into_parts();
+                let public_key = config.consensus.load_signer(&root)?.public_key().clone();
+                slashing_protection::export_to_file(
+                    &root.join(&config.consensus.slashing_protection_path),
+                    public_key,
+                    &output,
+                )?;
+                info!(output = %output.display(), "exported slashing protection data");
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ImportSlashingProtection { config, input } => {
+                let (root, config) = Self::init(&config, vec![])?.into_parts();
+                let public_key = config.consensus.load_signer(&root)?.public_key().clone();
+                slashing_protection::import_from_file(
+                    &root.join(&config.consensus.slashing_protection_path),
+                    public_key,
+                    &input,
+                )?;
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
@@ -37,6 +37,7 @@ use crate::{
     components::{
         fetcher::{
             EmptyValidationMetadata, Error as FetcherError, FetchItem, FetchResult, FetchedData,
+            PeerScores, Tag,
         },
         Component, ComponentState, InitializedComponent, ValidatorBoundComponent,
     },
@@ -211,6 +212,9 @@ pub(crate) struct BlockSynchronizer {
     historical: Option<BlockBuilder>,
     // deals with global state acquisition for historical blocks
     global_sync: GlobalStateSynchronizer,
+    // shared with the fetchers, used to prefer well performing peers
+    #[data_size(skip)]
+    peer_scores: PeerScores,
     #[data_size(skip)]
     metrics: Metrics,
 }
@@ -221,6 +225,7 @@ impl BlockSynchronizer {
         chainspec: Arc<Chainspec>,
         max_simultaneous_peers: u8,
         validator_matrix: ValidatorMatrix,
+        peer_scores: PeerScores,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(BlockSynchronizer {
@@ -231,7 +236,11 @@ impl BlockSynchronizer {
             validator_matrix,
             forward: None,
             historical: None,
-            global_sync: GlobalStateSynchronizer::new(config.max_parallel_trie_fetches as usize),
+            global_sync: GlobalStateSynchronizer::new(
+                config.max_parallel_trie_fetches as usize,
+                peer_scores.clone(),
+            ),
+            peer_scores,
             metrics: Metrics::new(registry)?,
         })
     }
@@ -296,6 +305,7 @@ impl BlockSynchronizer {
             should_fetch_execution_state,
             self.max_simultaneous_peers,
             self.config.peer_refresh_interval,
+            self.peer_scores.clone(),
             self.chainspec.core_config.legacy_required_finality,
             self.chainspec
                 .core_config
@@ -344,6 +354,7 @@ impl BlockSynchronizer {
                     should_fetch_execution_state,
                     self.max_simultaneous_peers,
                     self.config.peer_refresh_interval,
+                    self.peer_scores.clone(),
                     self.chainspec.core_config.legacy_required_finality,
                     self.chainspec
                         .core_config
@@ -649,9 +660,10 @@ impl BlockSynchronizer {
                             .event(Event::ApprovalsHashesFetched)
                     }))
                 }
-                NeedNext::DeployByHash(block_hash, deploy_hash) => {
-                    builder.latch_by(peers.len());
-                    results.extend(peers.into_iter().flat_map(|node_id| {
+                NeedNext::DeploysByHash(block_hash, deploy_hashes) => {
+                    let requests = spread_requests(deploy_hashes, peers);
+                    builder.latch_by(requests.len());
+                    results.extend(requests.into_iter().flat_map(|(deploy_hash, node_id)| {
                         effect_builder
                             .fetch::<LegacyDeploy>(
                                 deploy_hash,
@@ -664,9 +676,10 @@ impl BlockSynchronizer {
                             })
                     }))
                 }
-                NeedNext::DeployById(block_hash, deploy_id) => {
-                    builder.latch_by(peers.len());
-                    results.extend(peers.into_iter().flat_map(|node_id| {
+                NeedNext::DeploysById(block_hash, deploy_ids) => {
+                    let requests = spread_requests(deploy_ids, peers);
+                    builder.latch_by(requests.len());
+                    results.extend(requests.into_iter().flat_map(|(deploy_id, node_id)| {
                         effect_builder
                             .fetch::<Deploy>(deploy_id, node_id, Box::new(EmptyValidationMetadata))
                             .event(move |result| Event::DeployFetched {
@@ -1486,6 +1499,7 @@ impl<REv: ReactorEvent> Component<REv> for BlockSynchronizer {
                             let peers = self.historical.as_ref().map_or_else(Vec::new, |builder| {
                                 builder.peer_list().qualified_peers_up_to(
                                     rng,
+                                    Tag::TrieOrChunk,
                                     self.config.max_parallel_trie_fetches as usize,
                                 )
                             });
@@ -1663,3 +1677,22 @@ impl<REv: ReactorEvent> ValidatorBoundComponent<REv> for BlockSynchronizer {
         self.need_next(effect_builder, rng)
     }
 }
+
+/// Pairs the items to fetch with the peers to fetch them from, so that the requests are spread
+/// across all the given peers.
+///
+/// Every item is requested from at least one peer and, if there are more peers than items, every
+/// peer is asked for at least one item.  Returns no requests if there are no peers.
+fn spread_requests<T: Clone>(items: Vec<T>, peers: Vec<NodeId>) -> Vec<(T, NodeId)> {
+    if items.is_empty() || peers.is_empty() {
+        return vec![];
+    }
+    (0..items.len().max(peers.len()))
+        .map(|index| {
+            (
+                items[index % items.len()].clone(),
+                peers[index % peers.len()],
+            )
+        })
+        .collect()
+}
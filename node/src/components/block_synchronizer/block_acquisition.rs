@@ -417,7 +417,7 @@ impl BlockAcquisitionState {
                         rng,
                         validator_weights,
                         signatures,
-                        deploys.needs_deploy(),
+                        deploys.needs_deploys(max_simultaneous_peers as usize),
                         is_historical,
                         max_simultaneous_peers,
                     ))
@@ -433,7 +433,7 @@ impl BlockAcquisitionState {
                     rng,
                     validator_weights,
                     signatures,
-                    deploys.needs_deploy(),
+                    deploys.needs_deploys(max_simultaneous_peers as usize),
                     is_historical,
                     max_simultaneous_peers,
                 ))
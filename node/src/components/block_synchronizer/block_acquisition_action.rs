@@ -5,10 +5,13 @@ use casper_hashing::Digest;
 use casper_types::{EraId, PublicKey};
 
 use crate::{
-    components::block_synchronizer::{
-        deploy_acquisition::DeployIdentifier, need_next::NeedNext, peer_list::PeerList,
-        signature_acquisition::SignatureAcquisition, BlockAcquisitionError,
-        ExecutionResultsAcquisition, ExecutionResultsChecksum,
+    components::{
+        block_synchronizer::{
+            deploy_acquisition::NeededDeploys, need_next::NeedNext, peer_list::PeerList,
+            signature_acquisition::SignatureAcquisition, BlockAcquisitionError,
+            ExecutionResultsAcquisition, ExecutionResultsChecksum,
+        },
+        fetcher::Tag,
     },
     types::{
         Block, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader, Deploy, DeployHash,
@@ -76,7 +79,7 @@ impl BlockAcquisitionAction {
         next: BlockExecutionResultsOrChunkId,
         checksum: ExecutionResultsChecksum,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::BlockExecutionResults);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::ExecutionResults(block_hash, next, checksum),
@@ -84,36 +87,36 @@ impl BlockAcquisitionAction {
     }
 
     pub(super) fn approvals_hashes(block: &Block, peer_list: &PeerList, rng: &mut NodeRng) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::ApprovalsHashes);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::ApprovalsHashes(*block.hash(), Box::new(block.clone())),
         }
     }
 
-    pub(super) fn deploy_by_hash(
+    pub(super) fn deploys_by_hash(
         block_hash: BlockHash,
-        deploy_hash: DeployHash,
+        deploy_hashes: Vec<DeployHash>,
         peer_list: &PeerList,
         rng: &mut NodeRng,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::LegacyDeploy);
         BlockAcquisitionAction {
             peers_to_ask,
-            need_next: NeedNext::DeployByHash(block_hash, deploy_hash),
+            need_next: NeedNext::DeploysByHash(block_hash, deploy_hashes),
         }
     }
 
-    pub(super) fn deploy_by_id(
+    pub(super) fn deploys_by_id(
         block_hash: BlockHash,
-        deploy_id: DeployId,
+        deploy_ids: Vec<DeployId>,
         peer_list: &PeerList,
         rng: &mut NodeRng,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::Deploy);
         BlockAcquisitionAction {
             peers_to_ask,
-            need_next: NeedNext::DeployById(block_hash, deploy_id),
+            need_next: NeedNext::DeploysById(block_hash, deploy_ids),
         }
     }
 
@@ -123,7 +126,7 @@ impl BlockAcquisitionAction {
         block_hash: BlockHash,
         root_hash: Digest,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::TrieOrChunk);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::GlobalState(block_hash, root_hash),
@@ -136,7 +139,7 @@ impl BlockAcquisitionAction {
         block_header: &BlockHeader,
         missing_signatures: Vec<PublicKey>,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::FinalitySignature);
         let era_id = block_header.era_id();
         let block_hash = block_header.block_hash();
 
@@ -157,7 +160,7 @@ impl BlockAcquisitionAction {
         rng: &mut NodeRng,
         block_hash: BlockHash,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::Block);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::BlockBody(block_hash),
@@ -210,7 +213,7 @@ impl BlockAcquisitionAction {
         rng: &mut NodeRng,
         block_hash: BlockHash,
     ) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::BlockHeader);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::BlockHeader(block_hash),
@@ -218,7 +221,7 @@ impl BlockAcquisitionAction {
     }
 
     pub(super) fn era_validators(peer_list: &PeerList, rng: &mut NodeRng, era_id: EraId) -> Self {
-        let peers_to_ask = peer_list.qualified_peers(rng);
+        let peers_to_ask = peer_list.qualified_peers(rng, Tag::SyncLeap);
         BlockAcquisitionAction {
             peers_to_ask,
             need_next: NeedNext::EraValidators(era_id),
@@ -271,30 +274,36 @@ impl BlockAcquisitionAction {
         rng: &mut NodeRng,
         validator_weights: &EraValidatorWeights,
         signatures: &mut SignatureAcquisition,
-        needs_deploy: Option<DeployIdentifier>,
+        needs_deploys: NeededDeploys,
         is_historical: bool,
         max_simultaneous_peers: u8,
     ) -> Self {
-        match needs_deploy {
-            Some(DeployIdentifier::ById(deploy_id)) => {
-                debug!("BlockAcquisition: requesting missing deploy by ID");
-                BlockAcquisitionAction::deploy_by_id(
+        match needs_deploys {
+            NeededDeploys::ById(deploy_ids) if !deploy_ids.is_empty() => {
+                debug!(
+                    count = deploy_ids.len(),
+                    "BlockAcquisition: requesting missing deploys by ID"
+                );
+                BlockAcquisitionAction::deploys_by_id(
                     block_header.block_hash(),
-                    deploy_id,
+                    deploy_ids,
                     peer_list,
                     rng,
                 )
             }
-            Some(DeployIdentifier::ByHash(deploy_hash)) => {
-                debug!("BlockAcquisition: requesting missing deploy by hash");
-                BlockAcquisitionAction::deploy_by_hash(
+            NeededDeploys::ByHash(deploy_hashes) if !deploy_hashes.is_empty() => {
+                debug!(
+                    count = deploy_hashes.len(),
+                    "BlockAcquisition: requesting missing deploys by hash"
+                );
+                BlockAcquisitionAction::deploys_by_hash(
                     block_header.block_hash(),
-                    deploy_hash,
+                    deploy_hashes,
                     peer_list,
                     rng,
                 )
             }
-            None => {
+            NeededDeploys::ById(_) | NeededDeploys::ByHash(_) => {
                 if signatures.has_sufficient_finality(is_historical, true) {
                     BlockAcquisitionAction::switch_to_have_strict_finality(block_header)
                 } else {
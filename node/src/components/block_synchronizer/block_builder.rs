@@ -23,7 +23,7 @@ use super::{
     BlockAcquisitionError,
 };
 use crate::{
-    components::block_synchronizer::block_builder::latch::Latch,
+    components::{block_synchronizer::block_builder::latch::Latch, fetcher::PeerScores},
     types::{
         chainspec::LegacyRequiredFinality, ApprovalsHashes, Block, BlockExecutionResultsOrChunk,
         BlockHash, BlockHeader, BlockSignatures, Deploy, DeployHash, DeployId, EraValidatorWeights,
@@ -110,6 +110,7 @@ impl BlockBuilder {
         should_fetch_execution_state: bool,
         max_simultaneous_peers: u8,
         peer_refresh_interval: TimeDiff,
+        peer_scores: PeerScores,
         legacy_required_finality: LegacyRequiredFinality,
         strict_finality_protocol_version: ProtocolVersion,
     ) -> Self {
@@ -121,7 +122,7 @@ impl BlockBuilder {
                 block_hash,
                 SignatureAcquisition::new(vec![], legacy_required_finality),
             ),
-            peer_list: PeerList::new(max_simultaneous_peers, peer_refresh_interval),
+            peer_list: PeerList::new(max_simultaneous_peers, peer_refresh_interval, peer_scores),
             should_fetch_execution_state,
             strict_finality_protocol_version,
             sync_start: Instant::now(),
@@ -140,6 +141,7 @@ impl BlockBuilder {
         should_fetch_execution_state: bool,
         max_simultaneous_peers: u8,
         peer_refresh_interval: TimeDiff,
+        peer_scores: PeerScores,
         legacy_required_finality: LegacyRequiredFinality,
         strict_finality_protocol_version: ProtocolVersion,
     ) -> Self {
@@ -159,7 +161,8 @@ impl BlockBuilder {
             Box::new(block_header.clone()),
             signature_acquisition,
         );
-        let mut peer_list = PeerList::new(max_simultaneous_peers, peer_refresh_interval);
+        let mut peer_list =
+            PeerList::new(max_simultaneous_peers, peer_refresh_interval, peer_scores);
         peers.iter().for_each(|p| peer_list.register_peer(*p));
 
         BlockBuilder {
@@ -3,7 +3,10 @@ use std::{collections::BTreeMap, thread, time::Duration};
 use casper_types::testing::TestRng;
 use num_rational::Ratio;
 
-use crate::components::consensus::tests::utils::{ALICE_PUBLIC_KEY, ALICE_SECRET_KEY};
+use crate::components::{
+    consensus::tests::utils::{ALICE_PUBLIC_KEY, ALICE_SECRET_KEY},
+    fetcher::Tag,
+};
 
 use super::*;
 
@@ -16,6 +19,7 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -27,31 +31,46 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
     assert!(builder
         .handle_acceptance(None, Ok(Some(Acceptance::NeededIt)), true)
         .is_ok());
-    assert!(builder.peer_list().qualified_peers(&mut rng).is_empty());
+    assert!(builder
+        .peer_list()
+        .qualified_peers(&mut rng, Tag::Block)
+        .is_empty());
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for existent signature from ourselves.
     assert!(builder
         .handle_acceptance(None, Ok(Some(Acceptance::HadIt)), true)
         .is_ok());
-    assert!(builder.peer_list().qualified_peers(&mut rng).is_empty());
+    assert!(builder
+        .peer_list()
+        .qualified_peers(&mut rng, Tag::Block)
+        .is_empty());
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for no signature from ourselves.
     assert!(builder.handle_acceptance(None, Ok(None), true).is_ok());
-    assert!(builder.peer_list().qualified_peers(&mut rng).is_empty());
+    assert!(builder
+        .peer_list()
+        .qualified_peers(&mut rng, Tag::Block)
+        .is_empty());
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for no signature from a peer.
     // Peer shouldn't be registered.
     assert!(builder
         .handle_acceptance(Some(honest_peer), Ok(None), true)
         .is_ok());
-    assert!(builder.peer_list().qualified_peers(&mut rng).is_empty());
+    assert!(builder
+        .peer_list()
+        .qualified_peers(&mut rng, Tag::Block)
+        .is_empty());
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for existent signature from a peer.
     // Peer shouldn't be registered.
     assert!(builder
         .handle_acceptance(Some(honest_peer), Ok(Some(Acceptance::HadIt)), true)
         .is_ok());
-    assert!(builder.peer_list().qualified_peers(&mut rng).is_empty());
+    assert!(builder
+        .peer_list()
+        .qualified_peers(&mut rng, Tag::Block)
+        .is_empty());
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for needed signature from a peer.
     // Peer should be registered as honest.
@@ -60,7 +79,7 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
         .is_ok());
     assert!(builder
         .peer_list()
-        .qualified_peers(&mut rng)
+        .qualified_peers(&mut rng, Tag::Block)
         .contains(&honest_peer));
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for error on signature handling from ourselves.
@@ -73,7 +92,7 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
         .is_err());
     assert!(builder
         .peer_list()
-        .qualified_peers(&mut rng)
+        .qualified_peers(&mut rng, Tag::Block)
         .contains(&honest_peer));
     assert!(builder.peer_list().dishonest_peers().is_empty());
     // Builder acceptance for error on signature handling from a peer.
@@ -87,7 +106,7 @@ fn handle_acceptance_promotes_and_disqualifies_peers() {
         .is_err());
     assert!(builder
         .peer_list()
-        .qualified_peers(&mut rng)
+        .qualified_peers(&mut rng, Tag::Block)
         .contains(&honest_peer));
     assert!(builder
         .peer_list()
@@ -104,6 +123,7 @@ fn handle_acceptance_unlatches_builder() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -169,6 +189,7 @@ fn register_era_validator_weights() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -218,6 +239,7 @@ fn register_finalized_block() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -291,6 +313,7 @@ fn register_block_execution() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -373,6 +396,7 @@ fn register_block_executed() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -441,6 +465,7 @@ fn register_block_marked_complete() {
         false,
         1,
         TimeDiff::from_seconds(1),
+        PeerScores::default(),
         LegacyRequiredFinality::Strict,
         ProtocolVersion::V1_0_0,
     );
@@ -97,6 +97,20 @@ impl DeployAcquisition {
             DeployAcquisition::ById(acq) => acq.needs_deploy().map(DeployIdentifier::ById),
         }
     }
+
+    /// Returns up to `count` of the deploys still missing, to be fetched in parallel.
+    pub(super) fn needs_deploys(&self, count: usize) -> NeededDeploys {
+        match self {
+            DeployAcquisition::ByHash(acq) => NeededDeploys::ByHash(acq.needs_deploys(count)),
+            DeployAcquisition::ById(acq) => NeededDeploys::ById(acq.needs_deploys(count)),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, DataSize, Debug)]
+pub(super) enum NeededDeploys {
+    ByHash(Vec<DeployHash>),
+    ById(Vec<DeployId>),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, DataSize, Debug, Default)]
@@ -140,11 +154,17 @@ impl<T: Copy + Ord> Acquisition<T> {
     }
 
     fn needs_deploy(&self) -> Option<T> {
+        self.needs_deploys(1).pop()
+    }
+
+    fn needs_deploys(&self, count: usize) -> Vec<T> {
         self.inner
             .iter()
-            .find_map(|(deploy_identifier, state)| match state {
+            .filter_map(|(deploy_identifier, state)| match state {
                 DeployState::Vacant => Some(*deploy_identifier),
                 DeployState::HaveDeployBody => None,
             })
+            .take(count)
+            .collect()
     }
 }
@@ -18,7 +18,7 @@ use casper_types::Timestamp;
 
 use super::{TrieAccumulator, TrieAccumulatorError, TrieAccumulatorEvent, TrieAccumulatorResponse};
 use crate::{
-    components::Component,
+    components::{fetcher::PeerScores, Component},
     effect::{
         announcements::PeerBehaviorAnnouncement,
         requests::{
@@ -233,10 +233,10 @@ pub(super) struct GlobalStateSynchronizer {
 }
 
 impl GlobalStateSynchronizer {
-    pub(super) fn new(max_parallel_trie_fetches: usize) -> Self {
+    pub(super) fn new(max_parallel_trie_fetches: usize, peer_scores: PeerScores) -> Self {
         Self {
             max_parallel_trie_fetches,
-            trie_accumulator: TrieAccumulator::new(),
+            trie_accumulator: TrieAccumulator::new(peer_scores),
             request_state: None,
             tries_awaiting_children: Default::default(),
             fetch_queue: Default::default(),
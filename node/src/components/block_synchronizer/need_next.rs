@@ -33,10 +33,10 @@ pub(crate) enum NeedNext {
     FinalitySignatures(BlockHash, EraId, Vec<PublicKey>),
     #[display(fmt = "need next for {}: global state (state root hash {})", _0, _1)]
     GlobalState(BlockHash, Digest),
-    #[display(fmt = "need next for {}: deploy {}", _0, _1)]
-    DeployByHash(BlockHash, DeployHash),
-    #[display(fmt = "need next for {}: deploy {}", _0, _1)]
-    DeployById(BlockHash, DeployId),
+    #[display(fmt = "need next for {}: {} deploys by hash", _0, "_1.len()")]
+    DeploysByHash(BlockHash, Vec<DeployHash>),
+    #[display(fmt = "need next for {}: {} deploys by id", _0, "_1.len()")]
+    DeploysById(BlockHash, Vec<DeployId>),
     #[display(fmt = "need next for {}: make block executable (height {})", _0, _1)]
     MakeExecutableBlock(BlockHash, u64),
     #[display(
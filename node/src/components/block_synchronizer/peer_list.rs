@@ -5,10 +5,13 @@ use std::collections::{btree_map::Entry, BTreeMap};
 
 use datasize::DataSize;
 use itertools::Itertools;
-use rand::seq::IteratorRandom;
 use tracing::debug;
 
-use crate::{types::NodeId, NodeRng};
+use crate::{
+    components::fetcher::{PeerScores, Tag},
+    types::NodeId,
+    NodeRng,
+};
 use casper_types::{TimeDiff, Timestamp};
 
 #[derive(Copy, Clone, PartialEq, Eq, DataSize, Debug, Default)]
@@ -26,21 +29,28 @@ pub(super) enum PeersStatus {
     Stale,
 }
 
-#[derive(Clone, PartialEq, Eq, DataSize, Debug)]
+#[derive(Clone, DataSize, Debug)]
 pub(super) struct PeerList {
     peer_list: BTreeMap<NodeId, PeerQuality>,
     keep_fresh: Timestamp,
     max_simultaneous_peers: u8,
     peer_refresh_interval: TimeDiff,
+    #[data_size(skip)]
+    peer_scores: PeerScores,
 }
 
 impl PeerList {
-    pub(super) fn new(max_simultaneous_peers: u8, peer_refresh_interval: TimeDiff) -> Self {
+    pub(super) fn new(
+        max_simultaneous_peers: u8,
+        peer_refresh_interval: TimeDiff,
+        peer_scores: PeerScores,
+    ) -> Self {
         PeerList {
             peer_list: BTreeMap::new(),
             keep_fresh: Timestamp::now(),
             max_simultaneous_peers,
             peer_refresh_interval,
+            peer_scores,
         }
     }
     pub(super) fn register_peer(&mut self, peer: NodeId) {
@@ -143,40 +153,53 @@ impl PeerList {
         PeersStatus::Sufficient
     }
 
-    fn get_random_peers_by_quality(
+    /// Chooses up to `up_to` peers of the given quality, preferring the ones which served items
+    /// with the given tag best so far.
+    fn get_best_peers_by_quality(
         &self,
         rng: &mut NodeRng,
+        tag: Tag,
         up_to: usize,
         peer_quality: PeerQuality,
     ) -> Vec<NodeId> {
-        self.peer_list
+        let peers = self
+            .peer_list
             .iter()
             .filter(|(_peer, quality)| **quality == peer_quality)
-            .choose_multiple(rng, up_to)
-            .into_iter()
             .map(|(peer, _)| *peer)
-            .collect()
+            .collect();
+        self.peer_scores.choose_peers(tag, peers, up_to, rng)
     }
 
-    pub(super) fn qualified_peers(&self, rng: &mut NodeRng) -> Vec<NodeId> {
-        self.qualified_peers_up_to(rng, self.max_simultaneous_peers as usize)
+    pub(super) fn qualified_peers(&self, rng: &mut NodeRng, tag: Tag) -> Vec<NodeId> {
+        self.qualified_peers_up_to(rng, tag, self.max_simultaneous_peers as usize)
     }
 
-    pub(super) fn qualified_peers_up_to(&self, rng: &mut NodeRng, up_to: usize) -> Vec<NodeId> {
+    pub(super) fn qualified_peers_up_to(
+        &self,
+        rng: &mut NodeRng,
+        tag: Tag,
+        up_to: usize,
+    ) -> Vec<NodeId> {
         // get most useful up to limit
-        let mut peers = self.get_random_peers_by_quality(rng, up_to, PeerQuality::Reliable);
+        let mut peers = self.get_best_peers_by_quality(rng, tag, up_to, PeerQuality::Reliable);
 
         // if below limit get unknown peers which may or may not be useful
         let missing = up_to.saturating_sub(peers.len());
         if missing > 0 {
-            peers.extend(self.get_random_peers_by_quality(rng, missing, PeerQuality::Unknown));
+            peers.extend(self.get_best_peers_by_quality(rng, tag, missing, PeerQuality::Unknown));
         }
 
         // if still below limit try unreliable peers again until we have the chance to refresh the
         // peer list
         let missing = up_to.saturating_sub(peers.len());
         if missing > 0 {
-            peers.extend(self.get_random_peers_by_quality(rng, missing, PeerQuality::Unreliable));
+            peers.extend(self.get_best_peers_by_quality(
+                rng,
+                tag,
+                missing,
+                PeerQuality::Unreliable,
+            ));
         }
 
         peers
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, time::Duration};
 
 use super::*;
 use casper_types::testing::TestRng;
@@ -25,7 +25,7 @@ fn random_peers(rng: &mut TestRng, num_random_peers: usize) -> HashSet<NodeId> {
 #[test]
 fn number_of_qualified_peers_is_correct() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
 
     let test_peers: Vec<NodeId> = random_peers(&mut rng, 10).into_iter().collect();
 
@@ -37,35 +37,35 @@ fn number_of_qualified_peers_is_correct() {
 
     // All peers should be `Unknown`; check that the number of qualified peers is within the
     // `max_simultaneous_peers`
-    let qualified_peers = peer_list.qualified_peers(&mut rng);
+    let qualified_peers = peer_list.qualified_peers(&mut rng, Tag::Block);
     assert_eq!(qualified_peers.len(), 5);
 
     // Promote some peers to make them `Reliable`; check the count again
     for peer in &test_peers[..3] {
         peer_list.promote_peer(*peer);
     }
-    let qualified_peers = peer_list.qualified_peers(&mut rng);
+    let qualified_peers = peer_list.qualified_peers(&mut rng, Tag::Block);
     assert_eq!(qualified_peers.len(), 5);
 
     // Demote some peers to make them `Unreliable`; check the count again
     for peer in &test_peers[5..] {
         peer_list.demote_peer(*peer);
     }
-    let qualified_peers = peer_list.qualified_peers(&mut rng);
+    let qualified_peers = peer_list.qualified_peers(&mut rng, Tag::Block);
     assert_eq!(qualified_peers.len(), 5);
 
     // Disqualify 7 peers; only 3 peers should remain valid for proposal
     for peer in &test_peers[..7] {
         peer_list.disqualify_peer(*peer);
     }
-    let qualified_peers = peer_list.qualified_peers(&mut rng);
+    let qualified_peers = peer_list.qualified_peers(&mut rng, Tag::Block);
     assert_eq!(qualified_peers.len(), 3);
 }
 
 #[test]
 fn unknown_peer_becomes_reliable_when_promoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -77,7 +77,7 @@ fn unknown_peer_becomes_reliable_when_promoted() {
 #[test]
 fn unknown_peer_becomes_unreliable_when_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -89,7 +89,7 @@ fn unknown_peer_becomes_unreliable_when_demoted() {
 #[test]
 fn reliable_peer_becomes_unreliable_when_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -103,7 +103,7 @@ fn reliable_peer_becomes_unreliable_when_demoted() {
 #[test]
 fn unreliable_peer_becomes_reliable_when_promoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -117,7 +117,7 @@ fn unreliable_peer_becomes_reliable_when_promoted() {
 #[test]
 fn unreliable_peer_remains_unreliable_if_demoted() {
     let mut rng = TestRng::new();
-    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1));
+    let mut peer_list = PeerList::new(5, TimeDiff::from_seconds(1), PeerScores::default());
     let test_peer = NodeId::random(&mut rng);
 
     peer_list.register_peer(test_peer);
@@ -127,3 +127,30 @@ fn unreliable_peer_remains_unreliable_if_demoted() {
     peer_list.demote_peer(test_peer);
     assert!(peer_list.is_peer_unreliable(&test_peer));
 }
+
+#[test]
+fn qualified_peers_prefer_best_performing_peers() {
+    let mut rng = TestRng::new();
+    let peer_scores = PeerScores::default();
+    let mut peer_list = PeerList::new(1, TimeDiff::from_seconds(1), peer_scores.clone());
+    let fast_peer = NodeId::random(&mut rng);
+    let slow_peer = NodeId::random(&mut rng);
+    peer_list.register_peer(fast_peer);
+    peer_list.register_peer(slow_peer);
+
+    for _ in 0..10 {
+        peer_scores.record_success(fast_peer, Tag::Block, Duration::from_millis(10), 1000);
+        peer_scores.record_success(slow_peer, Tag::Block, Duration::from_secs(10), 1000);
+    }
+    let fast_count = (0..100)
+        .filter(|_| peer_list.qualified_peers(&mut rng, Tag::Block) == vec![fast_peer])
+        .count();
+    assert!(fast_count > 90, "fast peer chosen {} times", fast_count);
+
+    // Reliability still takes precedence over performance.
+    peer_list.promote_peer(slow_peer);
+    assert_eq!(
+        peer_list.qualified_peers(&mut rng, Tag::Block),
+        vec![slow_peer]
+    );
+}
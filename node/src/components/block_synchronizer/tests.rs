@@ -27,6 +27,7 @@ use crate::{
     },
     effect::Effect,
     reactor::{EventQueueHandle, QueueKind, Scheduler},
+    signer::Signer,
     tls::KeyFingerprint,
     types::{
         chainspec::LegacyRequiredFinality, BlockExecutionResultsOrChunkId, DeployId,
@@ -148,8 +149,7 @@ impl TestEnv {
             Ratio::new(1, 3),
             None,
             EraId::from(0),
-            self.validator_keys[0].clone(),
-            PublicKey::from(self.validator_keys[0].as_ref()),
+            Signer::from(self.validator_keys[0].clone()),
             1,
         );
         validator_matrix
@@ -271,6 +271,7 @@ impl BlockSynchronizer {
             Arc::new(Chainspec::random(rng)),
             MAX_SIMULTANEOUS_PEERS,
             validator_matrix,
+            PeerScores::default(),
             &prometheus::Registry::new(),
         )
         .expect("Failed to create BlockSynchronizer");
@@ -4163,3 +4164,28 @@ async fn historical_sync_latch_should_not_decrement_for_old_execution_results()
         .as_str(),
     );
 }
+
+#[test]
+fn should_spread_requests_across_all_peers() {
+    let mut rng = TestRng::new();
+    let peers: Vec<NodeId> = (0..3).map(|_| NodeId::random(&mut rng)).collect();
+
+    // More items than peers: every item is requested once, peers are reused.
+    let requests = spread_requests(vec![0, 1, 2, 3, 4], peers.clone());
+    assert_eq!(
+        requests.iter().map(|(item, _)| *item).collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert!(peers
+        .iter()
+        .all(|peer| requests.iter().any(|(_, node_id)| node_id == peer)));
+
+    // Fewer items than peers: every peer is asked, items are requested redundantly.
+    let requests = spread_requests(vec![0], peers.clone());
+    assert_eq!(
+        requests,
+        peers.iter().map(|peer| (0, *peer)).collect::<Vec<_>>()
+    );
+
+    assert!(spread_requests(vec![0, 1], vec![]).is_empty());
+}
@@ -8,7 +8,6 @@ use std::{
 
 use datasize::DataSize;
 use derive_more::From;
-use rand::seq::SliceRandom;
 use serde::Serialize;
 use thiserror::Error;
 use tracing::{debug, error, trace, warn};
@@ -21,6 +20,7 @@ use crate::{
     components::{
         fetcher::{
             EmptyValidationMetadata, Error as FetcherError, FetchItem, FetchResult, FetchedData,
+            PeerScores, Tag,
         },
         Component,
     },
@@ -126,6 +126,8 @@ impl PartialChunks {
 #[derive(DataSize, Debug)]
 pub(super) struct TrieAccumulator {
     partial_chunks: HashMap<Digest, PartialChunks>,
+    #[data_size(skip)]
+    peer_scores: PeerScores,
 }
 
 #[derive(DataSize, Debug, From, Serialize)]
@@ -150,9 +152,10 @@ impl fmt::Display for Event {
 }
 
 impl TrieAccumulator {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(peer_scores: PeerScores) -> Self {
         TrieAccumulator {
             partial_chunks: Default::default(),
+            peer_scores,
         }
     }
 
@@ -274,9 +277,11 @@ where
             Event::Request(TrieAccumulatorRequest {
                 hash,
                 responder,
-                mut peers,
+                peers,
             }) => {
-                peers.shuffle(rng);
+                // Peers are tried starting from the end of the list, so the best ones go last.
+                let mut peers = self.peer_scores.order_peers(Tag::TrieOrChunk, peers, rng);
+                peers.reverse();
                 let trie_id = TrieOrChunkId(0, hash);
                 let peer = match peers.last() {
                     Some(peer) => *peer,
@@ -88,7 +88,7 @@ fn unsolicited_chunk_produces_no_effects() {
     let reactor = MockReactor::new();
 
     // Empty accumulator. Does not expect any chunks.
-    let mut trie_accumulator = TrieAccumulator::new();
+    let mut trie_accumulator = TrieAccumulator::new(PeerScores::default());
     let (test_chunks, _, _) = test_chunks_with_proof(1);
 
     let effects = trie_accumulator.consume_chunk(reactor.effect_builder(), test_chunks[0].clone());
@@ -99,7 +99,7 @@ fn unsolicited_chunk_produces_no_effects() {
 async fn try_download_chunk_generates_fetch_effect() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut trie_accumulator = TrieAccumulator::new();
+    let mut trie_accumulator = TrieAccumulator::new(PeerScores::default());
 
     // Create a test chunk
     let (_, chunk_ids, _) = test_chunks_with_proof(1);
@@ -125,7 +125,7 @@ async fn try_download_chunk_generates_fetch_effect() {
 async fn failed_fetch_retriggers_download_with_different_peer() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut trie_accumulator = TrieAccumulator::new();
+    let mut trie_accumulator = TrieAccumulator::new(PeerScores::default());
 
     // Create a test chunk
     let (_, chunk_ids, _) = test_chunks_with_proof(1);
@@ -173,7 +173,7 @@ async fn failed_fetch_retriggers_download_with_different_peer() {
 async fn fetched_chunk_triggers_download_of_missing_chunk() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut trie_accumulator = TrieAccumulator::new();
+    let mut trie_accumulator = TrieAccumulator::new(PeerScores::default());
 
     // Create test chunks
     let (test_chunks, chunk_ids, _) = test_chunks_with_proof(2);
@@ -216,7 +216,7 @@ async fn fetched_chunk_triggers_download_of_missing_chunk() {
 async fn trie_returned_when_all_chunks_fetched() {
     let mut rng = TestRng::new();
     let reactor = MockReactor::new();
-    let mut trie_accumulator = TrieAccumulator::new();
+    let mut trie_accumulator = TrieAccumulator::new(PeerScores::default());
 
     // Create test chunks
     let (test_chunks, chunk_ids, data) = test_chunks_with_proof(3);
@@ -3,6 +3,11 @@
 //! The block validator checks whether all the deploys included in the block payload exist, either
 //! locally or on the network.
 //!
+//! Each deploy's maximum fee must be at least the chainspec's minimum base fee.  The actual base
+//! fee of the block depends on its parent, which may not have been executed yet when the block is
+//! validated, so it is enforced when executing the block: deploys whose maximum fee is below it
+//! fail without being charged.
+//!
 //! When multiple requests are made to validate the same block payload, they will eagerly return
 //! true if valid, but only fail if all sources have been exhausted. This is only relevant when
 //! calling for validation of the same proposed block multiple times at the same time.
@@ -84,6 +89,11 @@ impl BlockValidator {
         }
     }
 
+    /// Replaces the chainspec, e.g. after governance changed the block gas limit.
+    pub(crate) fn set_chainspec(&mut self, chainspec: Arc<Chainspec>) {
+        self.chainspec = chainspec;
+    }
+
     /// If the request is already being handled, we record the new info and return effects.  If not,
     /// the request is returned for processing as a new request.
     fn try_handle_as_existing_request<REv>(
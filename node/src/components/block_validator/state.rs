@@ -938,4 +938,17 @@ mod tests {
         assert_eq!(responders.len(), 1);
         assert!(matches!(state, BlockValidationState::Invalid(_)));
     }
+
+    #[test]
+    fn state_should_change_to_validation_failed_with_max_fee_below_min_base_fee() {
+        let mut fixture = Fixture::new();
+        fixture.chainspec.deploy_config.min_base_fee = u64::MAX;
+        let (mut state, _maybe_responder) = fixture.new_state(1, 0);
+        assert!(matches!(state, BlockValidationState::InProgress { .. }));
+
+        let (dt_hash, footprint) = fixture.footprints().pop().unwrap();
+        let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(state, BlockValidationState::Invalid(_)));
+    }
 }
@@ -12,6 +12,8 @@ pub(crate) mod error;
 mod leader_sequence;
 mod metrics;
 pub mod protocols;
+mod round_timeline;
+pub(crate) mod slashing_protection;
 #[cfg(test)]
 pub(crate) mod tests;
 mod traits;
@@ -67,6 +69,7 @@ pub(crate) use leader_sequence::LeaderSequence;
 pub(crate) use protocols::highway::max_rounds_per_era;
 #[cfg(test)]
 pub(crate) use protocols::highway::HighwayMessage;
+pub(crate) use round_timeline::ConsensusRound;
 pub(crate) use validator_change::ValidatorChange;
 
 const COMPONENT_NAME: &str = "consensus";
@@ -473,6 +476,9 @@ where
                 let validator_changes = self.get_validator_changes();
                 responder.respond(validator_changes).ignore()
             }
+            Event::ConsensusRequest(ConsensusRequest::Rounds(responder)) => {
+                responder.respond(self.get_consensus_rounds()).ignore()
+            }
             Event::DumpState(req @ DumpConsensusStateRequest { era_id, .. }) => {
                 let current_era = match self.current_era() {
                     None => {
@@ -2,40 +2,56 @@ use std::sync::Arc;
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{error, info};
 
 use casper_hashing::Digest;
-use casper_types::{crypto, PublicKey, SecretKey, Signature};
+use casper_types::{crypto, EraId, PublicKey, SecretKey, Signature};
 
 use crate::{
-    components::consensus::traits::{ConsensusValueT, Context, ValidatorSecret},
+    components::consensus::{
+        slashing_protection::{SigningSlot, SlashingProtection},
+        traits::{ConsensusValueT, Context, ValidatorSecret},
+    },
+    signer::Signer,
     types::BlockPayload,
 };
 
 #[derive(DataSize)]
 pub struct Keypair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    signer: Signer,
+    /// The era this key is used in, and the database recording everything signed with it.
+    #[data_size(skip)]
+    slashing_protection: Option<(EraId, Arc<SlashingProtection>)>,
 }
 
 impl Keypair {
-    pub(crate) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
+    pub(crate) fn new(signer: Signer) -> Self {
         Self {
-            secret_key,
-            public_key,
+            signer,
+            slashing_protection: None,
         }
     }
 
+    /// Returns a keypair that checks every signature for the given era against the slashing
+    /// protection database.
+    pub(crate) fn with_slashing_protection(
+        mut self,
+        era_id: EraId,
+        slashing_protection: Arc<SlashingProtection>,
+    ) -> Self {
+        self.slashing_protection = Some((era_id, slashing_protection));
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn public_key(&self) -> &PublicKey {
-        &self.public_key
+        self.signer.public_key()
     }
 }
 
 impl From<Arc<SecretKey>> for Keypair {
     fn from(secret_key: Arc<SecretKey>) -> Self {
-        let public_key: PublicKey = secret_key.as_ref().into();
-        Self::new(secret_key, public_key)
+        Self::new(Signer::from(secret_key))
     }
 }
 
@@ -43,8 +59,24 @@ impl ValidatorSecret for Keypair {
     type Hash = Digest;
     type Signature = Signature;
 
-    fn sign(&self, hash: &Digest) -> Signature {
-        crypto::sign(hash, self.secret_key.as_ref(), &self.public_key)
+    fn sign(&self, hash: &Digest) -> Option<Signature> {
+        match self.signer.sign(hash) {
+            Ok(signature) => Some(signature),
+            Err(error) => {
+                error!(%error, %hash, "failed to sign consensus message");
+                None
+            }
+        }
+    }
+
+    fn sign_slot(&self, slot: SigningSlot, hash: &Digest) -> Option<Signature> {
+        if let Some((era_id, slashing_protection)) = &self.slashing_protection {
+            if let Err(error) = slashing_protection.check_and_record(*era_id, slot, *hash) {
+                error!(%error, %hash, "slashing protection prevented signing");
+                return None;
+            }
+        }
+        self.sign(hash)
     }
 }
 
@@ -82,17 +114,13 @@ impl Context for ClContext {
 mod specimen_support {
     use super::Keypair;
     use crate::utils::specimen::{Cache, LargestSpecimen, SizeEstimator};
-    use casper_types::{PublicKey, SecretKey};
+    use casper_types::SecretKey;
     use std::sync::Arc;
 
     impl LargestSpecimen for Keypair {
         fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
             let secret_key = SecretKey::largest_specimen(estimator, cache);
-            let public_key = PublicKey::from(&secret_key);
-            Keypair {
-                secret_key: Arc::new(secret_key),
-                public_key,
-            }
+            Keypair::from(Arc::new(secret_key))
         }
     }
 }
@@ -1,21 +1,27 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_types::{PublicKey, SecretKey};
+use casper_types::SecretKey;
 
 use crate::{
     components::consensus::{
         era_supervisor::PAST_EVIDENCE_ERAS,
         protocols::{highway::config::Config as HighwayConfig, zug::config::Config as ZugConfig},
+        round_timeline::Config as RoundTimelineConfig,
         EraId,
     },
+    signer::{self, RemoteSignerConfig, Signer},
     types::Chainspec,
-    utils::{External, LoadError, Loadable},
+    utils::External,
 };
 
 const DEFAULT_MAX_EXECUTION_DELAY: u64 = 3;
+const DEFAULT_SLASHING_PROTECTION_PATH: &str = "slashing_protection.db";
 
 /// Consensus configuration.
 #[derive(DataSize, Debug, Serialize, Deserialize, Clone)]
@@ -24,6 +30,11 @@ const DEFAULT_MAX_EXECUTION_DELAY: u64 = 3;
 pub struct Config {
     /// Path to secret key file.
     pub secret_key_path: External,
+    /// Remote signer holding the secret key, used instead of `secret_key_path` if set.
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSignerConfig>,
+    /// Path to the slashing protection database, recording every message signed by the validator.
+    pub slashing_protection_path: PathBuf,
     /// The maximum number of blocks by which execution is allowed to lag behind finalization.
     /// If it is more than that, consensus will pause, and resume once the executor has caught up.
     pub max_execution_delay: u64,
@@ -33,30 +44,43 @@ pub struct Config {
     /// Zug-specific node configuration.
     #[serde(default)]
     pub zug: ZugConfig,
+    /// Configuration of the consensus round timeline.
+    #[serde(default)]
+    pub round_timeline: RoundTimelineConfig,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             secret_key_path: External::Missing,
+            remote_signer: None,
+            slashing_protection_path: PathBuf::from(DEFAULT_SLASHING_PROTECTION_PATH),
             max_execution_delay: DEFAULT_MAX_EXECUTION_DELAY,
             highway: HighwayConfig::default(),
             zug: ZugConfig::default(),
+            round_timeline: RoundTimelineConfig::default(),
         }
     }
 }
 
-type LoadKeyError = LoadError<<Arc<SecretKey> as Loadable>::Error>;
-
 impl Config {
-    /// Loads the secret key from the configuration file and derives the public key.
-    pub(crate) fn load_keys<P: AsRef<Path>>(
-        &self,
-        root: P,
-    ) -> Result<(Arc<SecretKey>, PublicKey), LoadKeyError> {
-        let secret_signing_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
-        let public_key: PublicKey = PublicKey::from(secret_signing_key.as_ref());
-        Ok((secret_signing_key, public_key))
+    /// Creates the signer for the validator's key, either loading the secret key from the
+    /// configured file or connecting to the remote signer.
+    pub(crate) fn load_signer<P: AsRef<Path>>(&self, root: P) -> Result<Signer, signer::Error> {
+        match &self.remote_signer {
+            None => {
+                let secret_signing_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
+                Ok(Signer::from(secret_signing_key))
+            }
+            Some(remote_signer) => {
+                let auth_key: Vec<u8> = remote_signer.auth_key_path.clone().load(root)?;
+                Signer::connect_remote(
+                    remote_signer.address.clone(),
+                    auth_key,
+                    remote_signer.timeout.into(),
+                )
+            }
+        }
     }
 }
 
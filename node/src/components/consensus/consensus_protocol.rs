@@ -12,7 +12,7 @@ use casper_hashing::Digest;
 use casper_types::{bytesrepr::ToBytes, TimeDiff, Timestamp};
 
 use crate::{
-    components::consensus::{traits::Context, ActionId, TimerId},
+    components::consensus::{round_timeline::RoundTimeline, traits::Context, ActionId, TimerId},
     types::NodeId,
     NodeRng,
 };
@@ -323,4 +323,7 @@ pub(crate) trait ConsensusProtocol<C: Context>: Send {
 
     // TODO: Make this less Highway-specific.
     fn next_round_length(&self) -> Option<TimeDiff>;
+
+    /// Removes and returns the timelines of the rounds that were completed since the last call.
+    fn take_complete_rounds(&mut self) -> Vec<RoundTimeline<C::ValidatorId>>;
 }
@@ -31,7 +31,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
-use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, TimeDiff, Timestamp};
+use casper_types::{AsymmetricType, EraId, PublicKey, TimeDiff, Timestamp};
 
 use crate::{
     components::{
@@ -42,6 +42,8 @@ use crate::{
                 ProtocolOutcome,
             },
             metrics::Metrics,
+            round_timeline::{ConsensusRound, RoundHistory},
+            slashing_protection::SlashingProtection,
             validator_change::{ValidatorChange, ValidatorChanges},
             ActionId, ChainspecConsensusExt, Config, ConsensusMessage, ConsensusRequestMessage,
             Event, HighwayProtocol, NewBlockPayload, ReactorEventT, ResolveValidity, TimerId, Zug,
@@ -55,9 +57,11 @@ use crate::{
     },
     failpoints::Failpoint,
     fatal, protocol,
+    signer::Signer,
     types::{
-        chainspec::ConsensusProtocolName, BlockHash, BlockHeader, Chainspec, Deploy, DeployHash,
-        DeployOrTransferHash, FinalizedApprovals, FinalizedBlock, MetaBlockState, NodeId,
+        chainspec::ConsensusProtocolName, BlockHash, BlockHeader, BlockPayload, Chainspec, Deploy,
+        DeployHash, DeployOrTransferHash, FinalizedApprovals, FinalizedBlock, MetaBlockState,
+        NodeId,
     },
     NodeRng,
 };
@@ -96,8 +100,10 @@ pub struct EraSupervisor {
     /// Since eras at or before the most recent activation point are never instantiated, shortly
     /// after that there can temporarily be fewer than three entries in the map.
     open_eras: BTreeMap<EraId, Era>,
-    secret_signing_key: Arc<SecretKey>,
+    signer: Signer,
     public_signing_key: PublicKey,
+    #[data_size(skip)]
+    slashing_protection: Arc<SlashingProtection>,
     chainspec: Arc<Chainspec>,
     config: Config,
     /// The height of the next block to be finalized.
@@ -114,6 +120,8 @@ pub struct EraSupervisor {
     /// The path to the folder where unit files will be stored.
     unit_files_folder: PathBuf,
     last_progress: Timestamp,
+    /// The most recent complete consensus rounds.
+    round_history: RoundHistory,
 
     /// Failpoints
     pub(super) message_delay_failpoint: Failpoint<u64>,
@@ -132,21 +140,27 @@ impl EraSupervisor {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         storage_dir: &Path,
-        secret_signing_key: Arc<SecretKey>,
-        public_signing_key: PublicKey,
+        signer: Signer,
+        slashing_protection: Arc<SlashingProtection>,
         config: Config,
         chainspec: Arc<Chainspec>,
         registry: &Registry,
     ) -> Result<Self, Error> {
         let unit_files_folder = storage_dir.join("unit_files");
         std::fs::create_dir_all(&unit_files_folder)?;
+        let public_signing_key = signer.public_key().clone();
         info!(our_id = %public_signing_key, "EraSupervisor pubkey",);
         let metrics = Metrics::new(registry)?;
+        let round_history = RoundHistory::new(
+            config.round_timeline.clone(),
+            storage_dir.join("consensus_rounds.jsonl"),
+        );
 
         let era_supervisor = Self {
             open_eras: Default::default(),
-            secret_signing_key,
+            signer,
             public_signing_key,
+            slashing_protection,
             chainspec,
             config,
             next_block_height: 0,
@@ -154,6 +168,7 @@ impl EraSupervisor {
             unit_files_folder,
             next_executed_height: 0,
             last_progress: Timestamp::now(),
+            round_history,
             message_delay_failpoint: Failpoint::new("consensus.message_delay"),
             proposal_delay_failpoint: Failpoint::new("consensus.proposal_delay"),
         };
@@ -246,6 +261,11 @@ impl EraSupervisor {
         Some(effects)
     }
 
+    /// Returns the most recent complete consensus rounds, oldest first.
+    pub(super) fn get_consensus_rounds(&self) -> Vec<ConsensusRound> {
+        self.round_history.recent()
+    }
+
     /// Returns a list of status changes of active validators.
     pub(super) fn get_validator_changes(
         &self,
@@ -357,6 +377,7 @@ impl EraSupervisor {
         self.metrics
             .consensus_current_era
             .set(era_id.value() as i64);
+        self.update_performance_metrics(era_id);
         let start_height = self.era(era_id).start_height;
         self.next_block_height = self.next_block_height.max(start_height);
         let outcomes = self.era_mut(era_id).consensus.handle_is_current(now);
@@ -384,7 +405,8 @@ impl EraSupervisor {
             vec![]
         } else {
             info!(era = era_id.value(), %our_id, "start voting");
-            let secret = Keypair::new(self.secret_signing_key.clone(), our_id.clone());
+            let secret = Keypair::new(self.signer.clone())
+                .with_slashing_protection(era_id, Arc::clone(&self.slashing_protection));
             let instance_id = self.era(era_id).consensus.instance_id();
             let unit_hash_file = self.unit_file(instance_id);
             self.era_mut(era_id).consensus.activate_validator(
@@ -506,8 +528,9 @@ impl EraSupervisor {
             .cloned()
             .collect();
 
-        // Create and insert the new era instance.
-        let (consensus, outcomes) = match self.chainspec.core_config.consensus_protocol {
+        // Create and insert the new era instance, using the consensus protocol the chainspec
+        // schedules for this era.
+        let (consensus, outcomes) = match self.chainspec.core_config.consensus_protocol(era_id) {
             ConsensusProtocolName::Highway => HighwayProtocol::new_boxed(
                 instance_id,
                 validators.clone(),
@@ -519,6 +542,7 @@ impl EraSupervisor {
                 start_time,
                 seed,
                 now,
+                Some(self.highway_wal_file(&instance_id)),
             ),
             ConsensusProtocolName::Zug => Zug::new_boxed(
                 instance_id,
@@ -535,6 +559,9 @@ impl EraSupervisor {
             ),
         };
 
+        // Unless we were already following the previous era, or are creating this one before it
+        // starts, we may have missed some of its rounds, e.g. after a restart.
+        let observed_from_start = maybe_prev_era.is_some() || now <= start_time;
         let era = Era::new(
             consensus,
             start_time,
@@ -542,6 +569,7 @@ impl EraSupervisor {
             faulty,
             inactive,
             validators.clone(),
+            observed_from_start,
         );
         let _ = self.open_eras.insert(era_id, era);
 
@@ -604,6 +632,12 @@ impl EraSupervisor {
                         err => warn!(?err, "could not delete unit hash file"),
                     }
                 }
+                if let Err(err) = fs::remove_file(self.highway_wal_file(&instance_id)) {
+                    match err.kind() {
+                        io::ErrorKind::NotFound => {}
+                        err => warn!(?err, "could not delete Highway WAL file"),
+                    }
+                }
             }
         }
 
@@ -619,6 +653,15 @@ impl EraSupervisor {
         ))
     }
 
+    /// Returns the path to the era's Highway Write-Ahead Log.
+    fn highway_wal_file(&self, instance_id: &Digest) -> PathBuf {
+        self.unit_files_folder.join(format!(
+            "highway_wal_{:?}_{}.dat",
+            instance_id,
+            self.public_signing_key.to_hex()
+        ))
+    }
+
     /// Applies `f` to the consensus protocol of the specified era.
     fn delegate_to_era<REv: ReactorEventT, F>(
         &mut self,
@@ -640,11 +683,39 @@ impl EraSupervisor {
             }
             Some(era) => {
                 let outcomes = f(&mut *era.consensus, rng);
-                self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes)
+                let complete_rounds = era.consensus.take_complete_rounds();
+                for timeline in &complete_rounds {
+                    era.record_round(timeline);
+                }
+                if !complete_rounds.is_empty() && self.current_era() == Some(era_id) {
+                    self.update_performance_metrics(era_id);
+                }
+                let mut effects = Effects::new();
+                for timeline in complete_rounds {
+                    if let Some(write) = self.round_history.add(ConsensusRound { era_id, timeline })
+                    {
+                        effects.extend(write.run_blocking().ignore());
+                    }
+                }
+                effects.extend(self.handle_consensus_outcomes(
+                    effect_builder,
+                    rng,
+                    era_id,
+                    outcomes,
+                ));
+                effects
             }
         }
     }
 
+    /// Updates the performance metrics with the rounds completed in the given era so far.
+    fn update_performance_metrics(&mut self, era_id: EraId) {
+        if let Some(era) = self.open_eras.get(&era_id) {
+            self.metrics
+                .era_performance(&era.performance, &self.public_signing_key);
+        }
+    }
+
     fn log_missing_era(&self, era_id: EraId) {
         let era = era_id.value();
         if let Some(current_era_id) = self.current_era() {
@@ -1043,6 +1114,15 @@ impl EraSupervisor {
                     .collect();
                 let random_bit = rng.gen();
                 let timestamp = block_context.timestamp();
+                let random_beacon_message = BlockPayload::random_beacon_message(era_id, timestamp);
+                let random_beacon_contribution = match self.signer.prove_vrf(random_beacon_message)
+                {
+                    Ok(proof) => Some(proof),
+                    Err(error) => {
+                        error!(%error, "failed to compute random beacon contribution");
+                        None
+                    }
+                };
                 let delay_by = self.proposal_delay_failpoint.fire(rng).cloned();
                 async move {
                     if let Some(delay) = delay_by {
@@ -1053,7 +1133,11 @@ impl EraSupervisor {
                     let appendable_block = effect_builder
                         .request_appendable_block(timestamp, proposal_expiry)
                         .await;
-                    Arc::new(appendable_block.into_block_payload(accusations, random_bit))
+                    Arc::new(appendable_block.into_block_payload(
+                        accusations,
+                        random_bit,
+                        random_beacon_contribution,
+                    ))
                 }
                 .event(move |block_payload| {
                     Event::NewBlockPayload(NewBlockPayload {
@@ -1094,6 +1178,10 @@ impl EraSupervisor {
                         inactive_validators: tbd.inactive_validators,
                     }
                 });
+                // All of the era's rounds are complete once its last block is finalized. We only
+                // store the performance if we observed all of them.
+                let performance = (era_report.is_some() && era.observed_from_start)
+                    .then(|| era.performance.clone());
                 let proposed_block = Arc::try_unwrap(value).unwrap_or_else(|arc| (*arc).clone());
                 let finalized_approvals: HashMap<_, _> = proposed_block
                     .deploys()
@@ -1139,6 +1227,13 @@ impl EraSupervisor {
                     execute_finalized_block(effect_builder, finalized_approvals, finalized_block)
                         .ignore(),
                 );
+                if let Some(performance) = performance {
+                    effects.extend(
+                        effect_builder
+                            .put_validator_performance(era_id, performance)
+                            .ignore(),
+                    );
+                }
                 let effects_from_updating_pause =
                     self.update_consensus_pause(effect_builder, rng, era_id);
                 effects.extend(effects_from_updating_pause);
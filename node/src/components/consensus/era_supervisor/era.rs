@@ -10,10 +10,14 @@ use tracing::{debug, warn};
 
 use casper_types::{PublicKey, Timestamp, U512};
 
-use crate::components::consensus::{
-    cl_context::ClContext,
-    consensus_protocol::{ConsensusProtocol, ProposedBlock},
-    protocols::{highway::HighwayProtocol, zug::Zug},
+use crate::{
+    components::consensus::{
+        cl_context::ClContext,
+        consensus_protocol::{ConsensusProtocol, ProposedBlock},
+        protocols::{highway::HighwayProtocol, zug::Zug},
+        round_timeline::RoundTimeline,
+    },
+    types::ValidatorPerformance,
 };
 
 const CASPER_ENABLE_DETAILED_CONSENSUS_METRICS_ENV_VAR: &str =
@@ -61,6 +65,11 @@ pub struct Era {
     pub(crate) accusations: HashSet<PublicKey>,
     /// The validator weights.
     pub(crate) validators: BTreeMap<PublicKey, U512>,
+    /// The validators' participation in the rounds completed in this era so far.
+    pub(crate) performance: BTreeMap<PublicKey, ValidatorPerformance>,
+    /// Whether this node has been following the era since before its first round, so that
+    /// `performance` covers all of its rounds.
+    pub(crate) observed_from_start: bool,
 }
 
 impl Era {
@@ -71,7 +80,12 @@ impl Era {
         faulty: HashSet<PublicKey>,
         cannot_propose: HashSet<PublicKey>,
         validators: BTreeMap<PublicKey, U512>,
+        observed_from_start: bool,
     ) -> Self {
+        let performance = validators
+            .keys()
+            .map(|public_key| (public_key.clone(), ValidatorPerformance::default()))
+            .collect();
         Era {
             consensus,
             start_time,
@@ -81,6 +95,8 @@ impl Era {
             cannot_propose,
             accusations: HashSet::new(),
             validators,
+            performance,
+            observed_from_start,
         }
     }
 
@@ -149,6 +165,19 @@ impl Era {
     pub(crate) fn validators(&self) -> &BTreeMap<PublicKey, U512> {
         &self.validators
     }
+
+    /// Records the leader's proposal and the validators' votes in a complete round.
+    pub(crate) fn record_round(&mut self, timeline: &RoundTimeline<PublicKey>) {
+        let leader = self.performance.entry(timeline.leader.clone()).or_default();
+        leader.proposals_assigned = leader.proposals_assigned.saturating_add(1);
+        if timeline.proposal_time.is_some() {
+            leader.proposals_made = leader.proposals_made.saturating_add(1);
+        }
+        for vote in &timeline.votes {
+            let voter = self.performance.entry(vote.validator.clone()).or_default();
+            voter.votes = voter.votes.saturating_add(1);
+        }
+    }
 }
 
 impl DataSize for Era {
@@ -168,6 +197,8 @@ impl DataSize for Era {
             cannot_propose,
             accusations,
             validators,
+            performance,
+            observed_from_start: _,
         } = self;
 
         // `DataSize` cannot be made object safe due its use of associated constants. We implement
@@ -212,5 +243,6 @@ impl DataSize for Era {
             .saturating_add(cannot_propose.estimate_heap_size())
             .saturating_add(accusations.estimate_heap_size())
             .saturating_add(validators.estimate_heap_size())
+            .saturating_add(performance.estimate_heap_size())
     }
 }
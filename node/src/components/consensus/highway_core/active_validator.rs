@@ -2,7 +2,7 @@ use std::{
     fmt::{self, Debug},
     fs::{self, File},
     io::{self, Read, Write},
-    iter,
+    iter, mem,
     path::{Path, PathBuf},
 };
 
@@ -14,7 +14,7 @@ use casper_types::{TimeDiff, Timestamp};
 use super::{
     endorsement::{Endorsement, SignedEndorsement},
     evidence::Evidence,
-    highway::{Ping, ValidVertex, Vertex, WireUnit},
+    highway::{HashedWireUnit, Ping, ValidVertex, Vertex, WireUnit},
     state::{self, Panorama, State, Unit},
     ENABLE_ENDORSEMENTS,
 };
@@ -26,6 +26,10 @@ use crate::components::consensus::{
     utils::{ValidatorIndex, Weight},
 };
 
+/// Delay after which signing a vertex is retried if it failed, e.g. because a remote signer was
+/// unavailable.
+const SIGNING_RETRY_DELAY: TimeDiff = TimeDiff::from_seconds(1);
+
 /// An action taken by a validator.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) enum Effect<C: Context> {
@@ -44,6 +48,20 @@ pub(crate) enum Effect<C: Context> {
     WeAreFaulty(Fault<C>),
 }
 
+/// A vertex of ours that could not be signed yet.
+#[derive(DataSize)]
+enum Unsigned<C>
+where
+    C: Context,
+{
+    /// A unit, which is dropped if we created another unit in the meantime.
+    Unit(HashedWireUnit<C>),
+    /// A ping with the given timestamp.
+    Ping(Timestamp, C::InstanceId),
+    /// An endorsement of the unit with the given hash.
+    Endorsement(C::Hash),
+}
+
 /// A validator that actively participates in consensus by creating new vertices.
 ///
 /// It implements the Highway schedule. The protocol proceeds in rounds, and in each round one
@@ -82,6 +100,10 @@ where
     target_ftt: Weight,
     /// If this flag is set we don't create new units and just send pings instead.
     paused: bool,
+    /// Vertices we failed to sign, to be signed once the signing retry timer fires.
+    unsigned: Vec<Unsigned<C>>,
+    /// Whether the signing retry timer is scheduled.
+    signing_retry_scheduled: bool,
 }
 
 impl<C: Context> Debug for ActiveValidator<C> {
@@ -91,6 +113,7 @@ impl<C: Context> Debug for ActiveValidator<C> {
             .field("next_round_len", &self.next_round_len)
             .field("next_timer", &self.next_timer)
             .field("paused", &self.paused)
+            .field("unsigned", &self.unsigned.len())
             .finish()
     }
 }
@@ -128,9 +151,12 @@ impl<C: Context> ActiveValidator<C> {
             own_last_unit,
             target_ftt,
             paused: false,
+            unsigned: Vec::new(),
+            signing_retry_scheduled: false,
         };
         let mut effects = av.schedule_timer(start_time, state);
-        effects.push(av.send_ping(current_time, instance_id));
+        effects.extend(av.send_ping(current_time, instance_id));
+        effects.extend(av.schedule_signing_retry(current_time));
         (av, effects)
     }
 
@@ -184,6 +210,20 @@ impl<C: Context> ActiveValidator<C> {
             warn!("Creator knows it's faulty. Won't create a message.");
             return vec![];
         }
+        self.signing_retry_scheduled = false;
+        let mut effects = self.retry_signing(state);
+        effects.extend(self.timer_effects(timestamp, state, instance_id));
+        effects.extend(self.schedule_signing_retry(timestamp));
+        effects
+    }
+
+    /// Returns the actions to take at the specified `timestamp`, other than retrying to sign.
+    fn timer_effects(
+        &mut self,
+        timestamp: Timestamp,
+        state: &State<C>,
+        instance_id: C::InstanceId,
+    ) -> Vec<Effect<C>> {
         let mut effects = self.schedule_timer(timestamp, state);
         if self.earliest_unit_time(state) > timestamp {
             warn!(%timestamp, "skipping outdated timer event");
@@ -221,15 +261,70 @@ impl<C: Context> ActiveValidator<C> {
             one_max_round_ago.saturating_add(TimeDiff::from_millis(1)),
         ) {
             warn!(%timestamp, "too many validators offline, sending ping");
-            effects.push(self.send_ping(timestamp, instance_id));
+            effects.extend(self.send_ping(timestamp, instance_id));
         }
         effects
     }
 
     /// Creates a Ping vertex.
-    pub(crate) fn send_ping(&self, timestamp: Timestamp, instance_id: C::InstanceId) -> Effect<C> {
-        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret);
-        Effect::NewVertex(ValidVertex(Vertex::Ping(ping)))
+    ///
+    /// If it could not be signed, signing is retried once the signing retry timer fires.
+    pub(crate) fn send_ping(
+        &mut self,
+        timestamp: Timestamp,
+        instance_id: C::InstanceId,
+    ) -> Option<Effect<C>> {
+        match Ping::new(self.vidx, timestamp, instance_id, &self.secret) {
+            Some(ping) => Some(Effect::NewVertex(ValidVertex(Vertex::Ping(ping)))),
+            None => {
+                warn!(%timestamp, "failed to sign ping, will retry");
+                self.unsigned.push(Unsigned::Ping(timestamp, instance_id));
+                None
+            }
+        }
+    }
+
+    /// Retries signing the vertices we failed to sign before.
+    fn retry_signing(&mut self, state: &State<C>) -> Vec<Effect<C>> {
+        let mut effects = Vec::new();
+        for unsigned in mem::take(&mut self.unsigned) {
+            let maybe_vertex = match unsigned {
+                Unsigned::Unit(hwunit) => {
+                    // Signing the unit after we created another one would be an equivocation.
+                    if hwunit.wire_unit().panorama[self.vidx] != state.panorama()[self.vidx] {
+                        info!("dropping unsigned unit superseded by a newer one");
+                        continue;
+                    }
+                    self.sign_unit(hwunit).map(Vertex::Unit)
+                }
+                Unsigned::Ping(timestamp, instance_id) => {
+                    match self.send_ping(timestamp, instance_id) {
+                        Some(effect) => {
+                            effects.push(effect);
+                            continue;
+                        }
+                        None => None,
+                    }
+                }
+                Unsigned::Endorsement(vhash) => self.endorse(&vhash),
+            };
+            if let Some(vertex) = maybe_vertex {
+                effects.push(Effect::NewVertex(ValidVertex(vertex)));
+            }
+        }
+        effects
+    }
+
+    /// Returns a timer effect for retrying to sign vertices, if there are any we failed to sign and
+    /// the timer is not scheduled yet.
+    fn schedule_signing_retry(&mut self, now: Timestamp) -> Option<Effect<C>> {
+        if self.unsigned.is_empty() || self.signing_retry_scheduled {
+            return None;
+        }
+        self.signing_retry_scheduled = true;
+        Some(Effect::ScheduleTimer(
+            now.saturating_add(SIGNING_RETRY_DELAY),
+        ))
     }
 
     /// Returns whether enough validators are online to finalize values with the target fault
@@ -273,15 +368,19 @@ impl<C: Context> ActiveValidator<C> {
             }
         };
         if self.should_endorse(uhash, state) {
-            let endorsement = self.endorse(uhash);
-            effects.extend(vec![Effect::NewVertex(ValidVertex(endorsement))]);
+            if let Some(endorsement) = self.endorse(uhash) {
+                effects.push(Effect::NewVertex(ValidVertex(endorsement)));
+            }
         }
+        effects.extend(self.schedule_signing_retry(now));
         effects
     }
 
     /// Returns actions validator needs to take upon receiving a new evidence.
     /// Endorses all latest units by honest validators that do not mark new perpetrator as faulty
     /// and cite some new message by that validator.
+    ///
+    /// Endorsements that could not be signed are retried on the next timer event.
     pub(crate) fn on_new_evidence(
         &mut self,
         evidence: &Evidence<C>,
@@ -297,7 +396,7 @@ impl<C: Context> ActiveValidator<C> {
                 let unit = state.unit(v);
                 unit.new_hash_obs(state, vidx)
             })
-            .map(|v| self.endorse(v))
+            .filter_map(|v| self.endorse(v))
             .map(|endorsement| Effect::NewVertex(ValidVertex(endorsement)))
             .collect()
     }
@@ -368,6 +467,7 @@ impl<C: Context> ActiveValidator<C> {
         self.new_unit(panorama, timestamp, Some(value), state, instance_id)
             .map(|proposal_unit| Effect::NewVertex(ValidVertex(Vertex::Unit(proposal_unit))))
             .into_iter()
+            .chain(self.schedule_signing_retry(timestamp))
             .collect()
     }
 
@@ -468,7 +568,27 @@ impl<C: Context> ActiveValidator<C> {
             endorsed,
         }
         .into_hashed();
-        let swunit = SignedWireUnit::new(hwunit, &self.secret);
+        self.sign_unit(hwunit)
+    }
+
+    /// Signs and persists the unit.
+    ///
+    /// If it could not be signed, it replaces any other unsigned unit and signing is retried once
+    /// the signing retry timer fires.
+    fn sign_unit(&mut self, hwunit: HashedWireUnit<C>) -> Option<SignedWireUnit<C>> {
+        let swunit = match SignedWireUnit::new(hwunit.clone(), &self.secret) {
+            Some(swunit) => swunit,
+            None => {
+                warn!(
+                    seq_number = hwunit.wire_unit().seq_number,
+                    "failed to sign unit, will retry"
+                );
+                self.unsigned
+                    .retain(|unsigned| !matches!(unsigned, Unsigned::Unit(_)));
+                self.unsigned.push(Unsigned::Unit(hwunit));
+                return None;
+            }
+        };
         write_last_unit(&self.unit_file, swunit.clone()).unwrap_or_else(|err| {
             panic!(
                 "should successfully write unit's hash to {:?}, got {:?}",
@@ -582,10 +702,21 @@ impl<C: Context> ActiveValidator<C> {
     }
 
     /// Creates endorsement of the `vhash`.
-    fn endorse(&self, vhash: &C::Hash) -> Vertex<C> {
+    ///
+    /// If it could not be signed, signing is retried once the signing retry timer fires.
+    fn endorse(&mut self, vhash: &C::Hash) -> Option<Vertex<C>> {
         let endorsement = Endorsement::new(*vhash, self.vidx);
-        let signature = self.secret.sign(&endorsement.hash());
-        Vertex::Endorsements(SignedEndorsement::new(endorsement, signature).into())
+        let signature = match self.secret.sign(&endorsement.hash()) {
+            Some(signature) => signature,
+            None => {
+                warn!(?vhash, "failed to sign endorsement, will retry");
+                self.unsigned.push(Unsigned::Endorsement(*vhash));
+                return None;
+            }
+        };
+        Some(Vertex::Endorsements(
+            SignedEndorsement::new(endorsement, signature).into(),
+        ))
     }
 
     /// Returns a panorama that is valid to use in our own unit at the given timestamp.
@@ -996,6 +1127,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn retries_signing_ping() {
+        let state = State::new_test(&[Weight(3)], 0);
+        let (mut alice, init_effects) = ActiveValidator::new(
+            ALICE,
+            UNAVAILABLE_SEC,
+            410.into(),
+            410.into(),
+            &state,
+            None,
+            Weight(2),
+            TEST_INSTANCE_ID,
+        );
+
+        // The ping could not be signed, so a retry is scheduled in addition to the round timer.
+        let retry_time = match &*init_effects {
+            &[Effect::ScheduleTimer(_), Effect::ScheduleTimer(retry_time)] => retry_time,
+            other => panic!("expected two timers on startup. Got {:?}", other),
+        };
+        assert_eq!(Timestamp::from(410) + SIGNING_RETRY_DELAY, retry_time);
+
+        // Once the signer is available again, the ping is sent when the retry timer fires.
+        alice.secret = TestSecret(ALICE.0);
+        let effects = alice.handle_timer(retry_time, &state, TEST_INSTANCE_ID);
+        let retried_ping = effects.iter().any(|effect| {
+            matches!(
+                effect,
+                Effect::NewVertex(ValidVertex(Vertex::Ping(ping))) if ping.timestamp() == 410.into()
+            )
+        });
+        assert!(
+            retried_ping,
+            "expected the ping to be sent. Got {:?}",
+            effects
+        );
+        assert!(alice.unsigned.is_empty());
+    }
+
     #[test]
     fn detects_doppelganger_ping() {
         let mut state = State::new_test(&[Weight(3)], 0);
@@ -1010,7 +1179,9 @@ mod tests {
             TEST_INSTANCE_ID,
         );
 
-        let ping = Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping = Vertex::Ping(
+            Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC).expect("should sign"),
+        );
 
         // The ping is suspicious if it is newer than the latest ping (or unit) that has been added
         // to the state.
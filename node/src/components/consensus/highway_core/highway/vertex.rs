@@ -11,6 +11,7 @@ use crate::components::consensus::{
         highway::{PingError, VertexError},
         state::Panorama,
     },
+    slashing_protection::SigningSlot,
     traits::{Context, ValidatorSecret},
     utils::{ValidatorIndex, Validators},
 };
@@ -318,15 +319,17 @@ where
 }
 
 impl<C: Context> SignedWireUnit<C> {
+    /// Signs the unit, returning `None` if it could not be signed.
     pub(crate) fn new(
         hashed_wire_unit: HashedWireUnit<C>,
         secret_key: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = secret_key.sign(&hashed_wire_unit.hash);
-        SignedWireUnit {
+    ) -> Option<Self> {
+        let slot = SigningSlot::Sequence(hashed_wire_unit.wire_unit().seq_number);
+        let signature = secret_key.sign_slot(slot, &hashed_wire_unit.hash)?;
+        Some(SignedWireUnit {
             hashed_wire_unit,
             signature,
-        }
+        })
     }
 
     /// Returns the inner `WireUnit`.
@@ -518,20 +521,20 @@ where
 }
 
 impl<C: Context> Ping<C> {
-    /// Creates a new signed ping.
+    /// Creates a new signed ping, or `None` if it could not be signed.
     pub(crate) fn new(
         creator: ValidatorIndex,
         timestamp: Timestamp,
         instance_id: C::InstanceId,
         sk: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = sk.sign(&Self::hash(creator, timestamp, instance_id));
-        Ping {
+    ) -> Option<Self> {
+        let signature = sk.sign(&Self::hash(creator, timestamp, instance_id))?;
+        Some(Ping {
             creator,
             timestamp,
             instance_id,
             signature,
-        }
+        })
     }
 
     /// The creator who signals that it is online.
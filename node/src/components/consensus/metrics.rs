@@ -1,8 +1,13 @@
-use prometheus::{Gauge, IntGauge, Registry};
+use std::collections::BTreeMap;
 
-use casper_types::Timestamp;
+use prometheus::{Gauge, IntGauge, IntGaugeVec, Opts, Registry};
 
-use crate::{types::FinalizedBlock, unregister_metric};
+use casper_types::{AsymmetricType, PublicKey, Timestamp};
+
+use crate::{
+    types::{FinalizedBlock, ValidatorPerformance},
+    unregister_metric,
+};
 
 /// Network metrics to track Consensus
 #[derive(Debug)]
@@ -17,6 +22,18 @@ pub(super) struct Metrics {
     time_of_last_finalized_block: IntGauge,
     /// The current era.
     pub(super) consensus_current_era: IntGauge,
+    /// The number of rounds in the current era in which we were the leader.
+    own_proposals_assigned: IntGauge,
+    /// The number of rounds in the current era in which we made a proposal.
+    own_proposals_made: IntGauge,
+    /// The number of rounds in the current era in which we voted.
+    own_votes: IntGauge,
+    /// The number of rounds in the current era in which each validator was the leader.
+    validator_proposals_assigned: IntGaugeVec,
+    /// The number of rounds in the current era in which each validator made a proposal.
+    validator_proposals_made: IntGaugeVec,
+    /// The number of rounds in the current era in which each validator voted.
+    validator_votes: IntGaugeVec,
     /// Registry component.
     registry: Registry,
 }
@@ -39,17 +56,62 @@ impl Metrics {
         )?;
         let consensus_current_era =
             IntGauge::new("consensus_current_era", "the current era in consensus")?;
+        let own_proposals_assigned = IntGauge::new(
+            "consensus_own_proposals_assigned",
+            "the number of complete rounds in the current era in which this node was the leader",
+        )?;
+        let own_proposals_made = IntGauge::new(
+            "consensus_own_proposals_made",
+            "the number of complete rounds in the current era in which this node made a proposal",
+        )?;
+        let own_votes = IntGauge::new(
+            "consensus_own_votes",
+            "the number of complete rounds in the current era in which this node voted",
+        )?;
+        let validator_proposals_assigned = IntGaugeVec::new(
+            Opts::new(
+                "consensus_validator_proposals_assigned",
+                "the number of complete rounds in the current era in which the validator was the leader",
+            ),
+            &["validator"],
+        )?;
+        let validator_proposals_made = IntGaugeVec::new(
+            Opts::new(
+                "consensus_validator_proposals_made",
+                "the number of complete rounds in the current era in which the validator made a proposal",
+            ),
+            &["validator"],
+        )?;
+        let validator_votes = IntGaugeVec::new(
+            Opts::new(
+                "consensus_validator_votes",
+                "the number of complete rounds in the current era in which the validator voted",
+            ),
+            &["validator"],
+        )?;
         registry.register(Box::new(finalization_time.clone()))?;
         registry.register(Box::new(finalized_block_count.clone()))?;
         registry.register(Box::new(consensus_current_era.clone()))?;
         registry.register(Box::new(time_of_last_proposed_block.clone()))?;
         registry.register(Box::new(time_of_last_finalized_block.clone()))?;
+        registry.register(Box::new(own_proposals_assigned.clone()))?;
+        registry.register(Box::new(own_proposals_made.clone()))?;
+        registry.register(Box::new(own_votes.clone()))?;
+        registry.register(Box::new(validator_proposals_assigned.clone()))?;
+        registry.register(Box::new(validator_proposals_made.clone()))?;
+        registry.register(Box::new(validator_votes.clone()))?;
         Ok(Metrics {
             finalization_time,
             finalized_block_count,
             time_of_last_proposed_block,
             time_of_last_finalized_block,
             consensus_current_era,
+            own_proposals_assigned,
+            own_proposals_made,
+            own_votes,
+            validator_proposals_assigned,
+            validator_proposals_made,
+            validator_votes,
             registry: registry.clone(),
         })
     }
@@ -69,6 +131,39 @@ impl Metrics {
         self.time_of_last_proposed_block
             .set(Timestamp::now().millis() as i64);
     }
+
+    /// Updates the metrics with the validators' participation in the current era.
+    ///
+    /// The per-validator metrics are labelled with the validator's hex-encoded public key.
+    /// Validators not in `performance` are removed from them.
+    pub(super) fn era_performance(
+        &mut self,
+        performance: &BTreeMap<PublicKey, ValidatorPerformance>,
+        own_public_key: &PublicKey,
+    ) {
+        let own_performance = performance.get(own_public_key).copied().unwrap_or_default();
+        self.own_proposals_assigned
+            .set(own_performance.proposals_assigned as i64);
+        self.own_proposals_made
+            .set(own_performance.proposals_made as i64);
+        self.own_votes.set(own_performance.votes as i64);
+
+        self.validator_proposals_assigned.reset();
+        self.validator_proposals_made.reset();
+        self.validator_votes.reset();
+        for (public_key, validator_performance) in performance {
+            let label = public_key.to_hex();
+            self.validator_proposals_assigned
+                .with_label_values(&[&label])
+                .set(validator_performance.proposals_assigned as i64);
+            self.validator_proposals_made
+                .with_label_values(&[&label])
+                .set(validator_performance.proposals_made as i64);
+            self.validator_votes
+                .with_label_values(&[&label])
+                .set(validator_performance.votes as i64);
+        }
+    }
 }
 
 impl Drop for Metrics {
@@ -78,5 +173,11 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.consensus_current_era);
         unregister_metric!(self.registry, self.time_of_last_finalized_block);
         unregister_metric!(self.registry, self.time_of_last_proposed_block);
+        unregister_metric!(self.registry, self.own_proposals_assigned);
+        unregister_metric!(self.registry, self.own_proposals_made);
+        unregister_metric!(self.registry, self.own_votes);
+        unregister_metric!(self.registry, self.validator_proposals_assigned);
+        unregister_metric!(self.registry, self.validator_proposals_made);
+        unregister_metric!(self.registry, self.validator_votes);
     }
 }
@@ -2,4 +2,5 @@
 
 pub mod common;
 pub(crate) mod highway;
+mod wal;
 pub(crate) mod zug;
@@ -3,6 +3,7 @@ mod participation;
 mod round_success_meter;
 #[cfg(test)]
 mod tests;
+mod wal;
 
 use std::{
     any::Any,
@@ -38,7 +39,11 @@ use crate::{
             state::{IndexObservation, IndexPanorama, Observation},
             synchronizer::Synchronizer,
         },
-        protocols,
+        protocols::{
+            self,
+            wal::{ReadWal, WriteWal},
+        },
+        round_timeline::{RoundTimeline, RoundTimelines},
         traits::{ConsensusValueT, Context},
         utils::ValidatorIndex,
         ActionId, TimerId,
@@ -47,7 +52,7 @@ use crate::{
     NodeRng,
 };
 
-use self::round_success_meter::RoundSuccessMeter;
+use self::{round_success_meter::RoundSuccessMeter, wal::Entry};
 
 /// Never allow more than this many units in a piece of evidence for conflicting endorsements,
 /// even if eras are longer than this.
@@ -84,6 +89,10 @@ where
     pvv_cache: HashMap<Dependency<C>, PreValidatedVertex<C>>,
     evidence_only: bool,
     config: config::Config,
+    /// The Write-Ahead Log, recording every vertex added to the protocol state.
+    write_wal: Option<WriteWal<Entry<C>>>,
+    /// The timelines of the rounds, as observed by us.
+    round_timelines: RoundTimelines<C::ValidatorId>,
 }
 
 impl<C: Context + 'static> HighwayProtocol<C> {
@@ -100,6 +109,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         era_start_time: Timestamp,
         seed: u64,
         now: Timestamp,
+        wal_file: Option<PathBuf>,
     ) -> (Box<dyn ConsensusProtocol<C>>, ProtocolOutcomes<C>) {
         let validators_count = validator_stakes.len();
         let validators = protocols::common::validators::<C>(faulty, inactive, validator_stakes);
@@ -187,10 +197,10 @@ impl<C: Context + 'static> HighwayProtocol<C> {
             endorsement_evidence_limit,
         );
 
-        let outcomes = Self::initialize_timers(now, era_start_time, &config.highway);
+        let mut outcomes = Self::initialize_timers(now, era_start_time, &config.highway);
 
         let highway = Highway::new(instance_id, validators, params);
-        let hw_proto = Box::new(HighwayProtocol {
+        let mut hw_proto = Box::new(HighwayProtocol {
             pending_values: HashMap::new(),
             finality_detector: FinalityDetector::new(ftt),
             highway,
@@ -199,11 +209,146 @@ impl<C: Context + 'static> HighwayProtocol<C> {
             pvv_cache: Default::default(),
             evidence_only: false,
             config: config.highway.clone(),
+            write_wal: None,
+            round_timelines: RoundTimelines::default(),
         });
 
+        if let Some(wal_file) = wal_file {
+            outcomes.extend(hw_proto.open_wal(wal_file, now));
+        }
+
         (hw_proto, outcomes)
     }
 
+    /// Adds all vertices recorded in the Write-Ahead Log to the protocol state, and sets up the log
+    /// for appending future vertices. If that fails it prints an error log and the WAL remains
+    /// `None`: After the next restart the state then has to be learned from peers again.
+    fn open_wal(&mut self, wal_file: PathBuf, now: Timestamp) -> ProtocolOutcomes<C> {
+        let mut read_wal = match ReadWal::<Entry<C>>::new(&wal_file) {
+            Ok(read_wal) => read_wal,
+            Err(err) => {
+                error!(%err, "could not create a ReadWal using this file");
+                return vec![];
+            }
+        };
+
+        let mut outcomes = vec![];
+        let mut replayed: usize = 0;
+        loop {
+            match read_wal.read_next_entry() {
+                Ok(Some(Entry::Vertex(vertex))) => {
+                    replayed = replayed.saturating_add(1);
+                    outcomes.extend(self.replay_vertex(vertex, now));
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    error!(?err, "couldn't read a vertex from the WAL");
+                    return outcomes; // Not setting WAL file; won't record new vertices.
+                }
+            }
+        }
+        if replayed > 0 {
+            info!(replayed, "restored vertices from the WAL");
+            outcomes.extend(self.detect_finality(now));
+            // We didn't observe these rounds' timelines, so don't report the restart as their
+            // arrival times.
+            self.round_timelines = RoundTimelines::default();
+        }
+
+        match WriteWal::new(&wal_file) {
+            Ok(write_wal) => self.write_wal = Some(write_wal),
+            Err(err) => error!(?err, ?wal_file, "could not create a WAL using this file"),
+        }
+        outcomes
+    }
+
+    /// Adds a vertex read from the Write-Ahead Log to the protocol state.
+    ///
+    /// The vertex was recorded after its dependencies, and its value had been validated before.
+    /// Only outcomes that are still relevant after the restart are returned.
+    fn replay_vertex(&mut self, vertex: Vertex<C>, now: Timestamp) -> ProtocolOutcomes<C> {
+        let pvv = match self.highway.pre_validate_vertex(vertex) {
+            Ok(pvv) => pvv,
+            Err((vertex, err)) => {
+                warn!(?vertex, ?err, "invalid vertex in WAL");
+                return vec![];
+            }
+        };
+        if let Some(dependency) = self.highway.missing_dependency(&pvv) {
+            warn!(?dependency, "vertex in WAL is missing a dependency");
+            return vec![];
+        }
+        let vv = match self.highway.validate_vertex(pvv) {
+            Ok(vv) => vv,
+            Err((pvv, err)) => {
+                warn!(?pvv, ?err, "invalid vertex in WAL");
+                return vec![];
+            }
+        };
+        self.add_valid_vertex(vv, now)
+            .into_iter()
+            .filter(|outcome| match outcome {
+                ProtocolOutcome::FttExceeded
+                | ProtocolOutcome::WeAreFaulty
+                | ProtocolOutcome::FinalizedBlock(_)
+                | ProtocolOutcome::HandledProposedBlock(..)
+                | ProtocolOutcome::NewEvidence(_) => true,
+                ProtocolOutcome::ValidateConsensusValue { .. }
+                | ProtocolOutcome::SendEvidence(_, _)
+                | ProtocolOutcome::CreatedGossipMessage(_)
+                | ProtocolOutcome::CreatedTargetedMessage(_, _)
+                | ProtocolOutcome::CreatedMessageToRandomPeer(_)
+                | ProtocolOutcome::CreatedRequestToRandomPeer(_)
+                | ProtocolOutcome::ScheduleTimer(_, _)
+                | ProtocolOutcome::QueueAction(_)
+                | ProtocolOutcome::CreateNewBlock(_, _)
+                | ProtocolOutcome::DoppelgangerDetected
+                | ProtocolOutcome::Disconnect(_) => false,
+            })
+            .collect()
+    }
+
+    /// Records a unit that was added to the protocol state in its round's timeline: as the round's
+    /// proposal if it contains a block, and otherwise as its creator's vote.
+    fn record_unit_in_timeline(&mut self, unit_hash: &C::Hash, now: Timestamp) {
+        let state = self.highway.state();
+        let unit = match state.maybe_unit(unit_hash) {
+            Some(unit) => unit,
+            None => return,
+        };
+        let round_id = unit.round_id();
+        let validators = self.highway.validators();
+        let (leader, creator) = match (
+            validators.id(state.leader(round_id)),
+            validators.id(unit.creator),
+        ) {
+            (Some(leader), Some(creator)) => (leader.clone(), creator.clone()),
+            _ => return,
+        };
+        let is_proposal = state.maybe_block(unit_hash).is_some();
+        if let Some(timeline) = self.round_timelines.round_mut(round_id.millis(), leader) {
+            if is_proposal {
+                timeline.record_proposal(now);
+            } else {
+                timeline.record_vote(creator, now);
+            }
+        }
+    }
+
+    /// Adds a vertex to the Write-Ahead Log, so it can be restored after a restart.
+    ///
+    /// Pings are not recorded: They don't need to be restored.
+    fn record_vertex(&mut self, vertex: &Vertex<C>) {
+        if matches!(vertex, Vertex::Ping(_)) {
+            return;
+        }
+        let entry = Entry::Vertex(vertex.clone());
+        if let Some(Err(err)) = self.write_wal.as_mut().map(|ww| ww.record_entry(&entry)) {
+            self.write_wal = None;
+            error!(%err, "could not record a vertex to the WAL; disabling it");
+        }
+    }
+
     fn initialize_timers(
         now: Timestamp,
         era_start_time: Timestamp,
@@ -241,9 +386,13 @@ impl<C: Context + 'static> HighwayProtocol<C> {
     fn process_av_effect(&mut self, effect: AvEffect<C>, now: Timestamp) -> ProtocolOutcomes<C> {
         match effect {
             AvEffect::NewVertex(vv) => {
+                self.record_vertex(vv.inner());
+                if let Some(unit_hash) = vv.inner().unit_hash() {
+                    self.record_unit_in_timeline(&unit_hash, now);
+                }
                 self.log_unit_size(vv.inner(), "sending new unit");
                 self.calculate_round_length(&vv, now);
-                self.process_new_vertex(vv)
+                self.process_new_vertex(vv, now)
             }
             AvEffect::ScheduleTimer(timestamp) => {
                 vec![ProtocolOutcome::ScheduleTimer(
@@ -261,7 +410,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         }
     }
 
-    fn process_new_vertex(&mut self, vv: ValidVertex<C>) -> ProtocolOutcomes<C> {
+    fn process_new_vertex(&mut self, vv: ValidVertex<C>, now: Timestamp) -> ProtocolOutcomes<C> {
         let mut outcomes = Vec::new();
         if let Vertex::Evidence(ev) = vv.inner() {
             let v_id = self
@@ -276,13 +425,28 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         outcomes.push(ProtocolOutcome::CreatedGossipMessage(
             SerializedMessage::from_message(&msg),
         ));
-        outcomes.extend(self.detect_finality());
+        outcomes.extend(self.detect_finality(now));
         outcomes
     }
 
-    fn detect_finality(&mut self) -> ProtocolOutcomes<C> {
+    fn detect_finality(&mut self, now: Timestamp) -> ProtocolOutcomes<C> {
         let faulty_weight = match self.finality_detector.run(&self.highway) {
-            Ok(iter) => return iter.map(ProtocolOutcome::FinalizedBlock).collect(),
+            Ok(iter) => {
+                let finalized_blocks = iter.collect_vec();
+                for finalized_block in &finalized_blocks {
+                    // Blocks are proposed at the beginning of a round, so the timestamp is the
+                    // round ID.
+                    self.round_timelines.record_finalization(
+                        finalized_block.timestamp.millis(),
+                        finalized_block.proposer.clone(),
+                        now,
+                    );
+                }
+                return finalized_blocks
+                    .into_iter()
+                    .map(ProtocolOutcome::FinalizedBlock)
+                    .collect();
+            }
             Err(FttExceeded(weight)) => weight.0,
         };
         error!(
@@ -370,7 +534,7 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         // waiting for are now satisfied, and try adding the pending vertices as well.
         outcomes.extend(self.synchronizer.remove_satisfied_deps(&self.highway));
         // Check whether any new blocks were finalized.
-        outcomes.extend(self.detect_finality());
+        outcomes.extend(self.detect_finality(now));
         outcomes
     }
 
@@ -425,7 +589,12 @@ impl<C: Context + 'static> HighwayProtocol<C> {
         // round has finished, we now have all the vertices from that round in the state, and no
         // newer ones.
         self.calculate_round_length(&vv, now);
+        self.record_vertex(vv.inner());
+        let maybe_unit_hash = vv.inner().unit_hash();
         let av_effects = self.highway.add_valid_vertex(vv, now);
+        if let Some(unit_hash) = maybe_unit_hash {
+            self.record_unit_in_timeline(&unit_hash, now);
+        }
         // Once vertex is added to the state, we can remove it from the cache.
         self.pvv_cache.remove(&vertex_id);
         outcomes.extend(self.process_av_effects(av_effects, now));
@@ -1032,7 +1201,7 @@ where
                 .flat_map(|(vv, _)| self.add_valid_vertex(vv, now))
                 .collect_vec();
             outcomes.extend(self.synchronizer.remove_satisfied_deps(&self.highway));
-            outcomes.extend(self.detect_finality());
+            outcomes.extend(self.detect_finality(now));
             outcomes
         } else {
             // TODO: Report proposer as faulty?
@@ -1136,6 +1305,10 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         self.highway.next_round_length()
     }
+
+    fn take_complete_rounds(&mut self) -> Vec<RoundTimeline<C::ValidatorId>> {
+        self.round_timelines.take_complete()
+    }
 }
 
 /// Maximum possible rounds in one era.
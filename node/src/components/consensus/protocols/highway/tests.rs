@@ -1,4 +1,4 @@
-use std::{collections::BTreeSet, sync::Arc};
+use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
 
 use casper_types::{testing::TestRng, PublicKey, TimeDiff, Timestamp, U512};
 
@@ -6,7 +6,7 @@ use crate::{
     components::consensus::{
         cl_context::{ClContext, Keypair},
         config::Config,
-        consensus_protocol::{ConsensusProtocol, ProtocolOutcome},
+        consensus_protocol::{ConsensusProtocol, ProtocolOutcome, ProtocolOutcomes},
         highway_core::{
             highway::{SignedWireUnit, Vertex, WireUnit},
             highway_testing,
@@ -56,6 +56,31 @@ pub(crate) fn new_test_highway_protocol<I1, I2, T>(
     weights: I1,
     init_faulty: I2,
 ) -> Box<dyn ConsensusProtocol<ClContext>>
+where
+    I1: IntoIterator<Item = (PublicKey, T)>,
+    I2: IntoIterator<Item = PublicKey>,
+    T: Into<U512>,
+{
+    let (hw_proto, outcomes) = new_test_highway_protocol_with_wal(weights, init_faulty, None);
+    // We expect three messages:
+    // * log participation timer,
+    // * log synchronizer queue length timer,
+    // * purge synchronizer queue timer
+    // If there are more, the tests might need to handle them.
+    assert_eq!(3, outcomes.len());
+    hw_proto
+}
+
+/// Returns a new Highway instance that records its vertices in the given Write-Ahead Log, and the
+/// outcomes of restoring its state from that log.
+fn new_test_highway_protocol_with_wal<I1, I2, T>(
+    weights: I1,
+    init_faulty: I2,
+    wal_file: Option<PathBuf>,
+) -> (
+    Box<dyn ConsensusProtocol<ClContext>>,
+    ProtocolOutcomes<ClContext>,
+)
 where
     I1: IntoIterator<Item = (PublicKey, T)>,
     I2: IntoIterator<Item = PublicKey>,
@@ -77,7 +102,7 @@ where
     };
     // Timestamp of the genesis era start and test start.
     let start_timestamp: Timestamp = 0.into();
-    let (hw_proto, outcomes) = HighwayProtocol::<ClContext>::new_boxed(
+    HighwayProtocol::<ClContext>::new_boxed(
         ClContext::hash(INSTANCE_ID_DATA),
         weights.into_iter().collect(),
         &init_faulty.into_iter().collect(),
@@ -88,14 +113,8 @@ where
         start_timestamp,
         0,
         start_timestamp,
-    );
-    // We expect three messages:
-    // * log participation timer,
-    // * log synchronizer queue length timer,
-    // * purge synchronizer queue timer
-    // If there are more, the tests might need to handle them.
-    assert_eq!(3, outcomes.len());
-    hw_proto
+        wal_file,
+    )
 }
 
 pub(crate) const N: Observation<ClContext> = Observation::None;
@@ -121,7 +140,7 @@ fn send_a_wire_unit_with_too_small_a_round_exp() {
     };
     let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
     let highway_message: HighwayMessage<ClContext> = HighwayMessage::NewVertex(Vertex::Unit(
-        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair),
+        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair).expect("should sign"),
     ));
     let mut highway_protocol = new_test_highway_protocol(validators, vec![]);
     let sender = *ALICE_NODE_ID;
@@ -143,7 +162,13 @@ fn send_a_valid_wire_unit() {
         panorama,
         creator,
         instance_id: ClContext::hash(INSTANCE_ID_DATA),
-        value: Some(Arc::new(BlockPayload::new(vec![], vec![], vec![], false))),
+        value: Some(Arc::new(BlockPayload::new(
+            vec![],
+            vec![],
+            vec![],
+            false,
+            None,
+        ))),
         seq_number,
         timestamp: now,
         round_exp: 0,
@@ -151,7 +176,7 @@ fn send_a_valid_wire_unit() {
     };
     let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
     let highway_message: HighwayMessage<ClContext> = HighwayMessage::NewVertex(Vertex::Unit(
-        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair),
+        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair).expect("should sign"),
     ));
 
     let mut highway_protocol = new_test_highway_protocol(validators, vec![]);
@@ -186,7 +211,7 @@ fn detect_doppelganger() {
     let instance_id = ClContext::hash(INSTANCE_ID_DATA);
     let round_exp = 0;
     let now = Timestamp::zero();
-    let value = Arc::new(BlockPayload::new(vec![], vec![], vec![], false));
+    let value = Arc::new(BlockPayload::new(vec![], vec![], vec![], false, None));
     let wunit: WireUnit<ClContext> = WireUnit {
         panorama,
         creator,
@@ -199,7 +224,7 @@ fn detect_doppelganger() {
     };
     let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
     let highway_message: HighwayMessage<ClContext> = HighwayMessage::NewVertex(Vertex::Unit(
-        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair),
+        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair).expect("should sign"),
     ));
     let mut highway_protocol = new_test_highway_protocol(validators, vec![]);
     // Activate ALICE as validator.
@@ -233,3 +258,60 @@ fn max_rounds_per_era_returns_the_correct_value_for_prod_chainspec_value() {
 
     assert_eq!(219, max_rounds_per_era);
 }
+
+#[test]
+fn restore_vertices_from_wal() {
+    let mut rng = TestRng::new();
+    let dir = tempfile::tempdir().unwrap();
+    let wal_file = dir.path().join("highway_wal.dat");
+    let creator: ValidatorIndex = ValidatorIndex(0);
+    let validators = vec![(ALICE_PUBLIC_KEY.clone(), 100)];
+    let state: State<ClContext> = new_test_state(validators.iter().map(|(_pk, w)| *w), 0);
+    let panorama: Panorama<ClContext> = Panorama::from(vec![N]);
+    let seq_number = panorama.next_seq_num(&state, creator);
+    let now = Timestamp::zero();
+    let wunit: WireUnit<ClContext> = WireUnit {
+        panorama,
+        creator,
+        instance_id: ClContext::hash(INSTANCE_ID_DATA),
+        value: Some(Arc::new(BlockPayload::new(
+            vec![],
+            vec![],
+            vec![],
+            false,
+            None,
+        ))),
+        seq_number,
+        timestamp: now,
+        round_exp: 0,
+        endorsed: BTreeSet::new(),
+    };
+    let alice_keypair: Keypair = Keypair::from(Arc::clone(&*ALICE_SECRET_KEY));
+    let highway_message: HighwayMessage<ClContext> = HighwayMessage::NewVertex(Vertex::Unit(
+        SignedWireUnit::new(wunit.into_hashed(), &alice_keypair).expect("should sign"),
+    ));
+
+    let (mut highway_protocol, _) =
+        new_test_highway_protocol_with_wal(validators.clone(), vec![], Some(wal_file.clone()));
+    let sender = *ALICE_NODE_ID;
+    let msg = SerializedMessage::from_message(&highway_message);
+    let mut outcomes = highway_protocol.handle_message(&mut rng, sender, msg, now);
+    while let Some(outcome) = outcomes.pop() {
+        if let ProtocolOutcome::QueueAction(ACTION_ID_VERTEX) = outcome {
+            outcomes.extend(highway_protocol.handle_action(ACTION_ID_VERTEX, now))
+        }
+    }
+    drop(highway_protocol);
+
+    // After a restart, the unit is restored from the WAL, and its block is finalized again.
+    let (_, outcomes) = new_test_highway_protocol_with_wal(validators, vec![], Some(wal_file));
+    assert!(outcomes
+        .iter()
+        .any(|outcome| matches!(outcome, ProtocolOutcome::HandledProposedBlock(_))));
+    assert!(outcomes
+        .iter()
+        .any(|outcome| matches!(outcome, ProtocolOutcome::FinalizedBlock(_))));
+    assert!(!outcomes
+        .iter()
+        .any(|outcome| matches!(outcome, ProtocolOutcome::CreatedGossipMessage(_))));
+}
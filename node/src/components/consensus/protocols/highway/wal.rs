@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::components::consensus::{highway_core::highway::Vertex, traits::Context};
+
+/// An entry in the Write-Ahead Log, storing a vertex we had added to our protocol state.
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(bound(
+    serialize = "C::Hash: Serialize",
+    deserialize = "C::Hash: Deserialize<'de>",
+))]
+pub(crate) enum Entry<C: Context> {
+    /// A vertex we created or received from a peer.
+    Vertex(Vertex<C>),
+}
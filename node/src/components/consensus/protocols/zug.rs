@@ -71,7 +71,7 @@ use std::{
     cmp::Reverse,
     collections::{btree_map, BTreeMap, HashMap, HashSet},
     fmt::Debug,
-    iter,
+    iter, mem,
     path::PathBuf,
 };
 
@@ -91,7 +91,11 @@ use crate::{
             ProtocolOutcomes, TerminalBlockData,
         },
         era_supervisor::SerializedMessage,
-        protocols,
+        protocols::{
+            self,
+            wal::{ReadWal, WriteWal},
+        },
+        round_timeline::{RoundTimeline, RoundTimelines},
         traits::{ConsensusValueT, Context},
         utils::{ValidatorIndex, ValidatorMap, Validators, Weight},
         ActionId, LeaderSequence, TimerId,
@@ -105,7 +109,7 @@ use params::Params;
 use participation::{Participation, ParticipationStatus};
 use proposal::{HashedProposal, Proposal};
 use round::Round;
-use wal::{Entry, ReadWal, WriteWal};
+use wal::Entry;
 
 pub(crate) use message::{Message, SyncRequest};
 
@@ -120,6 +124,10 @@ const TIMER_ID_LOG_PARTICIPATION: TimerId = TimerId(2);
 /// haven't started yet.
 const MAX_FUTURE_ROUNDS: u32 = 7200; // Don't drop messages in 2-hour eras with 1-second rounds.
 
+/// Delay after which signing a message is retried if it failed, e.g. because a remote signer was
+/// unavailable.
+const SIGNING_RETRY_DELAY: TimeDiff = TimeDiff::from_seconds(1);
+
 /// Identifies a single [`Round`] in the protocol.
 pub(crate) type RoundId = u32;
 
@@ -147,6 +155,18 @@ impl<C: Context> Debug for ActiveValidator<C> {
     }
 }
 
+/// Something of ours that could not be signed yet.
+#[derive(Debug, DataSize)]
+enum Unsigned<C>
+where
+    C: Context,
+{
+    /// A message with the given content in the given round.
+    Message(RoundId, Content<C>),
+    /// A proposal in the given round, together with its echo.
+    Proposal(RoundId, Proposal<C>),
+}
+
 /// Contains the state required for the protocol.
 #[derive(Debug, DataSize)]
 pub(crate) struct Zug<C>
@@ -198,9 +218,13 @@ where
     /// `update`.
     next_scheduled_update: Timestamp,
     /// The write-ahead log to prevent honest nodes from double-signing upon restart.
-    write_wal: Option<WriteWal<C>>,
+    write_wal: Option<WriteWal<Entry<C>>>,
     /// The rewards based on the finalized rounds so far.
     rewards: BTreeMap<C::ValidatorId, u64>,
+    /// The timelines of the rounds, as observed by us.
+    round_timelines: RoundTimelines<C::ValidatorId>,
+    /// Messages and proposals we failed to sign, to be retried on the next update.
+    unsigned: Vec<Unsigned<C>>,
 }
 
 impl<C: Context + 'static> Zug<C> {
@@ -268,6 +292,8 @@ impl<C: Context + 'static> Zug<C> {
             next_scheduled_update: Timestamp::MAX,
             write_wal: None,
             rewards,
+            round_timelines: RoundTimelines::default(),
+            unsigned: Vec::new(),
         }
     }
 
@@ -336,6 +362,12 @@ impl<C: Context + 'static> Zug<C> {
         self.active_validator.as_ref().map(|av| av.idx.0)
     }
 
+    /// Returns the timeline of the given round, or `None` if the round is already complete.
+    fn round_timeline(&mut self, round_id: RoundId) -> Option<&mut RoundTimeline<C::ValidatorId>> {
+        let leader = self.validators.id(self.leader(round_id))?.clone();
+        self.round_timelines.round_mut(u64::from(round_id), leader)
+    }
+
     /// Prints a log statement listing the inactive and faulty validators.
     fn log_participation(&self) {
         let mut inactive_w: u64 = 0;
@@ -646,13 +678,25 @@ impl<C: Context + 'static> Zug<C> {
         if already_signed {
             return None;
         }
-        let signed_msg = SignedMessage::sign_new(
+        let signed_msg = match SignedMessage::sign_new(
             round_id,
             *self.instance_id(),
             content,
             validator_idx,
             secret_key,
-        );
+        ) {
+            Some(signed_msg) => signed_msg,
+            None => {
+                warn!(
+                    our_idx = validator_idx.0,
+                    %round_id,
+                    ?content,
+                    "failed to sign message, will retry"
+                );
+                self.unsigned.push(Unsigned::Message(round_id, content));
+                return None;
+            }
+        };
         // We only return the new message if we are able to record it. If that fails we
         // wouldn't know about our own message after a restart and risk double-signing.
         if self.record_entry(&Entry::SignedMessage(signed_msg.clone()))
@@ -1071,7 +1115,14 @@ impl<C: Context + 'static> Zug<C> {
             );
         } else {
             self.record_entry(&Entry::SignedMessage(signed_msg.clone()));
+            let maybe_vote_round_id =
+                matches!(signed_msg.content, Content::Vote(true)).then_some(signed_msg.round_id);
             if self.add_content(signed_msg) {
+                if let Some(timeline) =
+                    maybe_vote_round_id.and_then(|round_id| self.round_timeline(round_id))
+                {
+                    timeline.record_vote(validator_id, now);
+                }
                 return self.update(now);
             }
         }
@@ -1232,6 +1283,10 @@ impl<C: Context + 'static> Zug<C> {
             return vec![];
         }
 
+        if let Some(timeline) = self.round_timeline(round_id) {
+            timeline.record_proposal(now);
+        }
+
         let ancestor_values = if let Some(parent_round_id) = hashed_prop.maybe_parent_round_id() {
             if let Some(ancestor_values) = self.ancestor_values(parent_round_id) {
                 ancestor_values
@@ -1316,7 +1371,7 @@ impl<C: Context + 'static> Zug<C> {
     pub(crate) fn open_wal(&mut self, wal_file: PathBuf, now: Timestamp) -> ProtocolOutcomes<C> {
         let our_idx = self.our_idx();
         // Open the file for reading.
-        let mut read_wal = match ReadWal::<C>::new(&wal_file) {
+        let mut read_wal = match ReadWal::<Entry<C>>::new(&wal_file) {
             Ok(read_wal) => read_wal,
             Err(err) => {
                 error!(our_idx, %err, "could not create a ReadWal using this file");
@@ -1560,6 +1615,34 @@ impl<C: Context + 'static> Zug<C> {
             }
         }
         self.maybe_dirty_round_id = None;
+        if !self.unsigned.is_empty() {
+            outcomes.extend(self.schedule_update(now.saturating_add(SIGNING_RETRY_DELAY)));
+        }
+        outcomes
+    }
+
+    /// Retries signing the messages and proposals we failed to sign before.
+    ///
+    /// Does not call `update`!
+    fn retry_signing(&mut self) -> ProtocolOutcomes<C> {
+        let mut outcomes = vec![];
+        for unsigned in mem::take(&mut self.unsigned) {
+            match unsigned {
+                Unsigned::Message(round_id, content) => {
+                    outcomes.extend(self.create_and_gossip_message(round_id, content));
+                }
+                Unsigned::Proposal(round_id, proposal) if round_id == self.current_round => {
+                    outcomes.extend(self.create_echo_and_proposal(proposal));
+                }
+                Unsigned::Proposal(round_id, _) => {
+                    info!(
+                        our_idx = self.our_idx(),
+                        %round_id,
+                        "dropping unsigned proposal from a past round"
+                    );
+                }
+            }
+        }
         outcomes
     }
 
@@ -1579,7 +1662,18 @@ impl<C: Context + 'static> Zug<C> {
                 self.update_proposal_timeout(now);
             }
             // Vote for finalizing this proposal.
-            outcomes.extend(self.create_and_gossip_message(round_id, Content::Vote(true)));
+            let vote_outcomes = self.create_and_gossip_message(round_id, Content::Vote(true));
+            if !vote_outcomes.is_empty() {
+                let our_id = self
+                    .active_validator
+                    .as_ref()
+                    .and_then(|av| self.validators.id(av.idx))
+                    .cloned();
+                if let (Some(our_id), Some(timeline)) = (our_id, self.round_timeline(round_id)) {
+                    timeline.record_vote(our_id, now);
+                }
+            }
+            outcomes.extend(vote_outcomes);
             // Proposed descendants of this proposal can now be validated.
             if let Some(proposals) = self.proposals_waiting_for_parent.remove(&round_id) {
                 let ancestor_values = self
@@ -1604,6 +1698,9 @@ impl<C: Context + 'static> Zug<C> {
                 .current_round_start
                 .saturating_add(self.proposal_timeout());
             if now >= current_timeout {
+                if let Some(timeline) = self.round_timeline(round_id) {
+                    timeline.record_timeout(now);
+                }
                 outcomes.extend(self.create_and_gossip_message(round_id, Content::Vote(false)));
                 self.update_proposal_timeout(now);
             } else if self.faults.contains_key(&self.leader(round_id)) {
@@ -1646,7 +1743,7 @@ impl<C: Context + 'static> Zug<C> {
 
         // If the round has an accepted proposal and is committed, it is finalized.
         if self.has_accepted_proposal(round_id) && self.is_committed_round(round_id) {
-            outcomes.extend(self.finalize_round(round_id));
+            outcomes.extend(self.finalize_round(round_id, now));
         }
         outcomes
     }
@@ -1783,7 +1880,7 @@ impl<C: Context + 'static> Zug<C> {
 
     /// Finalizes the round, notifying the rest of the node of the finalized block
     /// if it contained one.
-    fn finalize_round(&mut self, round_id: RoundId) -> ProtocolOutcomes<C> {
+    fn finalize_round(&mut self, round_id: RoundId, now: Timestamp) -> ProtocolOutcomes<C> {
         let mut outcomes = vec![];
         if round_id < self.first_non_finalized_round_id {
             return outcomes; // This round was already finalized.
@@ -1801,7 +1898,7 @@ impl<C: Context + 'static> Zug<C> {
         };
         if let Some(parent_round_id) = proposal.maybe_parent_round_id() {
             // Output the parent first if it isn't already finalized.
-            outcomes.extend(self.finalize_round(parent_round_id));
+            outcomes.extend(self.finalize_round(parent_round_id, now));
         }
         for prune_round_id in self.first_non_finalized_round_id..round_id {
             info!(
@@ -1812,6 +1909,10 @@ impl<C: Context + 'static> Zug<C> {
             self.round_mut(prune_round_id).prune_skipped();
         }
         self.first_non_finalized_round_id = round_id.saturating_add(1);
+        if let Some(leader) = self.validators.id(self.leader(round_id)).cloned() {
+            self.round_timelines
+                .record_finalization(u64::from(round_id), leader, now);
+        }
         let value = if let Some(block) = proposal.maybe_block() {
             block.clone()
         } else {
@@ -1905,6 +2006,14 @@ impl<C: Context + 'static> Zug<C> {
         let echo = if let Some(echo) = self.create_message(round_id, echo_content) {
             echo
         } else {
+            if matches!(
+                self.unsigned.last(),
+                Some(Unsigned::Message(r_id, content)) if *r_id == round_id && *content == echo_content
+            ) {
+                // Retry signing the echo together with the proposal, so it isn't sent on its own.
+                self.unsigned.pop();
+                self.unsigned.push(Unsigned::Proposal(round_id, proposal));
+            }
             return vec![];
         };
         let prop_msg = Message::Proposal {
@@ -2179,8 +2288,10 @@ where
                 if timestamp >= self.next_scheduled_update {
                     self.next_scheduled_update = Timestamp::MAX;
                 }
+                let mut outcomes = self.retry_signing();
                 self.mark_dirty(self.current_round);
-                self.update(now)
+                outcomes.extend(self.update(now));
+                outcomes
             }
             TIMER_ID_LOG_PARTICIPATION => {
                 self.log_participation();
@@ -2252,6 +2363,9 @@ where
             .map(|(idx, _)| idx)
             .filter(|idx| self.active[*idx].is_none() && !self.faults.contains_key(idx));
         let proposal = Proposal::with_block(&proposed_block, maybe_parent_round_id, inactive);
+        if let Some(timeline) = self.round_timeline(self.current_round) {
+            timeline.record_proposal(now);
+        }
         let mut outcomes = self.create_echo_and_proposal(proposal);
         outcomes.extend(self.update(now));
         outcomes
@@ -2430,6 +2544,10 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         Some(self.params.min_block_time())
     }
+
+    fn take_complete_rounds(&mut self) -> Vec<RoundTimeline<C::ValidatorId>> {
+        self.round_timelines.take_complete()
+    }
 }
 
 mod specimen_support {
@@ -2556,6 +2674,7 @@ mod specimen_support {
                 LargestSpecimen::largest_specimen(estimator, cache),
                 &LargestSpecimen::largest_specimen(estimator, cache),
             )
+            .expect("should sign")
         }
     }
 
@@ -315,7 +315,8 @@ impl ZugValidator {
                                     )),
                                     signed_msg.validator_idx,
                                     &TestSecret(signed_msg.validator_idx.0.into()),
-                                );
+                                )
+                                .expect("should sign");
                                 vec![
                                     ZugMessage::GossipMessage(SerializedMessage::from_message(
                                         &ZugProtocolMessage::Signed(conflicting_message),
@@ -330,7 +331,8 @@ impl ZugValidator {
                                     Content::<TestContext>::Vote(!vote),
                                     signed_msg.validator_idx,
                                     &TestSecret(signed_msg.validator_idx.0.into()),
-                                );
+                                )
+                                .expect("should sign");
                                 vec![
                                     ZugMessage::GossipMessage(SerializedMessage::from_message(
                                         &ZugProtocolMessage::Signed(conflicting_message),
@@ -1011,8 +1013,8 @@ impl ValidatorSecret for TestSecret {
     type Hash = HashWrapper;
     type Signature = SignatureWrapper;
 
-    fn sign(&self, data: &Self::Hash) -> Self::Signature {
-        SignatureWrapper(data.0 + self.0)
+    fn sign(&self, data: &Self::Hash) -> Option<Self::Signature> {
+        Some(SignatureWrapper(data.0 + self.0))
     }
 }
 
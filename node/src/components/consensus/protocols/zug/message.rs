@@ -8,6 +8,7 @@ use either::Either;
 use crate::{
     components::consensus::{
         protocols::zug::{Proposal, RoundId},
+        slashing_protection::SigningSlot,
         traits::{ConsensusNetworkMessage, Context, ValidatorSecret},
         utils::ValidatorIndex,
     },
@@ -128,22 +129,31 @@ where
 }
 
 impl<C: Context> SignedMessage<C> {
-    /// Creates a new signed message with a valid signature.
+    /// Creates a new signed message with a valid signature, or `None` if it could not be signed.
     pub(crate) fn sign_new(
         round_id: RoundId,
         instance_id: C::InstanceId,
         content: Content<C>,
         validator_idx: ValidatorIndex,
         secret: &C::ValidatorSecret,
-    ) -> SignedMessage<C> {
+    ) -> Option<SignedMessage<C>> {
         let hash = Self::hash_fields(round_id, &instance_id, &content, validator_idx);
-        SignedMessage {
+        // Each validator must sign at most one echo and one vote per round.
+        let kind = match content {
+            Content::Echo(_) => 0,
+            Content::Vote(_) => 1,
+        };
+        let slot = SigningSlot::Round {
+            round_id: round_id.into(),
+            kind,
+        };
+        Some(SignedMessage {
             round_id,
             instance_id,
             content,
             validator_idx,
-            signature: secret.sign(&hash),
-        }
+            signature: secret.sign_slot(slot, &hash)?,
+        })
     }
 
     /// Creates a new signed message with the alternative content and signature.
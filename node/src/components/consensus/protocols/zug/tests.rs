@@ -78,6 +78,7 @@ fn create_signed_message(
     let validator_idx = validators.get_index(keypair.public_key()).unwrap();
     let instance_id = ClContext::hash(INSTANCE_ID_DATA);
     SignedMessage::sign_new(round_id, instance_id, content, validator_idx, keypair)
+        .expect("should sign")
 }
 
 /// Creates a `Message::Signed`.
@@ -318,7 +319,7 @@ fn expect_timer(outcomes: &ProtocolOutcomes<ClContext>, timestamp: Timestamp, ti
 
 /// Creates a new payload with the given random bit and no deploys or transfers.
 fn new_payload(random_bit: bool) -> Arc<BlockPayload> {
-    Arc::new(BlockPayload::new(vec![], vec![], vec![], random_bit))
+    Arc::new(BlockPayload::new(vec![], vec![], vec![], random_bit, None))
 }
 
 fn vote(v: bool) -> Content<ClContext> {
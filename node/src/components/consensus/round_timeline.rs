@@ -0,0 +1,451 @@
+//! Timelines of consensus rounds, as observed by this node: when the proposal and each vote
+//! arrived, whether the round timed out, and when it was finalized.
+//!
+//! The protocols record the events of each round in their [`RoundTimelines`]. Once a round is
+//! complete, the era supervisor adds it to the [`RoundHistory`], which keeps the most recent
+//! rounds in memory for the `info_get_consensus_rounds` RPC and appends them to a rolling file in
+//! the background.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    mem,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::utils::display_error;
+
+use casper_types::{EraId, PublicKey, Timestamp};
+
+const DEFAULT_MAX_ROUNDS: usize = 1000;
+const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Round timeline configuration.
+/// *Note*: This is *not* protocol configuration that has to be the same on all nodes.
+#[derive(DataSize, Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The number of most recent completed rounds kept in memory.
+    pub max_rounds: usize,
+    /// The size in bytes at which the round timeline file is rotated. 0 means the timelines are
+    /// not written to disk.
+    pub max_file_size: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+}
+
+/// A vote for a round's proposal, and when we received it.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct VoteArrival<VID> {
+    /// The validator who voted.
+    pub(crate) validator: VID,
+    /// When we received the vote.
+    pub(crate) time: Timestamp,
+}
+
+/// The timeline of a single round, as observed by this node.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RoundTimeline<VID> {
+    /// The round ID: the round number in Zug, and the round's start time in milliseconds in
+    /// Highway.
+    pub(crate) round_id: u64,
+    /// The round's leader.
+    pub(crate) leader: VID,
+    /// When we received or created the round's proposal.
+    pub(crate) proposal_time: Option<Timestamp>,
+    /// The votes for the round's proposal, in the order in which they arrived. In Highway, every
+    /// validator's first unit in the round other than the proposal counts as a vote.
+    pub(crate) votes: Vec<VoteArrival<VID>>,
+    /// When our proposal timeout for the round expired. Highway has no timeouts.
+    pub(crate) timeout_time: Option<Timestamp>,
+    /// When the round's proposal was finalized.
+    pub(crate) finalization_time: Option<Timestamp>,
+}
+
+impl<VID: PartialEq> RoundTimeline<VID> {
+    fn new(round_id: u64, leader: VID) -> Self {
+        RoundTimeline {
+            round_id,
+            leader,
+            proposal_time: None,
+            votes: vec![],
+            timeout_time: None,
+            finalization_time: None,
+        }
+    }
+
+    /// Records the arrival of the round's proposal, unless we already had it.
+    pub(crate) fn record_proposal(&mut self, now: Timestamp) {
+        self.proposal_time.get_or_insert(now);
+    }
+
+    /// Records the arrival of the validator's vote, unless we already had one.
+    pub(crate) fn record_vote(&mut self, validator: VID, now: Timestamp) {
+        if !self.votes.iter().any(|vote| vote.validator == validator) {
+            self.votes.push(VoteArrival {
+                validator,
+                time: now,
+            });
+        }
+    }
+
+    /// Records the expiry of our proposal timeout, unless it already expired before.
+    pub(crate) fn record_timeout(&mut self, now: Timestamp) {
+        self.timeout_time.get_or_insert(now);
+    }
+}
+
+/// The timelines of a protocol instance's rounds.
+///
+/// A round is complete once it or a later round was finalized.
+#[derive(DataSize, Debug)]
+pub(crate) struct RoundTimelines<VID> {
+    /// The rounds that are not complete yet, by round ID.
+    open: BTreeMap<u64, RoundTimeline<VID>>,
+    /// The complete rounds that have not been taken yet.
+    complete: Vec<RoundTimeline<VID>>,
+    /// The lowest round ID that is not complete yet.
+    first_open_round_id: u64,
+}
+
+impl<VID> Default for RoundTimelines<VID> {
+    fn default() -> Self {
+        RoundTimelines {
+            open: BTreeMap::new(),
+            complete: vec![],
+            first_open_round_id: 0,
+        }
+    }
+}
+
+impl<VID: PartialEq> RoundTimelines<VID> {
+    /// Returns the timeline of the given round, or `None` if the round is already complete.
+    pub(crate) fn round_mut(
+        &mut self,
+        round_id: u64,
+        leader: VID,
+    ) -> Option<&mut RoundTimeline<VID>> {
+        if round_id < self.first_open_round_id {
+            return None;
+        }
+        Some(
+            self.open
+                .entry(round_id)
+                .or_insert_with(|| RoundTimeline::new(round_id, leader)),
+        )
+    }
+
+    /// Records that the given round's proposal was finalized. The round and all earlier ones are
+    /// complete now.
+    pub(crate) fn record_finalization(&mut self, round_id: u64, leader: VID, now: Timestamp) {
+        if let Some(round) = self.round_mut(round_id, leader) {
+            round.finalization_time.get_or_insert(now);
+        }
+        let later_rounds = self.open.split_off(&round_id.saturating_add(1));
+        let complete_rounds = mem::replace(&mut self.open, later_rounds);
+        self.complete.extend(complete_rounds.into_values());
+        self.first_open_round_id = self.first_open_round_id.max(round_id.saturating_add(1));
+    }
+
+    /// Removes and returns the rounds that were completed since the last call.
+    pub(crate) fn take_complete(&mut self) -> Vec<RoundTimeline<VID>> {
+        mem::take(&mut self.complete)
+    }
+}
+
+/// A complete round in a particular era.
+#[derive(Clone, DataSize, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ConsensusRound {
+    /// The era the round belongs to.
+    pub(crate) era_id: EraId,
+    /// The round's timeline.
+    pub(crate) timeline: RoundTimeline<PublicKey>,
+}
+
+/// The most recent complete rounds of all eras.
+///
+/// The rounds are kept in memory, up to the configured number, and appended to a file as JSON
+/// lines. Once that file exceeds the configured size it is renamed, replacing the previous one, and
+/// a new file is started.
+///
+/// Writing to the file is left to the [`RoundHistoryWrite`]s returned by [`RoundHistory::add`].
+#[derive(DataSize, Debug)]
+pub(crate) struct RoundHistory {
+    /// The most recent complete rounds, oldest first.
+    recent: VecDeque<ConsensusRound>,
+    config: Config,
+    /// The file the rounds are written to.
+    #[data_size(skip)]
+    file: Arc<RoundFile>,
+}
+
+/// The file complete rounds are appended to, and the lines not written to it yet.
+#[derive(Debug)]
+struct RoundFile {
+    /// The path of the file.
+    path: PathBuf,
+    /// The size in bytes at which the file is rotated.
+    max_size: u64,
+    /// The JSON lines of the rounds not taken by a write yet, oldest first.
+    pending: Mutex<Vec<u8>>,
+    /// Held while writing, so that concurrent writes append the rounds in order.
+    write_lock: Mutex<()>,
+}
+
+/// A pending write of complete rounds to the round timeline file.
+#[derive(Debug)]
+#[must_use]
+pub(crate) struct RoundHistoryWrite {
+    file: Arc<RoundFile>,
+}
+
+impl RoundHistory {
+    /// Creates a new round history, writing to the given file.
+    pub(crate) fn new(config: Config, path: PathBuf) -> Self {
+        let file = Arc::new(RoundFile {
+            path,
+            max_size: config.max_file_size,
+            pending: Mutex::new(Vec::new()),
+            write_lock: Mutex::new(()),
+        });
+        RoundHistory {
+            recent: VecDeque::new(),
+            config,
+            file,
+        }
+    }
+
+    /// Adds a complete round to the history.
+    ///
+    /// Returns the write appending it to the file, unless the file is disabled or a write that will
+    /// pick up the round is already pending.
+    pub(crate) fn add(&mut self, round: ConsensusRound) -> Option<RoundHistoryWrite> {
+        let maybe_write = if self.config.max_file_size > 0 {
+            self.append_pending(&round)
+        } else {
+            None
+        };
+        if self.config.max_rounds == 0 {
+            return maybe_write;
+        }
+        while self.recent.len() >= self.config.max_rounds {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(round);
+        maybe_write
+    }
+
+    /// Returns the most recent complete rounds, oldest first.
+    pub(crate) fn recent(&self) -> Vec<ConsensusRound> {
+        self.recent.iter().cloned().collect()
+    }
+
+    /// Adds the round to the lines to be written, returning a write if none is pending.
+    fn append_pending(&self, round: &ConsensusRound) -> Option<RoundHistoryWrite> {
+        let mut line = match serde_json::to_vec(round) {
+            Ok(line) => line,
+            Err(ref error) => {
+                warn!(
+                    error = display_error(error),
+                    "could not serialize round timeline"
+                );
+                return None;
+            }
+        };
+        line.push(b'\n');
+        let mut pending = self
+            .file
+            .pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // If there are lines already, a write is pending that hasn't taken them yet.
+        let write_pending = !pending.is_empty();
+        pending.extend(line);
+        (!write_pending).then(|| RoundHistoryWrite {
+            file: Arc::clone(&self.file),
+        })
+    }
+}
+
+impl RoundHistoryWrite {
+    /// Writes the pending rounds on a thread dedicated to blocking operations.
+    pub(crate) async fn run_blocking(self) {
+        if let Err(ref error) = tokio::task::spawn_blocking(move || self.run()).await {
+            warn!(
+                error = display_error(error),
+                "failed to join round timeline write"
+            );
+        }
+    }
+
+    /// Appends the pending rounds to the file, rotating it first if it is full, and logs any error.
+    ///
+    /// Performs blocking I/O.
+    pub(crate) fn run(self) {
+        let file = &self.file;
+        let _write_guard = file
+            .write_lock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let lines = mem::take(
+            &mut *file
+                .pending
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+        if lines.is_empty() {
+            return;
+        }
+        if let Err(ref error) = file.write(&lines) {
+            warn!(
+                path = %file.path.display(),
+                error = display_error(error),
+                "could not write round timeline"
+            );
+        }
+    }
+}
+
+impl RoundFile {
+    /// Appends the lines to the file, rotating it first if it is full.
+    fn write(&self, lines: &[u8]) -> io::Result<()> {
+        match fs::metadata(&self.path) {
+            Ok(metadata) if metadata.len() >= self.max_size => {
+                let mut rotated_path = OsString::from(self.path.as_os_str());
+                rotated_path.push(".1");
+                fs::rename(&self.path, rotated_path)?;
+            }
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::TimeDiff;
+
+    use super::*;
+
+    #[test]
+    fn finalization_completes_earlier_rounds() {
+        let mut timelines = RoundTimelines::<u32>::default();
+        let now = Timestamp::from(1000);
+        timelines.round_mut(0, 0).unwrap().record_proposal(now);
+        timelines.round_mut(1, 1).unwrap().record_timeout(now);
+        timelines.round_mut(2, 2).unwrap().record_vote(0, now);
+        timelines
+            .round_mut(2, 2)
+            .unwrap()
+            .record_vote(0, now.saturating_add(TimeDiff::from_millis(1)));
+        timelines.round_mut(3, 0).unwrap().record_proposal(now);
+        assert!(timelines.take_complete().is_empty());
+
+        timelines.record_finalization(2, 2, now);
+        let complete = timelines.take_complete();
+        assert_eq!(
+            vec![0, 1, 2],
+            complete.iter().map(|r| r.round_id).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(now), complete[2].finalization_time);
+        assert_eq!(
+            vec![VoteArrival {
+                validator: 0,
+                time: now
+            }],
+            complete[2].votes
+        );
+        assert!(timelines.take_complete().is_empty());
+
+        // Events in complete rounds are ignored.
+        assert!(timelines.round_mut(1, 1).is_none());
+        timelines.record_finalization(3, 0, now);
+        let complete = timelines.take_complete();
+        assert_eq!(1, complete.len());
+        assert_eq!(3, complete[0].round_id);
+    }
+
+    #[test]
+    fn history_keeps_recent_rounds_and_rotates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus_rounds.jsonl");
+        let config = Config {
+            max_rounds: 2,
+            max_file_size: 1,
+        };
+        let mut history = RoundHistory::new(config, path.clone());
+        for round_id in 0..3 {
+            let write = history
+                .add(ConsensusRound {
+                    era_id: EraId::new(1),
+                    timeline: RoundTimeline::new(round_id, PublicKey::System),
+                })
+                .expect("should return a write");
+            write.run();
+        }
+        let recent_ids: Vec<_> = history
+            .recent()
+            .into_iter()
+            .map(|round| round.timeline.round_id)
+            .collect();
+        assert_eq!(vec![1, 2], recent_ids);
+
+        // Every file exceeds the maximum size after one line, so it is rotated before each write.
+        let read_round = |path: &std::path::Path| -> ConsensusRound {
+            serde_json::from_str(fs::read_to_string(path).unwrap().trim_end()).unwrap()
+        };
+        assert_eq!(2, read_round(&path).timeline.round_id);
+        assert_eq!(
+            1,
+            read_round(&dir.path().join("consensus_rounds.jsonl.1"))
+                .timeline
+                .round_id
+        );
+    }
+
+    #[test]
+    fn pending_write_picks_up_later_rounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("consensus_rounds.jsonl");
+        let mut history = RoundHistory::new(Config::default(), path.clone());
+        let mut add = |round_id| {
+            history.add(ConsensusRound {
+                era_id: EraId::new(1),
+                timeline: RoundTimeline::new(round_id, PublicKey::System),
+            })
+        };
+        let write = add(0).expect("should return a write");
+        assert!(add(1).is_none());
+        assert!(!path.exists(), "should not write before the write is run");
+
+        write.run();
+        let round_ids: Vec<_> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ConsensusRound>(line).unwrap())
+            .map(|round| round.timeline.round_id)
+            .collect();
+        assert_eq!(vec![0, 1], round_ids);
+        assert!(add(2).is_some());
+    }
+}
@@ -0,0 +1,574 @@
+//! Slashing protection: a persistent record of every consensus message signed by the validator.
+//!
+//! Before signing a unit or message, the consensus protocols check that the signature would not
+//! conflict with one that was made earlier, by this or any previous run of the node. The record is
+//! stored in an append-only file, so it survives restarts, and guarded by a lock file so that a
+//! second instance of the node using the same database cannot start. To migrate a validator to a
+//! different machine, the record can be exported and imported in an interchange format.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use datasize::DataSize;
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use casper_hashing::Digest;
+use casper_types::{EraId, PublicKey};
+
+/// The number of most recent eras whose records are kept when the database is opened.
+///
+/// Only the current era is ever signed in, so older records are only needed to detect a node
+/// that was restored from an old state.
+const RETAINED_ERAS: u64 = 10;
+
+/// The message slot a signature is made for.
+///
+/// The slot determines which earlier signatures a new signature could conflict with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum SigningSlot {
+    /// A position in a sequence of messages, e.g. the sequence number of a Highway unit.
+    ///
+    /// Positions must be signed in strictly increasing order, and each only once.
+    Sequence(u64),
+    /// One of the messages a validator can sign in a round, e.g. a Zug echo or vote.
+    ///
+    /// `kind` distinguishes the different messages of a round; for each kind and round only a
+    /// single message may be signed.
+    Round {
+        /// The round ID.
+        round_id: u64,
+        /// The protocol-specific kind of message.
+        kind: u8,
+    },
+}
+
+/// An error in the slashing protection database.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The database is already used by another process.
+    #[error("slashing protection database {} is in use by another process", .0.display())]
+    Locked(PathBuf),
+    /// The database belongs to a different validator.
+    #[error("slashing protection database belongs to validator {found}, not {expected}")]
+    WrongValidator {
+        /// The validator the database was expected to belong to.
+        expected: Box<PublicKey>,
+        /// The validator found in the database.
+        found: Box<PublicKey>,
+    },
+    /// The database file is corrupt.
+    #[error("slashing protection database {} is corrupt", .0.display())]
+    Corrupt(PathBuf),
+    /// Signing would conflict with an earlier signature.
+    #[error("refusing to sign {slot:?} in era {era_id}: conflicts with an earlier signature")]
+    Conflict {
+        /// The era in which the signature was requested.
+        era_id: EraId,
+        /// The slot of the requested signature.
+        slot: SigningSlot,
+    },
+    /// Error reading or writing the database.
+    #[error("slashing protection database I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// Error encoding or decoding an entry of the database.
+    #[error("could not encode or decode slashing protection entry: {0}")]
+    Encoding(#[from] bincode::Error),
+    /// Error encoding or decoding an interchange file.
+    #[error("could not encode or decode slashing protection interchange file: {0}")]
+    Interchange(#[from] serde_json::Error),
+}
+
+/// An entry in the database file.
+#[derive(Debug, Serialize, Deserialize)]
+enum Entry {
+    /// The validator the database belongs to. Always the first entry.
+    Validator(PublicKey),
+    /// A signature made for the given slot, over the given hash.
+    Signed(SignedSlot),
+}
+
+/// A record of a signature made for a slot, as stored and exchanged.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SignedSlot {
+    era_id: EraId,
+    slot: SigningSlot,
+    hash: Digest,
+}
+
+/// The format in which slashing protection data is exported and imported.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Interchange {
+    /// The validator the data belongs to.
+    public_key: PublicKey,
+    /// The slots signed by the validator.
+    signed: Vec<SignedSlot>,
+}
+
+/// The signatures made in a single era.
+#[derive(Debug, Default)]
+struct EraRecord {
+    /// The highest sequence position signed, with the hash signed for it.
+    highest_sequence: Option<(u64, Digest)>,
+    /// The hash signed for each round and kind of message.
+    rounds: BTreeMap<(u64, u8), Digest>,
+}
+
+/// The outcome of checking a signature against an `EraRecord`.
+#[derive(Debug, Eq, PartialEq)]
+enum Check {
+    /// The slot is new and can be signed.
+    New,
+    /// The same hash was already signed for this slot; signing it again is safe.
+    AlreadySigned,
+    /// Signing would conflict with an earlier signature.
+    Conflict,
+}
+
+impl EraRecord {
+    fn check(&self, slot: SigningSlot, hash: &Digest) -> Check {
+        match slot {
+            SigningSlot::Sequence(position) => match self.highest_sequence {
+                None => Check::New,
+                Some((highest, _)) if position > highest => Check::New,
+                Some((highest, signed)) if position == highest && signed == *hash => {
+                    Check::AlreadySigned
+                }
+                Some(_) => Check::Conflict,
+            },
+            SigningSlot::Round { round_id, kind } => match self.rounds.get(&(round_id, kind)) {
+                None => Check::New,
+                Some(signed) if signed == hash => Check::AlreadySigned,
+                Some(_) => Check::Conflict,
+            },
+        }
+    }
+
+    fn insert(&mut self, slot: SigningSlot, hash: Digest) {
+        match slot {
+            SigningSlot::Sequence(position) => {
+                if self
+                    .highest_sequence
+                    .map_or(true, |(highest, _)| position > highest)
+                {
+                    self.highest_sequence = Some((position, hash));
+                }
+            }
+            SigningSlot::Round { round_id, kind } => {
+                self.rounds.entry((round_id, kind)).or_insert(hash);
+            }
+        }
+    }
+
+    fn signed_slots(&self, era_id: EraId) -> impl Iterator<Item = SignedSlot> + '_ {
+        let sequence = self.highest_sequence.map(|(position, hash)| SignedSlot {
+            era_id,
+            slot: SigningSlot::Sequence(position),
+            hash,
+        });
+        let rounds = self
+            .rounds
+            .iter()
+            .map(move |(&(round_id, kind), &hash)| SignedSlot {
+                era_id,
+                slot: SigningSlot::Round { round_id, kind },
+                hash,
+            });
+        sequence.into_iter().chain(rounds)
+    }
+}
+
+/// The open database.
+#[derive(Debug)]
+struct Inner {
+    writer: BufWriter<File>,
+    records: BTreeMap<EraId, EraRecord>,
+}
+
+/// A slashing protection database, exclusively opened by this process.
+#[derive(Debug, DataSize)]
+pub(crate) struct SlashingProtection {
+    path: PathBuf,
+    public_key: PublicKey,
+    #[data_size(skip)]
+    inner: Mutex<Inner>,
+    /// The lock file, which is locked for as long as the database is open.
+    #[data_size(skip)]
+    _lock: File,
+}
+
+impl SlashingProtection {
+    /// Opens or creates the database at `path` for the given validator.
+    ///
+    /// Records of old eras are discarded. Fails if the database is in use by another process or
+    /// belongs to a different validator.
+    pub(crate) fn open<P: AsRef<Path>>(path: P, public_key: PublicKey) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let lock = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(path.with_extension("lock"))?;
+        if lock.try_lock_exclusive().is_err() {
+            return Err(Error::Locked(path));
+        }
+
+        let mut records = BTreeMap::new();
+        if path.exists() {
+            let mut reader = BufReader::new(File::open(&path)?);
+            match read_entry(&mut reader)? {
+                None => {}
+                Some(Entry::Validator(found)) if found == public_key => {}
+                Some(Entry::Validator(found)) => {
+                    return Err(Error::WrongValidator {
+                        expected: Box::new(public_key),
+                        found: Box::new(found),
+                    });
+                }
+                Some(Entry::Signed(_)) => return Err(Error::Corrupt(path)),
+            }
+            while let Some(entry) = read_entry(&mut reader)? {
+                match entry {
+                    Entry::Signed(signed) => records
+                        .entry(signed.era_id)
+                        .or_insert_with(EraRecord::default)
+                        .insert(signed.slot, signed.hash),
+                    Entry::Validator(_) => return Err(Error::Corrupt(path)),
+                }
+            }
+        }
+        if let Some(&latest) = records.keys().next_back() {
+            let oldest_retained = EraId::from(latest.value().saturating_sub(RETAINED_ERAS - 1));
+            records = records.split_off(&oldest_retained);
+        }
+
+        // Rewrite the compacted database, and atomically replace the old one.
+        let compacted_path = path.with_extension("compacted");
+        let mut writer = BufWriter::new(File::create(&compacted_path)?);
+        write_entry(&mut writer, &Entry::Validator(public_key.clone()))?;
+        for (era_id, record) in &records {
+            for signed in record.signed_slots(*era_id) {
+                write_entry(&mut writer, &Entry::Signed(signed))?;
+            }
+        }
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&compacted_path, &path)?;
+
+        let file = OpenOptions::new().append(true).open(&path)?;
+        info!(path = %path.display(), eras = records.len(), "opened slashing protection database");
+        Ok(SlashingProtection {
+            path,
+            public_key,
+            inner: Mutex::new(Inner {
+                writer: BufWriter::new(file),
+                records,
+            }),
+            _lock: lock,
+        })
+    }
+
+    /// Checks that signing `hash` for `slot` in the given era doesn't conflict with any earlier
+    /// signature, and durably records it.
+    ///
+    /// The signature must only be made if this returns `Ok`.
+    pub(crate) fn check_and_record(
+        &self,
+        era_id: EraId,
+        slot: SigningSlot,
+        hash: Digest,
+    ) -> Result<(), Error> {
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("slashing protection lock poisoned");
+        let record = inner.records.entry(era_id).or_default();
+        match record.check(slot, &hash) {
+            Check::AlreadySigned => return Ok(()),
+            Check::Conflict => return Err(Error::Conflict { era_id, slot }),
+            Check::New => record.insert(slot, hash),
+        }
+        let entry = Entry::Signed(SignedSlot { era_id, slot, hash });
+        write_entry(&mut inner.writer, &entry)?;
+        inner.writer.flush()?;
+        inner.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+
+    /// Returns all records in the interchange format.
+    pub(crate) fn export(&self) -> Interchange {
+        let inner = self
+            .inner
+            .lock()
+            .expect("slashing protection lock poisoned");
+        let signed = inner
+            .records
+            .iter()
+            .flat_map(|(era_id, record)| record.signed_slots(*era_id))
+            .collect();
+        Interchange {
+            public_key: self.public_key.clone(),
+            signed,
+        }
+    }
+
+    /// Adds the records from the interchange data to the database.
+    ///
+    /// Signatures conflicting with existing records are kept out of the database; they indicate
+    /// that the validator has already equivocated. Returns the number of new records.
+    pub(crate) fn import(&self, interchange: Interchange) -> Result<usize, Error> {
+        if interchange.public_key != self.public_key {
+            return Err(Error::WrongValidator {
+                expected: Box::new(self.public_key.clone()),
+                found: Box::new(interchange.public_key),
+            });
+        }
+        let mut imported: usize = 0;
+        for signed in interchange.signed {
+            let check = {
+                let inner = self
+                    .inner
+                    .lock()
+                    .expect("slashing protection lock poisoned");
+                inner
+                    .records
+                    .get(&signed.era_id)
+                    .map_or(Check::New, |record| record.check(signed.slot, &signed.hash))
+            };
+            match check {
+                Check::AlreadySigned => {}
+                Check::New => {
+                    self.check_and_record(signed.era_id, signed.slot, signed.hash)?;
+                    imported = imported.saturating_add(1);
+                }
+                Check::Conflict => {
+                    // A lower sequence position is already covered by the higher one we have.
+                    if !matches!(signed.slot, SigningSlot::Sequence(_)) {
+                        warn!(
+                            era_id = %signed.era_id,
+                            slot = ?signed.slot,
+                            "imported signature conflicts with existing record"
+                        );
+                    }
+                }
+            }
+        }
+        info!(path = %self.path.display(), imported, "imported slashing protection data");
+        Ok(imported)
+    }
+}
+
+/// Exports the database at `path` to the interchange file at `output`.
+pub(crate) fn export_to_file(
+    path: &Path,
+    public_key: PublicKey,
+    output: &Path,
+) -> Result<(), Error> {
+    let interchange = SlashingProtection::open(path, public_key)?.export();
+    fs::write(output, serde_json::to_vec_pretty(&interchange)?)?;
+    Ok(())
+}
+
+/// Imports the interchange file at `input` into the database at `path`.
+pub(crate) fn import_from_file(
+    path: &Path,
+    public_key: PublicKey,
+    input: &Path,
+) -> Result<usize, Error> {
+    let interchange: Interchange = serde_json::from_slice(&fs::read(input)?)?;
+    SlashingProtection::open(path, public_key)?.import(interchange)
+}
+
+/// Writes a length-prefixed entry.
+fn write_entry<W: Write>(writer: &mut W, entry: &Entry) -> Result<(), Error> {
+    let entry_size = bincode::serialized_size(entry)?;
+    writer.write_all(&entry_size.to_le_bytes())?;
+    bincode::serialize_into(writer, entry)?;
+    Ok(())
+}
+
+/// Reads the next length-prefixed entry, or `None` at the end of the file.
+///
+/// An incomplete entry at the end of the file can only be the result of a crash while writing it,
+/// before the corresponding message was signed, so it is ignored.
+fn read_entry<R: Read>(reader: &mut R) -> Result<Option<Entry>, Error> {
+    let mut entry_size_buf = [0u8; mem::size_of::<u64>()];
+    if let Err(err) = reader.read_exact(&mut entry_size_buf) {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            return Ok(None);
+        }
+        return Err(err.into());
+    }
+    let entry_size = u64::from_le_bytes(entry_size_buf) as usize;
+    let mut entry_buf = vec![0; entry_size];
+    if let Err(err) = reader.read_exact(&mut entry_buf) {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            warn!("ignoring incomplete entry in slashing protection database");
+            return Ok(None);
+        }
+        return Err(err.into());
+    }
+    Ok(Some(bincode::deserialize(&entry_buf)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, SecretKey};
+
+    use super::*;
+
+    fn public_key(rng: &mut TestRng) -> PublicKey {
+        PublicKey::from(&SecretKey::random(rng))
+    }
+
+    #[test]
+    fn should_refuse_conflicting_signatures() {
+        let mut rng = crate::new_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let db = SlashingProtection::open(dir.path().join("db"), public_key(&mut rng)).unwrap();
+        let era_id = EraId::from(5);
+        let (hash0, hash1) = (Digest::hash([0]), Digest::hash([1]));
+        let echo = SigningSlot::Round {
+            round_id: 3,
+            kind: 0,
+        };
+        let vote = SigningSlot::Round {
+            round_id: 3,
+            kind: 1,
+        };
+
+        db.check_and_record(era_id, echo, hash0).unwrap();
+        db.check_and_record(era_id, echo, hash0).unwrap();
+        db.check_and_record(era_id, vote, hash1).unwrap();
+        assert!(db.check_and_record(era_id, echo, hash1).is_err());
+        // Earlier rounds can still be signed.
+        db.check_and_record(
+            era_id,
+            SigningSlot::Round {
+                round_id: 2,
+                kind: 0,
+            },
+            hash1,
+        )
+        .unwrap();
+
+        db.check_and_record(era_id, SigningSlot::Sequence(1), hash0)
+            .unwrap();
+        db.check_and_record(era_id, SigningSlot::Sequence(1), hash0)
+            .unwrap();
+        assert!(db
+            .check_and_record(era_id, SigningSlot::Sequence(1), hash1)
+            .is_err());
+        assert!(db
+            .check_and_record(era_id, SigningSlot::Sequence(0), hash1)
+            .is_err());
+        db.check_and_record(era_id, SigningSlot::Sequence(2), hash1)
+            .unwrap();
+
+        // The same slots in a different era are unaffected.
+        db.check_and_record(EraId::from(6), echo, hash1).unwrap();
+    }
+
+    #[test]
+    fn should_persist_records_and_lock_database() {
+        let mut rng = crate::new_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db");
+        let key = public_key(&mut rng);
+        let slot = SigningSlot::Sequence(7);
+
+        let db = SlashingProtection::open(&path, key.clone()).unwrap();
+        db.check_and_record(EraId::from(1), slot, Digest::hash([0]))
+            .unwrap();
+        assert!(matches!(
+            SlashingProtection::open(&path, key.clone()),
+            Err(Error::Locked(_))
+        ));
+        drop(db);
+
+        assert!(matches!(
+            SlashingProtection::open(&path, public_key(&mut rng)),
+            Err(Error::WrongValidator { .. })
+        ));
+        let db = SlashingProtection::open(&path, key).unwrap();
+        assert!(matches!(
+            db.check_and_record(EraId::from(1), slot, Digest::hash([1])),
+            Err(Error::Conflict { .. })
+        ));
+    }
+
+    #[test]
+    fn should_discard_old_eras() {
+        let mut rng = crate::new_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("db");
+        let key = public_key(&mut rng);
+        let slot = SigningSlot::Sequence(0);
+
+        let db = SlashingProtection::open(&path, key.clone()).unwrap();
+        for era in 0..=RETAINED_ERAS {
+            db.check_and_record(EraId::from(era), slot, Digest::hash([0]))
+                .unwrap();
+        }
+        drop(db);
+
+        let db = SlashingProtection::open(&path, key).unwrap();
+        db.check_and_record(EraId::from(0), slot, Digest::hash([1]))
+            .unwrap();
+        assert!(db
+            .check_and_record(EraId::from(1), slot, Digest::hash([1]))
+            .is_err());
+    }
+
+    #[test]
+    fn should_export_and_import() {
+        let mut rng = crate::new_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let key = public_key(&mut rng);
+        let era_id = EraId::from(2);
+        let round = SigningSlot::Round {
+            round_id: 4,
+            kind: 1,
+        };
+
+        let old_db = SlashingProtection::open(dir.path().join("old"), key.clone()).unwrap();
+        old_db
+            .check_and_record(era_id, SigningSlot::Sequence(3), Digest::hash([0]))
+            .unwrap();
+        old_db
+            .check_and_record(era_id, round, Digest::hash([0]))
+            .unwrap();
+        let interchange: Interchange =
+            serde_json::from_slice(&serde_json::to_vec(&old_db.export()).unwrap()).unwrap();
+
+        let new_db = SlashingProtection::open(dir.path().join("new"), key).unwrap();
+        new_db
+            .check_and_record(era_id, SigningSlot::Sequence(1), Digest::hash([1]))
+            .unwrap();
+        assert_eq!(new_db.import(interchange).unwrap(), 2);
+        assert!(new_db
+            .check_and_record(era_id, SigningSlot::Sequence(2), Digest::hash([1]))
+            .is_err());
+        assert!(new_db
+            .check_and_record(era_id, round, Digest::hash([1]))
+            .is_err());
+
+        let other = SlashingProtection::open(dir.path().join("other"), public_key(&mut rng))
+            .unwrap()
+            .export();
+        assert!(matches!(
+            new_db.import(other),
+            Err(Error::WrongValidator { .. })
+        ));
+    }
+}
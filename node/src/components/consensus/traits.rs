@@ -6,6 +6,8 @@ use std::{
 use datasize::DataSize;
 use serde::{de::DeserializeOwned, Serialize};
 
+use super::slashing_protection::SigningSlot;
+
 /// A validator identifier.
 pub trait ValidatorIdT: Eq + Ord + Clone + Debug + Hash + Send + DataSize + Display {}
 impl<VID> ValidatorIdT for VID where VID: Eq + Ord + Clone + Debug + Hash + Send + DataSize + Display
@@ -45,7 +47,16 @@ pub trait ValidatorSecret: Send + DataSize {
 
     type Signature: Eq + PartialEq + Clone + Debug + Hash + Serialize + DeserializeOwned + DataSize;
 
-    fn sign(&self, hash: &Self::Hash) -> Self::Signature;
+    /// Signs the given hash, returning `None` if no signature could be created, e.g. because a
+    /// remote signer is unavailable.
+    fn sign(&self, hash: &Self::Hash) -> Option<Self::Signature>;
+
+    /// Signs the hash of a message for the given slot, returning `None` if it could conflict with
+    /// a message signed earlier, according to the slashing protection database.
+    fn sign_slot(&self, slot: SigningSlot, hash: &Self::Hash) -> Option<Self::Signature> {
+        let _ = slot;
+        self.sign(hash)
+    }
 }
 
 /// The collection of types the user can choose for cryptography, IDs, transactions, etc.
@@ -42,7 +42,10 @@ use casper_execution_engine::{
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, PublicKey, Timestamp};
+use casper_types::{
+    bytesrepr::Bytes, system::auction::ParameterChanges, EraId, ProtocolVersion, PublicKey,
+    Timestamp,
+};
 
 use crate::{
     components::{fetcher::FetchResponse, Component, ComponentState},
@@ -58,8 +61,9 @@ use crate::{
     fatal,
     protocol::Message,
     types::{
-        ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, ChunkingError,
-        Deploy, FinalizedBlock, MetaBlock, MetaBlockState, TrieOrChunk, TrieOrChunkId,
+        chainspec::DeployConfig, ActivationPoint, BlockHash, BlockHeader, BlockHeaderExtension,
+        Chainspec, ChainspecRawBytes, ChunkingError, Deploy, FinalizedBlock, MetaBlock,
+        MetaBlockState, TrieOrChunk, TrieOrChunkId,
     },
     NodeRng,
 };
@@ -126,6 +130,8 @@ pub struct SpeculativeExecutionState {
     pub block_time: Timestamp,
     /// Protocol version used when creating the original block.
     pub protocol_version: ProtocolVersion,
+    /// Random beacon of the original block.
+    pub random_beacon: Digest,
 }
 
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
@@ -142,6 +148,8 @@ pub struct ExecutionPreState {
     /// The accumulated seed for the pseudo-random number generator to be incorporated into the
     /// next `Block`, where additional entropy will be introduced.
     parent_seed: Digest,
+    /// The base fee, gas used and random beacon of the parent `Block`, if its header has them.
+    parent_extension: Option<BlockHeaderExtension>,
 }
 
 impl ExecutionPreState {
@@ -150,12 +158,14 @@ impl ExecutionPreState {
         pre_state_root_hash: Digest,
         parent_hash: BlockHash,
         parent_seed: Digest,
+        parent_extension: Option<BlockHeaderExtension>,
     ) -> Self {
         ExecutionPreState {
             next_block_height,
             pre_state_root_hash,
             parent_hash,
             parent_seed,
+            parent_extension,
         }
     }
 
@@ -167,6 +177,7 @@ impl ExecutionPreState {
             next_block_height: block_header.height() + 1,
             parent_hash: block_header.block_hash(),
             parent_seed: block_header.accumulated_seed(),
+            parent_extension: block_header.extension().cloned(),
         }
     }
 }
@@ -212,6 +223,9 @@ pub(crate) struct ContractRuntime {
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    deploy_config: DeployConfig,
+    /// Whether the blocks executed in this protocol version have extended headers.
+    extended_block_headers: bool,
 }
 
 impl Debug for ContractRuntime {
@@ -494,6 +508,8 @@ impl ContractRuntime {
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
+                        let deploy_config = self.deploy_config;
+                        let extended_block_headers = self.extended_block_headers;
                         effects.extend(
                             Self::execute_finalized_block_or_requeue(
                                 engine_state,
@@ -508,6 +524,8 @@ impl ContractRuntime {
                                 activation_point,
                                 key_block_height_for_activation_point,
                                 prune_batch_size,
+                                deploy_config,
+                                extended_block_headers,
                                 meta_block_state,
                             )
                             .ignore(),
@@ -605,12 +623,15 @@ impl ContractRuntime {
         strict_argument_checking: bool,
         vesting_schedule_period_millis: u64,
         max_delegators_per_validator: Option<u32>,
+        inactive_eras_before_eviction: u64,
         registry: &Registry,
         administrative_accounts: BTreeSet<PublicKey>,
         allow_auction_bids: bool,
         allow_unrestricted_transfers: bool,
         refund_handling: RefundHandling,
         fee_handling: FeeHandling,
+        deploy_config: DeployConfig,
+        extended_block_headers: bool,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
         let execution_pre_state = Arc::new(Mutex::new(ExecutionPreState {
@@ -618,6 +639,7 @@ impl ContractRuntime {
             next_block_height: 0,
             parent_hash: Default::default(),
             parent_seed: Default::default(),
+            parent_extension: None,
         }));
 
         let environment = Arc::new(LmdbEnvironment::new(
@@ -642,6 +664,7 @@ impl ContractRuntime {
             .with_strict_argument_checking(strict_argument_checking)
             .with_vesting_schedule_period_millis(vesting_schedule_period_millis)
             .with_max_delegators_per_validator(max_delegators_per_validator)
+            .with_inactive_eras_before_eviction(inactive_eras_before_eviction)
             .with_wasm_config(wasm_config)
             .with_system_config(system_config)
             .with_administrative_accounts(administrative_accounts)
@@ -665,6 +688,8 @@ impl ContractRuntime {
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            deploy_config,
+            extended_block_headers,
         })
     }
 
@@ -723,6 +748,23 @@ impl ContractRuntime {
         Ok(result)
     }
 
+    /// Returns the accumulated parameter changes of applied governance proposals as of the given
+    /// state root hash.
+    pub(crate) fn parameter_overrides(
+        &self,
+        state_root_hash: Digest,
+    ) -> Result<ParameterChanges, engine_state::Error> {
+        self.engine_state
+            .get_parameter_overrides(CorrelationId::new(), state_root_hash)
+    }
+
+    /// Starts executing blocks with the deploy and Wasm configs of the given chainspec, e.g. after
+    /// governance changed the block gas limit or opcode costs.
+    pub(crate) fn update_chainspec(&mut self, chainspec: &Chainspec) {
+        self.deploy_config = chainspec.deploy_config;
+        self.engine_state = Arc::new(self.engine_state.with_wasm_config(chainspec.wasm_config));
+    }
+
     pub(crate) fn set_initial_state(&mut self, sequential_block_state: ExecutionPreState) {
         let next_block_height = sequential_block_state.next_block_height;
         let mut execution_pre_state = self.execution_pre_state.lock().unwrap();
@@ -756,6 +798,8 @@ impl ContractRuntime {
         activation_point: ActivationPoint,
         key_block_height_for_activation_point: u64,
         prune_batch_size: u64,
+        deploy_config: DeployConfig,
+        extended_block_headers: bool,
         mut meta_block_state: MetaBlockState,
     ) where
         REv: From<ContractRuntimeRequest>
@@ -784,6 +828,8 @@ impl ContractRuntime {
                 activation_point.era_id(),
                 key_block_height_for_activation_point,
                 prune_batch_size,
+                &deploy_config,
+                extended_block_headers,
             )
         })
         .await
@@ -997,7 +1043,10 @@ mod trie_chunking_tests {
     use crate::{
         components::fetcher::FetchResponse,
         contract_runtime::{Config as ContractRuntimeConfig, ContractRuntime},
-        types::{ActivationPoint, ChunkingError, TrieOrChunk, TrieOrChunkId, ValueOrChunk},
+        types::{
+            chainspec::DeployConfig, ActivationPoint, ChunkingError, TrieOrChunk, TrieOrChunkId,
+            ValueOrChunk,
+        },
     };
 
     use super::ContractRuntimeError;
@@ -1065,12 +1114,15 @@ mod trie_chunking_tests {
             true,
             1,
             None,
+            1,
             &Registry::default(),
             Default::default(),
             true,
             true,
             DEFAULT_REFUND_HANDLING,
             DEFAULT_FEE_HANDLING,
+            DeployConfig::default(),
+            true,
         )
         .unwrap();
         let empty_state_root = contract_runtime
@@ -32,8 +32,8 @@ use crate::{
         fetcher::FetchItem,
     },
     types::{
-        self, error::BlockCreationError, ApprovalsHashes, Block, Chunkable, Deploy, DeployHeader,
-        FinalizedBlock,
+        self, chainspec::DeployConfig, error::BlockCreationError, ApprovalsHashes, Block,
+        BlockHeaderExtension, Chunkable, Deploy, DeployHeader, FinalizedBlock,
     },
 };
 
@@ -99,6 +99,8 @@ pub fn execute_finalized_block(
     activation_point_era_id: EraId,
     key_block_height_for_activation_point: u64,
     prune_batch_size: u64,
+    deploy_config: &DeployConfig,
+    extended_block_headers: bool,
 ) -> Result<BlockAndExecutionResults, BlockExecutionError> {
     if finalized_block.height() != execution_pre_state.next_block_height {
         return Err(BlockExecutionError::WrongBlockHeight {
@@ -110,8 +112,23 @@ pub fn execute_finalized_block(
         pre_state_root_hash,
         parent_hash,
         parent_seed,
+        parent_extension,
         next_block_height: _,
     } = execution_pre_state;
+    // The first block with an extended header starts with the minimum base fee, and with a random
+    // beacon derived from its parent's accumulated seed.
+    let maybe_base_fee_and_random_beacon =
+        extended_block_headers.then(|| match &parent_extension {
+            Some(parent) => (
+                deploy_config.next_base_fee(parent.base_fee(), parent.gas_used()),
+                finalized_block.random_beacon(parent.random_beacon()),
+            ),
+            None => (
+                deploy_config.min_base_fee,
+                finalized_block.random_beacon(parent_seed),
+            ),
+        });
+    let mut gas_used = U512::zero();
     let mut state_root_hash = pre_state_root_hash;
     let mut execution_results: Vec<(_, DeployHeader, ExecutionResult)> =
         Vec::with_capacity(deploys.len());
@@ -129,13 +146,18 @@ pub fn execute_finalized_block(
     for deploy in deploys {
         let deploy_hash = *deploy.hash();
         let deploy_header = deploy.header().clone();
-        let execute_request = ExecuteRequest::new(
+        let mut execute_request = ExecuteRequest::new(
             state_root_hash,
             block_time,
             vec![DeployItem::from(deploy)],
             protocol_version,
             *finalized_block.proposer(),
         );
+        if let Some((base_fee, random_beacon)) = maybe_base_fee_and_random_beacon {
+            execute_request = execute_request
+                .with_base_fee(base_fee)
+                .with_random_beacon(random_beacon);
+        }
 
         // TODO: this is currently working coincidentally because we are passing only one
         // deploy_item per exec. The execution results coming back from the EE lack the
@@ -153,6 +175,7 @@ pub fn execute_finalized_block(
             deploy_hash.into(),
             result,
         )?;
+        gas_used = gas_used.saturating_add(execution_result_cost(&execution_result));
         execution_results.push((deploy_hash, deploy_header, execution_result));
         state_root_hash = state_hash;
     }
@@ -306,6 +329,14 @@ pub fn execute_finalized_block(
                 },
             );
 
+    let extension = maybe_base_fee_and_random_beacon.map(|(base_fee, random_beacon)| {
+        BlockHeaderExtension::new(
+            base_fee,
+            u64::try_from(gas_used).unwrap_or(u64::MAX),
+            finalized_block.random_beacon_contribution().cloned(),
+            random_beacon,
+        )
+    });
     let block = Arc::new(Block::new(
         parent_hash,
         parent_seed,
@@ -313,6 +344,7 @@ pub fn execute_finalized_block(
         finalized_block,
         next_era_validator_weights,
         protocol_version,
+        extension,
     )?);
 
     let approvals_hashes = deploy_ids
@@ -335,6 +367,13 @@ pub fn execute_finalized_block(
     })
 }
 
+/// Returns the gas consumed by the execution of a deploy.
+fn execution_result_cost(execution_result: &ExecutionResult) -> U512 {
+    match execution_result {
+        ExecutionResult::Failure { cost, .. } | ExecutionResult::Success { cost, .. } => *cost,
+    }
+}
+
 /// Commits the execution results.
 fn commit_execution_results<S>(
     engine_state: &EngineState<S>,
@@ -421,6 +460,7 @@ where
         state_root_hash,
         block_time,
         protocol_version,
+        random_beacon,
     } = execution_state;
     let deploy_hash = deploy.deploy_hash;
     let execute_request = ExecuteRequest::new(
@@ -429,7 +469,8 @@ where
         vec![deploy],
         protocol_version,
         PublicKey::System,
-    );
+    )
+    .with_random_beacon(random_beacon);
     let results = execute(engine_state, None, execute_request);
     results.map(|mut execution_results| {
         let len = execution_results.len();
@@ -494,13 +535,9 @@ where
         .map(|(vid, value)| RewardItem::new(vid.clone(), *value))
         .collect();
 
-    // Both inactive validators and equivocators are evicted
-    let evict_items = inactive_validators
-        .iter()
-        .chain(equivocators)
-        .cloned()
-        .map(EvictItem::new)
-        .collect();
+    // Equivocators are evicted immediately, inactive validators only after the configured number
+    // of consecutive inactive eras
+    let evict_items = equivocators.iter().cloned().map(EvictItem::new).collect();
 
     let step_request = StepRequest {
         pre_state_hash: pre_state_root_hash,
@@ -509,6 +546,7 @@ where
         // Note: The Casper Network does not slash, but another network could
         slash_items: vec![],
         evict_items,
+        inactive_validators: inactive_validators.clone(),
         next_era_id,
         era_end_timestamp_millis,
     };
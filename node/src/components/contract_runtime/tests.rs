@@ -119,12 +119,17 @@ impl reactor::Reactor for Reactor {
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             Some(chainspec.core_config.max_delegators_per_validator),
+            chainspec.core_config.inactive_eras_before_eviction,
             registry,
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.deploy_config,
+            chainspec
+                .core_config
+                .has_extended_block_headers(chainspec.protocol_version()),
         )?;
 
         let reactor = Reactor {
@@ -240,6 +245,7 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         post_commit_genesis_state_hash,
         BlockHash::default(),
         Digest::default(),
+        None,
     );
     runner
         .reactor_mut()
@@ -341,6 +347,7 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
         deploys.iter().map(DeployHashWithApprovals::from).collect(),
         vec![],
         true,
+        None,
     );
     let block_2 = FinalizedBlock::new(
         block_payload,
@@ -372,6 +379,7 @@ async fn should_not_set_shared_pre_state_to_lower_block_height() {
             Digest::hash(rng.next_u64().to_le_bytes()),
             BlockHash::random(rng),
             Digest::hash(rng.next_u64().to_le_bytes()),
+            None,
         ));
 
     runner
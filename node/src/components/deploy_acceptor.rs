@@ -103,6 +103,9 @@ pub(crate) enum DeployParameterFailure {
     /// Account does not exist.
     #[error("account with hash {account_hash} does not exist")]
     NonexistentAccount { account_hash: AccountHash },
+    /// Sponsor account does not exist.
+    #[error("sponsor account with hash {account_hash} does not exist")]
+    NonexistentSponsor { account_hash: AccountHash },
     /// Nonexistent contract at hash.
     #[error("contract at {contract_hash} does not exist")]
     NonexistentContractAtHash { contract_hash: ContractHash },
@@ -232,6 +235,11 @@ impl DeployAcceptor {
         })
     }
 
+    /// Replaces the deploy config, e.g. after governance changed the block gas limit.
+    pub(crate) fn set_deploy_config(&mut self, deploy_config: DeployConfig) {
+        self.deploy_config = deploy_config;
+    }
+
     /// Handles receiving a new `Deploy` from a peer or client.
     /// In the case of a peer, there should be no responder and the variant should be `None`
     /// In the case of a client, there should be a responder to communicate the validity of the
@@ -380,38 +388,25 @@ impl DeployAcceptor {
                 )
             }
             Some(account) => {
-                let authorization_keys = event_metadata
+                let authorization_keys: BTreeSet<AccountHash> = event_metadata
                     .deploy
                     .approvals()
                     .iter()
                     .map(|approval| approval.signer().to_account_hash())
                     .collect();
 
-                let admin_set: BTreeSet<AccountHash> = {
-                    self.core_config
-                        .administrators
-                        .iter()
-                        .map(|public_key| public_key.to_account_hash())
-                        .collect()
+                let maybe_sponsor = event_metadata.deploy.header().sponsor().cloned();
+                let (account_keys, unattributed_keys) = match maybe_sponsor {
+                    None => (authorization_keys, BTreeSet::new()),
+                    // The keys not associated with the deploy's account must all be associated with
+                    // the sponsor.
+                    Some(_) => authorization_keys.into_iter().partition(|key| {
+                        self.is_administrator(key) || account.associated_keys().contains_key(key)
+                    }),
                 };
-                if admin_set.intersection(&authorization_keys).next().is_some() {
-                    return effect_builder
-                        .check_purse_balance(*block_header.state_root_hash(), account.main_purse())
-                        .event(move |maybe_balance_value| Event::GetBalanceResult {
-                            event_metadata,
-                            maybe_balance_value,
-                            account_hash: account.account_hash(),
-                            verification_start_timestamp,
-                            block_header,
-                        });
-                }
 
-                if !account.can_authorize(&authorization_keys) {
-                    let error = Error::parameter_failure(
-                        &block_header,
-                        DeployParameterFailure::InvalidAssociatedKeys,
-                    );
-                    debug!(?authorization_keys, "account authorization invalid");
+                if let Err(failure) = self.verify_authorization(&account, &account_keys) {
+                    let error = Error::parameter_failure(&block_header, failure);
                     return self.handle_invalid_deploy_result(
                         effect_builder,
                         event_metadata,
@@ -420,32 +415,151 @@ impl DeployAcceptor {
                     );
                 }
 
-                if !account.can_deploy_with(&authorization_keys) {
-                    let error = Error::parameter_failure(
-                        &block_header,
-                        DeployParameterFailure::InsufficientDeploySignatureWeight,
-                    );
-                    debug!(?authorization_keys, "insufficient deploy signature weight");
-                    return self.handle_invalid_deploy_result(
-                        effect_builder,
-                        event_metadata,
-                        error,
-                        verification_start_timestamp,
-                    );
+                match maybe_sponsor {
+                    None => effect_builder
+                        .check_purse_balance(*block_header.state_root_hash(), account.main_purse())
+                        .event(move |maybe_balance_value| Event::GetBalanceResult {
+                            event_metadata,
+                            block_header,
+                            maybe_balance_value,
+                            account_hash: account.account_hash(),
+                            verification_start_timestamp,
+                        }),
+                    Some(sponsor) => {
+                        let sponsor_hash = sponsor.to_account_hash();
+                        effect_builder
+                            .get_account_from_global_state(
+                                *block_header.state_root_hash(),
+                                Key::from(sponsor_hash),
+                            )
+                            .event(move |maybe_sponsor| Event::GetSponsorAccountResult {
+                                event_metadata,
+                                block_header,
+                                sponsor_hash,
+                                maybe_sponsor: maybe_sponsor.map(Box::new),
+                                unattributed_keys,
+                                verification_start_timestamp,
+                            })
+                    }
                 }
-                effect_builder
-                    .check_purse_balance(*block_header.state_root_hash(), account.main_purse())
-                    .event(move |maybe_balance_value| Event::GetBalanceResult {
-                        event_metadata,
-                        block_header,
-                        maybe_balance_value,
-                        account_hash: account.account_hash(),
-                        verification_start_timestamp,
-                    })
             }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn handle_get_sponsor_account_result<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        sponsor_hash: AccountHash,
+        maybe_sponsor: Option<Box<Account>>,
+        unattributed_keys: BTreeSet<AccountHash>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        let sponsor = match maybe_sponsor {
+            Some(sponsor) => sponsor,
+            None => {
+                let error = Error::parameter_failure(
+                    &block_header,
+                    DeployParameterFailure::NonexistentSponsor {
+                        account_hash: sponsor_hash,
+                    },
+                );
+                debug!(?sponsor_hash, "nonexistent sponsor of the deploy");
+                return self.handle_invalid_deploy_result(
+                    effect_builder,
+                    event_metadata,
+                    error,
+                    verification_start_timestamp,
+                );
+            }
+        };
+
+        if let Some(key) = unattributed_keys
+            .iter()
+            .find(|key| !sponsor.associated_keys().contains_key(key))
+        {
+            let error = Error::parameter_failure(
+                &block_header,
+                DeployParameterFailure::InvalidAssociatedKeys,
+            );
+            debug!(
+                ?key,
+                "approval associated with neither the account nor the sponsor"
+            );
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+
+        let sponsor_keys: BTreeSet<AccountHash> = event_metadata
+            .deploy
+            .approvals()
+            .iter()
+            .map(|approval| approval.signer().to_account_hash())
+            .filter(|key| self.is_administrator(key) || sponsor.associated_keys().contains_key(key))
+            .collect();
+        if let Err(failure) = self.verify_authorization(&sponsor, &sponsor_keys) {
+            let error = Error::parameter_failure(&block_header, failure);
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+
+        effect_builder
+            .check_purse_balance(*block_header.state_root_hash(), sponsor.main_purse())
+            .event(move |maybe_balance_value| Event::GetBalanceResult {
+                event_metadata,
+                block_header,
+                maybe_balance_value,
+                account_hash: sponsor.account_hash(),
+                verification_start_timestamp,
+            })
+    }
+
+    /// Returns whether the given key belongs to one of the chain's administrators.
+    fn is_administrator(&self, key: &AccountHash) -> bool {
+        self.core_config
+            .administrators
+            .iter()
+            .any(|public_key| public_key.to_account_hash() == *key)
+    }
+
+    /// Verifies the given keys are sufficient to authorize a deploy on behalf of `account`.
+    ///
+    /// Any key belonging to an administrator is sufficient on its own.
+    fn verify_authorization(
+        &self,
+        account: &Account,
+        authorization_keys: &BTreeSet<AccountHash>,
+    ) -> Result<(), DeployParameterFailure> {
+        if authorization_keys
+            .iter()
+            .any(|key| self.is_administrator(key))
+        {
+            return Ok(());
+        }
+
+        if !account.can_authorize(authorization_keys) {
+            debug!(?authorization_keys, "account authorization invalid");
+            return Err(DeployParameterFailure::InvalidAssociatedKeys);
+        }
+
+        if !account.can_deploy_with(authorization_keys) {
+            debug!(?authorization_keys, "insufficient deploy signature weight");
+            return Err(DeployParameterFailure::InsufficientDeploySignatureWeight);
+        }
+
+        Ok(())
+    }
+
     fn handle_get_balance_result<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -1070,6 +1184,22 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 maybe_account,
                 verification_start_timestamp,
             ),
+            Event::GetSponsorAccountResult {
+                event_metadata,
+                block_header,
+                sponsor_hash,
+                maybe_sponsor,
+                unattributed_keys,
+                verification_start_timestamp,
+            } => self.handle_get_sponsor_account_result(
+                effect_builder,
+                event_metadata,
+                block_header,
+                sponsor_hash,
+                maybe_sponsor,
+                unattributed_keys,
+                verification_start_timestamp,
+            ),
             Event::GetBalanceResult {
                 event_metadata,
                 block_header,
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     fmt::{self, Display, Formatter},
     sync::Arc,
 };
@@ -74,7 +75,17 @@ pub(crate) enum Event {
         maybe_account: Option<Account>,
         verification_start_timestamp: Timestamp,
     },
-    /// The result of querying the balance of the `Account` associated with the `Deploy`.
+    /// The result of querying global state for the `Account` sponsoring the `Deploy`.
+    GetSponsorAccountResult {
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        sponsor_hash: AccountHash,
+        maybe_sponsor: Option<Box<Account>>,
+        /// The approvals' keys which are not associated with the deploy's account.
+        unattributed_keys: BTreeSet<AccountHash>,
+        verification_start_timestamp: Timestamp,
+    },
+    /// The result of querying the balance of the `Account` paying for the `Deploy`.
     GetBalanceResult {
         event_metadata: Box<EventMetadata>,
         block_header: Box<BlockHeader>,
@@ -161,6 +172,13 @@ impl Display for Event {
                     event_metadata.deploy.hash()
                 )
             }
+            Event::GetSponsorAccountResult { event_metadata, .. } => {
+                write!(
+                    formatter,
+                    "verifying sponsor account to validate deploy with hash {}",
+                    event_metadata.deploy.hash()
+                )
+            }
             Event::GetBalanceResult { event_metadata, .. } => {
                 write!(
                     formatter,
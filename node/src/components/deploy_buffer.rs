@@ -1,3 +1,4 @@
+mod block_builder;
 mod config;
 mod event;
 mod metrics;
@@ -5,7 +6,7 @@ mod metrics;
 mod tests;
 
 use std::{
-    collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet},
     convert::TryInto,
     iter::FromIterator,
     mem,
@@ -18,6 +19,7 @@ use prometheus::Registry;
 use smallvec::smallvec;
 use tracing::{debug, error, info, warn};
 
+#[cfg(test)]
 use casper_hashing::Digest;
 use casper_types::Timestamp;
 
@@ -46,6 +48,7 @@ use crate::{
 pub(crate) use config::Config;
 pub(crate) use event::Event;
 
+use block_builder::{BlockBuilder, Candidate};
 use metrics::Metrics;
 
 const COMPONENT_NAME: &str = "deploy_buffer";
@@ -73,6 +76,14 @@ pub(crate) struct DeployBuffer {
     hold: BTreeMap<Timestamp, HashSet<DeployHash>>,
     // deploy_hashes that should not be proposed, ever
     dead: HashSet<DeployHash>,
+    // the height of the highest known block and the base fee of its child, which is `None` if that
+    // block's header predates the fee market
+    //
+    // deploys offering a lower gas price than the base fee are not proposed
+    next_base_fee: Option<(u64, Option<u64>)>,
+    // the strategy choosing the deploys offered to proposed blocks
+    #[data_size(skip)]
+    block_builder: Box<dyn BlockBuilder>,
     // deploy buffer metrics
     #[data_size(skip)]
     metrics: Metrics,
@@ -87,15 +98,22 @@ impl DeployBuffer {
     ) -> Result<Self, prometheus::Error> {
         Ok(DeployBuffer {
             state: ComponentState::Uninitialized,
+            block_builder: block_builder::from_config(&cfg),
             cfg,
             deploy_config,
             buffer: HashMap::new(),
             hold: BTreeMap::new(),
             dead: HashSet::new(),
+            next_base_fee: None,
             metrics: Metrics::new(registry)?,
         })
     }
 
+    /// Replaces the deploy config, e.g. after governance changed the block gas limit.
+    pub(crate) fn set_deploy_config(&mut self, deploy_config: DeployConfig) {
+        self.deploy_config = deploy_config;
+    }
+
     pub(crate) fn initialize_component(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
@@ -298,9 +316,32 @@ impl DeployBuffer {
         let block_height = block.header().height();
         let timestamp = block.timestamp();
         debug!(%timestamp, "DeployBuffer: register_block({}) timestamp finalized", block_height);
+        if self
+            .next_base_fee
+            .map_or(true, |(highest_height, _)| highest_height < block_height)
+        {
+            let next_base_fee = block.header().extension().map(|extension| {
+                self.deploy_config
+                    .next_base_fee(extension.base_fee(), extension.gas_used())
+            });
+            debug!(
+                block_height,
+                ?next_base_fee,
+                "DeployBuffer: updated base fee"
+            );
+            self.next_base_fee = Some((block_height, next_base_fee));
+        }
         self.register_deploys(timestamp, block.deploy_and_transfer_hashes());
     }
 
+    /// Returns the base fee per unit of gas which applies to the next block, if any.
+    fn base_fee(&self) -> Option<u64> {
+        self.next_base_fee
+            .map_or(Some(self.deploy_config.min_base_fee), |(_, base_fee)| {
+                base_fee
+            })
+    }
+
     /// Update buffer and holds considering new finalized block.
     fn register_block_finalized(&mut self, finalized_block: &FinalizedBlock) {
         let block_height = finalized_block.height();
@@ -309,9 +350,11 @@ impl DeployBuffer {
         self.register_deploys(timestamp, finalized_block.deploy_and_transfer_hashes());
     }
 
-    /// Returns eligible deploys that are buffered and not held or dead.
-    fn proposable(&self) -> Vec<(DeployHashWithApprovals, DeployFootprint)> {
+    /// Returns eligible deploys that are buffered, not held or dead, and pay at least the base
+    /// fee.
+    fn proposable(&self) -> Vec<Candidate> {
         debug!("DeployBuffer: getting proposable deploys");
+        let base_fee = self.base_fee();
         self.buffer
             .iter()
             .filter(|(dh, _)| !self.hold.values().any(|hs| hs.contains(dh)))
@@ -324,23 +367,17 @@ impl DeployBuffer {
                     )
                 })
             })
+            .filter(|(_, footprint)| {
+                base_fee.map_or(true, |base_fee| {
+                    footprint.header.effective_gas_price(base_fee).is_some()
+                })
+            })
             .collect()
     }
 
-    fn buckets(&mut self) -> HashMap<Digest, Vec<(DeployHashWithApprovals, DeployFootprint)>> {
-        let proposable = self.proposable();
-
-        let mut buckets: HashMap<Digest, Vec<(DeployHashWithApprovals, DeployFootprint)>> =
-            HashMap::new();
-
-        for (with_approvals, footprint) in proposable {
-            let body_hash = *footprint.header.body_hash();
-            buckets
-                .entry(body_hash)
-                .and_modify(|vec| vec.push((with_approvals.clone(), footprint.clone())))
-                .or_insert(vec![(with_approvals, footprint)]);
-        }
-        buckets
+    #[cfg(test)]
+    fn buckets(&mut self) -> HashMap<Digest, Vec<Candidate>> {
+        block_builder::buckets(self.proposable(), self.base_fee().unwrap_or_default())
     }
 
     /// Returns a right-sized payload of deploys that can be proposed.
@@ -357,15 +394,18 @@ impl DeployBuffer {
         let mut have_hit_transfer_limit = false;
         let mut have_hit_deploy_limit = false;
 
-        let mut buckets = self.buckets();
-        let mut body_hashes_queue: VecDeque<_> = buckets.keys().cloned().collect();
+        // The configured block builder decides which deploys are offered in which order; we add
+        // them as long as they fit within the block limits.
+        let candidates = self
+            .block_builder
+            .order(self.proposable(), self.base_fee().unwrap_or_default());
 
         #[cfg(test)]
         let mut iter_counter = 0;
         #[cfg(test)]
         let iter_limit = self.buffer.len() * 4;
 
-        while let Some(body_hash) = body_hashes_queue.pop_front() {
+        for (with_approvals, footprint) in candidates {
             if Timestamp::now() > request_expiry {
                 break;
             }
@@ -378,14 +418,6 @@ impl DeployBuffer {
                 );
             }
 
-            let Some((with_approvals, footprint)) =
-                buckets.get_mut(&body_hash).and_then(Vec::<_>::pop)
-            else {
-                continue;
-            };
-            // bucket wasn't empty - push the hash back into the queue to be processed again on the
-            // next pass
-            body_hashes_queue.push_back(body_hash);
             if footprint.is_transfer && have_hit_transfer_limit {
                 continue;
             }
@@ -509,6 +541,12 @@ impl DeployBuffer {
         self.metrics
             .total_deploys
             .set(self.buffer.len().try_into().unwrap_or(i64::MIN));
+        self.metrics.base_fee.set(
+            self.base_fee()
+                .unwrap_or_default()
+                .try_into()
+                .unwrap_or(i64::MIN),
+        );
     }
 }
 
@@ -0,0 +1,224 @@
+//! Strategies for choosing the deploys offered to a proposed block.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    fmt::Debug,
+};
+
+use casper_hashing::Digest;
+use casper_types::PublicKey;
+
+use super::config::{BlockBuilderStrategy, Config, ContractReference};
+use crate::types::{DeployFootprint, DeployHashWithApprovals, SessionContract};
+
+/// A deploy which is eligible for inclusion in a proposed block.
+pub(super) type Candidate = (DeployHashWithApprovals, DeployFootprint);
+
+/// A strategy for choosing which of the proposable deploys are offered to a proposed block, and in
+/// which order.
+///
+/// The deploy buffer offers the candidates to the block in the returned order, skipping or stopping
+/// at those which would exceed one of the chainspec's `block_max_*` limits.
+pub(super) trait BlockBuilder: Debug + Send + Sync {
+    /// Returns the candidates to be offered to the block, highest priority first.
+    ///
+    /// Candidates which are left out are not proposed, but remain in the deploy buffer.
+    fn order(&self, candidates: Vec<Candidate>, base_fee: u64) -> Vec<Candidate>;
+}
+
+/// Returns the block builder configured by the given deploy buffer config.
+pub(super) fn from_config(config: &Config) -> Box<dyn BlockBuilder> {
+    let strategy: Box<dyn BlockBuilder> = match config.block_builder {
+        BlockBuilderStrategy::Bucketed => Box::new(BucketedBlockBuilder),
+        BlockBuilderStrategy::FeePriority => Box::new(FeePriorityBlockBuilder),
+    };
+    let access_lists = AccessLists::new(config);
+    if access_lists.is_empty() {
+        strategy
+    } else {
+        Box::new(AccessListBlockBuilder {
+            inner: strategy,
+            access_lists,
+        })
+    }
+}
+
+/// Returns the tip per gas the candidate pays to the proposer in a block with the given base fee.
+fn tip(footprint: &DeployFootprint, base_fee: u64) -> u64 {
+    footprint
+        .header
+        .effective_gas_price(base_fee)
+        .map_or(0, |gas_price| gas_price - base_fee)
+}
+
+/// Groups the candidates into buckets of deploys with equal body hashes, each sorted so that the
+/// deploy offering the highest tip is popped first.
+pub(super) fn buckets(
+    candidates: Vec<Candidate>,
+    base_fee: u64,
+) -> HashMap<Digest, Vec<Candidate>> {
+    let mut buckets: HashMap<Digest, Vec<Candidate>> = HashMap::new();
+    for (with_approvals, footprint) in candidates {
+        let body_hash = *footprint.header.body_hash();
+        buckets
+            .entry(body_hash)
+            .or_default()
+            .push((with_approvals, footprint));
+    }
+    for bucket in buckets.values_mut() {
+        bucket.sort_by_key(|(_, footprint)| tip(footprint, base_fee));
+    }
+    buckets
+}
+
+/// The default strategy: deploys are grouped into buckets by body hash, so that many deploys with
+/// equal bodies can't crowd out all others.
+///
+/// Buckets are visited in order of the tip offered by their next deploy.  Among equal tips they are
+/// visited round-robin, i.e. by the number of passes, then by their initial order.
+#[derive(Debug)]
+struct BucketedBlockBuilder;
+
+impl BlockBuilder for BucketedBlockBuilder {
+    fn order(&self, candidates: Vec<Candidate>, base_fee: u64) -> Vec<Candidate> {
+        let bucket_tip =
+            |bucket: &[Candidate]| bucket.last().map(|(_, footprint)| tip(footprint, base_fee));
+
+        let mut ordered = Vec::with_capacity(candidates.len());
+        let mut buckets = buckets(candidates, base_fee);
+        let mut body_hashes_queue: BinaryHeap<_> = buckets
+            .iter()
+            .enumerate()
+            .filter_map(|(position, (body_hash, bucket))| {
+                bucket_tip(bucket).map(|tip| (tip, Reverse(0_usize), Reverse(position), *body_hash))
+            })
+            .collect();
+
+        while let Some((_, Reverse(pass), position, body_hash)) = body_hashes_queue.pop() {
+            let Some(bucket) = buckets.get_mut(&body_hash) else {
+                continue;
+            };
+            let Some(candidate) = bucket.pop() else {
+                continue;
+            };
+            // if the bucket isn't empty, push the hash back into the queue to be processed again on
+            // the next pass
+            if let Some(next_tip) = bucket_tip(bucket) {
+                body_hashes_queue.push((next_tip, Reverse(pass + 1), position, body_hash));
+            }
+            ordered.push(candidate);
+        }
+        ordered
+    }
+}
+
+/// Deploys are offered strictly in order of the tip they pay, highest first.  Among equal tips,
+/// older deploys are offered first.
+#[derive(Debug)]
+struct FeePriorityBlockBuilder;
+
+impl BlockBuilder for FeePriorityBlockBuilder {
+    fn order(&self, mut candidates: Vec<Candidate>, base_fee: u64) -> Vec<Candidate> {
+        candidates.sort_by(|(hash1, footprint1), (hash2, footprint2)| {
+            tip(footprint2, base_fee)
+                .cmp(&tip(footprint1, base_fee))
+                .then_with(|| {
+                    footprint1
+                        .header
+                        .timestamp()
+                        .cmp(&footprint2.header.timestamp())
+                })
+                .then_with(|| hash1.deploy_hash().cmp(hash2.deploy_hash()))
+        });
+        candidates
+    }
+}
+
+/// The operator-defined lists of accounts and contracts whose deploys are prioritized, allowed or
+/// denied.
+#[derive(Debug)]
+struct AccessLists {
+    priority_accounts: BTreeSet<PublicKey>,
+    allowed_accounts: BTreeSet<PublicKey>,
+    denied_accounts: BTreeSet<PublicKey>,
+    allowed_contracts: BTreeSet<ContractReference>,
+    denied_contracts: BTreeSet<ContractReference>,
+}
+
+impl AccessLists {
+    fn new(config: &Config) -> Self {
+        AccessLists {
+            priority_accounts: config.priority_accounts.iter().cloned().collect(),
+            allowed_accounts: config.allowed_accounts.iter().cloned().collect(),
+            denied_accounts: config.denied_accounts.iter().cloned().collect(),
+            allowed_contracts: config.allowed_contracts.iter().copied().collect(),
+            denied_contracts: config.denied_contracts.iter().copied().collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.priority_accounts.is_empty()
+            && self.allowed_accounts.is_empty()
+            && self.denied_accounts.is_empty()
+            && self.allowed_contracts.is_empty()
+            && self.denied_contracts.is_empty()
+    }
+
+    /// Returns whether the lists permit proposing the deploy.
+    ///
+    /// The contract lists are only checked against the stored contract the session code calls
+    /// directly, by hash.  This is all that can be determined without executing the deploy: calls
+    /// made from session Wasm, payment code or other contracts are not restricted, so the lists are
+    /// a proposer's policy rather than a guarantee that the contracts are never executed.
+    fn permits(&self, footprint: &DeployFootprint) -> bool {
+        let account = footprint.header.account();
+        if self.denied_accounts.contains(account) {
+            return false;
+        }
+        if !self.allowed_accounts.is_empty() && !self.allowed_accounts.contains(account) {
+            return false;
+        }
+        let contract = match footprint.session_contract {
+            None => return true,
+            Some(SessionContract::Contract(hash)) => Some(ContractReference::Contract(hash)),
+            Some(SessionContract::ContractPackage(hash)) => {
+                Some(ContractReference::ContractPackage(hash))
+            }
+            // Contracts called by name can't be resolved without global state.
+            Some(SessionContract::ByName) => None,
+        };
+        match contract {
+            Some(contract) => {
+                !self.denied_contracts.contains(&contract)
+                    && (self.allowed_contracts.is_empty()
+                        || self.allowed_contracts.contains(&contract))
+            }
+            None => self.allowed_contracts.is_empty(),
+        }
+    }
+}
+
+/// Wraps another strategy, leaving out the deploys denied by the operator's access lists and
+/// offering the deploys from priority accounts before all others.
+#[derive(Debug)]
+struct AccessListBlockBuilder {
+    inner: Box<dyn BlockBuilder>,
+    access_lists: AccessLists,
+}
+
+impl BlockBuilder for AccessListBlockBuilder {
+    fn order(&self, candidates: Vec<Candidate>, base_fee: u64) -> Vec<Candidate> {
+        let (prioritized, others): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .filter(|(_, footprint)| self.access_lists.permits(footprint))
+            .partition(|(_, footprint)| {
+                self.access_lists
+                    .priority_accounts
+                    .contains(footprint.header.account())
+            });
+        let mut ordered = self.inner.order(prioritized, base_fee);
+        ordered.extend(self.inner.order(others, base_fee));
+        ordered
+    }
+}
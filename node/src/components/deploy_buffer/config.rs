@@ -1,15 +1,62 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_types::TimeDiff;
+use casper_types::{ContractHash, ContractPackageHash, PublicKey, TimeDiff};
 
 const DEFAULT_EXPIRY_CHECK_INTERVAL: &str = "1min";
 
-#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+/// The strategy used to order the deploys offered to a proposed block.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockBuilderStrategy {
+    /// Deploys with equal bodies are grouped into buckets, which are visited round-robin in order
+    /// of the tip offered by their next deploy.
+    #[default]
+    Bucketed,
+    /// Deploys are offered strictly in order of the gas price they pay, highest first.
+    FeePriority,
+}
+
+/// A stored contract or contract package, referenced by its hash.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(untagged)]
+pub enum ContractReference {
+    /// A contract hash, formatted as `contract-<hex>`.
+    Contract(ContractHash),
+    /// A contract package hash, formatted as `contract-package-<hex>`.
+    ContractPackage(ContractPackageHash),
+}
+
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// The interval of checking for expired deploys.
     pub expiry_check_interval: TimeDiff,
+    /// The strategy used to order the deploys offered to a proposed block.
+    #[serde(default)]
+    pub block_builder: BlockBuilderStrategy,
+    /// Accounts whose deploys are offered to a proposed block before all others.
+    #[serde(default)]
+    pub priority_accounts: Vec<PublicKey>,
+    /// If not empty, only deploys from these accounts are proposed.
+    #[serde(default)]
+    pub allowed_accounts: Vec<PublicKey>,
+    /// Accounts whose deploys are never proposed.
+    #[serde(default)]
+    pub denied_accounts: Vec<PublicKey>,
+    /// If not empty, deploys whose session code calls a stored contract are only proposed if they
+    /// call one of these by hash.
+    ///
+    /// Only the contract called directly by the session code is checked, not contracts called from
+    /// session Wasm, payment code or other contracts.
+    #[serde(default)]
+    pub allowed_contracts: Vec<ContractReference>,
+    /// Deploys whose session code calls one of these stored contracts by hash are never proposed.
+    ///
+    /// Like `allowed_contracts`, this only applies to the contract called directly by the session
+    /// code, so it doesn't prevent the listed contracts from being called in any other way.
+    #[serde(default)]
+    pub denied_contracts: Vec<ContractReference>,
 }
 
 impl Config {
@@ -23,6 +70,12 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             expiry_check_interval: DEFAULT_EXPIRY_CHECK_INTERVAL.parse().unwrap(),
+            block_builder: BlockBuilderStrategy::default(),
+            priority_accounts: vec![],
+            allowed_accounts: vec![],
+            denied_accounts: vec![],
+            allowed_contracts: vec![],
+            denied_contracts: vec![],
         }
     }
 }
@@ -11,6 +11,8 @@ pub(super) struct Metrics {
     pub(super) held_deploys: IntGauge,
     /// Number of deploys that should not be included in future proposals ever again.
     pub(super) dead_deploys: IntGauge,
+    /// Base fee per unit of gas which deploys must pay to be proposed.
+    pub(super) base_fee: IntGauge,
     registry: Registry,
 }
 
@@ -29,15 +31,21 @@ impl Metrics {
             "deploy_buffer_dead_deploys".to_string(),
             "number of deploys that should not be included in future proposals.".to_string(),
         )?;
+        let base_fee = IntGauge::new(
+            "deploy_buffer_base_fee".to_string(),
+            "base fee per unit of gas which deploys must pay to be proposed.".to_string(),
+        )?;
 
         registry.register(Box::new(total_deploys.clone()))?;
         registry.register(Box::new(held_deploys.clone()))?;
         registry.register(Box::new(dead_deploys.clone()))?;
+        registry.register(Box::new(base_fee.clone()))?;
 
         Ok(Metrics {
             total_deploys,
             held_deploys,
             dead_deploys,
+            base_fee,
             registry: registry.clone(),
         })
     }
@@ -48,5 +56,6 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.total_deploys);
         unregister_metric!(self.registry, self.held_deploys);
         unregister_metric!(self.registry, self.dead_deploys);
+        unregister_metric!(self.registry, self.base_fee);
     }
 }
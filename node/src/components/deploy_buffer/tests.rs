@@ -3,13 +3,16 @@ use std::iter;
 use prometheus::Registry;
 use rand::Rng;
 
-use casper_types::{testing::TestRng, EraId, SecretKey, TimeDiff};
+use casper_types::{testing::TestRng, ContractHash, EraId, PublicKey, SecretKey, TimeDiff};
 
-use super::*;
+use super::{
+    config::{BlockBuilderStrategy, ContractReference},
+    *,
+};
 use crate::{
     effect::announcements::DeployBufferAnnouncement::{self, DeploysExpired},
     reactor::{EventQueueHandle, QueueKind, Scheduler},
-    types::{Block, FinalizedBlock},
+    types::{Block, FeeLimits, FinalizedBlock, TestBlockBuilder},
     utils,
 };
 
@@ -691,6 +694,290 @@ fn should_have_diverse_proposable_blocks_with_stocked_buffer() {
     );
 }
 
+#[test]
+fn should_propose_deploys_offering_highest_tips_first() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_deploy_count: 10,
+        block_max_transfer_count: 10,
+        block_max_approval_count: 20,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+
+    let deploys = create_valid_deploys(&mut rng, 100, DeployType::Random, None, None);
+    deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+
+    let timestamp = Timestamp::now();
+    let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    assert!(!appendable_block.deploy_and_transfer_set().is_empty());
+
+    let gas_price_of = |deploy_hash: &DeployHash| {
+        deploys
+            .iter()
+            .find(|deploy| deploy.hash() == deploy_hash)
+            .map(|deploy| deploy.header().gas_price())
+            .expect("should have deploy")
+    };
+    let lowest_proposed_gas_price = appendable_block
+        .deploy_and_transfer_set()
+        .iter()
+        .map(gas_price_of)
+        .min()
+        .expect("should have proposed deploys");
+    let highest_remaining_gas_price = deploy_buffer
+        .proposable()
+        .iter()
+        .filter(|(_, footprint)| !footprint.is_transfer)
+        .map(|(_, footprint)| footprint.header.gas_price())
+        .max()
+        .expect("should have remaining deploys");
+    assert!(lowest_proposed_gas_price >= highest_remaining_gas_price);
+}
+
+#[test]
+fn should_not_propose_deploys_below_base_fee() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig::default();
+    let mut deploy_buffer =
+        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+    assert_eq!(deploy_buffer.base_fee(), Some(deploy_config.min_base_fee));
+
+    // the base fee for the next block is derived from the highest block
+    let block = Block::random_with_deploys(&mut rng, iter::empty());
+    deploy_buffer.register_block(&block);
+    let extension = block
+        .header()
+        .extension()
+        .expect("should have extended header");
+    let expected_base_fee = deploy_config.next_base_fee(extension.base_fee(), extension.gas_used());
+    assert_eq!(deploy_buffer.base_fee(), Some(expected_base_fee));
+
+    let deploys = create_valid_deploys(&mut rng, 50, DeployType::Random, None, None);
+    deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+
+    let base_fee = 50;
+    deploy_buffer.next_base_fee = Some((block.header().height(), Some(base_fee)));
+    let proposable = deploy_buffer.proposable();
+    let expected_count = deploys
+        .iter()
+        .filter(|deploy| deploy.header().gas_price() >= base_fee)
+        .count();
+    assert_eq!(proposable.len(), expected_count);
+    assert!(proposable
+        .iter()
+        .all(|(_, footprint)| footprint.header.gas_price() >= base_fee));
+
+    // deploys below the base fee stay buffered
+    deploy_buffer.update_all_metrics();
+    let block_deploy_count = block.deploy_and_transfer_hashes().count();
+    assert_container_sizes(
+        &deploy_buffer,
+        deploys.len() + block_deploy_count,
+        block_deploy_count,
+        0,
+    );
+    assert_eq!(deploy_buffer.metrics.base_fee.get(), base_fee as i64);
+}
+
+#[test]
+fn should_not_enforce_base_fee_after_legacy_block() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+
+    let block = TestBlockBuilder::new().legacy_header().build(&mut rng);
+    deploy_buffer.register_block(&block);
+    assert_eq!(deploy_buffer.base_fee(), None);
+
+    let deploys = create_valid_deploys(&mut rng, 20, DeployType::Random, None, None);
+    deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    assert_eq!(deploy_buffer.proposable().len(), deploys.len());
+}
+
+/// Creates a deploy from the given account, calling a stored contract by a random name so that its
+/// body hash is unique.
+fn create_deploy_from(rng: &mut TestRng, secret_key: &SecretKey) -> Deploy {
+    let secret_key =
+        SecretKey::from_pem(secret_key.to_pem().expect("should pemify")).expect("should un-pemify");
+    let contract_name = format!("{}", rng.gen::<u64>());
+    Deploy::random_contract_by_name(
+        rng,
+        Some(secret_key),
+        Some(contract_name),
+        None,
+        Some(Timestamp::now()),
+        None,
+    )
+}
+
+#[test]
+fn should_order_deploys_by_gas_price_with_fee_priority() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        block_builder: BlockBuilderStrategy::FeePriority,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+
+    create_valid_deploys(&mut rng, 100, DeployType::Random, None, None)
+        .into_iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy));
+    let proposable = deploy_buffer.proposable();
+    let count = proposable.len();
+
+    let ordered = deploy_buffer
+        .block_builder
+        .order(proposable, deploy_buffer.base_fee().unwrap_or_default());
+    assert_eq!(ordered.len(), count);
+    assert!(ordered
+        .iter()
+        .tuple_windows()
+        .all(|((_, footprint1), (_, footprint2))| {
+            footprint1.header.gas_price() >= footprint2.header.gas_price()
+        }));
+}
+
+/// Creates a random deploy with the given fee limits.
+fn create_deploy_with_fee_limits(rng: &mut TestRng, fee_limits: FeeLimits) -> Deploy {
+    let deploy = Deploy::random(rng);
+    Deploy::new_with_fee_limits(
+        Timestamp::now(),
+        TimeDiff::from_seconds(60),
+        fee_limits,
+        vec![],
+        deploy.header().chain_name().to_string(),
+        deploy.payment().clone(),
+        deploy.session().clone(),
+        &SecretKey::random(rng),
+        None,
+    )
+}
+
+#[test]
+fn should_order_deploys_by_tip_with_fee_limits() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        block_builder: BlockBuilderStrategy::FeePriority,
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+    let base_fee = 50;
+    deploy_buffer.next_base_fee = Some((0, Some(base_fee)));
+
+    // a high maximum fee with a capped tip pays less than a lower maximum fee without a cap
+    let capped = create_deploy_with_fee_limits(&mut rng, FeeLimits::new(base_fee + 100, 1));
+    let uncapped =
+        create_deploy_with_fee_limits(&mut rng, FeeLimits::new(base_fee + 10, base_fee + 10));
+    let below_base_fee = create_deploy_with_fee_limits(&mut rng, FeeLimits::new(base_fee - 1, 0));
+    for deploy in [&capped, &uncapped, &below_base_fee] {
+        deploy_buffer.register_deploy(deploy.clone());
+    }
+
+    let ordered: Vec<_> = deploy_buffer
+        .block_builder
+        .order(deploy_buffer.proposable(), base_fee)
+        .into_iter()
+        .map(|(with_approvals, _)| *with_approvals.deploy_hash())
+        .collect();
+    assert_eq!(ordered, vec![*uncapped.hash(), *capped.hash()]);
+}
+
+#[test]
+fn should_propose_deploys_from_priority_accounts_first() {
+    let mut rng = TestRng::new();
+    let deploy_config = DeployConfig {
+        block_max_deploy_count: 2,
+        block_max_transfer_count: 0,
+        block_max_approval_count: 2,
+        ..Default::default()
+    };
+    let institution = SecretKey::random(&mut rng);
+    let config = Config {
+        priority_accounts: vec![PublicKey::from(&institution)],
+        ..Default::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(deploy_config, config, &Registry::new()).unwrap();
+
+    for _ in 0..10 {
+        let other = SecretKey::random(&mut rng);
+        deploy_buffer.register_deploy(create_deploy_from(&mut rng, &other));
+    }
+    let prioritized: HashSet<_> = (0..2)
+        .map(|_| {
+            let deploy = create_deploy_from(&mut rng, &institution);
+            let deploy_hash = *deploy.hash();
+            deploy_buffer.register_deploy(deploy);
+            deploy_hash
+        })
+        .collect();
+
+    let timestamp = Timestamp::now();
+    let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    let proposed: HashSet<_> = appendable_block
+        .deploy_and_transfer_set()
+        .iter()
+        .copied()
+        .collect();
+    assert_eq!(proposed, prioritized);
+}
+
+#[test]
+fn should_not_propose_deploys_denied_by_access_lists() {
+    let mut rng = TestRng::new();
+    let allowed = SecretKey::random(&mut rng);
+    let denied = SecretKey::random(&mut rng);
+    // calls the contract with hash `[19; 32]`
+    let denied_contract_deploy =
+        Deploy::random_with_missing_entry_point_in_session_contract(&mut rng);
+    let config = Config {
+        allowed_accounts: vec![
+            PublicKey::from(&allowed),
+            PublicKey::from(&denied),
+            denied_contract_deploy.header().account().clone(),
+        ],
+        denied_accounts: vec![PublicKey::from(&denied)],
+        denied_contracts: vec![ContractReference::Contract(ContractHash::new([19; 32]))],
+        ..Default::default()
+    };
+    let mut deploy_buffer =
+        DeployBuffer::new(DeployConfig::default(), config, &Registry::new()).unwrap();
+
+    let allowed_deploy = create_deploy_from(&mut rng, &allowed);
+    let allowed_deploy_hash = *allowed_deploy.hash();
+    deploy_buffer.register_deploy(allowed_deploy);
+    deploy_buffer.register_deploy(create_deploy_from(&mut rng, &denied));
+    deploy_buffer.register_deploy(denied_contract_deploy);
+    // from an account which isn't allowed
+    let other = SecretKey::random(&mut rng);
+    deploy_buffer.register_deploy(create_deploy_from(&mut rng, &other));
+    assert_eq!(deploy_buffer.proposable().len(), 4);
+
+    let timestamp = Timestamp::now();
+    let expiry = timestamp.saturating_add(TimeDiff::from_seconds(1));
+    let appendable_block = deploy_buffer.appendable_block(timestamp, expiry);
+    let proposed: Vec<_> = appendable_block
+        .deploy_and_transfer_set()
+        .iter()
+        .copied()
+        .collect();
+    assert_eq!(proposed, vec![allowed_deploy_hash]);
+
+    // the denied deploys remain in the buffer
+    assert_container_sizes(&deploy_buffer, 4, 0, 1);
+}
+
 /// Event for the mock reactor.
 #[derive(Debug)]
 enum ReactorEvent {
@@ -904,3 +1191,15 @@ fn test_buckets_mixed_load() {
         Timestamp::now() + TimeDiff::from_millis(16384 / 6),
     );
 }
+
+#[test]
+fn should_default_omitted_block_builder_options() {
+    let config: Config = toml::from_str("expiry_check_interval = '2min'").unwrap();
+    assert_eq!(config.expiry_check_interval, TimeDiff::from_seconds(120));
+    assert_eq!(config.block_builder, BlockBuilderStrategy::Bucketed);
+    assert!(config.priority_accounts.is_empty());
+    assert!(config.allowed_accounts.is_empty());
+    assert!(config.denied_accounts.is_empty());
+    assert!(config.allowed_contracts.is_empty());
+    assert!(config.denied_contracts.is_empty());
+}
@@ -1,5 +1,6 @@
 use std::{
     fmt::{self, Display, Formatter},
+    net::SocketAddr,
     str::FromStr,
 };
 
@@ -8,6 +9,7 @@ use structopt::StructOpt;
 use thiserror::Error;
 
 use super::StopAtSpec;
+use crate::types::NodeId;
 
 /// Command processing error.
 ///
@@ -91,6 +93,27 @@ pub(super) enum Action {
     DumpQueues,
     /// Get detailed networking insights.
     NetInfo,
+    /// Show and modify the static peers and the node ID allowlist.
+    ///
+    /// All given changes are applied before the resulting peer groups are shown. Changes to the
+    /// allowlist only affect connections established afterwards.
+    PeerGroups {
+        /// Address of a peer to always stay connected to.
+        #[structopt(long)]
+        add_static: Vec<SocketAddr>,
+        /// Address of a static peer to remove.
+        #[structopt(long)]
+        remove_static: Vec<SocketAddr>,
+        /// Node ID, as full hex fingerprint, to add to the allowlist.
+        #[structopt(long)]
+        allow: Vec<NodeId>,
+        /// Node ID, as full hex fingerprint, to remove from the allowlist.
+        #[structopt(long)]
+        disallow: Vec<NodeId>,
+        /// Whether to reject connections to and from nodes not on the allowlist.
+        #[structopt(long)]
+        allowlist_only: Option<bool>,
+    },
     /// Stop the node at a certain condition.
     Stop {
         /// When to stop the node.
@@ -30,7 +30,7 @@ use super::{
     util::ShowUnixAddr,
 };
 use crate::{
-    components::consensus::EraDump,
+    components::{consensus::EraDump, network::PeerGroupChange},
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
         diagnostics_port::DumpConsensusStateRequest,
@@ -324,6 +324,36 @@ impl Session {
                         let insights = effect_builder.get_network_insights().await;
                         self.send_to_client(writer, &insights).await?;
                     }
+                    Action::PeerGroups {
+                        ref add_static,
+                        ref remove_static,
+                        ref allow,
+                        ref disallow,
+                        allowlist_only,
+                    } => {
+                        let changes = add_static
+                            .iter()
+                            .copied()
+                            .map(PeerGroupChange::AddStaticPeer)
+                            .chain(
+                                remove_static
+                                    .iter()
+                                    .copied()
+                                    .map(PeerGroupChange::RemoveStaticPeer),
+                            )
+                            .chain(allow.iter().copied().map(PeerGroupChange::AllowNode))
+                            .chain(disallow.iter().copied().map(PeerGroupChange::DisallowNode))
+                            .chain(allowlist_only.map(PeerGroupChange::SetAllowlistOnly))
+                            .collect::<Vec<_>>();
+                        let msg = if changes.is_empty() {
+                            "retrieving peer groups"
+                        } else {
+                            "updating peer groups"
+                        };
+                        let peer_groups = effect_builder.update_peer_groups(changes).await;
+                        self.send_outcome(writer, &Outcome::success(msg)).await?;
+                        self.send_to_client(writer, &peer_groups).await?;
+                    }
                     Action::Stop { at, clear } => {
                         let (msg, stop_at) = if clear {
                             ("clearing stopping point", None)
@@ -8,6 +8,7 @@ mod fetcher_impls;
 mod item_fetcher;
 mod item_handle;
 mod metrics;
+mod peer_scores;
 mod tag;
 mod tests;
 
@@ -45,6 +46,7 @@ pub(crate) use fetched_data::FetchedData;
 use item_fetcher::{ItemFetcher, StoringState};
 use item_handle::ItemHandle;
 use metrics::Metrics;
+pub(crate) use peer_scores::PeerScores;
 pub(crate) use tag::Tag;
 
 pub(crate) type FetchResult<T> = Result<FetchedData<T>, Error<T>>;
@@ -63,12 +65,15 @@ where
     name: &'static str,
     #[data_size(skip)]
     metrics: Metrics,
+    #[data_size(skip)]
+    peer_scores: PeerScores,
 }
 
 impl<T: FetchItem> Fetcher<T> {
     pub(crate) fn new(
         name: &'static str,
         config: &Config,
+        peer_scores: PeerScores,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(Fetcher {
@@ -76,6 +81,7 @@ impl<T: FetchItem> Fetcher<T> {
             item_handles: HashMap::new(),
             name,
             metrics: Metrics::new(name, registry)?,
+            peer_scores,
         })
     }
 }
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use futures::FutureExt;
 
 use crate::{
-    components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
+    components::fetcher::{
+        metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, PeerScores, StoringState,
+    },
     effect::{requests::StorageRequest, EffectBuilder},
     types::{ApprovalsHashes, BlockHash, NodeId},
 };
@@ -27,6 +29,10 @@ impl ItemFetcher<ApprovalsHashes> for Fetcher<ApprovalsHashes> {
         self.get_from_peer_timeout
     }
 
+    fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: BlockHash,
@@ -3,7 +3,9 @@ use std::{collections::HashMap, time::Duration};
 use async_trait::async_trait;
 
 use crate::{
-    components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
+    components::fetcher::{
+        metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, PeerScores, StoringState,
+    },
     effect::{requests::StorageRequest, EffectBuilder},
     types::{BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, NodeId},
 };
@@ -29,6 +31,10 @@ impl ItemFetcher<BlockExecutionResultsOrChunk> for Fetcher<BlockExecutionResults
         self.get_from_peer_timeout
     }
 
+    fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: BlockExecutionResultsOrChunkId,
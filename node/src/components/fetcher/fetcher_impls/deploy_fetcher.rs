@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use futures::FutureExt;
 
 use crate::{
-    components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
+    components::fetcher::{
+        metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, PeerScores, StoringState,
+    },
     effect::{requests::StorageRequest, EffectBuilder},
     types::{Deploy, DeployId, FinalizedApprovals, NodeId},
 };
@@ -25,6 +27,10 @@ impl ItemFetcher<Deploy> for Fetcher<Deploy> {
         self.get_from_peer_timeout
     }
 
+    fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     async fn get_locally<REv: From<StorageRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: DeployId,
@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use futures::FutureExt;
 
 use crate::{
-    components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
+    components::fetcher::{
+        metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, PeerScores, StoringState,
+    },
     effect::{
         announcements::FetchedNewFinalitySignatureAnnouncement,
         requests::{BlockAccumulatorRequest, StorageRequest},
@@ -32,6 +34,10 @@ impl ItemFetcher<FinalitySignature> for Fetcher<FinalitySignature> {
         self.get_from_peer_timeout
     }
 
+    fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     async fn get_locally<REv: From<StorageRequest> + From<BlockAccumulatorRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: Box<FinalitySignatureId>,
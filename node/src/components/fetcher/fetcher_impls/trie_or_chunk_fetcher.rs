@@ -4,7 +4,9 @@ use async_trait::async_trait;
 use tracing::error;
 
 use crate::{
-    components::fetcher::{metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, StoringState},
+    components::fetcher::{
+        metrics::Metrics, Fetcher, ItemFetcher, ItemHandle, PeerScores, StoringState,
+    },
     effect::{requests::ContractRuntimeRequest, EffectBuilder},
     types::{NodeId, TrieOrChunk, TrieOrChunkId},
 };
@@ -27,6 +29,10 @@ impl ItemFetcher<TrieOrChunk> for Fetcher<TrieOrChunk> {
         self.get_from_peer_timeout
     }
 
+    fn peer_scores(&self) -> &PeerScores {
+        &self.peer_scores
+    }
+
     async fn get_locally<REv: From<ContractRuntimeRequest> + Send>(
         effect_builder: EffectBuilder<REv>,
         id: TrieOrChunkId,
@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use futures::future::BoxFuture;
 use tracing::{debug, error, trace};
 
-use super::{Error, Event, FetchResponder, FetchedData, ItemHandle, Metrics};
+use super::{Error, Event, FetchResponder, FetchedData, ItemHandle, Metrics, PeerScores};
 use crate::{
     components::{fetcher::FetchItem, network::blocklist::BlocklistJustification},
     effect::{
@@ -44,6 +44,9 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
 
     fn peer_timeout(&self) -> Duration;
 
+    /// The performance statistics of peers, updated with the outcome of every request.
+    fn peer_scores(&self) -> &PeerScores;
+
     /// We've been asked to fetch the item by another component of this node.  We'll try to get it
     /// locally first (generally from our own storage component), and if that fails, we'll send a
     /// request to `peer` for the item.
@@ -163,6 +166,7 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
 
         if let Err(err) = item.validate(validation_metadata) {
             debug!(%peer, %err, ?item, "peer sent invalid item");
+            self.peer_scores().record_failure(peer, T::TAG);
             effect_builder
                 .announce_block_peer_with_justification(
                     peer,
@@ -279,6 +283,37 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
             + From<FetchedNewFinalitySignatureAnnouncement>
             + Send;
 
+    /// Records the outcome of a request to `peer` in the peer scores, if it is still pending.
+    fn record_outcome(&mut self, id: &T::Id, result: &Result<T, Error<T>>, peer: NodeId) {
+        let elapsed = match self
+            .item_handles()
+            .get(id)
+            .and_then(|item_handles| item_handles.get(&peer))
+        {
+            Some(item_handle) => item_handle.elapsed(),
+            None => return,
+        };
+        match result {
+            Ok(item) => {
+                let size = bincode::serialized_size(item).unwrap_or_default();
+                self.peer_scores()
+                    .record_success(peer, T::TAG, elapsed, size);
+            }
+            Err(Error::TimedOut { .. }) => {
+                self.peer_scores().record_timeout(peer, T::TAG, elapsed);
+            }
+            Err(Error::Absent { .. } | Error::Rejected { .. }) => {
+                self.peer_scores().record_failure(peer, T::TAG);
+            }
+            Err(
+                Error::CouldNotConstructGetRequest { .. }
+                | Error::ValidationMetadataMismatch { .. },
+            ) => {
+                // These are local failures, not caused by the peer.
+            }
+        }
+    }
+
     /// Handles signalling responders with the item or an error.
     fn signal(
         &mut self,
@@ -286,6 +321,7 @@ pub(super) trait ItemFetcher<T: FetchItem + 'static> {
         result: Result<T, Error<T>>,
         peer: NodeId,
     ) -> Effects<Event<T>> {
+        self.record_outcome(&id, &result, peer);
         match result {
             Ok(fetched_item) if Self::SAFE_TO_RESPOND_TO_ALL => {
                 self.respond_to_all(id, FetchedData::from_peer(fetched_item, peer))
@@ -1,3 +1,5 @@
+use std::time::{Duration, Instant};
+
 use datasize::DataSize;
 
 use super::{FetchItem, FetchResponder};
@@ -9,6 +11,9 @@ where
 {
     validation_metadata: Box<T::ValidationMetadata>,
     responders: Vec<FetchResponder<T>>,
+    /// The time the request for the item was sent.
+    #[data_size(skip)]
+    requested_at: Instant,
 }
 
 impl<T: FetchItem> ItemHandle<T> {
@@ -19,6 +24,7 @@ impl<T: FetchItem> ItemHandle<T> {
         Self {
             validation_metadata,
             responders: vec![responder],
+            requested_at: Instant::now(),
         }
     }
 
@@ -26,6 +32,11 @@ impl<T: FetchItem> ItemHandle<T> {
         &self.validation_metadata
     }
 
+    /// Returns the time elapsed since the request for the item was sent.
+    pub(super) fn elapsed(&self) -> Duration {
+        self.requested_at.elapsed()
+    }
+
     pub(super) fn push_responder(&mut self, responder: FetchResponder<T>) {
         self.responders.push(responder)
     }
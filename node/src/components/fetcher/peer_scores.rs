@@ -0,0 +1,267 @@
+//! Statistics on how well peers serve fetch requests.
+//!
+//! Every fetcher records the outcome of the requests it sends to peers in a [`PeerScores`] instance
+//! shared with the components deciding which peers to ask for items.  Peers are scored separately
+//! for every [`Tag`], since e.g. a peer quickly serving deploys may well be slow to serve large
+//! trie chunks.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use rand::Rng;
+use tracing::trace;
+
+use super::Tag;
+use crate::{types::NodeId, NodeRng};
+
+/// Weight of a new observation in the moving averages.
+const SMOOTHING_FACTOR: f64 = 0.2;
+/// Lower bound on the latency used when scoring a peer, in seconds.
+const MIN_LATENCY: f64 = 0.001;
+/// Lower bound on the success rate used when scoring a peer, so that peers which failed
+/// repeatedly are still asked occasionally.
+const MIN_SUCCESS_RATE: f64 = 0.01;
+
+/// Performance of a single peer serving items of a single type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PeerStats {
+    /// Moving average of the time taken by the peer to respond, in seconds.
+    latency: Option<f64>,
+    /// Moving average of the throughput of successful responses, in bytes per second.
+    throughput: Option<f64>,
+    /// Moving average of the fraction of failed requests.
+    error_rate: f64,
+}
+
+impl PeerStats {
+    fn new() -> Self {
+        PeerStats {
+            latency: None,
+            throughput: None,
+            error_rate: 0.0,
+        }
+    }
+
+    /// Returns the weight of the peer when choosing peers to ask, i.e. its expected number of
+    /// successful responses per second.
+    ///
+    /// Returns `None` if the peer has not responded yet.
+    fn weight(&self) -> Option<f64> {
+        let latency = self.latency?;
+        Some((1.0 - self.error_rate).max(MIN_SUCCESS_RATE) / latency.max(MIN_LATENCY))
+    }
+
+    fn record_latency(&mut self, elapsed: Duration) {
+        self.latency = Some(average(self.latency, elapsed.as_secs_f64()));
+    }
+
+    fn record_outcome(&mut self, failed: bool) {
+        self.error_rate = average(Some(self.error_rate), if failed { 1.0 } else { 0.0 });
+    }
+}
+
+impl Display for PeerStats {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self.latency {
+            Some(latency) => write!(formatter, "latency {:.3}s", latency)?,
+            None => write!(formatter, "latency unknown")?,
+        }
+        match self.throughput {
+            Some(throughput) => write!(formatter, ", throughput {:.0} B/s", throughput)?,
+            None => write!(formatter, ", throughput unknown")?,
+        }
+        write!(formatter, ", error rate {:.2}", self.error_rate)
+    }
+}
+
+/// Updates a moving average with a new observation.
+fn average(current: Option<f64>, observation: f64) -> f64 {
+    match current {
+        Some(current) => current + SMOOTHING_FACTOR * (observation - current),
+        None => observation,
+    }
+}
+
+/// Performance statistics of all peers, shared between the fetchers and the components choosing
+/// peers to fetch from.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PeerScores(Arc<RwLock<HashMap<(NodeId, Tag), PeerStats>>>);
+
+impl PeerScores {
+    /// Records a successful response of `size` bytes, received `elapsed` after the request.
+    pub(crate) fn record_success(&self, peer: NodeId, tag: Tag, elapsed: Duration, size: u64) {
+        self.update(peer, tag, |stats| {
+            stats.record_latency(elapsed);
+            stats.record_outcome(false);
+            let throughput = size as f64 / elapsed.as_secs_f64().max(MIN_LATENCY);
+            stats.throughput = Some(average(stats.throughput, throughput));
+        });
+    }
+
+    /// Records a request which timed out after `elapsed`.
+    pub(crate) fn record_timeout(&self, peer: NodeId, tag: Tag, elapsed: Duration) {
+        self.update(peer, tag, |stats| {
+            stats.record_latency(elapsed);
+            stats.record_outcome(true);
+        });
+    }
+
+    /// Records a request the peer responded to without providing a valid item.
+    pub(crate) fn record_failure(&self, peer: NodeId, tag: Tag) {
+        self.update(peer, tag, |stats| stats.record_outcome(true));
+    }
+
+    /// Returns the peers in random order, with better performing peers likely to come first.
+    ///
+    /// Every peer is chosen with probability proportional to its expected rate of successful
+    /// responses, so that requests are spread across all good peers rather than all being sent to
+    /// the single best one.  Peers without statistics yet are weighted like an average peer.
+    pub(crate) fn order_peers(
+        &self,
+        tag: Tag,
+        peers: Vec<NodeId>,
+        rng: &mut NodeRng,
+    ) -> Vec<NodeId> {
+        let weights: Vec<Option<f64>> = {
+            let scores = self.0.read().expect("peer scores lock poisoned");
+            peers
+                .iter()
+                .map(|peer| scores.get(&(*peer, tag)).and_then(PeerStats::weight))
+                .collect()
+        };
+        let known: Vec<f64> = weights.iter().flatten().copied().collect();
+        let default_weight = if known.is_empty() {
+            1.0
+        } else {
+            known.iter().sum::<f64>() / known.len() as f64
+        };
+
+        // Weighted random sampling without replacement: sorting by `u^(1/weight)` for uniformly
+        // random `u` yields every ordering with the desired probability.
+        let mut keyed: Vec<(f64, NodeId)> = peers
+            .into_iter()
+            .zip(weights)
+            .map(|(peer, weight)| {
+                let weight = weight.unwrap_or(default_weight);
+                (rng.gen::<f64>().powf(1.0 / weight), peer)
+            })
+            .collect();
+        keyed.sort_by(|(key1, _), (key2, _)| key2.total_cmp(key1));
+        keyed.into_iter().map(|(_, peer)| peer).collect()
+    }
+
+    /// Chooses up to `count` of the given peers, preferring better performing ones.
+    ///
+    /// See [`PeerScores::order_peers`].
+    pub(crate) fn choose_peers(
+        &self,
+        tag: Tag,
+        peers: Vec<NodeId>,
+        count: usize,
+        rng: &mut NodeRng,
+    ) -> Vec<NodeId> {
+        let mut peers = self.order_peers(tag, peers, rng);
+        peers.truncate(count);
+        peers
+    }
+
+    /// Removes the statistics of all peers for which `is_connected` returns `false`.
+    ///
+    /// A peer reconnecting later starts out without statistics, like any new peer.
+    pub(crate) fn retain_peers<F: Fn(&NodeId) -> bool>(&self, is_connected: F) {
+        self.0
+            .write()
+            .expect("peer scores lock poisoned")
+            .retain(|(peer, _), _| is_connected(peer));
+    }
+
+    fn update<F: FnOnce(&mut PeerStats)>(&self, peer: NodeId, tag: Tag, f: F) {
+        let mut scores = self.0.write().expect("peer scores lock poisoned");
+        let stats = scores.entry((peer, tag)).or_insert_with(PeerStats::new);
+        f(stats);
+        trace!(%peer, %tag, %stats, "updated peer stats");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_prefer_fast_and_reliable_peers() {
+        let mut rng = crate::new_rng();
+        let fast = NodeId::random(&mut rng);
+        let slow = NodeId::random(&mut rng);
+        let failing = NodeId::random(&mut rng);
+
+        let scores = PeerScores::default();
+        for _ in 0..10 {
+            scores.record_success(fast, Tag::TrieOrChunk, Duration::from_millis(10), 1000);
+            scores.record_success(slow, Tag::TrieOrChunk, Duration::from_secs(1), 1000);
+            scores.record_timeout(failing, Tag::TrieOrChunk, Duration::from_millis(10));
+            scores.record_failure(failing, Tag::TrieOrChunk);
+        }
+
+        let mut first_counts = HashMap::new();
+        for _ in 0..1000 {
+            let first =
+                scores.order_peers(Tag::TrieOrChunk, vec![slow, failing, fast], &mut rng)[0];
+            *first_counts.entry(first).or_insert(0) += 1;
+        }
+        assert!(first_counts[&fast] > 900, "{:?}", first_counts);
+        // Other peers are still chosen occasionally.
+        assert!(first_counts.get(&slow).copied().unwrap_or_default() < 50);
+    }
+
+    #[test]
+    fn should_score_tags_separately() {
+        let mut rng = crate::new_rng();
+        let peer1 = NodeId::random(&mut rng);
+        let peer2 = NodeId::random(&mut rng);
+
+        let scores = PeerScores::default();
+        scores.record_success(peer1, Tag::Deploy, Duration::from_millis(1), 100);
+        scores.record_success(peer2, Tag::Deploy, Duration::from_secs(100), 100);
+        scores.record_success(peer1, Tag::SyncLeap, Duration::from_secs(100), 100);
+        scores.record_success(peer2, Tag::SyncLeap, Duration::from_millis(1), 100);
+
+        let chosen = scores.choose_peers(Tag::SyncLeap, vec![peer1, peer2], 1, &mut rng);
+        assert_eq!(chosen, vec![peer2]);
+        let chosen = scores.choose_peers(Tag::Deploy, vec![peer1, peer2], 1, &mut rng);
+        assert_eq!(chosen, vec![peer1]);
+    }
+
+    #[test]
+    fn should_evict_disconnected_peers() {
+        let mut rng = crate::new_rng();
+        let connected = NodeId::random(&mut rng);
+        let disconnected = NodeId::random(&mut rng);
+
+        let scores = PeerScores::default();
+        for peer in [connected, disconnected] {
+            scores.record_success(peer, Tag::Deploy, Duration::from_millis(1), 100);
+            scores.record_success(peer, Tag::Block, Duration::from_millis(1), 100);
+        }
+
+        scores.retain_peers(|peer| *peer == connected);
+        let remaining: Vec<_> = scores.0.read().unwrap().keys().copied().collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|(peer, _)| *peer == connected));
+    }
+
+    #[test]
+    fn should_return_all_peers_without_statistics() {
+        let mut rng = crate::new_rng();
+        let peers: Vec<NodeId> = (0..10).map(|_| NodeId::random(&mut rng)).collect();
+
+        let mut ordered = PeerScores::default().order_peers(Tag::Block, peers.clone(), &mut rng);
+        ordered.sort();
+        let mut expected = peers;
+        expected.sort();
+        assert_eq!(ordered, expected);
+    }
+}
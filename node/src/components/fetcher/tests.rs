@@ -295,8 +295,13 @@ impl ReactorTrait for Reactor {
         .unwrap();
 
         let fake_deploy_acceptor = FakeDeployAcceptor::new();
-        let deploy_fetcher =
-            Fetcher::<Deploy>::new("deploy", &cfg.fetcher_config, registry).unwrap();
+        let deploy_fetcher = Fetcher::<Deploy>::new(
+            "deploy",
+            &cfg.fetcher_config,
+            PeerScores::default(),
+            registry,
+        )
+        .unwrap();
         let reactor = Reactor {
             network,
             storage,
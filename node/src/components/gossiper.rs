@@ -1,4 +1,5 @@
 mod config;
+mod digest;
 #[cfg(test)]
 mod error;
 mod event;
@@ -11,9 +12,10 @@ mod provider_impls;
 mod tests;
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     fmt::{self, Debug, Formatter},
-    time::Duration,
+    mem,
+    time::{Duration, Instant},
 };
 
 use datasize::DataSize;
@@ -33,6 +35,7 @@ use crate::{
     NodeRng,
 };
 pub(crate) use config::Config;
+pub(crate) use digest::BloomDigest;
 pub(crate) use event::Event;
 pub(crate) use gossip_item::{GossipItem, LargeGossipItem, SmallGossipItem};
 use gossip_table::{GossipAction, GossipTable};
@@ -40,6 +43,9 @@ use item_provider::ItemProvider;
 pub(crate) use message::Message;
 use metrics::Metrics;
 
+/// Maximum number of items gossiped to a peer in response to a single digest.
+const MAX_ITEMS_PUSHED_PER_DIGEST: usize = 256;
+
 /// The component which gossips to peers and handles incoming gossip messages from peers.
 #[allow(clippy::type_complexity)]
 pub(crate) struct Gossiper<const ID_IS_COMPLETE_ITEM: bool, T>
@@ -50,6 +56,9 @@ where
     gossip_timeout: Duration,
     get_from_peer_timeout: Duration,
     validate_and_store_timeout: Duration,
+    reconciliation_interval: Option<Duration>,
+    /// When the last digest, or reply digest, was accepted from each peer.
+    last_digests: HashMap<(NodeId, bool), Instant>,
     name: &'static str,
     metrics: Metrics,
 }
@@ -69,6 +78,9 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
             gossip_timeout: config.gossip_request_timeout().into(),
             get_from_peer_timeout: config.get_remainder_timeout().into(),
             validate_and_store_timeout: config.validate_and_store_timeout().into(),
+            reconciliation_interval: (config.reconciliation_interval().millis() > 0)
+                .then(|| config.reconciliation_interval().into()),
+            last_digests: HashMap::new(),
             name,
             metrics: Metrics::new(name, registry)?,
         })
@@ -540,6 +552,138 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         Effects::new()
     }
 
+    /// Disables reconciliation, e.g. since peers running the current protocol version don't
+    /// necessarily understand digests.
+    ///
+    /// No digests are sent, and digests received are ignored.
+    pub(crate) fn disable_reconciliation(&mut self) {
+        self.reconciliation_interval = None;
+    }
+
+    /// Schedules the next reconciliation round, unless reconciliation is disabled.
+    ///
+    /// The reactor calls this once for every gossiper which should take part in reconciliation,
+    /// every further round is scheduled by the previous one.
+    pub(crate) fn schedule_reconciliation<REv: Send>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event<T>> {
+        match self.reconciliation_interval {
+            Some(interval) => effect_builder
+                .set_timeout(interval)
+                .event(|_| Event::Reconcile),
+            None => Effects::new(),
+        }
+    }
+
+    /// Sends a digest of the items we hold to a random peer, and schedules the next round.
+    fn reconcile<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + Send,
+    {
+        if let Some(min_interval) = self.min_digest_interval() {
+            let now = Instant::now();
+            self.last_digests
+                .retain(|_, accepted| now.saturating_duration_since(*accepted) < min_interval);
+        }
+
+        let message = Message::Digest {
+            digest: self.digest(rng),
+            is_reply: false,
+        };
+        self.metrics.digests_sent.inc();
+        let mut effects = effect_builder
+            .gossip_message(message, GossipTarget::All, 1, HashSet::new())
+            .ignore();
+        effects.extend(self.schedule_reconciliation(effect_builder));
+        effects
+    }
+
+    /// Returns the minimum interval between two digests, or two reply digests, accepted from the
+    /// same peer, or `None` if reconciliation is disabled.
+    ///
+    /// Peers send digests to a single random peer once per interval, so twice as many are
+    /// tolerated.
+    fn min_digest_interval(&self) -> Option<Duration> {
+        self.reconciliation_interval.map(|interval| interval / 2)
+    }
+
+    /// Handles a digest received from a peer, gossiping the items it is missing to it and
+    /// replying with our own digest unless it is a reply itself.
+    ///
+    /// Digests are ignored if reconciliation is disabled, or if the peer sent another one recently.
+    fn handle_digest<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+        digest: BloomDigest,
+        is_reply: bool,
+        sender: NodeId,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + Send,
+    {
+        let min_interval = match self.min_digest_interval() {
+            Some(min_interval) => min_interval,
+            None => {
+                debug!(%sender, "reconciliation disabled, ignoring gossip digest");
+                return Effects::new();
+            }
+        };
+        if !digest.is_valid() {
+            debug!(%sender, %digest, "received malformed gossip digest");
+            return Effects::new();
+        }
+        let now = Instant::now();
+        if let Some(accepted) = self.last_digests.get(&(sender, is_reply)) {
+            if now.saturating_duration_since(*accepted) < min_interval {
+                debug!(%sender, is_reply, "ignoring gossip digest received too soon after the last");
+                self.metrics.digests_rate_limited.inc();
+                return Effects::new();
+            }
+        }
+        self.last_digests.insert((sender, is_reply), now);
+
+        let missing: Vec<T::Id> = self
+            .table
+            .held_ids()
+            .filter(|item_id| !digest.contains(&serialize_id::<T>(item_id)))
+            .take(MAX_ITEMS_PUSHED_PER_DIGEST)
+            .cloned()
+            .collect();
+        trace!(%sender, count = missing.len(), "pushing items missing from gossip digest");
+        self.metrics
+            .items_pushed_after_digest
+            .inc_by(missing.len() as u64);
+
+        let mut effects = Effects::new();
+        for item_id in missing {
+            effects.extend(
+                effect_builder
+                    .send_message(sender, Message::Gossip(item_id))
+                    .ignore(),
+            );
+        }
+        if !is_reply {
+            let reply = Message::Digest {
+                digest: self.digest(rng),
+                is_reply: true,
+            };
+            effects.extend(effect_builder.send_message(sender, reply).ignore());
+        }
+        effects
+    }
+
+    /// Creates a digest of the items we hold.
+    fn digest(&mut self, rng: &mut NodeRng) -> BloomDigest {
+        let serialized_ids: Vec<_> = self.table.held_ids().map(serialize_id::<T>).collect();
+        BloomDigest::new(serialized_ids.iter().map(Vec::as_slice), rng)
+    }
+
     /// Updates the gossiper metrics from the state of the gossip table.
     fn update_gossip_table_metrics(&self) {
         self.metrics
@@ -551,6 +695,11 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
     }
 }
 
+/// Serializes an item ID for inclusion in a [`BloomDigest`].
+fn serialize_id<T: GossipItem>(item_id: &T::Id) -> Vec<u8> {
+    bincode::serialize(item_id).expect("serialize gossip item ID")
+}
+
 /// Impl for gossipers of large items, i.e. where `T::ID_IS_COMPLETE_ITEM` is false.
 impl<T, REv> Component<REv> for Gossiper<false, T>
 where
@@ -566,7 +715,7 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
         let effects = match event {
@@ -617,6 +766,9 @@ where
                 Message::Item(item) => {
                     self.handle_item_received_from_peer(effect_builder, item, sender)
                 }
+                Message::Digest { digest, is_reply } => {
+                    self.handle_digest(effect_builder, rng, digest, is_reply, sender)
+                }
             },
             Event::CheckItemReceivedTimeout { item_id } => {
                 self.check_item_received_timeout(effect_builder, item_id)
@@ -643,6 +795,7 @@ where
                 Some(item) => Self::got_from_storage(effect_builder, item, requester),
                 None => self.failed_to_get_from_storage(effect_builder, item_id),
             },
+            Event::Reconcile => self.reconcile(effect_builder, rng),
         };
         self.update_gossip_table_metrics();
         effects
@@ -668,7 +821,7 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
         let effects = match event {
@@ -719,6 +872,9 @@ where
                     debug!(%item_id, %sender, "unexpected get response for small item");
                     Effects::new()
                 }
+                Message::Digest { digest, is_reply } => {
+                    self.handle_digest(effect_builder, rng, digest, is_reply, sender)
+                }
             },
             Event::CheckItemReceivedTimeout { item_id } => {
                 error!(%item_id, "should not timeout item-received for small item");
@@ -739,6 +895,7 @@ where
                 );
                 Effects::new()
             }
+            Event::Reconcile => self.reconcile(effect_builder, rng),
         };
         self.update_gossip_table_metrics();
         effects
@@ -762,6 +919,8 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Debug
                 "validate_and_store_timeout",
                 &self.validate_and_store_timeout,
             )
+            .field("reconciliation_interval", &self.reconciliation_interval)
+            .field("last_digests", &self.last_digests)
             .finish()
     }
 }
@@ -780,6 +939,8 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> DataSize
             gossip_timeout,
             get_from_peer_timeout,
             validate_and_store_timeout,
+            reconciliation_interval,
+            last_digests,
             name,
             metrics: _,
         } = self;
@@ -788,6 +949,8 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> DataSize
             + gossip_timeout.estimate_heap_size()
             + get_from_peer_timeout.estimate_heap_size()
             + validate_and_store_timeout.estimate_heap_size()
+            + reconciliation_interval.estimate_heap_size()
+            + last_digests.len() * mem::size_of::<((NodeId, bool), Instant)>()
             + name.estimate_heap_size()
     }
 }
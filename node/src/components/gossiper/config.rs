@@ -19,6 +19,7 @@ pub(super) const DEFAULT_FINISHED_ENTRY_DURATION: &str = "60sec";
 const DEFAULT_GOSSIP_REQUEST_TIMEOUT: &str = "10sec";
 const DEFAULT_GET_REMAINDER_TIMEOUT: &str = "60sec";
 const DEFAULT_VALIDATE_AND_STORE_TIMEOUT: &str = "60sec";
+const DEFAULT_RECONCILIATION_INTERVAL: &str = "30sec";
 #[cfg(test)]
 const SMALL_TIMEOUTS_FINISHED_ENTRY_DURATION: &str = "2sec";
 #[cfg(test)]
@@ -56,6 +57,10 @@ pub struct Config {
     /// The timeout duration for a newly-received, gossiped item to be validated and stored by
     /// another component before the gossiper abandons waiting to gossip the item onwards.
     pub validate_and_store_timeout: TimeDiff,
+    /// The interval between rounds of exchanging digests of recently gossiped deploy and finality
+    /// signature IDs with a random peer, in order to push items the peer is missing.  A value of
+    /// `0` disables these rounds.
+    pub reconciliation_interval: TimeDiff,
 }
 
 impl Config {
@@ -78,6 +83,7 @@ impl Config {
             gossip_request_timeout,
             get_remainder_timeout,
             validate_and_store_timeout,
+            reconciliation_interval: TimeDiff::from_str(DEFAULT_RECONCILIATION_INTERVAL).unwrap(),
         })
     }
 
@@ -121,6 +127,10 @@ impl Config {
     pub(crate) fn validate_and_store_timeout(&self) -> TimeDiff {
         self.validate_and_store_timeout
     }
+
+    pub(crate) fn reconciliation_interval(&self) -> TimeDiff {
+        self.reconciliation_interval
+    }
 }
 
 impl Default for Config {
@@ -133,6 +143,7 @@ impl Default for Config {
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
             validate_and_store_timeout: TimeDiff::from_str(DEFAULT_VALIDATE_AND_STORE_TIMEOUT)
                 .unwrap(),
+            reconciliation_interval: TimeDiff::from_str(DEFAULT_RECONCILIATION_INTERVAL).unwrap(),
         }
     }
 }
@@ -172,6 +183,7 @@ mod tests {
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
             validate_and_store_timeout: TimeDiff::from_str(DEFAULT_VALIDATE_AND_STORE_TIMEOUT)
                 .unwrap(),
+            reconciliation_interval: TimeDiff::from_str(DEFAULT_RECONCILIATION_INTERVAL).unwrap(),
         };
 
         // Parsing should fail.
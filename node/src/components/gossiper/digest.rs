@@ -0,0 +1,155 @@
+//! Bloom filter digests of gossiped item IDs.
+//!
+//! Periodically, a gossiper sends a digest of the IDs of all items it recently held to a random
+//! peer.  The peer pushes the items missing from the digest back via regular gossip messages, and
+//! replies with a digest of its own so that the original sender can do the same.  False positives
+//! of the filter only mean an item is not pushed during that round; since every digest uses a fresh
+//! random seed, the same item is unlikely to be missed repeatedly.
+
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use casper_hashing::Digest;
+
+use crate::utils::specimen::{Cache, LargestSpecimen, SizeEstimator};
+
+/// Number of filter bits allotted to every item in a digest.
+const BITS_PER_ITEM: usize = 10;
+/// Number of hash functions used, optimal for `BITS_PER_ITEM`.
+const NUM_HASHES: u8 = 7;
+/// Upper bound on the number of hash functions accepted in a digest from a peer.
+const MAX_NUM_HASHES: u8 = 16;
+/// Minimum size of a digest's filter in 64-bit words.
+const MIN_WORDS: usize = 8;
+/// Maximum size of a digest's filter in 64-bit words, i.e. 32 KiB.
+const MAX_WORDS: usize = 4096;
+
+/// A bloom filter over the IDs of items held by a gossiper.
+#[derive(Clone, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct BloomDigest {
+    /// Random seed mixed into the hash of every ID.
+    seed: u64,
+    /// Number of bits set per ID.
+    num_hashes: u8,
+    /// The filter bits.  The number of words is always a power of two.
+    bits: Vec<u64>,
+}
+
+impl BloomDigest {
+    /// Creates a digest containing the given IDs.
+    pub(super) fn new<'a, I, R>(ids: I, rng: &mut R) -> Self
+    where
+        I: ExactSizeIterator<Item = &'a [u8]>,
+        R: Rng + ?Sized,
+    {
+        let num_words = (ids.len() * BITS_PER_ITEM / 64)
+            .next_power_of_two()
+            .clamp(MIN_WORDS, MAX_WORDS);
+        let mut digest = BloomDigest {
+            seed: rng.gen(),
+            num_hashes: NUM_HASHES,
+            bits: vec![0; num_words],
+        };
+        for id in ids {
+            for index in digest.bit_indices(id) {
+                digest.bits[index / 64] |= 1 << (index % 64);
+            }
+        }
+        digest
+    }
+
+    /// Returns whether the given ID is possibly contained in the digest.
+    ///
+    /// Returns `true` for all IDs contained in it, and occasionally for others.
+    pub(super) fn contains(&self, id: &[u8]) -> bool {
+        self.bit_indices(id)
+            .all(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns whether the digest is well-formed, and can thus be queried.
+    pub(super) fn is_valid(&self) -> bool {
+        (1..=MAX_NUM_HASHES).contains(&self.num_hashes)
+            && self.bits.len().is_power_of_two()
+            && self.bits.len() <= MAX_WORDS
+    }
+
+    /// Returns the indices of the bits representing the given ID.
+    ///
+    /// Uses double hashing, deriving all indices from two 64-bit hashes of the seeded ID.
+    fn bit_indices(&self, id: &[u8]) -> impl Iterator<Item = usize> {
+        let mut seeded = Vec::with_capacity(8 + id.len());
+        seeded.extend_from_slice(&self.seed.to_le_bytes());
+        seeded.extend_from_slice(id);
+        let hash = Digest::hash(seeded).value();
+        let first = u64::from_le_bytes(hash[..8].try_into().expect("should be 8 bytes"));
+        let second = u64::from_le_bytes(hash[8..16].try_into().expect("should be 8 bytes"));
+        let num_bits = self.bits.len() as u64 * 64;
+        (0..u64::from(self.num_hashes))
+            .map(move |i| (first.wrapping_add(i.wrapping_mul(second)) % num_bits) as usize)
+    }
+}
+
+impl Display for BloomDigest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "bloom digest of {} bits", self.bits.len() * 64)
+    }
+}
+
+impl LargestSpecimen for BloomDigest {
+    fn largest_specimen<E: SizeEstimator>(_estimator: &E, _cache: &mut Cache) -> Self {
+        BloomDigest {
+            seed: u64::MAX,
+            num_hashes: MAX_NUM_HASHES,
+            bits: vec![u64::MAX; MAX_WORDS],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_ids(rng: &mut impl Rng, count: usize) -> Vec<[u8; 32]> {
+        (0..count).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn should_contain_all_inserted_ids() {
+        let mut rng = crate::new_rng();
+        let ids = random_ids(&mut rng, 1000);
+        let digest = BloomDigest::new(ids.iter().map(|id| &id[..]), &mut rng);
+
+        assert!(digest.is_valid());
+        assert!(ids.iter().all(|id| digest.contains(id)));
+    }
+
+    #[test]
+    fn should_rarely_contain_other_ids() {
+        let mut rng = crate::new_rng();
+        let ids = random_ids(&mut rng, 1000);
+        let digest = BloomDigest::new(ids.iter().map(|id| &id[..]), &mut rng);
+
+        let false_positives = random_ids(&mut rng, 1000)
+            .iter()
+            .filter(|id| digest.contains(&id[..]))
+            .count();
+        assert!(false_positives < 50, "{} false positives", false_positives);
+    }
+
+    #[test]
+    fn should_reject_malformed_digests() {
+        let mut rng = crate::new_rng();
+        let mut digest = BloomDigest::new(std::iter::empty(), &mut rng);
+        assert!(digest.is_valid());
+
+        digest.bits.pop();
+        assert!(!digest.is_valid());
+
+        let mut digest = BloomDigest::new(std::iter::empty(), &mut rng);
+        digest.num_hashes = 0;
+        assert!(!digest.is_valid());
+    }
+}
@@ -56,6 +56,9 @@ pub(crate) enum Event<T: GossipItem> {
         requester: NodeId,
         maybe_item: Option<Box<T>>,
     },
+    /// The timer for the next reconciliation round has fired, and we should send a digest of the
+    /// items we hold to a random peer.
+    Reconcile,
 }
 
 impl<T: GossipItem> Display for Event<T> {
@@ -119,6 +122,7 @@ impl<T: GossipItem> Display for Event<T> {
                     write!(formatter, "failed to get {} from storage", item_id)
                 }
             }
+            Event::Reconcile => write!(formatter, "reconcile"),
         }
     }
 }
@@ -189,6 +189,8 @@ pub(super) struct GossipTable<T> {
     current: HashMap<T, State>,
     /// Data IDs for which gossiping is complete.
     finished: HashSet<T>,
+    /// The subset of `finished` which we never held, e.g. since the data was deemed invalid.
+    finished_not_held: HashSet<T>,
     /// Timeouts for removal of items from the `finished` cache.
     timeouts: Timeouts<T>,
     /// See `Config::infection_target`.
@@ -220,6 +222,7 @@ impl<T: Clone + Eq + Hash + Display> GossipTable<T> {
         GossipTable {
             current: HashMap::new(),
             finished: HashSet::new(),
+            finished_not_held: HashSet::new(),
             timeouts: Timeouts::new(),
             infection_target: usize::from(config.infection_target()),
             attempted_to_infect_limit,
@@ -461,8 +464,8 @@ impl<T: Clone + Eq + Hash + Display> GossipTable<T> {
     ///
     /// Returns `true` if there was a current entry for this data.
     pub(super) fn force_finish(&mut self, data_id: &T) -> bool {
-        if self.current.remove(data_id).is_some() {
-            self.insert_to_finished(data_id);
+        if let Some(state) = self.current.remove(data_id) {
+            self.insert_to_finished(data_id, state.held_by_us());
             return true;
         }
         false
@@ -502,7 +505,7 @@ impl<T: Clone + Eq + Hash + Display> GossipTable<T> {
         let mut state = self.current.remove(data_id)?;
         update(&mut state);
         if state.is_finished(self.infection_target, self.attempted_to_infect_limit) {
-            self.insert_to_finished(data_id);
+            self.insert_to_finished(data_id, state.held_by_us());
             return Some(GossipAction::AnnounceFinished);
         }
         let is_new = false;
@@ -515,9 +518,23 @@ impl<T: Clone + Eq + Hash + Display> GossipTable<T> {
         Some(action)
     }
 
-    fn insert_to_finished(&mut self, data_id: &T) {
+    /// Returns the IDs of all data we hold which is either still being gossiped or finished
+    /// recently.
+    pub(super) fn held_ids(&mut self) -> impl Iterator<Item = &T> {
+        self.purge_finished();
+        self.current
+            .iter()
+            .filter(|(_, state)| state.held_by_us())
+            .map(|(data_id, _)| data_id)
+            .chain(self.finished.difference(&self.finished_not_held))
+    }
+
+    fn insert_to_finished(&mut self, data_id: &T, held_by_us: bool) {
         let timeout = Instant::now() + self.finished_entry_duration;
         let _ = self.finished.insert(data_id.clone());
+        if !held_by_us {
+            let _ = self.finished_not_held.insert(data_id.clone());
+        }
         self.timeouts.push(timeout, data_id.clone());
     }
 
@@ -527,6 +544,7 @@ impl<T: Clone + Eq + Hash + Display> GossipTable<T> {
 
         for expired_finished in self.timeouts.purge(&now) {
             let _ = self.finished.remove(&expired_finished);
+            let _ = self.finished_not_held.remove(&expired_finished);
         }
     }
 
@@ -1059,6 +1077,38 @@ mod tests {
         assert!(!gossip_table.force_finish(&data_id));
     }
 
+    #[test]
+    fn should_only_list_held_data() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let node_ids = random_node_ids(&mut rng);
+        let held_current: u64 = rng.gen();
+        let held_finished: u64 = rng.gen();
+        let not_held_current: u64 = rng.gen();
+        let not_held_finished: u64 = rng.gen();
+
+        let mut gossip_table = GossipTable::new(Config::default());
+
+        let _ = gossip_table.new_complete_data(&held_current, None, GossipTarget::All);
+        let _ = gossip_table.new_complete_data(&held_finished, None, GossipTarget::All);
+        assert!(gossip_table.force_finish(&held_finished));
+        let _ = gossip_table.new_data_id(&not_held_current, node_ids[0]);
+        let _ = gossip_table.new_data_id(&not_held_finished, node_ids[0]);
+        assert!(gossip_table.finish_if_not_held_by_us(&not_held_finished));
+
+        let held: BTreeSet<_> = gossip_table.held_ids().copied().collect();
+        assert_eq!(held, [held_current, held_finished].into_iter().collect());
+
+        // Once timed out, finished data is no longer listed.
+        let millis = TimeDiff::from_str(DEFAULT_FINISHED_ENTRY_DURATION)
+            .unwrap()
+            .millis();
+        Instant::advance_time(millis + 1);
+        let held: BTreeSet<_> = gossip_table.held_ids().copied().collect();
+        assert_eq!(held, [held_current].into_iter().collect());
+        assert!(gossip_table.finished_not_held.is_empty());
+    }
+
     #[test]
     fn should_purge() {
         let _ = logging::init();
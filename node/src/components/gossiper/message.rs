@@ -6,7 +6,7 @@ use std::{
 use serde::{Deserialize, Serialize};
 use strum::EnumDiscriminants;
 
-use super::GossipItem;
+use super::{BloomDigest, GossipItem};
 
 #[derive(Clone, Debug, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(strum::EnumIter))]
@@ -26,6 +26,13 @@ pub(crate) enum Message<T: GossipItem> {
     // Response to either a `GossipResponse` with `is_already_held` set to `false` or to a
     // `GetItem` message. Contains the actual item requested.
     Item(Box<T>),
+    /// A digest of the IDs of the items the sender recently held.  The recipient should gossip the
+    /// items it holds which are missing from the digest to the sender and, unless `is_reply` is
+    /// set, reply with a digest of its own.
+    Digest {
+        digest: BloomDigest,
+        is_reply: bool,
+    },
 }
 
 impl<T: GossipItem> Display for Message<T> {
@@ -42,6 +49,9 @@ impl<T: GossipItem> Display for Message<T> {
             ),
             Message::GetItem(item_id) => write!(formatter, "gossip-get-item({})", item_id),
             Message::Item(item) => write!(formatter, "gossip-item({})", item.gossip_id()),
+            Message::Digest { digest, is_reply } => {
+                write!(formatter, "gossip-digest({}, {})", digest, is_reply)
+            }
         }
     }
 }
@@ -76,6 +86,10 @@ mod specimen_support {
                     MessageDiscriminants::Item => {
                         Message::Item(LargestSpecimen::largest_specimen(estimator, cache))
                     }
+                    MessageDiscriminants::Digest => Message::Digest {
+                        digest: LargestSpecimen::largest_specimen(estimator, cache),
+                        is_reply: LargestSpecimen::largest_specimen(estimator, cache),
+                    },
                 },
             )
         }
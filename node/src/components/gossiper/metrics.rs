@@ -15,6 +15,12 @@ pub(super) struct Metrics {
     pub(super) table_items_current: IntGauge,
     /// Number of items in the gossip table that are finished.
     pub(super) table_items_finished: IntGauge,
+    /// Total number of digests sent to peers.
+    pub(super) digests_sent: IntCounter,
+    /// Total number of items gossiped to peers since they were missing from their digests.
+    pub(super) items_pushed_after_digest: IntCounter,
+    /// Total number of digests ignored since the peer sent another one too recently.
+    pub(super) digests_rate_limited: IntCounter,
     /// Reference to the registry for unregistering.
     registry: Registry,
 }
@@ -52,11 +58,34 @@ impl Metrics {
             ),
         )?;
 
+        let digests_sent = IntCounter::new(
+            format!("{}_digests_sent", name),
+            format!("number of digests the {} sent to peers", name),
+        )?;
+        let items_pushed_after_digest = IntCounter::new(
+            format!("{}_items_pushed_after_digest", name),
+            format!(
+                "number of items the {} gossiped to peers since they were missing from their \
+                digests",
+                name
+            ),
+        )?;
+        let digests_rate_limited = IntCounter::new(
+            format!("{}_digests_rate_limited", name),
+            format!(
+                "number of digests the {} ignored since the peer sent another one too recently",
+                name
+            ),
+        )?;
+
         registry.register(Box::new(items_received.clone()))?;
         registry.register(Box::new(times_gossiped.clone()))?;
         registry.register(Box::new(times_ran_out_of_peers.clone()))?;
         registry.register(Box::new(table_items_current.clone()))?;
         registry.register(Box::new(table_items_finished.clone()))?;
+        registry.register(Box::new(digests_sent.clone()))?;
+        registry.register(Box::new(items_pushed_after_digest.clone()))?;
+        registry.register(Box::new(digests_rate_limited.clone()))?;
 
         Ok(Metrics {
             items_received,
@@ -64,6 +93,9 @@ impl Metrics {
             times_ran_out_of_peers,
             table_items_current,
             table_items_finished,
+            digests_sent,
+            items_pushed_after_digest,
+            digests_rate_limited,
             registry: registry.clone(),
         })
     }
@@ -76,5 +108,8 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.times_ran_out_of_peers);
         unregister_metric!(self.registry, self.table_items_current);
         unregister_metric!(self.registry, self.table_items_finished);
+        unregister_metric!(self.registry, self.digests_sent);
+        unregister_metric!(self.registry, self.items_pushed_after_digest);
+        unregister_metric!(self.registry, self.digests_rate_limited);
     }
 }
@@ -749,3 +749,182 @@ async fn should_ignore_unexpected_get_item_message() {
 async fn should_ignore_unexpected_item_message() {
     should_ignore_unexpected_message(Unexpected::Item).await
 }
+
+/// Stores and gossips a deploy on node 0 of a new network, returning the deploy and the network's
+/// node IDs once gossiping has settled.
+async fn setup_held_deploy(
+    network: &mut TestingNetwork<Reactor>,
+    rng: &mut TestRng,
+    network_size: usize,
+) -> (Arc<Deploy>, Vec<NodeId>) {
+    const QUIET_FOR: Duration = Duration::from_millis(50);
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    let node_ids = network.add_nodes(rng, network_size).await;
+    let deploy = Arc::new(Deploy::random_valid_native_transfer(rng));
+    network
+        .process_injected_effect_on(&node_ids[0], announce_deploy_received(Arc::clone(&deploy)))
+        .await;
+    network.settle(rng, QUIET_FOR, TIMEOUT).await;
+    (deploy, node_ids)
+}
+
+/// Has node 0 handle a digest sent by `sender`, returning the deploy gossiper's metrics afterwards
+/// as `(items pushed, digests rate limited)`.
+async fn handle_digest_on_node_0(
+    network: &mut TestingNetwork<Reactor>,
+    rng: &mut TestRng,
+    node_ids: &[NodeId],
+    sender: NodeId,
+    digest: BloomDigest,
+    is_reply: bool,
+) -> (u64, u64) {
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    let node_0 = node_ids[0];
+    network
+        .process_injected_effect_on(&node_0, |effect_builder| {
+            let event = Event::DeployGossiperIncoming(GossiperIncoming {
+                sender,
+                message: Box::new(Message::Digest { digest, is_reply }),
+            });
+            effect_builder
+                .into_inner()
+                .schedule(event, QueueKind::Gossip)
+                .ignore()
+        })
+        .await;
+    let received_digest =
+        |event: &Event| -> bool { matches!(event, Event::DeployGossiperIncoming(..)) };
+    network
+        .crank_until(&node_0, rng, received_digest, TIMEOUT)
+        .await;
+
+    let metrics = &network
+        .nodes()
+        .get(&node_0)
+        .unwrap()
+        .reactor()
+        .inner()
+        .deploy_gossiper
+        .metrics;
+    (
+        metrics.items_pushed_after_digest.get(),
+        metrics.digests_rate_limited.get(),
+    )
+}
+
+#[tokio::test]
+async fn should_push_items_missing_from_digest() {
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = TestingNetwork::<Reactor>::new();
+    let mut test_rng = crate::new_rng();
+    let rng = &mut test_rng;
+
+    let (deploy, node_ids) = setup_held_deploy(&mut network, rng, 3).await;
+
+    // A digest lacking the deploy has it pushed to the sender.
+    let empty_digest = BloomDigest::new(iter::empty(), rng);
+    let outcome = handle_digest_on_node_0(
+        &mut network,
+        rng,
+        &node_ids,
+        node_ids[1],
+        empty_digest,
+        false,
+    )
+    .await;
+    assert_eq!(outcome, (1, 0));
+
+    // A digest containing it doesn't.
+    let serialized_id = serialize_id::<Deploy>(&deploy.gossip_id());
+    let full_digest = BloomDigest::new(iter::once(serialized_id.as_slice()), rng);
+    let outcome = handle_digest_on_node_0(
+        &mut network,
+        rng,
+        &node_ids,
+        node_ids[2],
+        full_digest,
+        false,
+    )
+    .await;
+    assert_eq!(outcome, (1, 0));
+
+    NetworkController::<NodeMessage>::remove_active();
+}
+
+#[tokio::test]
+async fn should_rate_limit_digests_per_peer() {
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = TestingNetwork::<Reactor>::new();
+    let mut test_rng = crate::new_rng();
+    let rng = &mut test_rng;
+
+    let (_deploy, node_ids) = setup_held_deploy(&mut network, rng, 3).await;
+
+    let digest = BloomDigest::new(iter::empty(), rng);
+    let outcome = handle_digest_on_node_0(
+        &mut network,
+        rng,
+        &node_ids,
+        node_ids[1],
+        digest.clone(),
+        false,
+    )
+    .await;
+    assert_eq!(outcome, (1, 0));
+
+    // Another digest from the same peer right away is ignored.
+    let outcome = handle_digest_on_node_0(
+        &mut network,
+        rng,
+        &node_ids,
+        node_ids[1],
+        digest.clone(),
+        false,
+    )
+    .await;
+    assert_eq!(outcome, (1, 1));
+
+    // A reply to a digest of ours, or a digest from another peer, is not.
+    let outcome = handle_digest_on_node_0(
+        &mut network,
+        rng,
+        &node_ids,
+        node_ids[1],
+        digest.clone(),
+        true,
+    )
+    .await;
+    assert_eq!(outcome, (2, 1));
+    let outcome =
+        handle_digest_on_node_0(&mut network, rng, &node_ids, node_ids[2], digest, false).await;
+    assert_eq!(outcome, (3, 1));
+
+    NetworkController::<NodeMessage>::remove_active();
+}
+
+#[tokio::test]
+async fn should_ignore_digests_if_reconciliation_is_disabled() {
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = TestingNetwork::<Reactor>::new();
+    let mut test_rng = crate::new_rng();
+    let rng = &mut test_rng;
+
+    let (_deploy, node_ids) = setup_held_deploy(&mut network, rng, 2).await;
+    network
+        .nodes_mut()
+        .get_mut(&node_ids[0])
+        .unwrap()
+        .reactor_mut()
+        .inner_mut()
+        .deploy_gossiper
+        .disable_reconciliation();
+
+    let digest = BloomDigest::new(iter::empty(), rng);
+    let outcome =
+        handle_digest_on_node_0(&mut network, rng, &node_ids, node_ids[1], digest, false).await;
+    assert_eq!(outcome, (0, 0));
+
+    NetworkController::<NodeMessage>::remove_active();
+}
@@ -26,6 +26,7 @@
 mod bincode_format;
 pub(crate) mod blocklist;
 mod chain_info;
+mod compression;
 mod config;
 mod counting_format;
 mod error;
@@ -38,31 +39,40 @@ mod limiter;
 mod message;
 mod message_pack_format;
 mod metrics;
+mod multiplex;
 mod outgoing;
+mod peer_db;
+mod peer_groups;
+mod quic;
+#[cfg(test)]
+pub(crate) mod simulation;
 mod symmetry;
 pub(crate) mod tasks;
 #[cfg(test)]
 mod tests;
 
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     io,
     net::{SocketAddr, TcpListener},
+    path::Path,
+    pin::Pin,
     sync::{Arc, Weak},
+    task::{Context, Poll},
     time::{Duration, Instant},
 };
 
 use datasize::DataSize;
-use futures::{future::BoxFuture, FutureExt};
+use futures::{future::BoxFuture, FutureExt, StreamExt};
 use itertools::Itertools;
 use prometheus::Registry;
 use rand::{
     seq::{IteratorRandom, SliceRandom},
     Rng,
 };
-use serde::{Deserialize, Serialize};
 use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
     net::TcpStream,
     sync::{
         mpsc::{self, UnboundedSender},
@@ -71,14 +81,14 @@ use tokio::{
     task::JoinHandle,
 };
 use tokio_openssl::SslStream;
-use tokio_util::codec::LengthDelimitedCodec;
+use tokio_util::codec::{FramedParts, LengthDelimitedCodec};
 use tracing::{debug, error, info, trace, warn, Instrument, Span};
 
-use casper_types::{EraId, PublicKey, SecretKey};
+use casper_types::{EraId, PublicKey, Timestamp};
 
 pub(crate) use self::{
     bincode_format::BincodeFormat,
-    config::{Config, IdentityConfig},
+    config::{Config, IdentityConfig, SentryMode, TransportProtocol},
     error::Error,
     event::Event,
     gossiped_address::GossipedAddress,
@@ -86,20 +96,24 @@ pub(crate) use self::{
     insights::NetworkInsights,
     message::{
         generate_largest_serialized_message, EstimatorWeights, FromIncoming, Message, MessageKind,
-        Payload,
+        MessageQuota, MessageQuotas, MessageType, Payload,
     },
+    peer_groups::{PeerGroupChange, PeerGroups},
 };
 use self::{
     blocklist::BlocklistJustification,
     chain_info::ChainInfo,
+    compression::Compressor,
     counting_format::{ConnectionId, CountingFormat, Role},
     error::{ConnectionError, Result},
     event::{IncomingConnection, OutgoingConnection},
     health::{HealthConfig, TaggedTimestamp},
     limiter::Limiter,
-    message::NodeKeyPair,
+    message::{NodeKeyPair, SentryCertificate},
     metrics::Metrics,
+    multiplex::{Demultiplexer, Frame, FrameCodec, FrameSink, MessageSink, MessageStream},
     outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    peer_db::{PeerDb, PEER_DB_FILENAME},
     symmetry::ConnectionSymmetry,
     tasks::{MessageQueueItem, NetworkContext},
 };
@@ -111,6 +125,7 @@ use crate::{
         AutoClosingResponder, EffectBuilder, EffectExt, Effects, GossipTarget,
     },
     reactor::{Finalize, ReactorEvent},
+    signer::Signer,
     tls,
     types::{NodeId, ValidatorMatrix},
     utils::{self, display_error, Source},
@@ -134,6 +149,9 @@ const BASE_RECONNECTION_TIMEOUT: Duration = Duration::from_secs(1);
 /// Interval during which to perform outgoing manager housekeeping.
 const OUTGOING_MANAGER_SWEEP_INTERVAL: Duration = Duration::from_secs(1);
 
+/// Maximum number of peers from the peer database dialed on startup.
+const MAX_STARTUP_PEERS: usize = 32;
+
 /// How often to send a ping down a healthy connection.
 const PING_INTERVAL: Duration = Duration::from_secs(30);
 
@@ -180,6 +198,18 @@ where
     /// Tracks nodes that have announced themselves as nodes that are syncing.
     syncing_nodes: HashSet<NodeId>,
 
+    /// Persistent record of known peers and their reputation.
+    #[data_size(skip)]
+    peer_db: PeerDb,
+
+    /// Validators each peer relays consensus messages for, as proven by sentry certificates.
+    sentry_relays: HashMap<NodeId, BTreeSet<PublicKey>>,
+    /// Certificates of the validators hidden behind this node in sentry mode, by node ID.
+    #[data_size(skip)]
+    protected_validators: BTreeMap<NodeId, SentryCertificate>,
+    /// Consensus keys proven by peers in the handshakes of their incoming connections.
+    incoming_consensus_keys: HashMap<NodeId, PublicKey>,
+
     channel_management: Option<ChannelManagement>,
 
     /// Networking metrics.
@@ -238,14 +268,17 @@ where
         + From<BeginGossipRequest<GossipedAddress>>,
 {
     /// Creates a new network component instance.
+    ///
+    /// If a `storage_dir` is given, the peer database is loaded from and persisted to it.
     #[allow(clippy::type_complexity)]
     pub(crate) fn new<C: Into<ChainInfo>>(
         cfg: Config,
         our_identity: Identity,
-        node_key_pair: Option<(Arc<SecretKey>, PublicKey)>,
+        node_key_pair: Option<Signer>,
         registry: &Registry,
         chain_info_source: C,
         validator_matrix: ValidatorMatrix,
+        storage_dir: Option<&Path>,
     ) -> Result<Network<REv, P>> {
         let net_metrics = Arc::new(Metrics::new(registry)?);
 
@@ -277,20 +310,50 @@ where
             net_metrics.create_outgoing_metrics(),
         );
 
+        let allowed_nodes = cfg
+            .allowed_node_ids
+            .iter()
+            .map(|node_id| {
+                node_id
+                    .parse::<NodeId>()
+                    .map_err(|error| Error::InvalidAllowedNodeId(node_id.clone(), error))
+            })
+            .collect::<Result<_>>()?;
+        // A validator behind sentries must only ever talk to its sentries.
+        let behind_sentries = cfg.sentry_mode == SentryMode::Validator;
+        if behind_sentries && (cfg.static_peers.is_empty() || cfg.allowed_node_ids.is_empty()) {
+            return Err(Error::SentriesNotConfigured);
+        }
+        // Static peers are only resolved and added on initialization.
+        let peer_groups = PeerGroups::new(
+            BTreeSet::new(),
+            allowed_nodes,
+            cfg.allowlist_only || behind_sentries,
+        );
+
         let context = Arc::new(NetworkContext::new(
             cfg.clone(),
             our_identity,
             node_key_pair.map(NodeKeyPair::new),
             chain_info_source.into(),
             &net_metrics,
+            peer_groups,
         ));
 
+        let peer_db = storage_dir.map_or_else(PeerDb::in_memory, |storage_dir| {
+            PeerDb::load(storage_dir.join(PEER_DB_FILENAME))
+        });
+
         let component = Network {
             cfg,
             context,
             outgoing_manager,
             connection_symmetries: HashMap::new(),
             syncing_nodes: HashSet::new(),
+            peer_db,
+            sentry_relays: HashMap::new(),
+            protected_validators: BTreeMap::new(),
+            incoming_consensus_keys: HashMap::new(),
             channel_management: None,
             net_metrics,
             outgoing_limiter,
@@ -318,8 +381,26 @@ where
             }
         }
 
-        // Assert we have at least one known address in the config.
-        if known_addresses.is_empty() {
+        for address in &self.cfg.static_peers {
+            match utils::resolve_address(address) {
+                Ok(static_peer) => {
+                    self.context
+                        .update_peer_groups(&PeerGroupChange::AddStaticPeer(static_peer));
+                }
+                Err(ref err) => {
+                    warn!(%address, err=display_error(err), "failed to resolve static peer");
+                }
+            }
+        }
+        let static_peers: Vec<_> = self.context.peer_groups().static_peers().collect();
+
+        // Peers which proved reliable before a restart are dialed in addition to the known ones.
+        let preferred_addresses = self
+            .peer_db
+            .preferred_addrs(Timestamp::now(), MAX_STARTUP_PEERS);
+
+        // Assert we have at least one address to connect to.
+        if known_addresses.is_empty() && static_peers.is_empty() && preferred_addresses.is_empty() {
             warn!("no known addresses provided via config or all failed DNS resolution");
             return Err(Error::EmptyKnownHosts);
         }
@@ -344,15 +425,21 @@ where
             public_addr.set_port(local_addr.port());
         }
 
+        // QUIC connections are accepted on the UDP port matching the TCP one.
+        let quic_endpoint = match self.cfg.transport {
+            TransportProtocol::Tcp => None,
+            TransportProtocol::Quic => Some(quic::create_endpoint(local_addr)?),
+        };
+
         Arc::get_mut(&mut self.context)
             .expect("should be no other pointers")
-            .initialize(public_addr, effect_builder.into_inner());
+            .initialize(public_addr, quic_endpoint, effect_builder.into_inner());
 
         let protocol_version = self.context.chain_info().protocol_version;
         // Run the server task.
         // We spawn it ourselves instead of through an effect to get a hold of the join handle,
         // which we need to shutdown cleanly later on.
-        info!(%local_addr, %public_addr, %protocol_version, "starting server background task");
+        info!(%local_addr, %public_addr, %protocol_version, transport=?self.cfg.transport, "starting server background task");
 
         let (server_shutdown_sender, server_shutdown_receiver) = watch::channel(());
         let (close_incoming_sender, close_incoming_receiver) = watch::channel(());
@@ -376,21 +463,48 @@ where
 
         self.channel_management = Some(channel_management);
 
-        // Learn all known addresses and mark them as unforgettable.
+        // Restore the blocks still in effect from before a restart, so blocked peers are not
+        // dialed below.
         let now = Instant::now();
-        let dial_requests: Vec<_> = known_addresses
+        let unblock_after: Duration = self.cfg.blocklist_retain_duration.into();
+        let blocked_addresses: Vec<_> = self.peer_db.blocked_addrs(Timestamp::now()).collect();
+        for (addr, remaining, reason) in blocked_addresses {
+            if static_peers.contains(&addr) {
+                continue;
+            }
+            let elapsed = unblock_after.saturating_sub(remaining.into());
+            let since = now.checked_sub(elapsed).unwrap_or(now);
+            // Blocking an address we are not connected to yet never results in a dial request.
+            let _ = self.outgoing_manager.block_addr(
+                addr,
+                since,
+                BlocklistJustification::BlockedBeforeRestart { reason },
+            );
+        }
+
+        // Learn all static and known addresses and mark them as unforgettable, then the preferred
+        // addresses from the peer database.
+        let mut dial_requests: Vec<_> = static_peers
             .into_iter()
+            .chain(known_addresses)
             .filter_map(|addr| self.outgoing_manager.learn_addr(addr, true, now))
             .collect();
+        dial_requests.extend(
+            preferred_addresses
+                .into_iter()
+                .filter_map(|addr| self.outgoing_manager.learn_addr(addr, false, now)),
+        );
 
         let mut effects = self.process_dial_requests(dial_requests);
 
-        // Start broadcasting our public listening address.
-        effects.extend(
-            effect_builder
-                .set_timeout(self.cfg.initial_gossip_delay.into())
-                .event(|_| Event::GossipOurAddress),
-        );
+        // Start broadcasting our public listening address, unless we are hidden behind sentries.
+        if self.cfg.sentry_mode != SentryMode::Validator {
+            effects.extend(
+                effect_builder
+                    .set_timeout(self.cfg.initial_gossip_delay.into())
+                    .event(|_| Event::GossipOurAddress),
+            );
+        }
 
         // Start regular housekeeping of the outgoing connections.
         effects.extend(
@@ -417,9 +531,20 @@ where
         let mut total_connected_validators_in_era = 0;
         let mut total_outgoing_manager_connected_peers = 0;
 
+        // Behind sentries, all of our peers are sentries relaying our messages.
+        let behind_sentries = self.cfg.sentry_mode == SentryMode::Validator;
+
         for peer_id in self.outgoing_manager.connected_peers() {
             total_outgoing_manager_connected_peers += 1;
-            if self.outgoing_limiter.is_validator_in_era(era_id, &peer_id) {
+            if behind_sentries
+                || self.outgoing_limiter.is_validator_in_era(era_id, &peer_id)
+                || self.relays_for_validator(&peer_id, None, |public_key| {
+                    self.outgoing_limiter
+                        .validator_matrix()
+                        .is_validator_in_era(era_id, public_key)
+                        .unwrap_or(false)
+                })
+            {
                 total_connected_validators_in_era += 1;
                 self.send_message(peer_id, msg.clone(), None)
             }
@@ -434,6 +559,96 @@ where
         );
     }
 
+    /// Returns whether the peer relays consensus messages for a validator matching `is_validator`,
+    /// other than `excluded`.
+    fn relays_for_validator<F>(
+        &self,
+        peer_id: &NodeId,
+        excluded: Option<&PublicKey>,
+        is_validator: F,
+    ) -> bool
+    where
+        F: Fn(&PublicKey) -> bool,
+    {
+        self.sentry_relays
+            .get(peer_id)
+            .map_or(false, |public_keys| {
+                public_keys
+                    .iter()
+                    .any(|public_key| Some(public_key) != excluded && is_validator(public_key))
+            })
+    }
+
+    /// Relays a consensus message received by a sentry.
+    ///
+    /// Messages from the validators behind the sentry are sent on to the active and upcoming
+    /// validators, while all other messages are forwarded to the validators behind the sentry.
+    fn relay_consensus_message(&self, sender: NodeId, payload: &P) {
+        let msg = Arc::new(Message::Payload(payload.clone()));
+
+        if let Some(certificate) = self.protected_validators.get(&sender) {
+            let origin = certificate.public_key();
+            let validator_matrix = self.outgoing_limiter.validator_matrix();
+            for peer_id in self.outgoing_manager.connected_peers() {
+                if peer_id != sender
+                    && (self.protected_validators.contains_key(&peer_id)
+                        || self
+                            .outgoing_limiter
+                            .is_active_or_upcoming_validator(&peer_id)
+                        || self.relays_for_validator(&peer_id, Some(origin), |public_key| {
+                            validator_matrix.is_active_or_upcoming_validator(public_key)
+                        }))
+                {
+                    self.send_message(peer_id, msg.clone(), None);
+                }
+            }
+        } else {
+            for &validator_id in self.protected_validators.keys() {
+                self.send_message(validator_id, msg.clone(), None);
+            }
+        }
+    }
+
+    /// Handles a sentry certificate received from a peer.
+    ///
+    /// A certificate for our own node ID is sent by a validator hiding behind us and advertised to
+    /// all of our peers, any other certificate proves that the peer relays for the validator.
+    ///
+    /// Since we advertise the certificates of our validators, a certificate for our own node ID is
+    /// only accepted from the peer that proved holding the validator's key in its handshake.
+    fn handle_sentry_certificate(&mut self, peer_id: NodeId, certificate: SentryCertificate) {
+        if self.cfg.sentry_mode == SentryMode::Sentry
+            && certificate.validate(self.context.our_id()).is_ok()
+        {
+            if self.incoming_consensus_keys.get(&peer_id) != Some(certificate.public_key()) {
+                warn!(%certificate, "received sentry certificate for us from a peer not holding its key");
+                return;
+            }
+            info!(%certificate, "validator is hiding behind us");
+            let msg = Arc::new(Message::SentryCertificate(certificate.clone()));
+            self.protected_validators.insert(peer_id, certificate);
+            for other_id in self.outgoing_manager.connected_peers() {
+                if !self.protected_validators.contains_key(&other_id) {
+                    self.send_message(other_id, msg.clone(), None);
+                }
+            }
+            return;
+        }
+
+        match certificate.validate(peer_id) {
+            Ok(public_key) => {
+                debug!(%public_key, "peer relays for validator");
+                self.sentry_relays
+                    .entry(peer_id)
+                    .or_default()
+                    .insert(public_key.clone());
+            }
+            Err(ref err) => {
+                warn!(%certificate, err=display_error(err), "received invalid sentry certificate");
+            }
+        }
+    }
+
     /// Queues a message to `count` random nodes on the network.
     fn gossip_message(
         &self,
@@ -573,6 +788,11 @@ where
 
                 info!(%public_addr, "new incoming connection established");
 
+                if let Some(ref public_key) = peer_consensus_public_key {
+                    self.incoming_consensus_keys
+                        .insert(peer_id, public_key.clone());
+                }
+
                 // Learn the address the peer gave us.
                 let dial_requests =
                     self.outgoing_manager
@@ -604,7 +824,7 @@ where
                 effects.extend(
                     tasks::message_reader(
                         self.context.clone(),
-                        stream,
+                        *stream,
                         self.incoming_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.channel_management().close_incoming_receiver.clone(),
@@ -644,10 +864,17 @@ where
             }
 
             // Update the connection symmetries.
-            self.connection_symmetries
-                .entry(peer_id)
-                .or_default()
-                .remove_incoming(peer_addr, Instant::now());
+            let symmetry = self.connection_symmetries.entry(peer_id).or_default();
+            symmetry.remove_incoming(peer_addr, Instant::now());
+
+            // Sentry certificates are received on incoming connections only.
+            if symmetry.incoming_addrs().is_none() {
+                self.sentry_relays.remove(&peer_id);
+                self.incoming_consensus_keys.remove(&peer_id);
+                if self.protected_validators.remove(&peer_id).is_some() {
+                    info!("validator hiding behind us disconnected");
+                }
+            }
 
             Effects::new()
         })
@@ -667,22 +894,33 @@ where
             | ConnectionError::TcpConnection(_)
             | ConnectionError::TcpNoDelay(_)
             | ConnectionError::TlsHandshake(_)
+            | ConnectionError::QuicConnect(_)
+            | ConnectionError::QuicConnection(_)
+            | ConnectionError::QuicTimeout
+            | ConnectionError::QuicIdentityExchange(_)
             | ConnectionError::HandshakeSend(_)
             | ConnectionError::HandshakeRecv(_)
             | ConnectionError::IncompatibleVersion(_) => None,
 
             // These errors are potential bugs on our side.
             ConnectionError::HandshakeSenderCrashed(_)
+            | ConnectionError::QuicKeyingMaterial
+            | ConnectionError::QuicIdentity(_)
             | ConnectionError::FailedToReuniteHandshakeSinkAndStream
             | ConnectionError::CouldNotEncodeOurHandshake(_) => None,
 
             // These could be candidates for blocking, but for now we decided not to.
             ConnectionError::NoPeerCertificate
+            | ConnectionError::QuicPeerCertificate(_)
+            | ConnectionError::QuicIdentityInvalid
             | ConnectionError::PeerCertificateInvalid(_)
             | ConnectionError::DidNotSendHandshake
             | ConnectionError::InvalidRemoteHandshakeMessage(_)
             | ConnectionError::InvalidConsensusCertificate(_) => None,
 
+            // Disallowed peers are rejected on every attempt anyway.
+            ConnectionError::PeerNotAllowed => None,
+
             // Definitely something we want to avoid.
             ConnectionError::WrongNetwork(peer_network_name) => {
                 Some(BlocklistJustification::WrongNetwork {
@@ -718,15 +956,13 @@ where
                 error,
             } => {
                 debug!(err=%display_error(&error), "outgoing connection failed");
+                self.peer_db.record_failure(peer_addr);
+
                 // We perform blocking first, to not trigger a reconnection before blocking.
                 let mut requests = Vec::new();
 
                 if let Some(justification) = self.is_blockable_offense_for_outgoing(&error) {
-                    requests.extend(self.outgoing_manager.block_addr(
-                        peer_addr,
-                        now,
-                        justification,
-                    ));
+                    requests.extend(self.block_addr(peer_addr, now, justification));
                 }
 
                 // Now we can proceed with the regular updates.
@@ -757,8 +993,16 @@ where
                 is_syncing,
             } => {
                 info!("new outgoing connection established");
+                self.peer_db.record_session(peer_addr, Timestamp::now());
 
                 let (sender, receiver) = mpsc::unbounded_channel();
+                #[cfg(test)]
+                let sender = simulation::simulated_link(
+                    self.context.our_id(),
+                    peer_id,
+                    sender,
+                    self.net_metrics.queued_messages.clone(),
+                );
                 let handle = OutgoingHandle { sender, peer_addr };
 
                 let request = self
@@ -783,10 +1027,12 @@ where
                     self.update_syncing_nodes_set(peer_id, is_syncing);
                 }
 
+                self.send_sentry_certificates(peer_id);
+
                 effects.extend(
                     tasks::message_sender(
                         receiver,
-                        sink,
+                        *sink,
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.net_metrics.queued_messages.clone(),
@@ -917,6 +1163,40 @@ where
         effects
     }
 
+    /// Sends the sentry certificates relevant to a newly connected peer.
+    ///
+    /// Behind sentries, the peer is given a certificate to present to its own peers. A sentry
+    /// advertises the certificates of all validators hiding behind it.
+    fn send_sentry_certificates(&self, peer_id: NodeId) {
+        match self.cfg.sentry_mode {
+            SentryMode::Disabled => {}
+            SentryMode::Validator => {
+                if let Some(certificate) = self
+                    .context
+                    .node_key_pair()
+                    .and_then(|key_pair| SentryCertificate::create(peer_id, key_pair))
+                {
+                    self.send_message(
+                        peer_id,
+                        Arc::new(Message::SentryCertificate(certificate)),
+                        None,
+                    );
+                }
+            }
+            SentryMode::Sentry => {
+                if !self.protected_validators.contains_key(&peer_id) {
+                    for certificate in self.protected_validators.values() {
+                        self.send_message(
+                            peer_id,
+                            Arc::new(Message::SentryCertificate(certificate.clone())),
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     /// Handles a received message.
     fn handle_incoming_message(
         &mut self,
@@ -945,7 +1225,14 @@ where
             Message::Pong { nonce } => {
                 // Record the time the pong arrived and forward it to outgoing.
                 let pong = TaggedTimestamp::from_parts(Instant::now(), nonce);
-                if self.outgoing_manager.record_pong(peer_id, pong) {
+                let exceeded_pong_limit = self.outgoing_manager.record_pong(peer_id, pong);
+                if let (Some(addr), Some(rtt)) = (
+                    self.outgoing_manager.get_addr(peer_id),
+                    self.outgoing_manager.round_trip_time(peer_id),
+                ) {
+                    self.peer_db.record_round_trip_time(addr, rtt);
+                }
+                if exceeded_pong_limit {
                     // Note: We no longer block peers here with a `PongLimitExceeded` for failed
                     //       pongs, merely warn.
                     info!(
@@ -957,11 +1244,62 @@ where
                 Effects::new()
             }
             Message::Payload(payload) => {
+                if self.cfg.sentry_mode == SentryMode::Sentry
+                    && matches!(payload.message_kind(), MessageKind::Consensus)
+                {
+                    self.relay_consensus_message(peer_id, &payload);
+                }
                 effect_builder.announce_incoming(peer_id, payload).ignore()
             }
+            Message::SentryCertificate(certificate) => {
+                self.handle_sentry_certificate(peer_id, certificate);
+                Effects::new()
+            }
         })
     }
 
+    /// Blocks an address, recording the offense in the peer database.
+    ///
+    /// Static peers are never blocked.
+    fn block_addr(
+        &mut self,
+        addr: SocketAddr,
+        now: Instant,
+        justification: BlocklistJustification,
+    ) -> Option<DialRequest<OutgoingHandle<P>>> {
+        if self.context.peer_groups().is_static(&addr) {
+            info!(%addr, %justification, "not blocking static peer");
+            return None;
+        }
+        let timestamp = Timestamp::now();
+        let blocked_until = timestamp + self.cfg.blocklist_retain_duration;
+        self.peer_db
+            .record_offense(addr, &justification, timestamp, blocked_until);
+        self.outgoing_manager.block_addr(addr, now, justification)
+    }
+
+    /// Applies runtime changes to the peer groups.
+    ///
+    /// Newly added static peers are connected to immediately, and redeemed if currently blocked.
+    fn update_peer_groups(&mut self, changes: Vec<PeerGroupChange>) -> Effects<Event<P>> {
+        let now = Instant::now();
+        let mut requests = Vec::new();
+        for change in changes {
+            if !self.context.update_peer_groups(&change) {
+                debug!(%change, "peer groups unchanged");
+                continue;
+            }
+            info!(%change, "updated peer groups");
+
+            if let PeerGroupChange::AddStaticPeer(addr) = change {
+                self.outgoing_manager.mark_unforgettable(addr);
+                requests.extend(self.outgoing_manager.redeem_addr(addr, now));
+                requests.extend(self.outgoing_manager.learn_addr(addr, true, now));
+            }
+        }
+        self.process_dial_requests(requests)
+    }
+
     /// Emits an announcement that a connection has been completed.
     fn connection_completed(&self, peer_id: NodeId) {
         trace!(num_peers = self.peers().len(), new_peer=%peer_id, "connection complete");
@@ -1016,6 +1354,14 @@ where
             .choose_multiple(rng, count)
     }
 
+    pub(crate) fn fully_connected_peers(&self) -> Vec<NodeId> {
+        self.connection_symmetries
+            .iter()
+            .filter(|(_, sym)| matches!(sym, ConnectionSymmetry::Symmetric { .. }))
+            .map(|(node_id, _)| *node_id)
+            .collect()
+    }
+
     pub(crate) fn has_sufficient_fully_connected_peers(&self) -> bool {
         self.connection_symmetries
             .iter()
@@ -1058,6 +1404,10 @@ where
                 }
             }
 
+            if let Some(write) = self.peer_db.persist() {
+                write.run_blocking().await;
+            }
+
             // Ensure there are no ongoing metrics updates.
             utils::wait_for_arc_drop(
                 self.net_metrics,
@@ -1207,7 +1557,10 @@ where
                 }
                 Event::NetworkInfoRequest { req } => match *req {
                     NetworkInfoRequest::Peers { responder } => {
-                        responder.respond(self.peers()).ignore()
+                        let mut peers = self.peers();
+                        // Validators hiding behind us are not disclosed.
+                        peers.retain(|node_id, _| !self.protected_validators.contains_key(node_id));
+                        responder.respond(peers).ignore()
                     }
                     NetworkInfoRequest::FullyConnectedPeers { count, responder } => responder
                         .respond(self.fully_connected_peers_random(rng, count))
@@ -1215,6 +1568,12 @@ where
                     NetworkInfoRequest::Insight { responder } => responder
                         .respond(NetworkInsights::collect_from_component(self))
                         .ignore(),
+                    NetworkInfoRequest::PeerGroups { changes, responder } => {
+                        let mut effects = self.update_peer_groups(changes);
+                        let peer_groups = self.context.peer_groups().clone();
+                        effects.extend(responder.respond(peer_groups).ignore());
+                        effects
+                    }
                 },
                 Event::GossipOurAddress => {
                     let our_address = GossipedAddress::new(
@@ -1234,12 +1593,18 @@ where
                     effects
                 }
                 Event::PeerAddressReceived(gossiped_address) => {
-                    let requests = self.outgoing_manager.learn_addr(
-                        gossiped_address.into(),
-                        false,
-                        Instant::now(),
-                    );
-                    self.process_dial_requests(requests)
+                    if self.context.peer_groups().allowlist_only() {
+                        // Dialing arbitrary nodes would only reveal us to them.
+                        trace!(%gossiped_address, "ignoring gossiped address in allowlist-only mode");
+                        Effects::new()
+                    } else {
+                        let requests = self.outgoing_manager.learn_addr(
+                            gossiped_address.into(),
+                            false,
+                            Instant::now(),
+                        );
+                        self.process_dial_requests(requests)
+                    }
                 }
                 Event::SweepOutgoing => {
                     let now = Instant::now();
@@ -1247,6 +1612,10 @@ where
 
                     let mut effects = self.process_dial_requests(requests);
 
+                    if let Some(write) = self.peer_db.persist_if_due(now) {
+                        effects.extend(write.run_blocking().ignore());
+                    }
+
                     effects.extend(
                         effect_builder
                             .set_timeout(OUTGOING_MANAGER_SWEEP_INTERVAL)
@@ -1265,11 +1634,7 @@ where
                         info!(%offender, %justification, "adding peer to blocklist after transgression");
 
                         if let Some(addr) = self.outgoing_manager.get_addr(*offender) {
-                            let requests = self.outgoing_manager.block_addr(
-                                addr,
-                                Instant::now(),
-                                *justification,
-                            );
+                            let requests = self.block_addr(addr, Instant::now(), *justification);
                             self.process_dial_requests(requests)
                         } else {
                             // Peer got away with it, no longer an outgoing connection.
@@ -1312,35 +1677,155 @@ where
     }
 }
 
-/// Transport type alias for base encrypted connections.
-type Transport = SslStream<TcpStream>;
+/// Base encrypted connection to a peer.
+#[derive(Debug)]
+pub(crate) enum Transport {
+    /// TLS over TCP.
+    Tls(SslStream<TcpStream>),
+    /// A stream of a QUIC connection.
+    Quic(quic::QuicStream),
+}
+
+impl Transport {
+    /// Returns the name of the underlying protocol, for logging.
+    fn protocol(&self) -> &'static str {
+        match self {
+            Transport::Tls(_) => "tls",
+            Transport::Quic(_) => "quic",
+        }
+    }
+
+    /// Derives the ID of the connection from secrets shared by both ends.
+    fn connection_id(
+        &self,
+        our_id: NodeId,
+        their_id: NodeId,
+    ) -> std::result::Result<ConnectionId, ConnectionError> {
+        match self {
+            Transport::Tls(stream) => Ok(ConnectionId::from_connection(
+                stream.ssl(),
+                our_id,
+                their_id,
+            )),
+            Transport::Quic(stream) => Ok(ConnectionId::from_keying_material(
+                stream.keying_material()?,
+                our_id,
+                their_id,
+            )),
+        }
+    }
+}
+
+impl AsyncRead for Transport {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Transport::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+            Transport::Quic(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Transport {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Transport::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+            Transport::Quic(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
 
-/// A framed transport for `Message`s.
-pub(crate) type FullTransport<P> = tokio_serde::Framed<
-    FramedTransport,
-    Message<P>,
-    Arc<Message<P>>,
-    CountingFormat<BincodeFormat>,
->;
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Transport::Tls(stream) => Pin::new(stream).poll_flush(cx),
+            Transport::Quic(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Transport::Tls(stream) => Pin::new(stream).poll_shutdown(cx),
+            Transport::Quic(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
 
 pub(crate) type FramedTransport = tokio_util::codec::Framed<Transport, LengthDelimitedCodec>;
 
-/// Constructs a new full transport on a stream.
+/// Constructs a multiplexed transport on a framed transport that completed the handshake.
 ///
-/// A full transport contains the framing as well as the encoding scheme used to send messages.
-fn full_transport<P>(
+/// Any data already buffered by the framed transport is carried over. The returned sink and
+/// stream send and receive `Message`s, split into fragments interleaved by channel priority if the
+/// peer advertised support for `multiplexed` frames.
+fn multiplexed_transport<P>(
     metrics: Weak<Metrics>,
     connection_id: ConnectionId,
     framed: FramedTransport,
     role: Role,
-) -> FullTransport<P>
+    maximum_net_message_size: u32,
+    compressor: Compressor,
+    multiplexed: bool,
+) -> (MessageSink<P>, MessageStream<P>)
 where
-    for<'de> P: Serialize + Deserialize<'de>,
-    for<'de> Message<P>: Serialize + Deserialize<'de>,
+    P: Payload,
 {
-    tokio_serde::Framed::new(
-        framed,
-        CountingFormat::new(metrics, connection_id, role, BincodeFormat::default()),
+    let parts = framed.into_parts();
+    let codec = FrameCodec::new(parts.codec, multiplexed);
+    // Only peers supporting multiplexing send each channel on a QUIC stream of its own.
+    let quic_connection = match parts.io {
+        Transport::Quic(ref stream) if multiplexed => Some(stream.connection()),
+        _ => None,
+    };
+    let mut multiplexed_parts = FramedParts::new::<Frame>(parts.io, codec.clone());
+    multiplexed_parts.read_buf = parts.read_buf;
+    multiplexed_parts.write_buf = parts.write_buf;
+    let (sink, stream) = tokio_util::codec::Framed::from_parts(multiplexed_parts).split();
+
+    let sink = match quic_connection {
+        Some(ref connection) => FrameSink::QuicStreams {
+            _transport: sink,
+            connection: connection.clone(),
+            codec: codec.clone(),
+            streams: Default::default(),
+        },
+        None => FrameSink::Transport(sink),
+    };
+    let stream = multiplex::incoming_frames(
+        stream,
+        quic_connection.map(|connection| (connection, codec)),
+    );
+
+    (
+        MessageSink::new(
+            sink,
+            CountingFormat::new(
+                metrics.clone(),
+                connection_id,
+                role,
+                BincodeFormat::default(),
+            ),
+            compressor,
+            multiplexed,
+            metrics.clone(),
+        ),
+        MessageStream::new(
+            stream,
+            Demultiplexer::new(maximum_net_message_size),
+            CountingFormat::new(
+                metrics.clone(),
+                connection_id,
+                role,
+                BincodeFormat::default(),
+            ),
+            multiplexed,
+            metrics,
+        ),
     )
 }
 
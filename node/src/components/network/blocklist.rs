@@ -9,6 +9,7 @@ use casper_types::EraId;
 use datasize::DataSize;
 use serde::Serialize;
 
+use super::MessageType;
 use crate::components::{block_accumulator, fetcher::Tag};
 
 /// Reasons why a peer was blocked.
@@ -58,6 +59,18 @@ pub(crate) enum BlocklistJustification {
     DishonestPeer,
     /// Peer sent too many finality signatures.
     SentTooManyFinalitySignatures { max_allowed: u32 },
+    /// Peer sent messages of a single type at a rate above the configured quota.
+    ExceededMessageQuota {
+        /// The type of messages sent.
+        message_type: MessageType,
+        /// The quota exceeded, in messages per second.
+        per_second: u32,
+    },
+    /// Peer was still blocked when the node restarted.
+    BlockedBeforeRestart {
+        /// The reason the peer was originally blocked for.
+        reason: String,
+    },
 }
 
 impl Display for BlocklistJustification {
@@ -106,6 +119,17 @@ impl Display for BlocklistJustification {
                 f,
                 "sent too many finality signatures: maximum {max_allowed} signatures are allowed"
             ),
+            BlocklistJustification::ExceededMessageQuota {
+                message_type,
+                per_second,
+            } => write!(
+                f,
+                "exceeded the quota of {} {} messages per second",
+                per_second, message_type
+            ),
+            BlocklistJustification::BlockedBeforeRestart { reason } => {
+                write!(f, "was blocked before the node restarted ({})", reason)
+            }
         }
     }
 }
@@ -10,6 +10,7 @@ use casper_types::ProtocolVersion;
 use datasize::DataSize;
 
 use super::{
+    compression::Compression,
     counting_format::ConnectionId,
     message::{ConsensusCertificate, NodeKeyPair},
     Message,
@@ -58,9 +59,11 @@ impl ChainInfo {
             public_addr,
             protocol_version: self.protocol_version,
             consensus_certificate: consensus_keys
-                .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+                .and_then(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            compression: Some(Compression::Deflate),
+            multiplexing: true,
         }
     }
 }
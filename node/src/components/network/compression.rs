@@ -0,0 +1,188 @@
+//! Compression of large network messages.
+//!
+//! Every node advertises the compression algorithm it is able to decompress in its handshake.
+//! Serialized messages at least as large as the configured threshold are compressed before being
+//! sent to peers supporting it, unless compression does not reduce their size. Compressed messages
+//! are marked as such in the header of each of their frames, see the
+//! [`multiplex`](super::multiplex) module.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, Read, Write},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use datasize::DataSize;
+use flate2::{read::DeflateDecoder, write::DeflateEncoder};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::specimen::{Cache, LargestSpecimen, SizeEstimator};
+
+/// A compression algorithm for network messages.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) enum Compression {
+    /// DEFLATE, as specified in RFC 1951.
+    Deflate,
+}
+
+impl Compression {
+    /// Compresses the given data.
+    fn compress(self, data: &[u8]) -> io::Result<Bytes> {
+        match self {
+            Compression::Deflate => {
+                let mut encoder = DeflateEncoder::new(
+                    BytesMut::with_capacity(data.len() / 2).writer(),
+                    flate2::Compression::fast(),
+                );
+                encoder.write_all(data)?;
+                Ok(encoder.finish()?.into_inner().freeze())
+            }
+        }
+    }
+
+    /// Decompresses the given data, failing if it decompresses to more than `max_size` bytes.
+    pub(super) fn decompress(self, data: &[u8], max_size: usize) -> io::Result<BytesMut> {
+        let mut decompressed = BytesMut::new().writer();
+        let copied = match self {
+            Compression::Deflate => io::copy(
+                &mut DeflateDecoder::new(data).take(max_size as u64 + 1),
+                &mut decompressed,
+            )?,
+        };
+        if copied > max_size as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "decompressed message exceeds maximum size of {} bytes",
+                    max_size
+                ),
+            ));
+        }
+        Ok(decompressed.into_inner())
+    }
+}
+
+impl Display for Compression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::Deflate => f.write_str("deflate"),
+        }
+    }
+}
+
+impl LargestSpecimen for Compression {
+    fn largest_specimen<E: SizeEstimator>(_estimator: &E, _cache: &mut Cache) -> Self {
+        Compression::Deflate
+    }
+}
+
+/// Compresses outgoing messages to a single peer.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Compressor {
+    /// The algorithm to compress with, `None` if the peer does not support compression or it is
+    /// disabled.
+    compression: Option<Compression>,
+    /// Minimum size of a serialized message to be compressed.
+    threshold: usize,
+}
+
+impl Compressor {
+    /// Creates a new compressor for a peer which advertised `peer_compression`.
+    ///
+    /// A `threshold` of `0` disables compression.
+    pub(super) fn new(peer_compression: Option<Compression>, threshold: u32) -> Self {
+        Compressor {
+            compression: peer_compression.filter(|_| threshold > 0),
+            threshold: threshold as usize,
+        }
+    }
+
+    /// Compresses a serialized message if it is large enough and compression reduces its size.
+    ///
+    /// Returns the data to send, along with whether it was compressed.
+    pub(super) fn compress(&self, serialized: Bytes) -> io::Result<(Bytes, bool)> {
+        match self.compression {
+            Some(compression) if serialized.len() >= self.threshold => {
+                let compressed = compression.compress(&serialized)?;
+                if compressed.len() < serialized.len() {
+                    Ok((compressed, true))
+                } else {
+                    Ok((serialized, false))
+                }
+            }
+            _ => Ok((serialized, false)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: u32 = 1024;
+
+    #[test]
+    fn large_messages_roundtrip() {
+        let compressor = Compressor::new(Some(Compression::Deflate), THRESHOLD);
+        let message = Bytes::from(b"casper".repeat(1000));
+
+        let (compressed, is_compressed) = compressor.compress(message.clone()).unwrap();
+        assert!(is_compressed);
+        assert!(compressed.len() < message.len());
+
+        let decompressed = Compression::Deflate
+            .decompress(&compressed, message.len())
+            .unwrap();
+        assert_eq!(decompressed, message);
+    }
+
+    #[test]
+    fn should_not_compress_small_or_incompressible_messages() {
+        let compressor = Compressor::new(Some(Compression::Deflate), THRESHOLD);
+
+        let small = Bytes::from(vec![0; THRESHOLD as usize - 1]);
+        assert_eq!(compressor.compress(small.clone()).unwrap(), (small, false));
+
+        let mut rng = crate::new_rng();
+        let random: Vec<u8> = (0..THRESHOLD * 4)
+            .map(|_| rand::Rng::gen(&mut rng))
+            .collect();
+        let random = Bytes::from(random);
+        assert_eq!(
+            compressor.compress(random.clone()).unwrap(),
+            (random, false)
+        );
+    }
+
+    #[test]
+    fn should_not_compress_if_unsupported_or_disabled() {
+        let message = Bytes::from(vec![0; THRESHOLD as usize * 4]);
+
+        let unsupported = Compressor::new(None, THRESHOLD);
+        assert_eq!(
+            unsupported.compress(message.clone()).unwrap(),
+            (message.clone(), false)
+        );
+
+        let disabled = Compressor::new(Some(Compression::Deflate), 0);
+        assert_eq!(
+            disabled.compress(message.clone()).unwrap(),
+            (message, false)
+        );
+    }
+
+    #[test]
+    fn should_reject_oversized_decompressed_messages() {
+        let compressor = Compressor::new(Some(Compression::Deflate), THRESHOLD);
+        let message = Bytes::from(vec![0; THRESHOLD as usize * 4]);
+        let (compressed, is_compressed) = compressor.compress(message.clone()).unwrap();
+        assert!(is_compressed);
+
+        assert!(Compression::Deflate
+            .decompress(&compressed, message.len() - 1)
+            .is_err());
+        assert!(Compression::Deflate
+            .decompress(&compressed, message.len())
+            .is_ok());
+    }
+}
@@ -6,7 +6,7 @@ use casper_types::{ProtocolVersion, TimeDiff};
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use super::EstimatorWeights;
+use super::{EstimatorWeights, MessageQuotas};
 
 /// Default binding address.
 ///
@@ -32,12 +32,24 @@ const DEFAULT_MAX_ADDR_PENDING_TIME: TimeDiff = TimeDiff::from_seconds(60);
 /// Default timeout during which the handshake needs to be completed.
 const DEFAULT_HANDSHAKE_TIMEOUT: TimeDiff = TimeDiff::from_seconds(20);
 
+/// Default minimum size of a serialized message to be compressed.
+const DEFAULT_COMPRESSION_THRESHOLD: u32 = 16 * 1024;
+
+fn default_compression_threshold() -> u32 {
+    DEFAULT_COMPRESSION_THRESHOLD
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
             bind_address: DEFAULT_BIND_ADDRESS.to_string(),
             public_address: DEFAULT_PUBLIC_ADDRESS.to_string(),
             known_addresses: Vec::new(),
+            static_peers: Vec::new(),
+            allowed_node_ids: Vec::new(),
+            allowlist_only: false,
+            sentry_mode: SentryMode::Disabled,
+            transport: TransportProtocol::Tcp,
             min_peers_for_initialization: DEFAULT_MIN_PEERS_FOR_INITIALIZATION,
             gossip_interval: DEFAULT_GOSSIP_INTERVAL,
             initial_gossip_delay: DEFAULT_INITIAL_GOSSIP_DELAY,
@@ -47,10 +59,12 @@ impl Default for Config {
             max_outgoing_byte_rate_non_validators: 0,
             max_incoming_message_rate_non_validators: 0,
             estimator_weights: Default::default(),
+            message_quotas: Default::default(),
             tarpit_version_threshold: None,
             tarpit_duration: TimeDiff::from_seconds(600),
             tarpit_chance: 0.2,
             max_in_flight_demands: 50,
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
             blocklist_retain_duration: TimeDiff::from_seconds(600),
             identity: None,
         }
@@ -70,6 +84,41 @@ pub struct IdentityConfig {
     pub ca_certificate: PathBuf,
 }
 
+/// The role of a node in a sentry node topology.
+///
+/// Sentry nodes shield a validator from the public network: the validator only connects to its
+/// sentries, which relay consensus messages to and from it.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SentryMode {
+    /// The node takes no part in a sentry node topology.
+    #[default]
+    Disabled,
+    /// A validator hidden behind sentry nodes.
+    ///
+    /// The node only connects to its static peers, which must all be on the allowlist, never
+    /// advertises its own address and sends its consensus messages to all of its peers.
+    Validator,
+    /// A sentry node relaying consensus messages for the validators behind it.
+    ///
+    /// The validators behind the sentry are not listed among its peers.
+    Sentry,
+}
+
+/// The transport used for connections to peers.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransportProtocol {
+    /// TLS over TCP.
+    #[default]
+    Tcp,
+    /// QUIC over UDP, on the same port as the TCP listener.
+    ///
+    /// Connections from peers are accepted over both QUIC and TCP, and connecting to peers falls
+    /// back to TCP for peers not reachable via QUIC.
+    Quic,
+}
+
 /// Network configuration.
 #[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
@@ -83,6 +132,18 @@ pub struct Config {
     pub public_address: String,
     /// Known address of a node on the network used for joining.
     pub known_addresses: Vec<String>,
+    /// Addresses of peers which are always connected to and never blocked.
+    pub static_peers: Vec<String>,
+    /// Node IDs, as full hex fingerprints, allowed to connect if `allowlist_only` is set.
+    pub allowed_node_ids: Vec<String>,
+    /// Reject connections to and from nodes whose ID is not in `allowed_node_ids`.
+    pub allowlist_only: bool,
+    /// The role of the node in a sentry node topology.
+    #[serde(default)]
+    pub sentry_mode: SentryMode,
+    /// The transport used for connecting to peers.
+    #[serde(default)]
+    pub transport: TransportProtocol,
     /// Minimum number of fully-connected peers to consider component initialized.
     pub min_peers_for_initialization: u16,
     /// Interval in milliseconds used for gossiping.
@@ -101,6 +162,11 @@ pub struct Config {
     pub max_incoming_message_rate_non_validators: u32,
     /// Weight distribution for the payload impact estimator.
     pub estimator_weights: EstimatorWeights,
+    /// Per-peer quotas on incoming messages by type, in messages per second.
+    ///
+    /// Apply to all peers, including validators.
+    #[serde(default)]
+    pub message_quotas: MessageQuotas,
     /// The protocol version at which (or under) tarpitting is enabled.
     pub tarpit_version_threshold: Option<ProtocolVersion>,
     /// If tarpitting is enabled, duration for which connections should be kept open.
@@ -109,6 +175,10 @@ pub struct Config {
     pub tarpit_chance: f32,
     /// Maximum number of demands for objects that can be in-flight.
     pub max_in_flight_demands: u32,
+    /// Minimum size in bytes of a serialized message to be compressed, if the receiving peer
+    /// supports compression. A value of `0` disables compression of outgoing messages.
+    #[serde(default = "default_compression_threshold")]
+    pub compression_threshold: u32,
     /// Duration peers are kept on the block list, before being redeemed.
     pub blocklist_retain_duration: TimeDiff,
     /// Network identity configuration option.
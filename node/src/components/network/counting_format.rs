@@ -271,6 +271,22 @@ impl ConnectionId {
         Self::create(TlsRandomData::collect(ssl), our_id, their_id)
     }
 
+    /// Creates a new connection ID from keying material exported from a QUIC connection.
+    #[inline]
+    pub(crate) fn from_keying_material(
+        keying_material: [u8; 12],
+        our_id: NodeId,
+        their_id: NodeId,
+    ) -> Self {
+        Self::create(
+            TlsRandomData {
+                combined_random: keying_material,
+            },
+            our_id,
+            their_id,
+        )
+    }
+
     /// Creates a random `ConnectionId`.
     #[cfg(test)]
     pub(super) fn random(rng: &mut TestRng) -> Self {
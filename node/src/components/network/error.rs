@@ -10,6 +10,7 @@ use casper_types::{crypto, ProtocolVersion};
 
 use crate::{
     tls::{LoadCertError, ValidationError},
+    types::NodeIdParseError,
     utils::ResolveAddressError,
 };
 
@@ -71,6 +72,39 @@ pub enum Error {
         #[from]
         LoadCertError,
     ),
+    /// Sentry mode `validator` was enabled without any sentries to connect to.
+    #[error("validator sentry mode requires static peers and allowed node IDs")]
+    SentriesNotConfigured,
+    /// Failed to encode our certificate for use with QUIC.
+    #[error("failed to encode certificate for QUIC")]
+    QuicCertificate(
+        #[serde(skip_serializing)]
+        #[source]
+        ErrorStack,
+    ),
+    /// Failed to configure TLS for QUIC.
+    #[error("failed to configure TLS for QUIC")]
+    QuicTlsConfiguration(
+        #[serde(skip_serializing)]
+        #[source]
+        rustls::Error,
+    ),
+    /// Failed to create a QUIC endpoint.
+    #[error("failed to create QUIC endpoint on {1}")]
+    QuicEndpoint(
+        #[serde(skip_serializing)]
+        #[source]
+        io::Error,
+        SocketAddr,
+    ),
+    /// A node ID in the allowlist could not be parsed.
+    #[error("invalid allowed node ID {0}")]
+    InvalidAllowedNodeId(
+        String,
+        #[serde(skip_serializing)]
+        #[source]
+        NodeIdParseError,
+    ),
 }
 
 // Manual implementation for `DataSize` - the type contains too many FFI variants that are hard to
@@ -126,6 +160,50 @@ pub enum ConnectionError {
         #[source]
         ssl::Error,
     ),
+    /// A QUIC connection could not be initiated.
+    #[error("QUIC connection could not be initiated")]
+    QuicConnect(
+        #[serde(skip_serializing)]
+        #[source]
+        quinn::ConnectError,
+    ),
+    /// A QUIC connection failed.
+    #[error("QUIC connection failed")]
+    QuicConnection(
+        #[serde(skip_serializing)]
+        #[source]
+        quinn::ConnectionError,
+    ),
+    /// Establishing a QUIC connection timed out.
+    #[error("QUIC connection timed out")]
+    QuicTimeout,
+    /// The certificate presented on a QUIC connection could not be decoded.
+    #[error("could not decode peer certificate of QUIC connection")]
+    QuicPeerCertificate(
+        #[serde(skip_serializing)]
+        #[source]
+        ErrorStack,
+    ),
+    /// Keying material could not be exported from a QUIC connection.
+    #[error("could not export keying material from QUIC connection")]
+    QuicKeyingMaterial,
+    /// Our node identity could not be proven on a QUIC connection.
+    #[error("could not prove our identity on QUIC connection")]
+    QuicIdentity(
+        #[serde(skip_serializing)]
+        #[source]
+        ErrorStack,
+    ),
+    /// Node identities could not be exchanged on a QUIC connection.
+    #[error("failed to exchange identities on QUIC connection")]
+    QuicIdentityExchange(
+        #[serde(skip_serializing)]
+        #[source]
+        io::Error,
+    ),
+    /// The peer's signature proving its identity on a QUIC connection is invalid.
+    #[error("invalid identity signature on QUIC connection")]
+    QuicIdentityInvalid,
     /// Remote failed to present a client/server certificate.
     #[error("no client certificate presented")]
     NoPeerCertificate,
@@ -159,6 +237,9 @@ pub enum ConnectionError {
     /// but didn't.
     #[error("peer did not include chainspec hash in the handshake when it was required")]
     MissingChainspecHash,
+    /// Peer is not on the allowlist while allowlist-only mode is enabled.
+    #[error("peer is not on the allowlist")]
+    PeerNotAllowed,
     /// Peer did not send any message, or a non-handshake as its first message.
     #[error("peer did not send handshake")]
     DidNotSendHandshake,
@@ -2,18 +2,20 @@ use std::{
     fmt::{self, Debug, Display, Formatter},
     io, mem,
     net::SocketAddr,
-    sync::Arc,
 };
 
 use derive_more::From;
-use futures::stream::{SplitSink, SplitStream};
 use serde::Serialize;
 use static_assertions::const_assert;
 use tracing::Span;
 
 use casper_types::PublicKey;
 
-use super::{error::ConnectionError, FullTransport, GossipedAddress, Message, NodeId};
+use super::{
+    error::ConnectionError,
+    multiplex::{MessageSink, MessageStream},
+    GossipedAddress, Message, NodeId,
+};
 use crate::{
     effect::{
         announcements::PeerBehaviorAnnouncement,
@@ -146,6 +148,7 @@ impl<P: Display> Display for Event<P> {
 }
 
 /// Outcome of an incoming connection negotiation.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize)]
 pub(crate) enum IncomingConnection<P> {
     /// The connection failed early on, before even a peer's [`NodeId`] could be determined.
@@ -178,7 +181,7 @@ pub(crate) enum IncomingConnection<P> {
         peer_consensus_public_key: Option<PublicKey>,
         /// Stream of incoming messages. for incoming connections.
         #[serde(skip_serializing)]
-        stream: SplitStream<FullTransport<P>>,
+        stream: Box<MessageStream<P>>,
     },
 }
 
@@ -218,6 +221,7 @@ impl<P> Display for IncomingConnection<P> {
 }
 
 /// Outcome of an outgoing connection attempt.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Serialize)]
 pub(crate) enum OutgoingConnection<P> {
     /// The outgoing connection failed early on, before a peer's [`NodeId`] could be determined.
@@ -248,7 +252,7 @@ pub(crate) enum OutgoingConnection<P> {
         peer_consensus_public_key: Option<PublicKey>,
         /// Sink for outgoing messages.
         #[serde(skip_serializing)]
-        sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
+        sink: Box<MessageSink<P>>,
         /// Holds the information whether the remote node is syncing.
         is_syncing: bool,
     },
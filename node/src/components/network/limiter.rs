@@ -15,11 +15,15 @@ use tracing::{error, trace, warn};
 
 use casper_types::{EraId, PublicKey};
 
+use super::{MessageQuota, MessageType};
 use crate::types::{NodeId, ValidatorMatrix};
 
 /// Amount of resource allowed to buffer in `Limiter`.
 const STORED_BUFFER_SECS: Duration = Duration::from_secs(2);
 
+/// Number of seconds worth of a message quota a peer may send in a single burst.
+const QUOTA_BURST_SECS: Duration = Duration::from_secs(2);
+
 /// A limiter dividing resources into two classes based on their validator status.
 ///
 /// Any consumer of a specific resource is expected to call `create_handle` for every peer and use
@@ -90,16 +94,21 @@ impl Limiter {
         }
     }
 
-    pub(super) fn is_validator_in_era(&self, era: EraId, peer_id: &NodeId) -> bool {
-        let public_key = match self.data.connected_validators.read() {
-            Ok(connected_validators) => match connected_validators.get(peer_id) {
-                None => return false,
-                Some(public_key) => public_key.clone(),
-            },
+    /// Returns the consensus key the given peer identified with, if any.
+    fn connected_validator_key(&self, peer_id: &NodeId) -> Option<PublicKey> {
+        match self.data.connected_validators.read() {
+            Ok(connected_validators) => connected_validators.get(peer_id).cloned(),
             Err(_) => {
                 error!("could not read from connected_validators of limiter, lock poisoned");
-                return false;
+                None
             }
+        }
+    }
+
+    pub(super) fn is_validator_in_era(&self, era: EraId, peer_id: &NodeId) -> bool {
+        let public_key = match self.connected_validator_key(peer_id) {
+            None => return false,
+            Some(public_key) => public_key,
         };
 
         match self.validator_matrix.is_validator_in_era(era, &public_key) {
@@ -111,6 +120,20 @@ impl Limiter {
         }
     }
 
+    /// Returns whether the given peer is an active or upcoming validator.
+    pub(super) fn is_active_or_upcoming_validator(&self, peer_id: &NodeId) -> bool {
+        self.connected_validator_key(peer_id)
+            .map_or(false, |public_key| {
+                self.validator_matrix
+                    .is_active_or_upcoming_validator(&public_key)
+            })
+    }
+
+    /// Returns the set of active and upcoming validators.
+    pub(super) fn validator_matrix(&self) -> &ValidatorMatrix {
+        &self.validator_matrix
+    }
+
     pub(super) fn debug_inspect_unspent_allowance(&self) -> Option<i64> {
         Some(task::block_in_place(move || {
             Handle::current().block_on(async move { self.data.resources.lock().await.available })
@@ -280,6 +303,55 @@ impl LimiterHandle {
     }
 }
 
+/// Tracks the messages received from a single peer against per-message-type quotas.
+///
+/// Unlike the `Limiter`, which slows down reading from non-validators, quotas apply to all peers
+/// and are never expected to be exceeded by a well-behaved one.
+#[derive(Debug, Default)]
+pub(super) struct QuotaTracker {
+    /// Remaining allowance by message type.
+    allowances: HashMap<MessageType, Allowance>,
+}
+
+/// The remaining allowance of messages of a single type.
+#[derive(Debug)]
+struct Allowance {
+    /// Number of messages which may still be received, replenished continuously.
+    available: f64,
+    /// Last time the allowance was replenished.
+    last_refill: Instant,
+}
+
+impl QuotaTracker {
+    /// Records a message received at `now`, returning whether it is within its quota.
+    pub(super) fn record_message(&mut self, quota: MessageQuota, now: Instant) -> bool {
+        if quota.per_second == 0 {
+            return true;
+        }
+
+        let per_second = quota.per_second as f64;
+        let max_available = per_second * QUOTA_BURST_SECS.as_secs_f64();
+        let allowance = self
+            .allowances
+            .entry(quota.message_type)
+            .or_insert(Allowance {
+                available: max_available,
+                last_refill: now,
+            });
+
+        let elapsed = now.saturating_duration_since(allowance.last_refill);
+        allowance.last_refill = now;
+        allowance.available =
+            (allowance.available + elapsed.as_secs_f64() * per_second).min(max_available);
+
+        if allowance.available < 1.0 {
+            return false;
+        }
+        allowance.available -= 1.0;
+        true
+    }
+}
+
 /// An identity for a consumer.
 #[derive(Debug)]
 struct ConsumerId {
@@ -298,8 +370,8 @@ mod tests {
     use prometheus::Counter;
     use tokio::time::Instant;
 
-    use super::{Limiter, NodeId, PublicKey};
-    use crate::{testing::init_logging, types::ValidatorMatrix};
+    use super::{Limiter, MessageQuota, MessageType, NodeId, PublicKey, QuotaTracker};
+    use crate::{signer::Signer, testing::init_logging, types::ValidatorMatrix};
 
     /// Something that happens almost immediately, with some allowance for test jitter.
     const SHORT_TIME: Duration = Duration::from_millis(250);
@@ -453,7 +525,6 @@ mod tests {
         let mut rng = crate::new_rng();
 
         let secret_key = SecretKey::random(&mut rng);
-        let consensus_key = PublicKey::from(&secret_key);
         let wait_metric = new_wait_time_sec();
         let limiter = Limiter::new(
             1_000,
@@ -462,8 +533,7 @@ mod tests {
                 Ratio::new(1, 3),
                 None,
                 EraId::from(0),
-                Arc::new(secret_key),
-                consensus_key.clone(),
+                Signer::from(Arc::new(secret_key)),
                 2,
             ),
         );
@@ -547,4 +617,53 @@ mod tests {
             v_completed
         );
     }
+
+    #[test]
+    fn quota_tracker_allows_bursts_within_quota() {
+        let quota = MessageQuota {
+            message_type: MessageType::TrieRequests,
+            per_second: 10,
+        };
+        let start = std::time::Instant::now();
+        let mut tracker = QuotaTracker::default();
+
+        // Two seconds worth of messages may be sent at once.
+        for _ in 0..20 {
+            assert!(tracker.record_message(quota, start));
+        }
+        assert!(!tracker.record_message(quota, start));
+
+        // The allowance is replenished over time.
+        let later = start + Duration::from_millis(500);
+        for _ in 0..5 {
+            assert!(tracker.record_message(quota, later));
+        }
+        assert!(!tracker.record_message(quota, later));
+    }
+
+    #[test]
+    fn quota_tracker_tracks_message_types_separately() {
+        let trie_requests = MessageQuota {
+            message_type: MessageType::TrieRequests,
+            per_second: 1,
+        };
+        let deploy_gossip = MessageQuota {
+            message_type: MessageType::DeployGossip,
+            per_second: 1,
+        };
+        let unlimited = MessageQuota {
+            message_type: MessageType::Consensus,
+            per_second: 0,
+        };
+        let now = std::time::Instant::now();
+        let mut tracker = QuotaTracker::default();
+
+        assert!(tracker.record_message(trie_requests, now));
+        assert!(tracker.record_message(trie_requests, now));
+        assert!(!tracker.record_message(trie_requests, now));
+        assert!(tracker.record_message(deploy_gossip, now));
+        for _ in 0..1000 {
+            assert!(tracker.record_message(unlimited, now));
+        }
+    }
 }
@@ -1,7 +1,9 @@
+#[cfg(test)]
+use std::sync::Arc;
 use std::{
+    collections::BTreeMap,
     fmt::{self, Debug, Display, Formatter},
     net::SocketAddr,
-    sync::Arc,
 };
 
 use datasize::DataSize;
@@ -11,16 +13,20 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use strum::EnumDiscriminants;
+use tracing::error;
 
 use casper_hashing::Digest;
+use casper_types::{crypto, AsymmetricType, ProtocolVersion, PublicKey, Signature};
 #[cfg(test)]
-use casper_types::testing::TestRng;
-use casper_types::{crypto, AsymmetricType, ProtocolVersion, PublicKey, SecretKey, Signature};
+use casper_types::{testing::TestRng, SecretKey};
 
-use super::{counting_format::ConnectionId, health::Nonce, BincodeFormat};
+use super::{
+    compression::Compression, counting_format::ConnectionId, health::Nonce, BincodeFormat,
+};
 use crate::{
     effect::EffectBuilder,
     protocol,
+    signer::Signer,
     types::{Chainspec, NodeId},
     utils::{
         opt_display::OptDisplay,
@@ -55,6 +61,12 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// The compression algorithm the node is able to decompress messages with, if any.
+        #[serde(default)]
+        compression: Option<Compression>,
+        /// True if the node supports multiplexed frames.
+        #[serde(default)]
+        multiplexing: bool,
     },
     /// A ping request.
     Ping {
@@ -67,6 +79,8 @@ pub(crate) enum Message<P> {
         nonce: Nonce,
     },
     Payload(P),
+    /// Proof that the sender relays consensus messages for a validator hidden behind it.
+    SentryCertificate(SentryCertificate),
 }
 
 impl<P: Payload> Message<P> {
@@ -74,9 +88,10 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn classify(&self) -> MessageKind {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => {
-                MessageKind::Protocol
-            }
+            Message::Handshake { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. }
+            | Message::SentryCertificate(_) => MessageKind::Protocol,
             Message::Payload(payload) => payload.message_kind(),
         }
     }
@@ -85,7 +100,10 @@ impl<P: Payload> Message<P> {
     #[inline]
     pub(super) fn is_low_priority(&self) -> bool {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Handshake { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. }
+            | Message::SentryCertificate(_) => false,
             Message::Payload(payload) => payload.is_low_priority(),
         }
     }
@@ -99,15 +117,35 @@ impl<P: Payload> Message<P> {
             // sent as a reply, it has a higher weight.
             Message::Ping { .. } => 2,
             Message::Pong { .. } => 1,
+            Message::SentryCertificate(_) => 1,
             Message::Payload(payload) => payload.incoming_resource_estimate(weights),
         }
     }
 
+    /// Returns the per-peer quota on incoming messages of this type, if any.
+    ///
+    /// Messages of the networking protocol itself are not subject to quotas.
+    #[inline]
+    pub(super) fn incoming_message_quota(&self, quotas: &MessageQuotas) -> Option<MessageQuota> {
+        match self {
+            Message::Handshake { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. }
+            | Message::SentryCertificate(_) => None,
+            Message::Payload(payload) => payload
+                .message_type()
+                .and_then(|message_type| quotas.quota(message_type)),
+        }
+    }
+
     /// Returns whether or not the payload is unsafe for syncing node consumption.
     #[inline]
     pub(super) fn payload_is_unsafe_for_syncing_nodes(&self) -> bool {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Handshake { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. }
+            | Message::SentryCertificate(_) => false,
             Message::Payload(payload) => payload.is_unsafe_for_syncing_peers(),
         }
     }
@@ -124,9 +162,10 @@ impl<P: Payload> Message<P> {
         REv: FromIncoming<P> + Send,
     {
         match self {
-            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => {
-                Err(self.into())
-            }
+            Message::Handshake { .. }
+            | Message::Ping { .. }
+            | Message::Pong { .. }
+            | Message::SentryCertificate(_) => Err(self.into()),
             Message::Payload(payload) => {
                 // Note: For now, the wrapping/unwrap of the payload is a bit unfortunate here.
                 REv::try_demand_from_incoming(effect_builder, sender, payload)
@@ -136,24 +175,31 @@ impl<P: Payload> Message<P> {
     }
 }
 
-/// A pair of secret keys used by consensus.
+/// The consensus key pair, used to sign certificates.
 pub(super) struct NodeKeyPair {
-    secret_key: Arc<SecretKey>,
-    public_key: PublicKey,
+    signer: Signer,
 }
 
 impl NodeKeyPair {
     /// Creates a new key pair for consensus signing.
-    pub(super) fn new(key_pair: (Arc<SecretKey>, PublicKey)) -> Self {
-        Self {
-            secret_key: key_pair.0,
-            public_key: key_pair.1,
-        }
+    pub(super) fn new(signer: Signer) -> Self {
+        Self { signer }
+    }
+
+    /// Returns the public key of this keypair.
+    fn public_key(&self) -> &PublicKey {
+        self.signer.public_key()
     }
 
-    /// Sign a value using this keypair.
-    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
-        crypto::sign(value, &self.secret_key, &self.public_key)
+    /// Sign a value using this keypair, returning `None` if signing failed.
+    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Option<Signature> {
+        match self.signer.sign(value) {
+            Ok(signature) => Some(signature),
+            Err(error) => {
+                error!(%error, "failed to sign with the consensus key");
+                None
+            }
+        }
     }
 }
 
@@ -170,12 +216,14 @@ pub(crate) struct ConsensusCertificate {
 
 impl ConsensusCertificate {
     /// Creates a new consensus certificate from a connection ID and key pair.
-    pub(super) fn create(connection_id: ConnectionId, key_pair: &NodeKeyPair) -> Self {
-        let signature = key_pair.sign(connection_id.as_bytes());
-        ConsensusCertificate {
-            public_key: key_pair.public_key.clone(),
+    ///
+    /// Returns `None` if the connection ID could not be signed.
+    pub(super) fn create(connection_id: ConnectionId, key_pair: &NodeKeyPair) -> Option<Self> {
+        let signature = key_pair.sign(connection_id.as_bytes())?;
+        Some(ConsensusCertificate {
+            public_key: key_pair.public_key().clone(),
             signature,
-        }
+        })
     }
 
     /// Validates a certificate, returning a `PublicKey` if valid.
@@ -188,11 +236,11 @@ impl ConsensusCertificate {
     #[cfg(test)]
     fn random(rng: &mut TestRng) -> Self {
         let secret_key = SecretKey::random(rng);
-        let public_key = PublicKey::from(&secret_key);
         ConsensusCertificate::create(
             ConnectionId::random(rng),
-            &NodeKeyPair::new((Arc::new(secret_key), public_key)),
+            &NodeKeyPair::new(Signer::from(Arc::new(secret_key))),
         )
+        .expect("should sign")
     }
 }
 
@@ -202,6 +250,59 @@ impl Display for ConsensusCertificate {
     }
 }
 
+/// Prefix of the data signed in a [`SentryCertificate`], separating it from other signatures made
+/// with the consensus key.
+const SENTRY_CERTIFICATE_PREFIX: &[u8] = b"casper-sentry:";
+
+/// Certificate used to indicate that the peer relays consensus messages for the validator using
+/// the specified public key.
+///
+/// The validator signs the node ID of its sentry, so the certificate can be presented by the
+/// sentry to all of its peers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct SentryCertificate {
+    public_key: PublicKey,
+    signature: Signature,
+}
+
+impl SentryCertificate {
+    /// Creates a new sentry certificate for the given sentry node from a key pair.
+    ///
+    /// Returns `None` if the certificate could not be signed.
+    pub(super) fn create(sentry: NodeId, key_pair: &NodeKeyPair) -> Option<Self> {
+        let signature = key_pair.sign(Self::signed_data(sentry))?;
+        Some(SentryCertificate {
+            public_key: key_pair.public_key().clone(),
+            signature,
+        })
+    }
+
+    /// Returns the public key of the validator.
+    pub(super) fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Validates a certificate presented by or sent to `sentry`, returning the validator's
+    /// `PublicKey` if valid.
+    pub(super) fn validate(&self, sentry: NodeId) -> Result<&PublicKey, crypto::Error> {
+        crypto::verify(Self::signed_data(sentry), &self.signature, &self.public_key)?;
+        Ok(&self.public_key)
+    }
+
+    /// Returns the data signed by the validator.
+    fn signed_data(sentry: NodeId) -> Vec<u8> {
+        let mut data = SENTRY_CERTIFICATE_PREFIX.to_vec();
+        data.extend_from_slice(sentry.hash_bytes());
+        data
+    }
+}
+
+impl Display for SentryCertificate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "sentry-for:{}", self.public_key)
+    }
+}
+
 /// This type and the `NonHumanReadableCertificate` are helper structs only used in the `Serialize`
 /// and `Deserialize` implementations of `ConsensusCertificate` to allow handshaking between nodes
 /// running the casper-node v1.4.2 and v1.4.3 software versions.
@@ -293,21 +394,28 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                compression,
+                multiplexing,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, compression: {}, multiplexing: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    OptDisplay::new(compression.as_ref(), "none"),
+                    multiplexing
                 )
             }
             Message::Ping { nonce } => write!(f, "ping({})", nonce),
             Message::Pong { nonce } => write!(f, "pong({})", nonce),
             Message::Payload(payload) => write!(f, "payload: {}", payload),
+            Message::SentryCertificate(certificate) => {
+                write!(f, "sentry certificate: {}", certificate)
+            }
         }
     }
 }
@@ -354,6 +462,110 @@ impl Display for MessageKind {
     }
 }
 
+/// The type of a payload message, as distinguished by per-peer quotas.
+#[derive(
+    Clone,
+    Copy,
+    DataSize,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MessageType {
+    Consensus,
+    BlockGossip,
+    DeployGossip,
+    FinalitySignatureGossip,
+    AddressGossip,
+    FinalitySignatureBroadcasts,
+    DeployRequests,
+    DeployResponses,
+    LegacyDeployRequests,
+    LegacyDeployResponses,
+    BlockRequests,
+    BlockResponses,
+    BlockHeaderRequests,
+    BlockHeaderResponses,
+    TrieRequests,
+    TrieResponses,
+    FinalitySignatureRequests,
+    FinalitySignatureResponses,
+    SyncLeapRequests,
+    SyncLeapResponses,
+    ApprovalsHashesRequests,
+    ApprovalsHashesResponses,
+    ExecutionResultsRequests,
+    ExecutionResultsResponses,
+}
+
+/// Per-peer quotas on incoming messages by type, in messages per second.
+///
+/// Types without a quota, or with a quota of `0`, are unlimited.
+///
+/// Serialized as a map from the names of message types to their quotas, since not all formats
+/// support enums as keys.
+#[derive(Clone, DataSize, Debug, Default, Deserialize, Serialize)]
+#[serde(try_from = "BTreeMap<String, u32>", into = "BTreeMap<String, u32>")]
+pub struct MessageQuotas(BTreeMap<MessageType, u32>);
+
+impl TryFrom<BTreeMap<String, u32>> for MessageQuotas {
+    type Error = String;
+
+    fn try_from(quotas: BTreeMap<String, u32>) -> Result<Self, Self::Error> {
+        quotas
+            .into_iter()
+            .map(|(name, per_second)| {
+                let message_type = name
+                    .parse()
+                    .map_err(|_| format!("unknown message type {}", name))?;
+                Ok((message_type, per_second))
+            })
+            .collect::<Result<_, _>>()
+            .map(MessageQuotas)
+    }
+}
+
+impl From<MessageQuotas> for BTreeMap<String, u32> {
+    fn from(quotas: MessageQuotas) -> Self {
+        quotas
+            .0
+            .into_iter()
+            .map(|(message_type, per_second)| (message_type.to_string(), per_second))
+            .collect()
+    }
+}
+
+impl MessageQuotas {
+    /// Returns the quota on messages of the given type, if limited.
+    pub(crate) fn quota(&self, message_type: MessageType) -> Option<MessageQuota> {
+        self.0
+            .get(&message_type)
+            .filter(|per_second| **per_second != 0)
+            .map(|per_second| MessageQuota {
+                message_type,
+                per_second: *per_second,
+            })
+    }
+}
+
+/// A per-peer quota on the rate of incoming messages of a single type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct MessageQuota {
+    /// The type of messages the quota applies to.
+    pub(crate) message_type: MessageType,
+    /// The maximum number of messages per second, `0` meaning unlimited.
+    pub(crate) per_second: u32,
+}
+
 /// Network message payload.
 ///
 /// Payloads are what is transferred across the network outside of control messages from the
@@ -367,6 +579,11 @@ pub(crate) trait Payload:
     /// The penalty for resource usage of a message to be applied when processed as incoming.
     fn incoming_resource_estimate(&self, _weights: &EstimatorWeights) -> u32;
 
+    /// The type of the payload subject to per-peer quotas on incoming messages, if any.
+    fn message_type(&self) -> Option<MessageType> {
+        None
+    }
+
     /// Determines if the payload should be considered low priority.
     fn is_low_priority(&self) -> bool {
         false
@@ -443,7 +660,7 @@ mod specimen_support {
         largest_variant, Cache, LargestSpecimen, SizeEstimator, HIGHEST_UNICODE_CODEPOINT,
     };
 
-    use super::{ConsensusCertificate, Message, MessageDiscriminants};
+    use super::{ConsensusCertificate, Message, MessageDiscriminants, SentryCertificate};
 
     impl<P> LargestSpecimen for Message<P>
     where
@@ -464,6 +681,8 @@ mod specimen_support {
                         consensus_certificate: LargestSpecimen::largest_specimen(estimator, cache),
                         is_syncing: LargestSpecimen::largest_specimen(estimator, cache),
                         chainspec_hash: LargestSpecimen::largest_specimen(estimator, cache),
+                        compression: LargestSpecimen::largest_specimen(estimator, cache),
+                        multiplexing: LargestSpecimen::largest_specimen(estimator, cache),
                     },
                     MessageDiscriminants::Ping => Message::Ping {
                         nonce: LargestSpecimen::largest_specimen(estimator, cache),
@@ -474,6 +693,9 @@ mod specimen_support {
                     MessageDiscriminants::Payload => {
                         Message::Payload(LargestSpecimen::largest_specimen(estimator, cache))
                     }
+                    MessageDiscriminants::SentryCertificate => Message::SentryCertificate(
+                        LargestSpecimen::largest_specimen(estimator, cache),
+                    ),
                 },
             )
         }
@@ -487,6 +709,15 @@ mod specimen_support {
             }
         }
     }
+
+    impl LargestSpecimen for SentryCertificate {
+        fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
+            SentryCertificate {
+                public_key: LargestSpecimen::largest_specimen(estimator, cache),
+                signature: LargestSpecimen::largest_specimen(estimator, cache),
+            }
+        }
+    }
 }
 
 /// An estimator that uses the serialized network representation as a measure of size.
@@ -768,6 +999,8 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            compression: Some(Compression::Deflate),
+            multiplexing: true,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -802,6 +1035,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            multiplexing,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -809,7 +1044,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!multiplexing)
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -826,6 +1063,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            multiplexing,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -834,7 +1073,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!multiplexing)
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -851,6 +1092,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            multiplexing,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -878,7 +1121,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!multiplexing)
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -895,6 +1140,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            compression,
+            multiplexing,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -922,7 +1169,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(compression.is_none());
+            assert!(!multiplexing)
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -952,6 +1201,43 @@ mod tests {
         roundtrip_certificate(false)
     }
 
+    #[test]
+    fn sentry_certificate_is_only_valid_for_its_sentry() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let key_pair = NodeKeyPair::new(Signer::from(Arc::new(secret_key)));
+        let sentry = NodeId::random(&mut rng);
+
+        let certificate = SentryCertificate::create(sentry, &key_pair).expect("should sign");
+        assert_eq!(certificate.validate(sentry).unwrap(), &public_key);
+        assert!(certificate.validate(NodeId::random(&mut rng)).is_err());
+    }
+
+    #[test]
+    fn message_quotas_are_keyed_by_message_type() {
+        let quotas: MessageQuotas = toml::from_str(
+            r#"
+            consensus = 0
+            trie_requests = 2000
+            "#,
+        )
+        .expect("should parse quotas");
+
+        assert_eq!(
+            quotas.quota(MessageType::TrieRequests),
+            Some(MessageQuota {
+                message_type: MessageType::TrieRequests,
+                per_second: 2000,
+            })
+        );
+        assert_eq!(quotas.quota(MessageType::Consensus), None);
+        assert_eq!(quotas.quota(MessageType::DeployGossip), None);
+        assert_eq!(MessageType::TrieRequests.to_string(), "trie_requests");
+
+        assert!(toml::from_str::<MessageQuotas>("trie_request = 2000").is_err());
+    }
+
     #[test]
     fn assert_the_largest_specimen_type_and_size() {
         let (chainspec, _) = crate::utils::Loadable::from_resources("production");
@@ -3,7 +3,7 @@ use std::sync::Weak;
 use prometheus::{Counter, IntCounter, IntGauge, Registry};
 use tracing::debug;
 
-use super::{outgoing::OutgoingMetrics, MessageKind};
+use super::{multiplex::Channel, outgoing::OutgoingMetrics, MessageKind};
 use crate::unregister_metric;
 
 /// Network-type agnostic networking metrics.
@@ -105,6 +105,51 @@ pub(super) struct Metrics {
     /// Count of incoming messages with other payload.
     pub(super) in_count_other: IntCounter,
 
+    /// Number of outgoing messages waiting to be sent on the consensus channel.
+    pub(super) channel_queued_consensus: IntGauge,
+    /// Number of outgoing messages waiting to be sent on the finality signatures channel.
+    pub(super) channel_queued_finality_signatures: IntGauge,
+    /// Number of outgoing messages waiting to be sent on the gossip channel.
+    pub(super) channel_queued_gossip: IntGauge,
+    /// Number of outgoing messages waiting to be sent on the bulk data channel.
+    pub(super) channel_queued_bulk_data: IntGauge,
+
+    /// Volume in bytes of outgoing message fragments on the consensus channel.
+    pub(super) channel_out_bytes_consensus: IntCounter,
+    /// Volume in bytes of outgoing message fragments on the finality signatures channel.
+    pub(super) channel_out_bytes_finality_signatures: IntCounter,
+    /// Volume in bytes of outgoing message fragments on the gossip channel.
+    pub(super) channel_out_bytes_gossip: IntCounter,
+    /// Volume in bytes of outgoing message fragments on the bulk data channel.
+    pub(super) channel_out_bytes_bulk_data: IntCounter,
+
+    /// Volume in bytes of incoming message fragments on the consensus channel.
+    pub(super) channel_in_bytes_consensus: IntCounter,
+    /// Volume in bytes of incoming message fragments on the finality signatures channel.
+    pub(super) channel_in_bytes_finality_signatures: IntCounter,
+    /// Volume in bytes of incoming message fragments on the gossip channel.
+    pub(super) channel_in_bytes_gossip: IntCounter,
+    /// Volume in bytes of incoming message fragments on the bulk data channel.
+    pub(super) channel_in_bytes_bulk_data: IntCounter,
+
+    /// Number of incoming messages on the consensus channel dropped due to a full queue.
+    pub(super) channel_dropped_consensus: IntCounter,
+    /// Number of incoming messages on the finality signatures channel dropped due to a full queue.
+    pub(super) channel_dropped_finality_signatures: IntCounter,
+    /// Number of incoming messages on the gossip channel dropped due to a full queue.
+    pub(super) channel_dropped_gossip: IntCounter,
+    /// Number of incoming messages on the bulk data channel dropped due to a full queue.
+    pub(super) channel_dropped_bulk_data: IntCounter,
+
+    /// Volume in bytes of outgoing messages before being compressed.
+    pub(super) compression_out_bytes_uncompressed: IntCounter,
+    /// Volume in bytes of outgoing messages after being compressed.
+    pub(super) compression_out_bytes_compressed: IntCounter,
+    /// Volume in bytes of incoming messages before being decompressed.
+    pub(super) compression_in_bytes_compressed: IntCounter,
+    /// Volume in bytes of incoming messages after being decompressed.
+    pub(super) compression_in_bytes_uncompressed: IntCounter,
+
     /// Number of trie requests accepted for processing.
     pub(super) requests_for_trie_accepted: IntCounter,
     /// Number of trie requests finished (successful or unsuccessful).
@@ -319,6 +364,92 @@ impl Metrics {
             "volume in bytes of incoming messages with other payload",
         )?;
 
+        let channel_queued_consensus = IntGauge::new(
+            "net_channel_queued_consensus",
+            "number of outgoing messages waiting to be sent on the consensus channel",
+        )?;
+        let channel_queued_finality_signatures = IntGauge::new(
+            "net_channel_queued_finality_signatures",
+            "number of outgoing messages waiting to be sent on the finality signatures channel",
+        )?;
+        let channel_queued_gossip = IntGauge::new(
+            "net_channel_queued_gossip",
+            "number of outgoing messages waiting to be sent on the gossip channel",
+        )?;
+        let channel_queued_bulk_data = IntGauge::new(
+            "net_channel_queued_bulk_data",
+            "number of outgoing messages waiting to be sent on the bulk data channel",
+        )?;
+
+        let channel_out_bytes_consensus = IntCounter::new(
+            "net_channel_out_bytes_consensus",
+            "volume in bytes of outgoing message fragments on the consensus channel",
+        )?;
+        let channel_out_bytes_finality_signatures = IntCounter::new(
+            "net_channel_out_bytes_finality_signatures",
+            "volume in bytes of outgoing message fragments on the finality signatures channel",
+        )?;
+        let channel_out_bytes_gossip = IntCounter::new(
+            "net_channel_out_bytes_gossip",
+            "volume in bytes of outgoing message fragments on the gossip channel",
+        )?;
+        let channel_out_bytes_bulk_data = IntCounter::new(
+            "net_channel_out_bytes_bulk_data",
+            "volume in bytes of outgoing message fragments on the bulk data channel",
+        )?;
+
+        let channel_in_bytes_consensus = IntCounter::new(
+            "net_channel_in_bytes_consensus",
+            "volume in bytes of incoming message fragments on the consensus channel",
+        )?;
+        let channel_in_bytes_finality_signatures = IntCounter::new(
+            "net_channel_in_bytes_finality_signatures",
+            "volume in bytes of incoming message fragments on the finality signatures channel",
+        )?;
+        let channel_in_bytes_gossip = IntCounter::new(
+            "net_channel_in_bytes_gossip",
+            "volume in bytes of incoming message fragments on the gossip channel",
+        )?;
+        let channel_in_bytes_bulk_data = IntCounter::new(
+            "net_channel_in_bytes_bulk_data",
+            "volume in bytes of incoming message fragments on the bulk data channel",
+        )?;
+
+        let channel_dropped_consensus = IntCounter::new(
+            "net_channel_dropped_consensus",
+            "number of incoming messages on the consensus channel dropped due to a full queue",
+        )?;
+        let channel_dropped_finality_signatures = IntCounter::new(
+            "net_channel_dropped_finality_signatures",
+            "number of incoming messages on the finality signatures channel dropped due to a full \
+             queue",
+        )?;
+        let channel_dropped_gossip = IntCounter::new(
+            "net_channel_dropped_gossip",
+            "number of incoming messages on the gossip channel dropped due to a full queue",
+        )?;
+        let channel_dropped_bulk_data = IntCounter::new(
+            "net_channel_dropped_bulk_data",
+            "number of incoming messages on the bulk data channel dropped due to a full queue",
+        )?;
+
+        let compression_out_bytes_uncompressed = IntCounter::new(
+            "net_compression_out_bytes_uncompressed",
+            "volume in bytes of compressed outgoing messages before compression",
+        )?;
+        let compression_out_bytes_compressed = IntCounter::new(
+            "net_compression_out_bytes_compressed",
+            "volume in bytes of compressed outgoing messages after compression",
+        )?;
+        let compression_in_bytes_compressed = IntCounter::new(
+            "net_compression_in_bytes_compressed",
+            "volume in bytes of compressed incoming messages before decompression",
+        )?;
+        let compression_in_bytes_uncompressed = IntCounter::new(
+            "net_compression_in_bytes_uncompressed",
+            "volume in bytes of compressed incoming messages after decompression",
+        )?;
+
         let requests_for_trie_accepted = IntCounter::new(
             "requests_for_trie_accepted",
             "number of trie requests accepted for processing",
@@ -392,6 +523,30 @@ impl Metrics {
         registry.register(Box::new(in_bytes_trie_transfer.clone()))?;
         registry.register(Box::new(in_bytes_other.clone()))?;
 
+        registry.register(Box::new(channel_queued_consensus.clone()))?;
+        registry.register(Box::new(channel_queued_finality_signatures.clone()))?;
+        registry.register(Box::new(channel_queued_gossip.clone()))?;
+        registry.register(Box::new(channel_queued_bulk_data.clone()))?;
+
+        registry.register(Box::new(channel_out_bytes_consensus.clone()))?;
+        registry.register(Box::new(channel_out_bytes_finality_signatures.clone()))?;
+        registry.register(Box::new(channel_out_bytes_gossip.clone()))?;
+        registry.register(Box::new(channel_out_bytes_bulk_data.clone()))?;
+
+        registry.register(Box::new(channel_in_bytes_consensus.clone()))?;
+        registry.register(Box::new(channel_in_bytes_finality_signatures.clone()))?;
+        registry.register(Box::new(channel_in_bytes_gossip.clone()))?;
+        registry.register(Box::new(channel_in_bytes_bulk_data.clone()))?;
+        registry.register(Box::new(channel_dropped_consensus.clone()))?;
+        registry.register(Box::new(channel_dropped_finality_signatures.clone()))?;
+        registry.register(Box::new(channel_dropped_gossip.clone()))?;
+        registry.register(Box::new(channel_dropped_bulk_data.clone()))?;
+
+        registry.register(Box::new(compression_out_bytes_uncompressed.clone()))?;
+        registry.register(Box::new(compression_out_bytes_compressed.clone()))?;
+        registry.register(Box::new(compression_in_bytes_compressed.clone()))?;
+        registry.register(Box::new(compression_in_bytes_uncompressed.clone()))?;
+
         registry.register(Box::new(requests_for_trie_accepted.clone()))?;
         registry.register(Box::new(requests_for_trie_finished.clone()))?;
 
@@ -448,6 +603,26 @@ impl Metrics {
             in_bytes_block_transfer,
             in_bytes_trie_transfer,
             in_bytes_other,
+            channel_queued_consensus,
+            channel_queued_finality_signatures,
+            channel_queued_gossip,
+            channel_queued_bulk_data,
+            channel_out_bytes_consensus,
+            channel_out_bytes_finality_signatures,
+            channel_out_bytes_gossip,
+            channel_out_bytes_bulk_data,
+            channel_in_bytes_consensus,
+            channel_in_bytes_finality_signatures,
+            channel_in_bytes_gossip,
+            channel_in_bytes_bulk_data,
+            channel_dropped_consensus,
+            channel_dropped_finality_signatures,
+            channel_dropped_gossip,
+            channel_dropped_bulk_data,
+            compression_out_bytes_uncompressed,
+            compression_out_bytes_compressed,
+            compression_in_bytes_compressed,
+            compression_in_bytes_uncompressed,
             requests_for_trie_accepted,
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
@@ -556,6 +731,92 @@ impl Metrics {
         }
     }
 
+    /// Records a change in the number of outgoing messages waiting on a channel.
+    pub(super) fn record_channel_queued(this: &Weak<Self>, channel: Channel, delta: i64) {
+        if let Some(metrics) = this.upgrade() {
+            match channel {
+                Channel::Consensus => metrics.channel_queued_consensus.add(delta),
+                Channel::FinalitySignatures => {
+                    metrics.channel_queued_finality_signatures.add(delta)
+                }
+                Channel::Gossip => metrics.channel_queued_gossip.add(delta),
+                Channel::BulkData => metrics.channel_queued_bulk_data.add(delta),
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records an outgoing message fragment on a channel.
+    pub(super) fn record_channel_out(this: &Weak<Self>, channel: Channel, size: u64) {
+        if let Some(metrics) = this.upgrade() {
+            match channel {
+                Channel::Consensus => metrics.channel_out_bytes_consensus.inc_by(size),
+                Channel::FinalitySignatures => {
+                    metrics.channel_out_bytes_finality_signatures.inc_by(size)
+                }
+                Channel::Gossip => metrics.channel_out_bytes_gossip.inc_by(size),
+                Channel::BulkData => metrics.channel_out_bytes_bulk_data.inc_by(size),
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records an incoming message fragment on a channel.
+    pub(super) fn record_channel_in(this: &Weak<Self>, channel: Channel, size: u64) {
+        if let Some(metrics) = this.upgrade() {
+            match channel {
+                Channel::Consensus => metrics.channel_in_bytes_consensus.inc_by(size),
+                Channel::FinalitySignatures => {
+                    metrics.channel_in_bytes_finality_signatures.inc_by(size)
+                }
+                Channel::Gossip => metrics.channel_in_bytes_gossip.inc_by(size),
+                Channel::BulkData => metrics.channel_in_bytes_bulk_data.inc_by(size),
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records an incoming message on a channel dropped due to the channel's queue being full.
+    pub(super) fn record_channel_dropped(this: &Weak<Self>, channel: Channel) {
+        if let Some(metrics) = this.upgrade() {
+            match channel {
+                Channel::Consensus => metrics.channel_dropped_consensus.inc(),
+                Channel::FinalitySignatures => metrics.channel_dropped_finality_signatures.inc(),
+                Channel::Gossip => metrics.channel_dropped_gossip.inc(),
+                Channel::BulkData => metrics.channel_dropped_bulk_data.inc(),
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records the sizes of an outgoing message before and after compression.
+    pub(super) fn record_compression_out(this: &Weak<Self>, uncompressed: u64, compressed: u64) {
+        if let Some(metrics) = this.upgrade() {
+            metrics
+                .compression_out_bytes_uncompressed
+                .inc_by(uncompressed);
+            metrics.compression_out_bytes_compressed.inc_by(compressed);
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records the sizes of an incoming message before and after decompression.
+    pub(super) fn record_compression_in(this: &Weak<Self>, compressed: u64, uncompressed: u64) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.compression_in_bytes_compressed.inc_by(compressed);
+            metrics
+                .compression_in_bytes_uncompressed
+                .inc_by(uncompressed);
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
     /// Creates a set of outgoing metrics that is connected to this set of metrics.
     pub(super) fn create_outgoing_metrics(&self) -> OutgoingMetrics {
         OutgoingMetrics {
@@ -643,6 +904,30 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.in_bytes_trie_transfer);
         unregister_metric!(self.registry, self.in_bytes_other);
 
+        unregister_metric!(self.registry, self.channel_queued_consensus);
+        unregister_metric!(self.registry, self.channel_queued_finality_signatures);
+        unregister_metric!(self.registry, self.channel_queued_gossip);
+        unregister_metric!(self.registry, self.channel_queued_bulk_data);
+
+        unregister_metric!(self.registry, self.channel_out_bytes_consensus);
+        unregister_metric!(self.registry, self.channel_out_bytes_finality_signatures);
+        unregister_metric!(self.registry, self.channel_out_bytes_gossip);
+        unregister_metric!(self.registry, self.channel_out_bytes_bulk_data);
+
+        unregister_metric!(self.registry, self.channel_in_bytes_consensus);
+        unregister_metric!(self.registry, self.channel_in_bytes_finality_signatures);
+        unregister_metric!(self.registry, self.channel_in_bytes_gossip);
+        unregister_metric!(self.registry, self.channel_in_bytes_bulk_data);
+        unregister_metric!(self.registry, self.channel_dropped_consensus);
+        unregister_metric!(self.registry, self.channel_dropped_finality_signatures);
+        unregister_metric!(self.registry, self.channel_dropped_gossip);
+        unregister_metric!(self.registry, self.channel_dropped_bulk_data);
+
+        unregister_metric!(self.registry, self.compression_out_bytes_uncompressed);
+        unregister_metric!(self.registry, self.compression_out_bytes_compressed);
+        unregister_metric!(self.registry, self.compression_in_bytes_compressed);
+        unregister_metric!(self.registry, self.compression_in_bytes_uncompressed);
+
         unregister_metric!(self.registry, self.requests_for_trie_accepted);
         unregister_metric!(self.registry, self.requests_for_trie_finished);
 
@@ -0,0 +1,946 @@
+//! Multiplexing of logical channels over a single connection.
+//!
+//! Every message sent to a peer is assigned a [`Channel`] based on its [`MessageKind`]. Serialized
+//! messages are split into fragments of at most [`MAX_FRAGMENT_SIZE`] bytes, and the fragments of
+//! messages on different channels are interleaved on the wire according to the channels'
+//! priorities. This way a large message, e.g. a trie sent to a syncing peer, delays a consensus
+//! message by at most a few fragments instead of by its entire size.
+//!
+//! Every frame on the wire consists of a one byte channel tag, a one byte set of flags indicating
+//! whether the frame holds the final fragment of a message and whether the message is compressed,
+//! and the fragment data.
+//!
+//! Multiplexing is only used with peers advertising support for it in their handshake. With other
+//! peers, every frame holds an entire uncompressed message without a header, as before; outgoing
+//! messages are still prioritized by channel, but only between messages.
+//!
+//! On QUIC connections, the frames of every channel are sent on a unidirectional QUIC stream of
+//! their own, opened once the channel is first used, so that packets lost on one channel do not
+//! hold up the others.  The bidirectional stream the handshake was carried out on stays open, but
+//! carries no further frames.
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug, Display, Formatter},
+    io,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Weak},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::{
+    stream::{self, SplitSink},
+    SinkExt, Stream, StreamExt,
+};
+use quinn::{Connection, SendStream};
+use tokio_serde::{Deserializer, Serializer};
+use tokio_util::codec::{Decoder, Encoder, Framed, FramedRead, FramedWrite, LengthDelimitedCodec};
+
+use super::{
+    compression::{Compression, Compressor},
+    counting_format::CountingFormat,
+    BincodeFormat, Message, MessageKind, Metrics, Payload, Transport,
+};
+
+/// Maximum number of message bytes carried by a single frame.
+pub(super) const MAX_FRAGMENT_SIZE: usize = 64 * 1024;
+
+/// Size of the header preceding the fragment data in every frame.
+const FRAME_HEADER_SIZE: usize = 2;
+
+/// Flag set on frames holding the final fragment of a message.
+const FINAL_FRAGMENT_FLAG: u8 = 1;
+
+/// Flag set on all frames of a compressed message.
+const COMPRESSED_FLAG: u8 = 2;
+
+/// A logical channel of a connection.
+///
+/// Channels are listed in order of decreasing priority.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub(crate) enum Channel {
+    /// Consensus messages, as well as protocol messages such as pings.
+    Consensus = 0,
+    /// Finality signatures being gossiped.
+    FinalitySignatures = 1,
+    /// Deploys, blocks and addresses being gossiped.
+    Gossip = 2,
+    /// Deploys, blocks and tries transferred via requests, usually as part of syncing.
+    BulkData = 3,
+}
+
+impl Channel {
+    /// Number of channels.
+    pub(super) const COUNT: usize = 4;
+
+    /// All channels, in order of decreasing priority.
+    pub(super) const ALL: [Channel; Channel::COUNT] = [
+        Channel::Consensus,
+        Channel::FinalitySignatures,
+        Channel::Gossip,
+        Channel::BulkData,
+    ];
+
+    /// Returns the channel messages of the given kind are sent on.
+    pub(super) fn for_message_kind(kind: MessageKind) -> Self {
+        match kind {
+            MessageKind::Protocol | MessageKind::Consensus => Channel::Consensus,
+            MessageKind::FinalitySignatureGossip => Channel::FinalitySignatures,
+            MessageKind::DeployGossip
+            | MessageKind::BlockGossip
+            | MessageKind::AddressGossip
+            | MessageKind::Other => Channel::Gossip,
+            MessageKind::DeployTransfer
+            | MessageKind::BlockTransfer
+            | MessageKind::TrieTransfer => Channel::BulkData,
+        }
+    }
+
+    /// Number of fragments the channel may send for every fragment sent on the lowest priority
+    /// channel while all channels are busy.
+    fn weight(self) -> u32 {
+        match self {
+            Channel::Consensus => 8,
+            Channel::FinalitySignatures => 4,
+            Channel::Gossip => 2,
+            Channel::BulkData => 1,
+        }
+    }
+
+    /// Returns the index of the channel in per-channel arrays.
+    pub(super) fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the channel with the given wire tag.
+    fn from_tag(tag: u8) -> Option<Self> {
+        Channel::ALL.get(tag as usize).copied()
+    }
+}
+
+impl Display for Channel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Consensus => f.write_str("consensus"),
+            Channel::FinalitySignatures => f.write_str("finality_signatures"),
+            Channel::Gossip => f.write_str("gossip"),
+            Channel::BulkData => f.write_str("bulk_data"),
+        }
+    }
+}
+
+/// A fragment of a message sent on a channel.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Frame {
+    /// Channel the message is sent on.
+    channel: Channel,
+    /// Whether this is the final fragment of the message.
+    is_final: bool,
+    /// Whether the message is compressed.
+    compressed: bool,
+    /// The fragment data.
+    data: Bytes,
+}
+
+impl Frame {
+    /// Returns the channel the frame was sent on.
+    pub(super) fn channel(&self) -> Channel {
+        self.channel
+    }
+
+    /// Returns whether the frame holds the final fragment of a message.
+    pub(super) fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    /// Returns the size of the fragment data.
+    pub(super) fn data_len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// Codec for [`Frame`]s, on top of length delimited framing.
+#[derive(Clone, Debug)]
+pub(crate) struct FrameCodec {
+    /// The underlying length delimited codec.
+    inner: LengthDelimitedCodec,
+    /// Whether frames carry a header, or each frame is an entire message as used by peers not
+    /// supporting multiplexing.
+    multiplexed: bool,
+}
+
+impl FrameCodec {
+    /// Creates a new frame codec, wrapping the given length delimited codec.
+    pub(super) fn new(inner: LengthDelimitedCodec, multiplexed: bool) -> Self {
+        FrameCodec { inner, multiplexed }
+    }
+}
+
+impl Encoder<Frame> for FrameCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, frame: Frame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if !self.multiplexed {
+            if !frame.is_final || frame.compressed {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "peer does not support fragmented or compressed messages",
+                ));
+            }
+            return self.inner.encode(frame.data, dst);
+        }
+
+        let mut raw = BytesMut::with_capacity(FRAME_HEADER_SIZE + frame.data.len());
+        raw.put_u8(frame.channel as u8);
+        let mut flags = 0;
+        if frame.is_final {
+            flags |= FINAL_FRAGMENT_FLAG;
+        }
+        if frame.compressed {
+            flags |= COMPRESSED_FLAG;
+        }
+        raw.put_u8(flags);
+        raw.extend_from_slice(&frame.data);
+        self.inner.encode(raw.freeze(), dst)
+    }
+}
+
+impl Decoder for FrameCodec {
+    type Item = Frame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let mut raw = match self.inner.decode(src)? {
+            Some(raw) => raw,
+            None => return Ok(None),
+        };
+
+        if !self.multiplexed {
+            // The actual channel is determined by the message once it is deserialized.
+            return Ok(Some(Frame {
+                channel: Channel::Gossip,
+                is_final: true,
+                compressed: false,
+                data: raw.freeze(),
+            }));
+        }
+
+        if raw.len() < FRAME_HEADER_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "frame too short to hold a header",
+            ));
+        }
+        let channel = Channel::from_tag(raw[0]).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid channel tag {}", raw[0]),
+            )
+        })?;
+        let flags = raw[1];
+        if flags & !(FINAL_FRAGMENT_FLAG | COMPRESSED_FLAG) != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid frame flags {}", flags),
+            ));
+        }
+        raw.advance(FRAME_HEADER_SIZE);
+
+        Ok(Some(Frame {
+            channel,
+            is_final: flags & FINAL_FRAGMENT_FLAG != 0,
+            compressed: flags & COMPRESSED_FLAG != 0,
+            data: raw.freeze(),
+        }))
+    }
+}
+
+/// Per-channel queues of outgoing messages.
+///
+/// Messages are serialized once they reach the front of their channel's queue, and sent fragment
+/// by fragment.  The channel to send the next fragment on is chosen by weighted round robin: every
+/// channel with pending data may send up to its [weight](Channel::weight) fragments, higher
+/// priority channels first, before the allowances of all channels are replenished.  Higher
+/// priority channels thus get the largest share of the bandwidth, while lower priority ones are
+/// never starved.
+#[derive(Debug)]
+pub(super) struct OutgoingQueues<M, R> {
+    /// Messages waiting to be sent, with their optional responders.
+    queued: [VecDeque<(M, Option<R>)>; Channel::COUNT],
+    /// The unsent remainder of the serialized message currently being sent on each channel, along
+    /// with whether it is compressed.
+    in_progress: [Option<(Bytes, bool, Option<R>)>; Channel::COUNT],
+    /// Fragments each channel may still send before the allowances are replenished.
+    credits: [u32; Channel::COUNT],
+    /// Maximum number of message bytes per fragment.
+    max_fragment_size: usize,
+}
+
+impl<M, R> Default for OutgoingQueues<M, R> {
+    fn default() -> Self {
+        OutgoingQueues::new(MAX_FRAGMENT_SIZE)
+    }
+}
+
+impl<M, R> OutgoingQueues<M, R> {
+    /// Creates new queues, splitting messages into fragments of at most `max_fragment_size` bytes.
+    pub(super) fn new(max_fragment_size: usize) -> Self {
+        OutgoingQueues {
+            queued: Default::default(),
+            in_progress: Default::default(),
+            credits: Channel::ALL.map(Channel::weight),
+            max_fragment_size,
+        }
+    }
+
+    /// Queues a message to be sent on the given channel.
+    pub(super) fn push(&mut self, channel: Channel, message: M, responder: Option<R>) {
+        self.queued[channel.index()].push_back((message, responder));
+    }
+
+    /// Returns the number of messages not yet completely sent on the given channel.
+    pub(super) fn pending(&self, channel: Channel) -> usize {
+        self.queued[channel.index()].len()
+            + usize::from(self.in_progress[channel.index()].is_some())
+    }
+
+    /// Returns `true` if there is nothing left to send on any channel.
+    pub(super) fn is_empty(&self) -> bool {
+        Channel::ALL
+            .iter()
+            .all(|channel| self.pending(*channel) == 0)
+    }
+
+    /// Chooses the channel to send the next fragment on.
+    fn next_channel(&mut self) -> Option<Channel> {
+        if self.is_empty() {
+            return None;
+        }
+
+        loop {
+            for channel in Channel::ALL {
+                if self.pending(channel) > 0 && self.credits[channel.index()] > 0 {
+                    self.credits[channel.index()] -= 1;
+                    return Some(channel);
+                }
+            }
+            // No channel with pending data has any allowance left, replenish all of them.
+            self.credits = Channel::ALL.map(Channel::weight);
+        }
+    }
+
+    /// Returns the next fragment to send, along with the responder of its message if it is the
+    /// final fragment.
+    ///
+    /// `serialize` is called on a message once it is about to be sent, and returns the data to send
+    /// along with whether it is compressed.
+    pub(super) fn next_fragment<F, E>(
+        &mut self,
+        mut serialize: F,
+    ) -> Result<Option<(Frame, Option<R>)>, E>
+    where
+        F: FnMut(&M) -> Result<(Bytes, bool), E>,
+    {
+        let channel = match self.next_channel() {
+            Some(channel) => channel,
+            None => return Ok(None),
+        };
+
+        let (mut remaining, compressed, responder) = match self.in_progress[channel.index()].take()
+        {
+            Some(in_progress) => in_progress,
+            None => {
+                let (message, responder) = self.queued[channel.index()]
+                    .pop_front()
+                    .expect("channel with pending data should have a queued message");
+                let (serialized, compressed) = serialize(&message)?;
+                (serialized, compressed, responder)
+            }
+        };
+
+        let data = if remaining.len() > self.max_fragment_size {
+            remaining.split_to(self.max_fragment_size)
+        } else {
+            remaining.split_off(0)
+        };
+        let is_final = remaining.is_empty();
+        let frame = Frame {
+            channel,
+            is_final,
+            compressed,
+            data,
+        };
+
+        if is_final {
+            Ok(Some((frame, responder)))
+        } else {
+            self.in_progress[channel.index()] = Some((remaining, compressed, responder));
+            Ok(Some((frame, None)))
+        }
+    }
+}
+
+/// Reassembles incoming messages from their fragments.
+#[derive(Debug)]
+pub(super) struct Demultiplexer {
+    /// Fragments received so far of the message currently being received on each channel.
+    buffers: [BytesMut; Channel::COUNT],
+    /// Maximum size of a reassembled message.
+    max_message_size: usize,
+}
+
+impl Demultiplexer {
+    /// Creates a new demultiplexer, rejecting messages larger than `max_message_size`.
+    pub(super) fn new(max_message_size: u32) -> Self {
+        Demultiplexer {
+            buffers: Default::default(),
+            max_message_size: max_message_size as usize,
+        }
+    }
+
+    /// Adds a received frame, returning the complete message along with whether it is compressed
+    /// once its final fragment arrived.
+    pub(super) fn push(&mut self, frame: Frame) -> io::Result<Option<(BytesMut, bool)>> {
+        let buffer = &mut self.buffers[frame.channel.index()];
+        if buffer.len() + frame.data.len() > self.max_message_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "message on {} channel exceeds maximum size of {} bytes",
+                    frame.channel, self.max_message_size
+                ),
+            ));
+        }
+
+        buffer.extend_from_slice(&frame.data);
+        if frame.is_final {
+            Ok(Some((buffer.split(), frame.compressed)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A multiplexed transport, carrying the frames of all channels.
+pub(crate) type MultiplexedTransport = Framed<Transport, FrameCodec>;
+
+/// Destination of outgoing frames.
+#[derive(Debug)]
+pub(super) enum FrameSink {
+    /// All frames are sent on the transport.
+    Transport(SplitSink<MultiplexedTransport, Frame>),
+    /// Frames are sent on a unidirectional QUIC stream per channel.
+    QuicStreams {
+        /// The transport the handshake was carried out on, which keeps the connection open.
+        _transport: SplitSink<MultiplexedTransport, Frame>,
+        /// The connection to open streams on.
+        connection: Connection,
+        /// Codec for the frames sent on every stream.
+        codec: FrameCodec,
+        /// The stream of each channel, once opened.
+        streams: [Option<FramedWrite<SendStream, FrameCodec>>; Channel::COUNT],
+    },
+}
+
+impl FrameSink {
+    /// Writes a frame, without flushing it.
+    async fn feed(&mut self, frame: Frame) -> io::Result<()> {
+        match self {
+            FrameSink::Transport(sink) => sink.feed(frame).await,
+            FrameSink::QuicStreams {
+                connection,
+                codec,
+                streams,
+                ..
+            } => {
+                let stream = match &mut streams[frame.channel.index()] {
+                    Some(stream) => stream,
+                    empty @ None => {
+                        let send = connection.open_uni().await?;
+                        empty.insert(FramedWrite::new(send, codec.clone()))
+                    }
+                };
+                stream.feed(frame).await
+            }
+        }
+    }
+
+    /// Flushes all frames written so far.
+    async fn flush(&mut self) -> io::Result<()> {
+        match self {
+            FrameSink::Transport(sink) => sink.flush().await,
+            FrameSink::QuicStreams { streams, .. } => {
+                for stream in streams.iter_mut().flatten() {
+                    stream.flush().await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A stream of incoming frames.
+pub(super) type FrameStream = Pin<Box<dyn Stream<Item = io::Result<Frame>> + Send + Sync>>;
+
+/// Creates the stream of incoming frames, taken from the transport and, on QUIC connections, all
+/// unidirectional streams opened by the peer.
+pub(super) fn incoming_frames(
+    transport: impl Stream<Item = io::Result<Frame>> + Send + Sync + 'static,
+    quic: Option<(Connection, FrameCodec)>,
+) -> FrameStream {
+    match quic {
+        None => Box::pin(transport),
+        Some((connection, codec)) => {
+            let streams = stream::unfold(connection, move |connection| {
+                let codec = codec.clone();
+                async move {
+                    let recv = connection.accept_uni().await.ok()?;
+                    Some((FramedRead::new(recv, codec), connection))
+                }
+            });
+            Box::pin(stream::select(transport, streams.flatten_unordered(None)))
+        }
+    }
+}
+
+/// The sending half of a multiplexed transport.
+#[derive(Debug)]
+pub(crate) struct MessageSink<P> {
+    /// The sink for outgoing frames.
+    frames: FrameSink,
+    /// Serializer for outgoing messages.
+    format: CountingFormat<BincodeFormat>,
+    /// Compressor for large outgoing messages.
+    compressor: Compressor,
+    /// Whether the peer supports multiplexing.
+    multiplexed: bool,
+    /// Metrics to update.
+    metrics: Weak<Metrics>,
+    _payload: PhantomData<P>,
+}
+
+impl<P: Payload> MessageSink<P> {
+    /// Creates a new message sink.
+    ///
+    /// Messages are only compressed if the peer supports multiplexing.
+    pub(super) fn new(
+        frames: FrameSink,
+        format: CountingFormat<BincodeFormat>,
+        compressor: Compressor,
+        multiplexed: bool,
+        metrics: Weak<Metrics>,
+    ) -> Self {
+        MessageSink {
+            frames,
+            format,
+            compressor: if multiplexed {
+                compressor
+            } else {
+                Compressor::new(None, 0)
+            },
+            multiplexed,
+            metrics,
+            _payload: PhantomData,
+        }
+    }
+
+    /// Returns the metrics updated by the sink.
+    pub(super) fn metrics(&self) -> &Weak<Metrics> {
+        &self.metrics
+    }
+
+    /// Returns the maximum number of message bytes the peer accepts in a single frame.
+    pub(super) fn max_fragment_size(&self) -> usize {
+        if self.multiplexed {
+            MAX_FRAGMENT_SIZE
+        } else {
+            usize::MAX
+        }
+    }
+
+    /// Serializes and, if worthwhile, compresses a message about to be sent.
+    ///
+    /// Returns the data to send, along with whether it is compressed.
+    pub(super) fn serialize(&mut self, message: &Arc<Message<P>>) -> io::Result<(Bytes, bool)> {
+        let serialized = Pin::new(&mut self.format).serialize(message)?;
+        let uncompressed_len = serialized.len();
+        let (data, compressed) = self.compressor.compress(serialized)?;
+        if compressed {
+            Metrics::record_compression_out(
+                &self.metrics,
+                uncompressed_len as u64,
+                data.len() as u64,
+            );
+        }
+        Ok((data, compressed))
+    }
+
+    /// Writes a frame to the connection, without flushing it.
+    pub(super) async fn feed(&mut self, frame: Frame) -> io::Result<()> {
+        Metrics::record_channel_out(&self.metrics, frame.channel, frame.data.len() as u64);
+        self.frames.feed(frame).await
+    }
+
+    /// Flushes all frames written so far.
+    pub(super) async fn flush(&mut self) -> io::Result<()> {
+        self.frames.flush().await
+    }
+}
+
+/// The receiving half of a multiplexed transport.
+pub(crate) struct MessageStream<P> {
+    /// The stream of incoming frames.
+    frames: FrameStream,
+    /// Reassembles messages from their fragments.
+    demultiplexer: Demultiplexer,
+    /// Deserializer for incoming messages.
+    format: CountingFormat<BincodeFormat>,
+    /// Whether the peer supports multiplexing.
+    multiplexed: bool,
+    /// Metrics to update.
+    metrics: Weak<Metrics>,
+    _payload: PhantomData<P>,
+}
+
+impl<P> Debug for MessageStream<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageStream")
+            .field("demultiplexer", &self.demultiplexer)
+            .field("multiplexed", &self.multiplexed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: Payload> MessageStream<P> {
+    /// Creates a new message stream.
+    pub(super) fn new(
+        frames: FrameStream,
+        demultiplexer: Demultiplexer,
+        format: CountingFormat<BincodeFormat>,
+        multiplexed: bool,
+        metrics: Weak<Metrics>,
+    ) -> Self {
+        MessageStream {
+            frames,
+            demultiplexer,
+            format,
+            multiplexed,
+            metrics,
+            _payload: PhantomData,
+        }
+    }
+
+    /// Receives the next complete message, along with the channel it was sent on.
+    ///
+    /// Returns `None` once the connection has been closed.
+    pub(super) async fn next_message(&mut self) -> Option<io::Result<(Channel, Message<P>)>> {
+        loop {
+            let frame = match self.frames.next().await? {
+                Ok(frame) => frame,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if !self.multiplexed {
+                // Every frame is a complete message, its channel is only known once deserialized.
+                let raw = match self.demultiplexer.push(frame) {
+                    Ok(Some((raw, _))) => raw,
+                    Ok(None) => continue,
+                    Err(err) => return Some(Err(err)),
+                };
+                let message: Message<P> = match Pin::new(&mut self.format).deserialize(&raw) {
+                    Ok(message) => message,
+                    Err(err) => return Some(Err(err)),
+                };
+                let channel = Channel::for_message_kind(message.classify());
+                Metrics::record_channel_in(&self.metrics, channel, raw.len() as u64);
+                return Some(Ok((channel, message)));
+            }
+
+            let channel = frame.channel;
+            Metrics::record_channel_in(&self.metrics, channel, frame.data.len() as u64);
+
+            match self.demultiplexer.push(frame) {
+                Ok(Some((raw, false))) => {
+                    return Some(
+                        Pin::new(&mut self.format)
+                            .deserialize(&raw)
+                            .map(|message| (channel, message)),
+                    );
+                }
+                Ok(Some((compressed, true))) => {
+                    // We only advertise support for a single algorithm, which peers must use.
+                    let raw = match Compression::Deflate
+                        .decompress(&compressed, self.demultiplexer.max_message_size)
+                    {
+                        Ok(raw) => raw,
+                        Err(err) => return Some(Err(err)),
+                    };
+                    Metrics::record_compression_in(
+                        &self.metrics,
+                        compressed.len() as u64,
+                        raw.len() as u64,
+                    );
+                    return Some(
+                        Pin::new(&mut self.format)
+                            .deserialize(&raw)
+                            .map(|message| (channel, message)),
+                    );
+                }
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::{super::quic, *};
+    use crate::tls;
+
+    fn serialize(message: &Bytes) -> Result<(Bytes, bool), Infallible> {
+        Ok((message.clone(), false))
+    }
+
+    fn drain(queues: &mut OutgoingQueues<Bytes, u32>) -> Vec<(Frame, Option<u32>)> {
+        let mut fragments = Vec::new();
+        while let Some(fragment) = queues.next_fragment(serialize).unwrap() {
+            fragments.push(fragment);
+        }
+        fragments
+    }
+
+    #[test]
+    fn frames_roundtrip_through_codec() {
+        let frame = Frame {
+            channel: Channel::FinalitySignatures,
+            is_final: true,
+            compressed: true,
+            data: Bytes::from_static(b"signature"),
+        };
+
+        let mut codec = FrameCodec::new(LengthDelimitedCodec::new(), true);
+        let mut buffer = BytesMut::new();
+        codec.encode(frame.clone(), &mut buffer).unwrap();
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some(frame));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn should_send_entire_messages_without_header_to_peers_not_multiplexing() {
+        let frame = Frame {
+            channel: Channel::Gossip,
+            is_final: true,
+            compressed: false,
+            data: Bytes::from_static(b"deploy"),
+        };
+
+        // The wire format is plain length delimited framing.
+        let mut codec = FrameCodec::new(LengthDelimitedCodec::new(), false);
+        let mut buffer = BytesMut::new();
+        codec.encode(frame.clone(), &mut buffer).unwrap();
+        assert_eq!(
+            LengthDelimitedCodec::new()
+                .decode(&mut buffer.clone())
+                .unwrap()
+                .unwrap(),
+            &b"deploy"[..]
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), Some(frame));
+
+        let fragment = Frame {
+            channel: Channel::BulkData,
+            is_final: false,
+            compressed: false,
+            data: Bytes::from_static(b"trie"),
+        };
+        assert!(codec.encode(fragment, &mut buffer).is_err());
+
+        // Messages aren't split into fragments.
+        let mut queues = OutgoingQueues::<_, u32>::new(usize::MAX);
+        queues.push(
+            Channel::BulkData,
+            Bytes::from(vec![0; MAX_FRAGMENT_SIZE * 2]),
+            None,
+        );
+        assert_eq!(drain(&mut queues).len(), 1);
+    }
+
+    #[test]
+    fn should_reject_frames_with_invalid_channel() {
+        let mut codec = FrameCodec::new(LengthDelimitedCodec::new(), true);
+        let mut buffer = BytesMut::new();
+        LengthDelimitedCodec::new()
+            .encode(
+                Bytes::from_static(&[Channel::COUNT as u8, 0, 1, 2]),
+                &mut buffer,
+            )
+            .unwrap();
+        assert!(codec.decode(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn should_fragment_and_reassemble_large_messages() {
+        let message: Vec<u8> = (0..(MAX_FRAGMENT_SIZE * 2 + 10)).map(|i| i as u8).collect();
+        let mut queues = OutgoingQueues::default();
+        queues.push(Channel::BulkData, Bytes::from(message.clone()), Some(7));
+
+        let fragments = drain(&mut queues);
+        assert_eq!(fragments.len(), 3);
+        assert!(fragments[..2]
+            .iter()
+            .all(|(frame, responder)| !frame.is_final && responder.is_none()));
+        assert_eq!(fragments[2].1, Some(7));
+        assert!(queues.is_empty());
+
+        let mut demultiplexer = Demultiplexer::new(message.len() as u32);
+        let reassembled: Vec<_> = fragments
+            .into_iter()
+            .filter_map(|(frame, _)| demultiplexer.push(frame).unwrap())
+            .collect();
+        assert_eq!(reassembled, vec![(BytesMut::from(&message[..]), false)]);
+    }
+
+    #[test]
+    fn should_mark_all_fragments_of_compressed_messages() {
+        let mut queues = OutgoingQueues::<_, u32>::default();
+        queues.push(
+            Channel::BulkData,
+            Bytes::from(vec![0; MAX_FRAGMENT_SIZE + 1]),
+            None,
+        );
+
+        let fragments: Vec<_> = std::iter::from_fn(|| {
+            queues
+                .next_fragment(|message: &Bytes| Ok::<_, Infallible>((message.clone(), true)))
+                .unwrap()
+        })
+        .collect();
+        assert_eq!(fragments.len(), 2);
+        assert!(fragments.iter().all(|(frame, _)| frame.compressed));
+
+        let mut demultiplexer = Demultiplexer::new(MAX_FRAGMENT_SIZE as u32 + 1);
+        let reassembled: Vec<_> = fragments
+            .into_iter()
+            .filter_map(|(frame, _)| demultiplexer.push(frame).unwrap())
+            .collect();
+        assert_eq!(reassembled.len(), 1);
+        assert!(reassembled[0].1);
+    }
+
+    #[test]
+    fn should_reject_oversized_messages() {
+        let mut demultiplexer = Demultiplexer::new(MAX_FRAGMENT_SIZE as u32);
+        let fragment = Frame {
+            channel: Channel::Gossip,
+            is_final: false,
+            compressed: false,
+            data: Bytes::from(vec![0; MAX_FRAGMENT_SIZE]),
+        };
+        assert!(demultiplexer.push(fragment.clone()).unwrap().is_none());
+        assert!(demultiplexer.push(fragment).is_err());
+    }
+
+    #[test]
+    fn should_interleave_consensus_messages_with_bulk_data() {
+        let mut queues = OutgoingQueues::default();
+        queues.push(
+            Channel::BulkData,
+            Bytes::from(vec![0; MAX_FRAGMENT_SIZE * 20]),
+            None,
+        );
+
+        // Start sending the bulk data, then queue a consensus message.
+        let (first, _) = queues.next_fragment(serialize).unwrap().unwrap();
+        assert_eq!(first.channel, Channel::BulkData);
+        queues.push(Channel::Consensus, Bytes::from(vec![1; 10]), Some(1));
+
+        let (next, responder) = queues.next_fragment(serialize).unwrap().unwrap();
+        assert_eq!(next.channel, Channel::Consensus);
+        assert!(next.is_final);
+        assert_eq!(responder, Some(1));
+    }
+
+    #[test]
+    fn should_share_bandwidth_by_weight_without_starving_any_channel() {
+        let mut queues = OutgoingQueues::<_, u32>::default();
+        for channel in Channel::ALL {
+            queues.push(channel, Bytes::from(vec![0; MAX_FRAGMENT_SIZE * 100]), None);
+        }
+
+        let total_weight: u32 = Channel::ALL.iter().map(|channel| channel.weight()).sum();
+        let mut counts = [0u32; Channel::COUNT];
+        for _ in 0..total_weight * 3 {
+            let (frame, _) = queues.next_fragment(serialize).unwrap().unwrap();
+            counts[frame.channel.index()] += 1;
+        }
+
+        assert_eq!(counts, Channel::ALL.map(|channel| channel.weight() * 3));
+    }
+
+    #[tokio::test]
+    async fn should_send_each_channel_on_its_own_quic_stream() {
+        let (server_cert, server_key) = tls::generate_node_cert().unwrap();
+        let (client_cert, client_key) = tls::generate_node_cert().unwrap();
+        let server = quic::create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = quic::create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let accepting = async {
+            let connecting = server.accept().await.unwrap();
+            quic::accept(connecting, &server_cert, &server_key)
+                .await
+                .unwrap()
+                .1
+        };
+        let connecting = async {
+            quic::connect(&client, server_addr, &client_cert, &client_key)
+                .await
+                .unwrap()
+                .1
+        };
+        let (listener, dialer) = tokio::join!(accepting, connecting);
+
+        let codec = FrameCodec::new(LengthDelimitedCodec::new(), true);
+        let connection = dialer.connection();
+        let (transport, _) = Framed::new(Transport::Quic(dialer), codec.clone()).split();
+        let mut sink = FrameSink::QuicStreams {
+            _transport: transport,
+            connection,
+            codec: codec.clone(),
+            streams: Default::default(),
+        };
+        let connection = listener.connection();
+        let (_, transport) = Framed::new(Transport::Quic(listener), codec.clone()).split();
+        let mut incoming = incoming_frames(transport, Some((connection, codec)));
+
+        let frames = [Channel::Consensus, Channel::BulkData].map(|channel| Frame {
+            channel,
+            is_final: true,
+            compressed: false,
+            data: Bytes::from(vec![channel as u8; 10]),
+        });
+        for frame in frames.iter().cloned() {
+            sink.feed(frame).await.unwrap();
+        }
+        sink.flush().await.unwrap();
+
+        let mut received = vec![
+            incoming.next().await.unwrap().unwrap(),
+            incoming.next().await.unwrap().unwrap(),
+        ];
+        received.sort_by_key(|frame| frame.channel.index());
+        assert_eq!(received, frames);
+
+        match sink {
+            FrameSink::QuicStreams { streams, .. } => {
+                assert_eq!(streams.iter().filter(|stream| stream.is_some()).count(), 2)
+            }
+            FrameSink::Transport(_) => unreachable!(),
+        }
+    }
+}
@@ -579,6 +579,18 @@ where
             })
     }
 
+    /// Marks an already known address as unforgettable.
+    ///
+    /// Does nothing if the address is unknown.
+    pub(super) fn mark_unforgettable(&mut self, addr: SocketAddr) {
+        if let Some(outgoing) = self.outgoing.get_mut(&addr) {
+            if !outgoing.is_unforgettable {
+                outgoing.is_unforgettable = true;
+                debug!(%addr, "marked unforgettable");
+            }
+        }
+    }
+
     /// Blocks an address.
     ///
     /// Causes any current connection to the address to be terminated and future ones prohibited.
@@ -704,6 +716,17 @@ where
         }
     }
 
+    /// Returns the most recently measured round-trip time to a connected peer.
+    pub(super) fn round_trip_time(&self, peer_id: NodeId) -> Option<Duration> {
+        let outgoing = self.outgoing.get(self.routes.get(&peer_id)?)?;
+
+        if let OutgoingState::Connected { ref health, .. } = outgoing.state {
+            health.calc_rrt()
+        } else {
+            None
+        }
+    }
+
     /// Performs housekeeping like reconnection or unblocking peers.
     ///
     /// This function must periodically be called. A good interval is every second.
@@ -0,0 +1,514 @@
+//! Persistent peer database.
+//!
+//! Keeps a record of every peer address we successfully connected to, along with how reliable it
+//! has proven to be, and persists it to disk. After a restart, the addresses with the best
+//! reputation are dialed right away and any block still in effect is restored, instead of
+//! rediscovering the network from the configured known addresses alone.
+//!
+//! Writing the database to disk is left to the caller, so it can be done off the reactor.
+
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs, io,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use casper_types::{TimeDiff, Timestamp};
+
+use super::blocklist::BlocklistJustification;
+use crate::utils::display_error;
+
+/// Name of the peer database file within the storage directory.
+pub(super) const PEER_DB_FILENAME: &str = "peers.json";
+
+/// Maximum number of peers kept in the database.
+const MAX_PEER_RECORDS: usize = 1000;
+
+/// Minimum interval between two writes of the database to disk.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Reputation gained per successful session, up to `MAX_SCORED_SESSIONS` sessions.
+const SESSION_SCORE: i64 = 10;
+
+/// Number of successful sessions beyond which reputation no longer increases.
+const MAX_SCORED_SESSIONS: u32 = 50;
+
+/// Reputation lost per failed connection attempt since the last successful one.
+const FAILURE_PENALTY: i64 = 20;
+
+/// Reputation lost per 100 milliseconds of round-trip time.
+const ROUND_TRIP_TIME_PENALTY: i64 = 5;
+
+/// Time after which the penalty for past offenses is halved.
+const OFFENSE_PENALTY_HALF_LIFE: TimeDiff = TimeDiff::from_seconds(24 * 60 * 60);
+
+/// What we know about a single peer address.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(super) struct PeerRecord {
+    /// Last time a connection to the peer was established.
+    last_seen: Option<Timestamp>,
+    /// Number of connections to the peer successfully established.
+    successful_sessions: u32,
+    /// Number of failed connection attempts since the last successful one.
+    consecutive_failures: u32,
+    /// Accumulated reputation penalty for offenses the peer was blocked for, as of
+    /// `last_offense_time`.
+    offense_penalty: u64,
+    /// The reason the peer was most recently blocked for.
+    last_offense: Option<String>,
+    /// Time the peer was most recently blocked.
+    last_offense_time: Option<Timestamp>,
+    /// Time until which the peer is blocked.
+    blocked_until: Option<Timestamp>,
+    /// Smoothed round-trip time to the peer, in milliseconds.
+    round_trip_time_millis: Option<u64>,
+}
+
+impl PeerRecord {
+    /// Returns the reputation of the peer at the given time; higher is better.
+    fn reputation(&self, now: Timestamp) -> i64 {
+        let sessions = i64::from(self.successful_sessions.min(MAX_SCORED_SESSIONS)) * SESSION_SCORE;
+        let failures = i64::from(self.consecutive_failures) * FAILURE_PENALTY;
+        let offenses = i64::try_from(self.offense_penalty(now)).unwrap_or(i64::MAX);
+        let latency = self
+            .round_trip_time_millis
+            .map_or(0, |rtt| i64::try_from(rtt / 100).unwrap_or(i64::MAX))
+            .saturating_mul(ROUND_TRIP_TIME_PENALTY);
+        sessions
+            .saturating_sub(failures)
+            .saturating_sub(offenses)
+            .saturating_sub(latency)
+    }
+
+    /// Returns the penalty for past offenses at the given time, halved for every
+    /// `OFFENSE_PENALTY_HALF_LIFE` passed since the last offense.
+    fn offense_penalty(&self, now: Timestamp) -> u64 {
+        let elapsed = self
+            .last_offense_time
+            .map_or(TimeDiff::from_millis(0), |time| now.saturating_diff(time));
+        let half_lives = elapsed.millis() / OFFENSE_PENALTY_HALF_LIFE.millis();
+        u32::try_from(half_lives)
+            .ok()
+            .and_then(|half_lives| self.offense_penalty.checked_shr(half_lives))
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the peer is blocked at the given time.
+    fn is_blocked(&self, now: Timestamp) -> bool {
+        matches!(self.blocked_until, Some(blocked_until) if now < blocked_until)
+    }
+}
+
+/// Returns the reputation penalty for an offense.
+///
+/// Peers on a different network or chainspec will never be useful, so they are penalized the most.
+fn offense_penalty(justification: &BlocklistJustification) -> u64 {
+    match justification {
+        BlocklistJustification::WrongNetwork { .. }
+        | BlocklistJustification::WrongChainspecHash { .. }
+        | BlocklistJustification::MissingChainspecHash => 1000,
+        BlocklistJustification::DishonestPeer
+        | BlocklistJustification::BadConsensusBehavior
+        | BlocklistJustification::SentInvalidConsensusValue { .. } => 500,
+        BlocklistJustification::SentBadItem { .. }
+        | BlocklistJustification::SentInvalidItem { .. }
+        | BlocklistJustification::SentBadFinalitySignature { .. }
+        | BlocklistJustification::SentBadBlock { .. }
+        | BlocklistJustification::PongLimitExceeded
+        | BlocklistJustification::SentTooManyFinalitySignatures { .. }
+        | BlocklistJustification::ExceededMessageQuota { .. }
+        | BlocklistJustification::BlockedBeforeRestart { .. } => 200,
+    }
+}
+
+/// The peer database.
+#[derive(Debug)]
+pub(super) struct PeerDb {
+    /// File the database is persisted to, if any.
+    path: Option<PathBuf>,
+    /// Records by peer address.
+    peers: BTreeMap<SocketAddr, PeerRecord>,
+    /// Whether there are changes not yet persisted.
+    dirty: bool,
+    /// When the database was last persisted.
+    last_persisted: Option<Instant>,
+    /// Number of snapshots taken so far.
+    generation: u64,
+    /// Generation of the most recent snapshot written to disk, shared with pending writes.
+    written_generation: Arc<Mutex<u64>>,
+}
+
+/// A snapshot of the peer database, to be written to disk.
+#[derive(Debug)]
+pub(super) struct PeerDbWrite {
+    /// File to write to.
+    path: PathBuf,
+    /// The serialized database.
+    bytes: Vec<u8>,
+    /// Generation of the snapshot.
+    generation: u64,
+    /// Generation of the most recent snapshot written to disk.
+    written_generation: Arc<Mutex<u64>>,
+}
+
+impl PeerDbWrite {
+    /// Writes the snapshot on a thread dedicated to blocking operations.
+    pub(super) async fn run_blocking(self) {
+        if let Err(ref error) = tokio::task::spawn_blocking(move || self.run()).await {
+            warn!(
+                error = display_error(error),
+                "failed to join peer database write"
+            );
+        }
+    }
+
+    /// Writes the snapshot to a temporary file, then moves it in place, logging any error.
+    ///
+    /// Performs blocking I/O. Does nothing if a more recent snapshot has been written already.
+    pub(super) fn run(self) {
+        // Holding the lock throughout ensures concurrent writes don't overwrite each other.
+        let mut written_generation = self
+            .written_generation
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *written_generation >= self.generation {
+            debug!(path=%self.path.display(), "skipping outdated peer database write");
+            return;
+        }
+
+        let temp_path = self.path.with_extension("json.tmp");
+        match fs::write(&temp_path, &self.bytes).and_then(|()| fs::rename(&temp_path, &self.path)) {
+            Ok(()) => {
+                debug!(path=%self.path.display(), "persisted peer database");
+                *written_generation = self.generation;
+            }
+            Err(ref error) => {
+                warn!(path=%self.path.display(), error=display_error(error), "could not persist peer database");
+            }
+        }
+    }
+}
+
+impl PeerDb {
+    /// Creates a peer database which is not persisted.
+    pub(super) fn in_memory() -> Self {
+        PeerDb {
+            path: None,
+            peers: BTreeMap::new(),
+            dirty: false,
+            last_persisted: None,
+            generation: 0,
+            written_generation: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Loads the peer database from the given file.
+    ///
+    /// Starts with an empty database if the file does not exist or cannot be read.
+    pub(super) fn load(path: PathBuf) -> Self {
+        let peers = match fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice(&bytes) {
+                Ok(peers) => peers,
+                Err(ref error) => {
+                    warn!(path=%path.display(), error=display_error(error), "could not parse peer database, starting with an empty one");
+                    BTreeMap::new()
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(ref error) => {
+                warn!(path=%path.display(), error=display_error(error), "could not read peer database, starting with an empty one");
+                BTreeMap::new()
+            }
+        };
+        info!(path=%path.display(), peers=peers.len(), "loaded peer database");
+
+        PeerDb {
+            path: Some(path),
+            peers,
+            dirty: false,
+            last_persisted: None,
+            generation: 0,
+            written_generation: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Returns up to `count` addresses of peers that are not blocked, best reputation first.
+    ///
+    /// Only peers we have successfully connected to before and whose reputation is positive are
+    /// considered.
+    pub(super) fn preferred_addrs(&self, now: Timestamp, count: usize) -> Vec<SocketAddr> {
+        let mut candidates: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, record)| {
+                record.successful_sessions > 0
+                    && record.reputation(now) > 0
+                    && !record.is_blocked(now)
+            })
+            .collect();
+        candidates.sort_by_key(|(_, record)| Reverse((record.reputation(now), record.last_seen)));
+        candidates
+            .into_iter()
+            .take(count)
+            .map(|(addr, _)| *addr)
+            .collect()
+    }
+
+    /// Returns the addresses blocked at the given time, with the remaining duration of their block
+    /// and the reason they were blocked for.
+    pub(super) fn blocked_addrs(
+        &self,
+        now: Timestamp,
+    ) -> impl Iterator<Item = (SocketAddr, TimeDiff, String)> + '_ {
+        self.peers.iter().filter_map(move |(addr, record)| {
+            let blocked_until = record.blocked_until.filter(|_| record.is_blocked(now))?;
+            Some((
+                *addr,
+                blocked_until.saturating_diff(now),
+                record.last_offense.clone().unwrap_or_default(),
+            ))
+        })
+    }
+
+    /// Records a successfully established connection.
+    pub(super) fn record_session(&mut self, addr: SocketAddr, now: Timestamp) {
+        let record = self.peers.entry(addr).or_default();
+        record.last_seen = Some(now);
+        record.successful_sessions = record.successful_sessions.saturating_add(1);
+        record.consecutive_failures = 0;
+        self.dirty = true;
+    }
+
+    /// Records a failed connection attempt to a peer we have connected to before.
+    pub(super) fn record_failure(&mut self, addr: SocketAddr) {
+        if let Some(record) = self.peers.get_mut(&addr) {
+            record.consecutive_failures = record.consecutive_failures.saturating_add(1);
+            self.dirty = true;
+        }
+    }
+
+    /// Records a peer being blocked at `now` until the given time.
+    pub(super) fn record_offense(
+        &mut self,
+        addr: SocketAddr,
+        justification: &BlocklistJustification,
+        now: Timestamp,
+        blocked_until: Timestamp,
+    ) {
+        let record = self.peers.entry(addr).or_default();
+        record.offense_penalty = record
+            .offense_penalty(now)
+            .saturating_add(offense_penalty(justification));
+        record.last_offense = Some(justification.to_string());
+        record.last_offense_time = Some(now);
+        record.blocked_until = Some(blocked_until);
+        self.dirty = true;
+    }
+
+    /// Records a round-trip time measured to a peer.
+    pub(super) fn record_round_trip_time(&mut self, addr: SocketAddr, rtt: Duration) {
+        if let Some(record) = self.peers.get_mut(&addr) {
+            let millis = u64::try_from(rtt.as_millis()).unwrap_or(u64::MAX);
+            // Smooth out the measurements, weighting the previous value 3:1.
+            record.round_trip_time_millis = Some(match record.round_trip_time_millis {
+                Some(previous) => previous.saturating_mul(3).saturating_add(millis) / 4,
+                None => millis,
+            });
+            self.dirty = true;
+        }
+    }
+
+    /// Returns a snapshot to be written to disk if the database has changed and was not persisted
+    /// in a while.
+    pub(super) fn persist_if_due(&mut self, now: Instant) -> Option<PeerDbWrite> {
+        let is_due = self.last_persisted.map_or(true, |last_persisted| {
+            now.saturating_duration_since(last_persisted) >= PERSIST_INTERVAL
+        });
+        if !is_due {
+            return None;
+        }
+        let write = self.persist()?;
+        self.last_persisted = Some(now);
+        Some(write)
+    }
+
+    /// Returns a snapshot to be written to disk if the database has changed.
+    pub(super) fn persist(&mut self) -> Option<PeerDbWrite> {
+        let path = self.path.clone()?;
+        if !self.dirty {
+            return None;
+        }
+
+        self.prune();
+        let bytes = match serde_json::to_vec_pretty(&self.peers) {
+            Ok(bytes) => bytes,
+            Err(ref error) => {
+                warn!(
+                    error = display_error(error),
+                    "could not serialize peer database"
+                );
+                return None;
+            }
+        };
+        self.dirty = false;
+        self.generation += 1;
+        Some(PeerDbWrite {
+            path,
+            bytes,
+            generation: self.generation,
+            written_generation: self.written_generation.clone(),
+        })
+    }
+
+    /// Removes the records with the worst reputation beyond `MAX_PEER_RECORDS`.
+    fn prune(&mut self) {
+        let excess = self.peers.len().saturating_sub(MAX_PEER_RECORDS);
+        if excess == 0 {
+            return;
+        }
+        let now = Timestamp::now();
+        let mut removable: Vec<_> = self
+            .peers
+            .iter()
+            .filter(|(_, record)| !record.is_blocked(now))
+            .map(|(addr, record)| (record.reputation(now), record.last_seen, *addr))
+            .collect();
+        removable.sort();
+        for (_, _, addr) in removable.into_iter().take(excess) {
+            self.peers.remove(&addr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    #[test]
+    fn should_persist_and_reload_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PEER_DB_FILENAME);
+        let now = Timestamp::from(1_000_000);
+
+        let mut peer_db = PeerDb::load(path.clone());
+        peer_db.record_session(addr(1), now);
+        peer_db.record_round_trip_time(addr(1), Duration::from_millis(120));
+        peer_db.record_offense(
+            addr(2),
+            &BlocklistJustification::DishonestPeer,
+            now,
+            now + TimeDiff::from_seconds(600),
+        );
+        let outdated_write = peer_db.persist().expect("should have changes to persist");
+        assert!(peer_db.persist().is_none());
+
+        peer_db.record_session(addr(3), now);
+        peer_db
+            .persist()
+            .expect("should have changes to persist")
+            .run();
+
+        // Writing an older snapshot after a newer one has no effect.
+        outdated_write.run();
+
+        let reloaded = PeerDb::load(path);
+        assert_eq!(reloaded.peers, peer_db.peers);
+        assert!(!reloaded.dirty);
+    }
+
+    #[test]
+    fn should_prefer_reliable_peers() {
+        let now = Timestamp::from(1_000_000);
+        let mut peer_db = PeerDb::in_memory();
+
+        // A peer with many sessions, one with a single session, and one with high latency.
+        for _ in 0..5 {
+            peer_db.record_session(addr(1), now);
+        }
+        peer_db.record_session(addr(2), now);
+        peer_db.record_session(addr(3), now);
+        peer_db.record_session(addr(3), now);
+        peer_db.record_round_trip_time(addr(3), Duration::from_millis(300));
+
+        // A peer failing to connect since its last session.
+        peer_db.record_session(addr(4), now);
+        peer_db.record_failure(addr(4));
+
+        assert_eq!(peer_db.preferred_addrs(now, 2), vec![addr(1), addr(2)]);
+        assert_eq!(peer_db.preferred_addrs(now, 10).len(), 3);
+    }
+
+    #[test]
+    fn should_exclude_blocked_peers_until_block_expires() {
+        let now = Timestamp::from(1_000_000);
+        let blocked_until = now + TimeDiff::from_seconds(60);
+        let mut peer_db = PeerDb::in_memory();
+        for _ in 0..50 {
+            peer_db.record_session(addr(1), now);
+        }
+        peer_db.record_offense(
+            addr(1),
+            &BlocklistJustification::DishonestPeer,
+            now,
+            blocked_until,
+        );
+
+        assert!(peer_db.preferred_addrs(now, 10).is_empty());
+        let blocked: Vec<_> = peer_db.blocked_addrs(now).collect();
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].0, addr(1));
+        assert_eq!(blocked[0].1, TimeDiff::from_seconds(60));
+
+        assert_eq!(peer_db.blocked_addrs(blocked_until).count(), 0);
+    }
+
+    #[test]
+    fn should_decay_offense_penalty_over_time() {
+        let now = Timestamp::from(1_000_000);
+        let mut peer_db = PeerDb::in_memory();
+        for _ in 0..10 {
+            peer_db.record_session(addr(1), now);
+        }
+        peer_db.record_offense(
+            addr(1),
+            &BlocklistJustification::SentBadItem {
+                tag: crate::components::fetcher::Tag::Deploy,
+            },
+            now,
+            now + TimeDiff::from_seconds(60),
+        );
+        let record = &peer_db.peers[&addr(1)];
+        assert_eq!(record.reputation(now), 100 - 200);
+
+        let later = now + OFFENSE_PENALTY_HALF_LIFE;
+        assert_eq!(record.reputation(later), 100 - 100);
+        assert!(peer_db.preferred_addrs(later, 10).is_empty());
+
+        let much_later = later + OFFENSE_PENALTY_HALF_LIFE + OFFENSE_PENALTY_HALF_LIFE;
+        assert_eq!(record.reputation(much_later), 100 - 25);
+        assert_eq!(peer_db.preferred_addrs(much_later, 10), vec![addr(1)]);
+
+        // A new offense adds to the decayed penalty.
+        peer_db.record_offense(
+            addr(1),
+            &BlocklistJustification::DishonestPeer,
+            much_later,
+            much_later + TimeDiff::from_seconds(60),
+        );
+        assert_eq!(
+            peer_db.peers[&addr(1)].offense_penalty(much_later),
+            25 + 500
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Operator-managed peer groups.
+//!
+//! Static peers are addresses the node always keeps a connection to: they are never forgotten and
+//! never blocked. The allowlist restricts connections in both directions to a fixed set of node
+//! IDs, which allows a validator to only talk to its own sentry nodes.
+//!
+//! Both groups are seeded from the configuration and can be modified at runtime through the
+//! diagnostics port. Changes to the allowlist only apply to connections established afterwards.
+
+use std::{
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+    net::SocketAddr,
+};
+
+use serde::Serialize;
+
+use crate::types::NodeId;
+
+/// The static peers and node ID allowlist of the networking component.
+#[derive(Clone, Debug, Default, Serialize)]
+pub(crate) struct PeerGroups {
+    /// Addresses which are always connected to and never blocked.
+    static_peers: BTreeSet<SocketAddr>,
+    /// Node IDs allowed to connect if `allowlist_only` is set.
+    allowed_nodes: BTreeSet<NodeId>,
+    /// Whether connections to or from nodes not on the allowlist are rejected.
+    allowlist_only: bool,
+}
+
+/// A runtime modification of the [`PeerGroups`].
+#[derive(Clone, Debug, Serialize)]
+pub(crate) enum PeerGroupChange {
+    /// Adds an address to the static peers.
+    AddStaticPeer(SocketAddr),
+    /// Removes an address from the static peers.
+    RemoveStaticPeer(SocketAddr),
+    /// Adds a node to the allowlist.
+    AllowNode(NodeId),
+    /// Removes a node from the allowlist.
+    DisallowNode(NodeId),
+    /// Enables or disables allowlist-only mode.
+    SetAllowlistOnly(bool),
+}
+
+impl PeerGroups {
+    /// Creates a new set of peer groups.
+    pub(super) fn new(
+        static_peers: BTreeSet<SocketAddr>,
+        allowed_nodes: BTreeSet<NodeId>,
+        allowlist_only: bool,
+    ) -> Self {
+        PeerGroups {
+            static_peers,
+            allowed_nodes,
+            allowlist_only,
+        }
+    }
+
+    /// Returns the static peers.
+    pub(super) fn static_peers(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        self.static_peers.iter().copied()
+    }
+
+    /// Returns whether the given address is a static peer.
+    pub(super) fn is_static(&self, addr: &SocketAddr) -> bool {
+        self.static_peers.contains(addr)
+    }
+
+    /// Returns whether allowlist-only mode is enabled.
+    pub(super) fn allowlist_only(&self) -> bool {
+        self.allowlist_only
+    }
+
+    /// Returns whether a connection to or from the given node is permitted.
+    pub(super) fn is_allowed(&self, node_id: &NodeId) -> bool {
+        !self.allowlist_only || self.allowed_nodes.contains(node_id)
+    }
+
+    /// Applies a change, returning whether the groups were modified.
+    pub(super) fn apply(&mut self, change: &PeerGroupChange) -> bool {
+        match *change {
+            PeerGroupChange::AddStaticPeer(addr) => self.static_peers.insert(addr),
+            PeerGroupChange::RemoveStaticPeer(addr) => self.static_peers.remove(&addr),
+            PeerGroupChange::AllowNode(node_id) => self.allowed_nodes.insert(node_id),
+            PeerGroupChange::DisallowNode(node_id) => self.allowed_nodes.remove(&node_id),
+            PeerGroupChange::SetAllowlistOnly(allowlist_only) => {
+                let changed = self.allowlist_only != allowlist_only;
+                self.allowlist_only = allowlist_only;
+                changed
+            }
+        }
+    }
+}
+
+impl Display for PeerGroups {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "static peers:")?;
+        for addr in &self.static_peers {
+            writeln!(f, "  {}", addr)?;
+        }
+        writeln!(
+            f,
+            "allowlist ({}):",
+            if self.allowlist_only {
+                "enforced"
+            } else {
+                "not enforced"
+            }
+        )?;
+        for node_id in &self.allowed_nodes {
+            writeln!(f, "  {}", base16::encode_lower(node_id.hash_bytes()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for PeerGroupChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PeerGroupChange::AddStaticPeer(addr) => write!(f, "add static peer {}", addr),
+            PeerGroupChange::RemoveStaticPeer(addr) => write!(f, "remove static peer {}", addr),
+            PeerGroupChange::AllowNode(node_id) => write!(f, "allow node {}", node_id),
+            PeerGroupChange::DisallowNode(node_id) => write!(f, "disallow node {}", node_id),
+            PeerGroupChange::SetAllowlistOnly(allowlist_only) => {
+                write!(f, "set allowlist-only mode to {}", allowlist_only)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist_is_only_enforced_when_enabled() {
+        let mut rng = crate::new_rng();
+        let allowed = NodeId::random(&mut rng);
+        let unknown = NodeId::random(&mut rng);
+
+        let mut peer_groups =
+            PeerGroups::new(BTreeSet::new(), [allowed].into_iter().collect(), false);
+        assert!(peer_groups.is_allowed(&allowed));
+        assert!(peer_groups.is_allowed(&unknown));
+
+        assert!(peer_groups.apply(&PeerGroupChange::SetAllowlistOnly(true)));
+        assert!(peer_groups.is_allowed(&allowed));
+        assert!(!peer_groups.is_allowed(&unknown));
+
+        assert!(peer_groups.apply(&PeerGroupChange::AllowNode(unknown)));
+        assert!(peer_groups.is_allowed(&unknown));
+        assert!(peer_groups.apply(&PeerGroupChange::DisallowNode(allowed)));
+        assert!(!peer_groups.is_allowed(&allowed));
+    }
+
+    #[test]
+    fn static_peers_can_be_changed() {
+        let addr: SocketAddr = "10.0.0.1:34553".parse().unwrap();
+        let mut peer_groups = PeerGroups::default();
+
+        assert!(!peer_groups.is_static(&addr));
+        assert!(peer_groups.apply(&PeerGroupChange::AddStaticPeer(addr)));
+        assert!(!peer_groups.apply(&PeerGroupChange::AddStaticPeer(addr)));
+        assert!(peer_groups.is_static(&addr));
+        assert!(peer_groups.apply(&PeerGroupChange::RemoveStaticPeer(addr)));
+        assert!(!peer_groups.is_static(&addr));
+    }
+}
@@ -0,0 +1,603 @@
+//! QUIC transport.
+//!
+//! As an alternative to TLS over TCP, nodes can connect via QUIC.  QUIC recovers from packet loss
+//! without stalling the whole connection, which greatly improves throughput on lossy long-distance
+//! links, and reconnecting to a peer resumes the previous session.  No data is sent in 0-RTT
+//! though, as the connection ID, which the protocol handshake relies on, is derived from keying
+//! material only available once the TLS handshake has completed.
+//!
+//! The TLS 1.3 handshake embedded in QUIC is carried out by `rustls`, which cannot sign handshakes
+//! on the curve of our node certificates.  Each endpoint therefore secures its connections with an
+//! ephemeral P-256 certificate, which is accepted without further checks.  Once the handshake has
+//! completed, both nodes prove their identity by sending their node certificate along with a
+//! signature over keying material exported from the connection, which binds the identity to the
+//! TLS session.  The node certificates are then validated the same way as on TCP connections.
+//! The handshake is carried out on a bidirectional QUIC stream, just like on a TLS stream, while
+//! messages are then sent on a unidirectional stream per channel, see [`super::multiplex`].
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+
+use openssl::{
+    asn1::Asn1Time,
+    ec::{EcGroup, EcKey},
+    error::ErrorStack,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Private},
+    sign::{Signer as OpenSslSigner, Verifier as OpenSslVerifier},
+    x509::{X509NameBuilder, X509},
+};
+use quinn::{Connection, Endpoint, RecvStream, SendStream};
+use rustls::{
+    client::{HandshakeSignatureValid, ResolvesClientCert, ServerCertVerified, ServerCertVerifier},
+    server::{ClientCertVerified, ClientCertVerifier, ClientHello, ResolvesServerCert},
+    sign::{CertifiedKey, Signer, SigningKey},
+    Certificate, CertificateError, DigitallySignedStruct, DistinguishedName, ServerName,
+    SignatureAlgorithm, SignatureScheme,
+};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+
+use super::{
+    error::{ConnectionError, Error},
+    multiplex::Channel,
+};
+
+/// ALPN protocol identifier of node-to-node connections.
+const ALPN_PROTOCOL: &[u8] = b"casper-node";
+
+/// Server name used when connecting, since peers are identified by their certificates only.
+const SERVER_NAME: &str = "casper-node";
+
+/// The only signature scheme supported by the ephemeral certificates securing connections.
+const SIGNATURE_SCHEME: SignatureScheme = SignatureScheme::ECDSA_NISTP256_SHA256;
+
+/// Validity of the ephemeral certificates securing connections, in days.
+const TRANSPORT_CERT_VALIDITY_DAYS: u32 = 3650;
+
+/// Label of the keying material exported to derive connection IDs.
+const CONNECTION_ID_LABEL: &[u8] = b"casper-node connection id";
+
+/// Label of the keying material signed to prove a node's identity.
+const IDENTITY_LABEL: &[u8] = b"casper-node identity";
+
+/// Context of the keying material signed by the dialing node.
+const DIALER_CONTEXT: &[u8] = b"dialer";
+
+/// Context of the keying material signed by the listening node.
+const LISTENER_CONTEXT: &[u8] = b"listener";
+
+/// Interval at which keep-alive packets are sent on idle connections.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time after which a connection without any received packets is closed.
+const MAX_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Signs TLS handshakes with the ephemeral key of an endpoint.
+#[derive(Debug)]
+struct TransportSigningKey(Arc<PKey<Private>>);
+
+impl SigningKey for TransportSigningKey {
+    fn choose_scheme(&self, offered: &[SignatureScheme]) -> Option<Box<dyn Signer>> {
+        if offered.contains(&SIGNATURE_SCHEME) {
+            Some(Box::new(TransportSigner(self.0.clone())))
+        } else {
+            None
+        }
+    }
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::ECDSA
+    }
+}
+
+#[derive(Debug)]
+struct TransportSigner(Arc<PKey<Private>>);
+
+impl Signer for TransportSigner {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, rustls::Error> {
+        sign(MessageDigest::sha256(), &self.0, message)
+            .map_err(|error| rustls::Error::General(error.to_string()))
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        SIGNATURE_SCHEME
+    }
+}
+
+/// Presents our ephemeral certificate to peers, and verifies the handshake signatures of theirs.
+///
+/// Peer certificates themselves are accepted here, as peers prove their node identity only after
+/// the handshake.
+struct TransportCertificates {
+    /// Our certificate along with the key signing handshakes.
+    certified_key: Arc<CertifiedKey>,
+}
+
+impl Debug for TransportCertificates {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TransportCertificates")
+            .finish_non_exhaustive()
+    }
+}
+
+impl TransportCertificates {
+    /// Creates a new ephemeral certificate.
+    fn generate() -> Result<Self, ErrorStack> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+        let secret_key = PKey::from_ec_key(EcKey::generate(&group)?)?;
+
+        let mut name = X509NameBuilder::new()?;
+        name.append_entry_by_text("CN", SERVER_NAME)?;
+        let name = name.build();
+
+        let mut builder = X509::builder()?;
+        builder.set_version(2)?;
+        builder.set_subject_name(&name)?;
+        builder.set_issuer_name(&name)?;
+        builder.set_pubkey(&secret_key)?;
+        builder.set_not_before(Asn1Time::days_from_now(0)?.as_ref())?;
+        builder.set_not_after(Asn1Time::days_from_now(TRANSPORT_CERT_VALIDITY_DAYS)?.as_ref())?;
+        builder.sign(&secret_key, MessageDigest::sha256())?;
+        let cert = builder.build();
+
+        Ok(TransportCertificates {
+            certified_key: Arc::new(CertifiedKey::new(
+                vec![Certificate(cert.to_der()?)],
+                Arc::new(TransportSigningKey(Arc::new(secret_key))),
+            )),
+        })
+    }
+
+    /// Verifies that `signature` over `message` was created with the key of `cert`.
+    fn verify_signature(
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        if dss.scheme != SIGNATURE_SCHEME {
+            return Err(rustls::Error::General(format!(
+                "unsupported signature scheme {:?}",
+                dss.scheme
+            )));
+        }
+        let valid = X509::from_der(&cert.0)
+            .and_then(|cert| verify(MessageDigest::sha256(), &cert, message, dss.signature()))
+            .unwrap_or(false);
+        if valid {
+            Ok(HandshakeSignatureValid::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::BadSignature,
+            ))
+        }
+    }
+}
+
+impl ResolvesServerCert for TransportCertificates {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.certified_key.clone())
+    }
+}
+
+impl ResolvesClientCert for TransportCertificates {
+    fn resolve(
+        &self,
+        _acceptable_issuers: &[&[u8]],
+        sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        sigschemes
+            .contains(&SIGNATURE_SCHEME)
+            .then(|| self.certified_key.clone())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+impl ServerCertVerifier for TransportCertificates {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Self::verify_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        vec![SIGNATURE_SCHEME]
+    }
+}
+
+impl ClientCertVerifier for TransportCertificates {
+    fn client_auth_root_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _now: SystemTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Self::verify_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        vec![SIGNATURE_SCHEME]
+    }
+}
+
+/// Creates a QUIC endpoint bound to `bind_address`, accepting connections and used for dialing.
+pub(super) fn create_endpoint(bind_address: SocketAddr) -> Result<Endpoint, Error> {
+    let certificates = Arc::new(TransportCertificates::generate().map_err(Error::QuicCertificate)?);
+
+    let mut transport_config = quinn::TransportConfig::default();
+    transport_config.keep_alive_interval(Some(KEEP_ALIVE_INTERVAL));
+    // Peers open at most one unidirectional stream per channel.
+    transport_config.max_concurrent_uni_streams(
+        u32::try_from(Channel::COUNT)
+            .expect("channel count should fit into u32")
+            .into(),
+    );
+    transport_config.max_idle_timeout(Some(
+        MAX_IDLE_TIMEOUT
+            .try_into()
+            .expect("idle timeout should be in range"),
+    ));
+    let transport_config = Arc::new(transport_config);
+
+    let mut server_crypto = rustls::ServerConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(Error::QuicTlsConfiguration)?
+        .with_client_cert_verifier(certificates.clone())
+        .with_cert_resolver(certificates.clone());
+    server_crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+    let mut server_config = quinn::ServerConfig::with_crypto(Arc::new(server_crypto));
+    server_config.transport_config(transport_config.clone());
+
+    let mut client_crypto = rustls::ClientConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .map_err(Error::QuicTlsConfiguration)?
+        .with_custom_certificate_verifier(certificates.clone())
+        .with_client_cert_resolver(certificates);
+    client_crypto.alpn_protocols = vec![ALPN_PROTOCOL.to_vec()];
+    let mut client_config = quinn::ClientConfig::new(Arc::new(client_crypto));
+    client_config.transport_config(transport_config);
+
+    let mut endpoint = Endpoint::server(server_config, bind_address)
+        .map_err(|error| Error::QuicEndpoint(error, bind_address))?;
+    endpoint.set_default_client_config(client_config);
+    Ok(endpoint)
+}
+
+/// Connects to a peer, resuming a previous session with it if possible.
+///
+/// Only returns once the handshake has completed and both nodes have proven their identity.
+/// Returns the stream along with the node certificate of the peer, which has not been validated
+/// yet.
+pub(super) async fn connect(
+    endpoint: &Endpoint,
+    peer_addr: SocketAddr,
+    our_cert: &X509,
+    secret_key: &PKey<Private>,
+) -> Result<(X509, QuicStream), ConnectionError> {
+    let connection = endpoint
+        .connect(peer_addr, SERVER_NAME)
+        .map_err(ConnectionError::QuicConnect)?
+        .await
+        .map_err(ConnectionError::QuicConnection)?;
+
+    // The peer only learns about the stream once we write to it, so we have to go first.
+    let (mut send, mut recv) = connection
+        .open_bi()
+        .await
+        .map_err(ConnectionError::QuicConnection)?;
+    send_identity(&connection, &mut send, DIALER_CONTEXT, our_cert, secret_key).await?;
+    let peer_cert = receive_identity(&connection, &mut recv, LISTENER_CONTEXT).await?;
+
+    Ok((
+        peer_cert,
+        QuicStream {
+            connection,
+            send,
+            recv,
+        },
+    ))
+}
+
+/// Completes an incoming connection and accepts the stream opened by the peer.
+///
+/// Returns the stream along with the node certificate of the peer, which has not been validated
+/// yet.
+pub(super) async fn accept(
+    connecting: quinn::Connecting,
+    our_cert: &X509,
+    secret_key: &PKey<Private>,
+) -> Result<(X509, QuicStream), ConnectionError> {
+    let connection = connecting.await.map_err(ConnectionError::QuicConnection)?;
+    let (mut send, mut recv) = connection
+        .accept_bi()
+        .await
+        .map_err(ConnectionError::QuicConnection)?;
+    let peer_cert = receive_identity(&connection, &mut recv, DIALER_CONTEXT).await?;
+    send_identity(
+        &connection,
+        &mut send,
+        LISTENER_CONTEXT,
+        our_cert,
+        secret_key,
+    )
+    .await?;
+
+    Ok((
+        peer_cert,
+        QuicStream {
+            connection,
+            send,
+            recv,
+        },
+    ))
+}
+
+/// Exports the keying material a node signs to prove its identity.
+///
+/// The material differs between the two nodes, as signatures could be reflected otherwise.
+fn identity_material(connection: &Connection, context: &[u8]) -> Result<[u8; 32], ConnectionError> {
+    let mut material = [0; 32];
+    connection
+        .export_keying_material(&mut material, IDENTITY_LABEL, context)
+        .map_err(|_| ConnectionError::QuicKeyingMaterial)?;
+    Ok(material)
+}
+
+/// Proves our node identity to the peer.
+///
+/// Sends our node certificate followed by a signature over keying material of the connection,
+/// each prefixed with its length.
+async fn send_identity(
+    connection: &Connection,
+    send: &mut SendStream,
+    context: &[u8],
+    our_cert: &X509,
+    secret_key: &PKey<Private>,
+) -> Result<(), ConnectionError> {
+    let material = identity_material(connection, context)?;
+    let cert = our_cert.to_der().map_err(ConnectionError::QuicIdentity)?;
+    let signature = sign(MessageDigest::sha512(), secret_key, &material)
+        .map_err(ConnectionError::QuicIdentity)?;
+
+    let mut message = Vec::with_capacity(4 + cert.len() + signature.len());
+    for field in [&cert, &signature] {
+        let length = u16::try_from(field.len()).expect("identity field should fit into u16");
+        message.extend_from_slice(&length.to_be_bytes());
+        message.extend_from_slice(field);
+    }
+    send.write_all(&message)
+        .await
+        .map_err(|error| ConnectionError::QuicIdentityExchange(error.into()))
+}
+
+/// Receives the node certificate of the peer, checking that it signed the keying material.
+async fn receive_identity(
+    connection: &Connection,
+    recv: &mut RecvStream,
+    context: &[u8],
+) -> Result<X509, ConnectionError> {
+    async fn read_field(recv: &mut RecvStream) -> io::Result<Vec<u8>> {
+        let length = recv.read_u16().await?;
+        let mut field = vec![0; usize::from(length)];
+        AsyncReadExt::read_exact(recv, &mut field).await?;
+        Ok(field)
+    }
+
+    let cert = read_field(recv)
+        .await
+        .map_err(ConnectionError::QuicIdentityExchange)?;
+    let signature = read_field(recv)
+        .await
+        .map_err(ConnectionError::QuicIdentityExchange)?;
+
+    let peer_cert = X509::from_der(&cert).map_err(ConnectionError::QuicPeerCertificate)?;
+    let material = identity_material(connection, context)?;
+    if !verify(MessageDigest::sha512(), &peer_cert, &material, &signature).unwrap_or(false) {
+        return Err(ConnectionError::QuicIdentityInvalid);
+    }
+    Ok(peer_cert)
+}
+
+/// Signs `message` with `secret_key`.
+fn sign(
+    digest: MessageDigest,
+    secret_key: &PKey<Private>,
+    message: &[u8],
+) -> Result<Vec<u8>, ErrorStack> {
+    let mut signer = OpenSslSigner::new(digest, secret_key)?;
+    signer.update(message)?;
+    signer.sign_to_vec()
+}
+
+/// Checks that `signature` over `message` was created with the key of `cert`.
+fn verify(
+    digest: MessageDigest,
+    cert: &X509,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, ErrorStack> {
+    let public_key = cert.public_key()?;
+    let mut verifier = OpenSslVerifier::new(digest, &public_key)?;
+    verifier.update(message)?;
+    verifier.verify(signature)
+}
+
+/// A bidirectional stream of a QUIC connection to a peer.
+#[derive(Debug)]
+pub(crate) struct QuicStream {
+    /// The connection the stream belongs to, kept to close it once the stream is dropped.
+    connection: Connection,
+    send: SendStream,
+    recv: RecvStream,
+}
+
+impl QuicStream {
+    /// Returns the connection the stream belongs to.
+    pub(super) fn connection(&self) -> Connection {
+        self.connection.clone()
+    }
+
+    /// Exports keying material which is the same on both ends of the connection.
+    ///
+    /// Fails if the handshake of the connection has not completed yet.
+    pub(super) fn keying_material(&self) -> Result<[u8; 12], ConnectionError> {
+        let mut material = [0; 12];
+        self.connection
+            .export_keying_material(&mut material, CONNECTION_ID_LABEL, &[])
+            .map_err(|_| ConnectionError::QuicKeyingMaterial)?;
+        Ok(material)
+    }
+}
+
+impl Drop for QuicStream {
+    fn drop(&mut self) {
+        self.connection.close(0u32.into(), b"closed");
+    }
+}
+
+impl AsyncRead for QuicStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.recv).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for QuicStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.send).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.send).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls;
+
+    #[tokio::test]
+    async fn should_exchange_node_identities_and_export_same_keying_material() {
+        let (server_cert, server_key) = tls::generate_node_cert().unwrap();
+        let (client_cert, client_key) = tls::generate_node_cert().unwrap();
+        let server = create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        // The second connection resumes the session of the first one.
+        for _ in 0..2 {
+            let accepting = async {
+                let connecting = server.accept().await.unwrap();
+                accept(connecting, &server_cert, &server_key).await.unwrap()
+            };
+            let connecting = async {
+                connect(&client, server_addr, &client_cert, &client_key)
+                    .await
+                    .unwrap()
+            };
+            let ((peer_of_server, server_stream), (peer_of_client, client_stream)) =
+                tokio::join!(accepting, connecting);
+
+            assert_eq!(peer_of_server, client_cert);
+            assert_eq!(peer_of_client, server_cert);
+            assert_eq!(
+                server_stream.keying_material().unwrap(),
+                client_stream.keying_material().unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_reject_identity_not_bound_to_connection() {
+        let (server_cert, server_key) = tls::generate_node_cert().unwrap();
+        let (client_cert, client_key) = tls::generate_node_cert().unwrap();
+        let server = create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = create_endpoint(([127, 0, 0, 1], 0).into()).unwrap();
+        let server_addr = server.local_addr().unwrap();
+
+        let accepting = async {
+            let connecting = server.accept().await.unwrap();
+            accept(connecting, &server_cert, &server_key).await
+        };
+        let connecting = async {
+            let connection = client
+                .connect(server_addr, SERVER_NAME)
+                .unwrap()
+                .await
+                .unwrap();
+            let (mut send, _recv) = connection.open_bi().await.unwrap();
+            // Signing the keying material meant for the listener cannot prove our identity.
+            send_identity(
+                &connection,
+                &mut send,
+                LISTENER_CONTEXT,
+                &client_cert,
+                &client_key,
+            )
+            .await
+            .unwrap();
+            connection
+        };
+        let (accepted, _connection) = tokio::join!(accepting, connecting);
+
+        assert!(matches!(
+            accepted,
+            Err(ConnectionError::QuicIdentityInvalid)
+        ));
+    }
+}
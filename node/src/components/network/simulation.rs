@@ -0,0 +1,473 @@
+//! Simulated link conditions for networking tests.
+//!
+//! A [`NetworkSimulator`] is shared by all nodes of a test network and decides the fate of every
+//! message sent between them: whether it is lost, and by how much its delivery is delayed due to
+//! latency and limited bandwidth.  Nodes can be split into partitions which cannot reach each
+//! other until healed, either directly or via a script of timed steps.
+//!
+//! All random decisions are drawn from a single RNG seeded on creation, so a test sending the same
+//! sequence of messages experiences the same conditions when rerun with the same seed.  Delivery
+//! times are taken from the tokio clock, so with time paused they are fully deterministic as well.
+//!
+//! While a simulator is [installed](NetworkSimulator::install) on the current thread, every
+//! outgoing connection established on it is routed over a [simulated link](simulated_link).
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use prometheus::IntGauge;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use tokio::{
+    sync::mpsc::{self, UnboundedSender},
+    task::JoinHandle,
+    time::Instant,
+};
+use tracing::{debug, info};
+
+use super::{tasks::MessageQueueItem, Payload};
+use crate::types::NodeId;
+
+thread_local! {
+    /// The simulator installed on the current thread, if any.
+    static ACTIVE_SIMULATOR: RefCell<Option<NetworkSimulator>> = RefCell::new(None);
+}
+
+/// The conditions of a directed link between two nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct LinkConditions {
+    /// Minimum latency of a message.
+    pub(crate) min_latency: Duration,
+    /// Maximum latency of a message, latencies are drawn uniformly from the range.
+    pub(crate) max_latency: Duration,
+    /// Probability of a message being lost, between 0.0 and 1.0.
+    pub(crate) loss: f64,
+    /// Bandwidth in bytes per second, unlimited if `None`.
+    pub(crate) bandwidth: Option<u64>,
+}
+
+/// A step of a simulation script.
+#[derive(Clone, Debug)]
+pub(crate) enum ScriptStep {
+    /// Splits the nodes into the given groups, see [`NetworkSimulator::partition`].
+    Partition(Vec<Vec<NodeId>>),
+    /// Removes all partitions.
+    Heal,
+    /// Changes the conditions of all links without specific conditions.
+    SetDefaultLink(LinkConditions),
+    /// Changes the conditions of the links between two nodes, in both directions.
+    SetLink(NodeId, NodeId, LinkConditions),
+}
+
+/// What happens to a message sent over a simulated link.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Fate {
+    /// The message is lost.
+    Lost,
+    /// The message is delivered at the given time.
+    DeliverAt(Instant),
+}
+
+/// Timing state of a directed link.
+#[derive(Debug)]
+struct LinkState {
+    /// Time at which the link finishes transmitting the messages sent so far.
+    busy_until: Instant,
+    /// Time at which the last message sent is delivered.
+    last_delivery: Instant,
+}
+
+#[derive(Debug)]
+struct SimulatorState {
+    /// Source of all random decisions.
+    rng: ChaCha20Rng,
+    /// Conditions of links without specific conditions.
+    default_link: LinkConditions,
+    /// Conditions of specific directed links.
+    links: HashMap<(NodeId, NodeId), LinkConditions>,
+    /// Index of the partition of every node which is part of one.
+    partitions: HashMap<NodeId, usize>,
+    /// Timing state of every directed link used so far.
+    link_states: HashMap<(NodeId, NodeId), LinkState>,
+    /// Number of messages lost so far.
+    lost: u64,
+    /// Number of messages delivered so far.
+    delivered: u64,
+}
+
+/// A simulated network, shared by all of its nodes.
+#[derive(Clone, Debug)]
+pub(crate) struct NetworkSimulator(Arc<Mutex<SimulatorState>>);
+
+impl NetworkSimulator {
+    /// Creates a new simulator with perfect links, drawing random decisions from `seed`.
+    pub(crate) fn new(seed: u64) -> Self {
+        NetworkSimulator(Arc::new(Mutex::new(SimulatorState {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+            default_link: LinkConditions::default(),
+            links: HashMap::new(),
+            partitions: HashMap::new(),
+            link_states: HashMap::new(),
+            lost: 0,
+            delivered: 0,
+        })))
+    }
+
+    /// Installs the simulator on the current thread, to be used for all outgoing connections
+    /// established on it until the returned guard is dropped.
+    #[must_use]
+    pub(crate) fn install(&self) -> InstalledSimulator {
+        ACTIVE_SIMULATOR.with(|active| *active.borrow_mut() = Some(self.clone()));
+        InstalledSimulator(())
+    }
+
+    /// Returns the simulator installed on the current thread, if any.
+    pub(crate) fn active() -> Option<Self> {
+        ACTIVE_SIMULATOR.with(|active| active.borrow().clone())
+    }
+
+    /// Sets the conditions of all links without specific conditions.
+    pub(crate) fn set_default_link(&self, conditions: LinkConditions) {
+        self.lock().default_link = conditions;
+    }
+
+    /// Sets the conditions of the links between two nodes, in both directions.
+    pub(crate) fn set_link(&self, node_a: NodeId, node_b: NodeId, conditions: LinkConditions) {
+        let mut state = self.lock();
+        state.links.insert((node_a, node_b), conditions);
+        state.links.insert((node_b, node_a), conditions);
+    }
+
+    /// Splits the nodes into the given groups, replacing any previous partitions.
+    ///
+    /// Messages between nodes in different groups are lost.  Nodes not contained in any group can
+    /// still reach every other node.
+    pub(crate) fn partition<G, I>(&self, groups: G)
+    where
+        G: IntoIterator<Item = I>,
+        I: IntoIterator<Item = NodeId>,
+    {
+        let mut state = self.lock();
+        state.partitions = groups
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, group)| group.into_iter().map(move |node_id| (node_id, index)))
+            .collect();
+        info!(partitions = ?state.partitions, "partitioned simulated network");
+    }
+
+    /// Removes all partitions.
+    pub(crate) fn heal(&self) {
+        self.lock().partitions.clear();
+        info!("healed simulated network");
+    }
+
+    /// Applies a single script step.
+    pub(crate) fn apply(&self, step: ScriptStep) {
+        match step {
+            ScriptStep::Partition(groups) => self.partition(groups),
+            ScriptStep::Heal => self.heal(),
+            ScriptStep::SetDefaultLink(conditions) => self.set_default_link(conditions),
+            ScriptStep::SetLink(node_a, node_b, conditions) => {
+                self.set_link(node_a, node_b, conditions)
+            }
+        }
+    }
+
+    /// Runs a script in the background, applying every step once the given time has passed since
+    /// the script was started.
+    pub(crate) fn run_script(&self, steps: Vec<(Duration, ScriptStep)>) -> JoinHandle<()> {
+        let simulator = self.clone();
+        let start = Instant::now();
+        tokio::spawn(async move {
+            for (offset, step) in steps {
+                tokio::time::sleep_until(start + offset).await;
+                simulator.apply(step);
+            }
+        })
+    }
+
+    /// Decides the fate of a message of `size` bytes sent from `sender` to `recipient` now.
+    ///
+    /// Messages on the same link are always delivered in the order they were sent.
+    pub(crate) fn fate(&self, sender: NodeId, recipient: NodeId, size: u64) -> Fate {
+        let mut state = self.lock();
+
+        let partitioned = match (
+            state.partitions.get(&sender),
+            state.partitions.get(&recipient),
+        ) {
+            (Some(sender_partition), Some(recipient_partition)) => {
+                sender_partition != recipient_partition
+            }
+            _ => false,
+        };
+        let conditions = state
+            .links
+            .get(&(sender, recipient))
+            .copied()
+            .unwrap_or(state.default_link);
+        if partitioned || (conditions.loss > 0.0 && state.rng.gen_bool(conditions.loss.min(1.0))) {
+            state.lost += 1;
+            return Fate::Lost;
+        }
+
+        let latency = if conditions.max_latency > conditions.min_latency {
+            state
+                .rng
+                .gen_range(conditions.min_latency..=conditions.max_latency)
+        } else {
+            conditions.min_latency
+        };
+        let transmission = conditions
+            .bandwidth
+            .map(|bandwidth| Duration::from_secs_f64(size as f64 / bandwidth.max(1) as f64))
+            .unwrap_or_default();
+
+        let now = Instant::now();
+        let link_state = state
+            .link_states
+            .entry((sender, recipient))
+            .or_insert(LinkState {
+                busy_until: now,
+                last_delivery: now,
+            });
+        link_state.busy_until = link_state.busy_until.max(now) + transmission;
+        let delivery = (link_state.busy_until + latency).max(link_state.last_delivery);
+        link_state.last_delivery = delivery;
+        state.delivered += 1;
+
+        Fate::DeliverAt(delivery)
+    }
+
+    /// Returns the number of messages lost so far.
+    pub(crate) fn lost_count(&self) -> u64 {
+        self.lock().lost
+    }
+
+    /// Returns the number of messages delivered so far.
+    pub(crate) fn delivered_count(&self) -> u64 {
+        self.lock().delivered
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SimulatorState> {
+        self.0.lock().expect("simulator lock poisoned")
+    }
+}
+
+/// Guard keeping a simulator installed on the current thread.
+///
+/// Uninstalls the simulator when dropped, including when a test panics.
+#[derive(Debug)]
+pub(crate) struct InstalledSimulator(());
+
+impl Drop for InstalledSimulator {
+    fn drop(&mut self) {
+        ACTIVE_SIMULATOR.with(|active| *active.borrow_mut() = None);
+    }
+}
+
+/// Routes the messages of an outgoing connection from `our_id` to `peer_id` over a simulated link,
+/// if a simulator is installed on the current thread.
+///
+/// Returns the sender to queue messages on, which is `sender` itself without a simulator.
+/// Otherwise, the fate of every message is decided as it enters the link, and a single task per
+/// link hands the messages on to `sender` in order, each at its delivery time.  Lost messages are
+/// removed from the `queued_messages` count.
+pub(super) fn simulated_link<P: Payload>(
+    our_id: NodeId,
+    peer_id: NodeId,
+    sender: UnboundedSender<MessageQueueItem<P>>,
+    queued_messages: IntGauge,
+) -> UnboundedSender<MessageQueueItem<P>> {
+    let simulator = match NetworkSimulator::active() {
+        Some(simulator) => simulator,
+        None => return sender,
+    };
+
+    let (link_sender, mut link_receiver) = mpsc::unbounded_channel::<MessageQueueItem<P>>();
+    tokio::spawn(async move {
+        let mut in_flight = VecDeque::new();
+        let mut is_closed = false;
+        loop {
+            let next_delivery = in_flight.front().map(|(delivery, _)| *delivery);
+            tokio::select! {
+                item = link_receiver.recv(), if !is_closed => match item {
+                    Some(item) => {
+                        let size = bincode::serialized_size(&*item.0).unwrap_or_default();
+                        match simulator.fate(our_id, peer_id, size) {
+                            Fate::Lost => {
+                                debug!(%our_id, %peer_id, "simulated loss of outgoing message");
+                                queued_messages.dec();
+                            }
+                            Fate::DeliverAt(delivery) => in_flight.push_back((delivery, item)),
+                        }
+                    }
+                    None => is_closed = true,
+                },
+                _ = tokio::time::sleep_until(next_delivery.unwrap_or_else(Instant::now)),
+                    if next_delivery.is_some() =>
+                {
+                    if let Some((_, item)) = in_flight.pop_front() {
+                        if sender.send(item).is_err() {
+                            // The connection is closed.
+                            break;
+                        }
+                    }
+                }
+            }
+            if is_closed && in_flight.is_empty() {
+                break;
+            }
+        }
+    });
+    link_sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_ids(count: usize) -> Vec<NodeId> {
+        let mut rng = crate::new_rng();
+        (0..count).map(|_| NodeId::random(&mut rng)).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_drop_messages_across_partitions_until_healed() {
+        let nodes = node_ids(3);
+        let simulator = NetworkSimulator::new(0);
+
+        simulator.partition([vec![nodes[0]], vec![nodes[1]]]);
+        assert_eq!(simulator.fate(nodes[0], nodes[1], 1), Fate::Lost);
+        assert_eq!(simulator.fate(nodes[1], nodes[0], 1), Fate::Lost);
+        // Nodes outside of any partition can reach everyone.
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[2], 1),
+            Fate::DeliverAt(Instant::now())
+        );
+
+        simulator.heal();
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1),
+            Fate::DeliverAt(Instant::now())
+        );
+        assert_eq!(simulator.lost_count(), 2);
+        assert_eq!(simulator.delivered_count(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_be_deterministic_for_a_seed() {
+        let nodes = node_ids(2);
+        let conditions = LinkConditions {
+            min_latency: Duration::from_millis(10),
+            max_latency: Duration::from_millis(200),
+            loss: 0.2,
+            bandwidth: None,
+        };
+        let fates = |seed| {
+            let simulator = NetworkSimulator::new(seed);
+            simulator.set_default_link(conditions);
+            (0..100)
+                .map(|_| simulator.fate(nodes[0], nodes[1], 1))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(fates(42), fates(42));
+        assert_ne!(fates(42), fates(43));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_limit_bandwidth_and_preserve_order() {
+        let nodes = node_ids(2);
+        let simulator = NetworkSimulator::new(0);
+        simulator.set_link(
+            nodes[0],
+            nodes[1],
+            LinkConditions {
+                min_latency: Duration::from_millis(100),
+                max_latency: Duration::from_millis(100),
+                loss: 0.0,
+                bandwidth: Some(1000),
+            },
+        );
+
+        // Each message takes a second to transmit, on top of the latency.
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1000),
+            Fate::DeliverAt(Instant::now() + Duration::from_millis(1100))
+        );
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1000),
+            Fate::DeliverAt(Instant::now() + Duration::from_millis(2100))
+        );
+        // The reverse direction is not affected by the traffic.
+        assert_eq!(
+            simulator.fate(nodes[1], nodes[0], 500),
+            Fate::DeliverAt(Instant::now() + Duration::from_millis(600))
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_apply_scripted_steps_in_time() {
+        let nodes = node_ids(3);
+        let simulator = NetworkSimulator::new(0);
+        let slow = LinkConditions {
+            min_latency: Duration::from_millis(500),
+            max_latency: Duration::from_millis(500),
+            loss: 0.0,
+            bandwidth: None,
+        };
+        let fast = LinkConditions {
+            min_latency: Duration::from_millis(5),
+            max_latency: Duration::from_millis(5),
+            ..slow
+        };
+        let script = simulator.run_script(vec![
+            (
+                Duration::from_secs(1),
+                ScriptStep::Partition(vec![vec![nodes[0]], vec![nodes[1]]]),
+            ),
+            (Duration::from_secs(2), ScriptStep::Heal),
+            (Duration::from_secs(3), ScriptStep::SetDefaultLink(slow)),
+            (
+                Duration::from_secs(4),
+                ScriptStep::SetLink(nodes[0], nodes[1], fast),
+            ),
+        ]);
+
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(simulator.fate(nodes[0], nodes[1], 1), Fate::Lost);
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1),
+            Fate::DeliverAt(Instant::now())
+        );
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1),
+            Fate::DeliverAt(Instant::now() + slow.min_latency)
+        );
+
+        script.await.unwrap();
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[1], 1),
+            Fate::DeliverAt(Instant::now() + fast.min_latency)
+        );
+        assert_eq!(
+            simulator.fate(nodes[1], nodes[0], 1),
+            Fate::DeliverAt(Instant::now() + fast.min_latency)
+        );
+        // Other links keep the default conditions.
+        assert_eq!(
+            simulator.fate(nodes[0], nodes[2], 1),
+            Fate::DeliverAt(Instant::now() + slow.min_latency)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Tasks run by the component.
 
 use std::{
+    collections::HashMap,
     error::Error as StdError,
     fmt::Display,
     io,
@@ -8,15 +9,13 @@ use std::{
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, Weak,
+        Arc, Mutex, RwLock, RwLockReadGuard, Weak,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use bincode::Options;
 use futures::{
     future::{self, Either},
-    stream::{SplitSink, SplitStream},
     Future, SinkExt, StreamExt,
 };
 use openssl::{
@@ -25,11 +24,15 @@ use openssl::{
     x509::X509,
 };
 use prometheus::IntGauge;
+use quinn::Endpoint;
 use rand::Rng;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tokio::{
     net::TcpStream,
-    sync::{mpsc::UnboundedReceiver, watch, Semaphore},
+    sync::{
+        mpsc::{self, UnboundedReceiver},
+        watch, Semaphore,
+    },
 };
 use tokio_openssl::SslStream;
 use tokio_serde::{Deserializer, Serializer};
@@ -42,19 +45,23 @@ use tracing::{
 use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
 
 use super::{
+    blocklist::BlocklistJustification,
     chain_info::ChainInfo,
+    compression::{Compression, Compressor},
     counting_format::{ConnectionId, Role},
     error::{ConnectionError, IoError},
     event::{IncomingConnection, OutgoingConnection},
-    full_transport,
-    limiter::LimiterHandle,
+    limiter::{LimiterHandle, QuotaTracker},
     message::NodeKeyPair,
     message_pack_format::MessagePackFormat,
-    EstimatorWeights, Event, FramedTransport, FullTransport, Identity, Message, Metrics, Payload,
-    Transport,
+    multiplex::{Channel, MessageSink, MessageStream, OutgoingQueues},
+    multiplexed_transport,
+    peer_groups::{PeerGroupChange, PeerGroups},
+    quic, EstimatorWeights, Event, FramedTransport, Identity, Message, MessageQuotas, Metrics,
+    Payload, Transport,
 };
 use crate::{
-    components::network::{framed_transport, BincodeFormat, Config, FromIncoming},
+    components::network::{framed_transport, Config, FromIncoming},
     effect::{
         announcements::PeerBehaviorAnnouncement, requests::NetworkRequest, AutoClosingResponder,
         EffectBuilder,
@@ -71,6 +78,16 @@ use crate::{
 /// successfully handed over to the kernel for sending.
 pub(super) type MessageQueueItem<P> = (Arc<Message<P>>, Option<AutoClosingResponder<()>>);
 
+/// Time after which connecting to a peer via QUIC is given up in favor of TCP.
+const QUIC_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time for which peers only reachable via TCP are connected to without trying QUIC first.
+const QUIC_RETRY_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Maximum number of received messages per channel waiting to be handled before reading from the
+/// connection stops.
+const INCOMING_CHANNEL_QUEUE_SIZE: usize = 64;
+
 /// The outcome of the handshake process.
 struct HandshakeOutcome {
     /// A framed transport for peer.
@@ -81,15 +98,19 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// The compression algorithm the peer is able to decompress messages with, if any.
+    peer_compression: Option<Compression>,
+    /// Whether the peer supports multiplexed frames.
+    peer_multiplexing: bool,
 }
 
 /// Low-level TLS connection function.
 ///
-/// Performs the actual TCP+TLS connection setup.
+/// Performs the actual TCP+TLS connection setup and derives the connection ID.
 async fn tls_connect<REv>(
     context: &NetworkContext<REv>,
     peer_addr: SocketAddr,
-) -> Result<(NodeId, Transport), ConnectionError>
+) -> Result<(NodeId, Transport, ConnectionId), ConnectionError>
 where
     REv: 'static,
 {
@@ -124,11 +145,54 @@ where
         .map_err(ConnectionError::PeerCertificateInvalid)?;
 
     let peer_id = NodeId::from(validated_peer_cert.public_key_fingerprint());
+    let transport = Transport::Tls(transport);
+    let connection_id = transport.connection_id(context.our_id, peer_id)?;
+
+    Ok((peer_id, transport, connection_id))
+}
+
+/// Low-level QUIC connection function.
+///
+/// Gives up after [`QUIC_CONNECT_TIMEOUT`], as peers not accepting QUIC connections will never
+/// respond.  Also derives the connection ID, so that any failure to set up the QUIC connection
+/// leads to falling back to TCP.
+async fn quic_connect<REv>(
+    context: &NetworkContext<REv>,
+    endpoint: &Endpoint,
+    peer_addr: SocketAddr,
+) -> Result<(NodeId, Transport, ConnectionId), ConnectionError>
+where
+    REv: 'static,
+{
+    let (peer_cert, stream) = tokio::time::timeout(
+        QUIC_CONNECT_TIMEOUT,
+        quic::connect(
+            endpoint,
+            peer_addr,
+            context.our_cert.as_x509(),
+            &context.secret_key,
+        ),
+    )
+    .await
+    .map_err(|_elapsed| ConnectionError::QuicTimeout)??;
+
+    let validated_peer_cert = context
+        .validate_peer_cert(peer_cert)
+        .map_err(ConnectionError::PeerCertificateInvalid)?;
+
+    let peer_id = NodeId::from(validated_peer_cert.public_key_fingerprint());
+    let transport = Transport::Quic(stream);
+    let connection_id = transport.connection_id(context.our_id, peer_id)?;
 
-    Ok((peer_id, transport))
+    Ok((peer_id, transport, connection_id))
 }
 
-/// Initiates a TLS connection to a remote address.
+/// Initiates a connection to a remote address.
+///
+/// If QUIC is enabled, it is tried first, falling back to TLS over TCP if the peer cannot be
+/// reached via QUIC.  Peers reached via TCP only are not tried via QUIC again until
+/// [`QUIC_RETRY_INTERVAL`] has passed, to not delay every connection to them by up to
+/// [`QUIC_CONNECT_TIMEOUT`].
 pub(super) async fn connect_outgoing<P, REv>(
     context: Arc<NetworkContext<REv>>,
     peer_addr: SocketAddr,
@@ -137,7 +201,26 @@ where
     REv: 'static,
     P: Payload,
 {
-    let (peer_id, transport) = match tls_connect(&context, peer_addr).await {
+    let connected = match context.quic_endpoint {
+        Some(ref endpoint) if context.should_try_quic(peer_addr) => {
+            match quic_connect(&context, endpoint, peer_addr).await {
+                Ok(value) => Ok(value),
+                Err(ref error) => {
+                    debug!(
+                        err = display_error(error),
+                        "failed to connect via QUIC, falling back to TCP"
+                    );
+                    let connected = tls_connect(&context, peer_addr).await;
+                    if connected.is_ok() {
+                        context.set_quic_unreachable(peer_addr);
+                    }
+                    connected
+                }
+            }
+        }
+        _ => tls_connect(&context, peer_addr).await,
+    };
+    let (peer_id, transport, connection_id) = match connected {
         Ok(value) => value,
         Err(error) => return OutgoingConnection::FailedEarly { peer_addr, error },
     };
@@ -150,10 +233,21 @@ where
         return OutgoingConnection::Loopback { peer_addr };
     }
 
-    debug!("Outgoing TLS connection established");
+    if !context.is_peer_allowed(&peer_id) {
+        info!("outgoing connection to node not on allowlist");
+        return OutgoingConnection::Failed {
+            peer_addr,
+            peer_id,
+            error: ConnectionError::PeerNotAllowed,
+        };
+    }
+
+    debug!(
+        protocol = transport.protocol(),
+        "outgoing connection established"
+    );
 
-    // Setup connection id and framed transport.
-    let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    // Setup framed transport.
     let framed_transport = framed_transport(transport, context.chain_info.maximum_net_message_size);
 
     // Negotiate the handshake, concluding the incoming connection process.
@@ -163,6 +257,8 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_compression,
+            peer_multiplexing,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
@@ -173,20 +269,22 @@ where
                 warn!(%public_addr, %peer_addr, "peer advertises a different public address than what we connected to");
             }
 
-            // Setup full framed transport, then close down receiving end of the transport.
-            let full_transport = full_transport::<P>(
+            // Setup multiplexed transport, then close down receiving end of the transport.
+            let (sink, _stream) = multiplexed_transport::<P>(
                 context.net_metrics.clone(),
                 connection_id,
                 framed_transport,
                 Role::Dialer,
+                context.chain_info.maximum_net_message_size,
+                Compressor::new(peer_compression, context.compression_threshold),
+                peer_multiplexing,
             );
-            let (sink, _stream) = full_transport.split();
 
             OutgoingConnection::Established {
                 peer_addr,
                 peer_id,
                 peer_consensus_public_key,
-                sink,
+                sink: Box::new(sink),
                 is_syncing,
             }
         }
@@ -226,6 +324,8 @@ where
     handshake_timeout: TimeDiff,
     /// Weights to estimate payloads with.
     payload_weights: EstimatorWeights,
+    /// Per-peer quotas on incoming messages by type.
+    message_quotas: MessageQuotas,
     /// The protocol version at which (or under) tarpitting is enabled.
     tarpit_version_threshold: Option<ProtocolVersion>,
     /// If tarpitting is enabled, duration for which connections should be kept open.
@@ -236,6 +336,14 @@ where
     max_in_flight_demands: usize,
     /// Flag indicating whether this node is syncing.
     is_syncing: AtomicBool,
+    /// Minimum size of a serialized message to be compressed, `0` if compression is disabled.
+    compression_threshold: u32,
+    /// Operator-managed static peers and node ID allowlist.
+    peer_groups: RwLock<PeerGroups>,
+    /// The QUIC endpoint accepting and initiating connections, if QUIC is enabled.
+    quic_endpoint: Option<Endpoint>,
+    /// Addresses of peers last reached via TCP only, along with when QUIC was last tried.
+    quic_unreachable: Mutex<HashMap<SocketAddr, Instant>>,
 }
 
 impl<REv> NetworkContext<REv> {
@@ -245,6 +353,7 @@ impl<REv> NetworkContext<REv> {
         node_key_pair: Option<NodeKeyPair>,
         chain_info: ChainInfo,
         net_metrics: &Arc<Metrics>,
+        peer_groups: PeerGroups,
     ) -> Self {
         // Set the demand max from configuration, regarding `0` as "unlimited".
         let max_in_flight_demands = if cfg.max_in_flight_demands == 0 {
@@ -272,23 +381,49 @@ impl<REv> NetworkContext<REv> {
             node_key_pair,
             handshake_timeout: cfg.handshake_timeout,
             payload_weights: cfg.estimator_weights.clone(),
+            message_quotas: cfg.message_quotas.clone(),
             tarpit_version_threshold: cfg.tarpit_version_threshold,
             tarpit_duration: cfg.tarpit_duration,
             tarpit_chance: cfg.tarpit_chance,
             max_in_flight_demands,
             is_syncing: AtomicBool::new(false),
+            compression_threshold: cfg.compression_threshold,
+            peer_groups: RwLock::new(peer_groups),
+            quic_endpoint: None,
+            quic_unreachable: Mutex::new(HashMap::new()),
         }
     }
 
     pub(super) fn initialize(
         &mut self,
         our_public_addr: SocketAddr,
+        quic_endpoint: Option<Endpoint>,
         event_queue: EventQueueHandle<REv>,
     ) {
         self.public_addr = Some(our_public_addr);
+        self.quic_endpoint = quic_endpoint;
         self.event_queue = Some(event_queue);
     }
 
+    /// Returns whether connecting to `peer_addr` should be tried via QUIC.
+    fn should_try_quic(&self, peer_addr: SocketAddr) -> bool {
+        let mut quic_unreachable = self
+            .quic_unreachable
+            .lock()
+            .expect("QUIC unreachable lock poisoned");
+        let now = Instant::now();
+        quic_unreachable.retain(|_, tried_at| now.duration_since(*tried_at) < QUIC_RETRY_INTERVAL);
+        !quic_unreachable.contains_key(&peer_addr)
+    }
+
+    /// Records that `peer_addr` could only be reached via TCP.
+    fn set_quic_unreachable(&self, peer_addr: SocketAddr) {
+        self.quic_unreachable
+            .lock()
+            .expect("QUIC unreachable lock poisoned")
+            .insert(peer_addr, Instant::now());
+    }
+
     /// Our own [`NodeId`].
     pub(super) fn our_id(&self) -> NodeId {
         self.our_id
@@ -318,14 +453,45 @@ impl<REv> NetworkContext<REv> {
     pub(crate) fn is_syncing(&self) -> &AtomicBool {
         &self.is_syncing
     }
+
+    /// Optional set of signing keys, to identify as a node during handshake.
+    pub(super) fn node_key_pair(&self) -> Option<&NodeKeyPair> {
+        self.node_key_pair.as_ref()
+    }
+
+    /// Operator-managed static peers and node ID allowlist.
+    pub(super) fn peer_groups(&self) -> RwLockReadGuard<'_, PeerGroups> {
+        self.peer_groups.read().expect("peer groups lock poisoned")
+    }
+
+    /// Returns whether a connection to or from the given node is permitted by the allowlist.
+    pub(super) fn is_peer_allowed(&self, peer_id: &NodeId) -> bool {
+        self.peer_groups().is_allowed(peer_id)
+    }
+
+    /// Applies a change to the peer groups, returning whether they were modified.
+    pub(super) fn update_peer_groups(&self, change: &PeerGroupChange) -> bool {
+        self.peer_groups
+            .write()
+            .expect("peer groups lock poisoned")
+            .apply(change)
+    }
+}
+
+/// An accepted connection, before its encryption has been set up.
+enum AcceptedConnection {
+    /// A TCP connection, to be secured by TLS.
+    Tcp(TcpStream),
+    /// A QUIC connection still completing its handshake.
+    Quic(quinn::Connecting),
 }
 
 /// Handles an incoming connection.
 ///
-/// Sets up a TLS stream and performs the protocol handshake.
+/// Sets up a TLS stream or QUIC connection and performs the protocol handshake.
 async fn handle_incoming<P, REv>(
     context: Arc<NetworkContext<REv>>,
-    stream: TcpStream,
+    accepted: AcceptedConnection,
     peer_addr: SocketAddr,
 ) -> IncomingConnection<P>
 where
@@ -334,7 +500,11 @@ where
     for<'de> P: Serialize + Deserialize<'de>,
     for<'de> Message<P>: Serialize + Deserialize<'de>,
 {
-    let (peer_id, transport) = match server_setup_tls(&context, stream).await {
+    let set_up = match accepted {
+        AcceptedConnection::Tcp(stream) => server_setup_tls(&context, stream).await,
+        AcceptedConnection::Quic(connecting) => server_setup_quic(&context, connecting).await,
+    };
+    let (peer_id, transport) = match set_up {
         Ok(value) => value,
         Err(error) => {
             return IncomingConnection::FailedEarly { peer_addr, error };
@@ -349,10 +519,31 @@ where
         return IncomingConnection::Loopback;
     }
 
-    debug!("Incoming TLS connection established");
+    if !context.is_peer_allowed(&peer_id) {
+        info!("rejecting incoming connection from node not on allowlist");
+        return IncomingConnection::Failed {
+            peer_addr,
+            peer_id,
+            error: ConnectionError::PeerNotAllowed,
+        };
+    }
+
+    debug!(
+        protocol = transport.protocol(),
+        "incoming connection established"
+    );
 
     // Setup connection id and framed transport.
-    let connection_id = ConnectionId::from_connection(transport.ssl(), context.our_id, peer_id);
+    let connection_id = match transport.connection_id(context.our_id, peer_id) {
+        Ok(connection_id) => connection_id,
+        Err(error) => {
+            return IncomingConnection::Failed {
+                peer_addr,
+                peer_id,
+                error,
+            }
+        }
+    };
     let framed_transport = framed_transport(transport, context.chain_info.maximum_net_message_size);
 
     // Negotiate the handshake, concluding the incoming connection process.
@@ -362,27 +553,30 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            peer_compression,
+            peer_multiplexing,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
             }
 
-            // Establish full transport and close the receiving end.
-            let full_transport = full_transport::<P>(
+            // Establish multiplexed transport and close the sending end.
+            let (_sink, stream) = multiplexed_transport::<P>(
                 context.net_metrics.clone(),
                 connection_id,
                 framed_transport,
                 Role::Listener,
+                context.chain_info.maximum_net_message_size,
+                Compressor::new(peer_compression, context.compression_threshold),
+                peer_multiplexing,
             );
 
-            let (_sink, stream) = full_transport.split();
-
             IncomingConnection::Established {
                 peer_addr,
                 public_addr,
                 peer_id,
                 peer_consensus_public_key,
-                stream,
+                stream: Box::new(stream),
             }
         }
         Err(error) => IncomingConnection::Failed {
@@ -424,7 +618,27 @@ pub(super) async fn server_setup_tls<REv>(
 
     Ok((
         NodeId::from(validated_peer_cert.public_key_fingerprint()),
-        tls_stream,
+        Transport::Tls(tls_stream),
+    ))
+}
+
+/// Server-side QUIC setup.
+///
+/// Completes the handshake of an incoming QUIC connection and validates the peer's certificate.
+async fn server_setup_quic<REv>(
+    context: &NetworkContext<REv>,
+    connecting: quinn::Connecting,
+) -> Result<(NodeId, Transport), ConnectionError> {
+    let (peer_cert, stream) =
+        quic::accept(connecting, context.our_cert.as_x509(), &context.secret_key).await?;
+
+    let validated_peer_cert = context
+        .validate_peer_cert(peer_cert)
+        .map_err(ConnectionError::PeerCertificateInvalid)?;
+
+    Ok((
+        NodeId::from(validated_peer_cert.public_key_fingerprint()),
+        Transport::Quic(stream),
     ))
 }
 
@@ -513,6 +727,8 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        compression,
+        multiplexing,
     } = remote_message
     {
         debug!(%protocol_version, "handshake received");
@@ -571,6 +787,8 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_compression: compression,
+            peer_multiplexing: multiplexing,
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -599,30 +817,12 @@ pub(super) async fn server<P, REv>(
             // shortage or the remote side closing the connection while it is waiting in
             // the queue.
             match listener.accept().await {
-                Ok((stream, peer_addr)) => {
-                    // The span setup here is used throughout the entire lifetime of the connection.
-                    let span =
-                        error_span!("incoming", %peer_addr, peer_id=Empty, consensus_key=Empty);
-
-                    let context = context.clone();
-                    let handler_span = span.clone();
-                    tokio::spawn(
-                        async move {
-                            let incoming =
-                                handle_incoming(context.clone(), stream, peer_addr).await;
-                            event_queue
-                                .schedule(
-                                    Event::IncomingConnection {
-                                        incoming: Box::new(incoming),
-                                        span,
-                                    },
-                                    QueueKind::NetworkIncoming,
-                                )
-                                .await;
-                        }
-                        .instrument(handler_span),
-                    );
-                }
+                Ok((stream, peer_addr)) => spawn_incoming_handler(
+                    context.clone(),
+                    event_queue,
+                    AcceptedConnection::Tcp(stream),
+                    peer_addr,
+                ),
 
                 // TODO: Handle resource errors gracefully.
                 //       In general, two kinds of errors occur here: Local resource exhaustion,
@@ -638,25 +838,82 @@ pub(super) async fn server<P, REv>(
         }
     };
 
+    // QUIC connections are accepted alongside TCP ones if enabled.
+    let accept_quic_connections = async {
+        let event_queue = context.event_queue.expect("component not initialized");
+        if let Some(ref endpoint) = context.quic_endpoint {
+            while let Some(connecting) = endpoint.accept().await {
+                let peer_addr = connecting.remote_address();
+                spawn_incoming_handler(
+                    context.clone(),
+                    event_queue,
+                    AcceptedConnection::Quic(connecting),
+                    peer_addr,
+                );
+            }
+        }
+        future::pending::<()>().await
+    };
+
     let shutdown_messages = async move { while shutdown_receiver.changed().await.is_ok() {} };
 
     // Now we can wait for either the `shutdown` channel's remote end to do be dropped or the
-    // infinite loop to terminate, which never happens.
-    match future::select(Box::pin(shutdown_messages), Box::pin(accept_connections)).await {
+    // infinite loops to terminate, which never happens.
+    let accept_all = future::join(accept_connections, accept_quic_connections);
+    match future::select(Box::pin(shutdown_messages), Box::pin(accept_all)).await {
         Either::Left(_) => info!(
             %context.our_id,
             "shutting down socket, no longer accepting incoming connections"
         ),
         Either::Right(_) => unreachable!(),
     }
+
+    if let Some(ref endpoint) = context.quic_endpoint {
+        endpoint.close(0u32.into(), b"shutting down");
+    }
+}
+
+/// Spawns a task handling an accepted connection, reporting the outcome to the reactor.
+fn spawn_incoming_handler<P, REv>(
+    context: Arc<NetworkContext<REv>>,
+    event_queue: EventQueueHandle<REv>,
+    accepted: AcceptedConnection,
+    peer_addr: SocketAddr,
+) where
+    REv: From<Event<P>> + Send,
+    P: Payload,
+{
+    // The span setup here is used throughout the entire lifetime of the connection.
+    let span = error_span!("incoming", %peer_addr, peer_id=Empty, consensus_key=Empty);
+
+    let handler_span = span.clone();
+    tokio::spawn(
+        async move {
+            let incoming = handle_incoming(context, accepted, peer_addr).await;
+            event_queue
+                .schedule(
+                    Event::IncomingConnection {
+                        incoming: Box::new(incoming),
+                        span,
+                    },
+                    QueueKind::NetworkIncoming,
+                )
+                .await;
+        }
+        .instrument(handler_span),
+    );
 }
 
 /// Network message reader.
 ///
-/// Schedules all received messages until the stream is closed or an error occurs.
+/// Reassembles received messages and hands each to the handler of the channel it was received on,
+/// until the stream is closed or an error occurs. Every channel is handled independently, so a
+/// channel held up by backpressure, e.g. from too many demands in flight, does not hold up the
+/// others: once its queue of received messages is full, further messages on that channel are
+/// dropped until the handler catches up.
 pub(super) async fn message_reader<REv, P>(
     context: Arc<NetworkContext<REv>>,
-    mut stream: SplitStream<FullTransport<P>>,
+    mut stream: MessageStream<P>,
     limiter: LimiterHandle,
     mut close_incoming_receiver: watch::Receiver<()>,
     peer_id: NodeId,
@@ -671,99 +928,47 @@ where
         + Send,
 {
     let demands_in_flight = Arc::new(Semaphore::new(context.max_in_flight_demands));
+
+    let (channel_senders, channel_receivers): (Vec<_>, Vec<_>) = Channel::ALL
+        .iter()
+        .map(|_| mpsc::channel(INCOMING_CHANNEL_QUEUE_SIZE))
+        .unzip();
+
     let event_queue = context.event_queue.expect("component not initialized");
+    let message_quotas = context.message_quotas.clone();
+    let mut quota_tracker = QuotaTracker::default();
+    let net_metrics = context.net_metrics.clone();
 
     let read_messages = async move {
-        while let Some(msg_result) = stream.next().await {
+        while let Some(msg_result) = stream.next_message().await {
             match msg_result {
-                Ok(msg) => {
-                    trace!(%msg, "message received");
-
-                    let effect_builder = EffectBuilder::new(event_queue);
-
-                    match msg.try_into_demand(effect_builder, peer_id) {
-                        Ok((event, wait_for_response)) => {
-                            // Note: For now, demands bypass the limiter, as we expect the
-                            //       backpressure to handle this instead.
-
-                            // Acquire a permit. If we are handling too many demands at this
-                            // time, this will block, halting the processing of new message,
-                            // thus letting the peer they have reached their maximum allowance.
-                            let in_flight = demands_in_flight
-                                .clone()
-                                .acquire_owned()
-                                .await
-                                // Note: Since the semaphore is reference counted, it must
-                                //       explicitly be closed for acquisition to fail, which we
-                                //       never do. If this happens, there is a bug in the code;
-                                //       we exit with an error and close the connection.
-                                .map_err(|_| {
-                                    io::Error::new(
-                                        io::ErrorKind::Other,
-                                        "demand limiter semaphore closed unexpectedly",
-                                    )
-                                })?;
-
-                            Metrics::record_trie_request_start(&context.net_metrics);
-
-                            let net_metrics = context.net_metrics.clone();
-                            // Spawn a future that will eventually send the returned message. It
-                            // will essentially buffer the response.
-                            tokio::spawn(async move {
-                                if let Some(payload) = wait_for_response.await {
-                                    // Send message and await its return. `send_message` should
-                                    // only return when the message has been buffered, if the
-                                    // peer is not accepting data, we will block here until the
-                                    // send buffer has sufficient room.
-                                    effect_builder.send_message(peer_id, payload).await;
-
-                                    // Note: We could short-circuit the event queue here and
-                                    //       directly insert into the outgoing message queue,
-                                    //       which may be potential performance improvement.
-                                }
-
-                                // Missing else: The handler of the demand did not deem it
-                                // worthy a response. Just drop it.
-
-                                // After we have either successfully buffered the message for
-                                // sending, failed to do so or did not have a message to send
-                                // out, we consider the request handled and free up the permit.
-                                Metrics::record_trie_request_end(&net_metrics);
-                                drop(in_flight);
-                            });
-
-                            // Schedule the created event.
-                            event_queue
-                                .schedule::<REv>(event, QueueKind::NetworkDemand)
-                                .await;
-                        }
-                        Err(msg) => {
-                            // We've received a non-demand message. Ensure we have the proper amount
-                            // of resources, then push it to the reactor.
-                            limiter
-                                .request_allowance(
-                                    msg.payload_incoming_resource_estimate(
-                                        &context.payload_weights,
-                                    ),
-                                )
-                                .await;
-
-                            let queue_kind = if msg.is_low_priority() {
-                                QueueKind::NetworkLowPriority
-                            } else {
-                                QueueKind::NetworkIncoming
-                            };
-
-                            event_queue
-                                .schedule(
-                                    Event::IncomingMessage {
-                                        peer_id: Box::new(peer_id),
-                                        msg,
-                                        span: span.clone(),
+                Ok((channel, msg)) => {
+                    trace!(%msg, %channel, "message received");
+
+                    if let Some(quota) = msg.incoming_message_quota(&message_quotas) {
+                        if !quota_tracker.record_message(quota, Instant::now()) {
+                            EffectBuilder::new(event_queue)
+                                .announce_block_peer_with_justification(
+                                    peer_id,
+                                    BlocklistJustification::ExceededMessageQuota {
+                                        message_type: quota.message_type,
+                                        per_second: quota.per_second,
                                     },
-                                    queue_kind,
                                 )
                                 .await;
+                            break;
+                        }
+                    }
+
+                    match channel_senders[channel.index()].try_send(msg) {
+                        Ok(()) => {}
+                        Err(mpsc::error::TrySendError::Full(msg)) => {
+                            debug!(%msg, %channel, "channel queue full, dropping message");
+                            Metrics::record_channel_dropped(&net_metrics, channel);
+                        }
+                        Err(mpsc::error::TrySendError::Closed(_)) => {
+                            // The channel handler exited with an error, which is handled below.
+                            break;
                         }
                     }
                 }
@@ -779,11 +984,27 @@ where
         Ok(())
     };
 
+    let handle_channels = future::try_join_all(channel_receivers.into_iter().map(|receiver| {
+        handle_channel_messages(
+            &context,
+            receiver,
+            &limiter,
+            &demands_in_flight,
+            peer_id,
+            &span,
+        )
+    }));
+
     let shutdown_messages = async move { while close_incoming_receiver.changed().await.is_ok() {} };
 
     // Now we can wait for either the `shutdown` channel's remote end to do be dropped or the
     // while loop to terminate.
-    match future::select(Box::pin(shutdown_messages), Box::pin(read_messages)).await {
+    match future::select(
+        Box::pin(shutdown_messages),
+        Box::pin(future::try_join(read_messages, handle_channels)),
+    )
+    .await
+    {
         Either::Left(_) => info!("shutting down incoming connection message reader"),
         Either::Right(_) => (),
     }
@@ -791,39 +1012,185 @@ where
     Ok(())
 }
 
+/// Handles the messages received on a single channel of a connection.
+///
+/// Returns once the reader hands over no further messages.
+async fn handle_channel_messages<REv, P>(
+    context: &NetworkContext<REv>,
+    mut receiver: mpsc::Receiver<Message<P>>,
+    limiter: &LimiterHandle,
+    demands_in_flight: &Arc<Semaphore>,
+    peer_id: NodeId,
+    span: &Span,
+) -> io::Result<()>
+where
+    P: DeserializeOwned + Send + Display + Payload,
+    REv: From<Event<P>>
+        + FromIncoming<P>
+        + From<NetworkRequest<P>>
+        + From<PeerBehaviorAnnouncement>
+        + Send,
+{
+    let event_queue = context.event_queue.expect("component not initialized");
+
+    while let Some(msg) = receiver.recv().await {
+        let effect_builder = EffectBuilder::new(event_queue);
+
+        match msg.try_into_demand(effect_builder, peer_id) {
+            Ok((event, wait_for_response)) => {
+                // Note: For now, demands bypass the limiter, as we expect the backpressure to
+                //       handle this instead.
+
+                // Acquire a permit. If we are handling too many demands at this time, this will
+                // block, halting the processing of new message, thus letting the peer they have
+                // reached their maximum allowance.
+                let in_flight = demands_in_flight
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    // Note: Since the semaphore is reference counted, it must explicitly be
+                    //       closed for acquisition to fail, which we never do. If this happens,
+                    //       there is a bug in the code; we exit with an error and close the
+                    //       connection.
+                    .map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::Other,
+                            "demand limiter semaphore closed unexpectedly",
+                        )
+                    })?;
+
+                Metrics::record_trie_request_start(&context.net_metrics);
+
+                let net_metrics = context.net_metrics.clone();
+                // Spawn a future that will eventually send the returned message. It will
+                // essentially buffer the response.
+                tokio::spawn(async move {
+                    if let Some(payload) = wait_for_response.await {
+                        // Send message and await its return. `send_message` should only return
+                        // when the message has been buffered, if the peer is not accepting data,
+                        // we will block here until the send buffer has sufficient room.
+                        effect_builder.send_message(peer_id, payload).await;
+
+                        // Note: We could short-circuit the event queue here and directly insert
+                        //       into the outgoing message queue, which may be potential
+                        //       performance improvement.
+                    }
+
+                    // Missing else: The handler of the demand did not deem it worthy a response.
+                    // Just drop it.
+
+                    // After we have either successfully buffered the message for sending, failed
+                    // to do so or did not have a message to send out, we consider the request
+                    // handled and free up the permit.
+                    Metrics::record_trie_request_end(&net_metrics);
+                    drop(in_flight);
+                });
+
+                // Schedule the created event.
+                event_queue
+                    .schedule::<REv>(event, QueueKind::NetworkDemand)
+                    .await;
+            }
+            Err(msg) => {
+                // We've received a non-demand message. Ensure we have the proper amount of
+                // resources, then push it to the reactor.
+                limiter
+                    .request_allowance(
+                        msg.payload_incoming_resource_estimate(&context.payload_weights),
+                    )
+                    .await;
+
+                let queue_kind = if msg.is_low_priority() {
+                    QueueKind::NetworkLowPriority
+                } else {
+                    QueueKind::NetworkIncoming
+                };
+
+                event_queue
+                    .schedule(
+                        Event::IncomingMessage {
+                            peer_id: Box::new(peer_id),
+                            msg,
+                            span: span.clone(),
+                        },
+                        queue_kind,
+                    )
+                    .await;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Network message sender.
 ///
 /// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
+///
+/// Queued messages are sorted into the queues of their channels, and sent fragment by fragment,
+/// interleaving the fragments of different channels by priority.
 pub(super) async fn message_sender<P>(
     mut queue: UnboundedReceiver<MessageQueueItem<P>>,
-    mut sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
+    mut sink: MessageSink<P>,
     limiter: LimiterHandle,
     counter: IntGauge,
 ) where
     P: Payload,
 {
-    while let Some((message, opt_responder)) = queue.recv().await {
-        counter.dec();
+    let mut outgoing = OutgoingQueues::new(sink.max_fragment_size());
+
+    loop {
+        // Sort everything queued so far into the channel queues, so the next fragment is chosen
+        // among all of them.
+        while let Ok((message, opt_responder)) = queue.try_recv() {
+            counter.dec();
+            let channel = Channel::for_message_kind(message.classify());
+            Metrics::record_channel_queued(sink.metrics(), channel, 1);
+            outgoing.push(channel, message, opt_responder);
+        }
+
+        if outgoing.is_empty() {
+            // Nothing left to send, ensure everything written so far reaches the peer, then wait
+            // for the next message.
+            if let Err(ref err) = sink.flush().await {
+                info!(
+                    err = display_error(err),
+                    "flushing failed, closing outgoing connection"
+                );
+                break;
+            }
 
-        let estimated_wire_size = match BincodeFormat::default().0.serialized_size(&*message) {
-            Ok(size) => size as u32,
+            match queue.recv().await {
+                Some((message, opt_responder)) => {
+                    counter.dec();
+                    let channel = Channel::for_message_kind(message.classify());
+                    Metrics::record_channel_queued(sink.metrics(), channel, 1);
+                    outgoing.push(channel, message, opt_responder);
+                }
+                None => break,
+            }
+        }
+
+        let (frame, opt_responder) = match outgoing.next_fragment(|message| sink.serialize(message))
+        {
+            Ok(Some(fragment)) => fragment,
+            Ok(None) => continue,
             Err(error) => {
                 error!(
                     error = display_error(&error),
-                    "failed to get serialized size of outgoing message, closing outgoing connection"
+                    "failed to serialize outgoing message, closing outgoing connection"
                 );
                 break;
             }
         };
-        limiter.request_allowance(estimated_wire_size).await;
+        let channel = frame.channel();
+        let is_final = frame.is_final();
 
-        let mut outcome = sink.send(message).await;
+        limiter.request_allowance(frame.data_len() as u32).await;
 
-        // Notify via responder that the message has been buffered by the kernel.
-        if let Some(auto_closing_responder) = opt_responder {
-            // Since someone is interested in the message, flush the socket to ensure it was sent.
-            outcome = outcome.and(sink.flush().await);
-            auto_closing_responder.respond(()).await;
+        let outcome = sink.feed(frame).await;
+
+        if is_final {
+            Metrics::record_channel_queued(sink.metrics(), channel, -1);
         }
 
         // We simply error-out if the sink fails, it means that our connection broke.
@@ -832,14 +1199,34 @@ pub(super) async fn message_sender<P>(
                 err = display_error(err),
                 "message send failed, closing outgoing connection"
             );
+            break;
+        };
 
-            // To ensure, metrics are up to date, we close the queue and drain it.
-            queue.close();
-            while queue.recv().await.is_some() {
-                counter.dec();
+        // Notify via responder that the message has been buffered by the kernel.
+        if let Some(auto_closing_responder) = opt_responder {
+            // Since someone is interested in the message, flush the socket to ensure it was sent
+            // before responding. If flushing fails, the responder is dropped without a response.
+            if let Err(ref err) = sink.flush().await {
+                info!(
+                    err = display_error(err),
+                    "flushing failed, closing outgoing connection"
+                );
+                break;
             }
+            auto_closing_responder.respond(()).await;
+        }
+    }
 
-            break;
-        };
+    // To ensure, metrics are up to date, we close the queue and drain it.
+    for channel in Channel::ALL {
+        Metrics::record_channel_queued(
+            sink.metrics(),
+            channel,
+            -(outgoing.pending(channel) as i64),
+        );
+    }
+    queue.close();
+    while queue.recv().await.is_some() {
+        counter.dec();
     }
 }
@@ -18,11 +18,13 @@ use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 use tracing::{debug, info};
 
-use casper_types::SecretKey;
+use casper_types::{PublicKey, SecretKey};
 
 use super::{
-    chain_info::ChainInfo, Config, Event as NetworkEvent, FromIncoming, GossipedAddress, Identity,
-    MessageKind, Network, Payload,
+    chain_info::ChainInfo,
+    message::{NodeKeyPair, SentryCertificate},
+    Config, Event as NetworkEvent, FromIncoming, GossipedAddress, Identity, MessageKind, Network,
+    Payload, SentryMode,
 };
 use crate::{
     components::{
@@ -40,6 +42,7 @@ use crate::{
     },
     protocol,
     reactor::{self, EventQueueHandle, Finalize, Reactor, Runner},
+    signer::Signer,
     testing::{
         self, init_logging,
         network::{NetworkedReactor, Nodes, TestingNetwork},
@@ -199,6 +202,7 @@ impl Reactor for TestReactor {
             registry,
             ChainInfo::create_for_testing(),
             ValidatorMatrix::new_with_validator(Arc::new(secret_key)),
+            None,
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -535,3 +539,44 @@ async fn ensure_peers_metric_is_correct() {
         net.finalize().await;
     }
 }
+
+/// Check that a sentry only protects a validator connected with the validator's key, not a peer
+/// replaying the certificate the sentry advertises for it.
+#[tokio::test]
+async fn sentry_ignores_replayed_certificate() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+
+    let mut cfg = Config::default_local_net_first_node(testing::unused_port_on_localhost());
+    cfg.sentry_mode = SentryMode::Sentry;
+    let mut net = TestingNetwork::<TestReactor>::new();
+    let (sentry_id, _) = net.add_node_with_config(cfg, &mut rng).await.unwrap();
+
+    let secret_key = SecretKey::random(&mut rng);
+    let public_key = PublicKey::from(&secret_key);
+    let key_pair = NodeKeyPair::new(Signer::from(Arc::new(secret_key)));
+    let certificate = SentryCertificate::create(sentry_id, &key_pair).expect("should sign");
+
+    let validator_id = NodeId::random(&mut rng);
+    let third_peer_id = NodeId::random(&mut rng);
+    let sentry = &mut net
+        .nodes_mut()
+        .get_mut(&sentry_id)
+        .expect("should have sentry")
+        .reactor_mut()
+        .inner_mut()
+        .net;
+    sentry
+        .incoming_consensus_keys
+        .insert(validator_id, public_key);
+
+    sentry.handle_sentry_certificate(third_peer_id, certificate.clone());
+    assert!(sentry.protected_validators.is_empty());
+
+    sentry.handle_sentry_certificate(validator_id, certificate);
+    assert!(sentry.protected_validators.contains_key(&validator_id));
+    assert_eq!(sentry.protected_validators.len(), 1);
+
+    net.finalize().await;
+}
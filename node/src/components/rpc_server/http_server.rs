@@ -10,7 +10,10 @@ use super::{
             GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
         },
         docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+        info::{
+            GetChainspec, GetConsensusRounds, GetDeploy, GetPeers, GetStatus, GetValidatorChanges,
+            GetValidatorPerformance,
+        },
         state::{
             GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
             QueryBalance, QueryGlobalState,
@@ -52,6 +55,8 @@ pub(super) async fn run<REv: ReactorEventT>(
     GetAuctionInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetTrie::register_as_handler(effect_builder, api_version, &mut handlers);
     GetValidatorChanges::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetConsensusRounds::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetValidatorPerformance::register_as_handler(effect_builder, api_version, &mut handlers);
     ListRpcs::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDictionaryItem::register_as_handler(effect_builder, api_version, &mut handlers);
     GetChainspec::register_as_handler(effect_builder, api_version, &mut handlers);
@@ -20,7 +20,10 @@ use super::{
     chain::{
         GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
     },
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+    info::{
+        GetChainspec, GetConsensusRounds, GetDeploy, GetPeers, GetStatus, GetValidatorChanges,
+        GetValidatorPerformance,
+    },
     state::{
         GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
         QueryGlobalState,
@@ -82,6 +85,13 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
     schema.push_without_params::<GetStatus>("returns the current status of the node");
     schema
         .push_without_params::<GetValidatorChanges>("returns status changes of active validators");
+    schema.push_without_params::<GetConsensusRounds>(
+        "returns the timelines of the most recent complete consensus rounds",
+    );
+    schema.push_with_optional_params::<GetValidatorPerformance>(
+        "returns the validators' participation in consensus and finality signatures in a \
+        given era, or in the most recent era for which it is known",
+    );
     schema.push_without_params::<GetChainspec>(
         "returns the raw bytes of the chainspec.toml, genesis accounts.toml, and \
         global_state.toml files",
@@ -568,6 +578,12 @@ mod tests {
         assert!(incorrect_optional_params.is_empty())
     }
 
+    #[test]
+    fn check_info_get_validator_performance_required_fields() {
+        let incorrect_optional_params = check_optional_params_fields::<GetValidatorPerformance>();
+        assert!(incorrect_optional_params.is_empty())
+    }
+
     #[test]
     fn check_state_get_auction_info_required_fields() {
         let incorrect_optional_params = check_optional_params_fields::<GetAuctionInfo>();
@@ -11,19 +11,20 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
-    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams,
+    Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithParams,
+    RpcWithoutParams,
 };
 use crate::{
-    components::consensus::ValidatorChange,
+    components::consensus::{ConsensusRound, ValidatorChange},
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
         Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployHash,
-        DeployMetadataExt, GetStatusResult, PeersMap,
+        DeployMetadataExt, GetStatusResult, PeersMap, ValidatorPerformance,
     },
 };
 
@@ -53,6 +54,46 @@ static GET_VALIDATOR_CHANGES_RESULT: Lazy<GetValidatorChangesResult> = Lazy::new
         changes,
     }
 });
+static GET_CONSENSUS_ROUNDS_RESULT: Lazy<GetConsensusRoundsResult> = Lazy::new(|| {
+    let public_key = PublicKey::doc_example().clone();
+    let timestamp = *Timestamp::doc_example();
+    let round = JsonConsensusRound {
+        era_id: EraId::new(1),
+        round_id: 3,
+        leader: public_key.clone(),
+        proposal_time: Some(timestamp),
+        votes: vec![JsonVoteArrival {
+            validator: public_key,
+            time: timestamp,
+        }],
+        timeout_time: None,
+        finalization_time: Some(timestamp),
+    };
+    GetConsensusRoundsResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        rounds: vec![round],
+    }
+});
+static GET_VALIDATOR_PERFORMANCE_PARAMS: Lazy<GetValidatorPerformanceParams> =
+    Lazy::new(|| GetValidatorPerformanceParams {
+        era_id: EraId::new(1),
+    });
+static GET_VALIDATOR_PERFORMANCE_RESULT: Lazy<GetValidatorPerformanceResult> = Lazy::new(|| {
+    let performance = ValidatorPerformance {
+        proposals_assigned: 5,
+        proposals_made: 4,
+        votes: 20,
+        finality_signatures: 19,
+    };
+    GetValidatorPerformanceResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        era_id: Some(EraId::new(1)),
+        validators: vec![JsonValidatorPerformance::new(
+            PublicKey::doc_example().clone(),
+            performance,
+        )],
+    }
+});
 static GET_CHAINSPEC_RESULT: Lazy<GetChainspecResult> = Lazy::new(|| GetChainspecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     chainspec_bytes: ChainspecRawBytes::new(vec![42, 42].into(), None, None),
@@ -348,6 +389,194 @@ impl RpcWithoutParams for GetValidatorChanges {
     }
 }
 
+/// A validator's vote for a round's proposal, and when this node received it.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonVoteArrival {
+    /// The public key of the validator.
+    validator: PublicKey,
+    /// When the vote was received.
+    time: Timestamp,
+}
+
+/// The timeline of a consensus round, as observed by this node.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonConsensusRound {
+    /// The era in which the round took place.
+    era_id: EraId,
+    /// The round number in Zug, or the round's start time in milliseconds in Highway.
+    round_id: u64,
+    /// The public key of the round's leader.
+    leader: PublicKey,
+    /// When the round's proposal was received or created.
+    proposal_time: Option<Timestamp>,
+    /// The votes for the round's proposal, in the order in which they were received.
+    votes: Vec<JsonVoteArrival>,
+    /// When this node's proposal timeout for the round expired, if it did.
+    timeout_time: Option<Timestamp>,
+    /// When the round's proposal was finalized, if it was.
+    finalization_time: Option<Timestamp>,
+}
+
+impl From<ConsensusRound> for JsonConsensusRound {
+    fn from(round: ConsensusRound) -> Self {
+        let ConsensusRound { era_id, timeline } = round;
+        JsonConsensusRound {
+            era_id,
+            round_id: timeline.round_id,
+            leader: timeline.leader,
+            proposal_time: timeline.proposal_time,
+            votes: timeline
+                .votes
+                .into_iter()
+                .map(|vote| JsonVoteArrival {
+                    validator: vote.validator,
+                    time: vote.time,
+                })
+                .collect(),
+            timeout_time: timeline.timeout_time,
+            finalization_time: timeline.finalization_time,
+        }
+    }
+}
+
+/// Result for the "info_get_consensus_rounds" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetConsensusRoundsResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The most recent complete consensus rounds, oldest first.
+    pub rounds: Vec<JsonConsensusRound>,
+}
+
+impl DocExample for GetConsensusRoundsResult {
+    fn doc_example() -> &'static Self {
+        &GET_CONSENSUS_ROUNDS_RESULT
+    }
+}
+
+/// "info_get_consensus_rounds" RPC.
+pub struct GetConsensusRounds {}
+
+#[async_trait]
+impl RpcWithoutParams for GetConsensusRounds {
+    const METHOD: &'static str = "info_get_consensus_rounds";
+    type ResponseResult = GetConsensusRoundsResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        let rounds = effect_builder.get_consensus_rounds().await;
+        Ok(Self::ResponseResult {
+            api_version,
+            rounds: rounds.into_iter().map(JsonConsensusRound::from).collect(),
+        })
+    }
+}
+
+/// Params for the "info_get_validator_performance" RPC.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetValidatorPerformanceParams {
+    /// The era to return the validator performance for.
+    pub era_id: EraId,
+}
+
+impl DocExample for GetValidatorPerformanceParams {
+    fn doc_example() -> &'static Self {
+        &GET_VALIDATOR_PERFORMANCE_PARAMS
+    }
+}
+
+/// A validator's participation in an era.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct JsonValidatorPerformance {
+    /// The public key of the validator.
+    public_key: PublicKey,
+    /// The number of rounds in which the validator was the leader.
+    proposals_assigned: u64,
+    /// The number of rounds led by the validator in which a proposal was made.
+    proposals_made: u64,
+    /// The number of rounds in which the validator voted for the proposal.
+    votes: u64,
+    /// The number of the era's blocks for which this node has a finality signature by the
+    /// validator.
+    finality_signatures: u64,
+}
+
+impl JsonValidatorPerformance {
+    fn new(public_key: PublicKey, performance: ValidatorPerformance) -> Self {
+        JsonValidatorPerformance {
+            public_key,
+            proposals_assigned: performance.proposals_assigned,
+            proposals_made: performance.proposals_made,
+            votes: performance.votes,
+            finality_signatures: performance.finality_signatures,
+        }
+    }
+}
+
+/// Result for the "info_get_validator_performance" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetValidatorPerformanceResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The era the performance refers to, or `None` if no performance is stored for the requested
+    /// era.  It is only stored for eras in which this node observed all rounds.
+    pub era_id: Option<EraId>,
+    /// The performance of each validator in the era.
+    pub validators: Vec<JsonValidatorPerformance>,
+}
+
+impl DocExample for GetValidatorPerformanceResult {
+    fn doc_example() -> &'static Self {
+        &GET_VALIDATOR_PERFORMANCE_RESULT
+    }
+}
+
+/// "info_get_validator_performance" RPC.
+pub struct GetValidatorPerformance {}
+
+#[async_trait]
+impl RpcWithOptionalParams for GetValidatorPerformance {
+    const METHOD: &'static str = "info_get_validator_performance";
+    type OptionalRequestParams = GetValidatorPerformanceParams;
+    type ResponseResult = GetValidatorPerformanceResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        maybe_params: Option<Self::OptionalRequestParams>,
+    ) -> Result<Self::ResponseResult, Error> {
+        let maybe_era_id = maybe_params.map(|params| params.era_id);
+        let result = match effect_builder.get_validator_performance(maybe_era_id).await {
+            Some((era_id, performance)) => Self::ResponseResult {
+                api_version,
+                era_id: Some(era_id),
+                validators: performance
+                    .into_iter()
+                    .map(|(public_key, performance)| {
+                        JsonValidatorPerformance::new(public_key, performance)
+                    })
+                    .collect(),
+            },
+            None => Self::ResponseResult {
+                api_version,
+                era_id: None,
+                validators: vec![],
+            },
+        };
+        Ok(result)
+    }
+}
+
 /// Result for the "info_get_chainspec" RPC.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetChainspecResult {
@@ -103,6 +103,7 @@ impl RpcWithParams for SpeculativeExec {
             state_root_hash: *block.state_root_hash(),
             block_time: block.timestamp(),
             protocol_version: block.protocol_version(),
+            random_beacon: block.header().random_beacon().unwrap_or_default(),
         };
 
         let accept_deploy_result = effect_builder
@@ -164,7 +165,9 @@ impl RpcWithParams for SpeculativeExec {
                     | EngineStateError::FailedToGetStoredWithdraws
                     | EngineStateError::FailedToGetWithdrawPurses
                     | EngineStateError::FailedToRetrieveUnbondingDelay
-                    | EngineStateError::FailedToRetrieveEraId => {
+                    | EngineStateError::FailedToRetrieveEraId
+                    | EngineStateError::FailedToRetrieveGovernanceProposals
+                    | EngineStateError::FailedToRetrieveGovernanceParameterOverrides => {
                         Error::new(ReservedErrorCode::InternalError, error.to_string())
                     }
                     _ => Error::new(
@@ -12,16 +12,21 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult},
+    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult, SystemContractRegistry},
     storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
 use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
-    bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    system::{
+        auction::{ERA_END_TIMESTAMP_MILLIS_KEY, ERA_ID_KEY},
+        mint::{self, PurseLock},
+        AUCTION,
+    },
+    CLTyped, CLValue, EraId, Key, ProtocolVersion, PublicKey, SecretKey,
+    StoredValue as DomainStoredValue, URef, U512,
 };
 
 use crate::{
@@ -57,6 +62,8 @@ static GET_BALANCE_PARAMS: Lazy<GetBalanceParams> = Lazy::new(|| GetBalanceParam
 static GET_BALANCE_RESULT: Lazy<GetBalanceResult> = Lazy::new(|| GetBalanceResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     balance_value: U512::from(123_456),
+    available_balance: U512::from(123_456),
+    locked_balance: U512::zero(),
     merkle_proof: MERKLE_PROOF.clone(),
 });
 static GET_AUCTION_INFO_PARAMS: Lazy<GetAuctionInfoParams> = Lazy::new(|| GetAuctionInfoParams {
@@ -236,6 +243,11 @@ pub struct GetBalanceResult {
     pub api_version: ProtocolVersion,
     /// The balance value.
     pub balance_value: U512,
+    /// The part of the balance which can be transferred out of the purse as of the requested
+    /// state.
+    pub available_balance: U512,
+    /// The part of the balance which is held back by the purse's lock as of the requested state.
+    pub locked_balance: U512,
     /// The Merkle proof.
     pub merkle_proof: String,
 }
@@ -315,10 +327,70 @@ impl RpcWithParams for GetBalance {
 
         let merkle_proof = base16::encode_lower(&proof_bytes);
 
+        // Evaluate the lock placed on the purse, if any, against the requested state: the era is
+        // that recorded by the auction, and the time is the end of the previous era.  A lock
+        // expiring mid-era is therefore reported as locked until the era ends.
+        let maybe_purse_lock: Option<PurseLock> = query_cl_value(
+            effect_builder,
+            params.state_root_hash,
+            mint::purse_lock_key(purse_uref),
+            vec![],
+        )
+        .await?;
+
+        let available_balance =
+            match maybe_purse_lock {
+                Some(purse_lock) => {
+                    let registry: SystemContractRegistry = query_cl_value(
+                        effect_builder,
+                        params.state_root_hash,
+                        Key::SystemContractRegistry,
+                        vec![],
+                    )
+                    .await?
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorCode::FailedToGetBalance,
+                            "missing system contract registry",
+                        )
+                    })?;
+                    let auction_key = registry
+                        .get(AUCTION)
+                        .map(|auction_hash| Key::from(*auction_hash))
+                        .ok_or_else(|| {
+                            Error::new(ErrorCode::FailedToGetBalance, "missing auction contract")
+                        })?;
+                    let maybe_era_id: Option<EraId> = query_cl_value(
+                        effect_builder,
+                        params.state_root_hash,
+                        auction_key,
+                        vec![ERA_ID_KEY.to_string()],
+                    )
+                    .await?;
+                    let maybe_era_end_timestamp_millis: Option<u64> = query_cl_value(
+                        effect_builder,
+                        params.state_root_hash,
+                        auction_key,
+                        vec![ERA_END_TIMESTAMP_MILLIS_KEY.to_string()],
+                    )
+                    .await?;
+                    match (maybe_era_id, maybe_era_end_timestamp_millis) {
+                        (Some(era_id), Some(era_end_timestamp_millis)) => purse_lock
+                            .available_balance(balance_value, era_end_timestamp_millis, era_id),
+                        // Without an era to evaluate the lock against, treat the whole balance as
+                        // locked.
+                        _ => U512::zero(),
+                    }
+                }
+                None => balance_value,
+            };
+
         // Return the result.
         let result = Self::ResponseResult {
             api_version,
             balance_value,
+            available_balance,
+            locked_balance: balance_value - available_balance,
             merkle_proof,
         };
         Ok(result)
@@ -1164,6 +1236,59 @@ pub(super) async fn handle_query_result<REv: ReactorEventT>(
     }
 }
 
+/// Queries the `CLValue` stored under `base_key` and `path`, returning `None` if there is none.
+async fn query_cl_value<REv: ReactorEventT, T: CLTyped + FromBytes>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    base_key: Key,
+    path: Vec<String>,
+) -> Result<Option<T>, Error> {
+    let query_result = effect_builder
+        .make_request(
+            |responder| RpcRequest::QueryGlobalState {
+                state_root_hash,
+                base_key,
+                path,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await;
+
+    match query_result {
+        Ok(QueryResult::Success { value, .. }) => match *value {
+            DomainStoredValue::CLValue(cl_value) => match cl_value.into_t::<T>() {
+                Ok(value) => Ok(Some(value)),
+                Err(error) => {
+                    let message = format!("failed to parse value under {}: {}", base_key, error);
+                    info!("{}", message);
+                    Err(Error::new(ReservedErrorCode::InternalError, message))
+                }
+            },
+            other => {
+                let message = format!("unexpected value under {}: {:?}", base_key, other);
+                info!("{}", message);
+                Err(Error::new(ReservedErrorCode::InternalError, message))
+            }
+        },
+        Ok(QueryResult::ValueNotFound(_)) => Ok(None),
+        Ok(query_result) => {
+            info!(?query_result, "query failed");
+            Err(Error::new(
+                ErrorCode::QueryFailed,
+                format!("{:?}", query_result),
+            ))
+        }
+        Err(error) => {
+            info!(?error, "query failed to execute");
+            Err(Error::new(
+                ErrorCode::QueryFailedToExecute,
+                format!("{:?}", error),
+            ))
+        }
+    }
+}
+
 async fn get_account<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     state_root_hash: Digest,
@@ -96,7 +96,7 @@ use crate::{
         BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployHeader, DeployId,
         DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
         FinalizedApprovals, FinalizedBlock, LegacyDeploy, MaxTtl, NodeId, SyncLeap,
-        SyncLeapIdentifier, ValueOrChunk,
+        SyncLeapIdentifier, ValidatorPerformance, ValueOrChunk,
     },
     utils::{display_error, WithDir},
     NodeRng,
@@ -129,7 +129,7 @@ const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 9;
+const MAX_DB_COUNT: u32 = 10;
 /// Key under which completed blocks are to be stored.
 const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
 /// Name of the file created when initializing a force resync.
@@ -148,6 +148,8 @@ const _STORAGE_EVENT_SIZE: usize = mem::size_of::<Event>();
 const_assert!(_STORAGE_EVENT_SIZE <= 32);
 
 type FinalizedBlockAndDeploys = (FinalizedBlock, Vec<Deploy>);
+/// An era and the performance of each validator in it.
+type EraValidatorPerformance = (EraId, BTreeMap<PublicKey, ValidatorPerformance>);
 
 const STORAGE_FILES: [&str; 5] = [
     "data.lmdb",
@@ -192,6 +194,9 @@ pub struct Storage {
     /// The finalized approvals database.
     #[data_size(skip)]
     finalized_approvals_db: Database,
+    /// The validator performance database, keyed by era ID.
+    #[data_size(skip)]
+    validator_performance_db: Database,
     /// A map of block height to block ID.
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
@@ -213,6 +218,8 @@ pub struct Storage {
     /// The number of eras relative to the highest block's era which are considered as recent for
     /// the purpose of deciding how to respond to a `NetRequest::SyncLeap`.
     recent_era_count: u64,
+    /// Whether to aggregate BLS finality signatures in sync leaps and blocks served to clients.
+    aggregate_finality_signatures: bool,
     #[data_size(skip)]
     metrics: Option<Metrics>,
     /// The maximum TTL of a deploy.
@@ -417,6 +424,8 @@ impl Storage {
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
         let approvals_hashes_db =
             env.create_db(Some("approvals_hashes"), DatabaseFlags::empty())?;
+        let validator_performance_db =
+            env.create_db(Some("validator_performance"), DatabaseFlags::empty())?;
 
         // We now need to restore the block-height index. Log messages allow timing here.
         info!("indexing block store");
@@ -435,7 +444,7 @@ impl Storage {
         for row in cursor.iter() {
             let (_, raw_val) = row?;
             let mut body_txn = env.begin_ro_txn()?;
-            let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
+            let block_header: BlockHeader = lmdb_ext::deserialize_block_header(raw_val)?;
             let maybe_block_body =
                 get_body_for_block_header(&mut body_txn, block_header.body_hash(), block_body_db);
             if let Some(invalid_era) = hard_reset_to_start_of_era {
@@ -508,6 +517,7 @@ impl Storage {
             transfer_db,
             state_store_db,
             finalized_approvals_db,
+            validator_performance_db,
             block_height_index,
             switch_block_era_id_index,
             deploy_hash_index,
@@ -517,6 +527,7 @@ impl Storage {
             enable_mem_deduplication: config.enable_mem_deduplication,
             serialized_item_pool: ObjectPool::new(config.mem_pool_prune_interval),
             recent_era_count,
+            aggregate_finality_signatures: config.aggregate_finality_signatures,
             max_ttl,
             metrics,
         };
@@ -987,10 +998,11 @@ impl Storage {
                     debug_assert_eq!(&block_hash, block.hash());
                     return Ok(responder.respond(None).ignore());
                 }
-                let block_signatures = match self.get_block_signatures(&mut txn, &block_hash)? {
+                let mut block_signatures = match self.get_block_signatures(&mut txn, &block_hash)? {
                     Some(signatures) => signatures,
                     None => BlockSignatures::new(block_hash, block.header().era_id()),
                 };
+                self.maybe_aggregate(&mut block_signatures);
                 if block_signatures.verify().is_err() {
                     error!(?block, "invalid block signatures for block");
                     debug_assert!(block_signatures.verify().is_ok());
@@ -1039,10 +1051,11 @@ impl Storage {
                 };
 
                 let hash = block.hash();
-                let block_signatures = match self.get_block_signatures(&mut txn, hash)? {
+                let mut block_signatures = match self.get_block_signatures(&mut txn, hash)? {
                     Some(signatures) => signatures,
                     None => BlockSignatures::new(*hash, block.header().era_id()),
                 };
+                self.maybe_aggregate(&mut block_signatures);
                 responder
                     .respond(Some(BlockWithMetadata {
                         block,
@@ -1069,10 +1082,11 @@ impl Storage {
                     None => return Ok(responder.respond(None).ignore()),
                 };
                 let hash = highest_block.hash();
-                let block_signatures = match self.get_block_signatures(&mut txn, hash)? {
+                let mut block_signatures = match self.get_block_signatures(&mut txn, hash)? {
                     Some(signatures) => signatures,
                     None => BlockSignatures::new(*hash, highest_block.header().era_id()),
                 };
+                self.maybe_aggregate(&mut block_signatures);
                 responder
                     .respond(Some(BlockWithMetadata {
                         block: highest_block,
@@ -1084,7 +1098,7 @@ impl Storage {
                 signatures,
                 responder,
             } => {
-                if signatures.proofs.is_empty() {
+                if signatures.is_empty() {
                     error!(
                         ?signatures,
                         "should not attempt to store empty collection of block signatures"
@@ -1095,11 +1109,9 @@ impl Storage {
                 let old_data: Option<BlockSignatures> =
                     txn.get_value(self.block_metadata_db, &signatures.block_hash)?;
                 let new_data = match old_data {
-                    None => signatures,
+                    None => *signatures,
                     Some(mut data) => {
-                        for (public_key, sig) in signatures.proofs {
-                            data.insert_proof(public_key, sig);
-                        }
+                        data.merge(*signatures);
                         data
                     }
                 };
@@ -1184,9 +1196,107 @@ impl Storage {
                     .respond(self.key_block_height_for_activation_point)
                     .ignore()
             }
+            StorageRequest::PutValidatorPerformance {
+                era_id,
+                performance,
+                responder,
+            } => {
+                let mut txn = self.env.begin_rw_txn()?;
+                // The first record wins: after a restart, the era supervisor may only have
+                // observed part of the era's rounds.
+                let outcome = txn.put_value(
+                    self.validator_performance_db,
+                    &era_id.to_le_bytes(),
+                    &performance,
+                    false,
+                )?;
+                txn.commit()?;
+                responder.respond(outcome).ignore()
+            }
+            StorageRequest::GetValidatorPerformance { era_id, responder } => responder
+                .respond(self.get_validator_performance(era_id)?)
+                .ignore(),
         })
     }
 
+    /// Retrieves the validators' performance in the given era, or in the most recent era for which
+    /// it is stored if `None`.
+    ///
+    /// The finality signatures are counted from those stored for the era's blocks at the time of
+    /// the request, since some of them usually arrive after the end of the era.
+    fn get_validator_performance(
+        &self,
+        maybe_era_id: Option<EraId>,
+    ) -> Result<Option<EraValidatorPerformance>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let mut get = |era_id: EraId| {
+            txn.get_value::<_, BTreeMap<PublicKey, ValidatorPerformance>>(
+                self.validator_performance_db,
+                &era_id.to_le_bytes(),
+            )
+            .map(|maybe_performance| maybe_performance.map(|performance| (era_id, performance)))
+        };
+        let found = match maybe_era_id {
+            Some(era_id) => get(era_id)?,
+            None => {
+                let mut found = None;
+                for era_id in self.switch_block_era_id_index.keys().rev() {
+                    found = get(*era_id)?;
+                    if found.is_some() {
+                        break;
+                    }
+                }
+                found
+            }
+        };
+        let (era_id, mut performance) = match found {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        for signatures in self.get_era_block_signatures(&mut txn, era_id)? {
+            for public_key in signatures.signers() {
+                performance
+                    .entry(public_key.clone())
+                    .or_default()
+                    .finality_signatures += 1;
+            }
+        }
+        Ok(Some((era_id, performance)))
+    }
+
+    /// Retrieves the finality signatures stored for the blocks of the given era, if the era's
+    /// switch block is stored.
+    fn get_era_block_signatures<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        era_id: EraId,
+    ) -> Result<Vec<BlockSignatures>, FatalStorageError> {
+        let end_height = match self.get_switch_block_header_by_era_id(txn, era_id)? {
+            Some(switch_block_header) => switch_block_header.height(),
+            None => return Ok(vec![]),
+        };
+        let start_height = match era_id.predecessor() {
+            Some(parent_era_id) => self
+                .get_switch_block_header_by_era_id(txn, parent_era_id)?
+                .map_or(0, |parent_header| parent_header.height() + 1),
+            None => 0,
+        };
+        let mut era_block_signatures = vec![];
+        for block_hash in self
+            .block_height_index
+            .range(start_height..=end_height)
+            .map(|(_, block_hash)| block_hash)
+        {
+            if let Some(signatures) = self.get_block_signatures(txn, block_hash)? {
+                // If the parent era's switch block is missing we may look at earlier eras' blocks.
+                if signatures.era_id == era_id {
+                    era_block_signatures.push(signatures);
+                }
+            }
+        }
+        Ok(era_block_signatures)
+    }
+
     fn put_finality_signature(
         &mut self,
         signature: Box<FinalitySignature>,
@@ -2418,11 +2528,14 @@ impl Storage {
 
         // The `highest_complete_block_header` and `trusted_block_header` are both within the
         // highest complete block range, thus so are all the switch blocks between them.
-        if let Some(signed_block_headers) = self.get_signed_block_headers(
+        if let Some(mut signed_block_headers) = self.get_signed_block_headers(
             &mut txn,
             &trusted_block_header,
             &highest_complete_block_header,
         )? {
+            for signed_block_header in signed_block_headers.iter_mut() {
+                self.maybe_aggregate(&mut signed_block_header.block_signatures);
+            }
             return Ok(FetchResponse::Fetched(SyncLeap {
                 trusted_ancestor_only: false,
                 trusted_block_header,
@@ -2434,6 +2547,13 @@ impl Storage {
         Ok(FetchResponse::NotFound(sync_leap_identifier))
     }
 
+    /// Aggregates the BLS finality signatures into a single proof, if configured to do so.
+    fn maybe_aggregate(&self, block_signatures: &mut BlockSignatures) {
+        if self.aggregate_finality_signatures {
+            block_signatures.aggregate();
+        }
+    }
+
     /// Creates a serialized representation of a `FetchResponse` and the resulting message.
     ///
     /// If the given item is `Some`, returns a serialization of `FetchResponse::Fetched`. If
@@ -2813,6 +2933,10 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// Whether to aggregate BLS finality signatures into a single proof per block in sync leaps
+    /// and in blocks returned to clients.
+    #[serde(default)]
+    pub aggregate_finality_signatures: bool,
 }
 
 impl Default for Config {
@@ -2826,6 +2950,7 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            aggregate_finality_signatures: false,
         }
     }
 }
@@ -2950,7 +3075,7 @@ fn construct_block_body_to_block_header_reverse_lookup(
     let mut block_body_hash_to_header_map: BTreeMap<Digest, BlockHeader> = BTreeMap::new();
     for row in txn.open_ro_cursor(*block_header_db)?.iter() {
         let (_raw_key, raw_val) = row?;
-        let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
+        let block_header: BlockHeader = lmdb_ext::deserialize_block_header(raw_val)?;
         block_body_hash_to_header_map.insert(block_header.body_hash().to_owned(), block_header);
     }
     Ok(block_body_hash_to_header_map)
@@ -10,7 +10,7 @@
 //! Serialization errors are unified into a generic, type erased `std` error to allow for easy
 //! interchange of the serialization format if desired.
 
-use std::any::TypeId;
+use std::any::{Any, TypeId};
 
 use lmdb::{Database, RwTransaction, Transaction, WriteFlags};
 use serde::{de::DeserializeOwned, Serialize};
@@ -21,7 +21,16 @@ use casper_types::{
     system::auction::UnbondingPurse,
 };
 
+use crate::types::{BlockHeader, BlockSignatures, Deploy, DeployV1};
+
 const UNBONDING_PURSE_V2_MAGIC_BYTES: &[u8] = &[121, 17, 133, 179, 91, 63, 69, 222];
+const BLOCK_SIGNATURES_V2_MAGIC_BYTES: &[u8] = &[64, 203, 27, 148, 230, 7, 159, 102];
+const BLOCK_HEADER_V2_MAGIC_BYTES: &[u8] = &[183, 52, 10, 241, 96, 138, 27, 205];
+const DEPLOY_V2_MAGIC_BYTES: &[u8] = &[29, 160, 88, 214, 5, 117, 240, 63];
+
+/// The bytes of `None` serialized with `bincode`, used to fill in a trailing optional field which
+/// is missing from the legacy version of a struct.
+const BINCODE_ENCODED_NONE: [u8; 4] = [0; 4];
 
 /// Error wrapper for lower-level storage errors.
 ///
@@ -182,15 +191,21 @@ where
 }
 
 /// Serializes `value` into the buffer.
-/// In case the `value` is of the `UnbondingPurse` type it uses the specialized
-/// function to provide compatibility with the legacy version of the `UnbondingPurse` struct.
-/// See [`serialize_unbonding_purse`] for more details.
+/// In case the `value` is of the `UnbondingPurse`, `BlockSignatures`, `BlockHeader` or `Deploy`
+/// type it uses the specialized function to provide compatibility with the legacy version of the
+/// struct. See [`serialize_unbonding_purse`] for more details.
 // TODO: Get rid of the 'static bound.
 pub(crate) fn serialize_internal<V: 'static + Serialize>(
     value: &V,
 ) -> Result<Vec<u8>, LmdbExtError> {
     let buffer = if TypeId::of::<UnbondingPurse>() == TypeId::of::<V>() {
         serialize_unbonding_purse(value)?
+    } else if TypeId::of::<BlockSignatures>() == TypeId::of::<V>() {
+        serialize_with_magic_bytes(value, BLOCK_SIGNATURES_V2_MAGIC_BYTES)?
+    } else if TypeId::of::<BlockHeader>() == TypeId::of::<V>() {
+        serialize_with_magic_bytes(value, BLOCK_HEADER_V2_MAGIC_BYTES)?
+    } else if TypeId::of::<Deploy>() == TypeId::of::<V>() {
+        serialize_with_magic_bytes(value, DEPLOY_V2_MAGIC_BYTES)?
     } else {
         serialize(value)?
     };
@@ -198,14 +213,22 @@ pub(crate) fn serialize_internal<V: 'static + Serialize>(
 }
 
 /// Deserializes an object from the raw bytes.
-/// In case the expected object is of the `UnbondingPurse` type it uses the specialized
-/// function to provide compatibility with the legacy version of the `UnbondingPurse` struct.
-/// See [`deserialize_unbonding_purse`] for more details.
+/// In case the expected object is of the `UnbondingPurse`, `BlockSignatures`, `BlockHeader` or
+/// `Deploy` type it uses the specialized function to provide compatibility with the legacy version
+/// of the struct. See [`deserialize_unbonding_purse`] for more details.
 pub(crate) fn deserialize_internal<V: 'static + DeserializeOwned>(
     raw: &[u8],
 ) -> Result<Option<V>, LmdbExtError> {
     if TypeId::of::<UnbondingPurse>() == TypeId::of::<V>() {
         deserialize_unbonding_purse(raw).map(Some)
+    } else if TypeId::of::<BlockSignatures>() == TypeId::of::<V>() {
+        // The legacy `BlockSignatures` lacks the trailing `aggregated_proof` field.
+        deserialize_with_magic_bytes(raw, BLOCK_SIGNATURES_V2_MAGIC_BYTES).map(Some)
+    } else if TypeId::of::<BlockHeader>() == TypeId::of::<V>() {
+        deserialize_block_header(raw).map(Some)
+    } else if TypeId::of::<Deploy>() == TypeId::of::<V>() {
+        let deploy: Box<dyn Any> = Box::new(deserialize_deploy(raw)?);
+        Ok(deploy.downcast::<V>().ok().map(|deploy| *deploy))
     } else {
         deserialize(raw).map(Some)
     }
@@ -265,9 +288,10 @@ pub(super) fn deserialize<T: DeserializeOwned>(raw: &[u8]) -> Result<T, LmdbExtE
     bincode::deserialize(raw).map_err(|err| LmdbExtError::DataCorrupted(Box::new(err)))
 }
 
-/// Returns `true` if the specified bytes represent the legacy version of `UnbondingPurse`.
-fn is_legacy(raw: &[u8]) -> bool {
-    !raw.starts_with(UNBONDING_PURSE_V2_MAGIC_BYTES)
+/// Returns `true` if the specified bytes represent the legacy version of a struct, i.e. they don't
+/// begin with the given magic bytes.
+fn is_legacy(raw: &[u8], magic_bytes: &[u8]) -> bool {
+    !raw.starts_with(magic_bytes)
 }
 
 /// Deserializes `UnbondingPurse` from a buffer.
@@ -281,11 +305,38 @@ fn is_legacy(raw: &[u8]) -> bool {
 pub(super) fn deserialize_unbonding_purse<T: DeserializeOwned>(
     raw: &[u8],
 ) -> Result<T, LmdbExtError> {
-    const BINCODE_ENCODED_NONE: [u8; 4] = [0; 4];
-    if is_legacy(raw) {
+    deserialize_with_magic_bytes(raw, UNBONDING_PURSE_V2_MAGIC_BYTES)
+}
+
+/// Deserializes `BlockHeader` from a buffer.
+/// The legacy `BlockHeader` lacks the trailing `extension` field, see
+/// [`deserialize_unbonding_purse`] for how both versions are told apart.
+pub(super) fn deserialize_block_header<T: DeserializeOwned>(raw: &[u8]) -> Result<T, LmdbExtError> {
+    deserialize_with_magic_bytes(raw, BLOCK_HEADER_V2_MAGIC_BYTES)
+}
+
+/// Deserializes `Deploy` from a buffer.
+/// The legacy `Deploy` lacks the optional fields of its header, which are not trailing, so it is
+/// deserialized as a [`DeployV1`] and converted.  See [`deserialize_unbonding_purse`] for how both
+/// versions are told apart.
+pub(super) fn deserialize_deploy(raw: &[u8]) -> Result<Deploy, LmdbExtError> {
+    if is_legacy(raw, DEPLOY_V2_MAGIC_BYTES) {
+        deserialize::<DeployV1>(raw).map(Deploy::from)
+    } else {
+        deserialize(&raw[DEPLOY_V2_MAGIC_BYTES.len()..])
+    }
+}
+
+/// Deserializes a struct whose current version is serialized with the given magic bytes prefix
+/// and whose legacy version lacks only a trailing optional field.
+fn deserialize_with_magic_bytes<T: DeserializeOwned>(
+    raw: &[u8],
+    magic_bytes: &[u8],
+) -> Result<T, LmdbExtError> {
+    if is_legacy(raw, magic_bytes) {
         deserialize(&[raw, &BINCODE_ENCODED_NONE].concat())
     } else {
-        deserialize(&raw[UNBONDING_PURSE_V2_MAGIC_BYTES.len()..])
+        deserialize(&raw[magic_bytes.len()..])
     }
 }
 
@@ -301,7 +352,16 @@ pub(super) fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, LmdbExtError
 /// deserialization routine to detect the version of the `UnbondingPurse` struct.
 #[inline(always)]
 pub(super) fn serialize_unbonding_purse<T: Serialize>(value: &T) -> Result<Vec<u8>, LmdbExtError> {
-    let mut serialized = UNBONDING_PURSE_V2_MAGIC_BYTES.to_vec();
+    serialize_with_magic_bytes(value, UNBONDING_PURSE_V2_MAGIC_BYTES)
+}
+
+/// Serializes into a buffer prefixed with the given magic bytes.
+#[inline(always)]
+fn serialize_with_magic_bytes<T: Serialize>(
+    value: &T,
+    magic_bytes: &[u8],
+) -> Result<Vec<u8>, LmdbExtError> {
+    let mut serialized = magic_bytes.to_vec();
     serialized.extend(bincode::serialize(value).map_err(|err| LmdbExtError::Other(Box::new(err)))?);
     Ok(serialized)
 }
@@ -15,8 +15,8 @@ use smallvec::smallvec;
 
 use casper_types::{
     generate_ed25519_keypair, system::auction::UnbondingPurse, testing::TestRng, AccessRights,
-    EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, TimeDiff,
-    Transfer, Transform, TransformEntry, URef, U512,
+    EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, Signature,
+    TimeDiff, Transfer, Transform, TransformEntry, URef, U512,
 };
 
 use super::{
@@ -37,7 +37,7 @@ use crate::{
         BlockHash, BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
         BlockSignatures, Chainspec, ChainspecRawBytes, Deploy, DeployHash, DeployMetadata,
         DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature, LegacyDeploy,
-        SyncLeapIdentifier, TestBlockBuilder,
+        SyncLeapIdentifier, TestBlockBuilder, ValidatorPerformance,
     },
     utils::{Loadable, WithDir},
 };
@@ -57,6 +57,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        aggregate_finality_signatures: false,
     }
 }
 
@@ -155,6 +156,7 @@ fn create_sync_leap_test_chain(
             block_hash: *block.hash(),
             era_id: block.header().era_id(),
             proofs,
+            aggregated_proof: None,
         };
 
         if !non_signed_blocks.contains(&block.height()) {
@@ -439,7 +441,7 @@ fn put_block_signatures(
 ) -> bool {
     let response = harness.send_request(storage, move |responder| {
         StorageRequest::PutBlockSignatures {
-            signatures,
+            signatures: Box::new(signatures),
             responder,
         }
         .into()
@@ -1189,6 +1191,7 @@ fn prepare_exec_result_with_transfer(
         rng.gen(),
         rng.gen(),
         Some(rng.gen()),
+        None,
     );
     let transform = TransformEntry {
         key: Key::DeployInfo((*deploy_hash).into()).to_formatted_string(),
@@ -2117,6 +2120,140 @@ fn unbonding_purse_serialization_roundtrip() {
     assert!(deserialized.new_validator().is_some())
 }
 
+#[test]
+fn should_read_legacy_block_signatures() {
+    // The `BlockSignatures` struct without the `aggregated_proof` field.
+    #[derive(Serialize)]
+    struct LegacyBlockSignatures {
+        block_hash: BlockHash,
+        era_id: EraId,
+        proofs: BTreeMap<PublicKey, Signature>,
+    }
+
+    let mut rng = TestRng::new();
+    let block = Block::random(&mut rng);
+    let expected = random_signatures(&mut rng, &block);
+    let legacy = LegacyBlockSignatures {
+        block_hash: expected.block_hash,
+        era_id: expected.era_id,
+        proofs: expected.proofs.clone(),
+    };
+
+    let legacy_bytes = bincode::serialize(&legacy).expect("serialization");
+    let deserialized: BlockSignatures = deserialize_internal(&legacy_bytes)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(expected, deserialized);
+}
+
+#[test]
+fn legacy_block_header_deserialization() {
+    let mut rng = TestRng::new();
+    let block = TestBlockBuilder::new().legacy_header().build(&mut rng);
+    let expected = block.header().clone();
+
+    // The legacy `BlockHeader` is encoded like the current one without the trailing `None`.
+    let mut legacy_bytes = bincode::serialize(&expected).expect("serialization");
+    assert_eq!(legacy_bytes.pop(), Some(0));
+
+    let deserialized: BlockHeader = deserialize_internal(&legacy_bytes)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(expected, deserialized);
+    assert_eq!(block.hash(), &deserialized.block_hash());
+}
+
+#[test]
+fn extended_block_header_serialization_roundtrip() {
+    let mut rng = TestRng::new();
+    let original = TestBlockBuilder::new().build(&mut rng).header().clone();
+    assert!(original.extension().is_some());
+
+    let serialized = serialize_internal(&original).expect("serialization");
+    let deserialized: BlockHeader = deserialize_internal(&serialized)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(original, deserialized);
+}
+
+#[test]
+fn legacy_deploy_deserialization() {
+    let mut rng = TestRng::new();
+    let expected = Deploy::random(&mut rng);
+    let header = expected.header();
+
+    // The legacy `Deploy` is encoded like the current one without the header's `fee_limits`.
+    let legacy_bytes = [
+        bincode::serialize(expected.hash()),
+        bincode::serialize(header.account()),
+        bincode::serialize(&header.timestamp()),
+        bincode::serialize(&header.ttl()),
+        bincode::serialize(&header.gas_price()),
+        bincode::serialize(header.body_hash()),
+        bincode::serialize(header.dependencies()),
+        bincode::serialize(header.chain_name()),
+        bincode::serialize(expected.payment()),
+        bincode::serialize(expected.session()),
+        bincode::serialize(expected.approvals()),
+    ]
+    .into_iter()
+    .map(|bytes| bytes.expect("serialization"))
+    .collect::<Vec<_>>()
+    .concat();
+
+    let deserialized: Deploy = deserialize_internal(&legacy_bytes)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(expected, deserialized);
+    assert_eq!(deserialized.has_valid_hash(), Ok(()));
+}
+
+#[test]
+fn deploy_serialization_roundtrip() {
+    let mut rng = TestRng::new();
+    let original = Deploy::random(&mut rng);
+
+    let serialized = serialize_internal(&original).expect("serialization");
+    let deserialized: Deploy = deserialize_internal(&serialized)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(original, deserialized);
+}
+
+#[test]
+fn block_signatures_with_aggregated_proof_serialization_roundtrip() {
+    let mut rng = TestRng::new();
+    let block = Block::random(&mut rng);
+    let block_hash = *block.hash();
+    let era_id = block.header().era_id();
+    let mut original = BlockSignatures::new(block_hash, era_id);
+    for _ in 0..3 {
+        let secret_key = SecretKey::random_bls(&mut rng);
+        let signature = FinalitySignature::create(
+            block_hash,
+            era_id,
+            &secret_key,
+            PublicKey::from(&secret_key),
+        );
+        original.insert_proof(signature.public_key, signature.signature);
+    }
+    original.aggregate();
+    assert!(original.aggregated_proof.is_some());
+
+    let serialized = serialize_internal(&original).expect("serialization");
+    let deserialized: BlockSignatures = deserialize_internal(&serialized)
+        .expect("should deserialize w/o error")
+        .expect("should be Some");
+
+    assert_eq!(original, deserialized);
+    assert!(deserialized.verify().is_ok());
+}
+
 // Clippy complains because there's a `OnceCell` in `FinalitySignature`, hence it should not be used
 // as a key in `BTreeSet`. However, we don't change the content of the cell during the course of the
 // test so there's no risk the hash or order of keys will change.
@@ -2235,3 +2372,60 @@ fn should_initialize_block_metadata_db() {
     assert_signatures(&storage, *block_3.hash(), vec![]);
     assert_signatures(&storage, *block_4.hash(), vec![]);
 }
+
+#[test]
+fn should_count_finality_signatures_in_validator_performance() {
+    // Era 2 consists of the blocks at heights 2, 3 and 4. The one at height 3 is not signed.
+    let (mut storage, _, blocks) = create_sync_leap_test_chain(&[3], false, None);
+    let mut harness = ComponentHarness::<UnitTestEvent>::default();
+    let validator = get_block_signatures(&mut storage, *blocks[2].hash())
+        .unwrap()
+        .proofs
+        .into_keys()
+        .next()
+        .unwrap();
+
+    let mut put_performance = |storage: &mut Storage, performance: ValidatorPerformance| {
+        let performance = iter::once((validator.clone(), performance)).collect();
+        let response = harness.send_request(storage, |responder| {
+            StorageRequest::PutValidatorPerformance {
+                era_id: EraId::new(2),
+                performance,
+                responder,
+            }
+            .into()
+        });
+        assert!(harness.is_idle());
+        response
+    };
+    let stored = ValidatorPerformance {
+        proposals_assigned: 2,
+        proposals_made: 1,
+        votes: 3,
+        finality_signatures: 0,
+    };
+    assert!(put_performance(&mut storage, stored));
+    assert!(!put_performance(
+        &mut storage,
+        ValidatorPerformance::default()
+    ));
+
+    let expected = ValidatorPerformance {
+        finality_signatures: 2,
+        ..stored
+    };
+    let (era_id, performance) = storage
+        .get_validator_performance(Some(EraId::new(2)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(EraId::new(2), era_id);
+    assert_eq!(Some(&expected), performance.get(&validator));
+
+    // Without an era ID, the most recent era with stored performance is returned.
+    let (era_id, _) = storage.get_validator_performance(None).unwrap().unwrap();
+    assert_eq!(EraId::new(2), era_id);
+    assert!(storage
+        .get_validator_performance(Some(EraId::new(3)))
+        .unwrap()
+        .is_none());
+}
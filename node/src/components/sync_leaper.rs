@@ -16,7 +16,7 @@ use tracing::{debug, error, info, warn};
 
 use crate::{
     components::{
-        fetcher::{self, FetchResult, FetchedData},
+        fetcher::{self, FetchResult, FetchedData, PeerScores, Tag},
         Component,
     },
     effect::{requests::FetcherRequest, EffectBuilder, EffectExt, Effects},
@@ -75,17 +75,21 @@ pub(crate) struct SyncLeaper {
     leap_activity: Option<LeapActivity>,
     chainspec: Arc<Chainspec>,
     #[data_size(skip)]
+    peer_scores: PeerScores,
+    #[data_size(skip)]
     metrics: Metrics,
 }
 
 impl SyncLeaper {
     pub(crate) fn new(
         chainspec: Arc<Chainspec>,
+        peer_scores: PeerScores,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(SyncLeaper {
             leap_activity: None,
             chainspec,
+            peer_scores,
             metrics: Metrics::new(registry)?,
         })
     }
@@ -276,36 +280,44 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
         match event {
             Event::AttemptLeap {
                 sync_leap_identifier,
                 peers_to_ask,
-            } => match self.register_leap_attempt(sync_leap_identifier, peers_to_ask) {
-                RegisterLeapAttemptOutcome::DoNothing => Effects::new(),
-                RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(peers) => {
-                    let mut effects = Effects::new();
-                    peers.into_iter().for_each(|peer| {
-                        effects.extend(
-                            effect_builder
-                                .fetch::<SyncLeap>(
-                                    sync_leap_identifier,
-                                    peer,
-                                    Box::new(SyncLeapValidationMetaData::from_chainspec(
-                                        self.chainspec.as_ref(),
-                                    )),
-                                )
-                                .event(move |fetch_result| Event::FetchedSyncLeapFromPeer {
-                                    sync_leap_identifier,
-                                    fetch_result,
-                                }),
-                        )
-                    });
-                    effects
+            } => {
+                let peers_to_ask = self.peer_scores.choose_peers(
+                    Tag::SyncLeap,
+                    peers_to_ask,
+                    self.chainspec.core_config.simultaneous_peer_requests as usize,
+                    rng,
+                );
+                match self.register_leap_attempt(sync_leap_identifier, peers_to_ask) {
+                    RegisterLeapAttemptOutcome::DoNothing => Effects::new(),
+                    RegisterLeapAttemptOutcome::FetchSyncLeapFromPeers(peers) => {
+                        let mut effects = Effects::new();
+                        peers.into_iter().for_each(|peer| {
+                            effects.extend(
+                                effect_builder
+                                    .fetch::<SyncLeap>(
+                                        sync_leap_identifier,
+                                        peer,
+                                        Box::new(SyncLeapValidationMetaData::from_chainspec(
+                                            self.chainspec.as_ref(),
+                                        )),
+                                    )
+                                    .event(move |fetch_result| Event::FetchedSyncLeapFromPeer {
+                                        sync_leap_identifier,
+                                        fetch_result,
+                                    }),
+                            )
+                        });
+                        effects
+                    }
                 }
-            },
+            }
             Event::FetchedSyncLeapFromPeer {
                 sync_leap_identifier,
                 fetch_result,
@@ -5,7 +5,7 @@ use prometheus::Registry;
 
 use crate::{
     components::{
-        fetcher::{self, FetchResult, FetchedData},
+        fetcher::{self, FetchResult, FetchedData, PeerScores},
         sync_leaper::{LeapState, PeerState, RegisterLeapAttemptOutcome},
     },
     types::{Block, BlockHash, Chainspec, NodeId, SyncLeap, SyncLeapIdentifier},
@@ -26,7 +26,7 @@ pub(crate) fn make_test_sync_leap(rng: &mut TestRng) -> SyncLeap {
 fn make_sync_leaper(rng: &mut TestRng) -> SyncLeaper {
     let chainspec = Chainspec::random(rng);
     let registry = Registry::new();
-    SyncLeaper::new(Arc::new(chainspec), &registry).unwrap()
+    SyncLeaper::new(Arc::new(chainspec), PeerScores::default(), &registry).unwrap()
 }
 
 fn assert_peers(expected: &[NodeId], actual: &Vec<(NodeId, PeerState)>) {
@@ -35,7 +35,7 @@ use crate::{
     },
     reactor::main_reactor::MainEvent,
     types::{
-        chainspec::{ProtocolConfig, CHAINSPEC_FILENAME},
+        chainspec::{ConsensusProtocolSwitch, ProtocolConfig, CHAINSPEC_FILENAME},
         ActivationPoint, Chainspec,
     },
     NodeRng,
@@ -178,6 +178,8 @@ pub(crate) struct UpgradeWatcher {
     root_dir: PathBuf,
     state: ComponentState,
     next_upgrade: Option<NextUpgrade>,
+    /// The consensus protocol switches scheduled by the current chainspec.
+    consensus_protocol_switches: Vec<ConsensusProtocolSwitch>,
 }
 
 impl UpgradeWatcher {
@@ -193,7 +195,12 @@ impl UpgradeWatcher {
             .ok_or(Error::NoChainspecDirParent)?;
 
         let current_version = chainspec.protocol_config.version;
-        let next_upgrade = next_upgrade(root_dir.clone(), current_version);
+        let consensus_protocol_switches = chainspec.core_config.consensus_protocol_switches.clone();
+        let next_upgrade = next_upgrade(
+            root_dir.clone(),
+            current_version,
+            &consensus_protocol_switches,
+        );
 
         let upgrade_watcher = UpgradeWatcher {
             current_version,
@@ -201,6 +208,7 @@ impl UpgradeWatcher {
             root_dir,
             state: ComponentState::Uninitialized,
             next_upgrade,
+            consensus_protocol_switches,
         };
 
         Ok(upgrade_watcher)
@@ -237,14 +245,16 @@ impl UpgradeWatcher {
     {
         let root_dir = self.root_dir.clone();
         let current_version = self.current_version;
+        let current_switches = self.consensus_protocol_switches.clone();
         let mut effects = async move {
-            let maybe_next_upgrade =
-                task::spawn_blocking(move || next_upgrade(root_dir, current_version))
-                    .await
-                    .unwrap_or_else(|error| {
-                        warn!(%error, "failed to join tokio task");
-                        None
-                    });
+            let maybe_next_upgrade = task::spawn_blocking(move || {
+                next_upgrade(root_dir, current_version, &current_switches)
+            })
+            .await
+            .unwrap_or_else(|error| {
+                warn!(%error, "failed to join tokio task");
+                None
+            });
             effect_builder
                 .upgrade_watcher_announcement(maybe_next_upgrade)
                 .await
@@ -374,6 +384,15 @@ where
 struct UpgradePoint {
     #[serde(rename = "protocol")]
     pub(crate) protocol_config: ProtocolConfig,
+    #[serde(rename = "core", default)]
+    pub(crate) core_config: UpgradePointCoreConfig,
+}
+
+/// The parts of the core config of a chainspec that need to be checked before an upgrade.
+#[derive(Default, Deserialize)]
+struct UpgradePointCoreConfig {
+    #[serde(default)]
+    consensus_protocol_switches: Vec<ConsensusProtocolSwitch>,
 }
 
 impl UpgradePoint {
@@ -447,10 +466,48 @@ fn next_installed_version(
     Ok(next_version)
 }
 
+/// Returns whether `next_switches` keeps all of `current_switches` unchanged and schedules at most
+/// one new switch, taking effect at `activation_era`.
+///
+/// Eras before the activation point have already been run with the protocols given by the current
+/// switches, so an upgrade can't change them.
+fn is_valid_switch_schedule(
+    current_switches: &[ConsensusProtocolSwitch],
+    next_switches: &[ConsensusProtocolSwitch],
+    activation_era: EraId,
+) -> bool {
+    if !next_switches.starts_with(current_switches) {
+        warn!(
+            ?current_switches,
+            ?next_switches,
+            "next chainspec changes previously scheduled consensus protocol switches"
+        );
+        return false;
+    }
+    match &next_switches[current_switches.len()..] {
+        [] => true,
+        [new_switch] if new_switch.era_id == activation_era => true,
+        new_switches => {
+            warn!(
+                ?new_switches,
+                activation_era = activation_era.value(),
+                "next chainspec must schedule at most one new consensus protocol switch, at its \
+                activation point"
+            );
+            false
+        }
+    }
+}
+
 /// Uses `next_installed_version()` to find the next versioned subdir.  If it exists, reads the
 /// UpgradePoint file from there and returns its version and activation point.  Returns `None` if
-/// there is no greater version available, or if any step errors.
-fn next_upgrade(dir: PathBuf, current_version: ProtocolVersion) -> Option<NextUpgrade> {
+/// there is no greater version available, if its consensus protocol switches are incompatible with
+/// `current_switches`, or if any step errors.
+fn next_upgrade(
+    dir: PathBuf,
+    current_version: ProtocolVersion,
+    current_switches: &[ConsensusProtocolSwitch],
+) -> Option<NextUpgrade> {
     let next_version = match next_installed_version(&dir, current_version) {
         Ok(version) => version,
         Err(_error) => {
@@ -482,6 +539,14 @@ fn next_upgrade(dir: PathBuf, current_version: ProtocolVersion) -> Option<NextUp
         return None;
     }
 
+    if !is_valid_switch_schedule(
+        current_switches,
+        &upgrade_point.core_config.consensus_protocol_switches,
+        upgrade_point.protocol_config.activation_point.era_id(),
+    ) {
+        return None;
+    }
+
     Some(NextUpgrade::from(upgrade_point.protocol_config))
 }
 
@@ -493,7 +558,7 @@ mod tests {
     use crate::{
         logging,
         types::{
-            chainspec::{ActivationPoint, CHAINSPEC_FILENAME},
+            chainspec::{ActivationPoint, ConsensusProtocolName, CHAINSPEC_FILENAME},
             ChainspecRawBytes,
         },
         utils::Loadable,
@@ -617,7 +682,7 @@ mod tests {
         let tempdir = tempfile::tempdir().expect("should create temp dir");
 
         let next_point = |current_version: ProtocolVersion| {
-            next_upgrade(tempdir.path().to_path_buf(), current_version).unwrap()
+            next_upgrade(tempdir.path().to_path_buf(), current_version, &[]).unwrap()
         };
 
         let mut rng = crate::new_rng();
@@ -634,7 +699,7 @@ mod tests {
         let tempdir = tempfile::tempdir().expect("should create temp dir");
 
         let maybe_next_point = |current_version: ProtocolVersion| {
-            next_upgrade(tempdir.path().to_path_buf(), current_version)
+            next_upgrade(tempdir.path().to_path_buf(), current_version, &[])
         };
 
         let mut rng = crate::new_rng();
@@ -675,6 +740,66 @@ mod tests {
         assert!(maybe_next_point(V0_9_9).is_none());
     }
 
+    #[test]
+    fn should_not_get_upgrade_with_invalid_consensus_protocol_switches() {
+        let tempdir = tempfile::tempdir().expect("should create temp dir");
+        let mut rng = crate::new_rng();
+
+        let mut chainspec = install_chainspec(&mut rng, tempdir.path(), V1_0_0);
+        let activation_era = EraId::new(10);
+        chainspec.protocol_config.activation_point = ActivationPoint::EraId(activation_era);
+        let earlier_switch = ConsensusProtocolSwitch {
+            era_id: EraId::new(5),
+            consensus_protocol: ConsensusProtocolName::Zug,
+        };
+        let new_switch = ConsensusProtocolSwitch {
+            era_id: activation_era,
+            consensus_protocol: ConsensusProtocolName::Highway,
+        };
+
+        let path = tempdir
+            .path()
+            .join(dir_name_from_version(V1_0_0))
+            .join(CHAINSPEC_FILENAME);
+        let maybe_next_point =
+            |switches: Vec<ConsensusProtocolSwitch>,
+             current_switches: &[ConsensusProtocolSwitch]| {
+                let mut chainspec = chainspec.clone();
+                chainspec.core_config.consensus_protocol_switches = switches;
+                // Converting to a `toml::Value` first moves the switches, which are encoded as an
+                // array of tables, after the other values of the core config.
+                let value = toml::Value::try_from(&chainspec).expect("should convert to toml");
+                fs::write(
+                    &path,
+                    toml::to_string_pretty(&value).expect("should encode to toml"),
+                )
+                .expect("should install chainspec");
+                next_upgrade(tempdir.path().to_path_buf(), V0_9_9, current_switches)
+            };
+
+        // A new switch at the activation point is accepted, with or without earlier switches.
+        assert!(maybe_next_point(vec![new_switch], &[]).is_some());
+        assert!(maybe_next_point(vec![earlier_switch, new_switch], &[earlier_switch]).is_some());
+        assert!(maybe_next_point(vec![earlier_switch], &[earlier_switch]).is_some());
+
+        // A new switch must be scheduled at the activation point.
+        assert!(maybe_next_point(vec![earlier_switch], &[]).is_none());
+        let later_switch = ConsensusProtocolSwitch {
+            era_id: activation_era.successor(),
+            ..new_switch
+        };
+        assert!(maybe_next_point(vec![later_switch], &[]).is_none());
+        assert!(maybe_next_point(vec![earlier_switch, new_switch], &[]).is_none());
+
+        // Earlier switches must not be changed or removed.
+        let changed_switch = ConsensusProtocolSwitch {
+            consensus_protocol: ConsensusProtocolName::Highway,
+            ..earlier_switch
+        };
+        assert!(maybe_next_point(vec![changed_switch, new_switch], &[earlier_switch]).is_none());
+        assert!(maybe_next_point(vec![new_switch], &[earlier_switch]).is_none());
+    }
+
     #[test]
     fn should_register_unstaged_upgrade() {
         let _ = logging::init();
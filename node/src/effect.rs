@@ -135,13 +135,16 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, EraDump, ProposedBlock, ValidatorChange},
+        consensus::{ClContext, ConsensusRound, EraDump, ProposedBlock, ValidatorChange},
         contract_runtime::{ContractRuntimeError, EraValidatorsRequest},
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::{blocklist::BlocklistJustification, FromIncoming, NetworkInsights},
+        network::{
+            blocklist::BlocklistJustification, FromIncoming, NetworkInsights, PeerGroupChange,
+            PeerGroups,
+        },
         upgrade_watcher::NextUpgrade,
     },
     contract_runtime::SpeculativeExecutionState,
@@ -153,7 +156,7 @@ use crate::{
         BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
         DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
         FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlock,
-        MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
+        MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId, ValidatorPerformance,
     },
     utils::{fmt_limit::FmtLimit, SharedFlag, Source},
 };
@@ -772,6 +775,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Applies changes to the operator-managed peer groups and returns the resulting groups.
+    pub(crate) async fn update_peer_groups(self, changes: Vec<PeerGroupChange>) -> PeerGroups
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::PeerGroups { changes, responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Gets a map of the current network peers to their socket addresses.
     pub(crate) async fn network_peers(self) -> BTreeMap<NodeId, String>
     where
@@ -1261,7 +1276,7 @@ impl<REv> EffectBuilder<REv> {
     {
         self.make_request(
             |responder| StorageRequest::PutBlockSignatures {
-                signatures,
+                signatures: Box::new(signatures),
                 responder,
             },
             QueueKind::ToStorage,
@@ -1359,6 +1374,42 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Stores the validators' participation in the consensus rounds of the given era.
+    pub(crate) async fn put_validator_performance(
+        self,
+        era_id: EraId,
+        performance: BTreeMap<PublicKey, ValidatorPerformance>,
+    ) -> bool
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutValidatorPerformance {
+                era_id,
+                performance,
+                responder,
+            },
+            QueueKind::ToStorage,
+        )
+        .await
+    }
+
+    /// Requests the validators' performance in the given era, or in the most recent era for
+    /// which it is stored if `None`.
+    pub(crate) async fn get_validator_performance(
+        self,
+        era_id: Option<EraId>,
+    ) -> Option<(EraId, BTreeMap<PublicKey, ValidatorPerformance>)>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetValidatorPerformance { era_id, responder },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Synchronize global state under the given root hash.
     pub(crate) async fn sync_global_state(
         self,
@@ -2068,6 +2119,15 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Returns the timelines of the most recent complete consensus rounds, oldest first.
+    pub(crate) async fn get_consensus_rounds(self) -> Vec<ConsensusRound>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(ConsensusRequest::Rounds, QueueKind::Consensus)
+            .await
+    }
+
     /// Dump consensus state for a specific era, using the supplied function to serialize the
     /// output.
     pub(crate) async fn diagnostics_port_dump_consensus_state(
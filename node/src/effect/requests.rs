@@ -37,13 +37,13 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, ProposedBlock, ValidatorChange},
+        consensus::{ClContext, ConsensusRound, ProposedBlock, ValidatorChange},
         contract_runtime::EraValidatorsRequest,
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::NetworkInsights,
+        network::{NetworkInsights, PeerGroupChange, PeerGroups},
         upgrade_watcher::NextUpgrade,
     },
     contract_runtime::{ContractRuntimeError, SpeculativeExecutionState},
@@ -56,7 +56,7 @@ use crate::{
         BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
         DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
         FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlockState,
-        NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
+        NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId, ValidatorPerformance,
     },
     utils::{DisplayIter, Source},
 };
@@ -213,6 +213,13 @@ pub(crate) enum NetworkInfoRequest {
     Insight {
         responder: Responder<NetworkInsights>,
     },
+    /// Apply changes to the operator-managed peer groups, then get the resulting groups.
+    PeerGroups {
+        /// Changes to apply, in order.
+        changes: Vec<PeerGroupChange>,
+        /// Responder to be called with the updated peer groups.
+        responder: Responder<PeerGroups>,
+    },
 }
 
 impl Display for NetworkInfoRequest {
@@ -230,6 +237,16 @@ impl Display for NetworkInfoRequest {
             NetworkInfoRequest::Insight { responder: _ } => {
                 formatter.write_str("get networking insights")
             }
+            NetworkInfoRequest::PeerGroups {
+                changes,
+                responder: _,
+            } => {
+                write!(
+                    formatter,
+                    "update peer groups: {}",
+                    DisplayIter::new(changes.iter())
+                )
+            }
         }
     }
 }
@@ -463,7 +480,7 @@ pub(crate) enum StorageRequest {
     /// Store finality signatures.
     PutBlockSignatures {
         /// Signatures that are to be stored.
-        signatures: BlockSignatures,
+        signatures: Box<BlockSignatures>,
         /// Responder to call with the result, if true then the signatures were successfully
         /// stored.
         responder: Responder<bool>,
@@ -498,6 +515,25 @@ pub(crate) enum StorageRequest {
     },
     /// Retrieve the height of the final block of the previous protocol version, if known.
     GetKeyBlockHeightForActivationPoint { responder: Responder<Option<u64>> },
+    /// Store the validators' participation in the consensus rounds of an era.
+    PutValidatorPerformance {
+        /// The era the performance refers to.
+        era_id: EraId,
+        /// The performance of each validator.
+        performance: BTreeMap<PublicKey, ValidatorPerformance>,
+        /// Responder to call with the result.  Returns true if the performance was stored on this
+        /// attempt or false if it was previously stored.
+        responder: Responder<bool>,
+    },
+    /// Retrieve the validators' performance in an era, including the finality signatures stored
+    /// for the era's blocks.
+    GetValidatorPerformance {
+        /// The era to retrieve the performance for, or `None` for the most recent era for which
+        /// it is stored.
+        era_id: Option<EraId>,
+        /// Responder to call with the era and performance, if stored.
+        responder: Responder<Option<(EraId, BTreeMap<PublicKey, ValidatorPerformance>)>>,
+    },
 }
 
 impl Display for StorageRequest {
@@ -620,6 +656,13 @@ impl Display for StorageRequest {
                     "get key block height for current activation point"
                 )
             }
+            StorageRequest::PutValidatorPerformance { era_id, .. } => {
+                write!(formatter, "put validator performance in {}", era_id)
+            }
+            StorageRequest::GetValidatorPerformance { era_id, .. } => match era_id {
+                Some(era_id) => write!(formatter, "get validator performance in {}", era_id),
+                None => write!(formatter, "get latest validator performance"),
+            },
         }
     }
 }
@@ -1076,6 +1119,8 @@ pub(crate) enum ConsensusRequest {
     Status(Responder<Option<(PublicKey, Option<TimeDiff>)>>),
     /// Request for a list of validator status changes, by public key.
     ValidatorChanges(Responder<BTreeMap<PublicKey, Vec<(EraId, ValidatorChange)>>>),
+    /// Request for the timelines of the most recent complete consensus rounds.
+    Rounds(Responder<Vec<ConsensusRound>>),
 }
 
 /// ChainspecLoader component requests.
@@ -33,6 +33,7 @@ pub(crate) mod failpoints;
 pub mod logging;
 pub(crate) mod protocol;
 pub(crate) mod reactor;
+pub(crate) mod signer;
 #[cfg(test)]
 pub(crate) mod testing;
 pub(crate) mod tls;
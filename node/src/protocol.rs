@@ -17,7 +17,9 @@ use crate::{
         consensus,
         fetcher::{FetchItem, FetchResponse, Tag},
         gossiper,
-        network::{EstimatorWeights, FromIncoming, GossipedAddress, MessageKind, Payload},
+        network::{
+            EstimatorWeights, FromIncoming, GossipedAddress, MessageKind, MessageType, Payload,
+        },
     },
     effect::{
         incoming::{
@@ -146,6 +148,42 @@ impl Payload for Message {
         }
     }
 
+    #[inline]
+    fn message_type(&self) -> Option<MessageType> {
+        let message_type = match self {
+            Message::Consensus(_) => MessageType::Consensus,
+            Message::ConsensusRequest(_) => MessageType::Consensus,
+            Message::BlockGossiper(_) => MessageType::BlockGossip,
+            Message::DeployGossiper(_) => MessageType::DeployGossip,
+            Message::FinalitySignatureGossiper(_) => MessageType::FinalitySignatureGossip,
+            Message::AddressGossiper(_) => MessageType::AddressGossip,
+            Message::GetRequest { tag, .. } => match tag {
+                Tag::Deploy => MessageType::DeployRequests,
+                Tag::LegacyDeploy => MessageType::LegacyDeployRequests,
+                Tag::Block => MessageType::BlockRequests,
+                Tag::BlockHeader => MessageType::BlockHeaderRequests,
+                Tag::TrieOrChunk => MessageType::TrieRequests,
+                Tag::FinalitySignature => MessageType::FinalitySignatureRequests,
+                Tag::SyncLeap => MessageType::SyncLeapRequests,
+                Tag::ApprovalsHashes => MessageType::ApprovalsHashesRequests,
+                Tag::BlockExecutionResults => MessageType::ExecutionResultsRequests,
+            },
+            Message::GetResponse { tag, .. } => match tag {
+                Tag::Deploy => MessageType::DeployResponses,
+                Tag::LegacyDeploy => MessageType::LegacyDeployResponses,
+                Tag::Block => MessageType::BlockResponses,
+                Tag::BlockHeader => MessageType::BlockHeaderResponses,
+                Tag::TrieOrChunk => MessageType::TrieResponses,
+                Tag::FinalitySignature => MessageType::FinalitySignatureResponses,
+                Tag::SyncLeap => MessageType::SyncLeapResponses,
+                Tag::ApprovalsHashes => MessageType::ApprovalsHashesResponses,
+                Tag::BlockExecutionResults => MessageType::ExecutionResultsResponses,
+            },
+            Message::FinalitySignature(_) => MessageType::FinalitySignatureBroadcasts,
+        };
+        Some(message_type)
+    }
+
     fn is_unsafe_for_syncing_peers(&self) -> bool {
         match self {
             Message::Consensus(_) => false,
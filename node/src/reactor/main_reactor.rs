@@ -34,12 +34,13 @@ use crate::{
         block_accumulator::{self, BlockAccumulator},
         block_synchronizer::{self, BlockSynchronizer},
         block_validator::{self, BlockValidator},
-        consensus::{self, EraSupervisor},
+        consensus::{self, slashing_protection::SlashingProtection, EraSupervisor},
         contract_runtime::ContractRuntime,
         deploy_acceptor::{self, DeployAcceptor},
         deploy_buffer::{self, DeployBuffer},
         diagnostics_port::DiagnosticsPort,
         event_stream_server::{self, EventStreamServer},
+        fetcher::PeerScores,
         gossiper::{self, GossipItem, Gossiper},
         metrics::Metrics,
         network::{self, GossipedAddress, Identity as NetworkIdentity, Network},
@@ -163,6 +164,8 @@ pub(crate) struct MainReactor {
     // record retrieval
     sync_leaper: SyncLeaper,
     fetchers: Fetchers, // <-- this contains all fetchers to reduce top-level clutter
+    #[data_size(skip)]
+    peer_scores: PeerScores,
 
     // Non-components.
     //   metrics
@@ -1009,7 +1012,11 @@ impl reactor::Reactor for MainReactor {
 
         let trusted_hash = config.value().node.trusted_hash;
         let (root_dir, config) = config.into_parts();
-        let (our_secret_key, our_public_key) = config.consensus.load_keys(&root_dir)?;
+        let our_signer = config.consensus.load_signer(&root_dir)?;
+        let slashing_protection = SlashingProtection::open(
+            root_dir.join(&config.consensus.slashing_protection_path),
+            our_signer.public_key().clone(),
+        )?;
         let validator_matrix = ValidatorMatrix::new(
             chainspec.core_config.finality_threshold_fraction,
             chainspec
@@ -1018,8 +1025,7 @@ impl reactor::Reactor for MainReactor {
                 .as_ref()
                 .and_then(|global_state_update| global_state_update.validators.clone()),
             chainspec.protocol_config.activation_point.era_id(),
-            our_secret_key.clone(),
-            our_public_key.clone(),
+            our_signer.clone(),
             chainspec.core_config.auction_delay,
         );
 
@@ -1059,21 +1065,27 @@ impl reactor::Reactor for MainReactor {
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             max_delegators_per_validator,
+            chainspec.core_config.inactive_eras_before_eviction,
             registry,
             chainspec.core_config.administrators.clone(),
             chainspec.core_config.allow_auction_bids,
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.deploy_config,
+            chainspec
+                .core_config
+                .has_extended_block_headers(protocol_version),
         )?;
 
         let network = Network::new(
             config.network.clone(),
             network_identity,
-            Some((our_secret_key.clone(), our_public_key.clone())),
+            Some(our_signer.clone()),
             registry,
             chainspec.as_ref(),
             validator_matrix.clone(),
+            Some(storage.root_path()),
         )?;
 
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -1105,8 +1117,9 @@ impl reactor::Reactor for MainReactor {
         let shutdown_trigger = ShutdownTrigger::new();
 
         // local / remote data management
-        let sync_leaper = SyncLeaper::new(chainspec.clone(), registry)?;
-        let fetchers = Fetchers::new(&config.fetcher, registry)?;
+        let peer_scores = PeerScores::default();
+        let sync_leaper = SyncLeaper::new(chainspec.clone(), peer_scores.clone(), registry)?;
+        let fetchers = Fetchers::new(&config.fetcher, &peer_scores, registry)?;
 
         // gossipers
         let block_gossiper = Gossiper::<{ Block::ID_IS_COMPLETE_ITEM }, _>::new(
@@ -1114,23 +1127,30 @@ impl reactor::Reactor for MainReactor {
             config.gossip,
             registry,
         )?;
-        let deploy_gossiper = Gossiper::<{ Deploy::ID_IS_COMPLETE_ITEM }, _>::new(
+        let mut deploy_gossiper = Gossiper::<{ Deploy::ID_IS_COMPLETE_ITEM }, _>::new(
             "deploy_gossiper",
             config.gossip,
             registry,
         )?;
-        let finality_signature_gossiper =
+        let mut finality_signature_gossiper =
             Gossiper::<{ FinalitySignature::ID_IS_COMPLETE_ITEM }, _>::new(
                 "finality_signature_gossiper",
                 config.gossip,
                 registry,
             )?;
+        if !chainspec
+            .core_config
+            .has_gossip_reconciliation(protocol_version)
+        {
+            deploy_gossiper.disable_reconciliation();
+            finality_signature_gossiper.disable_reconciliation();
+        }
 
         // consensus
         let consensus = EraSupervisor::new(
             storage.root_path(),
-            our_secret_key,
-            our_public_key,
+            our_signer,
+            Arc::new(slashing_protection),
             config.consensus,
             chainspec.clone(),
             registry,
@@ -1151,6 +1171,7 @@ impl reactor::Reactor for MainReactor {
             chainspec.clone(),
             chainspec.core_config.simultaneous_peer_requests,
             validator_matrix.clone(),
+            peer_scores.clone(),
             registry,
         )?;
         let block_validator = BlockValidator::new(Arc::clone(&chainspec), config.block_validator);
@@ -1175,6 +1196,7 @@ impl reactor::Reactor for MainReactor {
             event_stream_server,
             deploy_acceptor,
             fetchers,
+            peer_scores,
 
             block_gossiper,
             deploy_gossiper,
@@ -1213,7 +1235,7 @@ impl reactor::Reactor for MainReactor {
         // shut down immediately for upgrade.
         let should_upgrade_immediately = reactor.upgrade_watcher.next_upgrade_activation_point()
             == Some(reactor.chainspec.protocol_config.activation_point.era_id());
-        let effects = if should_upgrade_immediately {
+        let mut effects = if should_upgrade_immediately {
             info!("MainReactor: immediate shutdown for upgrade");
             effect_builder
                 .immediately()
@@ -1223,6 +1245,20 @@ impl reactor::Reactor for MainReactor {
                 .immediately()
                 .event(|()| MainEvent::ReactorCrank)
         };
+
+        // Deploys and finality signatures are periodically reconciled with random peers.
+        effects.extend(reactor::wrap_effects(
+            MainEvent::DeployGossiper,
+            reactor
+                .deploy_gossiper
+                .schedule_reconciliation(effect_builder),
+        ));
+        effects.extend(reactor::wrap_effects(
+            MainEvent::FinalitySignatureGossiper,
+            reactor
+                .finality_signature_gossiper
+                .schedule_reconciliation(effect_builder),
+        ));
         Ok((reactor, effects))
     }
 
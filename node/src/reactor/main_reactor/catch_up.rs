@@ -282,7 +282,7 @@ impl MainReactor {
         let leap_status = self.sync_leaper.leap_status();
         info!(%block_hash, %leap_status, "CatchUp: status");
         match leap_status {
-            LeapState::Idle => self.catch_up_leaper_idle(effect_builder, rng, block_hash),
+            LeapState::Idle => self.catch_up_leaper_idle(effect_builder, block_hash),
             LeapState::Awaiting { .. } => CatchUpInstruction::CheckLater(
                 "sync leaper is awaiting response".to_string(),
                 self.control_logic_default_delay.into(),
@@ -293,7 +293,7 @@ impl MainReactor {
                 ..
             } => self.catch_up_leap_received(effect_builder, rng, *best_available, from_peers),
             LeapState::Failed { error, .. } => {
-                self.catch_up_leap_failed(effect_builder, rng, block_hash, error)
+                self.catch_up_leap_failed(effect_builder, block_hash, error)
             }
         }
     }
@@ -301,7 +301,6 @@ impl MainReactor {
     fn catch_up_leap_failed(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-        rng: &mut NodeRng,
         block_hash: BlockHash,
         error: LeapActivityError,
     ) -> CatchUpInstruction {
@@ -311,20 +310,16 @@ impl MainReactor {
             remaining_attempts = %self.max_attempts.saturating_sub(self.attempts),
             "CatchUp: failed leap",
         );
-        self.catch_up_leaper_idle(effect_builder, rng, block_hash)
+        self.catch_up_leaper_idle(effect_builder, block_hash)
     }
 
     fn catch_up_leaper_idle(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-        rng: &mut NodeRng,
         block_hash: BlockHash,
     ) -> CatchUpInstruction {
-        // we get a random sampling of peers to ask.
-        let peers_to_ask = self.net.fully_connected_peers_random(
-            rng,
-            self.chainspec.core_config.simultaneous_peer_requests as usize,
-        );
+        // the sync leaper chooses which of the peers to ask, based on their past performance.
+        let peers_to_ask = self.net.fully_connected_peers();
         if peers_to_ask.is_empty() {
             return CatchUpInstruction::CheckLater(
                 "no peers".to_string(),
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tracing::{debug, error, info, trace};
 
 use casper_hashing::Digest;
@@ -18,7 +18,9 @@ use crate::{
         upgrading_instruction::UpgradingInstruction, utils, validate::ValidateInstruction,
         MainEvent, MainReactor, ReactorState,
     },
-    types::{BlockHash, BlockHeader, BlockPayload, FinalizedBlock, MetaBlockState},
+    types::{
+        BlockHash, BlockHeader, BlockHeaderExtension, BlockPayload, FinalizedBlock, MetaBlockState,
+    },
     NodeRng,
 };
 
@@ -31,6 +33,10 @@ impl MainReactor {
         if self.attempts > self.max_attempts {
             return fatal!(effect_builder, "exceeded reattempt tolerance").ignore();
         }
+        // Forget how well peers served us once we are disconnected from them.
+        let connected_peers = self.net.peers();
+        self.peer_scores
+            .retain_peers(|peer| connected_peers.contains_key(peer));
         let (delay, mut effects) = self.do_crank(effect_builder, rng);
         effects.extend(
             async move {
@@ -350,6 +356,7 @@ impl MainReactor {
             post_state_hash,
             BlockHash::default(),
             Digest::default(),
+            None,
         );
 
         let era_id = EraId::default();
@@ -444,6 +451,7 @@ impl MainReactor {
                         post_state_hash,
                         header.block_hash(),
                         header.accumulated_seed(),
+                        header.extension().cloned(),
                     );
 
                     let finalized_block = FinalizedBlock::new(
@@ -516,6 +524,7 @@ impl MainReactor {
                 *state_root_hash,
                 block_hash,
                 accumulated_seed,
+                block_header.extension().cloned(),
             );
         }
         Ok(())
@@ -527,6 +536,7 @@ impl MainReactor {
         pre_state_root_hash: Digest,
         parent_hash: BlockHash,
         parent_seed: Digest,
+        parent_extension: Option<BlockHeaderExtension>,
     ) {
         // a better approach might be to have an announcement for immediate switch block
         // creation, which the contract runtime handles and sets itself into
@@ -537,8 +547,49 @@ impl MainReactor {
             pre_state_root_hash,
             parent_hash,
             parent_seed,
+            parent_extension,
         );
         self.contract_runtime.set_initial_state(initial_pre_state);
+        self.apply_parameter_overrides(pre_state_root_hash);
+    }
+
+    /// Applies the changes of applied governance proposals to chainspec parameters which aren't
+    /// held in global state, such as the block gas limit, to the chainspec used by the components.
+    fn apply_parameter_overrides(&mut self, state_root_hash: Digest) {
+        let overrides = match self.contract_runtime.parameter_overrides(state_root_hash) {
+            Ok(overrides) => overrides,
+            Err(error) => {
+                error!(%state_root_hash, %error, "failed to read governance parameter overrides");
+                return;
+            }
+        };
+        if overrides.is_empty() {
+            return;
+        }
+        let chainspec = match self.chainspec.with_parameter_overrides(&overrides) {
+            Some(chainspec) => chainspec,
+            None => {
+                error!(?overrides, "invalid governance parameter overrides");
+                return;
+            }
+        };
+        if chainspec == *self.chainspec {
+            return;
+        }
+
+        info!(
+            block_gas_limit = chainspec.deploy_config.block_gas_limit,
+            opcode_costs = ?overrides.opcode_costs,
+            "applying governance parameter overrides to the chainspec"
+        );
+        let chainspec = Arc::new(chainspec);
+        self.contract_runtime.update_chainspec(&chainspec);
+        self.deploy_acceptor
+            .set_deploy_config(chainspec.deploy_config);
+        self.deploy_buffer
+            .set_deploy_config(chainspec.deploy_config);
+        self.block_validator.set_chainspec(Arc::clone(&chainspec));
+        self.chainspec = chainspec;
     }
 
     pub(super) fn update_last_progress(
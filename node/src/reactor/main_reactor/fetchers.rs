@@ -2,7 +2,11 @@ use datasize::DataSize;
 use prometheus::Registry;
 
 use crate::{
-    components::{fetcher, fetcher::Fetcher, Component},
+    components::{
+        fetcher,
+        fetcher::{Fetcher, PeerScores},
+        Component,
+    },
     effect::{announcements::DeployAcceptorAnnouncement, EffectBuilder, Effects},
     reactor,
     reactor::main_reactor::MainEvent,
@@ -30,24 +34,52 @@ pub(super) struct Fetchers {
 impl Fetchers {
     pub(super) fn new(
         config: &FetcherConfig,
+        peer_scores: &PeerScores,
         metrics_registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(Fetchers {
-            sync_leap_fetcher: Fetcher::new("sync_leap_fetcher", config, metrics_registry)?,
-            block_header_by_hash_fetcher: Fetcher::new("block_header", config, metrics_registry)?,
-            approvals_hashes_fetcher: Fetcher::new("approvals_hashes", config, metrics_registry)?,
+            sync_leap_fetcher: Fetcher::new(
+                "sync_leap_fetcher",
+                config,
+                peer_scores.clone(),
+                metrics_registry,
+            )?,
+            block_header_by_hash_fetcher: Fetcher::new(
+                "block_header",
+                config,
+                peer_scores.clone(),
+                metrics_registry,
+            )?,
+            approvals_hashes_fetcher: Fetcher::new(
+                "approvals_hashes",
+                config,
+                peer_scores.clone(),
+                metrics_registry,
+            )?,
             finality_signature_fetcher: Fetcher::new(
                 "finality_signature_fetcher",
                 config,
+                peer_scores.clone(),
+                metrics_registry,
+            )?,
+            legacy_deploy_fetcher: Fetcher::new(
+                "legacy_deploy",
+                config,
+                peer_scores.clone(),
+                metrics_registry,
+            )?,
+            block_fetcher: Fetcher::new("block", config, peer_scores.clone(), metrics_registry)?,
+            deploy_fetcher: Fetcher::new("deploy", config, peer_scores.clone(), metrics_registry)?,
+            trie_or_chunk_fetcher: Fetcher::new(
+                "trie_or_chunk",
+                config,
+                peer_scores.clone(),
                 metrics_registry,
             )?,
-            legacy_deploy_fetcher: Fetcher::new("legacy_deploy", config, metrics_registry)?,
-            block_fetcher: Fetcher::new("block", config, metrics_registry)?,
-            deploy_fetcher: Fetcher::new("deploy", config, metrics_registry)?,
-            trie_or_chunk_fetcher: Fetcher::new("trie_or_chunk", config, metrics_registry)?,
             block_execution_results_or_chunk_fetcher: Fetcher::new(
                 "block_execution_results_or_chunk_fetcher",
                 config,
+                peer_scores.clone(),
                 metrics_registry,
             )?,
         })
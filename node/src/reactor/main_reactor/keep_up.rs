@@ -312,7 +312,7 @@ impl MainReactor {
                     if self.validator_matrix.has_era(&sync_era) {
                         Some(self.sync_back_register(effect_builder, rng, sync_hash))
                     } else {
-                        Some(self.sync_back_leap(effect_builder, rng, sync_hash))
+                        Some(self.sync_back_leap(effect_builder, sync_hash))
                     }
                 }
             },
@@ -416,7 +416,6 @@ impl MainReactor {
     fn sync_back_leap(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-        rng: &mut NodeRng,
         parent_hash: BlockHash,
     ) -> KeepUpInstruction {
         // in this flow, we are leveraging the SyncLeap behavior to go backwards
@@ -439,7 +438,7 @@ impl MainReactor {
         match sync_back_status {
             LeapState::Idle => {
                 debug!("KeepUp: historical sync back idle");
-                self.sync_back_leaper_idle(effect_builder, rng, parent_hash, Duration::ZERO)
+                self.sync_back_leaper_idle(effect_builder, parent_hash, Duration::ZERO)
             }
             LeapState::Awaiting { .. } => KeepUpInstruction::CheckLater(
                 "KeepUp: historical sync back is awaiting response".to_string(),
@@ -451,7 +450,7 @@ impl MainReactor {
                 ..
             } => self.sync_back_leap_received(effect_builder, *best_available),
             LeapState::Failed { error, .. } => {
-                self.sync_back_leap_failed(effect_builder, rng, parent_hash, error)
+                self.sync_back_leap_failed(effect_builder, parent_hash, error)
             }
         }
     }
@@ -459,7 +458,6 @@ impl MainReactor {
     fn sync_back_leap_failed(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-        rng: &mut NodeRng,
         parent_hash: BlockHash,
         error: LeapActivityError,
     ) -> KeepUpInstruction {
@@ -469,7 +467,6 @@ impl MainReactor {
         );
         self.sync_back_leaper_idle(
             effect_builder,
-            rng,
             parent_hash,
             self.control_logic_default_delay.into(),
         )
@@ -478,15 +475,11 @@ impl MainReactor {
     fn sync_back_leaper_idle(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
-        rng: &mut NodeRng,
         parent_hash: BlockHash,
         offset: Duration,
     ) -> KeepUpInstruction {
-        // we get a random sampling of peers to ask.
-        let peers_to_ask = self.net.fully_connected_peers_random(
-            rng,
-            self.chainspec.core_config.simultaneous_peer_requests as usize,
-        );
+        // the sync leaper chooses which of the peers to ask, based on their past performance.
+        let peers_to_ask = self.net.fully_connected_peers();
         if peers_to_ask.is_empty() {
             return KeepUpInstruction::CheckLater(
                 "no peers".to_string(),
@@ -18,9 +18,16 @@ use casper_types::{
 use crate::{
     components::{
         consensus::{
-            self, ClContext, ConsensusMessage, HighwayMessage, HighwayVertex, NewBlockPayload,
+            self,
+            protocols::{highway::HighwayProtocol, zug::Zug},
+            ClContext, ConsensusMessage, HighwayMessage, HighwayVertex, NewBlockPayload,
         },
-        gossiper, network, storage,
+        gossiper,
+        network::{
+            self,
+            simulation::{InstalledSimulator, LinkConditions, NetworkSimulator, ScriptStep},
+        },
+        storage,
         upgrade_watcher::NextUpgrade,
     },
     effect::{
@@ -37,7 +44,10 @@ use crate::{
         self, filter_reactor::FilterReactor, network::TestingNetwork, ConditionCheckReactor,
     },
     types::{
-        chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
+        chainspec::{
+            AccountConfig, AccountsConfig, ConsensusProtocolName, ConsensusProtocolSwitch,
+            ValidatorConfig,
+        },
         ActivationPoint, AvailableBlockRange, Block, BlockHash, BlockHeader, BlockPayload,
         Chainspec, ChainspecRawBytes, Deploy, ExitCode, NodeId, SyncHandling,
     },
@@ -69,6 +79,7 @@ enum InitialStakes {
 struct ChainspecOverride {
     minimum_block_time: TimeDiff,
     minimum_era_height: u64,
+    consensus_protocol_switches: Vec<ConsensusProtocolSwitch>,
 }
 
 impl Default for ChainspecOverride {
@@ -76,6 +87,7 @@ impl Default for ChainspecOverride {
         ChainspecOverride {
             minimum_block_time: "1second".parse().unwrap(),
             minimum_era_height: 2,
+            consensus_protocol_switches: vec![],
         }
     }
 }
@@ -104,7 +116,30 @@ impl TestFixture {
     /// Runs the network until all nodes are initialized (i.e. none of their reactor states are
     /// still `ReactorState::Initialize`).
     async fn new(initial_stakes: InitialStakes, spec_override: Option<ChainspecOverride>) -> Self {
+        Self::new_with_rng(TestRng::new(), initial_stakes, spec_override).await
+    }
+
+    /// Sets up a new fixture like `new`, with all nodes connected via a simulated network.
+    ///
+    /// The simulator is seeded from the fixture's RNG, so its behavior is reproducible.  Links are
+    /// perfect until changed via the returned simulator, which stays installed as long as the
+    /// returned guard is held.
+    async fn new_simulated(
+        initial_stakes: InitialStakes,
+        spec_override: Option<ChainspecOverride>,
+    ) -> (Self, NetworkSimulator, InstalledSimulator) {
         let mut rng = TestRng::new();
+        let simulator = NetworkSimulator::new(rng.gen());
+        let installed = simulator.install();
+        let fixture = Self::new_with_rng(rng, initial_stakes, spec_override).await;
+        (fixture, simulator, installed)
+    }
+
+    async fn new_with_rng(
+        mut rng: TestRng,
+        initial_stakes: InitialStakes,
+        spec_override: Option<ChainspecOverride>,
+    ) -> Self {
         let stake_values = match initial_stakes {
             InitialStakes::FromVec(stakes) => {
                 stakes.into_iter().map(|stake| stake.into()).collect()
@@ -177,6 +212,8 @@ impl TestFixture {
         let spec_override = spec_override.unwrap_or_default();
         chainspec.core_config.minimum_block_time = spec_override.minimum_block_time;
         chainspec.core_config.minimum_era_height = spec_override.minimum_era_height;
+        chainspec.core_config.consensus_protocol_switches =
+            spec_override.consensus_protocol_switches;
         chainspec.highway_config.maximum_round_length =
             chainspec.core_config.minimum_block_time * 2;
 
@@ -259,6 +296,7 @@ impl TestFixture {
                 .to_file(secret_key_path.clone())
                 .expect("could not write secret key");
             cfg.consensus.secret_key_path = External::Path(secret_key_path);
+            cfg.consensus.slashing_protection_path = temp_dir.path().join("slashing_protection.db");
         }
         cfg.storage = storage_cfg;
         cfg.node.trusted_hash = maybe_trusted_hash;
@@ -549,6 +587,42 @@ async fn run_network() {
     fixture.run_until_consensus_in_era(ERA_TWO, ONE_MIN).await;
 }
 
+#[tokio::test]
+async fn should_switch_consensus_protocol_in_scheduled_era() {
+    // Start with Highway and switch to Zug in era 2. On a real network the switch would come with
+    // an upgrade activated in era 2, but the era supervisor only consults the chainspec, so we can
+    // exercise the transition era without restarting the nodes.
+    let initial_stakes = InitialStakes::Random { count: 5 };
+    let spec_override = ChainspecOverride {
+        consensus_protocol_switches: vec![ConsensusProtocolSwitch {
+            era_id: ERA_TWO,
+            consensus_protocol: ConsensusProtocolName::Zug,
+        }],
+        ..Default::default()
+    };
+    let mut fixture = TestFixture::new(initial_stakes, Some(spec_override)).await;
+    assert_eq!(
+        fixture.chainspec.core_config.consensus_protocol(ERA_ONE),
+        ConsensusProtocolName::Highway
+    );
+
+    // Run until the first Zug era has been completed and its switch block agreed upon.
+    fixture.run_until_consensus_in_era(ERA_THREE, ONE_MIN).await;
+    for runner in fixture.network.nodes().values() {
+        let open_eras = runner.main_reactor().consensus().open_eras();
+        let era_one = open_eras[&ERA_ONE].consensus.as_any();
+        assert!(era_one.is::<HighwayProtocol<ClContext>>());
+        let era_two = open_eras[&ERA_TWO].consensus.as_any();
+        assert!(era_two.is::<Zug<ClContext>>());
+    }
+    let switch_blocks = SwitchBlocks::collect(fixture.network.nodes(), 3);
+    assert!(switch_blocks.equivocators(2).is_empty());
+    assert!(switch_blocks
+        .next_era_validators(2)
+        .keys()
+        .eq(switch_blocks.next_era_validators(1).keys()));
+}
+
 #[tokio::test]
 async fn historical_sync_with_era_height_1() {
     let initial_stakes = InitialStakes::Random { count: 5 };
@@ -603,6 +677,7 @@ async fn should_not_historical_sync_no_sync_node() {
     let spec_override = ChainspecOverride {
         minimum_block_time: "4seconds".parse().unwrap(),
         minimum_era_height: 1,
+        ..Default::default()
     };
     let mut fixture = TestFixture::new(initial_stakes, Some(spec_override)).await;
 
@@ -1104,6 +1179,7 @@ async fn empty_block_validation_regression() {
                             vec![],
                             everyone_else.clone(),
                             false,
+                            None,
                         )),
                         block_context,
                     },
@@ -1171,3 +1247,38 @@ async fn network_should_recover_from_stall() {
     // Ensure all nodes progress until block 3 is marked complete.
     fixture.run_until_block_height(3, TEN_SECS).await;
 }
+
+#[tokio::test]
+async fn should_finalize_over_simulated_network_with_partition() {
+    // Set up a network with five nodes connected via lossy links with varying latency.
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 5,
+        stake: 100,
+    };
+    let (mut fixture, simulator, _installed) =
+        TestFixture::new_simulated(initial_stakes, None).await;
+    simulator.set_default_link(LinkConditions {
+        min_latency: Duration::from_millis(10),
+        max_latency: Duration::from_millis(150),
+        loss: 0.01,
+        bandwidth: Some(10_000_000),
+    });
+
+    fixture.run_until_block_height(2, ONE_MIN).await;
+
+    // Cut off a minority of two nodes for a while, then heal the partition.
+    let node_ids: Vec<NodeId> = fixture.node_contexts.iter().map(|node| node.id).collect();
+    let script = simulator.run_script(vec![
+        (
+            Duration::ZERO,
+            ScriptStep::Partition(vec![node_ids[..2].to_vec(), node_ids[2..].to_vec()]),
+        ),
+        (TEN_SECS, ScriptStep::Heal),
+    ]);
+
+    // All nodes, including the ones previously cut off, should finalize further blocks.
+    fixture.run_until_block_height(6, ONE_MIN * 2).await;
+    assert!(script.is_finished());
+    assert!(simulator.lost_count() > 0);
+    assert!(simulator.delivered_count() > 0);
+}
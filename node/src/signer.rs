@@ -0,0 +1,676 @@
+//! Signing with the validator's consensus key.
+//!
+//! The consensus key signs consensus messages, finality signatures and the certificates a node
+//! presents to its peers to prove it is a validator, and computes the VRF proofs contributing to
+//! the random beacon.  It is either loaded from
+//! `consensus.secret_key_path`, or held by a remote signer, so that it never needs to be stored on
+//! an internet-facing machine.
+//!
+//! # Remote signer protocol
+//!
+//! The node connects to the signer via TCP or a Unix socket and sends one request at a time on
+//! each connection, each answered by a single response.  Several connections may be open at once.
+//! Every message is a frame consisting of its length as a big-endian `u32`, a 32-byte MAC and the
+//! bincode-encoded [`Request`] or [`Response`].
+//!
+//! Both sides share a secret authentication key.  The MAC of a request is the BLAKE2b hash of the
+//! key followed by the encoded request; the MAC of a response is the hash of the key, the MAC of
+//! the request it answers and the encoded response.  Every request carries a random nonce, and a
+//! signer must ignore requests whose MAC is invalid or whose nonce it has seen before, so that
+//! recorded requests cannot be replayed.  The node additionally verifies every signature and VRF
+//! proof it receives against the validator's public key.
+
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    os::unix::net::UnixStream,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::debug;
+
+use casper_hashing::Digest;
+use casper_types::{
+    crypto, file_utils::ReadFileError, PublicKey, SecretKey, Signature, TimeDiff, VrfProof,
+};
+
+use crate::utils::{External, LoadError};
+
+/// Prefix of remote signer addresses denoting the path of a Unix socket.
+const UNIX_SOCKET_PREFIX: &str = "unix:";
+
+/// Maximum size of a frame exchanged with a remote signer.
+const MAX_FRAME_SIZE: u32 = 64 * 1024;
+
+/// Configuration of a remote signer holding the validator's secret key.
+#[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct RemoteSignerConfig {
+    /// Address of the signer, either `host:port` or `unix:` followed by the path of a Unix socket.
+    pub address: String,
+    /// Path to the file containing the authentication key shared with the signer.
+    pub auth_key_path: External,
+    /// Timeout for connecting to the signer and for each of its responses.
+    pub timeout: TimeDiff,
+}
+
+/// Error using the validator's consensus key.
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    /// The secret key could not be loaded.
+    #[error("could not load secret key: {0}")]
+    LoadSecretKey(#[from] LoadError<crypto::ErrorExt>),
+    /// The authentication key for the remote signer could not be loaded.
+    #[error("could not load remote signer authentication key: {0}")]
+    LoadAuthKey(#[from] LoadError<ReadFileError>),
+    /// The remote signer address could not be resolved.
+    #[error("could not resolve remote signer address {0}")]
+    ResolveAddress(String),
+    /// Failed to connect to the remote signer.
+    #[error("could not connect to remote signer at {address}: {error}")]
+    Connect {
+        /// The address of the signer.
+        address: String,
+        /// The underlying error.
+        #[source]
+        error: io::Error,
+    },
+    /// Failed to communicate with the remote signer.
+    #[error("remote signer communication failed: {0}")]
+    Io(#[from] io::Error),
+    /// A message to or from the remote signer could not be encoded or decoded.
+    #[error("could not encode or decode remote signer message: {0}")]
+    Encoding(#[from] bincode::Error),
+    /// A frame exceeded the maximum size.
+    #[error("remote signer frame of {0} bytes is invalid")]
+    InvalidFrameSize(usize),
+    /// The MAC of a response was invalid.
+    #[error("remote signer response failed authentication")]
+    Unauthenticated,
+    /// The remote signer refused to handle a request.
+    #[error("remote signer refused request: {0}")]
+    Refused(String),
+    /// The remote signer answered with a response not matching the request.
+    #[error("unexpected response from remote signer")]
+    UnexpectedResponse,
+    /// The remote signer returned an invalid signature.
+    #[error("invalid signature from remote signer: {0}")]
+    InvalidSignature(crypto::Error),
+    /// The remote signer returned an invalid VRF proof.
+    #[error("invalid VRF proof from remote signer: {0}")]
+    InvalidVrfProof(crypto::Error),
+}
+
+/// A request to a remote signer.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Request {
+    /// A random number that is never reused, to prevent replaying the request.
+    nonce: u128,
+    /// The requested operation.
+    operation: Operation,
+}
+
+/// An operation requested from a remote signer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum Operation {
+    /// Requests the public key of the validator.
+    PublicKey,
+    /// Requests a signature over the given message.
+    Sign(Vec<u8>),
+    /// Requests a VRF proof for the given message.
+    VrfProve(Vec<u8>),
+}
+
+/// A response of a remote signer.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Response {
+    /// The public key of the validator.
+    PublicKey(PublicKey),
+    /// The signature over the requested message.
+    Signature(Signature),
+    /// The VRF proof for the requested message.
+    VrfProof(VrfProof),
+    /// The request was refused, for the given reason.
+    Error(String),
+}
+
+/// Signs messages with the validator's consensus key.
+#[derive(Clone, DataSize)]
+pub(crate) struct Signer {
+    /// The validator's public key.
+    public_key: PublicKey,
+    /// Where the secret key is held.
+    backend: Backend,
+}
+
+#[derive(Clone, DataSize)]
+enum Backend {
+    /// The secret key, loaded from a file.
+    Local(Arc<SecretKey>),
+    /// A remote signer holding the secret key.
+    Remote {
+        #[data_size(skip)]
+        signer: Arc<RemoteSigner>,
+    },
+}
+
+impl Signer {
+    /// Connects to a remote signer and retrieves the validator's public key.
+    pub(crate) fn connect_remote(
+        address: String,
+        auth_key: Vec<u8>,
+        timeout: Duration,
+    ) -> Result<Self, Error> {
+        let signer = RemoteSigner {
+            address,
+            auth_key,
+            timeout,
+            idle_connections: Mutex::new(Vec::new()),
+        };
+        let public_key = match signer.request(Operation::PublicKey)? {
+            Response::PublicKey(public_key) => public_key,
+            Response::Error(reason) => return Err(Error::Refused(reason)),
+            Response::Signature(_) | Response::VrfProof(_) => {
+                return Err(Error::UnexpectedResponse)
+            }
+        };
+        Ok(Signer {
+            public_key,
+            backend: Backend::Remote {
+                signer: Arc::new(signer),
+            },
+        })
+    }
+
+    /// Returns the validator's public key.
+    pub(crate) fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Signs `message` with the validator's secret key.
+    ///
+    /// A remote signer is queried synchronously, blocking the calling thread for up to the
+    /// configured timeout.  When called on a worker of a multi-threaded tokio runtime, the worker's
+    /// other tasks are handed over to another thread for the duration of the request.
+    pub(crate) fn sign<T: AsRef<[u8]>>(&self, message: T) -> Result<Signature, Error> {
+        match &self.backend {
+            Backend::Local(secret_key) => Ok(crypto::sign(message, secret_key, &self.public_key)),
+            Backend::Remote { signer } => {
+                let message = message.as_ref();
+                let signature = match signer.request_blocking(Operation::Sign(message.to_vec()))? {
+                    Response::Signature(signature) => signature,
+                    Response::Error(reason) => return Err(Error::Refused(reason)),
+                    Response::PublicKey(_) | Response::VrfProof(_) => {
+                        return Err(Error::UnexpectedResponse)
+                    }
+                };
+                crypto::verify(message, &signature, &self.public_key)
+                    .map_err(Error::InvalidSignature)?;
+                Ok(signature)
+            }
+        }
+    }
+
+    /// Computes a VRF proof for `message` with the validator's secret key.
+    ///
+    /// Blocks the calling thread like [`Signer::sign`].
+    pub(crate) fn prove_vrf<T: AsRef<[u8]>>(&self, message: T) -> Result<VrfProof, Error> {
+        match &self.backend {
+            Backend::Local(secret_key) => {
+                Ok(crypto::vrf_prove(message, secret_key, &self.public_key))
+            }
+            Backend::Remote { signer } => {
+                let message = message.as_ref();
+                let proof = match signer.request_blocking(Operation::VrfProve(message.to_vec()))? {
+                    Response::VrfProof(proof) => proof,
+                    Response::Error(reason) => return Err(Error::Refused(reason)),
+                    Response::PublicKey(_) | Response::Signature(_) => {
+                        return Err(Error::UnexpectedResponse)
+                    }
+                };
+                crypto::vrf_verify(message, &proof, &self.public_key)
+                    .map_err(Error::InvalidVrfProof)?;
+                Ok(proof)
+            }
+        }
+    }
+}
+
+impl From<Arc<SecretKey>> for Signer {
+    fn from(secret_key: Arc<SecretKey>) -> Self {
+        Signer {
+            public_key: PublicKey::from(secret_key.as_ref()),
+            backend: Backend::Local(secret_key),
+        }
+    }
+}
+
+impl Debug for Signer {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let backend = match &self.backend {
+            Backend::Local(_) => "local".to_string(),
+            Backend::Remote { signer } => format!("remote at {}", signer.address),
+        };
+        formatter
+            .debug_struct("Signer")
+            .field("public_key", &self.public_key)
+            .field("backend", &backend)
+            .finish()
+    }
+}
+
+/// A connection to a remote signer.
+struct RemoteSigner {
+    /// Address of the signer.
+    address: String,
+    /// The key authenticating messages exchanged with the signer.
+    auth_key: Vec<u8>,
+    /// Timeout for connecting and for each response.
+    timeout: Duration,
+    /// Established connections to the signer that are not currently in use.
+    ///
+    /// The lock is only held to take or return a connection, never while communicating, so
+    /// concurrent requests use separate connections.
+    idle_connections: Mutex<Vec<Connection>>,
+}
+
+impl RemoteSigner {
+    /// Sends a request like [`RemoteSigner::request`], moving the other tasks of the current
+    /// tokio worker thread to another thread while blocking, if on a multi-threaded runtime.
+    fn request_blocking(&self, operation: Operation) -> Result<Response, Error> {
+        match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.request(operation))
+            }
+            _ => self.request(operation),
+        }
+    }
+
+    /// Sends a request for `operation` to the signer, returning its response.
+    ///
+    /// The signer may have closed an idle connection, so a request failing on an existing
+    /// connection is retried once on a new one, with a new nonce.
+    fn request(&self, operation: Operation) -> Result<Response, Error> {
+        let idle_connection = self
+            .idle_connections
+            .lock()
+            .expect("remote signer lock poisoned")
+            .pop();
+        if let Some(mut connection) = idle_connection {
+            match exchange(
+                &mut connection,
+                &self.auth_key,
+                &Request::new(operation.clone()),
+            ) {
+                Ok(response) => {
+                    self.release(connection);
+                    return Ok(response);
+                }
+                Err(error) => {
+                    debug!(
+                        %error,
+                        address = %self.address,
+                        "remote signer request failed, reconnecting"
+                    );
+                }
+            }
+        }
+
+        let mut connection = self.connect()?;
+        let response = exchange(&mut connection, &self.auth_key, &Request::new(operation))?;
+        self.release(connection);
+        Ok(response)
+    }
+
+    /// Returns a connection to the pool of idle connections.
+    fn release(&self, connection: Connection) {
+        self.idle_connections
+            .lock()
+            .expect("remote signer lock poisoned")
+            .push(connection);
+    }
+
+    fn connect(&self) -> Result<Connection, Error> {
+        let connect_error = |error| Error::Connect {
+            address: self.address.clone(),
+            error,
+        };
+        let connection = match self.address.strip_prefix(UNIX_SOCKET_PREFIX) {
+            Some(path) => {
+                let stream = UnixStream::connect(path).map_err(connect_error)?;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                Connection::Unix(stream)
+            }
+            None => {
+                let socket_addr = self
+                    .address
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addrs| addrs.next())
+                    .ok_or_else(|| Error::ResolveAddress(self.address.clone()))?;
+                let stream = TcpStream::connect_timeout(&socket_addr, self.timeout)
+                    .map_err(connect_error)?;
+                stream.set_nodelay(true)?;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                Connection::Tcp(stream)
+            }
+        };
+        Ok(connection)
+    }
+}
+
+impl Request {
+    /// Creates a request for `operation` with a new random nonce.
+    fn new(operation: Operation) -> Self {
+        Request {
+            nonce: rand::random(),
+            operation,
+        }
+    }
+}
+
+/// A stream connected to a remote signer.
+enum Connection {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(stream) => stream.read(buf),
+            Connection::Unix(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Connection::Tcp(stream) => stream.write(buf),
+            Connection::Unix(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Connection::Tcp(stream) => stream.flush(),
+            Connection::Unix(stream) => stream.flush(),
+        }
+    }
+}
+
+/// Sends an authenticated request and reads the authenticated response.
+fn exchange<S: Read + Write>(
+    stream: &mut S,
+    auth_key: &[u8],
+    request: &Request,
+) -> Result<Response, Error> {
+    let encoded_request = bincode::serialize(request)?;
+    let request_mac = mac(auth_key, &[&encoded_request]);
+    write_frame(stream, &request_mac, &encoded_request)?;
+
+    let (response_mac, encoded_response) = read_frame(stream)?;
+    if response_mac != mac(auth_key, &[request_mac.as_ref(), &encoded_response]) {
+        return Err(Error::Unauthenticated);
+    }
+    Ok(bincode::deserialize(&encoded_response)?)
+}
+
+/// Computes the MAC of the concatenation of `parts`.
+///
+/// Prefixing the key is a secure MAC construction for BLAKE2b, which is not susceptible to length
+/// extension attacks.
+fn mac(auth_key: &[u8], parts: &[&[u8]]) -> Digest {
+    let mut data = auth_key.to_vec();
+    for part in parts {
+        data.extend_from_slice(part);
+    }
+    Digest::hash(data)
+}
+
+/// Writes a frame consisting of a MAC and a payload.
+fn write_frame<W: Write>(writer: &mut W, mac: &Digest, payload: &[u8]) -> Result<(), Error> {
+    let length = Digest::LENGTH + payload.len();
+    if length > MAX_FRAME_SIZE as usize {
+        return Err(Error::InvalidFrameSize(length));
+    }
+    writer.write_all(&(length as u32).to_be_bytes())?;
+    writer.write_all(mac.as_ref())?;
+    writer.write_all(payload)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a frame consisting of a MAC and a payload.
+fn read_frame<R: Read>(reader: &mut R) -> Result<(Digest, Vec<u8>), Error> {
+    let mut length = [0; 4];
+    reader.read_exact(&mut length)?;
+    let length = u32::from_be_bytes(length) as usize;
+    if length > MAX_FRAME_SIZE as usize || length < Digest::LENGTH {
+        return Err(Error::InvalidFrameSize(length));
+    }
+    let mut mac = [0; Digest::LENGTH];
+    reader.read_exact(&mut mac)?;
+    let mut payload = vec![0; length - Digest::LENGTH];
+    reader.read_exact(&mut payload)?;
+    Ok((Digest::from(mac), payload))
+}
+
+/// Serves requests of a node on `stream` until it is closed, acting as a remote signer.
+///
+/// Requests failing authentication end the connection without a response.  Requests reusing a
+/// nonce in `seen_nonces` are refused.
+#[cfg(test)]
+pub(crate) fn serve<S: Read + Write>(
+    stream: &mut S,
+    secret_key: &SecretKey,
+    auth_key: &[u8],
+    seen_nonces: &Mutex<std::collections::HashSet<u128>>,
+) -> Result<(), Error> {
+    let public_key = PublicKey::from(secret_key);
+    loop {
+        let (request_mac, encoded_request) = match read_frame(stream) {
+            Ok(frame) => frame,
+            Err(Error::Io(error)) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        if request_mac != mac(auth_key, &[&encoded_request]) {
+            return Err(Error::Unauthenticated);
+        }
+        let request: Request = bincode::deserialize(&encoded_request)?;
+        let is_new_nonce = seen_nonces
+            .lock()
+            .expect("nonces lock poisoned")
+            .insert(request.nonce);
+        let response = match request.operation {
+            _ if !is_new_nonce => Response::Error("replayed request".to_string()),
+            Operation::PublicKey => Response::PublicKey(public_key.clone()),
+            Operation::Sign(message) => {
+                Response::Signature(crypto::sign(message, secret_key, &public_key))
+            }
+            Operation::VrfProve(message) => {
+                Response::VrfProof(crypto::vrf_prove(message, secret_key, &public_key))
+            }
+        };
+        let encoded_response = bincode::serialize(&response)?;
+        let response_mac = mac(auth_key, &[request_mac.as_ref(), &encoded_response]);
+        write_frame(stream, &response_mac, &encoded_response)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, net::TcpListener, os::unix::net::UnixListener, thread};
+
+    use super::*;
+
+    const AUTH_KEY: &[u8] = b"remote signer test authentication key";
+
+    /// Runs a stand-in signer serving a single connection accepted by `accept`.
+    fn spawn_signer<F, S>(accept: F, secret_key: SecretKey, auth_key: &'static [u8])
+    where
+        F: FnOnce() -> S + Send + 'static,
+        S: Read + Write,
+    {
+        thread::spawn(move || {
+            let mut stream = accept();
+            let _ = serve(
+                &mut stream,
+                &secret_key,
+                auth_key,
+                &Mutex::new(HashSet::new()),
+            );
+        });
+    }
+
+    #[test]
+    fn should_sign_with_local_key() {
+        let mut rng = crate::new_rng();
+        let signer = Signer::from(Arc::new(SecretKey::random(&mut rng)));
+
+        let signature = signer.sign(b"message").expect("should sign");
+        crypto::verify(b"message", &signature, signer.public_key()).expect("should verify");
+    }
+
+    #[test]
+    fn should_sign_with_remote_signer_over_tcp() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let expected_proof = crypto::vrf_prove(b"message", &secret_key, &public_key);
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let address = listener
+            .local_addr()
+            .expect("should have address")
+            .to_string();
+        spawn_signer(
+            move || listener.accept().expect("should accept").0,
+            secret_key,
+            AUTH_KEY,
+        );
+
+        let signer = Signer::connect_remote(address, AUTH_KEY.to_vec(), Duration::from_secs(5))
+            .expect("should connect");
+        assert_eq!(signer.public_key(), &public_key);
+
+        for message in [&b"first"[..], &b"second"[..]] {
+            let signature = signer.sign(message).expect("should sign");
+            crypto::verify(message, &signature, &public_key).expect("should verify");
+        }
+
+        let proof = signer.prove_vrf(b"message").expect("should prove");
+        assert_eq!(proof, expected_proof);
+    }
+
+    #[test]
+    fn should_sign_with_remote_signer_over_unix_socket() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+
+        let dir = tempfile::tempdir().expect("should create temp dir");
+        let socket_path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&socket_path).expect("should bind");
+        spawn_signer(
+            move || listener.accept().expect("should accept").0,
+            secret_key,
+            AUTH_KEY,
+        );
+
+        let address = format!("{}{}", UNIX_SOCKET_PREFIX, socket_path.display());
+        let signer = Signer::connect_remote(address, AUTH_KEY.to_vec(), Duration::from_secs(5))
+            .expect("should connect");
+        let signature = signer.sign(b"message").expect("should sign");
+        crypto::verify(b"message", &signature, signer.public_key()).expect("should verify");
+    }
+
+    #[test]
+    fn should_refuse_replayed_request() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let address = listener.local_addr().expect("should have address");
+        spawn_signer(
+            move || listener.accept().expect("should accept").0,
+            secret_key,
+            AUTH_KEY,
+        );
+
+        let mut stream = TcpStream::connect(address).expect("should connect");
+        let request = Request::new(Operation::Sign(b"message".to_vec()));
+        let response = exchange(&mut stream, AUTH_KEY, &request).expect("should exchange");
+        assert!(matches!(response, Response::Signature(_)));
+        let response = exchange(&mut stream, AUTH_KEY, &request).expect("should exchange");
+        assert!(matches!(response, Response::Error(_)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_sign_concurrently_on_runtime_worker() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let address = listener
+            .local_addr()
+            .expect("should have address")
+            .to_string();
+        thread::spawn(move || {
+            let secret_key = Arc::new(secret_key);
+            let seen_nonces = Arc::new(Mutex::new(HashSet::new()));
+            for stream in listener.incoming() {
+                let mut stream = stream.expect("should accept");
+                let secret_key = Arc::clone(&secret_key);
+                let seen_nonces = Arc::clone(&seen_nonces);
+                thread::spawn(move || {
+                    let _ = serve(&mut stream, &secret_key, AUTH_KEY, &seen_nonces);
+                });
+            }
+        });
+
+        let signer = Signer::connect_remote(address, AUTH_KEY.to_vec(), Duration::from_secs(5))
+            .expect("should connect");
+        let tasks: Vec<_> = (0..4u8)
+            .map(|index| {
+                let signer = signer.clone();
+                tokio::spawn(async move { (index, signer.sign([index]).expect("should sign")) })
+            })
+            .collect();
+        for task in tasks {
+            let (index, signature) = task.await.expect("task should complete");
+            crypto::verify([index], &signature, &public_key).expect("should verify");
+        }
+    }
+
+    #[test]
+    fn should_fail_with_wrong_auth_key() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let address = listener
+            .local_addr()
+            .expect("should have address")
+            .to_string();
+        spawn_signer(
+            move || listener.accept().expect("should accept").0,
+            secret_key,
+            AUTH_KEY,
+        );
+
+        let result =
+            Signer::connect_remote(address, b"some other key".to_vec(), Duration::from_secs(5));
+        assert!(result.is_err());
+    }
+}
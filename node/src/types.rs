@@ -19,6 +19,7 @@ mod status_feed;
 mod sync_leap;
 pub(crate) mod sync_leap_validation_metadata;
 mod validator_matrix;
+mod validator_performance;
 mod value_or_chunk;
 
 use rand::{CryptoRng, RngCore};
@@ -35,7 +36,7 @@ pub use block::{
     json_compatibility::{JsonBlock, JsonBlockHeader},
     Block, BlockAndDeploys, BlockBody, BlockExecutionResultsOrChunk,
     BlockExecutionResultsOrChunkId, BlockExecutionResultsOrChunkIdDisplay, BlockHash, BlockHeader,
-    BlockSignatures, FinalitySignature, FinalizedBlock,
+    BlockHeaderExtension, BlockSignatures, FinalitySignature, FinalizedBlock,
 };
 pub(crate) use block_hash_height_and_era::BlockHashHeightAndEra;
 pub use chainspec::Chainspec;
@@ -44,21 +45,22 @@ pub use chunkable::Chunkable;
 pub use datasize::DataSize;
 pub use deploy::{
     Approval, ApprovalsHash, Deploy, DeployConfigurationFailure, DeployError, DeployHash,
-    DeployHeader, DeployOrTransferHash, ExcessiveSizeError as ExcessiveSizeDeployError,
+    DeployHeader, DeployOrTransferHash, ExcessiveSizeError as ExcessiveSizeDeployError, FeeLimits,
 };
 pub(crate) use deploy::{
     DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata, DeployMetadataExt,
-    DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy,
+    DeployV1, DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy, SessionContract,
 };
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
 pub(crate) use max_ttl::MaxTtl;
 pub use node_config::{NodeConfig, SyncHandling};
-pub(crate) use node_id::NodeId;
+pub(crate) use node_id::{NodeId, NodeIdParseError};
 pub use peers_map::PeersMap;
 pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
 pub(crate) use sync_leap::{GlobalStatesMetadata, SyncLeap, SyncLeapIdentifier};
 pub(crate) use validator_matrix::{EraValidatorWeights, SignatureWeight, ValidatorMatrix};
+pub(crate) use validator_performance::ValidatorPerformance;
 pub use value_or_chunk::{
     ChunkingError, TrieOrChunk, TrieOrChunkId, TrieOrChunkIdDisplay, ValueOrChunk,
 };
@@ -3,7 +3,7 @@ use std::{
     fmt::{self, Display, Formatter},
 };
 
-use casper_types::{Gas, PublicKey, TimeDiff, Timestamp};
+use casper_types::{Gas, PublicKey, TimeDiff, Timestamp, VrfProof};
 use datasize::DataSize;
 use num_traits::Zero;
 use thiserror::Error;
@@ -177,16 +177,23 @@ impl AppendableBlock {
     }
 
     /// Creates a `BlockPayload` with the `AppendableBlock`s deploys and transfers, and the given
-    /// random bit and accusations.
+    /// random bit, random beacon contribution and accusations.
     pub(crate) fn into_block_payload(
         self,
         accusations: Vec<PublicKey>,
         random_bit: bool,
+        random_beacon_contribution: Option<VrfProof>,
     ) -> BlockPayload {
         let AppendableBlock {
             deploys, transfers, ..
         } = self;
-        BlockPayload::new(deploys, transfers, accusations, random_bit)
+        BlockPayload::new(
+            deploys,
+            transfers,
+            accusations,
+            random_bit,
+            random_beacon_contribution,
+        )
     }
 
     pub(crate) fn timestamp(&self) -> Timestamp {
@@ -31,7 +31,7 @@ use tracing::error;
 use casper_hashing::{ChunkWithProofVerificationError, Digest};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    crypto, EraId, ProtocolVersion, PublicKey, SecretKey, Signature, Timestamp, U512,
+    crypto, EraId, ProtocolVersion, PublicKey, SecretKey, Signature, Timestamp, VrfProof, U512,
 };
 #[cfg(any(feature = "testing", test))]
 use casper_types::{
@@ -59,6 +59,9 @@ pub(crate) use meta_block::{
     MergeMismatchError as MetaBlockMergeError, MetaBlock, State as MetaBlockState,
 };
 
+/// The domain separation tag of the messages signed as random beacon contributions.
+const RANDOM_BEACON_TAG: &[u8] = b"casper-random-beacon";
+
 static ERA_REPORT: Lazy<EraReport> = Lazy::new(|| {
     let secret_key_1 = SecretKey::ed25519_from_bytes([0; 32]).unwrap();
     let public_key_1 = PublicKey::from(&secret_key_1);
@@ -109,6 +112,12 @@ static FINALIZED_BLOCK: Lazy<FinalizedBlock> = Lazy::new(|| {
     let timestamp = *Timestamp::doc_example();
     let secret_key = SecretKey::doc_example();
     let public_key = PublicKey::from(secret_key);
+    let era_id = EraId::from(1);
+    let random_beacon_contribution = crypto::vrf_prove(
+        BlockPayload::random_beacon_message(era_id, timestamp),
+        secret_key,
+        &public_key,
+    );
     let block_payload = BlockPayload::new(
         vec![],
         transfer_hashes
@@ -122,9 +131,9 @@ static FINALIZED_BLOCK: Lazy<FinalizedBlock> = Lazy::new(|| {
             .collect(),
         vec![],
         random_bit,
+        Some(random_beacon_contribution),
     );
     let era_report = Some(EraReport::doc_example().clone());
-    let era_id = EraId::from(1);
     let height = 10;
     FinalizedBlock::new(
         block_payload,
@@ -139,7 +148,9 @@ static BLOCK: Lazy<Block> = Lazy::new(|| {
     let parent_hash = BlockHash::new(Digest::from([7u8; Digest::LENGTH]));
     let state_root_hash = Digest::from([8u8; Digest::LENGTH]);
     let finalized_block = FinalizedBlock::doc_example().clone();
+    let random_beacon_contribution = finalized_block.random_beacon_contribution().cloned();
     let parent_seed = Digest::from([9u8; Digest::LENGTH]);
+    let random_beacon = Digest::from([10u8; Digest::LENGTH]);
     let protocol_version = ProtocolVersion::V1_0_0;
 
     let secret_key = SecretKey::doc_example();
@@ -170,6 +181,12 @@ static BLOCK: Lazy<Block> = Lazy::new(|| {
         finalized_block,
         next_era_validator_weights,
         protocol_version,
+        Some(BlockHeaderExtension::new(
+            1,
+            100_000_000,
+            random_beacon_contribution,
+            random_beacon,
+        )),
     )
     .expect("could not construct block")
 });
@@ -231,6 +248,7 @@ pub struct BlockPayload {
     transfers: Vec<DeployHashWithApprovals>,
     accusations: Vec<PublicKey>,
     random_bit: bool,
+    random_beacon_contribution: Option<VrfProof>,
 }
 
 impl BlockPayload {
@@ -239,15 +257,30 @@ impl BlockPayload {
         transfers: Vec<DeployHashWithApprovals>,
         accusations: Vec<PublicKey>,
         random_bit: bool,
+        random_beacon_contribution: Option<VrfProof>,
     ) -> Self {
         BlockPayload {
             deploys,
             transfers,
             accusations,
             random_bit,
+            random_beacon_contribution,
         }
     }
 
+    /// Returns the message whose VRF proof the proposer of a block with the given era and timestamp
+    /// includes to contribute to the random beacon.
+    ///
+    /// The message doesn't depend on anything the proposer can choose, and the VRF output is unique
+    /// for the proposer's key and the message, so the proposer cannot try out different
+    /// contributions.
+    pub(crate) fn random_beacon_message(era_id: EraId, timestamp: Timestamp) -> Digest {
+        let mut data = RANDOM_BEACON_TAG.to_vec();
+        data.extend(era_id.value().to_le_bytes());
+        data.extend(timestamp.millis().to_le_bytes());
+        Digest::hash(data)
+    }
+
     /// Returns the set of validators that are reported as faulty in this block.
     pub(crate) fn accusations(&self) -> &Vec<PublicKey> {
         &self.accusations
@@ -368,6 +401,7 @@ impl BlockPayload {
             transfers,
             accusations,
             random_bit: rng.gen(),
+            random_beacon_contribution: None,
         }
     }
 }
@@ -432,6 +466,7 @@ pub struct FinalizedBlock {
     transfer_hashes: Vec<DeployHash>,
     timestamp: Timestamp,
     random_bit: bool,
+    random_beacon_contribution: Option<Box<VrfProof>>,
     era_report: Option<Box<EraReport>>,
     era_id: EraId,
     height: u64,
@@ -452,6 +487,7 @@ impl FinalizedBlock {
             transfer_hashes: block_payload.transfer_hashes().cloned().collect(),
             timestamp,
             random_bit: block_payload.random_bit,
+            random_beacon_contribution: block_payload.random_beacon_contribution.map(Box::new),
             era_report: era_report.map(Box::new),
             era_id,
             height,
@@ -484,6 +520,33 @@ impl FinalizedBlock {
         self.proposer.clone()
     }
 
+    /// Returns the proposer's contribution to the random beacon, if any.
+    pub(crate) fn random_beacon_contribution(&self) -> Option<&VrfProof> {
+        self.random_beacon_contribution.as_deref()
+    }
+
+    /// Returns the random beacon of this block, given the random beacon of its parent.
+    ///
+    /// The beacon is the hash of the parent's beacon and the VRF output proven by the proposer's
+    /// contribution.  A missing or invalid contribution is ignored.
+    ///
+    /// Since the VRF output is unique, the proposer cannot influence the beacon other than by
+    /// withholding its contribution or the whole block.  Doing so replaces the beacon with another
+    /// one the proposer can compute in advance, so each proposer can choose between two outcomes,
+    /// and a coalition of the proposers of consecutive blocks between correspondingly more.
+    /// Contracts should therefore not rely on the beacon where that bias matters.
+    pub(crate) fn random_beacon(&self, parent_random_beacon: Digest) -> Digest {
+        let message = BlockPayload::random_beacon_message(self.era_id, self.timestamp);
+        let maybe_output = self
+            .random_beacon_contribution
+            .as_ref()
+            .and_then(|proof| crypto::vrf_verify(message, proof, &self.proposer).ok());
+        match maybe_output {
+            Some(output) => Digest::hash_pair(parent_random_beacon, output),
+            None => Digest::hash(parent_random_beacon),
+        }
+    }
+
     /// The list of deploy hashes chained with the list of transfer hashes.
     pub fn deploy_and_transfer_hashes(&self) -> impl Iterator<Item = &DeployHash> {
         self.deploy_hashes.iter().chain(&self.transfer_hashes)
@@ -541,7 +604,20 @@ impl FinalizedBlock {
             );
         }
         let random_bit = rng.gen();
-        let block_payload = BlockPayload::new(deploys, vec![], vec![], random_bit);
+        let secret_key: SecretKey = SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap();
+        let public_key = PublicKey::from(&secret_key);
+        let random_beacon_contribution = crypto::vrf_prove(
+            BlockPayload::random_beacon_message(era_id, timestamp),
+            &secret_key,
+            &public_key,
+        );
+        let block_payload = BlockPayload::new(
+            deploys,
+            vec![],
+            vec![],
+            random_bit,
+            Some(random_beacon_contribution),
+        );
 
         let era_report = if is_switch {
             let equivocators_count = rng.gen_range(0..5);
@@ -571,8 +647,6 @@ impl FinalizedBlock {
         } else {
             None
         };
-        let secret_key: SecretKey = SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap();
-        let public_key = PublicKey::from(&secret_key);
 
         FinalizedBlock::new(
             block_payload,
@@ -598,6 +672,11 @@ impl From<Block> for FinalizedBlock {
             transfer_hashes: block.body.transfer_hashes,
             timestamp: block.header.timestamp,
             random_bit: block.header.random_bit,
+            random_beacon_contribution: block
+                .header
+                .extension
+                .and_then(|extension| extension.random_beacon_contribution)
+                .map(Box::new),
             era_report: block
                 .header
                 .era_end
@@ -822,11 +901,122 @@ pub struct BlockHeader {
     era_id: EraId,
     height: u64,
     protocol_version: ProtocolVersion,
+    /// The fields added by the fee market and the random beacon, present only in blocks created
+    /// from `start_protocol_version_with_extended_block_headers` onwards.
+    extension: Option<BlockHeaderExtension>,
     #[serde(skip)]
     #[data_size(with = ds::once_cell)]
     block_hash: OnceCell<BlockHash>,
 }
 
+/// The tag preceding the serialized `BlockHeaderExtension` in a serialized `BlockHeader`.
+///
+/// Headers without an extension are serialized, and hence hashed, exactly like the headers
+/// created before the extension was introduced.  In a serialized `Block` the header is followed
+/// by the proposer's public key, whose tag is never equal to this one.
+const BLOCK_HEADER_EXTENSION_TAG: u8 = u8::MAX;
+
+/// The part of a `BlockHeader` added by the fee market and the random beacon.
+#[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BlockHeaderExtension {
+    /// The base fee per unit of gas which applied to the deploys in the block.
+    base_fee: u64,
+    /// The total amount of gas consumed by the deploys in the block.
+    gas_used: u64,
+    /// The proposer's contribution to the random beacon.
+    random_beacon_contribution: Option<VrfProof>,
+    /// The random beacon, accumulated from the proposers' contributions.
+    random_beacon: Digest,
+}
+
+impl BlockHeaderExtension {
+    pub(crate) fn new(
+        base_fee: u64,
+        gas_used: u64,
+        random_beacon_contribution: Option<VrfProof>,
+        random_beacon: Digest,
+    ) -> Self {
+        BlockHeaderExtension {
+            base_fee,
+            gas_used,
+            random_beacon_contribution,
+            random_beacon,
+        }
+    }
+
+    /// Returns the base fee per unit of gas which applied to the deploys in the block.
+    pub fn base_fee(&self) -> u64 {
+        self.base_fee
+    }
+
+    /// Returns the total amount of gas consumed by the deploys in the block.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
+    /// The proposer's contribution to the random beacon, if any.
+    pub fn random_beacon_contribution(&self) -> Option<&VrfProof> {
+        self.random_beacon_contribution.as_ref()
+    }
+
+    /// The random beacon available to contracts executed in the block.
+    pub fn random_beacon(&self) -> Digest {
+        self.random_beacon
+    }
+
+    /// Generates a random instance using a `TestRng`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random(rng: &mut TestRng) -> Self {
+        BlockHeaderExtension {
+            base_fee: rng.gen_range(1..10),
+            gas_used: rng.gen_range(0..10_000_000_000),
+            random_beacon_contribution: None,
+            random_beacon: rng.gen::<[u8; Digest::LENGTH]>().into(),
+        }
+    }
+}
+
+impl ToBytes for BlockHeaderExtension {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.push(BLOCK_HEADER_EXTENSION_TAG);
+        buffer.extend(self.base_fee.to_bytes()?);
+        buffer.extend(self.gas_used.to_bytes()?);
+        buffer.extend(self.random_beacon_contribution.to_bytes()?);
+        buffer.extend(self.random_beacon.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        BLOCK_HEADER_EXTENSION_TAG.serialized_length()
+            + self.base_fee.serialized_length()
+            + self.gas_used.serialized_length()
+            + self.random_beacon_contribution.serialized_length()
+            + self.random_beacon.serialized_length()
+    }
+}
+
+impl FromBytes for BlockHeaderExtension {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        if tag != BLOCK_HEADER_EXTENSION_TAG {
+            return Err(bytesrepr::Error::Formatting);
+        }
+        let (base_fee, remainder) = u64::from_bytes(remainder)?;
+        let (gas_used, remainder) = u64::from_bytes(remainder)?;
+        let (random_beacon_contribution, remainder) = Option::<VrfProof>::from_bytes(remainder)?;
+        let (random_beacon, remainder) = Digest::from_bytes(remainder)?;
+        let extension = BlockHeaderExtension {
+            base_fee,
+            gas_used,
+            random_beacon_contribution,
+            random_beacon,
+        };
+        Ok((extension, remainder))
+    }
+}
+
 pub(crate) mod specimen_support {
     use crate::utils::specimen::{
         btree_map_distinct_from_prop, Cache, LargestSpecimen, SizeEstimator,
@@ -834,7 +1024,7 @@ pub(crate) mod specimen_support {
 
     use super::{
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHeader,
-        BlockHeaderWithMetadata, BlockSignatures, EraEnd,
+        BlockHeaderExtension, BlockHeaderWithMetadata, BlockSignatures, EraEnd,
     };
     use once_cell::sync::OnceCell;
 
@@ -862,6 +1052,7 @@ pub(crate) mod specimen_support {
                 era_id: LargestSpecimen::largest_specimen(estimator, cache),
                 height: LargestSpecimen::largest_specimen(estimator, cache),
                 protocol_version: LargestSpecimen::largest_specimen(estimator, cache),
+                extension: LargestSpecimen::largest_specimen(estimator, cache),
                 block_hash: OnceCell::with_value(LargestSpecimen::largest_specimen(
                     estimator, cache,
                 )),
@@ -882,6 +1073,7 @@ pub(crate) mod specimen_support {
                 era_id: LargestSpecimen::largest_specimen(estimator, cache),
                 height: LargestSpecimen::largest_specimen(estimator, cache),
                 protocol_version: LargestSpecimen::largest_specimen(estimator, cache),
+                extension: LargestSpecimen::largest_specimen(estimator, cache),
                 block_hash: OnceCell::with_value(LargestSpecimen::largest_specimen(
                     estimator, cache,
                 )),
@@ -889,6 +1081,17 @@ pub(crate) mod specimen_support {
         }
     }
 
+    impl LargestSpecimen for BlockHeaderExtension {
+        fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
+            BlockHeaderExtension {
+                base_fee: LargestSpecimen::largest_specimen(estimator, cache),
+                gas_used: LargestSpecimen::largest_specimen(estimator, cache),
+                random_beacon_contribution: LargestSpecimen::largest_specimen(estimator, cache),
+                random_beacon: LargestSpecimen::largest_specimen(estimator, cache),
+            }
+        }
+    }
+
     impl LargestSpecimen for EraEnd {
         fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
             EraEnd {
@@ -926,6 +1129,7 @@ pub(crate) mod specimen_support {
                 block_hash: LargestSpecimen::largest_specimen(estimator, cache),
                 era_id: LargestSpecimen::largest_specimen(estimator, cache),
                 proofs: btree_map_distinct_from_prop(estimator, "validator_count", cache),
+                aggregated_proof: None,
             }
         }
     }
@@ -1002,6 +1206,36 @@ impl BlockHeader {
         self.protocol_version
     }
 
+    /// Returns the fields added by the fee market and the random beacon, or `None` if this block
+    /// was created before they were activated.
+    pub fn extension(&self) -> Option<&BlockHeaderExtension> {
+        self.extension.as_ref()
+    }
+
+    /// Returns the base fee per unit of gas which applied to the deploys in this block, if any.
+    pub fn base_fee(&self) -> Option<u64> {
+        self.extension.as_ref().map(BlockHeaderExtension::base_fee)
+    }
+
+    /// Returns the total amount of gas consumed by the deploys in this block, if recorded.
+    pub fn gas_used(&self) -> Option<u64> {
+        self.extension.as_ref().map(BlockHeaderExtension::gas_used)
+    }
+
+    /// The proposer's contribution to the random beacon, if any.
+    pub fn random_beacon_contribution(&self) -> Option<&VrfProof> {
+        self.extension
+            .as_ref()
+            .and_then(BlockHeaderExtension::random_beacon_contribution)
+    }
+
+    /// The random beacon available to contracts executed in this block, if any.
+    pub fn random_beacon(&self) -> Option<Digest> {
+        self.extension
+            .as_ref()
+            .map(BlockHeaderExtension::random_beacon)
+    }
+
     /// Returns `true` if this block is the last one in the current era.
     pub fn is_switch_block(&self) -> bool {
         self.era_end.is_some()
@@ -1068,6 +1302,7 @@ impl PartialEq for BlockHeader {
             era_id,
             height,
             protocol_version,
+            extension,
             block_hash: _,
         } = self;
         *parent_hash == other.parent_hash
@@ -1080,6 +1315,7 @@ impl PartialEq for BlockHeader {
             && *era_id == other.era_id
             && *height == other.height
             && *protocol_version == other.protocol_version
+            && *extension == other.extension
     }
 }
 
@@ -1099,6 +1335,13 @@ impl Display for BlockHeader {
             self.random_bit,
             self.protocol_version,
         )?;
+        if let Some(extension) = &self.extension {
+            write!(
+                formatter,
+                ", base fee: {}, gas used: {}, random beacon {}",
+                extension.base_fee, extension.gas_used, extension.random_beacon
+            )?;
+        }
         if let Some(ee) = &self.era_end {
             write!(formatter, ", era_end: {}", ee)?;
         }
@@ -1119,6 +1362,9 @@ impl ToBytes for BlockHeader {
         buffer.extend(self.era_id.to_bytes()?);
         buffer.extend(self.height.to_bytes()?);
         buffer.extend(self.protocol_version.to_bytes()?);
+        if let Some(extension) = &self.extension {
+            buffer.extend(extension.to_bytes()?);
+        }
         Ok(buffer)
     }
 
@@ -1133,6 +1379,10 @@ impl ToBytes for BlockHeader {
             + self.era_id.serialized_length()
             + self.height.serialized_length()
             + self.protocol_version.serialized_length()
+            + self
+                .extension
+                .as_ref()
+                .map_or(0, BlockHeaderExtension::serialized_length)
     }
 }
 
@@ -1148,6 +1398,13 @@ impl FromBytes for BlockHeader {
         let (era_id, remainder) = EraId::from_bytes(remainder)?;
         let (height, remainder) = u64::from_bytes(remainder)?;
         let (protocol_version, remainder) = ProtocolVersion::from_bytes(remainder)?;
+        let (extension, remainder) = match remainder.first() {
+            Some(&BLOCK_HEADER_EXTENSION_TAG) => {
+                let (extension, remainder) = BlockHeaderExtension::from_bytes(remainder)?;
+                (Some(extension), remainder)
+            }
+            _ => (None, remainder),
+        };
         let block_header = BlockHeader {
             parent_hash,
             state_root_hash,
@@ -1159,6 +1416,7 @@ impl FromBytes for BlockHeader {
             era_id,
             height,
             protocol_version,
+            extension,
             block_hash: OnceCell::new(),
         };
         Ok((block_header, remainder))
@@ -1320,6 +1578,15 @@ impl FromBytes for BlockBody {
     }
 }
 
+/// A single BLS signature combining the finality signatures of several validators.
+#[derive(Clone, Debug, PartialOrd, Ord, Hash, Serialize, Deserialize, DataSize, Eq, PartialEq)]
+pub(crate) struct AggregatedProof {
+    /// The validators whose finality signatures are combined.
+    pub(crate) signers: BTreeSet<PublicKey>,
+    /// The aggregate signature.
+    pub(crate) signature: Signature,
+}
+
 /// A storage representation of finality signatures with the associated block hash.
 #[derive(Clone, Debug, PartialOrd, Ord, Hash, Serialize, Deserialize, DataSize, Eq, PartialEq)]
 pub struct BlockSignatures {
@@ -1329,6 +1596,8 @@ pub struct BlockSignatures {
     pub(crate) era_id: EraId,
     /// The signatures associated with the block hash.
     pub(crate) proofs: BTreeMap<PublicKey, Signature>,
+    /// The aggregate of the BLS signatures no longer held individually in `proofs`.
+    pub(crate) aggregated_proof: Option<Box<AggregatedProof>>,
 }
 
 impl BlockSignatures {
@@ -1337,6 +1606,7 @@ impl BlockSignatures {
             block_hash,
             era_id,
             proofs: BTreeMap::new(),
+            aggregated_proof: None,
         }
     }
 
@@ -1348,6 +1618,88 @@ impl BlockSignatures {
         self.proofs.insert(public_key, signature)
     }
 
+    /// Returns `true` if there are neither individual nor aggregated signatures.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.proofs.is_empty() && self.aggregated_proof.is_none()
+    }
+
+    /// Returns the public keys of all validators who signed the block, individually or as part of
+    /// the aggregated proof.
+    pub(crate) fn signers(&self) -> impl Iterator<Item = &PublicKey> + '_ {
+        let aggregated_signers = self
+            .aggregated_proof
+            .iter()
+            .flat_map(|aggregated_proof| aggregated_proof.signers.iter())
+            .filter(move |public_key| !self.proofs.contains_key(*public_key));
+        self.proofs.keys().chain(aggregated_signers)
+    }
+
+    /// Moves all individual BLS signatures into the aggregated proof.
+    ///
+    /// Signatures of other key types are kept individually, as they cannot be aggregated.
+    pub(crate) fn aggregate(&mut self) {
+        let mut signers = BTreeSet::new();
+        let mut signatures = vec![];
+        if let Some(aggregated_proof) = self.aggregated_proof.take() {
+            signers = aggregated_proof.signers;
+            signatures.push(aggregated_proof.signature);
+        }
+        self.proofs.retain(|public_key, signature| match signature {
+            Signature::Bls(_) => {
+                if signers.insert(public_key.clone()) {
+                    signatures.push(*signature);
+                }
+                false
+            }
+            _ => true,
+        });
+        self.aggregated_proof = crypto::aggregate_signatures(&signatures)
+            .ok()
+            .map(|signature| Box::new(AggregatedProof { signers, signature }));
+    }
+
+    /// Adds the signatures from `other`, which must belong to the same block.
+    ///
+    /// Aggregated proofs can only be combined if their signers are disjoint; otherwise the one with
+    /// more signers is kept.
+    pub(crate) fn merge(&mut self, other: BlockSignatures) {
+        for (public_key, signature) in other.proofs {
+            self.insert_proof(public_key, signature);
+        }
+        let other_aggregated_proof = match other.aggregated_proof {
+            Some(aggregated_proof) => aggregated_proof,
+            None => return,
+        };
+        self.aggregated_proof = match self.aggregated_proof.take() {
+            None => Some(other_aggregated_proof),
+            Some(mut aggregated_proof) => {
+                if aggregated_proof
+                    .signers
+                    .is_disjoint(&other_aggregated_proof.signers)
+                {
+                    let signatures = [aggregated_proof.signature, other_aggregated_proof.signature];
+                    match crypto::aggregate_signatures(&signatures) {
+                        Ok(signature) => {
+                            aggregated_proof.signature = signature;
+                            aggregated_proof
+                                .signers
+                                .extend(other_aggregated_proof.signers);
+                            Some(aggregated_proof)
+                        }
+                        Err(error) => {
+                            error!(%error, "failed to merge aggregated finality signatures");
+                            Some(aggregated_proof)
+                        }
+                    }
+                } else if other_aggregated_proof.signers.len() > aggregated_proof.signers.len() {
+                    Some(other_aggregated_proof)
+                } else {
+                    Some(aggregated_proof)
+                }
+            }
+        };
+    }
+
     /// Verify the signatures contained within.
     pub(crate) fn verify(&self) -> Result<(), crypto::Error> {
         for (public_key, signature) in self.proofs.iter() {
@@ -1360,6 +1712,13 @@ impl BlockSignatures {
             };
             signature.is_verified()?;
         }
+        if let Some(aggregated_proof) = &self.aggregated_proof {
+            crypto::verify_aggregate(
+                FinalitySignature::bytes_to_sign(&self.block_hash, self.era_id),
+                &aggregated_proof.signature,
+                &aggregated_proof.signers,
+            )?;
+        }
         Ok(())
     }
 
@@ -1374,6 +1733,12 @@ impl BlockSignatures {
 
     pub(crate) fn has_finality_signature(&self, public_key: &PublicKey) -> bool {
         self.proofs.contains_key(public_key)
+            || self
+                .aggregated_proof
+                .as_ref()
+                .map_or(false, |aggregated_proof| {
+                    aggregated_proof.signers.contains(public_key)
+                })
     }
 
     pub(crate) fn finality_signatures(&self) -> impl Iterator<Item = FinalitySignature> + '_ {
@@ -1391,7 +1756,15 @@ impl Display for BlockSignatures {
             self.block_hash,
             self.era_id,
             self.proofs.len()
-        )
+        )?;
+        if let Some(aggregated_proof) = &self.aggregated_proof {
+            write!(
+                formatter,
+                " and an aggregated proof by {} signers",
+                aggregated_proof.signers.len()
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -1405,6 +1778,7 @@ pub struct Block {
 }
 
 impl Block {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         parent_hash: BlockHash,
         parent_seed: Digest,
@@ -1412,6 +1786,7 @@ impl Block {
         finalized_block: FinalizedBlock,
         next_era_validator_weights: Option<BTreeMap<PublicKey, U512>>,
         protocol_version: ProtocolVersion,
+        extension: Option<BlockHeaderExtension>,
     ) -> Result<Self, BlockCreationError> {
         let body = BlockBody::new(
             *finalized_block.proposer,
@@ -1447,6 +1822,7 @@ impl Block {
             era_id: finalized_block.era_id,
             height: finalized_block.height,
             protocol_version,
+            extension,
             block_hash: OnceCell::new(),
         };
 
@@ -1647,6 +2023,7 @@ impl Block {
             finalized_block,
             next_era_validator_weights,
             protocol_version,
+            Some(BlockHeaderExtension::random(rng)),
         )
         .expect("Could not create random block with specifics")
     }
@@ -1697,6 +2074,7 @@ impl Block {
             finalized_block,
             next_era_validator_weights,
             protocol_version,
+            Some(BlockHeaderExtension::random(rng)),
         )
         .expect("Could not create random block with specifics")
     }
@@ -1825,7 +2203,7 @@ impl Display for BlockWithMetadata {
             "block #{}, {}, with {} block signatures",
             self.block.height(),
             self.block.hash(),
-            self.block_signatures.proofs.len()
+            self.block_signatures.signers().count()
         )
     }
 }
@@ -2189,6 +2567,9 @@ pub(crate) mod json_compatibility {
         pub height: u64,
         /// The protocol version.
         pub protocol_version: ProtocolVersion,
+        /// The fields added by the fee market and the random beacon, if activated.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub extension: Option<BlockHeaderExtension>,
     }
 
     impl From<BlockHeader> for JsonBlockHeader {
@@ -2204,6 +2585,7 @@ pub(crate) mod json_compatibility {
                 era_id: block_header.era_id,
                 height: block_header.height,
                 protocol_version: block_header.protocol_version,
+                extension: block_header.extension,
             }
         }
     }
@@ -2221,6 +2603,7 @@ pub(crate) mod json_compatibility {
                 era_id: block_header.era_id,
                 height: block_header.height,
                 protocol_version: block_header.protocol_version,
+                extension: block_header.extension,
                 block_hash: OnceCell::new(),
             }
         }
@@ -2274,6 +2657,9 @@ pub(crate) mod json_compatibility {
         pub body: JsonBlockBody,
         /// JSON-friendly list of proofs for this block.
         pub proofs: Vec<JsonProof>,
+        /// A single BLS signature combining the proofs of the listed signers, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub aggregated_proof: Option<JsonAggregatedProof>,
     }
 
     impl JsonBlock {
@@ -2282,8 +2668,15 @@ pub(crate) mod json_compatibility {
             let hash = *block.hash();
             let header = JsonBlockHeader::from(block.header.clone());
             let body = JsonBlockBody::from(&block.body);
-            let proofs = maybe_signatures
-                .map(|signatures| signatures.proofs.into_iter().map(JsonProof::from).collect())
+            let (proofs, aggregated_proof) = maybe_signatures
+                .map(|signatures| {
+                    (
+                        signatures.proofs.into_iter().map(JsonProof::from).collect(),
+                        signatures
+                            .aggregated_proof
+                            .map(|aggregated_proof| JsonAggregatedProof::from(*aggregated_proof)),
+                    )
+                })
                 .unwrap_or_default();
 
             JsonBlock {
@@ -2291,6 +2684,7 @@ pub(crate) mod json_compatibility {
                 header,
                 body,
                 proofs,
+                aggregated_proof,
             }
         }
 
@@ -2344,6 +2738,24 @@ pub(crate) mod json_compatibility {
         }
     }
 
+    /// A JSON-friendly representation of an aggregated proof, i.e. a single BLS signature combining
+    /// several validators' finality signatures of a block.
+    #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Eq, DataSize)]
+    #[serde(deny_unknown_fields)]
+    pub struct JsonAggregatedProof {
+        signers: Vec<PublicKey>,
+        signature: Signature,
+    }
+
+    impl From<AggregatedProof> for JsonAggregatedProof {
+        fn from(aggregated_proof: AggregatedProof) -> JsonAggregatedProof {
+            JsonAggregatedProof {
+                signers: aggregated_proof.signers.into_iter().collect(),
+                signature: aggregated_proof.signature,
+            }
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -2433,8 +2845,7 @@ impl FinalitySignature {
         secret_key: &SecretKey,
         public_key: PublicKey,
     ) -> Self {
-        let mut bytes = block_hash.inner().into_vec();
-        bytes.extend_from_slice(&era_id.to_le_bytes());
+        let bytes = Self::bytes_to_sign(&block_hash, era_id);
         let signature = crypto::sign(bytes, secret_key, &public_key);
         FinalitySignature {
             block_hash,
@@ -2461,13 +2872,18 @@ impl FinalitySignature {
         }
     }
 
+    /// Returns the bytes a validator signs to attest to the finality of the given block.
+    pub(crate) fn bytes_to_sign(block_hash: &BlockHash, era_id: EraId) -> Vec<u8> {
+        let mut bytes = block_hash.inner().into_vec();
+        bytes.extend_from_slice(&era_id.to_le_bytes());
+        bytes
+    }
+
     /// Verifies whether the signature is correct.
     pub fn is_verified(&self) -> Result<(), crypto::Error> {
         self.is_verified
             .get_or_init(|| {
-                // NOTE: This needs to be in sync with the `new` constructor.
-                let mut bytes = self.block_hash.inner().into_vec();
-                bytes.extend_from_slice(&self.era_id.to_le_bytes());
+                let bytes = Self::bytes_to_sign(&self.block_hash, self.era_id);
                 crypto::verify(bytes, &self.signature, &self.public_key)
             })
             .clone()
@@ -2637,6 +3053,7 @@ mod tests {
     use casper_types::{bytesrepr, testing::TestRng};
 
     use super::*;
+    use crate::types::TestBlockBuilder;
 
     #[test]
     fn json_block_roundtrip() {
@@ -2670,6 +3087,37 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&block_header);
     }
 
+    #[test]
+    fn legacy_block_header_keeps_encoding_and_hash() {
+        let mut rng = TestRng::new();
+        let block = TestBlockBuilder::new().legacy_header().build(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&block);
+
+        let header = block.header();
+        let mut legacy_bytes = vec![];
+        legacy_bytes.extend(header.parent_hash.to_bytes().unwrap());
+        legacy_bytes.extend(header.state_root_hash.to_bytes().unwrap());
+        legacy_bytes.extend(header.body_hash.to_bytes().unwrap());
+        legacy_bytes.extend(header.random_bit.to_bytes().unwrap());
+        legacy_bytes.extend(header.accumulated_seed.to_bytes().unwrap());
+        legacy_bytes.extend(header.era_end.to_bytes().unwrap());
+        legacy_bytes.extend(header.timestamp.to_bytes().unwrap());
+        legacy_bytes.extend(header.era_id.to_bytes().unwrap());
+        legacy_bytes.extend(header.height.to_bytes().unwrap());
+        legacy_bytes.extend(header.protocol_version.to_bytes().unwrap());
+        assert_eq!(header.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(*block.hash(), BlockHash::new(Digest::hash(legacy_bytes)));
+    }
+
+    #[test]
+    fn extended_block_header_bytesrepr_roundtrip_within_block() {
+        let mut rng = TestRng::new();
+        let block = TestBlockBuilder::new().build(&mut rng);
+        assert!(block.header().extension().is_some());
+        bytesrepr::test_serialization_roundtrip(&block);
+        assert!(block.verify().is_ok());
+    }
+
     #[test]
     fn bytesrepr_roundtrip_era_report() {
         let mut rng = TestRng::new();
@@ -2694,6 +3142,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn random_beacon_ignores_invalid_contribution() {
+        let mut rng = TestRng::new();
+        let parent_random_beacon = Digest::hash(b"parent");
+        let mut finalized_block = FinalizedBlock::random(&mut rng);
+        let valid_beacon = finalized_block.random_beacon(parent_random_beacon);
+        let no_contribution_beacon = Digest::hash(parent_random_beacon);
+        assert_ne!(valid_beacon, no_contribution_beacon);
+        assert_ne!(
+            valid_beacon,
+            finalized_block.random_beacon(Digest::hash(b"other parent"))
+        );
+
+        // A proof by someone other than the proposer is ignored.
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let message =
+            BlockPayload::random_beacon_message(finalized_block.era_id, finalized_block.timestamp);
+        finalized_block.random_beacon_contribution = Some(Box::new(crypto::vrf_prove(
+            message,
+            &secret_key,
+            &public_key,
+        )));
+        assert_eq!(
+            no_contribution_beacon,
+            finalized_block.random_beacon(parent_random_beacon)
+        );
+
+        finalized_block.random_beacon_contribution = None;
+        assert_eq!(
+            no_contribution_beacon,
+            finalized_block.random_beacon(parent_random_beacon)
+        );
+    }
+
     #[test]
     fn random_block_check() {
         let mut rng = TestRng::new();
@@ -2766,4 +3249,63 @@ mod tests {
         // Test should fail b/c `signature` is over `era_id=1` and here we're using `era_id=2`.
         assert!(fs_manufactured.is_verified().is_err());
     }
+    #[test]
+    fn block_signatures_aggregate_and_merge() {
+        let mut rng = TestRng::new();
+        let block = Block::random(&mut rng);
+        let block_hash = *block.hash();
+        let era_id = block.header().era_id();
+        let mut signatures = BlockSignatures::new(block_hash, era_id);
+        let mut other_signatures = BlockSignatures::new(block_hash, era_id);
+
+        // One signer with an ed25519 key, whose signature can't be aggregated.
+        let (secret_key, public_key) = generate_ed25519_keypair();
+        let fs = FinalitySignature::create(block_hash, era_id, &secret_key, public_key.clone());
+        signatures.insert_proof(public_key, fs.signature);
+        // Two signers with BLS keys in each set.
+        let bls_signers: Vec<PublicKey> = (0..4)
+            .map(|index| {
+                let secret_key = SecretKey::random_bls(&mut rng);
+                let public_key = PublicKey::from(&secret_key);
+                let fs = FinalitySignature::create(block_hash, era_id, &secret_key, public_key);
+                if index < 2 {
+                    signatures.insert_proof(fs.public_key.clone(), fs.signature);
+                } else {
+                    other_signatures.insert_proof(fs.public_key.clone(), fs.signature);
+                }
+                fs.public_key
+            })
+            .collect();
+
+        signatures.aggregate();
+        assert_eq!(signatures.proofs.len(), 1);
+        assert_eq!(signatures.signers().count(), 3);
+        assert!(signatures.verify().is_ok());
+        assert!(bls_signers[..2]
+            .iter()
+            .all(|public_key| signatures.has_finality_signature(public_key)));
+        // Individual signatures can't be recovered from the aggregate.
+        assert!(signatures.get_finality_signature(&bls_signers[0]).is_none());
+
+        // Merging disjoint aggregated proofs combines them.
+        other_signatures.aggregate();
+        signatures.merge(other_signatures.clone());
+        assert_eq!(signatures.signers().count(), 5);
+        assert!(signatures.verify().is_ok());
+
+        // Merging an overlapping, smaller aggregated proof keeps the larger one.
+        signatures.merge(other_signatures);
+        assert_eq!(signatures.signers().count(), 5);
+        assert!(signatures.verify().is_ok());
+
+        // An aggregated proof claiming an additional signer is rejected.
+        let mut invalid_signatures = signatures.clone();
+        invalid_signatures
+            .aggregated_proof
+            .as_mut()
+            .unwrap()
+            .signers
+            .insert(PublicKey::random_bls(&mut rng));
+        assert!(invalid_signatures.verify().is_err());
+    }
 }
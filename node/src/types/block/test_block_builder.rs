@@ -9,7 +9,10 @@ use rand::Rng;
 
 use crate::{
     components::consensus::EraReport,
-    types::{Block, BlockHash, BlockPayload, Deploy, DeployHashWithApprovals, FinalizedBlock},
+    types::{
+        Block, BlockHash, BlockHeaderExtension, BlockPayload, Deploy, DeployHashWithApprovals,
+        FinalizedBlock,
+    },
 };
 
 pub(crate) struct TestBlockBuilder {
@@ -21,6 +24,7 @@ pub(crate) struct TestBlockBuilder {
     protocol_version: ProtocolVersion,
     deploys: Vec<Deploy>,
     is_switch: Option<bool>,
+    legacy_header: bool,
 }
 
 impl TestBlockBuilder {
@@ -35,6 +39,7 @@ impl TestBlockBuilder {
             state_root_hash: None,
             parent_hash: None,
             timestamp: None,
+            legacy_header: false,
         }
     }
 
@@ -92,6 +97,12 @@ impl TestBlockBuilder {
         self
     }
 
+    #[allow(unused)]
+    pub(crate) fn legacy_header(mut self) -> Self {
+        self.legacy_header = true;
+        self
+    }
+
     #[allow(unused)]
     pub(crate) fn build(self, rng: &mut TestRng) -> Block {
         let state_root_hash = if let Some(root_hash) = self.state_root_hash {
@@ -140,7 +151,7 @@ impl TestBlockBuilder {
                 .collect::<Vec<_>>();
 
             let random_bit = rng.gen();
-            let block_payload = BlockPayload::new(deploy_hashes, vec![], vec![], random_bit);
+            let block_payload = BlockPayload::new(deploy_hashes, vec![], vec![], random_bit, None);
 
             let era_report = if is_switch {
                 let equivocators_count = rng.gen_range(0..5);
@@ -200,6 +211,7 @@ impl TestBlockBuilder {
             finalized_block,
             next_era_validator_weights,
             self.protocol_version,
+            (!self.legacy_header).then(|| BlockHeaderExtension::random(rng)),
         )
         .expect("Could not create random block with specifics")
     }
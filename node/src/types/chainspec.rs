@@ -33,6 +33,7 @@ use casper_hashing::Digest;
 use casper_types::testing::TestRng;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::ParameterChanges,
     EraId, ProtocolVersion,
 };
 
@@ -40,7 +41,9 @@ pub use self::{
     accounts_config::{AccountConfig, AccountsConfig, DelegatorConfig, ValidatorConfig},
     activation_point::ActivationPoint,
     chainspec_raw_bytes::ChainspecRawBytes,
-    core_config::{ConsensusProtocolName, CoreConfig, LegacyRequiredFinality},
+    core_config::{
+        ConsensusProtocolName, ConsensusProtocolSwitch, CoreConfig, LegacyRequiredFinality,
+    },
     deploy_config::DeployConfig,
     error::Error,
     global_state_update::GlobalStateUpdate,
@@ -58,7 +61,7 @@ const CHAINSPEC_NETWORK_MESSAGE_SAFETY_MARGIN: usize = 256;
 
 /// A collection of configuration settings describing the state of the system at genesis and after
 /// upgrades to basic system functionality occurring after genesis.
-#[derive(DataSize, PartialEq, Eq, Serialize, Debug)]
+#[derive(Clone, DataSize, PartialEq, Eq, Serialize, Debug)]
 pub struct Chainspec {
     /// Protocol config.
     #[serde(rename = "protocol")]
@@ -123,7 +126,21 @@ impl Chainspec {
             warn!("era duration is less than minimum era height * block time!");
         }
 
-        if self.core_config.consensus_protocol == ConsensusProtocolName::Highway {
+        // Switching the consensus protocol is only supported at an upgrade's activation point, so
+        // no switch can be scheduled for a later era.
+        let activation_era = self.protocol_config.activation_point.era_id();
+        if let Some(switch) = self.core_config.consensus_protocol_switches.last() {
+            if switch.era_id > activation_era {
+                error!(
+                    switch_era = switch.era_id.value(),
+                    activation_era = activation_era.value(),
+                    "consensus protocol switch must not be later than the activation point",
+                );
+                return false;
+            }
+        }
+
+        if self.core_config.consensus_protocol(activation_era) == ConsensusProtocolName::Highway {
             if self.core_config.minimum_block_time > self.highway_config.maximum_round_length {
                 error!(
                     minimum_block_time = %self.core_config.minimum_block_time,
@@ -164,6 +181,29 @@ impl Chainspec {
             .then(|| self.protocol_config.activation_point.era_id())
     }
 
+    /// Returns a copy of the chainspec with the parameter changes of applied governance proposals
+    /// which aren't held in global state, or `None` if they name an unknown opcode cost.
+    pub(crate) fn with_parameter_overrides(&self, overrides: &ParameterChanges) -> Option<Self> {
+        let mut chainspec = self.clone();
+        if let Some(block_gas_limit) = overrides.block_gas_limit {
+            chainspec.deploy_config.block_gas_limit = block_gas_limit;
+        }
+        if !overrides.opcode_costs.is_empty() {
+            let wasm_config = self.wasm_config;
+            let opcode_costs = wasm_config
+                .opcode_costs()
+                .with_changes(&overrides.opcode_costs)?;
+            chainspec.wasm_config = WasmConfig::new(
+                wasm_config.max_memory,
+                wasm_config.max_stack_height,
+                opcode_costs,
+                wasm_config.storage_costs(),
+                wasm_config.take_host_function_costs(),
+            );
+        }
+        Some(chainspec)
+    }
+
     pub(crate) fn ee_upgrade_config(
         &self,
         pre_state_hash: Digest,
@@ -363,6 +403,7 @@ mod tests {
             blake2b: HostFunction::new(133, [0, 1, 2, 3]),
             random_bytes: HostFunction::new(123, [0, 1]),
             enable_contract_version: HostFunction::new(142, [0, 1, 2, 3]),
+            get_random_beacon: HostFunction::new(143, [0]),
         });
     static EXPECTED_GENESIS_WASM_COSTS: Lazy<WasmConfig> = Lazy::new(|| {
         WasmConfig::new(
@@ -532,6 +573,72 @@ mod tests {
         assert!(chainspec.is_valid());
     }
 
+    #[test]
+    fn should_validate_consensus_protocol_switch_at_activation_point() {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        chainspec.protocol_config.activation_point = ActivationPoint::EraId(EraId::from(10));
+        chainspec.core_config.consensus_protocol = ConsensusProtocolName::Highway;
+
+        // Switching to Zug at the activation point doesn't require a valid Highway config.
+        chainspec.core_config.consensus_protocol_switches = vec![ConsensusProtocolSwitch {
+            era_id: EraId::from(10),
+            consensus_protocol: ConsensusProtocolName::Zug,
+        }];
+        chainspec.core_config.minimum_block_time = TimeDiff::from_millis(8);
+        chainspec.highway_config.maximum_round_length = TimeDiff::from_millis(7);
+        assert!(chainspec.is_valid());
+
+        // A switch can't be scheduled after the activation point.
+        chainspec.core_config.consensus_protocol_switches[0].era_id = EraId::from(11);
+        assert!(!chainspec.is_valid());
+    }
+
+    #[test]
+    fn should_apply_parameter_overrides() {
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        let overrides = ParameterChanges {
+            validator_slots: Some(1),
+            block_gas_limit: Some(chainspec.deploy_config.block_gas_limit + 1),
+            opcode_costs: [("add".to_string(), 1)].into_iter().collect(),
+            ..Default::default()
+        };
+        let updated = chainspec.with_parameter_overrides(&overrides).unwrap();
+        assert_eq!(
+            updated.deploy_config.block_gas_limit,
+            chainspec.deploy_config.block_gas_limit + 1
+        );
+        assert_eq!(updated.wasm_config.opcode_costs().add, 1);
+        assert_eq!(
+            updated.wasm_config.opcode_costs().mul,
+            chainspec.wasm_config.opcode_costs().mul
+        );
+        // Parameters held in global state are left to the execution engine.
+        assert_eq!(
+            updated.core_config.validator_slots,
+            chainspec.core_config.validator_slots
+        );
+
+        let unknown = ParameterChanges {
+            opcode_costs: [("call".to_string(), 1)].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(chainspec.with_parameter_overrides(&unknown).is_none());
+    }
+
+    #[test]
+    fn should_parse_consensus_protocol_switches() {
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        assert!(chainspec.core_config.consensus_protocol_switches.is_empty());
+
+        let toml = r#"
+            era_id = 10
+            consensus_protocol = 'Zug'
+        "#;
+        let switch: ConsensusProtocolSwitch = toml::from_str(toml).unwrap();
+        assert_eq!(switch.era_id, EraId::from(10));
+        assert_eq!(switch.consensus_protocol, ConsensusProtocolName::Zug);
+    }
+
     #[ignore = "We probably need to reconsider our approach here"]
     #[test]
     fn should_have_deterministic_chainspec_hash() {
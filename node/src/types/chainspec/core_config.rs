@@ -7,7 +7,7 @@ use casper_execution_engine::core::engine_state::engine_config::{FeeHandling, Re
 use casper_types::testing::TestRng;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    PublicKey,
+    EraId, PublicKey,
 };
 
 use datasize::DataSize;
@@ -54,6 +54,15 @@ pub struct CoreConfig {
     /// `start_protocol_version_with_strict_finality_signatures_required`.
     pub legacy_required_finality: LegacyRequiredFinality,
 
+    /// Protocol version from which block headers include the base fee, the gas used and the
+    /// random beacon.  Headers of blocks created in earlier protocol versions lack these fields,
+    /// so that their encoding and hashes stay unchanged.
+    pub start_protocol_version_with_extended_block_headers: ProtocolVersion,
+
+    /// Protocol version from which nodes reconcile gossiped items by exchanging digests.  Nodes
+    /// running earlier protocol versions don't understand digests.
+    pub start_protocol_version_with_gossip_reconciliation: ProtocolVersion,
+
     /// Number of eras before an auction actually defines the set of validators.
     /// If you bond with a sufficient bid in era N, you will be a validator in era N +
     /// auction_delay + 1
@@ -90,12 +99,19 @@ pub struct CoreConfig {
     /// How many peers to simultaneously ask when sync leaping.
     pub simultaneous_peer_requests: u8,
 
-    /// Which consensus protocol to use.
+    /// Which consensus protocol to use, unless superseded by one of the
+    /// `consensus_protocol_switches`.
     pub consensus_protocol: ConsensusProtocolName,
 
+    /// Switches of the consensus protocol scheduled at upgrade activation points, ordered by era.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consensus_protocol_switches: Vec<ConsensusProtocolSwitch>,
+
     /// The maximum amount of delegators per validator.
     /// if the value is 0, there is no maximum capacity.
     pub max_delegators_per_validator: u32,
+    /// The number of consecutive eras a validator can be inactive before its bid is deactivated.
+    pub inactive_eras_before_eviction: u64,
     /// Auction entrypoints such as "add_bid" or "delegate" are disabled if this flag is set to
     /// `false`. Setting up this option makes sense only for private chains where validator set
     /// rotation is unnecessary.
@@ -115,6 +131,16 @@ pub struct CoreConfig {
 }
 
 impl CoreConfig {
+    /// Returns `true` if blocks created in the given protocol version have extended headers.
+    pub(crate) fn has_extended_block_headers(&self, protocol_version: ProtocolVersion) -> bool {
+        protocol_version >= self.start_protocol_version_with_extended_block_headers
+    }
+
+    /// Returns `true` if gossiped items are reconciled in the given protocol version.
+    pub(crate) fn has_gossip_reconciliation(&self, protocol_version: ProtocolVersion) -> bool {
+        protocol_version >= self.start_protocol_version_with_gossip_reconciliation
+    }
+
     /// The number of eras that have already started and whose validators are still bonded.
     pub fn recent_era_count(&self) -> u64 {
         // Safe to use naked `-` operation assuming `CoreConfig::is_valid()` has been checked.
@@ -161,8 +187,34 @@ impl CoreConfig {
             return false;
         }
 
+        let mut previous_switch_era = None;
+        for switch in &self.consensus_protocol_switches {
+            if previous_switch_era.map_or(false, |era_id| era_id >= switch.era_id) {
+                error!(
+                    era = switch.era_id.value(),
+                    "consensus protocol switches must be ordered by strictly increasing era",
+                );
+                return false;
+            }
+            previous_switch_era = Some(switch.era_id);
+        }
+
+        if self.inactive_eras_before_eviction == 0 {
+            error!("inactive eras before eviction must be at least 1");
+            return false;
+        }
+
         true
     }
+
+    /// Returns the consensus protocol used in the given era.
+    pub fn consensus_protocol(&self, era_id: EraId) -> ConsensusProtocolName {
+        self.consensus_protocol_switches
+            .iter()
+            .rev()
+            .find(|switch| switch.era_id <= era_id)
+            .map_or(self.consensus_protocol, |switch| switch.consensus_protocol)
+    }
 }
 
 #[cfg(test)]
@@ -177,6 +229,10 @@ impl CoreConfig {
         let start_protocol_version_with_strict_finality_signatures_required =
             ProtocolVersion::from_parts(1, rng.gen_range(5..10), rng.gen_range(0..100));
         let legacy_required_finality = rng.gen();
+        let start_protocol_version_with_extended_block_headers =
+            ProtocolVersion::from_parts(rng.gen_range(1..3), rng.gen_range(0..10), 0);
+        let start_protocol_version_with_gossip_reconciliation =
+            ProtocolVersion::from_parts(rng.gen_range(1..3), rng.gen_range(0..10), 0);
         let auction_delay = rng.gen_range(1..5);
         let locked_funds_period = TimeDiff::from_seconds(rng.gen_range(600..604_800));
         let vesting_schedule_period = TimeDiff::from_seconds(rng.gen_range(600..604_800));
@@ -192,6 +248,7 @@ impl CoreConfig {
         let strict_argument_checking = rng.gen();
         let simultaneous_peer_requests = rng.gen_range(3..100);
         let consensus_protocol = rng.gen();
+        let inactive_eras_before_eviction = rng.gen_range(1..10);
         let allow_auction_bids = rng.gen();
         let allow_unrestricted_transfers = rng.gen();
         let compute_rewards = rng.gen();
@@ -218,6 +275,8 @@ impl CoreConfig {
             finality_threshold_fraction,
             start_protocol_version_with_strict_finality_signatures_required,
             legacy_required_finality,
+            start_protocol_version_with_extended_block_headers,
+            start_protocol_version_with_gossip_reconciliation,
             auction_delay,
             locked_funds_period,
             vesting_schedule_period,
@@ -230,7 +289,9 @@ impl CoreConfig {
             strict_argument_checking,
             simultaneous_peer_requests,
             consensus_protocol,
+            consensus_protocol_switches: vec![],
             max_delegators_per_validator: 0,
+            inactive_eras_before_eviction,
             allow_auction_bids,
             administrators,
             allow_unrestricted_transfers,
@@ -254,6 +315,14 @@ impl ToBytes for CoreConfig {
                 .to_bytes()?,
         );
         buffer.extend(self.legacy_required_finality.to_bytes()?);
+        buffer.extend(
+            self.start_protocol_version_with_extended_block_headers
+                .to_bytes()?,
+        );
+        buffer.extend(
+            self.start_protocol_version_with_gossip_reconciliation
+                .to_bytes()?,
+        );
         buffer.extend(self.auction_delay.to_bytes()?);
         buffer.extend(self.locked_funds_period.to_bytes()?);
         buffer.extend(self.vesting_schedule_period.to_bytes()?);
@@ -266,7 +335,9 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.strict_argument_checking.to_bytes()?);
         buffer.extend(self.simultaneous_peer_requests.to_bytes()?);
         buffer.extend(self.consensus_protocol.to_bytes()?);
+        buffer.extend(self.consensus_protocol_switches.to_bytes()?);
         buffer.extend(self.max_delegators_per_validator.to_bytes()?);
+        buffer.extend(self.inactive_eras_before_eviction.to_bytes()?);
         buffer.extend(self.allow_auction_bids.to_bytes()?);
         buffer.extend(self.allow_unrestricted_transfers.to_bytes()?);
         buffer.extend(self.compute_rewards.to_bytes()?);
@@ -286,6 +357,12 @@ impl ToBytes for CoreConfig {
                 .start_protocol_version_with_strict_finality_signatures_required
                 .serialized_length()
             + self.legacy_required_finality.serialized_length()
+            + self
+                .start_protocol_version_with_extended_block_headers
+                .serialized_length()
+            + self
+                .start_protocol_version_with_gossip_reconciliation
+                .serialized_length()
             + self.auction_delay.serialized_length()
             + self.locked_funds_period.serialized_length()
             + self.vesting_schedule_period.serialized_length()
@@ -298,7 +375,9 @@ impl ToBytes for CoreConfig {
             + self.strict_argument_checking.serialized_length()
             + self.simultaneous_peer_requests.serialized_length()
             + self.consensus_protocol.serialized_length()
+            + self.consensus_protocol_switches.serialized_length()
             + self.max_delegators_per_validator.serialized_length()
+            + self.inactive_eras_before_eviction.serialized_length()
             + self.allow_auction_bids.serialized_length()
             + self.allow_unrestricted_transfers.serialized_length()
             + self.compute_rewards.serialized_length()
@@ -318,6 +397,10 @@ impl FromBytes for CoreConfig {
         let (start_protocol_version_with_strict_finality_signatures_required, remainder) =
             ProtocolVersion::from_bytes(remainder)?;
         let (legacy_required_finality, remainder) = LegacyRequiredFinality::from_bytes(remainder)?;
+        let (start_protocol_version_with_extended_block_headers, remainder) =
+            ProtocolVersion::from_bytes(remainder)?;
+        let (start_protocol_version_with_gossip_reconciliation, remainder) =
+            ProtocolVersion::from_bytes(remainder)?;
         let (auction_delay, remainder) = u64::from_bytes(remainder)?;
         let (locked_funds_period, remainder) = TimeDiff::from_bytes(remainder)?;
         let (vesting_schedule_period, remainder) = TimeDiff::from_bytes(remainder)?;
@@ -330,7 +413,9 @@ impl FromBytes for CoreConfig {
         let (strict_argument_checking, remainder) = bool::from_bytes(remainder)?;
         let (simultaneous_peer_requests, remainder) = u8::from_bytes(remainder)?;
         let (consensus_protocol, remainder) = ConsensusProtocolName::from_bytes(remainder)?;
+        let (consensus_protocol_switches, remainder) = FromBytes::from_bytes(remainder)?;
         let (max_delegators_per_validator, remainder) = FromBytes::from_bytes(remainder)?;
+        let (inactive_eras_before_eviction, remainder) = u64::from_bytes(remainder)?;
         let (allow_auction_bids, remainder) = FromBytes::from_bytes(remainder)?;
         let (allow_unrestricted_transfers, remainder) = FromBytes::from_bytes(remainder)?;
         let (compute_rewards, remainder) = bool::from_bytes(remainder)?;
@@ -345,6 +430,8 @@ impl FromBytes for CoreConfig {
             finality_threshold_fraction,
             start_protocol_version_with_strict_finality_signatures_required,
             legacy_required_finality,
+            start_protocol_version_with_extended_block_headers,
+            start_protocol_version_with_gossip_reconciliation,
             auction_delay,
             locked_funds_period,
             vesting_schedule_period,
@@ -357,7 +444,9 @@ impl FromBytes for CoreConfig {
             strict_argument_checking,
             simultaneous_peer_requests,
             consensus_protocol,
+            consensus_protocol_switches,
             max_delegators_per_validator,
+            inactive_eras_before_eviction,
             allow_auction_bids,
             allow_unrestricted_transfers,
             compute_rewards,
@@ -441,6 +530,43 @@ impl Distribution<ConsensusProtocolName> for Standard {
     }
 }
 
+/// A change of the consensus protocol, taking effect from the given era on.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ConsensusProtocolSwitch {
+    /// The first era in which the new consensus protocol is used.
+    ///
+    /// This should be the activation point of the upgrade that introduces the switch.
+    pub era_id: EraId,
+    /// The consensus protocol used from `era_id` on.
+    pub consensus_protocol: ConsensusProtocolName,
+}
+
+impl ToBytes for ConsensusProtocolSwitch {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.era_id.to_bytes()?);
+        buffer.extend(self.consensus_protocol.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.era_id.serialized_length() + self.consensus_protocol.serialized_length()
+    }
+}
+
+impl FromBytes for ConsensusProtocolSwitch {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (era_id, remainder) = EraId::from_bytes(bytes)?;
+        let (consensus_protocol, remainder) = ConsensusProtocolName::from_bytes(remainder)?;
+        let switch = ConsensusProtocolSwitch {
+            era_id,
+            consensus_protocol,
+        };
+        Ok((switch, remainder))
+    }
+}
+
 /// Which finality a legacy block needs during a fast sync.
 #[derive(Copy, Clone, DataSize, PartialEq, Eq, Debug)]
 pub enum LegacyRequiredFinality {
@@ -540,6 +666,80 @@ mod tests {
         assert_eq!(config, decoded);
     }
 
+    #[test]
+    fn bytesrepr_roundtrip_with_consensus_protocol_switches() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.consensus_protocol_switches = vec![
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(rng.gen_range(1..100)),
+                consensus_protocol: rng.gen(),
+            },
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(rng.gen_range(100..200)),
+                consensus_protocol: rng.gen(),
+            },
+        ];
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn should_select_consensus_protocol_per_era() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.consensus_protocol = ConsensusProtocolName::Highway;
+        config.consensus_protocol_switches = vec![
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(5),
+                consensus_protocol: ConsensusProtocolName::Zug,
+            },
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(9),
+                consensus_protocol: ConsensusProtocolName::Highway,
+            },
+        ];
+        assert!(config.is_valid());
+
+        let protocol = |era: u64| config.consensus_protocol(EraId::from(era));
+        assert_eq!(protocol(0), ConsensusProtocolName::Highway);
+        assert_eq!(protocol(4), ConsensusProtocolName::Highway);
+        assert_eq!(protocol(5), ConsensusProtocolName::Zug);
+        assert_eq!(protocol(8), ConsensusProtocolName::Zug);
+        assert_eq!(protocol(9), ConsensusProtocolName::Highway);
+        assert_eq!(protocol(1000), ConsensusProtocolName::Highway);
+    }
+
+    #[test]
+    fn should_validate_consensus_protocol_switch_order() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.consensus_protocol_switches = vec![
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(5),
+                consensus_protocol: ConsensusProtocolName::Zug,
+            },
+            ConsensusProtocolSwitch {
+                era_id: EraId::from(5),
+                consensus_protocol: ConsensusProtocolName::Highway,
+            },
+        ];
+        assert!(!config.is_valid());
+        config.consensus_protocol_switches[1].era_id = EraId::from(4);
+        assert!(!config.is_valid());
+        config.consensus_protocol_switches[1].era_id = EraId::from(6);
+        assert!(config.is_valid());
+    }
+
+    #[test]
+    fn should_validate_inactive_eras_before_eviction() {
+        let mut rng = crate::new_rng();
+        let mut config = CoreConfig::random(&mut rng);
+        config.inactive_eras_before_eviction = 1;
+        assert!(config.is_valid());
+        config.inactive_eras_before_eviction = 0;
+        assert!(!config.is_valid());
+    }
+
     #[test]
     fn should_validate_for_finality_threshold() {
         let mut rng = crate::new_rng();
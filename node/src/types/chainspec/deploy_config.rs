@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 #[cfg(test)]
 use std::str::FromStr;
 
@@ -35,11 +36,23 @@ pub struct DeployConfig {
     pub(crate) session_args_max_length: u32,
     pub(crate) native_transfer_minimum_motes: u64,
     pub(crate) max_timestamp_leeway: TimeDiff,
+    /// The lowest value the base fee per unit of gas can take.
+    pub(crate) min_base_fee: u64,
+    /// Bounds the change of the base fee between consecutive blocks to
+    /// `1 / base_fee_change_denominator` of the parent block's base fee.
+    pub(crate) base_fee_change_denominator: u64,
 }
 
+/// The ratio of `block_gas_limit` to the amount of gas a block is targeted to consume.  Blocks
+/// consuming more than the target raise the base fee, blocks consuming less lower it.
+const BASE_FEE_ELASTICITY_MULTIPLIER: u64 = 2;
+
 impl DeployConfig {
     /// Validates `DeployConfig` parameters
     pub fn is_valid(&self) -> bool {
+        if self.base_fee_change_denominator == 0 {
+            return false;
+        }
         // the total number of deploys + transfers should not exceed the number of approvals because
         // each deploy or transfer needs at least one approval to be valid
         if let Some(total_deploy_and_transfer_slots) = self
@@ -51,6 +64,43 @@ impl DeployConfig {
             false
         }
     }
+
+    /// Returns the amount of gas a block is targeted to consume.
+    pub(crate) fn block_gas_target(&self) -> u64 {
+        self.block_gas_limit / BASE_FEE_ELASTICITY_MULTIPLIER
+    }
+
+    /// Returns the base fee per unit of gas for the child of a block which had the given base fee
+    /// and consumed the given amount of gas.
+    ///
+    /// The base fee moves towards the value at which blocks consume `block_gas_target` of gas, by
+    /// at most `1 / base_fee_change_denominator` per block, and never drops below `min_base_fee`.
+    pub(crate) fn next_base_fee(&self, parent_base_fee: u64, parent_gas_used: u64) -> u64 {
+        let gas_target = self.block_gas_target();
+        let parent_base_fee = parent_base_fee.max(self.min_base_fee);
+        if gas_target == 0 || self.base_fee_change_denominator == 0 {
+            return parent_base_fee;
+        }
+        let delta = |gas_delta: u64| -> u128 {
+            u128::from(parent_base_fee) * u128::from(gas_delta)
+                / u128::from(gas_target)
+                / u128::from(self.base_fee_change_denominator)
+        };
+        let next_base_fee = match parent_gas_used.cmp(&gas_target) {
+            Ordering::Equal => u128::from(parent_base_fee),
+            Ordering::Greater => {
+                // Always increase by at least one so that a low base fee can't get stuck.
+                let increase = delta(parent_gas_used - gas_target).max(1);
+                u128::from(parent_base_fee) + increase
+            }
+            Ordering::Less => {
+                u128::from(parent_base_fee).saturating_sub(delta(gas_target - parent_gas_used))
+            }
+        };
+        u64::try_from(next_base_fee)
+            .unwrap_or(u64::MAX)
+            .max(self.min_base_fee)
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +121,8 @@ impl DeployConfig {
         let native_transfer_minimum_motes =
             rng.gen_range(MAX_PAYMENT_AMOUNT..1_000_000_000_000_000);
         let max_timestamp_leeway = TimeDiff::from_seconds(rng.gen_range(0..6));
+        let min_base_fee = rng.gen_range(1..10);
+        let base_fee_change_denominator = rng.gen_range(1..16);
 
         DeployConfig {
             max_payment_cost,
@@ -86,6 +138,8 @@ impl DeployConfig {
             session_args_max_length,
             native_transfer_minimum_motes,
             max_timestamp_leeway,
+            min_base_fee,
+            base_fee_change_denominator,
         }
     }
 }
@@ -107,6 +161,8 @@ impl Default for DeployConfig {
             session_args_max_length: 1024,
             native_transfer_minimum_motes: MAX_PAYMENT_AMOUNT,
             max_timestamp_leeway: TimeDiff::from_str("5sec").unwrap(),
+            min_base_fee: 1,
+            base_fee_change_denominator: 8,
         }
     }
 }
@@ -127,6 +183,8 @@ impl ToBytes for DeployConfig {
         buffer.extend(self.session_args_max_length.to_bytes()?);
         buffer.extend(self.native_transfer_minimum_motes.to_bytes()?);
         buffer.extend(self.max_timestamp_leeway.to_bytes()?);
+        buffer.extend(self.min_base_fee.to_bytes()?);
+        buffer.extend(self.base_fee_change_denominator.to_bytes()?);
         Ok(buffer)
     }
 
@@ -144,6 +202,8 @@ impl ToBytes for DeployConfig {
             + self.session_args_max_length.serialized_length()
             + self.native_transfer_minimum_motes.serialized_length()
             + self.max_timestamp_leeway.serialized_length()
+            + self.min_base_fee.serialized_length()
+            + self.base_fee_change_denominator.serialized_length()
     }
 }
 
@@ -163,6 +223,8 @@ impl FromBytes for DeployConfig {
         let (session_args_max_length, remainder) = u32::from_bytes(remainder)?;
         let (native_transfer_minimum_motes, remainder) = u64::from_bytes(remainder)?;
         let (max_timestamp_leeway, remainder) = TimeDiff::from_bytes(remainder)?;
+        let (min_base_fee, remainder) = u64::from_bytes(remainder)?;
+        let (base_fee_change_denominator, remainder) = u64::from_bytes(remainder)?;
         let config = DeployConfig {
             max_payment_cost,
             max_ttl,
@@ -177,6 +239,8 @@ impl FromBytes for DeployConfig {
             session_args_max_length,
             native_transfer_minimum_motes,
             max_timestamp_leeway,
+            min_base_fee,
+            base_fee_change_denominator,
         };
         Ok((config, remainder))
     }
@@ -228,4 +292,37 @@ mod tests {
         };
         assert!(config.is_valid());
     }
+
+    #[test]
+    fn should_adjust_base_fee_towards_gas_target() {
+        let config = DeployConfig {
+            block_gas_limit: 1_000,
+            min_base_fee: 10,
+            base_fee_change_denominator: 8,
+            ..Default::default()
+        };
+        let gas_target = config.block_gas_target();
+        assert_eq!(gas_target, 500);
+
+        // Exactly on target: unchanged.
+        assert_eq!(config.next_base_fee(800, gas_target), 800);
+        // Full block: increased by 1/8.
+        assert_eq!(config.next_base_fee(800, 1_000), 900);
+        // Empty block: decreased by 1/8.
+        assert_eq!(config.next_base_fee(800, 0), 700);
+        // Slightly over target with a low base fee: still increases.
+        assert_eq!(config.next_base_fee(10, gas_target + 1), 11);
+        // Never drops below the minimum.
+        assert_eq!(config.next_base_fee(10, 0), 10);
+        assert_eq!(config.next_base_fee(0, gas_target), 10);
+    }
+
+    #[test]
+    fn should_reject_zero_base_fee_change_denominator() {
+        let config = DeployConfig {
+            base_fee_change_denominator: 0,
+            ..Default::default()
+        };
+        assert!(!config.is_valid());
+    }
 }
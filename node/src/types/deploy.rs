@@ -62,12 +62,13 @@ pub use approval::Approval;
 pub use approvals_hash::ApprovalsHash;
 pub use deploy_hash::DeployHash;
 pub(crate) use deploy_hash_with_approvals::DeployHashWithApprovals;
-pub use deploy_header::DeployHeader;
+use deploy_header::DeployHeaderV1;
+pub use deploy_header::{DeployHeader, FeeLimits};
 pub use deploy_or_transfer_hash::DeployOrTransferHash;
 pub(crate) use deploy_with_finalized_approvals::DeployWithFinalizedApprovals;
 pub use error::{DeployConfigurationFailure, Error as DeployError, ExcessiveSizeError};
 pub(crate) use finalized_approvals::FinalizedApprovals;
-pub(crate) use footprint::Footprint as DeployFootprint;
+pub(crate) use footprint::{Footprint as DeployFootprint, SessionContract};
 pub use id::Id as DeployId;
 pub(crate) use legacy_deploy::LegacyDeploy;
 pub(crate) use metadata::{Metadata as DeployMetadata, MetadataExt as DeployMetadataExt};
@@ -97,6 +98,8 @@ static DEPLOY: Lazy<Deploy> = Lazy::new(|| {
         body_hash,
         vec![DeployHash::new(Digest::from([1u8; Digest::LENGTH]))],
         String::from("casper-example"),
+        None,
+        None,
     );
     let serialized_header = serialize_header(&header);
     let hash = DeployHash::new(Digest::hash(serialized_header));
@@ -142,6 +145,96 @@ impl Deploy {
         session: ExecutableDeployItem,
         secret_key: &SecretKey,
         account: Option<PublicKey>,
+    ) -> Deploy {
+        Deploy::build(
+            timestamp,
+            ttl,
+            gas_price,
+            None,
+            None,
+            dependencies,
+            chain_name,
+            payment,
+            session,
+            secret_key,
+            account,
+        )
+    }
+
+    /// Constructs a new signed `Deploy` paid for by the `sponsor` account.
+    ///
+    /// The deploy must also be signed by keys satisfying the sponsor's authorization thresholds.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_sponsored(
+        timestamp: Timestamp,
+        ttl: TimeDiff,
+        gas_price: u64,
+        sponsor: PublicKey,
+        dependencies: Vec<DeployHash>,
+        chain_name: String,
+        payment: ExecutableDeployItem,
+        session: ExecutableDeployItem,
+        secret_key: &SecretKey,
+        account: Option<PublicKey>,
+    ) -> Deploy {
+        Deploy::build(
+            timestamp,
+            ttl,
+            gas_price,
+            None,
+            Some(sponsor),
+            dependencies,
+            chain_name,
+            payment,
+            session,
+            secret_key,
+            account,
+        )
+    }
+
+    /// Constructs a new signed `Deploy` with explicit fee limits.
+    ///
+    /// The deploy's gas price is set to the maximum fee per gas.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_fee_limits(
+        timestamp: Timestamp,
+        ttl: TimeDiff,
+        fee_limits: FeeLimits,
+        dependencies: Vec<DeployHash>,
+        chain_name: String,
+        payment: ExecutableDeployItem,
+        session: ExecutableDeployItem,
+        secret_key: &SecretKey,
+        account: Option<PublicKey>,
+    ) -> Deploy {
+        Deploy::build(
+            timestamp,
+            ttl,
+            fee_limits.max_fee_per_gas(),
+            Some(fee_limits),
+            None,
+            dependencies,
+            chain_name,
+            payment,
+            session,
+            secret_key,
+            account,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        timestamp: Timestamp,
+        ttl: TimeDiff,
+        gas_price: u64,
+        fee_limits: Option<FeeLimits>,
+        sponsor: Option<PublicKey>,
+        dependencies: Vec<DeployHash>,
+        chain_name: String,
+        payment: ExecutableDeployItem,
+        session: ExecutableDeployItem,
+        secret_key: &SecretKey,
+        account: Option<PublicKey>,
     ) -> Deploy {
         let serialized_body = serialize_body(&payment, &session);
         let body_hash = Digest::hash(serialized_body);
@@ -158,6 +251,8 @@ impl Deploy {
             body_hash,
             dependencies,
             chain_name,
+            fee_limits,
+            sponsor,
         );
         let serialized_header = serialize_header(&header);
         let hash = DeployHash::new(Digest::hash(serialized_header));
@@ -246,11 +341,27 @@ impl Deploy {
         };
         let size_estimate = self.serialized_length();
         let is_transfer = self.session.is_transfer();
+        let session_contract = match &self.session {
+            ExecutableDeployItem::StoredContractByHash { hash, .. } => {
+                Some(SessionContract::Contract(*hash))
+            }
+            ExecutableDeployItem::StoredVersionedContractByHash { hash, .. } => {
+                Some(SessionContract::ContractPackage(*hash))
+            }
+            ExecutableDeployItem::StoredContractByName { .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { .. } => {
+                Some(SessionContract::ByName)
+            }
+            ExecutableDeployItem::ModuleBytes { .. } | ExecutableDeployItem::Transfer { .. } => {
+                None
+            }
+        };
         Ok(DeployFootprint {
             header,
             gas_estimate,
             size_estimate,
             is_transfer,
+            session_contract,
         })
     }
 
@@ -362,6 +473,17 @@ impl Deploy {
             }
         }
 
+        if let Some(sponsor) = header.sponsor() {
+            if self.session().is_transfer() {
+                debug!(%sponsor, "native transfers cannot be sponsored");
+                return Err(DeployConfigurationFailure::SponsoredTransfer);
+            }
+            if sponsor == header.account() {
+                debug!(%sponsor, "sponsor is the deploy's own account");
+                return Err(DeployConfigurationFailure::SponsorIsDeployAccount);
+            }
+        }
+
         let payment_args_length = self.payment().args().serialized_length();
         if payment_args_length > config.payment_args_max_length as usize {
             debug!(
@@ -421,6 +543,40 @@ impl Deploy {
     }
 }
 
+/// A [`Deploy`] as serialized by versions without support for the header's optional fields.
+///
+/// Its `bincode` encoding lacks the header's `fee_limits` and `sponsor` fields, which are not
+/// trailing in the `Deploy`, so it can't be filled in like other fields missing from legacy stored
+/// values.
+#[derive(Deserialize)]
+pub(crate) struct DeployV1 {
+    hash: DeployHash,
+    header: DeployHeaderV1,
+    payment: ExecutableDeployItem,
+    session: ExecutableDeployItem,
+    approvals: BTreeSet<Approval>,
+}
+
+impl From<DeployV1> for Deploy {
+    fn from(deploy: DeployV1) -> Self {
+        let DeployV1 {
+            hash,
+            header,
+            payment,
+            session,
+            approvals,
+        } = deploy;
+        Deploy {
+            hash,
+            header: header.into(),
+            payment,
+            session,
+            approvals,
+            is_valid: OnceCell::new(),
+        }
+    }
+}
+
 impl hash::Hash for Deploy {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
         // Destructure to make sure we don't accidentally omit fields.
@@ -484,13 +640,16 @@ impl PartialOrd for Deploy {
     }
 }
 
+// The header's optional fields are written after all other fields, as the first byte of the hash
+// following the rest of the header could be mistaken for one of their tags.
 impl ToBytes for Deploy {
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
-        self.header.write_bytes(writer)?;
+        self.header.write_legacy_fields(writer)?;
         self.hash.write_bytes(writer)?;
         self.payment.write_bytes(writer)?;
         self.session.write_bytes(writer)?;
-        self.approvals.write_bytes(writer)
+        self.approvals.write_bytes(writer)?;
+        self.header.write_optional_fields(writer)
     }
 
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
@@ -500,21 +659,23 @@ impl ToBytes for Deploy {
     }
 
     fn serialized_length(&self) -> usize {
-        self.header.serialized_length()
+        self.header.legacy_fields_serialized_length()
             + self.hash.serialized_length()
             + self.payment.serialized_length()
             + self.session.serialized_length()
             + self.approvals.serialized_length()
+            + self.header.optional_fields_serialized_length()
     }
 }
 
 impl FromBytes for Deploy {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
-        let (header, remainder) = DeployHeader::from_bytes(bytes)?;
+        let (header, remainder) = DeployHeader::legacy_fields_from_bytes(bytes)?;
         let (hash, remainder) = DeployHash::from_bytes(remainder)?;
         let (payment, remainder) = ExecutableDeployItem::from_bytes(remainder)?;
         let (session, remainder) = ExecutableDeployItem::from_bytes(remainder)?;
         let (approvals, remainder) = BTreeSet::<Approval>::from_bytes(remainder)?;
+        let (header, remainder) = header.optional_fields_from_bytes(remainder)?;
         let maybe_valid_deploy = Deploy {
             header,
             hash,
@@ -599,14 +760,21 @@ impl From<Deploy> for DeployItem {
             .map(|approval| approval.signer().to_account_hash())
             .collect();
 
-        DeployItem::new(
+        let mut deploy_item = DeployItem::new(
             address,
             deploy.session().clone(),
             deploy.payment().clone(),
-            deploy.header().gas_price(),
+            deploy.header().max_fee_per_gas(),
             authorization_keys,
             casper_types::DeployHash::new(deploy.hash().inner().value()),
-        )
+        );
+        if let Some(max_priority_fee_per_gas) = deploy.header().max_priority_fee_per_gas() {
+            deploy_item = deploy_item.with_max_priority_fee_per_gas(max_priority_fee_per_gas);
+        }
+        if let Some(sponsor) = deploy.header().sponsor() {
+            deploy_item = deploy_item.with_sponsor(sponsor.to_account_hash());
+        }
+        deploy_item
     }
 }
 
@@ -1152,6 +1320,34 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&deploy);
     }
 
+    #[test]
+    fn bytesrepr_roundtrip_with_fee_limits() {
+        let mut rng = crate::new_rng();
+        let deploy = create_deploy_with_fee_limits(&mut rng, FeeLimits::new(10, 3));
+        bytesrepr::test_serialization_roundtrip(deploy.header());
+        bytesrepr::test_serialization_roundtrip(&deploy);
+        assert_eq!(deploy.has_valid_hash(), Ok(()));
+    }
+
+    #[test]
+    fn fee_limits_should_cap_effective_gas_price() {
+        let mut rng = crate::new_rng();
+        let deploy = create_deploy_with_fee_limits(&mut rng, FeeLimits::new(10, 3));
+        let header = deploy.header();
+        assert_eq!(header.gas_price(), 10);
+        assert_eq!(header.effective_gas_price(5), Some(8));
+        assert_eq!(header.effective_gas_price(8), Some(10));
+        assert_eq!(header.effective_gas_price(10), Some(10));
+        assert_eq!(header.effective_gas_price(11), None);
+
+        // Without fee limits the whole gas price is paid.
+        let legacy_deploy = create_deploy(&mut rng, TimeDiff::from_seconds(60), 0, "net-1");
+        let legacy_header = legacy_deploy.header();
+        assert_eq!(legacy_header.effective_gas_price(0), Some(1));
+        assert_eq!(legacy_header.effective_gas_price(1), Some(1));
+        assert_eq!(legacy_header.effective_gas_price(2), None);
+    }
+
     fn create_deploy(
         rng: &mut TestRng,
         ttl: TimeDiff,
@@ -1187,6 +1383,21 @@ mod tests {
         )
     }
 
+    fn create_deploy_with_fee_limits(rng: &mut TestRng, fee_limits: FeeLimits) -> Deploy {
+        let deploy = create_deploy(rng, TimeDiff::from_seconds(60), 0, "net-1");
+        Deploy::new_with_fee_limits(
+            deploy.header.timestamp(),
+            deploy.header.ttl(),
+            fee_limits,
+            vec![],
+            deploy.header.chain_name().to_string(),
+            deploy.payment,
+            deploy.session,
+            &SecretKey::random(rng),
+            None,
+        )
+    }
+
     #[test]
     fn is_valid() {
         let mut rng = crate::new_rng();
@@ -1432,6 +1643,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn not_acceptable_due_to_inconsistent_fee_limits() {
+        let mut rng = crate::new_rng();
+        let deploy_config = DeployConfig::default();
+        let fee_limits = FeeLimits::new(10, 11);
+        let deploy = create_deploy_with_fee_limits(&mut rng, fee_limits);
+
+        let expected_error = DeployConfigurationFailure::InvalidFeeLimits {
+            gas_price: 10,
+            fee_limits,
+        };
+
+        assert_eq!(
+            deploy.is_config_compliant(
+                "net-1",
+                &deploy_config,
+                DEFAULT_MAX_ASSOCIATED_KEYS,
+                TimeDiff::default(),
+                deploy.header().timestamp()
+            ),
+            Err(expected_error)
+        );
+    }
+
+    #[test]
+    fn not_acceptable_due_to_max_fee_below_min_base_fee() {
+        let mut rng = crate::new_rng();
+        let deploy_config = DeployConfig {
+            min_base_fee: 11,
+            ..DeployConfig::default()
+        };
+        let deploy = create_deploy_with_fee_limits(&mut rng, FeeLimits::new(10, 3));
+
+        let expected_error = DeployConfigurationFailure::MaxFeeBelowMinBaseFee {
+            min_base_fee: 11,
+            got: 10,
+        };
+
+        assert_eq!(
+            deploy.is_config_compliant(
+                "net-1",
+                &deploy_config,
+                DEFAULT_MAX_ASSOCIATED_KEYS,
+                TimeDiff::default(),
+                deploy.header().timestamp()
+            ),
+            Err(expected_error)
+        );
+    }
+
     #[test]
     fn not_acceptable_due_to_timestamp_in_future() {
         let mut rng = crate::new_rng();
@@ -1588,6 +1849,114 @@ mod tests {
         );
     }
 
+    /// Creates a deploy whose sponsor is derived from its account.
+    fn create_sponsored_deploy(
+        rng: &mut TestRng,
+        sponsor: impl FnOnce(&PublicKey) -> PublicKey,
+        session: ExecutableDeployItem,
+    ) -> Deploy {
+        let deploy_config = DeployConfig::default();
+        let secret_key = SecretKey::random(rng);
+        let account = PublicKey::from(&secret_key);
+        Deploy::new_sponsored(
+            Timestamp::now(),
+            deploy_config.max_ttl,
+            1,
+            sponsor(&account),
+            vec![],
+            "net-1".to_string(),
+            ExecutableDeployItem::ModuleBytes {
+                module_bytes: Bytes::new(),
+                args: runtime_args! {
+                    "amount" => U512::one()
+                },
+            },
+            session,
+            &secret_key,
+            None,
+        )
+    }
+
+    fn stored_session() -> ExecutableDeployItem {
+        ExecutableDeployItem::StoredContractByName {
+            name: "".to_string(),
+            entry_point: "".to_string(),
+            args: Default::default(),
+        }
+    }
+
+    fn check_config_compliance(deploy: &Deploy) -> Result<(), DeployConfigurationFailure> {
+        let current_timestamp = deploy.header().timestamp();
+        deploy.is_config_compliant(
+            "net-1",
+            &DeployConfig::default(),
+            DEFAULT_MAX_ASSOCIATED_KEYS,
+            TimeDiff::default(),
+            current_timestamp,
+        )
+    }
+
+    #[test]
+    fn sponsored_deploy_should_be_acceptable() {
+        let mut rng = crate::new_rng();
+        let sponsor = PublicKey::random(&mut rng);
+        let deploy = create_sponsored_deploy(&mut rng, |_| sponsor.clone(), stored_session());
+        assert_eq!(deploy.header().sponsor(), Some(&sponsor));
+        assert_eq!(check_config_compliance(&deploy), Ok(()));
+        assert_eq!(deploy.is_valid(), Ok(()));
+    }
+
+    #[test]
+    fn sponsor_should_be_covered_by_deploy_hash() {
+        let mut rng = crate::new_rng();
+        let sponsor = PublicKey::random(&mut rng);
+        let deploy = create_sponsored_deploy(&mut rng, |_| sponsor, stored_session());
+        bytesrepr::test_serialization_roundtrip(deploy.header());
+        bytesrepr::test_serialization_roundtrip(&deploy);
+        assert_eq!(deploy.has_valid_hash(), Ok(()));
+
+        // The same deploy without a sponsor has a different hash.
+        let secret_key = SecretKey::random(&mut rng);
+        let unsponsored = Deploy::new(
+            deploy.header().timestamp(),
+            deploy.header().ttl(),
+            deploy.header().gas_price(),
+            vec![],
+            deploy.header().chain_name().to_string(),
+            deploy.payment().clone(),
+            deploy.session().clone(),
+            &secret_key,
+            Some(deploy.header().account().clone()),
+        );
+        assert_eq!(
+            unsponsored.header().body_hash(),
+            deploy.header().body_hash()
+        );
+        assert_ne!(unsponsored.hash(), deploy.hash());
+    }
+
+    #[test]
+    fn not_acceptable_due_to_sponsor_being_deploy_account() {
+        let mut rng = crate::new_rng();
+        let deploy = create_sponsored_deploy(&mut rng, |account| account.clone(), stored_session());
+        assert_eq!(
+            check_config_compliance(&deploy),
+            Err(DeployConfigurationFailure::SponsorIsDeployAccount)
+        );
+    }
+
+    #[test]
+    fn not_acceptable_due_to_sponsored_transfer() {
+        let mut rng = crate::new_rng();
+        let transfer = create_deploy(&mut rng, TimeDiff::from_seconds(60), 0, "net-1").session;
+        let sponsor = PublicKey::random(&mut rng);
+        let deploy = create_sponsored_deploy(&mut rng, |_| sponsor, transfer);
+        assert_eq!(
+            check_config_compliance(&deploy),
+            Err(DeployConfigurationFailure::SponsoredTransfer)
+        );
+    }
+
     #[test]
     fn not_acceptable_due_to_excessive_payment_amount() {
         let mut rng = crate::new_rng();
@@ -16,6 +16,94 @@ use super::Deploy;
 use super::{DeployConfigurationFailure, DeployHash};
 use crate::{types::chainspec::DeployConfig, utils::DisplayIter};
 
+/// The tag preceding the serialized [`FeeLimits`] of a [`DeployHeader`].
+///
+/// The optional fields of a deploy header are serialized after all others, each preceded by its
+/// tag if present, so legacy deploy headers without them are serialized exactly as before they were
+/// introduced.
+const FEE_LIMITS_TAG: u8 = u8::MAX;
+/// The tag preceding the serialized sponsor of a [`DeployHeader`].
+const SPONSOR_TAG: u8 = u8::MAX - 1;
+
+/// The explicit fee limits of a `Deploy`.
+#[derive(
+    Copy,
+    Clone,
+    DataSize,
+    Ord,
+    PartialOrd,
+    Eq,
+    PartialEq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Debug,
+    JsonSchema,
+)]
+#[serde(deny_unknown_fields)]
+pub struct FeeLimits {
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+}
+
+impl FeeLimits {
+    /// Returns new `FeeLimits`.
+    pub fn new(max_fee_per_gas: u64, max_priority_fee_per_gas: u64) -> Self {
+        FeeLimits {
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+
+    /// The maximum price per gas unit the deploy's sender is willing to pay, including the tip.
+    pub fn max_fee_per_gas(&self) -> u64 {
+        self.max_fee_per_gas
+    }
+
+    /// The maximum price per gas unit above the base fee which is paid to the proposer as a tip.
+    pub fn max_priority_fee_per_gas(&self) -> u64 {
+        self.max_priority_fee_per_gas
+    }
+}
+
+impl Display for FeeLimits {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "fee-limits[max_fee_per_gas: {}, max_priority_fee_per_gas: {}]",
+            self.max_fee_per_gas, self.max_priority_fee_per_gas
+        )
+    }
+}
+
+impl ToBytes for FeeLimits {
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.max_fee_per_gas.write_bytes(writer)?;
+        self.max_priority_fee_per_gas.write_bytes(writer)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.max_fee_per_gas.serialized_length() + self.max_priority_fee_per_gas.serialized_length()
+    }
+}
+
+impl FromBytes for FeeLimits {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (max_fee_per_gas, remainder) = u64::from_bytes(bytes)?;
+        let (max_priority_fee_per_gas, remainder) = u64::from_bytes(remainder)?;
+        Ok((
+            FeeLimits::new(max_fee_per_gas, max_priority_fee_per_gas),
+            remainder,
+        ))
+    }
+}
+
 /// The header portion of a [`Deploy`].
 #[derive(
     Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, JsonSchema,
@@ -29,9 +117,14 @@ pub struct DeployHeader {
     body_hash: Digest,
     dependencies: Vec<DeployHash>,
     chain_name: String,
+    #[serde(default)]
+    fee_limits: Option<FeeLimits>,
+    #[serde(default)]
+    sponsor: Option<PublicKey>,
 }
 
 impl DeployHeader {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         account: PublicKey,
         timestamp: Timestamp,
@@ -40,6 +133,8 @@ impl DeployHeader {
         body_hash: Digest,
         dependencies: Vec<DeployHash>,
         chain_name: String,
+        fee_limits: Option<FeeLimits>,
+        sponsor: Option<PublicKey>,
     ) -> Self {
         DeployHeader {
             account,
@@ -49,6 +144,8 @@ impl DeployHeader {
             body_hash,
             dependencies,
             chain_name,
+            fee_limits,
+            sponsor,
         }
     }
 
@@ -57,6 +154,13 @@ impl DeployHeader {
         &self.account
     }
 
+    /// The account paying for the deploy, if it is sponsored.
+    ///
+    /// The sponsor's main purse is used to pay for the deploy, and receives any refund.
+    pub fn sponsor(&self) -> Option<&PublicKey> {
+        self.sponsor.as_ref()
+    }
+
     /// When the deploy was created.
     pub fn timestamp(&self) -> Timestamp {
         self.timestamp
@@ -73,10 +177,48 @@ impl DeployHeader {
     }
 
     /// Price per gas unit for this deploy.
+    ///
+    /// If the deploy has explicit fee limits, this is equal to their maximum fee per gas.
     pub fn gas_price(&self) -> u64 {
         self.gas_price
     }
 
+    /// The explicit fee limits of the deploy, if any.
+    pub fn fee_limits(&self) -> Option<&FeeLimits> {
+        self.fee_limits.as_ref()
+    }
+
+    /// The maximum price per gas unit the deploy's sender is willing to pay.
+    ///
+    /// The deploy can only be included in a block whose base fee doesn't exceed it.
+    pub fn max_fee_per_gas(&self) -> u64 {
+        self.fee_limits
+            .map_or(self.gas_price, |fee_limits| fee_limits.max_fee_per_gas())
+    }
+
+    /// The maximum price per gas unit above the base fee which is paid to the block's proposer as
+    /// a tip.
+    ///
+    /// Deploys without explicit fee limits pay the whole excess over the base fee as a tip.
+    pub fn max_priority_fee_per_gas(&self) -> Option<u64> {
+        self.fee_limits
+            .map(|fee_limits| fee_limits.max_priority_fee_per_gas())
+    }
+
+    /// The price per gas unit the deploy pays in a block with the given base fee, or `None` if the
+    /// base fee exceeds the deploy's maximum fee.
+    pub fn effective_gas_price(&self, base_fee: u64) -> Option<u64> {
+        let max_fee_per_gas = self.max_fee_per_gas();
+        if max_fee_per_gas < base_fee {
+            return None;
+        }
+        let tip = self
+            .max_priority_fee_per_gas()
+            .unwrap_or(max_fee_per_gas)
+            .min(max_fee_per_gas - base_fee);
+        Some(base_fee + tip)
+    }
+
     /// Hash of the Wasm code.
     pub fn body_hash(&self) -> &Digest {
         &self.body_hash
@@ -92,8 +234,9 @@ impl DeployHeader {
         &self.chain_name
     }
 
-    /// Returns Ok if and only if the dependencies count and TTL are within limits, and the
-    /// timestamp is not later than `at + timestamp_leeway`.  Does NOT check for expiry.
+    /// Returns Ok if and only if the fee limits are consistent, the maximum fee is at least the
+    /// minimum base fee, the dependencies count and TTL are within limits, and the timestamp is not
+    /// later than `at + timestamp_leeway`.  Does NOT check for expiry.
     pub fn is_valid(
         &self,
         config: &DeployConfig,
@@ -101,6 +244,31 @@ impl DeployHeader {
         at: Timestamp,
         deploy_hash: &DeployHash,
     ) -> Result<(), DeployConfigurationFailure> {
+        if let Some(fee_limits) = self.fee_limits {
+            if fee_limits.max_fee_per_gas() != self.gas_price
+                || fee_limits.max_priority_fee_per_gas() > fee_limits.max_fee_per_gas()
+            {
+                debug!(%deploy_hash, deploy_header = %self, "deploy fee limits inconsistent");
+                return Err(DeployConfigurationFailure::InvalidFeeLimits {
+                    gas_price: self.gas_price,
+                    fee_limits,
+                });
+            }
+        }
+
+        if self.max_fee_per_gas() < config.min_base_fee {
+            debug!(
+                %deploy_hash,
+                deploy_header = %self,
+                min_base_fee = %config.min_base_fee,
+                "deploy maximum fee below minimum base fee"
+            );
+            return Err(DeployConfigurationFailure::MaxFeeBelowMinBaseFee {
+                min_base_fee: config.min_base_fee,
+                got: self.max_fee_per_gas(),
+            });
+        }
+
         if self.dependencies.len() > config.max_dependencies as usize {
             debug!(
                 %deploy_hash,
@@ -143,10 +311,9 @@ impl DeployHeader {
     pub fn expires(&self) -> Timestamp {
         self.timestamp.saturating_add(self.ttl)
     }
-}
 
-impl ToBytes for DeployHeader {
-    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+    /// Writes all fields except the optional ones.
+    pub(super) fn write_legacy_fields(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
         self.account.write_bytes(writer)?;
         self.timestamp.write_bytes(writer)?;
         self.ttl.write_bytes(writer)?;
@@ -156,13 +323,23 @@ impl ToBytes for DeployHeader {
         self.chain_name.write_bytes(writer)
     }
 
-    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
-        let mut buffer = bytesrepr::allocate_buffer(self)?;
-        self.write_bytes(&mut buffer)?;
-        Ok(buffer)
+    /// Writes the optional fields which are present, each preceded by its tag.
+    pub(super) fn write_optional_fields(
+        &self,
+        writer: &mut Vec<u8>,
+    ) -> Result<(), bytesrepr::Error> {
+        if let Some(fee_limits) = &self.fee_limits {
+            writer.push(FEE_LIMITS_TAG);
+            fee_limits.write_bytes(writer)?;
+        }
+        if let Some(sponsor) = &self.sponsor {
+            writer.push(SPONSOR_TAG);
+            sponsor.write_bytes(writer)?;
+        }
+        Ok(())
     }
 
-    fn serialized_length(&self) -> usize {
+    pub(super) fn legacy_fields_serialized_length(&self) -> usize {
         self.account.serialized_length()
             + self.timestamp.serialized_length()
             + self.ttl.serialized_length()
@@ -171,10 +348,19 @@ impl ToBytes for DeployHeader {
             + self.dependencies.serialized_length()
             + self.chain_name.serialized_length()
     }
-}
 
-impl FromBytes for DeployHeader {
-    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+    pub(super) fn optional_fields_serialized_length(&self) -> usize {
+        self.fee_limits.as_ref().map_or(0, |fee_limits| {
+            FEE_LIMITS_TAG.serialized_length() + fee_limits.serialized_length()
+        }) + self.sponsor.as_ref().map_or(0, |sponsor| {
+            SPONSOR_TAG.serialized_length() + sponsor.serialized_length()
+        })
+    }
+
+    /// Parses all fields except the optional ones, which are set to `None`.
+    pub(super) fn legacy_fields_from_bytes(
+        bytes: &[u8],
+    ) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (account, remainder) = PublicKey::from_bytes(bytes)?;
         let (timestamp, remainder) = Timestamp::from_bytes(remainder)?;
         let (ttl, remainder) = TimeDiff::from_bytes(remainder)?;
@@ -190,16 +376,97 @@ impl FromBytes for DeployHeader {
             body_hash,
             dependencies,
             chain_name,
+            fee_limits: None,
+            sponsor: None,
         };
         Ok((deploy_header, remainder))
     }
+
+    /// Parses the tagged optional fields which are present.
+    pub(super) fn optional_fields_from_bytes(
+        mut self,
+        mut bytes: &[u8],
+    ) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        if let Some((&FEE_LIMITS_TAG, remainder)) = bytes.split_first() {
+            let (fee_limits, remainder) = FeeLimits::from_bytes(remainder)?;
+            self.fee_limits = Some(fee_limits);
+            bytes = remainder;
+        }
+        if let Some((&SPONSOR_TAG, remainder)) = bytes.split_first() {
+            let (sponsor, remainder) = PublicKey::from_bytes(remainder)?;
+            self.sponsor = Some(sponsor);
+            bytes = remainder;
+        }
+        Ok((self, bytes))
+    }
+}
+
+impl ToBytes for DeployHeader {
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.write_legacy_fields(writer)?;
+        self.write_optional_fields(writer)
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.legacy_fields_serialized_length() + self.optional_fields_serialized_length()
+    }
+}
+
+impl FromBytes for DeployHeader {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (deploy_header, remainder) = DeployHeader::legacy_fields_from_bytes(bytes)?;
+        deploy_header.optional_fields_from_bytes(remainder)
+    }
+}
+
+/// A [`DeployHeader`] as serialized by versions without support for its optional fields.
+#[derive(Deserialize)]
+pub(super) struct DeployHeaderV1 {
+    account: PublicKey,
+    timestamp: Timestamp,
+    ttl: TimeDiff,
+    gas_price: u64,
+    body_hash: Digest,
+    dependencies: Vec<DeployHash>,
+    chain_name: String,
+}
+
+impl From<DeployHeaderV1> for DeployHeader {
+    fn from(header: DeployHeaderV1) -> Self {
+        let DeployHeaderV1 {
+            account,
+            timestamp,
+            ttl,
+            gas_price,
+            body_hash,
+            dependencies,
+            chain_name,
+        } = header;
+        DeployHeader {
+            account,
+            timestamp,
+            ttl,
+            gas_price,
+            body_hash,
+            dependencies,
+            chain_name,
+            fee_limits: None,
+            sponsor: None,
+        }
+    }
 }
 
 impl Display for DeployHeader {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         write!(
             formatter,
-            "deploy-header[account: {}, timestamp: {}, ttl: {}, gas_price: {}, body_hash: {}, dependencies: [{}], chain_name: {}]",
+            "deploy-header[account: {}, timestamp: {}, ttl: {}, gas_price: {}, body_hash: {}, dependencies: [{}], chain_name: {}",
             self.account,
             self.timestamp,
             self.ttl,
@@ -207,7 +474,14 @@ impl Display for DeployHeader {
             self.body_hash,
             DisplayIter::new(self.dependencies.iter()),
             self.chain_name,
-        )
+        )?;
+        if let Some(fee_limits) = &self.fee_limits {
+            write!(formatter, ", {}", fee_limits)?;
+        }
+        if let Some(sponsor) = &self.sponsor {
+            write!(formatter, ", sponsor: {}", sponsor)?;
+        }
+        write!(formatter, "]")
     }
 }
 
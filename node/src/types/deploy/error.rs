@@ -6,6 +6,8 @@ use thiserror::Error;
 
 use casper_types::{TimeDiff, Timestamp, U512};
 
+use super::FeeLimits;
+
 /// A representation of the way in which a deploy failed validation checks.
 #[derive(Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Error, Serialize)]
 pub enum DeployConfigurationFailure {
@@ -40,6 +42,24 @@ pub enum DeployConfigurationFailure {
         got: TimeDiff,
     },
 
+    /// The deploy's fee limits are inconsistent with each other or with its gas price.
+    #[error("{fee_limits} are inconsistent with the gas price of {gas_price}")]
+    InvalidFeeLimits {
+        /// The deploy's gas price.
+        gas_price: u64,
+        /// The deploy's fee limits.
+        fee_limits: FeeLimits,
+    },
+
+    /// The deploy's maximum fee is below the minimum base fee.
+    #[error("maximum fee per gas of {got} is below the minimum base fee of {min_base_fee}")]
+    MaxFeeBelowMinBaseFee {
+        /// The minimum base fee.
+        min_base_fee: u64,
+        /// The deploy's maximum fee per gas.
+        got: u64,
+    },
+
     /// Deploy's timestamp is in the future.
     #[error(
         "timestamp of {got} is later than node's validation timestamp of {validation_timestamp} \
@@ -127,6 +147,14 @@ pub enum DeployConfigurationFailure {
         attempted: Box<U512>,
     },
 
+    /// The sponsor of the deploy is the deploy's own account.
+    #[error("the sponsor of the deploy is the deploy's own account")]
+    SponsorIsDeployAccount,
+
+    /// Native transfers cannot be sponsored.
+    #[error("native transfers cannot be sponsored")]
+    SponsoredTransfer,
+
     /// The amount of approvals on the deploy exceeds the max_associated_keys limit.
     #[error("number of associated keys {got} exceeds the maximum {max_associated_keys}")]
     ExcessiveApprovals {
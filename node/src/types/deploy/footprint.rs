@@ -1,7 +1,7 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_types::Gas;
+use casper_types::{ContractHash, ContractPackageHash, Gas};
 
 use super::DeployHeader;
 
@@ -12,4 +12,17 @@ pub(crate) struct Footprint {
     pub(crate) gas_estimate: Gas,
     pub(crate) size_estimate: usize,
     pub(crate) is_transfer: bool,
+    /// The stored contract called by the session, if any.
+    pub(crate) session_contract: Option<SessionContract>,
+}
+
+/// A stored contract called by the session of a deploy.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq)]
+pub(crate) enum SessionContract {
+    /// A contract referenced by its hash.
+    Contract(ContractHash),
+    /// A contract package referenced by its hash.
+    ContractPackage(ContractPackageHash),
+    /// A contract or contract package referenced by a named key of the deploy's account.
+    ByName,
 }
@@ -1,4 +1,7 @@
-use std::fmt::{self, Debug, Display, Formatter};
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
 
 use datasize::DataSize;
 use hex_fmt::HexFmt;
@@ -6,6 +9,7 @@ use once_cell::sync::Lazy;
 #[cfg(test)]
 use rand::Rng;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
 
 #[cfg(test)]
 use casper_types::testing::TestRng;
@@ -30,6 +34,29 @@ impl NodeId {
     pub fn hash_bytes(&self) -> &[u8] {
         self.0.as_ref()
     }
+
+    /// Parses a node ID from the full hex encoding of its fingerprint.
+    fn from_hex(hex_value: &str) -> Result<Self, NodeIdParseError> {
+        let bytes = base16::decode(hex_value.as_bytes())?;
+        if bytes.len() != KeyFingerprint::LENGTH {
+            return Err(NodeIdParseError::WrongLength(bytes.len()));
+        }
+        let mut array = [0_u8; KeyFingerprint::LENGTH];
+        array.copy_from_slice(bytes.as_slice());
+
+        Ok(NodeId(KeyFingerprint::from(array)))
+    }
+}
+
+/// Error parsing a [`NodeId`] from a string.
+#[derive(Debug, Error)]
+pub enum NodeIdParseError {
+    /// The fingerprint is not valid hex.
+    #[error("invalid hex: {0}")]
+    Hex(#[from] base16::DecodeError),
+    /// The fingerprint has the wrong length.
+    #[error("wrong length: expected {} bytes, got {0}", KeyFingerprint::LENGTH)]
+    WrongLength(usize),
 }
 
 /// Used to serialize and deserialize `NodeID` where the (de)serializer isn't a human-readable type.
@@ -58,15 +85,7 @@ impl<'de> Deserialize<'de> for NodeId {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         if deserializer.is_human_readable() {
             let NodeIdAsString::Tls(hex_value) = NodeIdAsString::deserialize(deserializer)?;
-
-            let bytes = base16::decode(hex_value.as_bytes()).map_err(D::Error::custom)?;
-            if bytes.len() != KeyFingerprint::LENGTH {
-                return Err(SerdeError::custom("wrong length"));
-            }
-            let mut array = [0_u8; KeyFingerprint::LENGTH];
-            array.copy_from_slice(bytes.as_slice());
-
-            Ok(NodeId(KeyFingerprint::from(array)))
+            NodeId::from_hex(&hex_value).map_err(D::Error::custom)
         } else {
             let NodeIdAsBytes::Tls(key_fingerprint) = NodeIdAsBytes::deserialize(deserializer)?;
             Ok(NodeId(key_fingerprint))
@@ -95,6 +114,16 @@ impl Display for NodeId {
     }
 }
 
+/// Parses the full hex encoding of a node ID, optionally prefixed with `tls:` as in its `Display`
+/// output.
+impl FromStr for NodeId {
+    type Err = NodeIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NodeId::from_hex(s.strip_prefix("tls:").unwrap_or(s))
+    }
+}
+
 impl From<KeyFingerprint> for NodeId {
     fn from(id: KeyFingerprint) -> Self {
         NodeId(id)
@@ -182,4 +211,18 @@ mod tests {
         let decoded = serde_json::from_str(&json_string).unwrap();
         assert_eq!(node_id, decoded);
     }
+
+    #[test]
+    fn from_str_roundtrip() {
+        let node_id = NodeId::from(EXAMPLE_HASH_RAW);
+        let hex_value = base16::encode_lower(&EXAMPLE_HASH_RAW);
+
+        assert_eq!(hex_value.parse::<NodeId>().unwrap(), node_id);
+        assert_eq!(
+            format!("tls:{}", hex_value).parse::<NodeId>().unwrap(),
+            node_id
+        );
+        assert!(hex_value[..10].parse::<NodeId>().is_err());
+        assert!("not hex".parse::<NodeId>().is_err());
+    }
 }
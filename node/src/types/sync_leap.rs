@@ -1109,6 +1109,83 @@ mod tests {
         assert!(matches!(result, Err(SyncLeapValidationError::Crypto(_))));
     }
 
+    #[test]
+    fn should_validate_sync_leap_with_aggregated_proofs() {
+        // Chain
+        // 0   1   2   3   4   5   6   7   8   9   10   11
+        // S           S           S           S
+        let switch_blocks = [0, 3, 6, 9];
+        let validation_metadata = test_sync_leap_validation_metadata();
+
+        let mut rng = TestRng::new();
+
+        let validators: Vec<_> = iter::repeat_with(|| SecretKey::random_bls(&mut rng))
+            .take(2)
+            .map(|secret_key| ValidatorSpec {
+                public_key: PublicKey::from(&secret_key),
+                secret_key,
+                weight: Some(100.into()),
+            })
+            .collect();
+        let query = 5;
+        let trusted_ancestor_headers = [4, 3];
+        let signed_block_headers = [6, 9, 11];
+        let add_proofs = true;
+        let mut sync_leap = make_test_sync_leap_with_validators(
+            &mut rng,
+            &validators,
+            &switch_blocks,
+            query,
+            &trusted_ancestor_headers,
+            &signed_block_headers,
+            add_proofs,
+        );
+        for signed_block_header in sync_leap.signed_block_headers.iter_mut() {
+            signed_block_header.block_signatures.aggregate();
+            assert!(signed_block_header.block_signatures.proofs.is_empty());
+        }
+
+        let result = sync_leap.validate(&validation_metadata);
+        assert!(result.is_ok());
+
+        // An aggregated proof for a different block must be rejected.
+        let mut invalid_sync_leap = sync_leap.clone();
+        let other_aggregated_proof = invalid_sync_leap.signed_block_headers[0]
+            .block_signatures
+            .aggregated_proof
+            .clone();
+        invalid_sync_leap
+            .signed_block_headers
+            .last_mut()
+            .unwrap()
+            .block_signatures
+            .aggregated_proof = other_aggregated_proof;
+        let result = invalid_sync_leap.validate(&validation_metadata);
+        assert!(matches!(result, Err(SyncLeapValidationError::Crypto(_))));
+
+        // Claiming a signer whose signature is not part of the aggregate must be rejected.
+        let mut invalid_sync_leap = sync_leap;
+        let mut signed_block_header = invalid_sync_leap.signed_block_headers.pop().unwrap();
+        let aggregated_proof = signed_block_header
+            .block_signatures
+            .aggregated_proof
+            .as_mut()
+            .unwrap();
+        aggregated_proof.signature = crypto::sign(
+            FinalitySignature::bytes_to_sign(
+                &signed_block_header.block_header.block_hash(),
+                signed_block_header.block_header.era_id(),
+            ),
+            &validators[0].secret_key,
+            &validators[0].public_key,
+        );
+        invalid_sync_leap
+            .signed_block_headers
+            .push(signed_block_header);
+        let result = invalid_sync_leap.validate(&validation_metadata);
+        assert!(matches!(result, Err(SyncLeapValidationError::Crypto(_))));
+    }
+
     #[test]
     fn should_use_correct_validator_weights_on_upgrade() {
         // Chain
@@ -2291,6 +2368,7 @@ mod tests {
                 ),
                 validators,
                 self.protocol_version,
+                self.block.header().extension().cloned(),
             )
             .unwrap();
             self.block = next.clone();
@@ -11,11 +11,14 @@ use itertools::Itertools;
 use num_rational::Ratio;
 use serde::Serialize;
 use static_assertions::const_assert;
-use tracing::info;
+use tracing::{error, info};
 
-use casper_types::{EraId, PublicKey, SecretKey, U512};
+#[cfg(test)]
+use casper_types::SecretKey;
+use casper_types::{EraId, PublicKey, U512};
 
 use super::{BlockHeader, FinalitySignature};
+use crate::signer::Signer;
 
 const MAX_VALIDATOR_MATRIX_ENTRIES: usize = 6;
 const_assert!(MAX_VALIDATOR_MATRIX_ENTRIES % 2 == 0);
@@ -47,7 +50,7 @@ pub(crate) struct ValidatorMatrix {
     chainspec_activation_era: EraId,
     #[data_size(skip)]
     finality_threshold_fraction: Ratio<u64>,
-    secret_signing_key: Arc<SecretKey>,
+    signer: Signer,
     public_signing_key: PublicKey,
     auction_delay: u64,
     retrograde_latch: Option<EraId>,
@@ -58,17 +61,17 @@ impl ValidatorMatrix {
         finality_threshold_fraction: Ratio<u64>,
         chainspec_validators: Option<BTreeMap<PublicKey, U512>>,
         chainspec_activation_era: EraId,
-        secret_signing_key: Arc<SecretKey>,
-        public_signing_key: PublicKey,
+        signer: Signer,
         auction_delay: u64,
     ) -> Self {
+        let public_signing_key = signer.public_key().clone();
         let inner = Arc::new(RwLock::new(BTreeMap::new()));
         ValidatorMatrix {
             inner,
             finality_threshold_fraction,
             chainspec_validators: chainspec_validators.map(Arc::new),
             chainspec_activation_era,
-            secret_signing_key,
+            signer,
             public_signing_key,
             auction_delay,
             retrograde_latch: None,
@@ -92,7 +95,7 @@ impl ValidatorMatrix {
             chainspec_activation_era: EraId::from(0),
             finality_threshold_fraction,
             public_signing_key,
-            secret_signing_key,
+            signer: Signer::from(secret_signing_key),
             auction_delay: 1,
             retrograde_latch: None,
         }
@@ -270,12 +273,21 @@ impl ValidatorMatrix {
             .is_self_validator_in_era(block_header.era_id())
             .unwrap_or(false)
         {
-            return Some(FinalitySignature::create(
-                block_header.block_hash(),
-                block_header.era_id(),
-                &self.secret_signing_key,
-                self.public_signing_key.clone(),
-            ));
+            let block_hash = block_header.block_hash();
+            let era_id = block_header.era_id();
+            let bytes = FinalitySignature::bytes_to_sign(&block_hash, era_id);
+            return match self.signer.sign(bytes) {
+                Ok(signature) => Some(FinalitySignature::new(
+                    block_hash,
+                    era_id,
+                    signature,
+                    self.public_signing_key.clone(),
+                )),
+                Err(error) => {
+                    error!(%error, %block_hash, "failed to create finality signature");
+                    None
+                }
+            };
         }
         None
     }
@@ -0,0 +1,16 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+/// A validator's participation in an era: in consensus, as observed by this node, and in the
+/// finality signatures of the era's blocks.
+#[derive(Clone, Copy, DataSize, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct ValidatorPerformance {
+    /// The number of rounds in which the validator was the leader.
+    pub(crate) proposals_assigned: u64,
+    /// The number of rounds led by the validator in which a proposal was made.
+    pub(crate) proposals_made: u64,
+    /// The number of rounds in which the validator voted for the proposal.
+    pub(crate) votes: u64,
+    /// The number of the era's blocks for which a finality signature by the validator is stored.
+    pub(crate) finality_signatures: u64,
+}
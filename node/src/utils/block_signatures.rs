@@ -16,7 +16,8 @@ fn quorum_fraction(fault_tolerance_fraction: Ratio<u64>) -> Ratio<u64> {
 
 /// Returns `Ok(())` if the block signatures' total weight exceeds the threshold which is
 /// calculated using the provided quorum formula. Returns an error if it doesn't, or if one of the
-/// signatures does not belong to a validator.
+/// signatures does not belong to a validator. Signers of an aggregated proof count the same as
+/// individual signers.
 ///
 /// This does _not_ cryptographically verify the signatures.
 pub(crate) fn check_sufficient_block_signatures_with_quorum_formula<F>(
@@ -40,7 +41,7 @@ where
     match maybe_block_signatures {
         Some(block_signatures) => {
             let mut bogus_validators = vec![];
-            for (public_key, _) in block_signatures.proofs.iter() {
+            for public_key in block_signatures.signers() {
                 match trusted_validator_weights.get(public_key) {
                     None => {
                         bogus_validators.push(public_key.clone());
@@ -319,6 +320,58 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn block_signatures_sufficiency_with_aggregated_proof() {
+        const TOTAL_VALIDATORS: usize = 20;
+        const TOTAL_VALIDATORS_WEIGHT: usize = TOTAL_VALIDATORS * TEST_VALIDATOR_WEIGHT;
+        const INSUFFICIENT_BLOCK_SIGNATURES: usize = 13;
+        const JUST_ENOUGH_BLOCK_SIGNATURES: usize = 14;
+
+        let mut rng = TestRng::new();
+
+        // Half of the validators use BLS keys, so their signatures can be aggregated.
+        let (mut validators, mut validator_weights) = generate_validators(TOTAL_VALIDATORS / 2);
+        for _ in 0..(TOTAL_VALIDATORS / 2) {
+            let secret_key = SecretKey::random_bls(&mut rng);
+            let pub_key = PublicKey::from(&secret_key);
+            validators.insert(pub_key.clone(), secret_key);
+            validator_weights.insert(pub_key, U512::from(TEST_VALIDATOR_WEIGHT));
+        }
+        let fault_tolerance_fraction = Ratio::new_raw(1, 3);
+
+        let mut insufficient =
+            create_signatures(&mut rng, &validators, INSUFFICIENT_BLOCK_SIGNATURES);
+        let mut just_enough_weight =
+            create_signatures(&mut rng, &validators, JUST_ENOUGH_BLOCK_SIGNATURES);
+        insufficient.aggregate();
+        just_enough_weight.aggregate();
+        assert!(just_enough_weight.aggregated_proof.is_some());
+        assert!(just_enough_weight.proofs.len() < JUST_ENOUGH_BLOCK_SIGNATURES);
+
+        let result = check_sufficient_block_signatures(
+            &validator_weights,
+            fault_tolerance_fraction,
+            Some(&insufficient),
+        );
+        assert!(matches!(
+            result,
+            Err(BlockSignatureError::InsufficientWeightForFinality {
+                trusted_validator_weights: _,
+                block_signatures: _,
+                signature_weight,
+                total_validator_weight,
+                fault_tolerance_fraction: _
+            }) if *total_validator_weight == TOTAL_VALIDATORS_WEIGHT.into() && **signature_weight.as_ref().unwrap() == INSUFFICIENT_BLOCK_SIGNATURES.into()
+        ));
+
+        let result = check_sufficient_block_signatures(
+            &validator_weights,
+            fault_tolerance_fraction,
+            Some(&just_enough_weight),
+        );
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn detects_bogus_validator() {
         const TOTAL_VALIDATORS: usize = 20;
@@ -16,9 +16,9 @@ use casper_execution_engine::core::engine_state::ExecutableDeployItem;
 use casper_hashing::{ChunkWithProof, Digest};
 use casper_types::{
     bytesrepr::Bytes,
-    crypto::{sign, PublicKey, Signature},
+    crypto::{sign, vrf_prove, PublicKey, Signature},
     AsymmetricType, ContractPackageHash, EraId, ProtocolVersion, RuntimeArgs, SecretKey, SemVer,
-    TimeDiff, Timestamp, KEY_HASH_LENGTH, U512,
+    TimeDiff, Timestamp, VrfProof, KEY_HASH_LENGTH, U512,
 };
 use either::Either;
 use serde::Serialize;
@@ -412,6 +412,7 @@ where
                 System,
                 Ed25519,
                 Secp256k1,
+                Bls,
             }
             largest_variant::<PublicKey, PublicKeyDiscriminants, _, _>(estimator, |variant| {
                 // We take advantage of two things here:
@@ -435,6 +436,15 @@ where
                             .expect("unable to create secp256k1 key from seed bytes");
                         PublicKey::from(&secp256k1_sec)
                     }
+                    PublicKeyDiscriminants::Bls => {
+                        // Clearing the most significant byte keeps the big-endian scalar below the
+                        // BLS12-381 group order.
+                        let mut bls_seed_bytes = seed_bytes;
+                        bls_seed_bytes[0] = 0;
+                        let bls_sec = SecretKey::bls_from_bytes(bls_seed_bytes)
+                            .expect("unable to create BLS key from seed bytes");
+                        PublicKey::from(&bls_sec)
+                    }
                 }
             })
         }
@@ -479,6 +489,7 @@ impl LargestSpecimen for Signature {
             System,
             Ed25519,
             Secp256k1,
+            Bls,
         }
 
         *cache.set(largest_variant::<Self, SignatureDiscriminants, _, _>(
@@ -495,11 +506,47 @@ impl LargestSpecimen for Signature {
 
                     sign([0_u8], secp256k1_sec, &secp256k1_sec.into())
                 }
+                SignatureDiscriminants::Bls => {
+                    let bls_sec = &SecretKey::generate_bls().expect("a correct secret");
+
+                    sign([0_u8], bls_sec, &bls_sec.into())
+                }
             },
         ))
     }
 }
 
+impl LargestSpecimen for VrfProof {
+    fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
+        if let Some(item) = cache.get::<Self>() {
+            return item.clone();
+        }
+
+        #[derive(Debug, Copy, Clone, EnumIter)]
+        enum KeyDiscriminants {
+            Ed25519,
+            Secp256k1,
+            Bls,
+        }
+
+        cache
+            .set(largest_variant::<Self, KeyDiscriminants, _, _>(
+                estimator,
+                |variant| {
+                    let secret_key = match variant {
+                        KeyDiscriminants::Ed25519 => SecretKey::generate_ed25519(),
+                        KeyDiscriminants::Secp256k1 => SecretKey::generate_secp256k1(),
+                        KeyDiscriminants::Bls => SecretKey::generate_bls(),
+                    }
+                    .expect("a correct secret");
+
+                    vrf_prove([0_u8], &secret_key, &(&secret_key).into())
+                },
+            ))
+            .clone()
+    }
+}
+
 impl LargestSpecimen for EraId {
     fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
         EraId::new(LargestSpecimen::largest_specimen(estimator, cache))
@@ -532,6 +579,7 @@ impl LargestSpecimen for Block {
                 cache,
             )),
             LargestSpecimen::largest_specimen(estimator, cache),
+            LargestSpecimen::largest_specimen(estimator, cache),
         )
         .expect("did not expect largest specimen creation of block to fail")
     }
@@ -620,6 +668,7 @@ impl LargestSpecimen for BlockPayload {
             transfers,
             vec_prop_specimen(estimator, "max_accusations_per_block", cache),
             LargestSpecimen::largest_specimen(estimator, cache),
+            LargestSpecimen::largest_specimen(estimator, cache),
         )
     }
 }
@@ -359,6 +359,18 @@ pub fn random_bytes() -> [u8; RANDOM_BYTES_COUNT] {
     ret
 }
 
+/// Returns the random beacon of the current block.
+///
+/// Unlike [`random_bytes`], the beacon is derived from contributions of the block proposers, so it
+/// cannot be predicted by the deployer. It is the same for all deploys in a block, and a proposer
+/// can choose to withhold its contribution, so it should only be relied upon where that is
+/// acceptable.
+pub fn get_random_beacon() -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut ret = [0; BLAKE2B_DIGEST_LENGTH];
+    unsafe { ext_ffi::casper_get_random_beacon(ret.as_mut_ptr()) };
+    ret
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
@@ -5,13 +5,14 @@ use core::mem::MaybeUninit;
 
 use casper_types::{
     account::AccountHash,
-    api_error, bytesrepr,
+    api_error, bytesrepr, runtime_args,
     system::{
         auction::{self, EraInfo},
+        mint::{self, AssetId},
         SystemContractType,
     },
-    ApiError, ContractHash, EraId, HashAddr, PublicKey, TransferResult, TransferredTo, URef, U512,
-    UREF_SERIALIZED_LENGTH,
+    ApiError, ContractHash, EraId, HashAddr, PublicKey, RuntimeArgs, TransferResult, TransferredTo,
+    URef, URefAddr, U512, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -112,6 +113,63 @@ pub fn get_balance() -> Option<U512> {
     get_purse_balance(account::get_main_purse())
 }
 
+/// Creates a new asset with a fixed supply of `initial_supply` and returns the [`URef`] of a new
+/// purse holding the whole supply.
+///
+/// Only contracts and administrator accounts may create assets.
+pub fn create_asset(initial_supply: U512) -> URef {
+    let result: Result<URef, mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_CREATE_ASSET,
+        runtime_args! { mint::ARG_AMOUNT => initial_supply },
+    );
+    result.unwrap_or_revert()
+}
+
+/// Creates a new empty purse for the given asset and returns its [`URef`].
+pub fn create_asset_purse(asset_id: AssetId) -> URef {
+    let result: Result<URef, mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_CREATE_ASSET_PURSE,
+        runtime_args! { mint::ARG_ASSET_ID => asset_id },
+    );
+    result.unwrap_or_revert()
+}
+
+/// Returns the asset held in the given purse, or `None` if it holds the native token.
+pub fn get_purse_asset(purse: URef) -> Option<AssetId> {
+    let result: Result<Option<AssetId>, mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_PURSE_ASSET,
+        runtime_args! { mint::ARG_PURSE => purse },
+    );
+    result.unwrap_or_revert()
+}
+
+/// Locks the given purse: nothing can be transferred out of it before `unlock_timestamp`, at most
+/// `era_spending_limit` can be transferred out of it per era, and if `allowed_targets` is not empty
+/// it can only transfer to the listed purses.
+///
+/// Once a purse is locked, only the system or an administrator can replace its lock.
+pub fn set_purse_lock(
+    purse: URef,
+    unlock_timestamp: Option<u64>,
+    era_spending_limit: Option<U512>,
+    allowed_targets: Vec<URefAddr>,
+) {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        mint::METHOD_SET_PURSE_LOCK,
+        runtime_args! {
+            mint::ARG_PURSE => purse,
+            mint::ARG_UNLOCK_TIMESTAMP => unlock_timestamp,
+            mint::ARG_ERA_SPENDING_LIMIT => era_spending_limit,
+            mint::ARG_ALLOWED_TARGETS => allowed_targets,
+        },
+    );
+    result.unwrap_or_revert()
+}
+
 /// Transfers `amount` of motes from the default purse of the account to `target`
 /// account.  If `target` does not exist it will be created.
 pub fn transfer_to_account(target: AccountHash, amount: U512, id: Option<u64>) -> TransferResult {
@@ -805,4 +805,13 @@ extern "C" {
         contract_hash_ptr: *const u8,
         contract_hash_size: usize,
     ) -> i32;
+    /// Writes the random beacon of the current block to `dest_ptr`. The beacon is 32 bytes long,
+    /// accumulated from contributions of the block proposers. It is up to the caller to ensure
+    /// there are 32 bytes allocated at `dest_ptr`, otherwise data corruption in the wasm memory
+    /// may occur.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest_ptr` - pointer in wasm memory where to write the result
+    pub fn casper_get_random_beacon(dest_ptr: *mut u8);
 }
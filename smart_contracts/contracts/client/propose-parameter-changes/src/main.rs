@@ -0,0 +1,53 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::contract_api::{runtime, system};
+use casper_types::{
+    runtime_args,
+    system::auction::{self, ParameterChanges},
+    EraId, PublicKey, RuntimeArgs,
+};
+
+const ARG_VALIDATOR_SLOTS: &str = "validator_slots";
+const ARG_AUCTION_DELAY: &str = "auction_delay";
+const ARG_LOCKED_FUNDS_PERIOD_MILLIS: &str = "locked_funds_period_millis";
+const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
+const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
+const ARG_BLOCK_GAS_LIMIT: &str = "block_gas_limit";
+const ARG_OPCODE_COSTS: &str = "opcode_costs";
+
+fn propose_parameter_changes(
+    proposer: PublicKey,
+    parameter_changes: ParameterChanges,
+    activation_era: EraId,
+) -> u64 {
+    let contract_hash = system::get_auction();
+    let args = runtime_args! {
+        auction::ARG_PROPOSER => proposer,
+        auction::ARG_PARAMETER_CHANGES => parameter_changes,
+        auction::ARG_ACTIVATION_ERA => activation_era,
+    };
+    runtime::call_contract(
+        contract_hash,
+        auction::METHOD_PROPOSE_PARAMETER_CHANGES,
+        args,
+    )
+}
+
+// Accepts a public key, an activation era and optional new values of the chainspec parameters.
+// Issues a governance proposal to the auction contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let proposer = runtime::get_named_arg(auction::ARG_PROPOSER);
+    let activation_era = runtime::get_named_arg(auction::ARG_ACTIVATION_ERA);
+    let parameter_changes = ParameterChanges {
+        validator_slots: runtime::get_named_arg(ARG_VALIDATOR_SLOTS),
+        auction_delay: runtime::get_named_arg(ARG_AUCTION_DELAY),
+        locked_funds_period_millis: runtime::get_named_arg(ARG_LOCKED_FUNDS_PERIOD_MILLIS),
+        round_seigniorage_rate: runtime::get_named_arg(ARG_ROUND_SEIGNIORAGE_RATE),
+        unbonding_delay: runtime::get_named_arg(ARG_UNBONDING_DELAY),
+        block_gas_limit: runtime::get_named_arg(ARG_BLOCK_GAS_LIMIT),
+        opcode_costs: runtime::get_named_arg(ARG_OPCODE_COSTS),
+    };
+    propose_parameter_changes(proposer, parameter_changes, activation_era);
+}
@@ -0,0 +1,25 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::contract_api::{runtime, system};
+use casper_types::{runtime_args, system::auction, PublicKey, RuntimeArgs};
+
+fn vote_on_proposal(voter: PublicKey, proposal_id: u64, approve: bool) {
+    let contract_hash = system::get_auction();
+    let args = runtime_args! {
+        auction::ARG_VOTER => voter,
+        auction::ARG_PROPOSAL_ID => proposal_id,
+        auction::ARG_APPROVE => approve,
+    };
+    runtime::call_contract::<()>(contract_hash, auction::METHOD_VOTE_ON_PROPOSAL, args);
+}
+
+// Accepts a public key, a proposal id and whether the proposal is approved. Issues a vote on the
+// governance proposal to the auction contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let voter = runtime::get_named_arg(auction::ARG_VOTER);
+    let proposal_id = runtime::get_named_arg(auction::ARG_PROPOSAL_ID);
+    let approve = runtime::get_named_arg(auction::ARG_APPROVE);
+    vote_on_proposal(voter, proposal_id, approve);
+}
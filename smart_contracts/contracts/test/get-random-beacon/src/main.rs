@@ -0,0 +1,17 @@
+#![no_std]
+#![no_main]
+
+use casper_contract::contract_api::runtime;
+
+const ARG_KNOWN_RANDOM_BEACON: &str = "known_random_beacon";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let known_random_beacon: [u8; 32] = runtime::get_named_arg(ARG_KNOWN_RANDOM_BEACON);
+    let actual_random_beacon = runtime::get_random_beacon();
+
+    assert_eq!(
+        actual_random_beacon, known_random_beacon,
+        "actual random beacon not known random beacon"
+    );
+}
@@ -0,0 +1,56 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use casper_contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, Key, URef, U512};
+
+const ARG_ACTION: &str = "action";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE_NAME: &str = "purse_name";
+const ARG_SOURCE_PURSE_NAME: &str = "source_purse_name";
+
+const ACTION_CREATE_ASSET: &str = "create_asset";
+const ACTION_CREATE_ASSET_PURSE: &str = "create_asset_purse";
+const ACTION_TRANSFER: &str = "transfer";
+
+fn get_named_purse(name: &str) -> URef {
+    runtime::get_key(name)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_named_arg(ARG_ACTION);
+    let purse_name: String = runtime::get_named_arg(ARG_PURSE_NAME);
+
+    match action.as_str() {
+        ACTION_CREATE_ASSET => {
+            let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+            let purse = system::create_asset(amount);
+            runtime::put_key(&purse_name, purse.into());
+        }
+        ACTION_CREATE_ASSET_PURSE => {
+            let source_purse_name: String = runtime::get_named_arg(ARG_SOURCE_PURSE_NAME);
+            let asset_id = system::get_purse_asset(get_named_purse(&source_purse_name))
+                .unwrap_or_revert_with(ApiError::InvalidPurse);
+            let purse = system::create_asset_purse(asset_id);
+            runtime::put_key(&purse_name, purse.into());
+        }
+        ACTION_TRANSFER => {
+            let source_purse_name: String = runtime::get_named_arg(ARG_SOURCE_PURSE_NAME);
+            let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+            let source = get_named_purse(&source_purse_name);
+            let target = get_named_purse(&purse_name);
+            system::transfer_from_purse_to_purse(source, target, amount, None).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,83 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use casper_contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{ApiError, Key, URef, U512};
+
+const ARG_ACTION: &str = "action";
+const ARG_AMOUNT: &str = "amount";
+const ARG_UNLOCK_TIMESTAMP: &str = "unlock_timestamp";
+const ARG_ERA_SPENDING_LIMIT: &str = "era_spending_limit";
+const ARG_RESTRICT_TO_TARGET: &str = "restrict_to_target";
+const ARG_TARGET_PURSE_NAME: &str = "target_purse_name";
+
+const ACTION_CREATE_LOCKED_PURSE: &str = "create_locked_purse";
+const ACTION_TRANSFER: &str = "transfer";
+
+const LOCKED_PURSE: &str = "locked_purse";
+const TARGET_PURSE: &str = "target_purse";
+
+fn get_or_create_named_purse(name: &str) -> URef {
+    match runtime::get_key(name) {
+        Some(key) => key
+            .into_uref()
+            .unwrap_or_revert_with(ApiError::InvalidPurse),
+        None => {
+            let purse = system::create_purse();
+            runtime::put_key(name, Key::from(purse));
+            purse
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let action: String = runtime::get_named_arg(ARG_ACTION);
+
+    match action.as_str() {
+        ACTION_CREATE_LOCKED_PURSE => {
+            let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+            let unlock_timestamp: Option<u64> = runtime::get_named_arg(ARG_UNLOCK_TIMESTAMP);
+            let era_spending_limit: Option<U512> = runtime::get_named_arg(ARG_ERA_SPENDING_LIMIT);
+            let restrict_to_target: bool = runtime::get_named_arg(ARG_RESTRICT_TO_TARGET);
+
+            let locked_purse = get_or_create_named_purse(LOCKED_PURSE);
+            let target_purse = get_or_create_named_purse(TARGET_PURSE);
+            system::transfer_from_purse_to_purse(
+                account::get_main_purse(),
+                locked_purse,
+                amount,
+                None,
+            )
+            .unwrap_or_revert();
+
+            let allowed_targets: Vec<_> = if restrict_to_target {
+                vec![target_purse.addr()]
+            } else {
+                Vec::new()
+            };
+            system::set_purse_lock(
+                locked_purse,
+                unlock_timestamp,
+                era_spending_limit,
+                allowed_targets,
+            );
+        }
+        ACTION_TRANSFER => {
+            let amount: U512 = runtime::get_named_arg(ARG_AMOUNT);
+            let target_purse_name: String = runtime::get_named_arg(ARG_TARGET_PURSE_NAME);
+            let locked_purse = get_or_create_named_purse(LOCKED_PURSE);
+            let target_purse = get_or_create_named_purse(&target_purse_name);
+            system::transfer_from_purse_to_purse(locked_purse, target_purse, amount, None)
+                .unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -370,7 +370,7 @@ impl ContractHash {
         let remainder = input
             .strip_prefix(CONTRACT_STRING_PREFIX)
             .ok_or(FromStrError::InvalidPrefix)?;
-        let bytes = HashAddr::try_from(checksummed_hex::decode(remainder)?.as_ref())?;
+        let bytes = HashAddr::try_from(checksummed_hex::decode(remainder)?.as_slice())?;
         Ok(ContractHash(bytes))
     }
 }
@@ -523,7 +523,7 @@ impl ContractPackageHash {
             .strip_prefix(PACKAGE_STRING_LEGACY_EXTRA_PREFIX)
             .unwrap_or(remainder);
 
-        let bytes = HashAddr::try_from(checksummed_hex::decode(hex_addr)?.as_ref())?;
+        let bytes = HashAddr::try_from(checksummed_hex::decode(hex_addr)?.as_slice())?;
         Ok(ContractPackageHash(bytes))
     }
 }
@@ -16,6 +16,7 @@ use core::{
 #[cfg(any(feature = "std", test))]
 use std::path::Path;
 
+use bls12_381::Scalar as BlsSecretKey;
 #[cfg(feature = "datasize")]
 use datasize::DataSize;
 #[cfg(any(feature = "std", test))]
@@ -54,16 +55,18 @@ use crate::testing::TestRng;
 use crate::{
     account::AccountHash,
     bytesrepr,
-    bytesrepr::{FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    bytesrepr::{Bytes, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     checksummed_hex,
-    crypto::Error,
+    crypto::{blake2b, Error},
     CLType, CLTyped, Tagged,
 };
 
+mod bls;
 #[cfg(any(feature = "testing", test))]
 pub mod gens;
 #[cfg(test)]
 mod tests;
+mod vrf;
 
 const TAG_LENGTH: usize = U8_SERIALIZED_LENGTH;
 
@@ -79,6 +82,10 @@ const ED25519: &str = "Ed25519";
 pub const SECP256K1_TAG: u8 = 2;
 const SECP256K1: &str = "Secp256k1";
 
+/// Tag for BLS12-381 variant.
+pub const BLS_TAG: u8 = 3;
+const BLS: &str = "Bls";
+
 const SECP256K1_SECRET_KEY_LENGTH: usize = 32;
 const SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH: usize = 33;
 const SECP256K1_SIGNATURE_LENGTH: usize = 64;
@@ -106,6 +113,12 @@ const SECP256K1_PEM_SECRET_KEY_TAG: &str = "EC PRIVATE KEY";
 #[cfg(any(feature = "std", test))]
 const SECP256K1_PEM_PUBLIC_KEY_TAG: &str = "PUBLIC KEY";
 
+// BLS12-381 keys have no standard DER encoding, so their PEM files hold the raw key bytes.
+#[cfg(any(feature = "std", test))]
+const BLS_PEM_SECRET_KEY_TAG: &str = "BLS12-381 PRIVATE KEY";
+#[cfg(any(feature = "std", test))]
+const BLS_PEM_PUBLIC_KEY_TAG: &str = "BLS12-381 PUBLIC KEY";
+
 #[cfg(any(feature = "std", test))]
 static ED25519_SECRET_KEY: Lazy<SecretKey> = Lazy::new(|| {
     let bytes = [15u8; SecretKey::ED25519_LENGTH];
@@ -159,9 +172,10 @@ where
             }
             ED25519_TAG => Self::ed25519_from_bytes(&key_bytes),
             SECP256K1_TAG => Self::secp256k1_from_bytes(&key_bytes),
+            BLS_TAG => Self::bls_from_bytes(&key_bytes),
             _ => Err(Error::AsymmetricKey(format!(
-                "failed to decode from hex: invalid tag.  Expected {}, {} or {}, got {}",
-                SYSTEM_TAG, ED25519_TAG, SECP256K1_TAG, tag[0]
+                "failed to decode from hex: invalid tag.  Expected {}, {}, {} or {}, got {}",
+                SYSTEM_TAG, ED25519_TAG, SECP256K1_TAG, BLS_TAG, tag[0]
             ))),
         }
     }
@@ -174,6 +188,9 @@ where
 
     /// Constructs a new secp256k1 variant from a byte slice.
     fn secp256k1_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error>;
+
+    /// Constructs a new BLS12-381 variant from a byte slice.
+    fn bls_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error>;
 }
 
 /// A secret or private asymmetric key.
@@ -189,6 +206,9 @@ pub enum SecretKey {
     /// secp256k1 secret key.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1SecretKey),
+    /// BLS12-381 secret key.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Bls(BlsSecretKey),
 }
 
 impl SecretKey {
@@ -201,6 +221,9 @@ impl SecretKey {
     /// The length in bytes of a secp256k1 secret key.
     pub const SECP256K1_LENGTH: usize = SECP256K1_SECRET_KEY_LENGTH;
 
+    /// The length in bytes of a BLS12-381 secret key.
+    pub const BLS_LENGTH: usize = bls::SECRET_KEY_LENGTH;
+
     /// Constructs a new system variant.
     pub fn system() -> Self {
         SecretKey::System
@@ -220,11 +243,17 @@ impl SecretKey {
         ))
     }
 
+    /// Constructs a new BLS12-381 variant from its big-endian byte representation.
+    pub fn bls_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        Ok(SecretKey::Bls(bls::secret_key_from_bytes(bytes.as_ref())?))
+    }
+
     fn variant_name(&self) -> &str {
         match self {
             SecretKey::System => SYSTEM,
             SecretKey::Ed25519(_) => ED25519,
             SecretKey::Secp256k1(_) => SECP256K1,
+            SecretKey::Bls(_) => BLS,
         }
     }
 }
@@ -245,6 +274,13 @@ impl SecretKey {
         SecretKey::secp256k1_from_bytes(bytes).map_err(Into::into)
     }
 
+    /// Generates a new BLS12-381 variant using the system's secure random number generator.
+    pub fn generate_bls() -> Result<Self, ErrorExt> {
+        let mut seed = [0u8; bls::SEED_LENGTH];
+        getrandom::getrandom(&mut seed[..])?;
+        Ok(SecretKey::Bls(bls::secret_key_from_seed(&seed)))
+    }
+
     /// Attempts to write the key bytes to the configured file path.
     #[cfg(any(feature = "std-fs-io", test))]
     pub fn to_file<P: AsRef<Path>>(&self, file: P) -> Result<(), ErrorExt> {
@@ -292,6 +328,10 @@ impl SecretKey {
                 })?;
                 Ok(encoded)
             }
+            SecretKey::Bls(_) => Err(Error::AsymmetricKey(String::from(
+                "BLS12-381 keys have no DER encoding",
+            ))
+            .into()),
         }
     }
 
@@ -374,12 +414,15 @@ impl SecretKey {
 
     /// PEM encodes a key.
     pub fn to_pem(&self) -> Result<String, ErrorExt> {
-        let tag = match self {
+        let (tag, contents) = match self {
             SecretKey::System => return Err(Error::System(String::from("to_pem")).into()),
-            SecretKey::Ed25519(_) => ED25519_PEM_SECRET_KEY_TAG.to_string(),
-            SecretKey::Secp256k1(_) => SECP256K1_PEM_SECRET_KEY_TAG.to_string(),
+            SecretKey::Ed25519(_) => (ED25519_PEM_SECRET_KEY_TAG.to_string(), self.to_der()?),
+            SecretKey::Secp256k1(_) => (SECP256K1_PEM_SECRET_KEY_TAG.to_string(), self.to_der()?),
+            SecretKey::Bls(secret_key) => (
+                BLS_PEM_SECRET_KEY_TAG.to_string(),
+                bls::secret_key_to_bytes(secret_key).to_vec(),
+            ),
         };
-        let contents = self.to_der()?;
         let pem = Pem { tag, contents };
         Ok(pem::encode(&pem))
     }
@@ -388,6 +431,10 @@ impl SecretKey {
     pub fn from_pem<T: AsRef<[u8]>>(input: T) -> Result<Self, ErrorExt> {
         let pem = pem::parse(input)?;
 
+        if pem.tag == BLS_PEM_SECRET_KEY_TAG {
+            return SecretKey::bls_from_bytes(&pem.contents).map_err(Into::into);
+        }
+
         let secret_key = Self::from_der(&pem.contents)?;
 
         let bad_tag = |expected_tag: &str| {
@@ -409,6 +456,7 @@ impl SecretKey {
                     return Err(bad_tag(SECP256K1_PEM_SECRET_KEY_TAG));
                 }
             }
+            SecretKey::Bls(_) => return Err(bad_tag(BLS_PEM_SECRET_KEY_TAG)),
         }
 
         Ok(secret_key)
@@ -440,6 +488,14 @@ impl SecretKey {
         SecretKey::secp256k1_from_bytes(bytes).unwrap()
     }
 
+    /// Generates a random BLS12-381 instance using a `TestRng`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random_bls(rng: &mut TestRng) -> Self {
+        let mut seed = [0u8; bls::SEED_LENGTH];
+        rng.fill_bytes(&mut seed[..]);
+        SecretKey::Bls(bls::secret_key_from_seed(&seed))
+    }
+
     /// Returns an example value for documentation purposes.
     pub fn doc_example() -> &'static Self {
         &ED25519_SECRET_KEY
@@ -464,6 +520,7 @@ impl Tagged<u8> for SecretKey {
             SecretKey::System => SYSTEM_TAG,
             SecretKey::Ed25519(_) => ED25519_TAG,
             SecretKey::Secp256k1(_) => SECP256K1_TAG,
+            SecretKey::Bls(_) => BLS_TAG,
         }
     }
 }
@@ -481,6 +538,9 @@ pub enum PublicKey {
     /// secp256k1 public key.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1PublicKey),
+    /// BLS12-381 public key.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Bls(bls::CompressedPublicKey),
 }
 
 impl PublicKey {
@@ -493,6 +553,9 @@ impl PublicKey {
     /// The length in bytes of a secp256k1 public key.
     pub const SECP256K1_LENGTH: usize = SECP256K1_COMPRESSED_PUBLIC_KEY_LENGTH;
 
+    /// The length in bytes of a compressed BLS12-381 public key.
+    pub const BLS_LENGTH: usize = bls::PUBLIC_KEY_LENGTH;
+
     /// Creates an `AccountHash` from a given `PublicKey` instance.
     pub fn to_account_hash(&self) -> AccountHash {
         AccountHash::from(self)
@@ -508,6 +571,7 @@ impl PublicKey {
             PublicKey::System => SYSTEM,
             PublicKey::Ed25519(_) => ED25519,
             PublicKey::Secp256k1(_) => SECP256K1,
+            PublicKey::Bls(_) => BLS,
         }
     }
 }
@@ -568,6 +632,10 @@ impl PublicKey {
                 })?;
                 Ok(encoded)
             }
+            PublicKey::Bls(_) => Err(Error::AsymmetricKey(String::from(
+                "BLS12-381 keys have no DER encoding",
+            ))
+            .into()),
         }
     }
 
@@ -612,12 +680,15 @@ impl PublicKey {
 
     /// PEM encodes a key.
     pub fn to_pem(&self) -> Result<String, ErrorExt> {
-        let tag = match self {
+        let (tag, contents) = match self {
             PublicKey::System => return Err(Error::System(String::from("to_pem")).into()),
-            PublicKey::Ed25519(_) => ED25519_PEM_PUBLIC_KEY_TAG.to_string(),
-            PublicKey::Secp256k1(_) => SECP256K1_PEM_PUBLIC_KEY_TAG.to_string(),
+            PublicKey::Ed25519(_) => (ED25519_PEM_PUBLIC_KEY_TAG.to_string(), self.to_der()?),
+            PublicKey::Secp256k1(_) => (SECP256K1_PEM_PUBLIC_KEY_TAG.to_string(), self.to_der()?),
+            PublicKey::Bls(public_key) => (
+                BLS_PEM_PUBLIC_KEY_TAG.to_string(),
+                public_key.to_vec(),
+            ),
         };
-        let contents = self.to_der()?;
         let pem = Pem { tag, contents };
         Ok(pem::encode(&pem))
     }
@@ -625,6 +696,9 @@ impl PublicKey {
     /// Decodes a key from a PEM-encoded slice.
     pub fn from_pem<T: AsRef<[u8]>>(input: T) -> Result<Self, ErrorExt> {
         let pem = pem::parse(input)?;
+        if pem.tag == BLS_PEM_PUBLIC_KEY_TAG {
+            return PublicKey::bls_from_bytes(&pem.contents).map_err(Into::into);
+        }
         let public_key = Self::from_der(&pem.contents)?;
         let bad_tag = |expected_tag: &str| {
             ErrorExt::FromPem(format!(
@@ -644,6 +718,7 @@ impl PublicKey {
                     return Err(bad_tag(SECP256K1_PEM_PUBLIC_KEY_TAG));
                 }
             }
+            PublicKey::Bls(_) => return Err(bad_tag(BLS_PEM_PUBLIC_KEY_TAG)),
         }
         Ok(public_key)
     }
@@ -669,6 +744,13 @@ impl PublicKey {
         PublicKey::from(&secret_key)
     }
 
+    /// Generates a random BLS12-381 instance using a `TestRng`.
+    #[cfg(any(feature = "testing", test))]
+    pub fn random_bls(rng: &mut TestRng) -> Self {
+        let secret_key = SecretKey::random_bls(rng);
+        PublicKey::from(&secret_key)
+    }
+
     /// Returns an example value for documentation purposes.
     pub fn doc_example() -> &'static Self {
         &ED25519_PUBLIC_KEY
@@ -692,6 +774,10 @@ impl AsymmetricType<'_> for PublicKey {
                 .map_err(|_| Error::SignatureError)?,
         ))
     }
+
+    fn bls_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        Ok(PublicKey::Bls(bls::public_key_from_bytes(bytes.as_ref())?))
+    }
 }
 
 impl From<&SecretKey> for PublicKey {
@@ -700,6 +786,7 @@ impl From<&SecretKey> for PublicKey {
             SecretKey::System => PublicKey::System,
             SecretKey::Ed25519(secret_key) => PublicKey::Ed25519(secret_key.into()),
             SecretKey::Secp256k1(secret_key) => PublicKey::Secp256k1(secret_key.into()),
+            SecretKey::Bls(secret_key) => PublicKey::Bls(bls::public_key(secret_key)),
         }
     }
 }
@@ -710,6 +797,7 @@ impl From<&PublicKey> for Vec<u8> {
             PublicKey::System => Vec::new(),
             PublicKey::Ed25519(key) => key.to_bytes().into(),
             PublicKey::Secp256k1(key) => key.to_encoded_point(true).as_ref().into(),
+            PublicKey::Bls(key) => key.to_vec(),
         }
     }
 }
@@ -776,6 +864,7 @@ impl Tagged<u8> for PublicKey {
             PublicKey::System => SYSTEM_TAG,
             PublicKey::Ed25519(_) => ED25519_TAG,
             PublicKey::Secp256k1(_) => SECP256K1_TAG,
+            PublicKey::Bls(_) => BLS_TAG,
         }
     }
 }
@@ -793,6 +882,7 @@ impl ToBytes for PublicKey {
                 PublicKey::System => Self::SYSTEM_LENGTH,
                 PublicKey::Ed25519(_) => Self::ED25519_LENGTH,
                 PublicKey::Secp256k1(_) => Self::SECP256K1_LENGTH,
+                PublicKey::Bls(_) => Self::BLS_LENGTH,
             }
     }
 
@@ -807,6 +897,10 @@ impl ToBytes for PublicKey {
                 writer.push(SECP256K1_TAG);
                 writer.extend_from_slice(public_key.to_encoded_point(true).as_ref());
             }
+            PublicKey::Bls(public_key) => {
+                writer.push(BLS_TAG);
+                writer.extend_from_slice(public_key);
+            }
         }
         Ok(())
     }
@@ -831,6 +925,13 @@ impl FromBytes for PublicKey {
                     .map_err(|_error| bytesrepr::Error::Formatting)?;
                 Ok((public_key, remainder))
             }
+            BLS_TAG => {
+                let (raw_bytes, remainder): ([u8; Self::BLS_LENGTH], _) =
+                    FromBytes::from_bytes(remainder)?;
+                let public_key = Self::bls_from_bytes(raw_bytes)
+                    .map_err(|_error| bytesrepr::Error::Formatting)?;
+                Ok((public_key, remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -904,6 +1005,9 @@ pub enum Signature {
     /// Secp256k1 signature.
     #[cfg_attr(feature = "datasize", data_size(skip))]
     Secp256k1(Secp256k1Signature),
+    /// BLS12-381 signature, possibly aggregating the signatures of several signers.
+    #[cfg_attr(feature = "datasize", data_size(skip))]
+    Bls(bls::CompressedSignature),
 }
 
 impl Signature {
@@ -916,6 +1020,9 @@ impl Signature {
     /// The length in bytes of a secp256k1 signature
     pub const SECP256K1_LENGTH: usize = SECP256K1_SIGNATURE_LENGTH;
 
+    /// The length in bytes of a compressed BLS12-381 signature.
+    pub const BLS_LENGTH: usize = bls::SIGNATURE_LENGTH;
+
     /// Constructs a new Ed25519 variant from a byte array.
     pub fn ed25519(bytes: [u8; Self::ED25519_LENGTH]) -> Result<Self, Error> {
         let signature = Ed25519Signature::from_bytes(&bytes);
@@ -934,11 +1041,17 @@ impl Signature {
         Ok(Signature::Secp256k1(signature))
     }
 
+    /// Constructs a new BLS12-381 variant from a byte array.
+    pub fn bls(bytes: [u8; Self::BLS_LENGTH]) -> Result<Self, Error> {
+        Ok(Signature::Bls(bls::signature_from_bytes(&bytes)?))
+    }
+
     fn variant_name(&self) -> &str {
         match self {
             Signature::System => SYSTEM,
             Signature::Ed25519(_) => ED25519,
             Signature::Secp256k1(_) => SECP256K1,
+            Signature::Bls(_) => BLS,
         }
     }
 }
@@ -967,6 +1080,10 @@ impl AsymmetricType<'_> for Signature {
         })?;
         Ok(Signature::Secp256k1(signature))
     }
+
+    fn bls_from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, Error> {
+        Ok(Signature::Bls(bls::signature_from_bytes(bytes.as_ref())?))
+    }
 }
 
 impl Debug for Signature {
@@ -1030,6 +1147,7 @@ impl Tagged<u8> for Signature {
             Signature::System => SYSTEM_TAG,
             Signature::Ed25519(_) => ED25519_TAG,
             Signature::Secp256k1(_) => SECP256K1_TAG,
+            Signature::Bls(_) => BLS_TAG,
         }
     }
 }
@@ -1047,6 +1165,7 @@ impl ToBytes for Signature {
                 Signature::System => Self::SYSTEM_LENGTH,
                 Signature::Ed25519(_) => Self::ED25519_LENGTH,
                 Signature::Secp256k1(_) => Self::SECP256K1_LENGTH,
+                Signature::Bls(_) => Self::BLS_LENGTH,
             }
     }
 
@@ -1063,6 +1182,10 @@ impl ToBytes for Signature {
                 writer.push(SECP256K1_TAG);
                 writer.extend_from_slice(&signature.to_bytes());
             }
+            Signature::Bls(signature) => {
+                writer.push(BLS_TAG);
+                writer.extend_from_slice(signature);
+            }
         }
         Ok(())
     }
@@ -1087,6 +1210,13 @@ impl FromBytes for Signature {
                     Self::secp256k1(raw_bytes).map_err(|_error| bytesrepr::Error::Formatting)?;
                 Ok((public_key, remainder))
             }
+            BLS_TAG => {
+                let (raw_bytes, remainder): ([u8; Self::BLS_LENGTH], _) =
+                    FromBytes::from_bytes(remainder)?;
+                let signature =
+                    Self::bls(raw_bytes).map_err(|_error| bytesrepr::Error::Formatting)?;
+                Ok((signature, remainder))
+            }
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
@@ -1110,6 +1240,7 @@ impl From<&Signature> for Vec<u8> {
             Signature::System => Vec::new(),
             Signature::Ed25519(signature) => signature.to_bytes().into(),
             Signature::Secp256k1(signature) => (*signature.to_bytes()).into(),
+            Signature::Bls(signature) => signature.to_vec(),
         }
     }
 }
@@ -1157,6 +1288,9 @@ pub fn sign<T: AsRef<[u8]>>(
                 .expect("should create signature");
             Signature::Secp256k1(signature)
         }
+        (SecretKey::Bls(secret_key), PublicKey::Bls(public_key)) => {
+            Signature::Bls(bls::sign(message.as_ref(), secret_key, public_key))
+        }
         _ => panic!("secret and public key types must match"),
     }
 }
@@ -1182,6 +1316,9 @@ pub fn verify<T: AsRef<[u8]>>(
                     Error::AsymmetricKey(format!("failed to verify secp256k1 signature: {}", error))
                 })
         }
+        (Signature::Bls(signature), PublicKey::Bls(public_key)) => {
+            bls::verify(message.as_ref(), signature, public_key)
+        }
         _ => Err(Error::AsymmetricKey(format!(
             "type mismatch between {} and {}",
             signature, public_key
@@ -1189,6 +1326,166 @@ pub fn verify<T: AsRef<[u8]>>(
     }
 }
 
+/// Aggregates the given BLS12-381 signatures into a single signature.
+///
+/// Returns an error if there are no signatures or any of them is not a BLS12-381 signature.
+pub fn aggregate_signatures<'a, I>(signatures: I) -> Result<Signature, Error>
+where
+    I: IntoIterator<Item = &'a Signature>,
+{
+    let bls_signatures = signatures
+        .into_iter()
+        .map(|signature| match signature {
+            Signature::Bls(signature) => Ok(signature),
+            _ => Err(Error::AsymmetricKey(format!(
+                "cannot aggregate non-BLS signature {}",
+                signature
+            ))),
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    if bls_signatures.is_empty() {
+        return Err(Error::AsymmetricKey(String::from(
+            "cannot aggregate an empty set of signatures",
+        )));
+    }
+    Ok(Signature::Bls(bls::aggregate(bls_signatures)?))
+}
+
+/// Verifies that the aggregate signature combines signatures of the given message by each of the
+/// given public keys, all of which must be BLS12-381 keys.
+pub fn verify_aggregate<'a, T, I>(
+    message: T,
+    signature: &Signature,
+    public_keys: I,
+) -> Result<(), Error>
+where
+    T: AsRef<[u8]>,
+    I: IntoIterator<Item = &'a PublicKey>,
+{
+    let signature = match signature {
+        Signature::Bls(signature) => signature,
+        _ => {
+            return Err(Error::AsymmetricKey(format!(
+                "{} is not an aggregate signature",
+                signature
+            )))
+        }
+    };
+    let bls_public_keys = public_keys
+        .into_iter()
+        .map(|public_key| match public_key {
+            PublicKey::Bls(public_key) => Ok(public_key),
+            _ => Err(Error::AsymmetricKey(format!(
+                "{} cannot be part of an aggregate signature",
+                public_key
+            ))),
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    bls::verify_aggregate(message.as_ref(), signature, &bls_public_keys)
+}
+
+/// A proof that a VRF output was correctly computed from a message and a secret key.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[serde(transparent)]
+pub struct VrfProof(Bytes);
+
+impl AsRef<[u8]> for VrfProof {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl ToBytes for VrfProof {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)
+    }
+}
+
+impl FromBytes for VrfProof {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (proof, remainder) = Bytes::from_bytes(bytes)?;
+        Ok((VrfProof(proof), remainder))
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchema for VrfProof {
+    fn schema_name() -> String {
+        String::from("VrfProof")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let schema = gen.subschema_for::<String>();
+        let mut schema_object = schema.into_object();
+        schema_object.metadata().description =
+            Some("Hex-encoded proof of a verifiable random function output.".to_string());
+        schema_object.into()
+    }
+}
+
+/// Computes the proof of the output of a verifiable random function (VRF) for the given message
+/// using the given key pair.
+///
+/// Unlike a signature, the proof and output are uniquely determined by the public key and message,
+/// so the holder of the secret key cannot choose between different outputs.  BLS12-381 keys use
+/// their signatures, which are unique, as proofs.
+pub fn vrf_prove<T: AsRef<[u8]>>(
+    message: T,
+    secret_key: &SecretKey,
+    public_key: &PublicKey,
+) -> VrfProof {
+    let proof = match (secret_key, public_key) {
+        (SecretKey::System, PublicKey::System) => {
+            panic!("cannot create VRF proof with system keys")
+        }
+        (SecretKey::Ed25519(secret_key), PublicKey::Ed25519(public_key)) => {
+            vrf::ed25519_prove(secret_key, public_key, message.as_ref())
+        }
+        (SecretKey::Secp256k1(secret_key), PublicKey::Secp256k1(public_key)) => {
+            vrf::secp256k1_prove(secret_key, public_key, message.as_ref())
+        }
+        (SecretKey::Bls(secret_key), PublicKey::Bls(public_key)) => {
+            bls::sign(message.as_ref(), secret_key, public_key).to_vec()
+        }
+        _ => panic!("secret and public key types must match"),
+    };
+    VrfProof(proof.into())
+}
+
+/// Verifies the VRF proof for the given message against the given public key, and returns the
+/// VRF output.
+pub fn vrf_verify<T: AsRef<[u8]>>(
+    message: T,
+    proof: &VrfProof,
+    public_key: &PublicKey,
+) -> Result<Vec<u8>, Error> {
+    match public_key {
+        PublicKey::System => Err(Error::AsymmetricKey(String::from(
+            "VRF proofs based on the system key cannot be verified",
+        ))),
+        PublicKey::Ed25519(public_key) => {
+            vrf::ed25519_verify(public_key, message.as_ref(), proof.as_ref())
+        }
+        PublicKey::Secp256k1(public_key) => {
+            vrf::secp256k1_verify(public_key, message.as_ref(), proof.as_ref())
+        }
+        PublicKey::Bls(public_key) => {
+            let signature = bls::signature_from_bytes(proof.as_ref())?;
+            bls::verify(message.as_ref(), &signature, public_key)?;
+            Ok(blake2b(signature).to_vec())
+        }
+    }
+}
+
 /// Generates an Ed25519 keypair using the operating system's cryptographically secure random number
 /// generator.
 #[cfg(any(feature = "std", test))]
@@ -1215,6 +1512,7 @@ mod detail {
         System,
         Ed25519(Vec<u8>),
         Secp256k1(Vec<u8>),
+        Bls(Vec<u8>),
     }
 
     impl From<&PublicKey> for AsymmetricTypeAsBytes {
@@ -1223,6 +1521,7 @@ mod detail {
                 PublicKey::System => AsymmetricTypeAsBytes::System,
                 key @ PublicKey::Ed25519(_) => AsymmetricTypeAsBytes::Ed25519(key.into()),
                 key @ PublicKey::Secp256k1(_) => AsymmetricTypeAsBytes::Secp256k1(key.into()),
+                key @ PublicKey::Bls(_) => AsymmetricTypeAsBytes::Bls(key.into()),
             }
         }
     }
@@ -1233,6 +1532,7 @@ mod detail {
                 Signature::System => AsymmetricTypeAsBytes::System,
                 key @ Signature::Ed25519(_) => AsymmetricTypeAsBytes::Ed25519(key.into()),
                 key @ Signature::Secp256k1(_) => AsymmetricTypeAsBytes::Secp256k1(key.into()),
+                key @ Signature::Bls(_) => AsymmetricTypeAsBytes::Bls(key.into()),
             }
         }
     }
@@ -1272,6 +1572,9 @@ mod detail {
             AsymmetricTypeAsBytes::Secp256k1(raw_bytes) => {
                 T::secp256k1_from_bytes(raw_bytes).map_err(D::Error::custom)
             }
+            AsymmetricTypeAsBytes::Bls(raw_bytes) => {
+                T::bls_from_bytes(raw_bytes).map_err(D::Error::custom)
+            }
         }
     }
 }
@@ -0,0 +1,191 @@
+//! BLS12-381 signatures, as used for aggregatable finality signatures.
+//!
+//! Public keys are points on G1 and signatures points on G2, both in their compressed encoding.
+//! Every signature covers the signer's public key followed by the message (the "message
+//! augmentation" scheme), so signatures on the same message can be aggregated without requiring
+//! proofs of possession of the secret keys.
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+use bls12_381::{
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+    multi_miller_loop, pairing, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar,
+};
+use sha2::Sha256;
+
+use crate::crypto::Error;
+
+/// The length in bytes of a BLS secret key.
+pub(super) const SECRET_KEY_LENGTH: usize = 32;
+/// The length in bytes of a compressed BLS public key.
+pub(super) const PUBLIC_KEY_LENGTH: usize = 48;
+/// The length in bytes of a compressed BLS signature.
+pub(super) const SIGNATURE_LENGTH: usize = 96;
+/// The length in bytes of the uniformly random input used to derive a secret key.
+#[cfg(any(feature = "std", feature = "testing", test))]
+pub(super) const SEED_LENGTH: usize = 64;
+
+/// The domain separation tag of the message augmentation scheme.
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+
+/// Parses a secret key from its 32-byte big-endian encoding.
+pub(super) fn secret_key_from_bytes(bytes: &[u8]) -> Result<Scalar, Error> {
+    let mut le_bytes: [u8; SECRET_KEY_LENGTH] = bytes
+        .try_into()
+        .map_err(|_| Error::AsymmetricKey(String::from("BLS secret key must be 32 bytes long")))?;
+    le_bytes.reverse();
+    let secret_key: Scalar = Option::from(Scalar::from_bytes(&le_bytes)).ok_or_else(|| {
+        Error::AsymmetricKey(String::from("BLS secret key is not a canonical scalar"))
+    })?;
+    if secret_key == Scalar::from(0u64) {
+        return Err(Error::AsymmetricKey(String::from("BLS secret key is zero")));
+    }
+    Ok(secret_key)
+}
+
+/// Derives a secret key from uniformly random bytes.
+#[cfg(any(feature = "std", feature = "testing", test))]
+pub(super) fn secret_key_from_seed(seed: &[u8; SEED_LENGTH]) -> Scalar {
+    let secret_key = Scalar::from_bytes_wide(seed);
+    if secret_key == Scalar::from(0u64) {
+        // Happens with negligible probability only.
+        Scalar::from(1u64)
+    } else {
+        secret_key
+    }
+}
+
+/// Returns the 32-byte big-endian encoding of the secret key.
+#[cfg(any(feature = "std", test))]
+pub(super) fn secret_key_to_bytes(secret_key: &Scalar) -> [u8; SECRET_KEY_LENGTH] {
+    let mut bytes = secret_key.to_bytes();
+    bytes.reverse();
+    bytes
+}
+
+/// A public key in its compressed encoding, known to be a valid non-identity point in G1.
+pub(super) type CompressedPublicKey = [u8; PUBLIC_KEY_LENGTH];
+/// A signature in its compressed encoding, known to be a valid point in G2.
+pub(super) type CompressedSignature = [u8; SIGNATURE_LENGTH];
+
+/// Returns the public key of the given secret key.
+pub(super) fn public_key(secret_key: &Scalar) -> CompressedPublicKey {
+    G1Affine::from(G1Projective::generator() * secret_key).to_compressed()
+}
+
+/// Parses a compressed public key, checking that it is a valid non-identity point in G1.
+pub(super) fn public_key_from_bytes(bytes: &[u8]) -> Result<CompressedPublicKey, Error> {
+    let compressed: CompressedPublicKey = bytes
+        .try_into()
+        .map_err(|_| Error::AsymmetricKey(String::from("BLS public key must be 48 bytes long")))?;
+    decompress_public_key(&compressed)?;
+    Ok(compressed)
+}
+
+/// Parses a compressed signature, checking that it is a valid point in G2.
+pub(super) fn signature_from_bytes(bytes: &[u8]) -> Result<CompressedSignature, Error> {
+    let compressed: CompressedSignature = bytes
+        .try_into()
+        .map_err(|_| Error::AsymmetricKey(String::from("BLS signature must be 96 bytes long")))?;
+    decompress_signature(&compressed)?;
+    Ok(compressed)
+}
+
+fn decompress_public_key(compressed: &CompressedPublicKey) -> Result<G1Affine, Error> {
+    let public_key: G1Affine = Option::from(G1Affine::from_compressed(compressed))
+        .ok_or_else(|| Error::AsymmetricKey(String::from("invalid BLS public key")))?;
+    if bool::from(public_key.is_identity()) {
+        return Err(Error::AsymmetricKey(String::from(
+            "BLS public key is the identity",
+        )));
+    }
+    Ok(public_key)
+}
+
+fn decompress_signature(compressed: &CompressedSignature) -> Result<G2Affine, Error> {
+    Option::from(G2Affine::from_compressed(compressed))
+        .ok_or_else(|| Error::AsymmetricKey(String::from("invalid BLS signature")))
+}
+
+/// Hashes the public key and the message it signs to a point in G2.
+fn hash_to_g2(message: &[u8], public_key: &CompressedPublicKey) -> G2Affine {
+    let augmented_message: Vec<u8> = public_key.iter().chain(message).copied().collect();
+    G2Affine::from(
+        <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(augmented_message, DST),
+    )
+}
+
+/// Signs the message with the given secret key.
+pub(super) fn sign(
+    message: &[u8],
+    secret_key: &Scalar,
+    public_key: &CompressedPublicKey,
+) -> CompressedSignature {
+    G2Affine::from(G2Projective::from(hash_to_g2(message, public_key)) * secret_key).to_compressed()
+}
+
+/// Verifies a signature of the message by the given public key.
+pub(super) fn verify(
+    message: &[u8],
+    signature: &CompressedSignature,
+    public_key: &CompressedPublicKey,
+) -> Result<(), Error> {
+    let signature = decompress_signature(signature)?;
+    if pairing(
+        &decompress_public_key(public_key)?,
+        &hash_to_g2(message, public_key),
+    ) == pairing(&G1Affine::generator(), &signature)
+    {
+        Ok(())
+    } else {
+        Err(Error::AsymmetricKey(String::from(
+            "failed to verify BLS signature",
+        )))
+    }
+}
+
+/// Combines the given signatures into a single one.
+pub(super) fn aggregate<'a, I>(signatures: I) -> Result<CompressedSignature, Error>
+where
+    I: IntoIterator<Item = &'a CompressedSignature>,
+{
+    let mut sum = G2Projective::identity();
+    for signature in signatures {
+        sum += G2Projective::from(decompress_signature(signature)?);
+    }
+    Ok(G2Affine::from(sum).to_compressed())
+}
+
+/// Verifies that the aggregate signature combines signatures of the message by each of the
+/// given public keys.
+pub(super) fn verify_aggregate(
+    message: &[u8],
+    signature: &CompressedSignature,
+    public_keys: &[&CompressedPublicKey],
+) -> Result<(), Error> {
+    if public_keys.is_empty() {
+        return Err(Error::AsymmetricKey(String::from(
+            "cannot verify an aggregate BLS signature without signers",
+        )));
+    }
+    let signature = decompress_signature(signature)?;
+    let points = public_keys
+        .iter()
+        .map(|public_key| decompress_public_key(public_key))
+        .collect::<Result<Vec<G1Affine>, Error>>()?;
+    let hashes: Vec<G2Prepared> = public_keys
+        .iter()
+        .map(|public_key| G2Prepared::from(hash_to_g2(message, public_key)))
+        .collect();
+    let terms: Vec<(&G1Affine, &G2Prepared)> = points.iter().zip(hashes.iter()).collect();
+    if multi_miller_loop(&terms).final_exponentiation()
+        == pairing(&G1Affine::generator(), &signature)
+    {
+        Ok(())
+    } else {
+        Err(Error::AsymmetricKey(String::from(
+            "failed to verify aggregate BLS signature",
+        )))
+    }
+}
@@ -23,6 +23,14 @@ pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
             let bytes_array: [u8; SecretKey::SECP256K1_LENGTH] = bytes.try_into().unwrap();
             let secret_key = SecretKey::secp256k1_from_bytes(bytes_array).unwrap();
             PublicKey::from(&secret_key)
+        }),
+        collection::vec(<u8>::arbitrary(), SecretKey::BLS_LENGTH).prop_map(|bytes| {
+            let mut bytes_array: [u8; SecretKey::BLS_LENGTH] = bytes.try_into().unwrap();
+            // Keep the big-endian scalar below the group order and nonzero.
+            bytes_array[0] = 0;
+            bytes_array[SecretKey::BLS_LENGTH - 1] |= 1;
+            let secret_key = SecretKey::bls_from_bytes(bytes_array).unwrap();
+            PublicKey::from(&secret_key)
         })
     ]
 }
@@ -39,6 +47,14 @@ pub fn public_key_arb_no_system() -> impl Strategy<Value = PublicKey> {
             let bytes_array: [u8; SecretKey::SECP256K1_LENGTH] = bytes.try_into().unwrap();
             let secret_key = SecretKey::secp256k1_from_bytes(bytes_array).unwrap();
             PublicKey::from(&secret_key)
+        }),
+        collection::vec(<u8>::arbitrary(), SecretKey::BLS_LENGTH).prop_map(|bytes| {
+            let mut bytes_array: [u8; SecretKey::BLS_LENGTH] = bytes.try_into().unwrap();
+            // Keep the big-endian scalar below the group order and nonzero.
+            bytes_array[0] = 0;
+            bytes_array[SecretKey::BLS_LENGTH - 1] |= 1;
+            let secret_key = SecretKey::bls_from_bytes(bytes_array).unwrap();
+            PublicKey::from(&secret_key)
         })
     ]
 }
@@ -749,6 +749,186 @@ kv+kBR5u4ISEAkuc2TFWQHX0Yj9oTB9fx9+vvQdxJOhMtu46kGo0Uw==
     }
 }
 
+mod bls {
+    use super::*;
+    use crate::BLS_TAG;
+
+    const SECRET_KEY_LENGTH: usize = SecretKey::BLS_LENGTH;
+
+    #[test]
+    fn secret_key_from_bytes() {
+        // Zero and values not below the group order are not valid secret keys.
+        let bytes = [0; SECRET_KEY_LENGTH];
+        assert!(SecretKey::bls_from_bytes(&bytes[..]).is_err());
+        let bytes = [255; SECRET_KEY_LENGTH];
+        assert!(SecretKey::bls_from_bytes(&bytes[..]).is_err());
+        assert!(SecretKey::bls_from_bytes(&[1; SECRET_KEY_LENGTH - 1][..]).is_err());
+
+        let bytes = [1; SECRET_KEY_LENGTH];
+        assert!(SecretKey::bls_from_bytes(&bytes[..]).is_ok());
+    }
+
+    #[test]
+    fn secret_key_to_der_should_error() {
+        let mut rng = TestRng::new();
+        assert!(SecretKey::random_bls(&mut rng).to_der().is_err());
+    }
+
+    #[test]
+    fn secret_key_to_and_from_pem() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random_bls(&mut rng);
+        let pem_encoded = secret_key.to_pem().unwrap();
+        let decoded = SecretKey::from_pem(pem_encoded.as_bytes()).unwrap();
+        assert_secret_keys_equal(&secret_key, &decoded);
+        assert_eq!(BLS_TAG, decoded.tag());
+
+        // Ensure malformed encoded version fails to decode.
+        SecretKey::from_pem(&pem_encoded[1..]).unwrap_err();
+    }
+
+    #[test]
+    fn secret_key_to_and_from_file() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random_bls(&mut rng);
+        secret_key_file_roundtrip(secret_key);
+    }
+
+    #[test]
+    fn public_key_serialization_roundtrip() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        super::public_key_serialization_roundtrip(public_key);
+    }
+
+    #[test]
+    fn public_key_from_bytes() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        let bytes: Vec<u8> = public_key.clone().into();
+        assert_eq!(bytes.len(), PublicKey::BLS_LENGTH);
+        assert_eq!(PublicKey::bls_from_bytes(&bytes).unwrap(), public_key);
+
+        // Wrong lengths and points not on the curve should fail.
+        assert!(PublicKey::bls_from_bytes(&bytes[1..]).is_err());
+        assert!(PublicKey::bls_from_bytes([255; PublicKey::BLS_LENGTH]).is_err());
+    }
+
+    #[test]
+    fn public_key_to_and_from_pem() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        let pem_encoded = public_key.to_pem().unwrap();
+        let decoded = PublicKey::from_pem(pem_encoded.as_bytes()).unwrap();
+        assert_eq!(public_key, decoded);
+
+        // Ensure malformed encoded version fails to decode.
+        PublicKey::from_pem(&pem_encoded[1..]).unwrap_err();
+    }
+
+    #[test]
+    fn public_key_to_and_from_file() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        public_key_file_roundtrip(public_key);
+    }
+
+    #[test]
+    fn public_key_to_and_from_hex() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        public_key_hex_roundtrip(public_key);
+    }
+
+    #[test]
+    fn signature_serialization_roundtrip() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random_bls(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let signature = sign(b"data", &secret_key, &public_key);
+        super::signature_serialization_roundtrip(signature);
+        signature_hex_roundtrip(signature);
+        bytesrepr::test_serialization_roundtrip(&signature);
+    }
+
+    #[test]
+    fn public_key_to_account_hash() {
+        let mut rng = TestRng::new();
+        let public_key = PublicKey::random_bls(&mut rng);
+        assert_ne!(
+            public_key.to_account_hash().as_ref(),
+            Into::<Vec<u8>>::into(public_key)
+        );
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random_bls(&mut rng);
+
+        let public_key = PublicKey::from(&secret_key);
+        let other_public_key = PublicKey::random_bls(&mut rng);
+        let wrong_type_public_key = PublicKey::random_ed25519(&mut rng);
+
+        let message = b"message";
+        let signature = sign(message, &secret_key, &public_key);
+
+        assert!(verify(message, &signature, &public_key).is_ok());
+        assert!(verify(message, &signature, &other_public_key).is_err());
+        assert!(verify(message, &signature, &wrong_type_public_key).is_err());
+        assert!(verify(&message[1..], &signature, &public_key).is_err());
+    }
+
+    #[test]
+    fn aggregate_and_verify() {
+        let mut rng = TestRng::new();
+        let message = b"message";
+        let signers: Vec<(SecretKey, PublicKey)> = (0..4)
+            .map(|_| {
+                let secret_key = SecretKey::random_bls(&mut rng);
+                let public_key = PublicKey::from(&secret_key);
+                (secret_key, public_key)
+            })
+            .collect();
+        let signatures: Vec<Signature> = signers
+            .iter()
+            .map(|(secret_key, public_key)| sign(message, secret_key, public_key))
+            .collect();
+        let public_keys: Vec<PublicKey> = signers
+            .iter()
+            .map(|(_, public_key)| public_key.clone())
+            .collect();
+
+        let aggregate = aggregate_signatures(&signatures).unwrap();
+        assert!(verify_aggregate(message, &aggregate, &public_keys).is_ok());
+        assert!(verify_aggregate(&message[1..], &aggregate, &public_keys).is_err());
+
+        // Missing and additional signers must both be detected.
+        assert!(verify_aggregate(message, &aggregate, &public_keys[1..]).is_err());
+        let mut extended_public_keys = public_keys.clone();
+        extended_public_keys.push(PublicKey::random_bls(&mut rng));
+        assert!(verify_aggregate(message, &aggregate, &extended_public_keys).is_err());
+        assert!(verify_aggregate(message, &aggregate, &[]).is_err());
+
+        // A single signature is its own aggregate.
+        let single = aggregate_signatures(&signatures[..1]).unwrap();
+        assert_eq!(single, signatures[0]);
+        assert!(verify_aggregate(message, &single, &public_keys[..1]).is_ok());
+    }
+
+    #[test]
+    fn aggregate_should_reject_other_signature_types() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random_ed25519(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let signature = sign(b"message", &secret_key, &public_key);
+
+        assert!(aggregate_signatures(&[signature]).is_err());
+        assert!(aggregate_signatures(&[]).is_err());
+        assert!(verify_aggregate(b"message", &signature, &[public_key]).is_err());
+    }
+}
+
 #[test]
 fn public_key_traits() {
     let system_key = PublicKey::system();
@@ -859,3 +1039,63 @@ fn generate_secp256k1_should_generate_an_secp256k1_key() {
     let secret_key = SecretKey::generate_secp256k1().unwrap();
     assert!(matches!(secret_key, SecretKey::Secp256k1(_)))
 }
+
+#[test]
+fn vrf_prove_and_verify() {
+    let mut rng = TestRng::new();
+    for secret_key in [
+        SecretKey::random_ed25519(&mut rng),
+        SecretKey::random_secp256k1(&mut rng),
+        SecretKey::random_bls(&mut rng),
+    ] {
+        let public_key = PublicKey::from(&secret_key);
+        let other_public_key = PublicKey::from(&SecretKey::random_ed25519(&mut rng));
+
+        let proof = vrf_prove(b"message", &secret_key, &public_key);
+        let output = vrf_verify(b"message", &proof, &public_key).expect("should verify");
+        assert_eq!(proof, vrf_prove(b"message", &secret_key, &public_key));
+
+        let other_proof = vrf_prove(b"other message", &secret_key, &public_key);
+        let other_output =
+            vrf_verify(b"other message", &other_proof, &public_key).expect("should verify");
+        assert_ne!(output, other_output);
+
+        assert!(vrf_verify(b"other message", &proof, &public_key).is_err());
+        assert!(vrf_verify(b"message", &proof, &other_public_key).is_err());
+        let mut tampered_bytes = proof.as_ref().to_vec();
+        let last = tampered_bytes.len() - 1;
+        tampered_bytes[last] ^= 1;
+        let tampered_proof = VrfProof(tampered_bytes.into());
+        assert!(vrf_verify(b"message", &tampered_proof, &public_key).is_err());
+    }
+}
+
+#[test]
+fn vrf_should_match_rfc_9381_test_vector() {
+    // Example 16 of RFC 9381, ECVRF-EDWARDS25519-SHA512-TAI with an empty message.
+    let secret_key = SecretKey::ed25519_from_bytes(
+        checksummed_hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap(),
+    )
+    .unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let proof = vrf_prove([], &secret_key, &public_key);
+    assert_eq!(
+        "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f\
+         26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab12\
+         68a1b0db10836d9826a528ca76567805",
+        base16::encode_lower(&proof)
+    );
+    let output = vrf_verify([], &proof, &public_key).expect("should verify");
+    assert_eq!(
+        "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff\
+         66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+        base16::encode_lower(&output)
+    );
+}
+
+#[test]
+fn generate_bls_should_generate_a_bls_key() {
+    let secret_key = SecretKey::generate_bls().unwrap();
+    assert!(matches!(secret_key, SecretKey::Bls(_)))
+}
@@ -0,0 +1,306 @@
+//! Verifiable random functions (VRFs) with Ed25519 and secp256k1 keys.
+//!
+//! For a given public key and message there is exactly one output that verifies, so unlike
+//! signatures, which the holder of the secret key can compute in many different ways, the output
+//! can't be chosen by the prover.
+//!
+//! Ed25519 keys use ECVRF-EDWARDS25519-SHA512-TAI as specified in RFC 9381.  secp256k1 keys use
+//! the same construction with SHA-256, compressed SEC1 point encodings, big-endian integers and the
+//! suite string `0xFE`, with the nonce derived by hashing the secret key and the hashed message as
+//! in the Edwards25519 suite.
+
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar as Ed25519Scalar,
+    traits::IsIdentity,
+};
+use ed25519_dalek::{SigningKey as Ed25519SecretKey, VerifyingKey as Ed25519PublicKey};
+use k256::{
+    ecdsa::{SigningKey as Secp256k1SecretKey, VerifyingKey as Secp256k1PublicKey},
+    elliptic_curve::{
+        group::Group,
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        PrimeField,
+    },
+    AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar as Secp256k1Scalar, U256,
+};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::crypto::Error;
+
+/// The suite string of ECVRF-EDWARDS25519-SHA512-TAI.
+const ED25519_SUITE: u8 = 0x03;
+/// The suite string of the secp256k1 VRF.
+const SECP256K1_SUITE: u8 = 0xFE;
+
+/// The domain separators of the hash invocations, as specified in RFC 9381.
+const ENCODE_TO_CURVE_FRONT: u8 = 0x01;
+const CHALLENGE_FRONT: u8 = 0x02;
+const PROOF_TO_HASH_FRONT: u8 = 0x03;
+const BACK: u8 = 0x00;
+
+/// The length in bytes of the challenge.
+const CHALLENGE_LENGTH: usize = 16;
+
+/// The length in bytes of an Ed25519 VRF proof: a point, the challenge and a scalar.
+pub(super) const ED25519_PROOF_LENGTH: usize = 32 + CHALLENGE_LENGTH + 32;
+/// The length in bytes of a secp256k1 VRF proof: a point, the challenge and a scalar.
+pub(super) const SECP256K1_PROOF_LENGTH: usize = 33 + CHALLENGE_LENGTH + 32;
+
+fn invalid_proof() -> Error {
+    Error::AsymmetricKey(String::from("invalid VRF proof"))
+}
+
+/// Computes the Ed25519 VRF proof for `message`.
+pub(super) fn ed25519_prove(
+    secret_key: &Ed25519SecretKey,
+    public_key: &Ed25519PublicKey,
+    message: &[u8],
+) -> Vec<u8> {
+    // The secret scalar and the nonce prefix are derived from the secret key as in RFC 8032.
+    let hashed_secret_key = Sha512::digest(&secret_key.to_bytes());
+    let mut scalar_bytes: [u8; 32] = hashed_secret_key[..32]
+        .try_into()
+        .expect("should be 32 bytes");
+    scalar_bytes[0] &= 248;
+    scalar_bytes[31] &= 127;
+    scalar_bytes[31] |= 64;
+    let secret_scalar = Ed25519Scalar::from_bytes_mod_order(scalar_bytes);
+
+    let public_key_bytes = public_key.to_bytes();
+    let h_point = ed25519_encode_to_curve(&public_key_bytes, message)
+        .expect("encoding to curve fails with negligible probability only");
+    let h_bytes = h_point.compress().to_bytes();
+    let gamma = h_point * secret_scalar;
+    let nonce_hash = Sha512::new()
+        .chain(&hashed_secret_key[32..])
+        .chain(h_bytes)
+        .finalize();
+    let nonce = Ed25519Scalar::from_bytes_mod_order_wide(
+        nonce_hash
+            .as_slice()
+            .try_into()
+            .expect("should be 64 bytes"),
+    );
+    let challenge = ed25519_challenge(&[
+        &public_key_bytes,
+        &h_bytes,
+        gamma.compress().as_bytes(),
+        EdwardsPoint::mul_base(&nonce).compress().as_bytes(),
+        (h_point * nonce).compress().as_bytes(),
+    ]);
+    let s = nonce + ed25519_challenge_scalar(&challenge) * secret_scalar;
+
+    let mut proof = Vec::with_capacity(ED25519_PROOF_LENGTH);
+    proof.extend_from_slice(gamma.compress().as_bytes());
+    proof.extend_from_slice(&challenge);
+    proof.extend_from_slice(s.as_bytes());
+    proof
+}
+
+/// Verifies the Ed25519 VRF proof for `message`, returning the VRF output.
+pub(super) fn ed25519_verify(
+    public_key: &Ed25519PublicKey,
+    message: &[u8],
+    proof: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if proof.len() != ED25519_PROOF_LENGTH {
+        return Err(invalid_proof());
+    }
+    let public_key_bytes = public_key.to_bytes();
+    let y_point = CompressedEdwardsY(public_key_bytes)
+        .decompress()
+        .filter(|point| !point.is_small_order())
+        .ok_or_else(|| Error::AsymmetricKey(String::from("weak Ed25519 public key")))?;
+    let gamma = CompressedEdwardsY(proof[..32].try_into().expect("should be 32 bytes"))
+        .decompress()
+        .ok_or_else(invalid_proof)?;
+    let challenge: [u8; CHALLENGE_LENGTH] = proof[32..48].try_into().expect("should be 16 bytes");
+    let s: Ed25519Scalar = Option::from(Ed25519Scalar::from_canonical_bytes(
+        proof[48..].try_into().expect("should be 32 bytes"),
+    ))
+    .ok_or_else(invalid_proof)?;
+
+    let h_point = ed25519_encode_to_curve(&public_key_bytes, message).ok_or_else(invalid_proof)?;
+    let c = ed25519_challenge_scalar(&challenge);
+    let u_point = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-c, &y_point, &s);
+    let v_point = h_point * s - gamma * c;
+    let expected_challenge = ed25519_challenge(&[
+        &public_key_bytes,
+        h_point.compress().as_bytes(),
+        gamma.compress().as_bytes(),
+        u_point.compress().as_bytes(),
+        v_point.compress().as_bytes(),
+    ]);
+    if challenge != expected_challenge {
+        return Err(invalid_proof());
+    }
+    Ok(Sha512::new()
+        .chain([ED25519_SUITE, PROOF_TO_HASH_FRONT])
+        .chain(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain([BACK])
+        .finalize()
+        .to_vec())
+}
+
+/// Hashes the message to a point on the curve, using the try-and-increment method.
+fn ed25519_encode_to_curve(public_key: &[u8], message: &[u8]) -> Option<EdwardsPoint> {
+    (0..=u8::MAX).find_map(|counter| {
+        let hash = Sha512::new()
+            .chain([ED25519_SUITE, ENCODE_TO_CURVE_FRONT])
+            .chain(public_key)
+            .chain(message)
+            .chain([counter, BACK])
+            .finalize();
+        CompressedEdwardsY(hash[..32].try_into().expect("should be 32 bytes"))
+            .decompress()
+            .map(|point| point.mul_by_cofactor())
+            .filter(|point| !point.is_identity())
+    })
+}
+
+/// Returns the challenge hash of the given encoded points.
+fn ed25519_challenge(points: &[&[u8]]) -> [u8; CHALLENGE_LENGTH] {
+    let mut hasher = Sha512::new().chain([ED25519_SUITE, CHALLENGE_FRONT]);
+    for point in points {
+        hasher.update(point);
+    }
+    let hash = hasher.chain([BACK]).finalize();
+    hash[..CHALLENGE_LENGTH]
+        .try_into()
+        .expect("should be 16 bytes")
+}
+
+/// Interprets the challenge as a little-endian integer.
+fn ed25519_challenge_scalar(challenge: &[u8; CHALLENGE_LENGTH]) -> Ed25519Scalar {
+    let mut bytes = [0; 32];
+    bytes[..CHALLENGE_LENGTH].copy_from_slice(challenge);
+    Ed25519Scalar::from_bytes_mod_order(bytes)
+}
+
+/// Computes the secp256k1 VRF proof for `message`.
+pub(super) fn secp256k1_prove(
+    secret_key: &Secp256k1SecretKey,
+    public_key: &Secp256k1PublicKey,
+    message: &[u8],
+) -> Vec<u8> {
+    let secret_scalar: Secp256k1Scalar = *secret_key.as_nonzero_scalar().as_ref();
+    let public_key_bytes =
+        secp256k1_point_to_bytes(&ProjectivePoint::from(*public_key.as_affine()));
+    let h_point = secp256k1_encode_to_curve(&public_key_bytes, message)
+        .expect("encoding to curve fails with negligible probability only");
+    let h_bytes = secp256k1_point_to_bytes(&h_point);
+    let gamma = h_point * secret_scalar;
+    let nonce = <Secp256k1Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::clone_from_slice(
+        &Sha256::new()
+            .chain(secret_scalar.to_bytes())
+            .chain(&h_bytes)
+            .finalize(),
+    ));
+    let challenge = secp256k1_challenge(&[
+        &public_key_bytes,
+        &h_bytes,
+        &secp256k1_point_to_bytes(&gamma),
+        &secp256k1_point_to_bytes(&(ProjectivePoint::GENERATOR * nonce)),
+        &secp256k1_point_to_bytes(&(h_point * nonce)),
+    ]);
+    let s = nonce + secp256k1_challenge_scalar(&challenge) * secret_scalar;
+
+    let mut proof = Vec::with_capacity(SECP256K1_PROOF_LENGTH);
+    proof.extend_from_slice(&secp256k1_point_to_bytes(&gamma));
+    proof.extend_from_slice(&challenge);
+    proof.extend_from_slice(&s.to_bytes());
+    proof
+}
+
+/// Verifies the secp256k1 VRF proof for `message`, returning the VRF output.
+pub(super) fn secp256k1_verify(
+    public_key: &Secp256k1PublicKey,
+    message: &[u8],
+    proof: &[u8],
+) -> Result<Vec<u8>, Error> {
+    if proof.len() != SECP256K1_PROOF_LENGTH {
+        return Err(invalid_proof());
+    }
+    let y_point = ProjectivePoint::from(*public_key.as_affine());
+    let public_key_bytes = secp256k1_point_to_bytes(&y_point);
+    let gamma = secp256k1_point_from_bytes(&proof[..33]).ok_or_else(invalid_proof)?;
+    let challenge: [u8; CHALLENGE_LENGTH] = proof[33..49].try_into().expect("should be 16 bytes");
+    let s: Secp256k1Scalar = Option::from(Secp256k1Scalar::from_repr(
+        FieldBytes::clone_from_slice(&proof[49..]),
+    ))
+    .ok_or_else(invalid_proof)?;
+
+    let h_point =
+        secp256k1_encode_to_curve(&public_key_bytes, message).ok_or_else(invalid_proof)?;
+    let c = secp256k1_challenge_scalar(&challenge);
+    let u_point = ProjectivePoint::GENERATOR * s - y_point * c;
+    let v_point = h_point * s - gamma * c;
+    let expected_challenge = secp256k1_challenge(&[
+        &public_key_bytes,
+        &secp256k1_point_to_bytes(&h_point),
+        &secp256k1_point_to_bytes(&gamma),
+        &secp256k1_point_to_bytes(&u_point),
+        &secp256k1_point_to_bytes(&v_point),
+    ]);
+    if challenge != expected_challenge {
+        return Err(invalid_proof());
+    }
+    Ok(Sha256::new()
+        .chain([SECP256K1_SUITE, PROOF_TO_HASH_FRONT])
+        .chain(secp256k1_point_to_bytes(&gamma))
+        .chain([BACK])
+        .finalize()
+        .to_vec())
+}
+
+/// Hashes the message to a point on the curve, using the try-and-increment method.
+fn secp256k1_encode_to_curve(public_key: &[u8], message: &[u8]) -> Option<ProjectivePoint> {
+    (0..=u8::MAX).find_map(|counter| {
+        let hash = Sha256::new()
+            .chain([SECP256K1_SUITE, ENCODE_TO_CURVE_FRONT])
+            .chain(public_key)
+            .chain(message)
+            .chain([counter, BACK])
+            .finalize();
+        let mut compressed = [0x02; 33];
+        compressed[1..].copy_from_slice(&hash);
+        secp256k1_point_from_bytes(&compressed)
+    })
+}
+
+/// Returns the challenge hash of the given encoded points.
+fn secp256k1_challenge(points: &[&[u8]]) -> [u8; CHALLENGE_LENGTH] {
+    let mut hasher = Sha256::new().chain([SECP256K1_SUITE, CHALLENGE_FRONT]);
+    for point in points {
+        hasher.update(point);
+    }
+    let hash = hasher.chain([BACK]).finalize();
+    hash[..CHALLENGE_LENGTH]
+        .try_into()
+        .expect("should be 16 bytes")
+}
+
+/// Interprets the challenge as a big-endian integer.
+fn secp256k1_challenge_scalar(challenge: &[u8; CHALLENGE_LENGTH]) -> Secp256k1Scalar {
+    let mut bytes = FieldBytes::default();
+    bytes[32 - CHALLENGE_LENGTH..].copy_from_slice(challenge);
+    <Secp256k1Scalar as Reduce<U256>>::reduce_bytes(&bytes)
+}
+
+/// Returns the compressed SEC1 encoding of the point.
+fn secp256k1_point_to_bytes(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// Parses a compressed SEC1 encoding of a point other than the identity.
+fn secp256k1_point_from_bytes(bytes: &[u8]) -> Option<ProjectivePoint> {
+    let encoded_point = EncodedPoint::from_bytes(bytes).ok()?;
+    let point = Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded_point))?;
+    let point = ProjectivePoint::from(point);
+    (!bool::from(point.is_identity())).then_some(point)
+}
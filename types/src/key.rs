@@ -361,7 +361,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(HASH_PREFIX) {
             let addr = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Hash(error.to_string()))?;
-            let hash_addr = HashAddr::try_from(addr.as_ref())
+            let hash_addr = HashAddr::try_from(addr.as_slice())
                 .map_err(|error| FromStrError::Hash(error.to_string()))?;
             return Ok(Key::Hash(hash_addr));
         }
@@ -369,7 +369,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(DEPLOY_INFO_PREFIX) {
             let hash = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::DeployInfo(error.to_string()))?;
-            let hash_array = <[u8; DEPLOY_HASH_LENGTH]>::try_from(hash.as_ref())
+            let hash_array = <[u8; DEPLOY_HASH_LENGTH]>::try_from(hash.as_slice())
                 .map_err(|error| FromStrError::DeployInfo(error.to_string()))?;
             return Ok(Key::DeployInfo(DeployHash::new(hash_array)));
         }
@@ -389,7 +389,7 @@ impl Key {
         if let Some(era_summary_padding) = input.strip_prefix(ERA_SUMMARY_PREFIX) {
             let padded_bytes = checksummed_hex::decode(era_summary_padding)
                 .map_err(|error| FromStrError::EraSummary(error.to_string()))?;
-            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_ref()).map_err(|_| {
+            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_slice()).map_err(|_| {
                 FromStrError::EraSummary("Failed to deserialize era summary key".to_string())
             })?;
             return Ok(Key::EraSummary);
@@ -404,7 +404,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(BALANCE_PREFIX) {
             let addr = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Balance(error.to_string()))?;
-            let uref_addr = URefAddr::try_from(addr.as_ref())
+            let uref_addr = URefAddr::try_from(addr.as_slice())
                 .map_err(|error| FromStrError::Balance(error.to_string()))?;
             return Ok(Key::Balance(uref_addr));
         }
@@ -412,7 +412,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(BID_PREFIX) {
             let hash = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Bid(error.to_string()))?;
-            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_ref())
+            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_slice())
                 .map_err(|error| FromStrError::Bid(error.to_string()))?;
             return Ok(Key::Bid(AccountHash::new(account_hash)));
         }
@@ -420,7 +420,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(WITHDRAW_PREFIX) {
             let hash = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Withdraw(error.to_string()))?;
-            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_ref())
+            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_slice())
                 .map_err(|error| FromStrError::Withdraw(error.to_string()))?;
             return Ok(Key::Withdraw(AccountHash::new(account_hash)));
         }
@@ -428,7 +428,7 @@ impl Key {
         if let Some(hex) = input.strip_prefix(UNBOND_PREFIX) {
             let hash = checksummed_hex::decode(hex)
                 .map_err(|error| FromStrError::Unbond(error.to_string()))?;
-            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_ref())
+            let account_hash = <[u8; ACCOUNT_HASH_LENGTH]>::try_from(hash.as_slice())
                 .map_err(|error| FromStrError::Unbond(error.to_string()))?;
             return Ok(Key::Unbond(AccountHash::new(account_hash)));
         }
@@ -436,7 +436,7 @@ impl Key {
         if let Some(dictionary_addr) = input.strip_prefix(DICTIONARY_PREFIX) {
             let dictionary_addr_bytes = checksummed_hex::decode(dictionary_addr)
                 .map_err(|error| FromStrError::Dictionary(error.to_string()))?;
-            let addr = DictionaryAddr::try_from(dictionary_addr_bytes.as_ref())
+            let addr = DictionaryAddr::try_from(dictionary_addr_bytes.as_slice())
                 .map_err(|error| FromStrError::Dictionary(error.to_string()))?;
             return Ok(Key::Dictionary(addr));
         }
@@ -444,7 +444,7 @@ impl Key {
         if let Some(registry_address) = input.strip_prefix(SYSTEM_CONTRACT_REGISTRY_PREFIX) {
             let padded_bytes = checksummed_hex::decode(registry_address)
                 .map_err(|error| FromStrError::SystemContractRegistry(error.to_string()))?;
-            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_ref()).map_err(|_| {
+            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_slice()).map_err(|_| {
                 FromStrError::SystemContractRegistry(
                     "Failed to deserialize system registry key".to_string(),
                 )
@@ -455,7 +455,7 @@ impl Key {
         if let Some(registry_address) = input.strip_prefix(CHAINSPEC_REGISTRY_PREFIX) {
             let padded_bytes = checksummed_hex::decode(registry_address)
                 .map_err(|error| FromStrError::ChainspecRegistry(error.to_string()))?;
-            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_ref()).map_err(|_| {
+            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_slice()).map_err(|_| {
                 FromStrError::ChainspecRegistry(
                     "Failed to deserialize chainspec registry key".to_string(),
                 )
@@ -466,7 +466,7 @@ impl Key {
         if let Some(registry_address) = input.strip_prefix(CHECKSUM_REGISTRY_PREFIX) {
             let padded_bytes = checksummed_hex::decode(registry_address)
                 .map_err(|error| FromStrError::ChecksumRegistry(error.to_string()))?;
-            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_ref()).map_err(|_| {
+            let _padding: [u8; 32] = TryFrom::try_from(padded_bytes.as_slice()).map_err(|_| {
                 FromStrError::ChecksumRegistry(
                     "Failed to deserialize checksum registry key".to_string(),
                 )
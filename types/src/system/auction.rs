@@ -5,6 +5,8 @@ mod delegator;
 mod entry_points;
 mod era_info;
 mod error;
+mod inactivity;
+mod proposal;
 mod seigniorage_recipient;
 mod unbonding_purse;
 mod withdraw_purse;
@@ -17,6 +19,11 @@ pub use delegator::Delegator;
 pub use entry_points::auction_entry_points;
 pub use era_info::{EraInfo, SeigniorageAllocation};
 pub use error::Error;
+pub use inactivity::{validator_inactivity_key, ValidatorInactivity};
+pub use proposal::{
+    governance_parameter_overrides_key, governance_proposals_key, ParameterChanges, Proposal,
+    ProposalStatus, Proposals,
+};
 pub use seigniorage_recipient::SeigniorageRecipient;
 pub use unbonding_purse::UnbondingPurse;
 pub use withdraw_purse::WithdrawPurse;
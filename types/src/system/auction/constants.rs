@@ -58,6 +58,20 @@ pub const ARG_UNBONDING_DELAY: &str = "unbonding_delay";
 pub const ARG_ERA_END_TIMESTAMP_MILLIS: &str = "era_end_timestamp_millis";
 /// Named constant for `evicted_validators`;
 pub const ARG_EVICTED_VALIDATORS: &str = "evicted_validators";
+/// Named constant for `inactive_validators`;
+pub const ARG_INACTIVE_VALIDATORS: &str = "inactive_validators";
+/// Named constant for `proposer`.
+pub const ARG_PROPOSER: &str = "proposer";
+/// Named constant for `parameter_changes`.
+pub const ARG_PARAMETER_CHANGES: &str = "parameter_changes";
+/// Named constant for `activation_era`.
+pub const ARG_ACTIVATION_ERA: &str = "activation_era";
+/// Named constant for `voter`.
+pub const ARG_VOTER: &str = "voter";
+/// Named constant for `proposal_id`.
+pub const ARG_PROPOSAL_ID: &str = "proposal_id";
+/// Named constant for `approve`.
+pub const ARG_APPROVE: &str = "approve";
 
 /// Named constant for method `get_era_validators`.
 pub const METHOD_GET_ERA_VALIDATORS: &str = "get_era_validators";
@@ -81,6 +95,10 @@ pub const METHOD_DISTRIBUTE: &str = "distribute";
 pub const METHOD_READ_ERA_ID: &str = "read_era_id";
 /// Named constant for method `activate_bid`.
 pub const METHOD_ACTIVATE_BID: &str = "activate_bid";
+/// Named constant for method `propose_parameter_changes`.
+pub const METHOD_PROPOSE_PARAMETER_CHANGES: &str = "propose_parameter_changes";
+/// Named constant for method `vote_on_proposal`.
+pub const METHOD_VOTE_ON_PROPOSAL: &str = "vote_on_proposal";
 
 /// Storage for `EraId`.
 pub const ERA_ID_KEY: &str = "era_id";
@@ -2,14 +2,16 @@ use alloc::boxed::Box;
 
 use crate::{
     system::auction::{
-        DelegationRate, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
-        ARG_ERA_END_TIMESTAMP_MILLIS, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_REWARD_FACTORS,
-        ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, METHOD_ACTIVATE_BID, METHOD_ADD_BID,
-        METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS, METHOD_READ_ERA_ID,
+        DelegationRate, ParameterChanges, ValidatorWeights, ARG_ACTIVATION_ERA, ARG_AMOUNT,
+        ARG_APPROVE, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_ERA_END_TIMESTAMP_MILLIS,
+        ARG_NEW_VALIDATOR, ARG_PARAMETER_CHANGES, ARG_PROPOSAL_ID, ARG_PROPOSER, ARG_PUBLIC_KEY,
+        ARG_REWARD_FACTORS, ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, ARG_VOTER,
+        METHOD_ACTIVATE_BID, METHOD_ADD_BID, METHOD_DELEGATE, METHOD_DISTRIBUTE,
+        METHOD_GET_ERA_VALIDATORS, METHOD_PROPOSE_PARAMETER_CHANGES, METHOD_READ_ERA_ID,
         METHOD_REDELEGATE, METHOD_RUN_AUCTION, METHOD_SLASH, METHOD_UNDELEGATE,
-        METHOD_WITHDRAW_BID,
+        METHOD_VOTE_ON_PROPOSAL, METHOD_WITHDRAW_BID,
     },
-    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, EraId, Parameter,
     PublicKey, U512,
 };
 
@@ -142,5 +144,31 @@ pub fn auction_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_PROPOSE_PARAMETER_CHANGES,
+        vec![
+            Parameter::new(ARG_PROPOSER, PublicKey::cl_type()),
+            Parameter::new(ARG_PARAMETER_CHANGES, ParameterChanges::cl_type()),
+            Parameter::new(ARG_ACTIVATION_ERA, EraId::cl_type()),
+        ],
+        u64::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_VOTE_ON_PROPOSAL,
+        vec![
+            Parameter::new(ARG_VOTER, PublicKey::cl_type()),
+            Parameter::new(ARG_PROPOSAL_ID, u64::cl_type()),
+            Parameter::new(ARG_APPROVE, bool::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
@@ -327,6 +327,31 @@ pub enum Error {
     /// assert_eq!(49, Error::TransferToAdministrator as u8);
     /// ```
     TransferToAdministrator = 49,
+    /// Governance proposal not found.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(50, Error::ProposalNotFound as u8);
+    /// ```
+    ProposalNotFound = 50,
+    /// Governance proposal changes no parameters, changes them to invalid values or doesn't
+    /// activate in a future era.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(51, Error::InvalidProposal as u8);
+    /// ```
+    InvalidProposal = 51,
+    /// Only validators of the current era can propose and vote on governance proposals.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(52, Error::NotAnEraValidator as u8);
+    /// ```
+    NotAnEraValidator = 52,
+    /// Voting on the governance proposal has already ended.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(53, Error::ProposalClosed as u8);
+    /// ```
+    ProposalClosed = 53,
 }
 
 impl Display for Error {
@@ -382,6 +407,10 @@ impl Display for Error {
             Error::AuctionBidsDisabled => formatter.write_str("Auction bids are disabled"),
             Error::GetAccumulationPurse => formatter.write_str("Get accumulation purse error"),
             Error::TransferToAdministrator => formatter.write_str("Transfer to administrator error"),
+            Error::ProposalNotFound => formatter.write_str("Governance proposal not found"),
+            Error::InvalidProposal => formatter.write_str("Invalid governance proposal"),
+            Error::NotAnEraValidator => formatter.write_str("Not a validator of the current era"),
+            Error::ProposalClosed => formatter.write_str("Voting on the governance proposal has ended"),
         }
     }
 }
@@ -463,6 +492,10 @@ impl TryFrom<u8> for Error {
             d if d == Error::AuctionBidsDisabled as u8 => Ok(Error::AuctionBidsDisabled),
             d if d == Error::GetAccumulationPurse as u8 => Ok(Error::GetAccumulationPurse),
             d if d == Error::TransferToAdministrator as u8 => Ok(Error::TransferToAdministrator),
+            d if d == Error::ProposalNotFound as u8 => Ok(Error::ProposalNotFound),
+            d if d == Error::InvalidProposal as u8 => Ok(Error::InvalidProposal),
+            d if d == Error::NotAnEraValidator as u8 => Ok(Error::NotAnEraValidator),
+            d if d == Error::ProposalClosed as u8 => Ok(Error::ProposalClosed),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -0,0 +1,17 @@
+use alloc::collections::BTreeMap;
+
+use crate::{crypto, AccessRights, Key, PublicKey, URef};
+
+const VALIDATOR_INACTIVITY_KEY_PREFIX: &[u8] = b"validator-inactivity";
+
+/// Returns the key under which the [`ValidatorInactivity`] is stored.
+pub fn validator_inactivity_key() -> Key {
+    Key::URef(URef::new(
+        crypto::blake2b(VALIDATOR_INACTIVITY_KEY_PREFIX),
+        AccessRights::NONE,
+    ))
+}
+
+/// The number of consecutive eras up to the current one in which each validator was reported as
+/// inactive by consensus.  Validators who were active in the current era are not included.
+pub type ValidatorInactivity = BTreeMap<PublicKey, u64>;
@@ -0,0 +1,552 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use num_rational::Ratio;
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    crypto, AccessRights, CLType, CLTyped, EraId, Key, PublicKey, URef, U512,
+};
+
+const GOVERNANCE_PROPOSALS_KEY_PREFIX: &[u8] = b"governance-proposals";
+const GOVERNANCE_PARAMETER_OVERRIDES_KEY_PREFIX: &[u8] = b"governance-parameter-overrides";
+
+/// Returns the key under which all governance [`Proposals`] are stored.
+pub fn governance_proposals_key() -> Key {
+    Key::URef(URef::new(
+        crypto::blake2b(GOVERNANCE_PROPOSALS_KEY_PREFIX),
+        AccessRights::NONE,
+    ))
+}
+
+/// Returns the key under which the [`ParameterChanges`] of all applied governance proposals are
+/// accumulated.
+///
+/// These take precedence over the chainspec: they are reapplied after every protocol upgrade, and
+/// the node applies those to parameters which aren't held in global state on top of its chainspec.
+pub fn governance_parameter_overrides_key() -> Key {
+    Key::URef(URef::new(
+        crypto::blake2b(GOVERNANCE_PARAMETER_OVERRIDES_KEY_PREFIX),
+        AccessRights::NONE,
+    ))
+}
+
+/// Governance proposals which are still pending or waiting to be applied, mapped by their id.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Proposals {
+    /// Id given to the next proposal.  Ids are never reused, even once proposals are pruned.
+    next_id: u64,
+    proposals: BTreeMap<u64, Proposal>,
+}
+
+impl Proposals {
+    /// Adds a new proposal, returning its id, or `None` if ids have been exhausted.
+    pub fn insert(&mut self, proposal: Proposal) -> Option<u64> {
+        let id = self.next_id;
+        self.next_id = id.checked_add(1)?;
+        self.proposals.insert(id, proposal);
+        Some(id)
+    }
+
+    /// Returns the proposal with the given id.
+    pub fn get(&self, id: &u64) -> Option<&Proposal> {
+        self.proposals.get(id)
+    }
+
+    /// Returns the proposal with the given id.
+    pub fn get_mut(&mut self, id: &u64) -> Option<&mut Proposal> {
+        self.proposals.get_mut(id)
+    }
+
+    /// Returns an iterator over the proposals and their ids.
+    pub fn iter(&self) -> impl Iterator<Item = (&u64, &Proposal)> {
+        self.proposals.iter()
+    }
+
+    /// Returns an iterator over the proposals and their ids, allowing the proposals to be modified.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&u64, &mut Proposal)> {
+        self.proposals.iter_mut()
+    }
+
+    /// Returns an iterator over the proposals, allowing them to be modified.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Proposal> {
+        self.proposals.values_mut()
+    }
+
+    /// Returns the number of proposals.
+    pub fn len(&self) -> usize {
+        self.proposals.len()
+    }
+
+    /// Returns `true` if there are no proposals.
+    pub fn is_empty(&self) -> bool {
+        self.proposals.is_empty()
+    }
+
+    /// Removes applied and rejected proposals.
+    pub fn prune_finished(&mut self) {
+        self.proposals.retain(|_, proposal| {
+            matches!(
+                proposal.status(),
+                ProposalStatus::Pending | ProposalStatus::Approved
+            )
+        });
+    }
+}
+
+impl ToBytes for Proposals {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.next_id.serialized_length() + self.proposals.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.next_id.write_bytes(writer)?;
+        self.proposals.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for Proposals {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (next_id, remainder) = FromBytes::from_bytes(bytes)?;
+        let (proposals, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((Proposals { next_id, proposals }, remainder))
+    }
+}
+
+impl CLTyped for Proposals {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// A set of chainspec parameter changes.  Parameters left as `None` are unchanged.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct ParameterChanges {
+    /// New number of validator slots.
+    pub validator_slots: Option<u32>,
+    /// New auction delay.
+    pub auction_delay: Option<u64>,
+    /// New locked funds period, in milliseconds.
+    pub locked_funds_period_millis: Option<u64>,
+    /// New round seigniorage rate.
+    pub round_seigniorage_rate: Option<Ratio<u64>>,
+    /// New unbonding delay.
+    pub unbonding_delay: Option<u64>,
+    /// New block gas limit.
+    pub block_gas_limit: Option<u64>,
+    /// New costs of Wasm opcodes, keyed by their name in the chainspec's `wasm.opcode_costs`
+    /// table.  Only the top-level entries of the table can be changed.
+    ///
+    /// This and the block gas limit aren't held in global state, and are applied by the node.
+    pub opcode_costs: BTreeMap<String, u32>,
+}
+
+impl ParameterChanges {
+    /// Returns `true` if no parameter is changed.
+    pub fn is_empty(&self) -> bool {
+        self == &ParameterChanges::default()
+    }
+
+    /// Applies `other` on top of `self`, so that parameters changed by both take their value from
+    /// `other`.
+    pub fn extend(&mut self, other: &ParameterChanges) {
+        let ParameterChanges {
+            validator_slots,
+            auction_delay,
+            locked_funds_period_millis,
+            round_seigniorage_rate,
+            unbonding_delay,
+            block_gas_limit,
+            opcode_costs,
+        } = other;
+        self.validator_slots = validator_slots.or(self.validator_slots);
+        self.auction_delay = auction_delay.or(self.auction_delay);
+        self.locked_funds_period_millis =
+            locked_funds_period_millis.or(self.locked_funds_period_millis);
+        self.round_seigniorage_rate = round_seigniorage_rate.or(self.round_seigniorage_rate);
+        self.unbonding_delay = unbonding_delay.or(self.unbonding_delay);
+        self.block_gas_limit = block_gas_limit.or(self.block_gas_limit);
+        self.opcode_costs.extend(
+            opcode_costs
+                .iter()
+                .map(|(name, cost)| (name.clone(), *cost)),
+        );
+    }
+}
+
+impl ToBytes for ParameterChanges {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_slots.serialized_length()
+            + self.auction_delay.serialized_length()
+            + self.locked_funds_period_millis.serialized_length()
+            + self.round_seigniorage_rate.serialized_length()
+            + self.unbonding_delay.serialized_length()
+            + self.block_gas_limit.serialized_length()
+            + self.opcode_costs.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.validator_slots.write_bytes(writer)?;
+        self.auction_delay.write_bytes(writer)?;
+        self.locked_funds_period_millis.write_bytes(writer)?;
+        self.round_seigniorage_rate.write_bytes(writer)?;
+        self.unbonding_delay.write_bytes(writer)?;
+        self.block_gas_limit.write_bytes(writer)?;
+        self.opcode_costs.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for ParameterChanges {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_slots, remainder) = FromBytes::from_bytes(bytes)?;
+        let (auction_delay, remainder) = FromBytes::from_bytes(remainder)?;
+        let (locked_funds_period_millis, remainder) = FromBytes::from_bytes(remainder)?;
+        let (round_seigniorage_rate, remainder) = FromBytes::from_bytes(remainder)?;
+        let (unbonding_delay, remainder) = FromBytes::from_bytes(remainder)?;
+        let (block_gas_limit, remainder) = FromBytes::from_bytes(remainder)?;
+        let (opcode_costs, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            ParameterChanges {
+                validator_slots,
+                auction_delay,
+                locked_funds_period_millis,
+                round_seigniorage_rate,
+                unbonding_delay,
+                block_gas_limit,
+                opcode_costs,
+            },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for ParameterChanges {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// The state of a governance proposal.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum ProposalStatus {
+    /// Votes are still being collected.
+    Pending = 0,
+    /// The proposal was approved and will be applied by the first protocol upgrade activating at
+    /// or after its activation era.
+    Approved = 1,
+    /// The proposal did not collect enough votes.
+    Rejected = 2,
+    /// The proposal was applied.
+    Applied = 3,
+}
+
+impl ToBytes for ProposalStatus {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ProposalStatus {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder): (u8, _) = FromBytes::from_bytes(bytes)?;
+        let status = match tag {
+            tag if tag == ProposalStatus::Pending as u8 => ProposalStatus::Pending,
+            tag if tag == ProposalStatus::Approved as u8 => ProposalStatus::Approved,
+            tag if tag == ProposalStatus::Rejected as u8 => ProposalStatus::Rejected,
+            tag if tag == ProposalStatus::Applied as u8 => ProposalStatus::Applied,
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        Ok((status, remainder))
+    }
+}
+
+/// A proposal to change chainspec parameters, voted on by the validators.
+///
+/// Votes are collected until the end of the era preceding `activation_era`, when they are
+/// weighted by the stakes of that era's validators.  A proposal approved by validators holding
+/// more than two thirds of the total stake is applied by the first protocol upgrade whose
+/// activation point is at or after `activation_era`.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Proposal {
+    proposer: PublicKey,
+    parameter_changes: ParameterChanges,
+    activation_era: EraId,
+    votes: BTreeMap<PublicKey, bool>,
+    status: ProposalStatus,
+}
+
+impl Proposal {
+    /// Creates a new pending [`Proposal`].
+    pub fn new(
+        proposer: PublicKey,
+        parameter_changes: ParameterChanges,
+        activation_era: EraId,
+    ) -> Self {
+        Proposal {
+            proposer,
+            parameter_changes,
+            activation_era,
+            votes: BTreeMap::new(),
+            status: ProposalStatus::Pending,
+        }
+    }
+
+    /// Returns the public key of the validator who made the proposal.
+    pub fn proposer(&self) -> &PublicKey {
+        &self.proposer
+    }
+
+    /// Returns the proposed parameter changes.
+    pub fn parameter_changes(&self) -> &ParameterChanges {
+        &self.parameter_changes
+    }
+
+    /// Returns the earliest era at the start of which the proposal is applied if approved.
+    pub fn activation_era(&self) -> EraId {
+        self.activation_era
+    }
+
+    /// Returns the votes cast so far, `true` meaning in favor of the proposal.
+    pub fn votes(&self) -> &BTreeMap<PublicKey, bool> {
+        &self.votes
+    }
+
+    /// Returns the state of the proposal.
+    pub fn status(&self) -> ProposalStatus {
+        self.status
+    }
+
+    /// Records the vote of `voter`, replacing any previous vote.
+    pub fn vote(&mut self, voter: PublicKey, approve: bool) {
+        self.votes.insert(voter, approve);
+    }
+
+    /// Counts the votes against the given validator weights and marks the proposal as approved or
+    /// rejected.
+    pub fn tally(&mut self, validator_weights: &BTreeMap<PublicKey, U512>) {
+        let total_weight = validator_weights
+            .values()
+            .fold(U512::zero(), |total, weight| total.saturating_add(*weight));
+        let approving_weight = self
+            .votes
+            .iter()
+            .filter(|(_, approve)| **approve)
+            .filter_map(|(voter, _)| validator_weights.get(voter))
+            .fold(U512::zero(), |total, weight| total.saturating_add(*weight));
+        self.status = if !total_weight.is_zero()
+            && approving_weight.saturating_mul(U512::from(3))
+                > total_weight.saturating_mul(U512::from(2))
+        {
+            ProposalStatus::Approved
+        } else {
+            ProposalStatus::Rejected
+        };
+    }
+
+    /// Marks the proposal as applied.
+    pub fn mark_applied(&mut self) {
+        self.status = ProposalStatus::Applied;
+    }
+}
+
+impl ToBytes for Proposal {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.proposer.serialized_length()
+            + self.parameter_changes.serialized_length()
+            + self.activation_era.serialized_length()
+            + self.votes.serialized_length()
+            + self.status.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.proposer.write_bytes(writer)?;
+        self.parameter_changes.write_bytes(writer)?;
+        self.activation_era.write_bytes(writer)?;
+        self.votes.write_bytes(writer)?;
+        self.status.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for Proposal {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (proposer, remainder) = FromBytes::from_bytes(bytes)?;
+        let (parameter_changes, remainder) = FromBytes::from_bytes(remainder)?;
+        let (activation_era, remainder) = FromBytes::from_bytes(remainder)?;
+        let (votes, remainder) = FromBytes::from_bytes(remainder)?;
+        let (status, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            Proposal {
+                proposer,
+                parameter_changes,
+                activation_era,
+                votes,
+                status,
+            },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for Proposal {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use super::*;
+    use crate::SecretKey;
+
+    fn validator(seed: u8) -> PublicKey {
+        let secret_key = SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    }
+
+    fn parameter_changes() -> ParameterChanges {
+        ParameterChanges {
+            validator_slots: Some(10),
+            round_seigniorage_rate: Some(Ratio::new(1, 1_000)),
+            block_gas_limit: Some(1_000_000),
+            opcode_costs: [("add".to_string(), 5)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn serialization_roundtrip_for_proposal() {
+        let mut proposal = Proposal::new(validator(1), parameter_changes(), EraId::new(5));
+        proposal.vote(validator(1), true);
+        proposal.vote(validator(2), false);
+        bytesrepr::test_serialization_roundtrip(&proposal);
+    }
+
+    #[test]
+    fn serialization_roundtrip_for_proposals() {
+        let mut proposals = Proposals::default();
+        proposals.insert(Proposal::new(
+            validator(1),
+            parameter_changes(),
+            EraId::new(5),
+        ));
+        bytesrepr::test_serialization_roundtrip(&proposals);
+    }
+
+    #[test]
+    fn should_not_reuse_ids_of_pruned_proposals() {
+        let weights: BTreeMap<PublicKey, U512> =
+            [(validator(1), U512::from(10))].into_iter().collect();
+        let mut proposals = Proposals::default();
+        let first_id = proposals
+            .insert(Proposal::new(
+                validator(1),
+                parameter_changes(),
+                EraId::new(5),
+            ))
+            .unwrap();
+        let second_id = proposals
+            .insert(Proposal::new(
+                validator(1),
+                parameter_changes(),
+                EraId::new(5),
+            ))
+            .unwrap();
+
+        // The first proposal is approved and applied, the second is rejected.
+        let first = proposals.get_mut(&first_id).unwrap();
+        first.vote(validator(1), true);
+        first.tally(&weights);
+        first.mark_applied();
+        proposals.get_mut(&second_id).unwrap().tally(&weights);
+
+        proposals.prune_finished();
+        assert!(proposals.is_empty());
+
+        let third_id = proposals
+            .insert(Proposal::new(
+                validator(1),
+                parameter_changes(),
+                EraId::new(6),
+            ))
+            .unwrap();
+        assert!(third_id > second_id);
+    }
+
+    #[test]
+    fn should_extend_parameter_changes() {
+        let mut changes = ParameterChanges {
+            validator_slots: Some(10),
+            opcode_costs: [("add".to_string(), 5), ("mul".to_string(), 6)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        changes.extend(&ParameterChanges {
+            block_gas_limit: Some(1_000),
+            opcode_costs: [("mul".to_string(), 7)].into_iter().collect(),
+            ..Default::default()
+        });
+        assert_eq!(changes.validator_slots, Some(10));
+        assert_eq!(changes.block_gas_limit, Some(1_000));
+        assert_eq!(changes.opcode_costs["add"], 5);
+        assert_eq!(changes.opcode_costs["mul"], 7);
+    }
+
+    #[test]
+    fn should_approve_with_supermajority_of_stake() {
+        let weights: BTreeMap<PublicKey, U512> = [
+            (validator(1), U512::from(60)),
+            (validator(2), U512::from(30)),
+            (validator(3), U512::from(10)),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut proposal = Proposal::new(validator(1), parameter_changes(), EraId::new(5));
+        proposal.vote(validator(1), true);
+        proposal.tally(&weights);
+        assert_eq!(proposal.status(), ProposalStatus::Rejected);
+
+        proposal.vote(validator(3), true);
+        proposal.tally(&weights);
+        assert_eq!(proposal.status(), ProposalStatus::Approved);
+    }
+
+    #[test]
+    fn should_ignore_votes_of_non_validators() {
+        let weights: BTreeMap<PublicKey, U512> =
+            [(validator(1), U512::from(10))].into_iter().collect();
+
+        let mut proposal = Proposal::new(validator(1), parameter_changes(), EraId::new(5));
+        proposal.vote(validator(2), true);
+        proposal.tally(&weights);
+        assert_eq!(proposal.status(), ProposalStatus::Rejected);
+    }
+}
@@ -6,6 +6,8 @@ pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_ACCOUNT: &str = "account";
 /// Named constant for `target`.
 pub const ARG_TARGET: &str = "target";
+/// Named constant for `proposer_tip`.
+pub const ARG_PROPOSER_TIP: &str = "proposer_tip";
 
 /// Named constant for method `get_payment_purse`.
 pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
@@ -1,8 +1,12 @@
 //! Contains implementation of a Mint contract functionality.
+mod asset_id;
 mod constants;
 mod entry_points;
 mod error;
+mod purse_lock;
 
+pub use asset_id::{purse_asset_key, AssetId, ASSET_ID_LENGTH};
 pub use constants::*;
 pub use entry_points::mint_entry_points;
 pub use error::Error;
+pub use purse_lock::{purse_lock_key, PurseLock};
@@ -0,0 +1,180 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{string::String, vec::Vec};
+use core::{
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+#[cfg(feature = "json-schema")]
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    checksummed_hex, crypto, AccessRights, CLType, CLTyped, Key, URef,
+};
+
+/// The length of an asset id.
+pub const ASSET_ID_LENGTH: usize = 32;
+
+const PURSE_ASSET_KEY_PREFIX: &[u8] = b"purse-asset";
+
+/// Returns the key under which the [`AssetId`] held in the given purse is stored.
+///
+/// Only the mint writes under this key, so holders of the purse can't change its asset.
+pub fn purse_asset_key(purse: URef) -> Key {
+    let mut preimage = Vec::with_capacity(PURSE_ASSET_KEY_PREFIX.len() + purse.addr().len());
+    preimage.extend_from_slice(PURSE_ASSET_KEY_PREFIX);
+    preimage.extend_from_slice(&purse.addr());
+    Key::URef(URef::new(crypto::blake2b(preimage), AccessRights::NONE))
+}
+
+/// A newtype wrapping a <code>[u8; [ASSET_ID_LENGTH]]</code> which identifies a non-native asset
+/// managed by the mint.
+///
+/// Purses holding the native token have no asset id.
+#[derive(Default, PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+pub struct AssetId([u8; ASSET_ID_LENGTH]);
+
+impl AssetId {
+    /// Constructs a new `AssetId` instance from the raw bytes of an asset id.
+    pub const fn new(value: [u8; ASSET_ID_LENGTH]) -> AssetId {
+        AssetId(value)
+    }
+
+    /// Returns the raw bytes of the asset id as an array.
+    pub fn value(&self) -> [u8; ASSET_ID_LENGTH] {
+        self.0
+    }
+
+    /// Returns the raw bytes of the asset id as a `slice`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for AssetId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchema for AssetId {
+    fn schema_name() -> String {
+        String::from("AssetId")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let schema = gen.subschema_for::<String>();
+        let mut schema_object = schema.into_object();
+        schema_object.metadata().description = Some("Hex-encoded asset id.".to_string());
+        schema_object.into()
+    }
+}
+
+impl CLTyped for AssetId {
+    fn cl_type() -> CLType {
+        CLType::ByteArray(ASSET_ID_LENGTH as u32)
+    }
+}
+
+impl ToBytes for AssetId {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for AssetId {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        <[u8; ASSET_ID_LENGTH]>::from_bytes(bytes)
+            .map(|(inner, remainder)| (AssetId(inner), remainder))
+    }
+}
+
+impl Serialize for AssetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            base16::encode_lower(&self.0).serialize(serializer)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AssetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = if deserializer.is_human_readable() {
+            let hex_string = String::deserialize(deserializer)?;
+            let vec_bytes =
+                checksummed_hex::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
+            <[u8; ASSET_ID_LENGTH]>::try_from(vec_bytes.as_slice()).map_err(SerdeError::custom)?
+        } else {
+            <[u8; ASSET_ID_LENGTH]>::deserialize(deserializer)?
+        };
+        Ok(AssetId(bytes))
+    }
+}
+
+impl Display for AssetId {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}", base16::encode_lower(&self.0))
+    }
+}
+
+impl Debug for AssetId {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "AssetId({})", base16::encode_lower(&self.0))
+    }
+}
+
+impl Distribution<AssetId> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> AssetId {
+        AssetId::new(rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_id_bytesrepr_roundtrip() {
+        let asset_id = AssetId([7; 32]);
+        bytesrepr::test_serialization_roundtrip(&asset_id);
+    }
+
+    #[test]
+    fn asset_id_serde_roundtrip() {
+        let asset_id = AssetId([255; 32]);
+        let serialized = bincode::serialize(&asset_id).unwrap();
+        let decoded = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(asset_id, decoded);
+    }
+
+    #[test]
+    fn asset_id_json_roundtrip() {
+        let asset_id = AssetId([255; 32]);
+        let json_string = serde_json::to_string_pretty(&asset_id).unwrap();
+        let decoded = serde_json::from_str(&json_string).unwrap();
+        assert_eq!(asset_id, decoded);
+    }
+}
@@ -10,6 +10,14 @@ pub const ARG_TO: &str = "to";
 pub const ARG_SOURCE: &str = "source";
 /// Named constant for `target`.
 pub const ARG_TARGET: &str = "target";
+/// Named constant for `asset_id`.
+pub const ARG_ASSET_ID: &str = "asset_id";
+/// Named constant for `unlock_timestamp`.
+pub const ARG_UNLOCK_TIMESTAMP: &str = "unlock_timestamp";
+/// Named constant for `era_spending_limit`.
+pub const ARG_ERA_SPENDING_LIMIT: &str = "era_spending_limit";
+/// Named constant for `allowed_targets`.
+pub const ARG_ALLOWED_TARGETS: &str = "allowed_targets";
 /// Named constant for `round_seigniorage_rate` used in installer.
 pub const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
 
@@ -27,6 +35,14 @@ pub const METHOD_TRANSFER: &str = "transfer";
 pub const METHOD_READ_BASE_ROUND_REWARD: &str = "read_base_round_reward";
 /// Named constant for method `mint_into_existing_purse`.
 pub const METHOD_MINT_INTO_EXISTING_PURSE: &str = "mint_into_existing_purse";
+/// Named constant for method `create_asset`.
+pub const METHOD_CREATE_ASSET: &str = "create_asset";
+/// Named constant for method `create_asset_purse`.
+pub const METHOD_CREATE_ASSET_PURSE: &str = "create_asset_purse";
+/// Named constant for method `purse_asset`.
+pub const METHOD_PURSE_ASSET: &str = "purse_asset";
+/// Named constant for method `set_purse_lock`.
+pub const METHOD_SET_PURSE_LOCK: &str = "set_purse_lock";
 
 /// Storage for mint contract hash.
 pub const HASH_KEY: &str = "mint_hash";
@@ -1,13 +1,16 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 
 use crate::{
     contracts::Parameters,
     system::mint::{
-        ARG_AMOUNT, ARG_ID, ARG_PURSE, ARG_SOURCE, ARG_TARGET, ARG_TO, METHOD_BALANCE,
-        METHOD_CREATE, METHOD_MINT, METHOD_MINT_INTO_EXISTING_PURSE, METHOD_READ_BASE_ROUND_REWARD,
-        METHOD_REDUCE_TOTAL_SUPPLY, METHOD_TRANSFER,
+        AssetId, ARG_ALLOWED_TARGETS, ARG_AMOUNT, ARG_ASSET_ID, ARG_ERA_SPENDING_LIMIT, ARG_ID,
+        ARG_PURSE, ARG_SOURCE, ARG_TARGET, ARG_TO, ARG_UNLOCK_TIMESTAMP, METHOD_BALANCE,
+        METHOD_CREATE, METHOD_CREATE_ASSET, METHOD_CREATE_ASSET_PURSE, METHOD_MINT,
+        METHOD_MINT_INTO_EXISTING_PURSE, METHOD_PURSE_ASSET, METHOD_READ_BASE_ROUND_REWARD,
+        METHOD_REDUCE_TOTAL_SUPPLY, METHOD_SET_PURSE_LOCK, METHOD_TRANSFER,
     },
-    CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+    URefAddr, U512,
 };
 
 /// Returns entry points for a mint system contract.
@@ -98,5 +101,58 @@ pub fn mint_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_CREATE_ASSET,
+        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+        CLType::Result {
+            ok: Box::new(CLType::URef),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_CREATE_ASSET_PURSE,
+        vec![Parameter::new(ARG_ASSET_ID, AssetId::cl_type())],
+        CLType::Result {
+            ok: Box::new(CLType::URef),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_PURSE_ASSET,
+        vec![Parameter::new(ARG_PURSE, CLType::URef)],
+        CLType::Result {
+            ok: Box::new(CLType::Option(Box::new(AssetId::cl_type()))),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_PURSE_LOCK,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_UNLOCK_TIMESTAMP, Option::<u64>::cl_type()),
+            Parameter::new(ARG_ERA_SPENDING_LIMIT, Option::<U512>::cl_type()),
+            Parameter::new(ARG_ALLOWED_TARGETS, Vec::<URefAddr>::cl_type()),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
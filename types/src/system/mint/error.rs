@@ -154,6 +154,48 @@ pub enum Error {
     /// assert_eq!(22, Error::DisabledUnrestrictedTransfers as u8);
     DisabledUnrestrictedTransfers = 22,
 
+    /// The given asset does not exist.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(23, Error::AssetNotFound as u8);
+    AssetNotFound = 23,
+
+    /// Tried to transfer between purses holding different assets.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(24, Error::MismatchedAssets as u8);
+    MismatchedAssets = 24,
+
+    /// Tried to create an asset from neither an administrator account nor a contract.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(25, Error::InvalidAssetCreation as u8);
+    InvalidAssetCreation = 25,
+
+    /// Tried to transfer out of a purse before its unlock time.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(26, Error::PurseLocked as u8);
+    PurseLocked = 26,
+
+    /// Tried to transfer more than the per-era spending limit of a purse.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(27, Error::EraSpendingLimitExceeded as u8);
+    EraSpendingLimitExceeded = 27,
+
+    /// Tried to transfer to a purse which isn't among the allowed targets of the source purse.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(28, Error::TargetNotAllowed as u8);
+    TargetNotAllowed = 28,
+
+    /// Tried to replace the lock of a purse from neither the system nor an administrator account.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(29, Error::PurseLockAlreadySet as u8);
+    PurseLockAlreadySet = 29,
+
     #[cfg(test)]
     #[doc(hidden)]
     Sentinel,
@@ -209,6 +251,13 @@ impl TryFrom<u8> for Error {
             d if d == Error::DisabledUnrestrictedTransfers as u8 => {
                 Ok(Error::DisabledUnrestrictedTransfers)
             }
+            d if d == Error::AssetNotFound as u8 => Ok(Error::AssetNotFound),
+            d if d == Error::MismatchedAssets as u8 => Ok(Error::MismatchedAssets),
+            d if d == Error::InvalidAssetCreation as u8 => Ok(Error::InvalidAssetCreation),
+            d if d == Error::PurseLocked as u8 => Ok(Error::PurseLocked),
+            d if d == Error::EraSpendingLimitExceeded as u8 => Ok(Error::EraSpendingLimitExceeded),
+            d if d == Error::TargetNotAllowed as u8 => Ok(Error::TargetNotAllowed),
+            d if d == Error::PurseLockAlreadySet as u8 => Ok(Error::PurseLockAlreadySet),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -269,6 +318,13 @@ impl Display for Error {
             Error::DisabledUnrestrictedTransfers => {
                 formatter.write_str("Disabled unrestricted transfers")
             }
+            Error::AssetNotFound => formatter.write_str("Asset not found"),
+            Error::MismatchedAssets => formatter.write_str("Mismatched assets"),
+            Error::InvalidAssetCreation => formatter.write_str("Invalid asset creation"),
+            Error::PurseLocked => formatter.write_str("Purse locked"),
+            Error::EraSpendingLimitExceeded => formatter.write_str("Era spending limit exceeded"),
+            Error::TargetNotAllowed => formatter.write_str("Target not allowed"),
+            Error::PurseLockAlreadySet => formatter.write_str("Purse lock already set"),
             #[cfg(test)]
             Error::Sentinel => formatter.write_str("Sentinel error"),
         }
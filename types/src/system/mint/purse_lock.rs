@@ -0,0 +1,216 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    crypto, AccessRights, CLType, CLTyped, EraId, Key, URef, URefAddr, U512,
+};
+
+const PURSE_LOCK_KEY_PREFIX: &[u8] = b"purse-lock";
+
+/// Returns the key under which the [`PurseLock`] of the given purse is stored.
+pub fn purse_lock_key(purse: URef) -> Key {
+    let mut preimage = Vec::with_capacity(PURSE_LOCK_KEY_PREFIX.len() + purse.addr().len());
+    preimage.extend_from_slice(PURSE_LOCK_KEY_PREFIX);
+    preimage.extend_from_slice(&purse.addr());
+    Key::URef(URef::new(crypto::blake2b(preimage), AccessRights::NONE))
+}
+
+/// Restrictions placed by the mint on transfers out of a purse.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct PurseLock {
+    /// Block time (in milliseconds since the Unix epoch) before which nothing can be transferred
+    /// out of the purse.
+    unlock_timestamp: Option<u64>,
+    /// Maximum amount which can be transferred out of the purse in a single era.
+    era_spending_limit: Option<U512>,
+    /// Purses which can receive transfers from the purse.  If empty, any purse can.
+    allowed_targets: BTreeSet<URefAddr>,
+    /// Era in which `spent_in_era` was transferred out of the purse.
+    era_id: EraId,
+    /// Amount transferred out of the purse in `era_id`.
+    spent_in_era: U512,
+}
+
+impl PurseLock {
+    /// Creates a new [`PurseLock`].
+    pub fn new(
+        unlock_timestamp: Option<u64>,
+        era_spending_limit: Option<U512>,
+        allowed_targets: BTreeSet<URefAddr>,
+    ) -> Self {
+        PurseLock {
+            unlock_timestamp,
+            era_spending_limit,
+            allowed_targets,
+            era_id: EraId::default(),
+            spent_in_era: U512::zero(),
+        }
+    }
+
+    /// Returns the block time before which nothing can be transferred out of the purse.
+    pub fn unlock_timestamp(&self) -> Option<u64> {
+        self.unlock_timestamp
+    }
+
+    /// Returns the maximum amount which can be transferred out of the purse in a single era.
+    pub fn era_spending_limit(&self) -> Option<U512> {
+        self.era_spending_limit
+    }
+
+    /// Returns the purses which can receive transfers from the purse.  If empty, any purse can.
+    pub fn allowed_targets(&self) -> &BTreeSet<URefAddr> {
+        &self.allowed_targets
+    }
+
+    /// Returns the amount transferred out of the purse in the given era.
+    pub fn spent_in_era(&self, era_id: EraId) -> U512 {
+        if self.era_id == era_id {
+            self.spent_in_era
+        } else {
+            U512::zero()
+        }
+    }
+
+    /// Returns `true` if the purse is locked at the given block time.
+    pub fn is_time_locked(&self, block_time: u64) -> bool {
+        matches!(self.unlock_timestamp, Some(unlock_timestamp) if block_time < unlock_timestamp)
+    }
+
+    /// Returns `true` if transfers from the purse to `target` are allowed.
+    pub fn is_allowed_target(&self, target: URefAddr) -> bool {
+        self.allowed_targets.is_empty() || self.allowed_targets.contains(&target)
+    }
+
+    /// Returns the part of `balance` which can be transferred out of the purse at the given block
+    /// time and era.
+    pub fn available_balance(&self, balance: U512, block_time: u64, era_id: EraId) -> U512 {
+        if self.is_time_locked(block_time) {
+            return U512::zero();
+        }
+        match self.era_spending_limit {
+            Some(era_spending_limit) => {
+                let remaining = era_spending_limit.saturating_sub(self.spent_in_era(era_id));
+                balance.min(remaining)
+            }
+            None => balance,
+        }
+    }
+
+    /// Records `amount` as transferred out of the purse in the given era.
+    pub fn record_spending(&mut self, era_id: EraId, amount: U512) {
+        self.spent_in_era = self.spent_in_era(era_id).saturating_add(amount);
+        self.era_id = era_id;
+    }
+}
+
+impl ToBytes for PurseLock {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.unlock_timestamp.serialized_length()
+            + self.era_spending_limit.serialized_length()
+            + self.allowed_targets.serialized_length()
+            + self.era_id.serialized_length()
+            + self.spent_in_era.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.unlock_timestamp.write_bytes(writer)?;
+        self.era_spending_limit.write_bytes(writer)?;
+        self.allowed_targets.write_bytes(writer)?;
+        self.era_id.write_bytes(writer)?;
+        self.spent_in_era.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for PurseLock {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (unlock_timestamp, remainder) = FromBytes::from_bytes(bytes)?;
+        let (era_spending_limit, remainder) = FromBytes::from_bytes(remainder)?;
+        let (allowed_targets, remainder) = FromBytes::from_bytes(remainder)?;
+        let (era_id, remainder) = FromBytes::from_bytes(remainder)?;
+        let (spent_in_era, remainder) = FromBytes::from_bytes(remainder)?;
+        Ok((
+            PurseLock {
+                unlock_timestamp,
+                era_spending_limit,
+                allowed_targets,
+                era_id,
+                spent_in_era,
+            },
+            remainder,
+        ))
+    }
+}
+
+impl CLTyped for PurseLock {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeSet;
+
+    use super::*;
+
+    const BLOCK_TIME: u64 = 1_000;
+
+    #[test]
+    fn serialization_roundtrip_for_purse_lock() {
+        let mut purse_lock = PurseLock::new(
+            Some(BLOCK_TIME),
+            Some(U512::from(100)),
+            [[1; 32], [2; 32]].into_iter().collect(),
+        );
+        purse_lock.record_spending(EraId::new(3), U512::from(10));
+        bytesrepr::test_serialization_roundtrip(&purse_lock);
+    }
+
+    #[test]
+    fn should_lock_whole_balance_until_unlock_timestamp() {
+        let purse_lock = PurseLock::new(Some(BLOCK_TIME), None, BTreeSet::new());
+        let balance = U512::from(500);
+        assert_eq!(
+            purse_lock.available_balance(balance, BLOCK_TIME - 1, EraId::new(1)),
+            U512::zero()
+        );
+        assert_eq!(
+            purse_lock.available_balance(balance, BLOCK_TIME, EraId::new(1)),
+            balance
+        );
+    }
+
+    #[test]
+    fn should_reset_spending_in_new_era() {
+        let mut purse_lock = PurseLock::new(None, Some(U512::from(100)), BTreeSet::new());
+        let balance = U512::from(500);
+        purse_lock.record_spending(EraId::new(1), U512::from(60));
+        assert_eq!(
+            purse_lock.available_balance(balance, BLOCK_TIME, EraId::new(1)),
+            U512::from(40)
+        );
+        assert_eq!(
+            purse_lock.available_balance(balance, BLOCK_TIME, EraId::new(2)),
+            U512::from(100)
+        );
+        purse_lock.record_spending(EraId::new(2), U512::from(30));
+        assert_eq!(purse_lock.spent_in_era(EraId::new(2)), U512::from(30));
+    }
+
+    #[test]
+    fn should_allow_any_target_without_allowed_targets() {
+        let purse_lock = PurseLock::new(None, None, BTreeSet::new());
+        assert!(purse_lock.is_allowed_target([1; 32]));
+
+        let purse_lock = PurseLock::new(None, None, [[1; 32]].into_iter().collect());
+        assert!(purse_lock.is_allowed_target([1; 32]));
+        assert!(!purse_lock.is_allowed_target([2; 32]));
+    }
+}
@@ -21,7 +21,9 @@ use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Seria
 use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes},
-    checksummed_hex, CLType, CLTyped, URef, U512,
+    checksummed_hex,
+    system::mint::AssetId,
+    CLType, CLTyped, URef, U512,
 };
 
 /// The length of a deploy hash.
@@ -30,6 +32,16 @@ pub const DEPLOY_HASH_LENGTH: usize = 32;
 pub const TRANSFER_ADDR_LENGTH: usize = 32;
 pub(super) const TRANSFER_ADDR_FORMATTED_STRING_PREFIX: &str = "transfer-";
 
+/// Tag of a transfer's `id` when it is `None`.
+const ID_NONE_TAG: u8 = 0;
+/// Tag of a transfer's `id` when it is `Some`.
+const ID_SOME_TAG: u8 = 1;
+/// Flag set on the tag of a transfer's `id` when an asset id follows the transfer's legacy fields.
+///
+/// Transfers of the native token omit their `asset_id`, so they are encoded exactly as before
+/// assets were introduced.
+const ASSET_ID_FLAG: u8 = 0b10;
+
 /// A newtype wrapping a <code>[u8; [DEPLOY_HASH_LENGTH]]</code> which is the raw bytes of the
 /// deploy hash.
 #[derive(Default, PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
@@ -111,7 +123,8 @@ impl<'de> Deserialize<'de> for DeployHash {
             let hex_string = String::deserialize(deserializer)?;
             let vec_bytes =
                 checksummed_hex::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
-            <[u8; DEPLOY_HASH_LENGTH]>::try_from(vec_bytes.as_ref()).map_err(SerdeError::custom)?
+            <[u8; DEPLOY_HASH_LENGTH]>::try_from(vec_bytes.as_slice())
+                .map_err(SerdeError::custom)?
         } else {
             <[u8; DEPLOY_HASH_LENGTH]>::deserialize(deserializer)?
         };
@@ -153,6 +166,9 @@ pub struct Transfer {
     pub gas: U512,
     /// User-defined id
     pub id: Option<u64>,
+    /// Asset transferred, or `None` for the native token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<AssetId>,
 }
 
 impl Transfer {
@@ -167,6 +183,7 @@ impl Transfer {
         amount: U512,
         gas: U512,
         id: Option<u64>,
+        asset_id: Option<AssetId>,
     ) -> Self {
         Transfer {
             deploy_hash,
@@ -177,6 +194,22 @@ impl Transfer {
             amount,
             gas,
             id,
+            asset_id,
+        }
+    }
+}
+
+impl Transfer {
+    fn id_tag(&self) -> u8 {
+        let tag = if self.id.is_some() {
+            ID_SOME_TAG
+        } else {
+            ID_NONE_TAG
+        };
+        if self.asset_id.is_some() {
+            tag | ASSET_ID_FLAG
+        } else {
+            tag
         }
     }
 }
@@ -190,7 +223,21 @@ impl FromBytes for Transfer {
         let (target, rem) = URef::from_bytes(rem)?;
         let (amount, rem) = U512::from_bytes(rem)?;
         let (gas, rem) = U512::from_bytes(rem)?;
-        let (id, rem) = <Option<u64>>::from_bytes(rem)?;
+        let (id_tag, rem) = u8::from_bytes(rem)?;
+        let (id, rem) = match id_tag & !ASSET_ID_FLAG {
+            ID_NONE_TAG => (None, rem),
+            ID_SOME_TAG => {
+                let (id, rem) = u64::from_bytes(rem)?;
+                (Some(id), rem)
+            }
+            _ => return Err(bytesrepr::Error::Formatting),
+        };
+        let (asset_id, rem) = if id_tag & ASSET_ID_FLAG == 0 {
+            (None, rem)
+        } else {
+            let (asset_id, rem) = AssetId::from_bytes(rem)?;
+            (Some(asset_id), rem)
+        };
         Ok((
             Transfer {
                 deploy_hash,
@@ -201,6 +248,7 @@ impl FromBytes for Transfer {
                 amount,
                 gas,
                 id,
+                asset_id,
             },
             rem,
         ))
@@ -210,14 +258,7 @@ impl FromBytes for Transfer {
 impl ToBytes for Transfer {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
-        self.deploy_hash.write_bytes(&mut result)?;
-        self.from.write_bytes(&mut result)?;
-        self.to.write_bytes(&mut result)?;
-        self.source.write_bytes(&mut result)?;
-        self.target.write_bytes(&mut result)?;
-        self.amount.write_bytes(&mut result)?;
-        self.gas.write_bytes(&mut result)?;
-        self.id.write_bytes(&mut result)?;
+        self.write_bytes(&mut result)?;
         Ok(result)
     }
 
@@ -230,6 +271,7 @@ impl ToBytes for Transfer {
             + self.amount.serialized_length()
             + self.gas.serialized_length()
             + self.id.serialized_length()
+            + self.asset_id.as_ref().map_or(0, AssetId::serialized_length)
     }
 
     fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
@@ -240,7 +282,13 @@ impl ToBytes for Transfer {
         self.target.write_bytes(writer)?;
         self.amount.write_bytes(writer)?;
         self.gas.write_bytes(writer)?;
-        self.id.write_bytes(writer)?;
+        writer.push(self.id_tag());
+        if let Some(id) = self.id {
+            id.write_bytes(writer)?;
+        }
+        if let Some(asset_id) = self.asset_id {
+            asset_id.write_bytes(writer)?;
+        }
         Ok(())
     }
 }
@@ -318,7 +366,7 @@ impl TransferAddr {
             .strip_prefix(TRANSFER_ADDR_FORMATTED_STRING_PREFIX)
             .ok_or(FromStrError::InvalidPrefix)?;
         let bytes =
-            <[u8; TRANSFER_ADDR_LENGTH]>::try_from(checksummed_hex::decode(remainder)?.as_ref())?;
+            <[u8; TRANSFER_ADDR_LENGTH]>::try_from(checksummed_hex::decode(remainder)?.as_slice())?;
         Ok(TransferAddr(bytes))
     }
 }
@@ -422,6 +470,7 @@ pub mod gens {
     use crate::{
         deploy_info::gens::{account_hash_arb, deploy_hash_arb},
         gens::{u512_arb, uref_arb},
+        system::mint::{AssetId, ASSET_ID_LENGTH},
         Transfer,
     };
 
@@ -436,9 +485,10 @@ pub mod gens {
             u512_arb(),
             u512_arb(),
             option::of(<u64>::arbitrary()),
+            option::of(<[u8; ASSET_ID_LENGTH]>::arbitrary().prop_map(AssetId::new)),
         )
-            .prop_map(|(deploy_hash, from, to, source, target, amount, gas, id)| {
-                Transfer {
+            .prop_map(
+                |(deploy_hash, from, to, source, target, amount, gas, id, asset_id)| Transfer {
                     deploy_hash,
                     from,
                     to,
@@ -447,8 +497,9 @@ pub mod gens {
                     amount,
                     gas,
                     id,
-                }
-            })
+                    asset_id,
+                },
+            )
     }
 }
 
@@ -456,7 +507,7 @@ pub mod gens {
 mod tests {
     use proptest::prelude::*;
 
-    use crate::bytesrepr;
+    use crate::{bytesrepr, AccessRights};
 
     use super::*;
 
@@ -467,6 +518,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn native_transfer_should_keep_legacy_encoding() {
+        let transfer = Transfer::new(
+            DeployHash::new([1; 32]),
+            AccountHash::new([2; 32]),
+            None,
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([4; 32], AccessRights::ADD),
+            U512::from(5),
+            U512::from(6),
+            Some(7),
+            None,
+        );
+        let mut legacy_bytes = vec![];
+        legacy_bytes.extend(transfer.deploy_hash.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.from.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.to.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.source.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.target.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.amount.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.gas.to_bytes().unwrap());
+        legacy_bytes.extend(transfer.id.to_bytes().unwrap());
+        assert_eq!(transfer.to_bytes().unwrap(), legacy_bytes);
+        assert_eq!(
+            bytesrepr::deserialize::<Transfer>(legacy_bytes),
+            Ok(transfer)
+        );
+
+        let asset_transfer = Transfer {
+            asset_id: Some(AssetId::new([8; 32])),
+            ..transfer
+        };
+        assert_ne!(
+            asset_transfer.to_bytes().unwrap(),
+            transfer.to_bytes().unwrap()
+        );
+        bytesrepr::test_serialization_roundtrip(&asset_transfer);
+    }
+
     #[test]
     fn transfer_addr_from_str() {
         let transfer_address = TransferAddr([4; 32]);
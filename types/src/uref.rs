@@ -200,7 +200,7 @@ impl URef {
         if parts.len() != 2 {
             return Err(FromStrError::MissingSuffix);
         }
-        let addr = URefAddr::try_from(checksummed_hex::decode(parts[0])?.as_ref())?;
+        let addr = URefAddr::try_from(checksummed_hex::decode(parts[0])?.as_slice())?;
         let access_rights_value = u8::from_str_radix(parts[1], 8)?;
         let access_rights = AccessRights::from_bits(access_rights_value)
             .ok_or(FromStrError::InvalidAccessRights)?;